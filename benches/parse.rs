@@ -0,0 +1,145 @@
+//! HARファイル読み込み方式（メモリマップ/通常読み込み）の比較ベンチマーク
+//!
+//! 合成したHARファイルを一時ディレクトリに書き出し、各読み込み方式の
+//! 処理時間とヒープ使用量・常駐メモリ（RSS）のピークを計測する。
+//! RSSのピークはプロセス全体の値のため、読み込み方式ごとに子プロセスで計測する（Linuxのみ）。
+//!
+//! ```bash
+//! cargo bench --bench parse
+//! HAR_BENCH_ENTRIES=200000 cargo bench --bench parse
+//! ```
+
+use rs_har_analyzer::analyzer::{HarAnalyzer, ReadMode};
+use rs_har_analyzer::synthetic::synthetic_har;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// 既定の合成エントリ数
+const DEFAULT_ENTRIES: usize = 20_000;
+
+/// 子プロセスで計測する読み込み方式を渡す環境変数
+const CHILD_MODE_VAR: &str = "HAR_BENCH_CHILD_MODE";
+
+/// 子プロセスで読み込むHARファイルのパスを渡す環境変数
+const CHILD_FILE_VAR: &str = "HAR_BENCH_CHILD_FILE";
+
+/// 読み込み方式の名前と値
+const MODES: [(&str, ReadMode); 2] = [("read", ReadMode::Read), ("mmap", ReadMode::Mmap)];
+
+/// ヒープ使用量のピークを記録するアロケータ
+struct PeakAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+/// プロセスの常駐メモリのピーク（`/proc/self/status`の`VmHWM`）
+/// 
+/// # Returns
+/// * `Option<usize>` - バイト数（Linux以外では取得できないためNone）
+fn peak_rss() -> Option<usize> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib: usize = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// 子プロセスとして1つの読み込み方式で読み込み、`処理時間(ms) ヒープのピーク RSSのピーク`を出力
+/// 
+/// # Arguments
+/// * `mode_name` - 読み込み方式の名前
+/// * `path` - HARファイルのパス
+fn run_child(mode_name: &str, path: &str) {
+    let (_, mode) = MODES.into_iter().find(|(name, _)| *name == mode_name).expect("不明な読み込み方式です");
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+
+    let start = Instant::now();
+    let analyzer = HarAnalyzer::with_read_mode(path, mode).expect("HARファイルの読み込みに失敗しました");
+    let elapsed = start.elapsed();
+    let peak = PEAK.load(Ordering::Relaxed) - baseline;
+    let rss = peak_rss();
+    drop(analyzer);
+
+    let rss = rss.map_or("-".to_string(), |rss| rss.to_string());
+    println!("{} {} {}", elapsed.as_secs_f64() * 1000.0, peak, rss);
+}
+
+/// MiB単位の表記
+/// 
+/// # Arguments
+/// * `bytes` - バイト数
+/// 
+/// # Returns
+/// * `String` - MiB単位の値（不明な場合は`-`）
+fn mib(bytes: Option<f64>) -> String {
+    bytes.map_or("-".to_string(), |bytes| format!("{:.1}", bytes / 1024.0 / 1024.0))
+}
+
+fn main() {
+    if let (Ok(mode), Ok(path)) = (std::env::var(CHILD_MODE_VAR), std::env::var(CHILD_FILE_VAR)) {
+        run_child(&mode, &path);
+        return;
+    }
+
+    let entries = std::env::var("HAR_BENCH_ENTRIES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_ENTRIES);
+
+    let path = std::env::temp_dir().join(format!("rs_har_analyzer_bench_{}.har", std::process::id()));
+    let content = synthetic_har(entries);
+    fs::write(&path, &content).expect("合成HARファイルの書き込みに失敗しました");
+    let file_size = content.len();
+    drop(content);
+
+    println!("entries: {}, file size: {:.1} MiB", entries, file_size as f64 / 1024.0 / 1024.0);
+    println!("{:<8} {:>12} {:>18} {:>18}", "mode", "time (ms)", "peak heap (MiB)", "peak RSS (MiB)");
+
+    let exe = std::env::current_exe().expect("ベンチマークの実行ファイルが見つかりません");
+    let mut results: Vec<(f64, Option<f64>)> = Vec::new();
+    for (name, _) in MODES {
+        let output = Command::new(&exe)
+            .env(CHILD_MODE_VAR, name)
+            .env(CHILD_FILE_VAR, &path)
+            .output()
+            .expect("子プロセスの実行に失敗しました");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let fields: Vec<&str> = stdout.split_whitespace().collect();
+        let [elapsed, heap, rss] = fields[..] else {
+            panic!("子プロセスの出力が不正です: {}{}", stdout, String::from_utf8_lossy(&output.stderr));
+        };
+        let elapsed: f64 = elapsed.parse().unwrap();
+        let heap: f64 = heap.parse().unwrap();
+        let rss: Option<f64> = rss.parse().ok();
+        println!("{:<8} {:>12.1} {:>18} {:>18}", name, elapsed, mib(Some(heap)), mib(rss));
+        results.push((heap, rss));
+    }
+    if let [(read_heap, read_rss), (mmap_heap, mmap_rss)] = results[..] {
+        let rss = read_rss.zip(mmap_rss).map(|(read, mmap)| mmap - read);
+        println!("mmap - read: heap {} MiB, RSS {} MiB", mib(Some(mmap_heap - read_heap)), mib(rss));
+    }
+
+    let _ = fs::remove_file(&path);
+}
@@ -0,0 +1,296 @@
+//! アクセス解析のビーコン（GA4・Segment・Amplitude）のペイロードのデコードを実装
+//! 
+//! まとめて送信されたイベントやパーセントエンコードされたパラメータを、
+//! イベントの一覧を表すJSONに変換してリクエストペイロード列に出力する
+
+use serde_json::{Map, Value, json};
+use url::{Url, form_urlencoded};
+
+/// アクセス解析のサービス
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalyticsProvider {
+    /// Google Analytics 4（Measurement Protocol v2）
+    Ga4,
+    /// Segment
+    Segment,
+    /// Amplitude
+    Amplitude,
+}
+
+impl AnalyticsProvider {
+    /// リクエストURLからアクセス解析のサービスを判定
+    /// 
+    /// GA4はサーバーサイドのタグマネージャーで独自のドメインを使用することがあるため、パス（`/g/collect`）で判定する
+    /// 
+    /// # Arguments
+    /// * `url` - リクエストURL
+    /// 
+    /// # Returns
+    /// * `Option<AnalyticsProvider>` - サービス（アクセス解析のエンドポイントでない場合はNone）
+    pub fn detect(url: &str) -> Option<Self> {
+        let url = Url::parse(url).ok()?;
+        let host = url.host_str()?.to_ascii_lowercase();
+        let path = url.path();
+        let on_domain = |domain: &str| host == domain || host.ends_with(&format!(".{}", domain));
+
+        if path.ends_with("/g/collect") {
+            Some(AnalyticsProvider::Ga4)
+        } else if on_domain("segment.io") || on_domain("segment.com") {
+            let method = path.strip_prefix("/v1/")?;
+            matches!(
+                method,
+                "batch" | "b" | "import" | "track" | "t" | "page" | "p" | "screen" | "s" | "identify" | "i" | "group" | "g" | "alias" | "a"
+            )
+            .then_some(AnalyticsProvider::Segment)
+        } else if on_domain("amplitude.com") {
+            matches!(path, "/" | "/2/httpapi" | "/batch").then_some(AnalyticsProvider::Amplitude)
+        } else {
+            None
+        }
+    }
+
+    /// 出力するサービスの名前
+    /// 
+    /// # Returns
+    /// * `&'static str` - サービスの名前
+    pub fn label(&self) -> &'static str {
+        match self {
+            AnalyticsProvider::Ga4 => "GA4",
+            AnalyticsProvider::Segment => "Segment",
+            AnalyticsProvider::Amplitude => "Amplitude",
+        }
+    }
+
+    /// ビーコンのペイロードをイベントの一覧を表す整形したJSONに変換
+    /// 
+    /// # Arguments
+    /// * `url` - リクエストURL
+    /// * `body` - リクエストボディ（無い場合はNone）
+    /// 
+    /// # Returns
+    /// * `Option<String>` - 整形したJSON（イベントが見つからない場合はNone）
+    pub fn decode(&self, url: &str, body: Option<&str>) -> Option<String> {
+        let decoded = match self {
+            AnalyticsProvider::Ga4 => decode_ga4(url, body),
+            AnalyticsProvider::Segment => decode_segment(body?),
+            AnalyticsProvider::Amplitude => decode_amplitude(body?),
+        }?;
+        let mut document = Map::new();
+        document.insert("provider".to_string(), json!(self.label()));
+        document.extend(decoded);
+        serde_json::to_string_pretty(&document).ok()
+    }
+}
+
+/// GA4のペイロードをデコード
+/// 
+/// URLのクエリは全てのイベントに共通のパラメータ、ボディの各行は1件ずつのイベントのパラメータとなる
+/// 
+/// # Arguments
+/// * `url` - リクエストURL
+/// * `body` - リクエストボディ
+/// 
+/// # Returns
+/// * `Option<Map<String, Value>>` - 共通のパラメータ（`common`）とイベント（`events`）
+fn decode_ga4(url: &str, body: Option<&str>) -> Option<Map<String, Value>> {
+    let url = Url::parse(url).ok()?;
+    let common: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    let lines: Vec<Vec<(String, String)>> = body
+        .map(|body| {
+            body.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(|line| form_urlencoded::parse(line.as_bytes()).into_owned().collect())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // ボディが無い場合はURLのクエリに1件のイベントが含まれる
+    let events: Vec<Value> = if lines.is_empty() {
+        vec![ga4_event(&common, &[])]
+    } else {
+        lines.iter().map(|line| ga4_event(&common, line)).collect()
+    };
+    if events.iter().all(|event| event["name"].is_null()) {
+        return None;
+    }
+
+    let common_fields: Map<String, Value> = common
+        .iter()
+        .filter(|(name, _)| ga4_event_field(name).is_none())
+        .map(|(name, value)| (name.clone(), json!(value)))
+        .collect();
+    let mut decoded = Map::new();
+    decoded.insert("common".to_string(), Value::Object(common_fields));
+    decoded.insert("events".to_string(), Value::Array(events));
+    Some(decoded)
+}
+
+/// GA4のイベントのパラメータの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ga4Field<'a> {
+    /// イベント名（`en`）
+    Name,
+    /// イベントのパラメータ（`ep.`は文字列、`epn.`は数値）
+    Param(&'a str, bool),
+    /// ユーザープロパティ（`up.`は文字列、`upn.`は数値）
+    UserProperty(&'a str, bool),
+}
+
+/// パラメータ名からイベントのパラメータの種類を判定
+/// 
+/// # Arguments
+/// * `name` - パラメータ名
+/// 
+/// # Returns
+/// * `Option<Ga4Field>` - 種類（イベントに関係しないパラメータの場合はNone）
+fn ga4_event_field(name: &str) -> Option<Ga4Field<'_>> {
+    if name == "en" {
+        Some(Ga4Field::Name)
+    } else if let Some(key) = name.strip_prefix("epn.") {
+        Some(Ga4Field::Param(key, true))
+    } else if let Some(key) = name.strip_prefix("ep.") {
+        Some(Ga4Field::Param(key, false))
+    } else if let Some(key) = name.strip_prefix("upn.") {
+        Some(Ga4Field::UserProperty(key, true))
+    } else {
+        name.strip_prefix("up.").map(|key| Ga4Field::UserProperty(key, false))
+    }
+}
+
+/// GA4の1件のイベントを作成
+/// 
+/// # Arguments
+/// * `common` - URLのクエリのパラメータ
+/// * `line` - ボディの行のパラメータ（共通のパラメータより優先）
+/// 
+/// # Returns
+/// * `Value` - イベント名（`name`）・パラメータ（`params`）・ユーザープロパティ（`user_properties`）
+fn ga4_event(common: &[(String, String)], line: &[(String, String)]) -> Value {
+    let mut name = Value::Null;
+    let mut params = Map::new();
+    let mut user_properties = Map::new();
+    let mut fields = Map::new();
+    for (key, value) in common.iter().chain(line) {
+        let typed = |numeric: bool| {
+            let number = || match value.parse::<i64>() {
+                Ok(integer) => Some(json!(integer)),
+                Err(_) => value.parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(Value::Number),
+            };
+            numeric.then(number).flatten().unwrap_or_else(|| json!(value))
+        };
+        match ga4_event_field(key) {
+            Some(Ga4Field::Name) => name = json!(value),
+            Some(Ga4Field::Param(key, numeric)) => {
+                params.insert(key.to_string(), typed(numeric));
+            }
+            Some(Ga4Field::UserProperty(key, numeric)) => {
+                user_properties.insert(key.to_string(), typed(numeric));
+            }
+            // 行に固有のその他のパラメータ（_etのエンゲージメント時間等）
+            None if line.iter().any(|(line_key, _)| line_key == key) => {
+                fields.insert(key.clone(), json!(value));
+            }
+            None => {}
+        }
+    }
+
+    let mut event = Map::new();
+    event.insert("name".to_string(), name);
+    event.insert("params".to_string(), Value::Object(params));
+    if !user_properties.is_empty() {
+        event.insert("user_properties".to_string(), Value::Object(user_properties));
+    }
+    if !fields.is_empty() {
+        event.insert("fields".to_string(), Value::Object(fields));
+    }
+    Value::Object(event)
+}
+
+/// Segmentのペイロードをデコード
+/// 
+/// # Arguments
+/// * `body` - リクエストボディ（JSON。`batch`にまとめて送信される場合がある）
+/// 
+/// # Returns
+/// * `Option<Map<String, Value>>` - イベント（`events`）
+fn decode_segment(body: &str) -> Option<Map<String, Value>> {
+    let document: Value = serde_json::from_str(body).ok()?;
+    let messages = match document.get("batch") {
+        Some(Value::Array(batch)) => batch.clone(),
+        _ => vec![document],
+    };
+    let events: Vec<Value> = messages
+        .iter()
+        .map(|message| {
+            select_fields(
+                message,
+                &["type", "event", "name", "userId", "anonymousId", "timestamp", "properties", "traits"],
+            )
+        })
+        .collect();
+    events_map(events)
+}
+
+/// Amplitudeのペイロードをデコード
+/// 
+/// HTTP API v2・Batch APIのJSONと、旧形式のフォーム（`e`にパーセントエンコードされたJSONの配列）に対応する
+/// 
+/// # Arguments
+/// * `body` - リクエストボディ
+/// 
+/// # Returns
+/// * `Option<Map<String, Value>>` - イベント（`events`）
+fn decode_amplitude(body: &str) -> Option<Map<String, Value>> {
+    let events = match serde_json::from_str::<Value>(body) {
+        Ok(document) => document.get("events")?.clone(),
+        Err(_) => {
+            let (_, events) = form_urlencoded::parse(body.as_bytes()).find(|(name, _)| name == "e")?;
+            serde_json::from_str(&events).ok()?
+        }
+    };
+    let events: Vec<Value> = events
+        .as_array()?
+        .iter()
+        .map(|event| {
+            select_fields(
+                event,
+                &["event_type", "user_id", "device_id", "session_id", "time", "event_properties", "user_properties"],
+            )
+        })
+        .collect();
+    events_map(events)
+}
+
+/// イベントから出力する項目を取り出す
+/// 
+/// # Arguments
+/// * `event` - イベントのJSON
+/// * `fields` - 出力する項目名
+/// 
+/// # Returns
+/// * `Value` - 存在する項目のみのオブジェクト
+fn select_fields(event: &Value, fields: &[&str]) -> Value {
+    Value::Object(
+        fields
+            .iter()
+            .filter_map(|field| Some((field.to_string(), event.get(*field)?.clone())))
+            .collect(),
+    )
+}
+
+/// イベントの一覧を出力するマップを作成
+/// 
+/// # Arguments
+/// * `events` - イベントのリスト
+/// 
+/// # Returns
+/// * `Option<Map<String, Value>>` - イベント（`events`、イベントが無い場合はNone）
+fn events_map(events: Vec<Value>) -> Option<Map<String, Value>> {
+    if events.is_empty() {
+        return None;
+    }
+    let mut decoded = Map::new();
+    decoded.insert("events".to_string(), Value::Array(events));
+    Some(decoded)
+}
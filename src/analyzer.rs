@@ -1,17 +1,92 @@
 //! HARファイルの解析処理を実装
 
-use crate::har_types::{AnalysisResult, HarFile};
-use anyhow::{anyhow, Result};
-use base64::{engine::general_purpose, Engine as _};
-use chrono::DateTime;
+use crate::analytics::AnalyticsProvider;
+use crate::beautify::JsCssMode;
+use crate::annotations::Annotations;
+use crate::asset_collapse;
+use crate::body_kind::BodyKind;
+use crate::budgets::{self, BudgetViolation, Budgets};
+use crate::business_hours::BusinessHours;
+use crate::cache_lint::{self, CacheRules, CacheViolation};
+use crate::clock_skew;
+use crate::compression::{self, HostSavings};
+use crate::conditional::{self, ConditionalStats};
+use crate::connections::{self, ConnectionUsage};
+use crate::critical_path;
+use crate::egress_cost::{self, EgressCostReport, EgressPricing};
+use crate::endpoint_sampling;
+use crate::duplicates::{self, DuplicateGroup};
+use crate::entry_warnings::{self, EntryWarning, EntryWarnings};
+use crate::events::{FrameDirection, PageEvent, TrafficEvent, WebSocketFrame};
+use crate::filter::EntryFilter;
+use crate::flags::EntryFlags;
+use crate::gaps::{self, EntryGap};
+use crate::har_types::{AnalysisResult, Entry, HarFile, Page, WebSocketMessage};
+use crate::header_inventory::{self, HeaderUsage};
+use crate::milestones::{self, PageMilestones};
+use crate::mime_map::{BodyFormat, MimeCategory};
+use crate::pagination::{self, PageSequence};
+use crate::grpc_web;
+use crate::html::HtmlMode;
+use crate::infrastructure::{self, HostInfrastructure};
+use crate::payload::{body_encoding, is_unlabeled_text, looks_like_json, LazyText, PayloadOptions, RawPayload, TextFormat};
+use crate::payload_cache::PayloadCache;
+use crate::producer::Producer;
+use crate::soap;
+use crate::tls;
+use crate::protocol::TrafficProtocol;
+use crate::server_timing::{self, ServerTimingEntry};
+use crate::trackers::{self, Blocklist, DomainCategory};
+use crate::url_parts::UrlParts;
+use crate::error::{AnalyzerError, Result};
+use chrono::{DateTime, Local, NaiveDateTime, SecondsFormat};
 use log::{debug, info, warn};
-use std::collections::HashMap;
-use std::fs;
-use url::Url;
+use memmap2::{MmapMut, MmapOptions};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use url::{form_urlencoded, Url};
+
+/// 解析対象のHTTPメソッド
+pub const TARGET_METHODS: &[&str] = &["GET", "POST"];
+
+/// HARファイルの読み込み方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadMode {
+    /// ファイルをメモリマップして解析（ファイル全体のコピーを作らない）
+    #[default]
+    Mmap,
+    /// ファイル全体をメモリに読み込んでから解析
+    Read,
+}
 
 /// HARファイルアナライザ
 pub struct HarAnalyzer {
     har_data: HarFile,
+    producer: Producer,
+    /// エントリごとの前の通信からの間隔（エントリと同じ順序）
+    gaps: Vec<EntryGap>,
+    /// 解析結果を出力する順のエントリの位置（`--sort-by-time`で並べ替えた場合のみ。エントリ自体は記録順のまま保持する）
+    order: Option<Vec<usize>>,
+    /// エントリの位置ごとの開始時刻の前後関係の警告
+    clock_warnings: BTreeMap<usize, Vec<EntryWarning>>,
+    /// ボディの加工方法のオプション
+    payload_options: PayloadOptions,
+    /// 加工済みのボディのキャッシュ
+    payload_cache: Option<PayloadCache>,
+    /// 解析対象のエントリを絞り込むフィルタ
+    filter: EntryFilter,
+    /// レビューのメモ
+    annotations: Annotations,
+    /// 注目させるエントリの条件
+    flags: EntryFlags,
+    /// ドメインの分類のリスト
+    blocklist: Blocklist,
+    /// キャプチャしたページのサイト（ファーストパーティの判定に使用）
+    first_party_site: Option<String>,
+    /// 営業時間（指定していない場合はNone）
+    business_hours: Option<BusinessHours>,
+    /// エントリIDを求めるかどうか（エントリ全体をJSONにしてハッシュを求めるため、使用しない場合は省略する）
+    entry_ids: bool,
 }
 
 impl HarAnalyzer {
@@ -23,25 +98,383 @@ impl HarAnalyzer {
     /// # Returns
     /// * `Result<HarAnalyzer>` - 成功時はアナライザ、失敗時はエラー
     pub fn new(file_path: &str) -> Result<Self> {
+        Self::with_read_mode(file_path, ReadMode::default())
+    }
+
+    /// 読み込み方式を指定してHARファイルを読み込み、新しいアナライザを作成
+    /// 
+    /// メモリマップに失敗した場合（ネットワークドライブ等）は通常の読み込みにフォールバックする
+    /// 
+    /// # Arguments
+    /// * `file_path` - HARファイルのパス
+    /// * `read_mode` - 読み込み方式
+    /// 
+    /// # Returns
+    /// * `Result<HarAnalyzer>` - 成功時はアナライザ、失敗時はエラー
+    pub fn with_read_mode(file_path: &str, read_mode: ReadMode) -> Result<Self> {
         info!("HARファイルを読み込んでいます: {}", file_path);
         
-        let content = fs::read_to_string(file_path)
-            .map_err(|e| anyhow!("ファイルの読み込みに失敗しました: {}", e))?;
+        let har_data = match read_mode {
+            ReadMode::Mmap => match Self::map_file(file_path) {
+                Ok(mut mmap) => {
+                    debug!("ファイルサイズ: {} bytes (メモリマップ)", mmap.len());
+                    Self::parse_file_buffer(&mut mmap, file_path)?
+                }
+                Err(e) => {
+                    warn!("メモリマップに失敗したため通常の読み込みを行います: {}", e);
+                    Self::read_and_parse(file_path)?
+                }
+            },
+            ReadMode::Read => Self::read_and_parse(file_path)?,
+        };
+        Ok(Self::from_har(har_data))
+    }
+
+    /// HARのJSONのバイト列から新しいアナライザを作成
+    /// 
+    /// `simd`フィーチャーが有効な場合、simd-jsonに渡すためにバイト列を複製する
+    /// 
+    /// # Arguments
+    /// * `content` - JSONバイト列
+    /// 
+    /// # Returns
+    /// * `Result<HarAnalyzer>` - 成功時はアナライザ、失敗時はエラー
+    pub fn from_bytes(content: &[u8]) -> Result<Self> {
+        Ok(Self::from_har(Self::parse_json(content)?))
+    }
+
+    /// 解析したHARデータからアナライザを作成
+    /// 
+    /// # Arguments
+    /// * `har_data` - 解析されたHARデータ
+    /// 
+    /// # Returns
+    /// * `HarAnalyzer` - アナライザ
+    fn from_har(har_data: HarFile) -> Self {
+        info!("HARファイルの読み込みが完了しました。エントリ数: {}", har_data.log.entries.len());
         
-        debug!("ファイルサイズ: {} bytes", content.len());
+        // 作成ツールを判定し、既知の癖があれば警告
+        let producer = Producer::detect(&har_data.log);
+        producer.log(&har_data.log);
+        tls::log_warnings(&har_data.log.entries);
         
-        let har_data: HarFile = serde_json::from_str(&content)
-            .map_err(|e| {
-                let error_msg = format!("JSONの解析に失敗しました: {}", e);
-                // より詳細なエラー情報を出力
-                warn!("エラーが発生した行: {}", e.line());
-                warn!("エラーが発生した列: {}", e.column());
-                anyhow!(error_msg)
-            })?;
+        let mut analyzer = HarAnalyzer {
+            har_data,
+            producer,
+            gaps: Vec::new(),
+            order: None,
+            clock_warnings: BTreeMap::new(),
+            payload_options: PayloadOptions::default(),
+            payload_cache: None,
+            filter: EntryFilter::default(),
+            annotations: Annotations::default(),
+            flags: EntryFlags::default(),
+            blocklist: Blocklist::default(),
+            first_party_site: None,
+            business_hours: None,
+            entry_ids: true,
+        };
+        analyzer.first_party_site = trackers::first_party_site(&analyzer.har_data.log);
+        analyzer.gaps = gaps::entry_gaps(&analyzer.har_data.log.entries, |entry| analyzer.entry_duration(entry));
+        analyzer.clock_warnings = clock_skew::check(&analyzer.har_data.log.entries);
+        if !analyzer.clock_warnings.is_empty() {
+            warn!(
+                "開始時刻が記録順と前後している、または前後のエントリから大きく離れているエントリ: {}件（Warningsシートを参照）",
+                analyzer.clock_warnings.len()
+            );
+        }
+        analyzer
+    }
+
+    /// ファイルをコピーオンライトでメモリマップ
+    /// 
+    /// プライベートなマップのため、書き換えてもファイルには反映されず、書き換えたページのみが複製される
+    /// 
+    /// # Arguments
+    /// * `file_path` - HARファイルのパス
+    /// 
+    /// # Returns
+    /// * `std::io::Result<MmapMut>` - メモリマップされたファイル
+    fn map_file(file_path: &str) -> std::io::Result<MmapMut> {
+        let file = File::open(file_path)?;
+        // SAFETY: 解析中にファイルが他のプロセスから変更されないことを前提とする。
+        // マップした内容は解析が終わるとすぐに破棄され、以降は所有データのみを参照する。
+        unsafe { MmapOptions::new().map_copy(&file) }
+    }
+
+    /// ファイル全体を読み込んで解析
+    /// 
+    /// # Arguments
+    /// * `file_path` - HARファイルのパス
+    /// 
+    /// # Returns
+    /// * `Result<HarFile>` - 解析されたHARデータ
+    fn read_and_parse(file_path: &str) -> Result<HarFile> {
+        let mut content = fs::read(file_path)
+            .map_err(AnalyzerError::io("ファイルの読み込みに失敗しました"))?;
         
-        info!("HARファイルの読み込みが完了しました。エントリ数: {}", har_data.log.entries.len());
+        debug!("ファイルサイズ: {} bytes", content.len());
         
-        Ok(HarAnalyzer { har_data })
+        Self::parse_file_buffer(&mut content, file_path)
+    }
+
+    /// ファイルから読み込んだ書き換え可能なバッファをHARデータに変換
+    /// 
+    /// simd-jsonでバッファをその場で書き換えながら解析し、失敗した場合は書き換える前の内容を
+    /// ファイルから読み直してserde_jsonで再解析する
+    /// 
+    /// # Arguments
+    /// * `buffer` - ファイルの内容（メモリマップまたは読み込んだバッファ）
+    /// * `file_path` - HARファイルのパス
+    /// 
+    /// # Returns
+    /// * `Result<HarFile>` - 解析されたHARデータ
+    #[cfg(feature = "simd")]
+    fn parse_file_buffer(buffer: &mut [u8], file_path: &str) -> Result<HarFile> {
+        if let Some(har_data) = Self::parse_simd(buffer) {
+            return Ok(har_data);
+        }
+        let content = fs::read(file_path)
+            .map_err(AnalyzerError::io("ファイルの読み込みに失敗しました"))?;
+        Self::parse_serde(&content)
+    }
+
+    /// ファイルから読み込んだバッファをHARデータに変換
+    /// 
+    /// # Arguments
+    /// * `buffer` - ファイルの内容（メモリマップまたは読み込んだバッファ）
+    /// * `_file_path` - HARファイルのパス（`simd`フィーチャーが無効な場合は使用しない）
+    /// 
+    /// # Returns
+    /// * `Result<HarFile>` - 解析されたHARデータ
+    #[cfg(not(feature = "simd"))]
+    fn parse_file_buffer(buffer: &mut [u8], _file_path: &str) -> Result<HarFile> {
+        Self::parse_serde(buffer)
+    }
+
+    /// JSONバイト列をHARデータに変換
+    /// 
+    /// `simd`フィーチャーが有効な場合はsimd-jsonで解析し、失敗した場合はserde_jsonで再解析する
+    /// 
+    /// # Arguments
+    /// * `content` - JSONバイト列
+    /// 
+    /// # Returns
+    /// * `Result<HarFile>` - 解析されたHARデータ
+    fn parse_json(content: &[u8]) -> Result<HarFile> {
+        #[cfg(feature = "simd")]
+        {
+            // simd-jsonは入力バッファをその場で書き換えるため複製を渡す
+            if let Some(har_data) = Self::parse_simd(&mut content.to_vec()) {
+                return Ok(har_data);
+            }
+        }
+        Self::parse_serde(content)
+    }
+
+    /// simd-jsonでバッファをその場で書き換えながらHARデータに変換
+    /// 
+    /// # Arguments
+    /// * `buffer` - JSONバイト列（解析の成否によらず内容が書き換えられる）
+    /// 
+    /// # Returns
+    /// * `Option<HarFile>` - 解析されたHARデータ（失敗した場合はNone）
+    #[cfg(feature = "simd")]
+    fn parse_simd(buffer: &mut [u8]) -> Option<HarFile> {
+        match simd_json::serde::from_slice::<HarFile>(buffer) {
+            Ok(har_data) => {
+                debug!("simd-jsonで解析しました");
+                Some(har_data)
+            }
+            Err(e) => {
+                warn!("simd-jsonでの解析に失敗したためserde_jsonで再解析します: {}", e);
+                None
+            }
+        }
+    }
+
+    /// serde_jsonでJSONバイト列をHARデータに変換
+    /// 
+    /// # Arguments
+    /// * `content` - JSONバイト列
+    /// 
+    /// # Returns
+    /// * `Result<HarFile>` - 解析されたHARデータ
+    fn parse_serde(content: &[u8]) -> Result<HarFile> {
+        serde_json::from_slice(content).map_err(|e| {
+            // より詳細なエラー情報を出力
+            warn!("エラーが発生した行: {}", e.line());
+            warn!("エラーが発生した列: {}", e.column());
+            AnalyzerError::from(e)
+        })
+    }
+
+    /// ボディの加工方法（文字コードの指定・HTMLの要約）を指定
+    /// 
+    /// # Arguments
+    /// * `payload_options` - ボディの加工方法のオプション
+    /// 
+    /// # Returns
+    /// * `HarAnalyzer` - 加工方法を指定したアナライザ
+    pub fn with_payload_options(mut self, payload_options: PayloadOptions) -> Self {
+        self.payload_options = payload_options;
+        self
+    }
+
+    /// 加工済みのボディのキャッシュを指定
+    /// 
+    /// キャッシュにあるエントリはボディのデコード・整形を省略し、無いエントリは解析時に加工してキャッシュに保存する
+    /// 
+    /// # Arguments
+    /// * `payload_cache` - 加工済みのボディのキャッシュ
+    /// 
+    /// # Returns
+    /// * `HarAnalyzer` - キャッシュを指定したアナライザ
+    pub fn with_payload_cache(mut self, payload_cache: PayloadCache) -> Self {
+        self.payload_cache = Some(payload_cache);
+        self
+    }
+
+    /// 加工済みのボディのキャッシュを取得
+    /// 
+    /// # Returns
+    /// * `Option<&PayloadCache>` - キャッシュ（指定していない場合はNone）
+    pub fn payload_cache(&self) -> Option<&PayloadCache> {
+        self.payload_cache.as_ref()
+    }
+
+    /// 解析対象のエントリを絞り込むフィルタを指定
+    /// 
+    /// # Arguments
+    /// * `filter` - フィルタ
+    /// 
+    /// # Returns
+    /// * `HarAnalyzer` - フィルタを指定したアナライザ
+    pub fn with_filter(mut self, filter: EntryFilter) -> Self {
+        if !filter.resource_types.is_empty()
+            && self.har_data.log.entries.iter().all(|entry| entry.resource_type.is_none())
+        {
+            warn!("HARにリソースの種類（_resourceType）が記録されていないため、--resource-typeに一致するエントリはありません");
+        }
+        self.filter = filter;
+        self
+    }
+
+    /// コメント列に出力するレビューのメモを指定
+    /// 
+    /// # Arguments
+    /// * `annotations` - レビューのメモ
+    /// 
+    /// # Returns
+    /// * `HarAnalyzer` - メモを指定したアナライザ
+    pub fn with_annotations(mut self, annotations: Annotations) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
+    /// 強調表示してFlaggedシートに出力するエントリの条件を指定
+    /// 
+    /// # Arguments
+    /// * `flags` - 注目させるエントリの条件
+    /// 
+    /// # Returns
+    /// * `HarAnalyzer` - 条件を指定したアナライザ
+    pub fn with_flags(mut self, flags: EntryFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// 解析結果を開始時刻順に出力する
+    /// 
+    /// 出力する順序のみを変え、エントリ番号・`--flag-index`・`--annotate`・指摘事項のエントリ番号は記録順のまま保つ
+    /// 
+    /// # Returns
+    /// * `usize` - 位置が変わったエントリの数
+    pub fn sort_by_time(&mut self) -> usize {
+        let order = clock_skew::time_order(&self.har_data.log.entries);
+        let moved = order.iter().enumerate().filter(|(position, index)| position != *index).count();
+        if moved == 0 {
+            return 0;
+        }
+        self.order = Some(order);
+        info!("エントリを開始時刻順に並べ替えました（位置が変わったエントリ: {}件）", moved);
+        moved
+    }
+
+    /// 営業時間列に出力する営業時間を指定
+    /// 
+    /// # Arguments
+    /// * `business_hours` - 営業時間（指定しない場合はNone）
+    /// 
+    /// # Returns
+    /// * `HarAnalyzer` - 営業時間を指定したアナライザ
+    pub fn with_business_hours(mut self, business_hours: Option<BusinessHours>) -> Self {
+        self.business_hours = business_hours;
+        self
+    }
+
+    /// エントリIDを求めるかどうかを指定
+    /// 
+    /// 求めない場合、解析結果のエントリIDは空文字列になる
+    /// 
+    /// # Arguments
+    /// * `entry_ids` - エントリIDを求めるかどうか
+    /// 
+    /// # Returns
+    /// * `HarAnalyzer` - 指定したアナライザ
+    pub fn with_entry_ids(mut self, entry_ids: bool) -> Self {
+        self.entry_ids = entry_ids;
+        self
+    }
+
+    /// ドメインの分類に使用するリストを指定
+    /// 
+    /// # Arguments
+    /// * `blocklist` - ドメインの分類のリスト
+    /// 
+    /// # Returns
+    /// * `HarAnalyzer` - リストを指定したアナライザ
+    pub fn with_blocklist(mut self, blocklist: Blocklist) -> Self {
+        self.blocklist = blocklist;
+        self
+    }
+
+    /// 読み込んだHARデータを取得
+    /// 
+    /// # Returns
+    /// * `&HarFile` - HARデータ
+    pub fn har_data(&self) -> &HarFile {
+        &self.har_data
+    }
+
+    /// 指定したエントリのリクエスト・レスポンスのボディを、データシートと同じ方法でデコード・整形
+    /// 
+    /// # Arguments
+    /// * `index` - エントリの位置（0始まり）
+    /// 
+    /// # Returns
+    /// * `Option<(String, String)>` - (リクエストボディ, レスポンスボディ)（範囲外の場合はNone）
+    pub fn entry_payloads(&self, index: usize) -> Option<(String, String)> {
+        let entry = self.har_data.log.entries.get(index)?;
+        let request = LazyText::new(self.extract_request_payload(entry));
+        let response = LazyText::new(self.extract_response_payload(entry));
+        Some((request.as_str().to_string(), response.as_str().to_string()))
+    }
+
+    /// HARファイルの作成ツールを取得
+    /// 
+    /// # Returns
+    /// * `Producer` - 作成ツール
+    pub fn producer(&self) -> Producer {
+        self.producer
+    }
+
+    /// アナライザを消費してHARデータを取り出す
+    /// 
+    /// # Returns
+    /// * `HarFile` - HARデータ
+    pub fn into_har_data(self) -> HarFile {
+        self.har_data
     }
 
     /// HARファイルを解析して結果を取得
@@ -51,45 +484,561 @@ impl HarAnalyzer {
     pub fn analyze(&self) -> Result<Vec<AnalysisResult>> {
         info!("HARファイルの解析を開始します");
         
-        let mut results = Vec::new();
+        let results = self.results().collect::<Result<Vec<_>>>()?;
+        
+        info!("解析が完了しました。結果数: {}", results.len());
+        Ok(results)
+    }
+
+    /// 解析結果を1件ずつ返すイテレータを取得
+    /// 
+    /// 結果を全てメモリに保持せずに出力する場合に使用する
+    /// 
+    /// # Returns
+    /// * `impl Iterator<Item = Result<AnalysisResult>>` - 解析結果のイテレータ
+    pub fn results(&self) -> impl Iterator<Item = Result<AnalysisResult>> + '_ {
+        self.target_results().map(|(_, result)| result)
+    }
+
+    /// 解析対象のエントリを1件ずつ返すイテレータを取得
+    /// 
+    /// # Returns
+    /// * `impl Iterator<Item = &Entry>` - 解析対象のエントリのイテレータ
+    pub fn target_entries(&self) -> impl Iterator<Item = &Entry> + '_ {
+        self.target_results().map(|(index, _)| &self.har_data.log.entries[index])
+    }
+
+    /// 絞り込みと抽出を適用した解析結果を、エントリの番号とともに1件ずつ返すイテレータを取得
+    /// 
+    /// # Returns
+    /// * `impl Iterator<Item = (usize, Result<AnalysisResult>)>` - (エントリの番号, 解析結果)のイテレータ
+    fn target_results(&self) -> impl Iterator<Item = (usize, Result<AnalysisResult>)> + '_ {
+        let sampling = self.filter.sampling;
+        let entries = &self.har_data.log.entries;
+        let asset_counts = self
+            .filter
+            .collapse_assets
+            .then(|| asset_collapse::repeat_counts(entries, |entry| self.is_target(entry)));
+        let page_counts = self
+            .filter
+            .collapse_pages
+            .then(|| pagination::repeat_counts(&self.page_sequences(), entries.len()));
+        // 両方で集約する場合は、どちらかで集約されたエントリを出力せず、集約先の回数は大きい方とする
+        let repeat_counts = match (asset_counts, page_counts) {
+            (Some(assets), Some(pages)) => Some(
+                assets
+                    .iter()
+                    .zip(&pages)
+                    .map(|(&asset, &page)| {
+                        if asset == asset_collapse::COLLAPSED || page == asset_collapse::COLLAPSED {
+                            asset_collapse::COLLAPSED
+                        } else {
+                            asset.max(page)
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            (assets, pages) => assets.or(pages),
+        };
+        // エンドポイントごとの抽出は集約した後のエントリから選ぶ
+        let selected = sampling.per_endpoint.map(|per_endpoint| {
+            endpoint_sampling::select(
+                entries,
+                per_endpoint,
+                |index, entry| {
+                    self.is_target(entry)
+                        && repeat_counts.as_ref().is_none_or(|counts| counts[index] != asset_collapse::COLLAPSED)
+                },
+                |entry| self.entry_duration(entry),
+            )
+        });
+        self.entry_order()
+            .into_iter()
+            .map(move |index| (index, (&entries[index], &self.gaps[index])))
+            .filter(|(_, (entry, _))| self.is_target(entry))
+            .filter(move |(index, _)| selected.as_ref().is_none_or(|selected| selected[*index]))
+            .map(move |(index, (entry, gap))| {
+                let repeat_count = repeat_counts.as_ref().map_or(1, |counts| counts[index]);
+                (index, entry, gap, repeat_count)
+            })
+            .filter(|(_, _, _, repeat_count)| *repeat_count != asset_collapse::COLLAPSED)
+            .map(|(index, entry, gap, repeat_count)| {
+                let result = self
+                    .analyze_entry(entry, index, *gap)
+                    .map(|result| AnalysisResult { repeat_count, ..result });
+                (index, entry, result)
+            })
+            .filter(|(_, entry, result)| self.matches_body(entry, result))
+            .map(|(index, _, result)| (index, result))
+            .enumerate()
+            .filter(move |(position, _)| sampling.includes(*position))
+            .map(|(_, target)| target)
+            .skip(sampling.offset)
+            .take(sampling.limit.unwrap_or(usize::MAX))
+    }
+
+    /// 解析結果を出力する順のエントリの位置を返すイテレータを取得
+    /// 
+    /// # Returns
+    /// * `Vec<usize>` - エントリの位置（並べ替えていない場合は記録順）
+    fn entry_order(&self) -> Vec<usize> {
+        self.order.clone().unwrap_or_else(|| (0..self.har_data.log.entries.len()).collect())
+    }
+
+    /// 解析対象のエントリからページ送りの一連のリクエストを検出
+    /// 
+    /// # Returns
+    /// * `Vec<PageSequence>` - 最初のリクエストの順のページ送り
+    pub fn page_sequences(&self) -> Vec<PageSequence> {
+        pagination::detect(
+            &self.har_data.log.entries,
+            |entry| self.is_target(entry),
+            |entry| self.entry_duration(entry),
+        )
+    }
+
+    /// 解析対象のエントリのキャッシュの設定を規則と照合
+    /// 
+    /// # Arguments
+    /// * `rules` - キャッシュの規則
+    /// 
+    /// # Returns
+    /// * `Vec<CacheViolation>` - エントリ順の違反
+    pub fn cache_violations(&self, rules: &CacheRules) -> Vec<CacheViolation> {
+        cache_lint::lint(
+            self.har_data.log.entries.iter().enumerate().filter(|(_, entry)| self.is_target(entry)),
+            rules,
+        )
+    }
+
+    /// 解析対象のエントリをサイズの予算と照合
+    /// 
+    /// # Arguments
+    /// * `budgets` - サイズの予算
+    /// 
+    /// # Returns
+    /// * `Vec<BudgetViolation>` - エントリ順の超過
+    pub fn budget_violations(&self, budgets: &Budgets) -> Vec<BudgetViolation> {
+        budgets::check(
+            self.har_data.log.entries.iter().enumerate().filter(|(_, entry)| self.is_target(entry)),
+            budgets,
+            &self.payload_options.mime_map,
+        )
+    }
+
+    /// 解析対象のエントリからServer-Timingヘッダーを記録したエントリを収集
+    /// 
+    /// # Returns
+    /// * `Vec<ServerTimingEntry>` - エントリ順のServer-Timingヘッダーを記録したエントリ
+    pub fn server_timings(&self) -> Vec<ServerTimingEntry> {
+        server_timing::collect(self.har_data.log.entries.iter().enumerate().filter(|(_, entry)| self.is_target(entry)))
+    }
+
+    /// 解析対象のエントリから異なるURLで同じ内容のレスポンスを検出
+    /// 
+    /// # Returns
+    /// * `Vec<DuplicateGroup>` - 削減できるサイズの大きい順のグループ
+    pub fn duplicate_responses(&self) -> Vec<DuplicateGroup> {
+        duplicates::detect(self.har_data.log.entries.iter().enumerate().filter(|(_, entry)| self.is_target(entry)))
+    }
+
+    /// 解析対象のエントリのうち圧縮せずに配信されたテキストのレスポンスについて、圧縮による削減量を見積もる
+    /// 
+    /// # Returns
+    /// * `Vec<HostSavings>` - brotliで削減できるサイズの大きい順のホストごとの見積もり
+    pub fn compression_savings(&self) -> Vec<HostSavings> {
+        compression::estimate(
+            self.har_data.log.entries.iter().filter(|entry| self.is_target(entry)),
+            &self.payload_options.mime_map,
+        )
+    }
+
+    /// 解析対象のエントリのレスポンスの転送量から費用を見積もる
+    /// 
+    /// # Arguments
+    /// * `pricing` - 転送量の単価の設定
+    /// 
+    /// # Returns
+    /// * `EgressCostReport` - ホスト・コンテンツタイプごとの転送量の費用の見積もり
+    pub fn egress_costs(&self, pricing: &EgressPricing) -> EgressCostReport {
+        egress_cost::estimate(self.har_data.log.entries.iter().filter(|entry| self.is_target(entry)), pricing)
+    }
+
+    /// 解析対象のエントリの条件付きリクエストをURLごとに集計
+    /// 
+    /// # Returns
+    /// * `Vec<ConditionalStats>` - 条件付きリクエストを送信したURLの集計
+    pub fn conditional_requests(&self) -> Vec<ConditionalStats> {
+        conditional::analyze(self.har_data.log.entries.iter().filter(|entry| self.is_target(entry)))
+    }
+
+    /// 解析対象のエントリを接続（`connection`フィールド）ごとに集計
+    /// 
+    /// # Returns
+    /// * `Vec<ConnectionUsage>` - 開始時刻の順の接続ごとの利用状況（`connection`フィールドが無いHARでは空）
+    pub fn connection_usage(&self) -> Vec<ConnectionUsage> {
+        connections::analyze(self.har_data.log.entries.iter().enumerate().filter(|(_, entry)| self.is_target(entry)))
+    }
+
+    /// 解析対象のエントリのレスポンスヘッダーからホストごとのCDN・プロキシ・バックエンドを推定
+    /// 
+    /// # Returns
+    /// * `Vec<HostInfrastructure>` - ホスト名の順の基盤の情報
+    pub fn infrastructure(&self) -> Vec<HostInfrastructure> {
+        infrastructure::fingerprint(self.har_data.log.entries.iter().filter(|entry| self.is_target(entry)))
+    }
+
+    /// 解析対象のエントリのリクエスト・レスポンスのヘッダーを名前ごとに集計
+    /// 
+    /// # Arguments
+    /// * `max_examples` - ヘッダーごとの値の例の最大数
+    /// 
+    /// # Returns
+    /// * `Vec<HeaderUsage>` - 方向・名前の順のヘッダーの使用状況
+    pub fn header_inventory(&self, max_examples: usize) -> Vec<HeaderUsage> {
+        header_inventory::collect(self.har_data.log.entries.iter().filter(|entry| self.is_target(entry)), max_examples)
+    }
+
+    /// HARファイルを解析して全ての通信イベントを取得
+    /// 
+    /// # Returns
+    /// * `Result<Vec<TrafficEvent>>` - 通信イベントのリスト
+    pub fn analyze_events(&self) -> Result<Vec<TrafficEvent>> {
+        info!("HARファイルの解析を開始します");
+        
+        let events = self.events().collect::<Result<Vec<_>>>()?;
+        
+        let http_count = events.iter().filter(|e| e.as_http().is_some()).count();
+        info!("解析が完了しました。結果数: {}", http_count);
+        Ok(events)
+    }
+
+    /// 通信イベントを1件ずつ返すイテレータを取得
+    /// 
+    /// ページの読み込みを先に返し、以降はエントリ順にHTTPの通信とそのWebSocketメッセージを返す
+    /// 
+    /// # Returns
+    /// * `impl Iterator<Item = Result<TrafficEvent>>` - 通信イベントのイテレータ
+    pub fn events(&self) -> impl Iterator<Item = Result<TrafficEvent>> + '_ {
+        let pages = self.page_events().into_iter().map(|page| Ok(TrafficEvent::PageEvent(page)));
+        
+        let mut exchanges = self.target_results().peekable();
+        let entries = self.entry_order().into_iter().flat_map(move |index| {
+            let entry = &self.har_data.log.entries[index];
+            let exchange = exchanges
+                .next_if(|(target, _)| *target == index)
+                .map(|(_, result)| result.map(TrafficEvent::HttpExchange));
+            let frames = entry
+                .web_socket_messages
+                .iter()
+                .map(move |message| Ok(TrafficEvent::WebSocketFrame(Self::websocket_frame(entry, message))));
+            exchange.into_iter().chain(frames)
+        });
         
+        pages.chain(entries)
+    }
+
+    /// 解析対象のエントリかどうか（GET/POSTリクエストのうちフィルタの条件を満たすもののみを処理）
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `bool` - 解析対象の場合はtrue
+    fn is_target(&self, entry: &Entry) -> bool {
+        debug!("エントリを処理中: {} {}", entry.request.method, entry.request.url);
+        
+        if !TARGET_METHODS.contains(&entry.request.method.as_str()) {
+            debug!("スキップ: {} メソッドは対象外", entry.request.method);
+            return false;
+        }
+        if !self.filter.matches(entry) {
+            debug!("スキップ: フィルタの条件に一致しません");
+            return false;
+        }
+        true
+    }
+
+    /// 解析結果がペイロードの条件を満たすかどうか（解析に失敗した場合はエラーを返すため対象とする）
+    /// 
+    /// HTMLの要約・JavaScript・CSSの整形は出力のための加工のため、レスポンスはその前のデコードしたボディで判定する
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// * `result` - 解析結果
+    /// 
+    /// # Returns
+    /// * `bool` - 対象とする場合はtrue
+    fn matches_body(&self, entry: &Entry, result: &Result<AnalysisResult>) -> bool {
+        match result {
+            Ok(result) => {
+                if !self.filter.filters_body() {
+                    return true;
+                }
+                let options = &self.payload_options;
+                let summarizes = options.html_mode != HtmlMode::Full || options.js_css_mode != JsCssMode::Full;
+                let unsummarized = match summarizes.then(|| self.extract_response_payload(entry)) {
+                    Some(RawPayload::Html { body, .. } | RawPayload::JsCss { body, .. }) => Some(LazyText::new(*body)),
+                    _ => None,
+                };
+                let response = unsummarized.as_ref().unwrap_or(&result.response_payload);
+                let matched = self.filter.matches_payloads(result.request_payload.as_str(), response.as_str());
+                if !matched {
+                    debug!("スキップ: ペイロードが条件に一致しません");
+                }
+                matched
+            }
+            Err(_) => true,
+        }
+    }
+
+    /// エントリが属するページを取得
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `Option<&Page>` - ページ（ページに属さない場合はNone）
+    fn page(&self, entry: &Entry) -> Option<&Page> {
+        let pageref = entry.pageref.as_deref()?;
+        self.har_data.log.pages.iter().find(|page| page.id == pageref)
+    }
+
+    /// ページの読み込みイベントを取得
+    /// 
+    /// # Returns
+    /// * `Vec<PageEvent>` - ページの読み込みイベントのリスト
+    fn page_events(&self) -> Vec<PageEvent> {
+        let mut page_entries: HashMap<&str, Vec<&Entry>> = HashMap::new();
         for entry in &self.har_data.log.entries {
-            debug!("エントリを処理中: {} {}", entry.request.method, entry.request.url);
-            
-            // GET/POSTリクエストのみを処理
-            if !matches!(entry.request.method.as_str(), "GET" | "POST") {
-                debug!("スキップ: {} メソッドは対象外", entry.request.method);
-                continue;
+            if let Some(pageref) = &entry.pageref {
+                page_entries.entry(pageref.as_str()).or_default().push(entry);
             }
-            
-            // 時刻の解析
-            let timestamp = self.parse_timestamp(&entry.started_date_time)?;
-            
-            // IPアドレスの取得
-            let (source_ip, destination_ip) = self.extract_ip_addresses(entry)?;
-            
-            // リクエストペイロードの取得
-            let request_payload = self.extract_request_payload(entry);
-            
-            // レスポンスペイロードの取得
-            let response_payload = self.extract_response_payload(entry);
-            
-            let result = AnalysisResult {
-                timestamp,
-                source_ip,
-                destination_ip,
-                method: entry.request.method.clone(),
-                status_code: entry.response.status,
-                request_url: self.decode_url(&entry.request.url),
-                request_payload,
-                response_payload,
-            };
-            
-            results.push(result);
         }
+        let no_entries = Vec::new();
         
-        info!("解析が完了しました。結果数: {}", results.len());
-        Ok(results)
+        self.har_data
+            .log
+            .pages
+            .iter()
+            .map(|page| {
+                let entries = page_entries.get(page.id.as_str()).unwrap_or(&no_entries);
+                PageEvent {
+                    page_id: page.id.clone(),
+                    title: page.title.clone(),
+                    started: self
+                        .parse_timestamp(&page.started_date_time)
+                        .map(|(timestamp, _, _)| timestamp)
+                        .unwrap_or_else(|_| page.started_date_time.clone()),
+                    on_content_load: page.page_timings.on_content_load.filter(|t| *t >= 0.0),
+                    on_load: page.page_timings.on_load.filter(|t| *t >= 0.0),
+                    request_count: entries.len(),
+                    milestones: PageMilestones::compute(
+                        page,
+                        entries,
+                        |entry| self.entry_duration(entry),
+                        Self::response_size,
+                    ),
+                    critical_path: critical_path::estimate(page, entries, |entry| self.entry_duration(entry)),
+                }
+            })
+            .collect()
+    }
+
+    /// WebSocketのメッセージをイベントに変換
+    /// 
+    /// # Arguments
+    /// * `entry` - WebSocketの接続を行ったHARエントリ
+    /// * `message` - WebSocketのメッセージ
+    /// 
+    /// # Returns
+    /// * `WebSocketFrame` - WebSocketのメッセージ
+    fn websocket_frame(entry: &Entry, message: &WebSocketMessage) -> WebSocketFrame {
+        let timestamp = DateTime::from_timestamp_millis((message.time * 1000.0).round() as i64)
+            .map(|dt| dt.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+            .unwrap_or_else(|| message.time.to_string());
+        
+        WebSocketFrame {
+            timestamp,
+            url: entry.request.url.clone(),
+            direction: FrameDirection::from_message_type(&message.message_type),
+            opcode: message.opcode,
+            data: message.data.clone(),
+        }
+    }
+
+    /// 1件のエントリを解析
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// * `index` - エントリの位置（0始まり）
+    /// * `gap` - 前の通信からの間隔
+    /// 
+    /// # Returns
+    /// * `Result<AnalysisResult>` - 解析結果
+    fn analyze_entry(&self, entry: &Entry, index: usize, gap: EntryGap) -> Result<AnalysisResult> {
+        // 時刻の解析
+        let (timestamp, timestamp_iso, started_at) = self.parse_timestamp(&entry.started_date_time)?;
+        
+        // IPアドレスの取得
+        let (source_ip, destination_ip) = self.extract_ip_addresses(entry)?;
+        
+        // バックエンドが報告した処理時間
+        let server_timing = server_timing::metrics(entry);
+        
+        // リクエストの発生元（DevToolsは0始まりの行番号を記録する）
+        let initiator = entry.initiator.as_ref().and_then(|initiator| initiator.source_location());
+        
+        // リクエスト・レスポンスペイロードの取得（キャッシュを使用しない場合、加工は出力時に行う）
+        let (request_payload, response_payload) = match &self.payload_cache {
+            Some(cache) => {
+                let (request, response) = cache.get_or_render(
+                    entry,
+                    || LazyText::new(self.extract_request_payload(entry)).as_str().to_string(),
+                    || LazyText::new(self.extract_response_payload(entry)).as_str().to_string(),
+                );
+                // 出力に使用しない側は加工せず、参照された場合のみ加工する
+                (
+                    request.map_or_else(|| LazyText::new(self.extract_request_payload(entry)), LazyText::rendered),
+                    response.map_or_else(|| LazyText::new(self.extract_response_payload(entry)), LazyText::rendered),
+                )
+            }
+            None => (
+                LazyText::new(self.extract_request_payload(entry)),
+                LazyText::new(self.extract_response_payload(entry)),
+            ),
+        };
+        
+        Ok(AnalysisResult {
+            timestamp,
+            timestamp_iso,
+            started_at,
+            source_ip,
+            destination_ip,
+            method: entry.request.method.clone(),
+            status_code: entry.response.status,
+            status_text: entry.response.status_text.clone(),
+            redirect_url: Self::redirect_target(entry),
+            duration: self.entry_duration(entry),
+            request_size: Self::request_size(entry),
+            response_size: Self::response_size(entry),
+            latency_outlier: false,
+            gap: gap.overall,
+            host_gap: gap.host,
+            soap_operation: soap::operation(entry),
+            repeat_count: 1,
+            comments: self.comments(entry, index),
+            entry_index: index,
+            entry_id: if self.entry_ids { entry.content_id() } else { String::new() },
+            flag: self.flags.reason(index, &entry.request.url),
+            security_details: entry.security_details.clone(),
+            cert_expiry: entry.security_details.as_ref().and_then(|details| details.expiry_iso()),
+            domain_category: self.domain_category(entry),
+            protocol: TrafficProtocol::detect(entry),
+            body_kind: BodyKind::of_response(entry),
+            business_hours: self
+                .business_hours
+                .as_ref()
+                .and_then(|hours| hours.contains_started(&entry.started_date_time)),
+            wait: entry.timings.wait.max(0.0),
+            server_timing: server_timing::describe(&server_timing),
+            server_time: server_timing::server_time(&server_timing),
+            warnings: self.entry_warnings(entry, index),
+            request_url: entry.request.url.clone(),
+            decoded_url: LazyText::new(RawPayload::EncodedUrl(entry.request.url.clone())),
+            url_parts: UrlParts::parse(&entry.request.url),
+            resource_type: entry.resource_type.clone().unwrap_or_default(),
+            initiator_url: initiator.map(|(url, _)| url.to_string()).unwrap_or_default(),
+            initiator_line: initiator.and_then(|(_, line)| line).map(|line| line + 1),
+            priority: entry.priority.clone().unwrap_or_default(),
+            render_blocking: self.page(entry).is_some_and(|page| milestones::is_render_blocking(entry, page)),
+            pageref: entry.pageref.clone(),
+            request_headers: entry.request.headers.clone(),
+            response_headers: entry.response.headers.clone(),
+            request_payload,
+            response_payload,
+        })
+    }
+
+    /// リクエスト先のドメインを分類
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `DomainCategory` - 分類（URLを解析できない場合はサードパーティ）
+    fn domain_category(&self, entry: &Entry) -> DomainCategory {
+        Url::parse(&entry.request.url)
+            .ok()
+            .and_then(|url| url.host_str().map(|host| self.blocklist.classify(host, self.first_party_site.as_deref())))
+            .unwrap_or_default()
+    }
+
+    /// リダイレクト先のURLを取得
+    /// 
+    /// `redirectURL`が記録されていない3xxのレスポンスはLocationヘッダーを使用し、相対URLはリクエストURLを基準に解決する
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `String` - リダイレクト先のURL（無い場合は空文字列）
+    fn redirect_target(entry: &Entry) -> String {
+        if !entry.response.redirect_url.is_empty() || !(300..=399).contains(&entry.response.status) {
+            return entry.response.redirect_url.clone();
+        }
+        let Some(location) = entry
+            .response
+            .headers
+            .iter()
+            .find(|header| header.name.eq_ignore_ascii_case("location"))
+        else {
+            return String::new();
+        };
+        Url::parse(&entry.request.url)
+            .and_then(|base| base.join(location.value.trim()))
+            .map_or_else(|_| location.value.trim().to_string(), String::from)
+    }
+
+    /// エントリの解析・デコードの警告と開始時刻の前後関係の警告をまとめる
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// * `index` - エントリの位置（0始まり）
+    /// 
+    /// # Returns
+    /// * `EntryWarnings` - 警告の一覧
+    fn entry_warnings(&self, entry: &Entry, index: usize) -> EntryWarnings {
+        let mut items = self.clock_warnings.get(&index).cloned().unwrap_or_default();
+        items.extend_from_slice(entry_warnings::check(entry, &self.payload_options.mime_map).items());
+        EntryWarnings::new(items)
+    }
+
+    /// エントリのコメントとレビューのメモを1つのテキストにまとめる
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// * `index` - エントリの位置（0始まり）
+    /// 
+    /// # Returns
+    /// * `String` - 1行に1件ずつ記述したコメント（無い場合は空文字列）
+    fn comments(&self, entry: &Entry, index: usize) -> String {
+        let har_comments = [
+            ("エントリ", &entry.comment),
+            ("リクエスト", &entry.request.comment),
+            ("レスポンス", &entry.response.comment),
+        ]
+        .into_iter()
+        .filter_map(|(label, comment)| {
+            let comment = comment.as_deref()?.trim();
+            (!comment.is_empty()).then(|| format!("{}: {}", label, comment))
+        });
+        let notes = self
+            .annotations
+            .notes(index, &entry.request.url)
+            .into_iter()
+            .map(|note| format!("メモ: {}", note));
+        har_comments.chain(notes).collect::<Vec<_>>().join("\n")
     }
 
     /// タイムスタンプを解析してフォーマット
@@ -98,17 +1047,74 @@ impl HarAnalyzer {
     /// * `timestamp_str` - タイムスタンプ文字列
     /// 
     /// # Returns
-    /// * `Result<String>` - フォーマットされたタイムスタンプ
-    fn parse_timestamp(&self, timestamp_str: &str) -> Result<String> {
+    /// * `Result<(String, String, Option<NaiveDateTime>)>` - (表示用のタイムスタンプ, ISO 8601形式のタイムスタンプ, 記録されたタイムゾーンでの日時)
+    fn parse_timestamp(&self, timestamp_str: &str) -> Result<(String, String, Option<NaiveDateTime>)> {
         match DateTime::parse_from_rfc3339(timestamp_str) {
-            Ok(dt) => Ok(dt.format("%Y-%m-%d %H:%M:%S%.3f").to_string()),
+            Ok(dt) => Ok((
+                dt.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+                dt.to_rfc3339_opts(SecondsFormat::Millis, true),
+                Some(dt.naive_local()),
+            )),
             Err(_) => {
                 warn!("タイムスタンプの解析に失敗しました: {}", timestamp_str);
-                Ok(timestamp_str.to_string())
+                Ok((timestamp_str.to_string(), timestamp_str.to_string(), None))
             }
         }
     }
 
+    /// エントリの所要時間を取得
+    /// 
+    /// 所要時間を記録しない作成ツールの場合は各フェーズの合計で補完する
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `f64` - 所要時間（ミリ秒）
+    fn entry_duration(&self, entry: &Entry) -> f64 {
+        if self.producer.recomputes_duration() && entry.time <= 0.0 {
+            entry.timings.total()
+        } else {
+            entry.time
+        }
+    }
+
+    /// リクエストボディのサイズを取得
+    /// 
+    /// bodySizeが不明（-1）の場合はPOSTデータのテキスト長を使用する
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `i64` - サイズ（バイト）
+    pub(crate) fn request_size(entry: &Entry) -> i64 {
+        if entry.request.body_size >= 0 {
+            return entry.request.body_size;
+        }
+        entry.request.post_data
+            .as_ref()
+            .and_then(|post_data| post_data.text.as_ref())
+            .map_or(0, |text| text.len() as i64)
+    }
+
+    /// レスポンスボディのサイズを取得
+    /// 
+    /// 展開後のサイズ（content.size）を優先し、不明な場合はbodySizeを使用する
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `i64` - サイズ（バイト）
+    pub(crate) fn response_size(entry: &Entry) -> i64 {
+        if entry.response.content.size >= 0 {
+            entry.response.content.size
+        } else {
+            entry.response.body_size.max(0)
+        }
+    }
+
     /// IPアドレスを抽出
     /// 
     /// # Arguments
@@ -116,13 +1122,14 @@ impl HarAnalyzer {
     /// 
     /// # Returns
     /// * `Result<(String, String)>` - (送信元IP, 送信先IP)
-    fn extract_ip_addresses(&self, entry: &crate::har_types::Entry) -> Result<(String, String)> {
+    fn extract_ip_addresses(&self, entry: &Entry) -> Result<(String, String)> {
         // 送信元IPは通常、HARファイルには含まれないため、プレースホルダーを使用
         let source_ip = "localhost".to_string();
         
         // 送信先IPはserverIPAddressフィールドまたはURLのホスト名から取得
         let destination_ip = if let Some(server_ip) = &entry.server_ip_address {
-            server_ip.clone()
+            // FirefoxはIPv6アドレスを角括弧付きで記録する
+            server_ip.trim_start_matches('[').trim_end_matches(']').to_string()
         } else {
             // URLからホスト名を抽出
             match Url::parse(&entry.request.url) {
@@ -142,136 +1149,202 @@ impl HarAnalyzer {
 
     /// リクエストペイロードを抽出
     /// 
+    /// アクセス解析のビーコンの場合は、イベントの一覧に変換するペイロードとする
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `RawPayload` - 加工前のリクエストペイロード
+    fn extract_request_payload(&self, entry: &Entry) -> RawPayload {
+        let payload = self.extract_request_body(entry);
+        let Some(provider) = AnalyticsProvider::detect(&entry.request.url) else {
+            return payload;
+        };
+        // フォームのパラメータのみが記録されている場合はフォームの形式に戻す
+        let body = entry.request.post_data.as_ref().and_then(|post_data| {
+            post_data.text.clone().or_else(|| {
+                (!post_data.params.is_empty()).then(|| {
+                    form_urlencoded::Serializer::new(String::new())
+                        .extend_pairs(post_data.params.iter().map(|param| {
+                            (param.name.as_str(), param.value.as_deref().unwrap_or_default())
+                        }))
+                        .finish()
+                })
+            })
+        });
+        RawPayload::Analytics {
+            provider,
+            url: entry.request.url.clone(),
+            body,
+            fallback: Box::new(payload),
+        }
+    }
+
+    /// リクエストボディ（ボディが無い場合はクエリパラメータ）を抽出
+    /// 
     /// # Arguments
     /// * `entry` - HARエントリ
     /// 
     /// # Returns
-    /// * `String` - リクエストペイロード（JSON形式）
-    fn extract_request_payload(&self, entry: &crate::har_types::Entry) -> String {
+    /// * `RawPayload` - 加工前のリクエストボディ
+    fn extract_request_body(&self, entry: &Entry) -> RawPayload {
         if let Some(post_data) = &entry.request.post_data {
             if let Some(text) = &post_data.text {
-                // JSONかどうかを確認
-                if self.is_json_content(&post_data.mime_type) {
-                    return self.format_json(text);
-                }
-                return text.clone();
+                return match self.payload_options.mime_map.body_format(&post_data.mime_type) {
+                    BodyFormat::GrpcWeb { text: text_format } => {
+                        self.grpc_web_payload(entry, text, false, text_format, true)
+                    }
+                    BodyFormat::Binary(format) => RawPayload::Binary { text: text.clone(), base64: false, format },
+                    BodyFormat::Text(TextFormat::Json) => RawPayload::Json(text.clone()),
+                    BodyFormat::Text(TextFormat::Ndjson) => RawPayload::Ndjson(text.clone()),
+                    // MIMEタイプが無い・text/plainの場合はボディの先頭からJSONかどうかを推定
+                    BodyFormat::Text(_) if self.sniffs_json(&post_data.mime_type) && looks_like_json(text) => {
+                        RawPayload::Json(text.clone())
+                    }
+                    BodyFormat::Text(_) => RawPayload::Text(text.clone()),
+                };
             }
             
             // パラメータからJSONを構築
             if !post_data.params.is_empty() {
-                let mut param_map = HashMap::new();
-                for param in &post_data.params {
-                    param_map.insert(param.name.clone(), param.value.clone().unwrap_or_default());
-                }
-                
-                match serde_json::to_string_pretty(&param_map) {
-                    Ok(json) => return json,
-                    Err(_) => {}
-                }
+                return RawPayload::Pairs(
+                    post_data
+                        .params
+                        .iter()
+                        .map(|param| (param.name.clone(), param.value.clone().unwrap_or_default()))
+                        .collect(),
+                );
             }
         }
         
         // クエリパラメータをJSONとして出力
         if !entry.request.query_string.is_empty() {
-            let mut query_map = HashMap::new();
-            for query in &entry.request.query_string {
-                query_map.insert(query.name.clone(), query.value.clone());
-            }
-            
-            match serde_json::to_string_pretty(&query_map) {
-                Ok(json) => return json,
-                Err(_) => {}
-            }
+            return RawPayload::Pairs(
+                entry
+                    .request
+                    .query_string
+                    .iter()
+                    .map(|query| (query.name.clone(), query.value.clone()))
+                    .collect(),
+            );
         }
         
-        "{}".to_string()
+        RawPayload::Text("{}".to_string())
     }
 
-    /// レスポンスペイロードを抽出
+    /// gRPC-webのペイロードを作成
+    /// 
+    /// 記述子が指定されている場合は、リクエストURLのパスからメッセージの型を求める
     /// 
     /// # Arguments
     /// * `entry` - HARエントリ
+    /// * `text` - 記録されたボディ
+    /// * `base64` - ボディがBase64で記録されている場合はtrue
+    /// * `text_format` - gRPC-webのテキスト形式（`application/grpc-web-text`）の場合はtrue
+    /// * `request` - リクエストボディの場合はtrue、レスポンスボディの場合はfalse
     /// 
     /// # Returns
-    /// * `String` - レスポンスペイロード（JSON形式）
-    fn extract_response_payload(&self, entry: &crate::har_types::Entry) -> String {
-        if let Some(text) = &entry.response.content.text {
-            // Base64デコードが必要な場合
-            if let Some(encoding) = &entry.response.content.encoding {
-                if encoding == "base64" {
-                    match general_purpose::STANDARD.decode(text) {
-                        Ok(decoded) => {
-                            match String::from_utf8(decoded) {
-                                Ok(decoded_text) => {
-                                    if self.is_json_content(&entry.response.content.mime_type) {
-                                        return self.format_json(&decoded_text);
-                                    }
-                                    return decoded_text;
-                                }
-                                Err(_) => return text.clone(),
-                            }
-                        }
-                        Err(_) => return text.clone(),
-                    }
-                }
-            }
-            
-            // JSONコンテンツの場合はフォーマット
-            if self.is_json_content(&entry.response.content.mime_type) {
-                return self.format_json(text);
-            }
-            
-            text.clone()
-        } else {
-            "{}".to_string()
+    /// * `RawPayload` - 加工前のペイロード
+    fn grpc_web_payload(&self, entry: &Entry, text: &str, base64: bool, text_format: bool, request: bool) -> RawPayload {
+        let message = self.payload_options.proto_descriptor.as_ref().and_then(|pool| {
+            let path = Url::parse(&entry.request.url).ok()?.path().to_string();
+            grpc_web::message_descriptor(pool, &path, request)
+        });
+        RawPayload::GrpcWeb {
+            text: text.to_string(),
+            base64,
+            text_format,
+            message,
         }
     }
 
-    /// コンテンツタイプがJSONかどうかを判定
+    /// レスポンスペイロードを抽出
+    /// 
+    /// HTMLの要約が指定されている場合は、ボディの種類がHTMLのボディを要約するペイロードとし、
+    /// JavaScript・CSSの折り返し・整形が指定されている場合は、ボディの種類がJavaScript・CSSのボディを整形するペイロードとする
     /// 
     /// # Arguments
-    /// * `mime_type` - MIMEタイプ
+    /// * `entry` - HARエントリ
     /// 
     /// # Returns
-    /// * `bool` - JSONの場合はtrue
-    fn is_json_content(&self, mime_type: &str) -> bool {
-        mime_type.contains("application/json") || mime_type.contains("text/json")
+    /// * `RawPayload` - 加工前のレスポンスペイロード
+    fn extract_response_payload(&self, entry: &Entry) -> RawPayload {
+        let payload = self.extract_response_body(entry);
+        let content = &entry.response.content;
+        let options = &self.payload_options;
+        if content.text.is_none() || (options.html_mode == HtmlMode::Full && options.js_css_mode == JsCssMode::Full) {
+            return payload;
+        }
+        let body_kind = BodyKind::of_response(entry);
+        // MIMEタイプの分類の対応付けが無い場合は、ボディの内容から判定した種類で判定する
+        let is_html = match options.mime_map.mapped(&content.mime_type) {
+            Some(category) => category == MimeCategory::Html,
+            None => body_kind == BodyKind::Html,
+        };
+        if options.html_mode != HtmlMode::Full && is_html {
+            return RawPayload::Html { body: Box::new(payload), mode: options.html_mode };
+        }
+        if options.js_css_mode != JsCssMode::Full && matches!(body_kind, BodyKind::JavaScript | BodyKind::Css) {
+            return RawPayload::JsCss { body: Box::new(payload), kind: body_kind, mode: options.js_css_mode };
+        }
+        payload
     }
 
-    /// JSON文字列をフォーマット
+    /// レスポンスボディを抽出
     /// 
     /// # Arguments
-    /// * `json_str` - JSON文字列
+    /// * `entry` - HARエントリ
     /// 
     /// # Returns
-    /// * `String` - フォーマットされたJSON文字列
-    fn format_json(&self, json_str: &str) -> String {
-        match serde_json::from_str::<serde_json::Value>(json_str) {
-            Ok(value) => {
-                match serde_json::to_string_pretty(&value) {
-                    Ok(formatted) => formatted,
-                    Err(_) => json_str.to_string(),
-                }
+    /// * `RawPayload` - 加工前のレスポンスボディ
+    fn extract_response_body(&self, entry: &Entry) -> RawPayload {
+        let content = &entry.response.content;
+        let Some(text) = &content.text else {
+            return RawPayload::Text("{}".to_string());
+        };
+
+        let base64 = content.encoding.as_deref() == Some("base64");
+        let mut format = match self.payload_options.mime_map.body_format(&content.mime_type) {
+            BodyFormat::GrpcWeb { text: text_format } => {
+                return self.grpc_web_payload(entry, text, base64, text_format, false);
             }
-            Err(_) => json_str.to_string(),
+            // MessagePack・CBORはJSONに変換
+            BodyFormat::Binary(format) => return RawPayload::Binary { text: text.clone(), base64, format },
+            BodyFormat::Text(format) => format,
+        };
+        if format == TextFormat::Plain && self.sniffs_json(&content.mime_type) {
+            format = TextFormat::Sniffed;
+        }
+
+        // Base64デコードが必要な場合（デコード後のバイト列はcharsetに従って文字列に変換）
+        if base64 {
+            return RawPayload::Base64 {
+                text: text.clone(),
+                format,
+                encoding: body_encoding(&content.mime_type, self.payload_options.charset_override),
+            };
+        }
+        
+        // JSON・NDJSONコンテンツの場合はフォーマット
+        match format {
+            TextFormat::Json => RawPayload::Json(text.clone()),
+            TextFormat::Ndjson => RawPayload::Ndjson(text.clone()),
+            TextFormat::Sniffed if looks_like_json(text) => RawPayload::Json(text.clone()),
+            TextFormat::Plain | TextFormat::Sniffed => RawPayload::Text(text.clone()),
         }
     }
 
-    /// URLをUTF-8でデコード
+    /// ボディの先頭からJSONかどうかを推定するMIMEタイプかどうか
     /// 
     /// # Arguments
-    /// * `url_str` - エンコードされたURL文字列
+    /// * `mime_type` - MIMEタイプ
     /// 
     /// # Returns
-    /// * `String` - デコードされたURL文字列
-    fn decode_url(&self, url_str: &str) -> String {
-        // パーセントエンコーディングをデコード
-        match urlencoding::decode(url_str) {
-            Ok(decoded) => decoded.into_owned(),
-            Err(_) => {
-                warn!("URLのデコードに失敗しました: {}", url_str);
-                url_str.to_string()
-            }
-        }
+    /// * `bool` - `--sniff-json`が指定され、MIMEタイプがtext/plainまたは未指定で分類の対応付けも無い場合はtrue
+    fn sniffs_json(&self, mime_type: &str) -> bool {
+        self.payload_options.sniff_json
+            && is_unlabeled_text(mime_type)
+            && self.payload_options.mime_map.mapped(mime_type).is_none()
     }
-}
\ No newline at end of file
+}
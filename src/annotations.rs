@@ -0,0 +1,77 @@
+//! レビューのメモ（`--annotate`で指定するCSVファイル）の読み込みを実装
+//! 
+//! CSVの1列目にエントリ番号（HAR内の順序で1始まり）またはURL、2列目にメモを記述する
+
+use crate::error::{AnalyzerError, Result};
+use std::collections::HashMap;
+
+/// エントリ番号またはURLに対応付けたレビューのメモ
+#[derive(Debug, Clone, Default)]
+pub struct Annotations {
+    /// エントリ番号（1始まり）ごとのメモ
+    by_index: HashMap<usize, Vec<String>>,
+    /// URLごとのメモ
+    by_url: HashMap<String, Vec<String>>,
+}
+
+impl Annotations {
+    /// CSVファイルからメモを読み込む
+    /// 
+    /// 1行目の1列目がエントリ番号でもURLでもない場合は見出し行として読み飛ばす
+    /// 
+    /// # Arguments
+    /// * `path` - CSVファイルのパス
+    /// 
+    /// # Returns
+    /// * `Result<Annotations>` - 成功時はメモ、失敗時はエラー
+    pub fn load(path: &str) -> Result<Self> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_path(path)
+            .map_err(|e| AnalyzerError::InvalidArgument(format!("メモのファイルを開けません: {}: {}", path, e)))?;
+
+        let mut annotations = Annotations::default();
+        for (line, record) in reader.records().enumerate() {
+            let record = record.map_err(|e| {
+                AnalyzerError::InvalidArgument(format!("メモのファイルを解析できません: {}: {}", path, e))
+            })?;
+            let key = record.get(0).unwrap_or_default().trim();
+            let note = record.get(1).unwrap_or_default().trim();
+            let index = key.parse::<usize>().ok().filter(|index| *index > 0);
+            if line == 0 && index.is_none() && !key.contains("://") {
+                continue;
+            }
+            if key.is_empty() || note.is_empty() {
+                continue;
+            }
+            match index {
+                Some(index) => annotations.by_index.entry(index).or_default().push(note.to_string()),
+                None => annotations.by_url.entry(key.to_string()).or_default().push(note.to_string()),
+            }
+        }
+        Ok(annotations)
+    }
+
+    /// エントリに対応するメモを取得
+    /// 
+    /// URLはHARに記録された形式と、レポートに表示するデコード後の形式のいずれでも一致する
+    /// 
+    /// # Arguments
+    /// * `index` - エントリの位置（0始まり）
+    /// * `url` - リクエストURL
+    /// 
+    /// # Returns
+    /// * `Vec<&str>` - エントリ番号、URLの順のメモ
+    pub fn notes(&self, index: usize, url: &str) -> Vec<&str> {
+        let decoded = urlencoding::decode(url).ok().filter(|decoded| decoded != url);
+        self.by_index
+            .get(&(index + 1))
+            .into_iter()
+            .chain(self.by_url.get(url))
+            .chain(decoded.and_then(|decoded| self.by_url.get(decoded.as_ref())))
+            .flatten()
+            .map(String::as_str)
+            .collect()
+    }
+}
@@ -0,0 +1,135 @@
+//! 出力したファイル一式のZIPファイルへのまとめを実装
+//! 
+//! ワークブック・外部ファイルに退避したペイロード・依存関係グラフ等の出力を1つのZIPファイルにまとめ、
+//! ファイルごとのサイズとSHA-256ハッシュを記録したマニフェスト（`manifest.json`）を追加する
+
+use crate::error::{AnalyzerError, Result};
+use crate::run_info::DETERMINISTIC_GENERATED_AT;
+use chrono::Local;
+use log::{info, warn};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+/// マニフェストのファイル名
+pub const MANIFEST_NAME: &str = "manifest.json";
+
+/// マニフェスト
+#[derive(Debug, Serialize)]
+struct Manifest {
+    /// ツールのバージョン
+    tool_version: String,
+    /// 作成日時
+    generated_at: String,
+    /// まとめたファイル
+    files: Vec<ManifestFile>,
+}
+
+/// マニフェストに記録するファイル
+#[derive(Debug, Serialize)]
+struct ManifestFile {
+    /// ZIPファイル内のパス
+    path: String,
+    /// サイズ（バイト）
+    size: u64,
+    /// SHA-256ハッシュ
+    sha256: String,
+}
+
+/// ファイル一式をマニフェスト付きのZIPファイルにまとめる
+/// 
+/// ディレクトリを指定した場合は配下のファイルをディレクトリ名の下にまとめる
+/// 
+/// # Arguments
+/// * `archive_path` - 出力するZIPファイルのパス
+/// * `paths` - まとめるファイル・ディレクトリのパス
+/// * `deterministic` - マニフェストの作成日時を固定するかどうか
+/// 
+/// # Returns
+/// * `Result<usize>` - 成功時はまとめたファイルの数、失敗時はエラー
+pub fn create(archive_path: &str, paths: &[PathBuf], deterministic: bool) -> Result<usize> {
+    info!("出力したファイルをZIPファイルにまとめています: {}", archive_path);
+
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+    for path in paths {
+        let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        collect(path, &name, &mut files)?;
+    }
+
+    if let Some(parent) = Path::new(archive_path).parent() {
+        fs::create_dir_all(parent)
+            .map_err(AnalyzerError::io("出力ディレクトリの作成に失敗しました"))?;
+    }
+    let file = File::create(archive_path).map_err(AnalyzerError::io("ZIPファイルの作成に失敗しました"))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+
+    let mut names: HashSet<String> = HashSet::new();
+    let mut manifest = Manifest {
+        tool_version: format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+        generated_at: if deterministic {
+            DETERMINISTIC_GENERATED_AT.to_string()
+        } else {
+            Local::now().format("%Y-%m-%d %H:%M:%S%:z").to_string()
+        },
+        files: Vec::new(),
+    };
+    for (name, path) in files {
+        if name == MANIFEST_NAME || !names.insert(name.clone()) {
+            warn!("同じ名前のファイルがあるためZIPファイルに含めません: {}", path.display());
+            continue;
+        }
+        let content = fs::read(&path).map_err(AnalyzerError::io("ZIPファイルにまとめるファイルの読み込みに失敗しました"))?;
+        zip.start_file(name.as_str(), options)
+            .map_err(AnalyzerError::export("ZIPファイルの作成に失敗しました"))?;
+        zip.write_all(&content).map_err(AnalyzerError::io("ZIPファイルの作成に失敗しました"))?;
+        manifest.files.push(ManifestFile {
+            path: name,
+            size: content.len() as u64,
+            sha256: Sha256::digest(&content).iter().map(|b| format!("{:02x}", b)).collect(),
+        });
+    }
+
+    let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+    zip.start_file(MANIFEST_NAME, options)
+        .map_err(AnalyzerError::export("ZIPファイルの作成に失敗しました"))?;
+    zip.write_all(&manifest_json).map_err(AnalyzerError::io("ZIPファイルの作成に失敗しました"))?;
+    zip.finish().map_err(AnalyzerError::export("ZIPファイルの作成に失敗しました"))?;
+
+    info!("ZIPファイルの作成が完了しました: {} ({}件のファイル)", archive_path, manifest.files.len());
+    Ok(manifest.files.len())
+}
+
+/// まとめるファイルを集める
+/// 
+/// # Arguments
+/// * `path` - ファイル・ディレクトリのパス
+/// * `name` - ZIPファイル内のパス
+/// * `files` - (ZIPファイル内のパス, ファイルのパス)のリスト
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+fn collect(path: &Path, name: &str, files: &mut Vec<(String, PathBuf)>) -> Result<()> {
+    if !path.is_dir() {
+        if path.exists() {
+            files.push((name.to_string(), path.to_path_buf()));
+        }
+        return Ok(());
+    }
+    let mut entries: Vec<PathBuf> = fs::read_dir(path)
+        .map_err(AnalyzerError::io("出力ディレクトリの読み込みに失敗しました"))?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+    for entry in entries {
+        let child = entry.file_name().map(|child| child.to_string_lossy().into_owned()).unwrap_or_default();
+        collect(&entry, &format!("{}/{}", name, child), files)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,78 @@
+//! 同じ静的アセットへの繰り返しのリクエストの集約（`--collapse-assets`）を実装
+//! 
+//! SPAのキャプチャでは同じスクリプトや画像への200・304のGETが大量に記録されるため、
+//! URLとETagが同じエントリを最初の1件にまとめ、繰り返し回数を記録する
+
+use crate::har_types::{Entry, NameValue};
+use std::collections::HashMap;
+
+/// 集約されて出力しないエントリの繰り返し回数
+pub const COLLAPSED: usize = 0;
+
+/// エントリごとの繰り返し回数を求める
+/// 
+/// URLとETagが同じ、ステータスコードが200または304のGETリクエストを最初のエントリに集約する。
+/// 304のレスポンスにETagが無い場合は、リクエストのIf-None-Matchを検証されたETagとみなす
+/// 
+/// # Arguments
+/// * `entries` - HARエントリのリスト
+/// * `is_target` - 解析対象のエントリかどうかを判定する関数（対象外のエントリは集約しない）
+/// 
+/// # Returns
+/// * `Vec<usize>` - エントリと同じ順序の繰り返し回数（集約先のエントリは回数、集約されたエントリは`COLLAPSED`、それ以外は1）
+pub fn repeat_counts(entries: &[Entry], is_target: impl Fn(&Entry) -> bool) -> Vec<usize> {
+    let mut counts = vec![1; entries.len()];
+    let mut first_index: HashMap<(&str, &str), usize> = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let Some(etag) = asset_etag(entry) else {
+            continue;
+        };
+        if !is_target(entry) {
+            continue;
+        }
+        match first_index.get(&(entry.request.url.as_str(), etag)) {
+            Some(&first) => {
+                counts[first] += 1;
+                counts[index] = COLLAPSED;
+            }
+            None => {
+                first_index.insert((entry.request.url.as_str(), etag), index);
+            }
+        }
+    }
+    counts
+}
+
+/// 集約の対象となるエントリのETagを取得
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `Option<&str>` - ETag（GETの200・304でない場合やETagが無い場合はNone）
+fn asset_etag(entry: &Entry) -> Option<&str> {
+    if entry.request.method != "GET" || !matches!(entry.response.status, 200 | 304) {
+        return None;
+    }
+    header_value(&entry.response.headers, "etag").or_else(|| {
+        (entry.response.status == 304)
+            .then(|| header_value(&entry.request.headers, "if-none-match"))
+            .flatten()
+    })
+}
+
+/// ヘッダーの値を取得
+/// 
+/// # Arguments
+/// * `headers` - ヘッダーのリスト
+/// * `name` - ヘッダー名（小文字）
+/// 
+/// # Returns
+/// * `Option<&str>` - ヘッダーの値（無い場合や空の場合はNone）
+fn header_value<'a>(headers: &'a [NameValue], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case(name))
+        .map(|header| header.value.trim())
+        .filter(|value| !value.is_empty())
+}
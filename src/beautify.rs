@@ -0,0 +1,334 @@
+//! 圧縮（minify）されたJavaScript・CSSのレスポンスボディの整形を実装
+//! 
+//! 数MBが1行に詰め込まれたボディはExcelのセルの表示を崩すため、一定の文字数で折り返すか、
+//! 括弧とセミコロンの位置で改行・インデントして読める形にする。レポートでの確認用の整形であり、実行できる形は保証しない
+
+use crate::body_kind::BodyKind;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// 圧縮されたボディとみなす最長の行の文字数（これ以下のボディは元の改行を保つためそのまま出力する）
+const MINIFIED_LINE_CHARS: usize = 500;
+
+/// 折り返す行の文字数
+const WRAP_CHARS: usize = 120;
+
+/// 直後の`/`を正規表現リテラルの開始とみなすキーワード
+const REGEX_KEYWORDS: &[&str] = &[
+    "return", "typeof", "instanceof", "in", "of", "new", "delete", "void", "throw", "case", "do", "else", "yield", "await",
+];
+
+/// インデントの幅
+const INDENT: &str = "  ";
+
+/// JavaScript・CSSのレスポンスボディの出力方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsCssMode {
+    /// 一定の文字数で折り返す
+    Wrap,
+    /// 改行とインデントで整形する
+    Pretty,
+    /// そのまま出力
+    #[default]
+    Full,
+}
+
+impl JsCssMode {
+    /// `--js-css-mode`で指定する名前から出力方法を取得
+    /// 
+    /// # Arguments
+    /// * `name` - 出力方法の名前
+    /// 
+    /// # Returns
+    /// * `Option<JsCssMode>` - 対応する出力方法
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "wrap" => Some(JsCssMode::Wrap),
+            "pretty" => Some(JsCssMode::Pretty),
+            "full" => Some(JsCssMode::Full),
+            _ => None,
+        }
+    }
+}
+
+/// JavaScript・CSSを出力方法に従って整形
+/// 
+/// # Arguments
+/// * `text` - ボディ
+/// * `kind` - ボディの種類（JavaScript・CSS以外はそのまま返す）
+/// * `mode` - 出力方法
+/// 
+/// # Returns
+/// * `String` - 整形したテキスト（圧縮されていないボディはそのまま）
+pub fn format(text: &str, kind: BodyKind, mode: JsCssMode) -> String {
+    if !matches!(kind, BodyKind::JavaScript | BodyKind::Css) || !is_minified(text) {
+        return text.to_string();
+    }
+    match mode {
+        JsCssMode::Wrap => wrap(text),
+        JsCssMode::Pretty => pretty(text, kind == BodyKind::JavaScript),
+        JsCssMode::Full => text.to_string(),
+    }
+}
+
+/// 圧縮されたボディかどうか
+/// 
+/// # Arguments
+/// * `text` - ボディ
+/// 
+/// # Returns
+/// * `bool` - 最長の行が`MINIFIED_LINE_CHARS`文字を超える場合はtrue
+fn is_minified(text: &str) -> bool {
+    text.lines().any(|line| line.len() > MINIFIED_LINE_CHARS && line.chars().count() > MINIFIED_LINE_CHARS)
+}
+
+/// 長い行を折り返す
+/// 
+/// `WRAP_CHARS`文字に達した行を、それまでの最後の`;`・`,`・`{`・`}`・空白の直後で改行する（無い場合はその位置で改行する）
+/// 
+/// # Arguments
+/// * `text` - ボディ
+/// 
+/// # Returns
+/// * `String` - 折り返したテキスト
+fn wrap(text: &str) -> String {
+    let mut wrapped = String::with_capacity(text.len() + text.len() / WRAP_CHARS + 1);
+    for (number, line) in text.lines().enumerate() {
+        if number > 0 {
+            wrapped.push('\n');
+        }
+        let mut start = 0;
+        let mut chars = 0;
+        let mut last_break = None;
+        for (position, c) in line.char_indices() {
+            let next = position + c.len_utf8();
+            chars += 1;
+            if matches!(c, ';' | ',' | '{' | '}' | ' ') {
+                last_break = Some(next);
+            }
+            if chars >= WRAP_CHARS {
+                let end = last_break.unwrap_or(next);
+                wrapped.push_str(line[start..end].trim_end());
+                wrapped.push('\n');
+                chars = line[end..next].chars().count();
+                start = end;
+                last_break = None;
+            }
+        }
+        wrapped.push_str(&line[start..]);
+    }
+    wrapped
+}
+
+/// 字句の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lexeme {
+    /// コード
+    Code,
+    /// 文字列（引用符）
+    Str(char),
+    /// 行コメント（JavaScriptのみ）
+    LineComment,
+    /// ブロックコメント
+    BlockComment,
+    /// 正規表現リテラル（JavaScriptのみ、文字クラスの内側かどうか）
+    Regex { class: bool },
+}
+
+/// 改行とインデントで整形
+/// 
+/// 文字列・コメント・正規表現リテラルの内側を除き、`{`の後・`;`の後（括弧の内側を除く）・`}`の前後で改行し、
+/// `{}`の入れ子の深さでインデントする
+/// 
+/// # Arguments
+/// * `text` - ボディ
+/// * `script` - JavaScriptの場合はtrue（CSSの場合はfalse）
+/// 
+/// # Returns
+/// * `String` - 整形したテキスト
+fn pretty(text: &str, script: bool) -> String {
+    let mut formatted = String::with_capacity(text.len() + text.len() / 4);
+    let mut lexeme = Lexeme::Code;
+    let mut indent = 0usize;
+    let mut parens = 0usize;
+    let mut pending_newline = false;
+    let mut escaped = false;
+    let mut chars = text.chars().peekable();
+    // 正規表現リテラルの判定に使用する、直前の空白以外の文字
+    let mut previous = None;
+
+    while let Some(c) = chars.next() {
+        match lexeme {
+            Lexeme::Code => {
+                if c.is_whitespace() {
+                    // 元の改行は文の区切りの可能性があるため保つ
+                    if c == '\n' {
+                        pending_newline = true;
+                    } else if !pending_newline && !formatted.ends_with([' ', '\n']) && !formatted.is_empty() {
+                        formatted.push(' ');
+                    }
+                    continue;
+                }
+                // `},`・`}();`・`} else {`等は閉じ括弧と同じ行に続ける
+                if pending_newline
+                    && formatted.ends_with('}')
+                    && (matches!(c, ';' | ',' | '(' | ')' | ']' | '.') || continues_block(c, &chars))
+                {
+                    pending_newline = false;
+                    if c.is_alphabetic() {
+                        formatted.push(' ');
+                    }
+                }
+                if pending_newline {
+                    newline(&mut formatted, indent);
+                    pending_newline = false;
+                }
+                match c {
+                    '\'' | '"' | '`' => lexeme = Lexeme::Str(c),
+                    '/' if chars.peek() == Some(&'*') => lexeme = Lexeme::BlockComment,
+                    '/' if script && chars.peek() == Some(&'/') => lexeme = Lexeme::LineComment,
+                    '/' if script && starts_regex(previous, &formatted) => {
+                        lexeme = Lexeme::Regex { class: false };
+                    }
+                    '(' | '[' => parens += 1,
+                    ')' | ']' => parens = parens.saturating_sub(1),
+                    _ => {}
+                }
+                match c {
+                    '{' if chars.peek() == Some(&'}') => {
+                        chars.next();
+                        formatted.push_str("{}");
+                        previous = Some('}');
+                        continue;
+                    }
+                    '{' => {
+                        formatted.push('{');
+                        indent += 1;
+                        pending_newline = true;
+                    }
+                    '}' => {
+                        indent = indent.saturating_sub(1);
+                        newline(&mut formatted, indent);
+                        formatted.push('}');
+                        pending_newline = true;
+                    }
+                    ';' => {
+                        formatted.push(';');
+                        pending_newline = parens == 0;
+                    }
+                    _ => formatted.push(c),
+                }
+                previous = Some(c);
+            }
+            Lexeme::Str(quote) => {
+                formatted.push(c);
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == quote || (c == '\n' && quote != '`') {
+                    lexeme = Lexeme::Code;
+                    previous = Some(c);
+                }
+            }
+            Lexeme::LineComment => {
+                if c == '\n' {
+                    lexeme = Lexeme::Code;
+                    pending_newline = true;
+                } else {
+                    formatted.push(c);
+                }
+            }
+            Lexeme::BlockComment => {
+                formatted.push(c);
+                if c == '*' && chars.peek() == Some(&'/') {
+                    formatted.push('/');
+                    chars.next();
+                    lexeme = Lexeme::Code;
+                }
+            }
+            Lexeme::Regex { class } => {
+                formatted.push(c);
+                if escaped {
+                    escaped = false;
+                    continue;
+                }
+                lexeme = match c {
+                    '\\' => {
+                        escaped = true;
+                        lexeme
+                    }
+                    '[' => Lexeme::Regex { class: true },
+                    ']' => Lexeme::Regex { class: false },
+                    '/' if !class => {
+                        previous = Some('/');
+                        Lexeme::Code
+                    }
+                    '\n' => Lexeme::Code,
+                    _ => lexeme,
+                };
+            }
+        }
+    }
+    formatted.truncate(formatted.trim_end().len());
+    formatted
+}
+
+/// `/`が正規表現リテラルの開始かどうか
+/// 
+/// # Arguments
+/// * `previous` - 直前の空白以外の文字
+/// * `formatted` - 整形中のテキスト
+/// 
+/// # Returns
+/// * `bool` - 先頭・演算子・開き括弧・区切り文字、または`return`等のキーワードの後の場合はtrue（それ以外は除算とみなす）
+fn starts_regex(previous: Option<char>, formatted: &str) -> bool {
+    let Some(previous) = previous else {
+        return true;
+    };
+    if "(,=:[!&|?{};+-*%<>~^".contains(previous) {
+        return true;
+    }
+    let word = formatted
+        .trim_end()
+        .rsplit(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '$')))
+        .next()
+        .unwrap_or_default();
+    REGEX_KEYWORDS.contains(&word)
+}
+
+/// 閉じ括弧の後に続く`else`・`catch`・`finally`・`while`かどうか
+/// 
+/// # Arguments
+/// * `c` - 現在の文字
+/// * `rest` - 現在の文字より後の文字
+/// 
+/// # Returns
+/// * `bool` - 現在の文字から始まる単語がいずれかのキーワードの場合はtrue
+fn continues_block(c: char, rest: &Peekable<Chars>) -> bool {
+    if !c.is_ascii_alphabetic() {
+        return false;
+    }
+    let word: String = std::iter::once(c)
+        .chain(rest.clone().take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '$')))
+        .collect();
+    matches!(word.as_str(), "else" | "catch" | "finally" | "while")
+}
+
+/// 末尾の空白を除いて改行し、インデントする
+/// 
+/// # Arguments
+/// * `formatted` - 整形中のテキスト
+/// * `indent` - インデントの深さ
+fn newline(formatted: &mut String, indent: usize) {
+    formatted.truncate(formatted.trim_end_matches([' ', '\t']).len());
+    if formatted.is_empty() {
+        return;
+    }
+    if formatted.ends_with('\n') {
+        // 空白のみの行を作らない
+        formatted.truncate(formatted.trim_end().len());
+    }
+    formatted.push('\n');
+    formatted.push_str(&INDENT.repeat(indent));
+}
@@ -0,0 +1,96 @@
+//! バイナリ形式（MessagePack・CBOR）のペイロードのデコードを実装
+//! 
+//! JSONと同じように読めるよう、デコードした値を整形したJSONとして出力する
+
+use base64::{Engine as _, engine::general_purpose};
+use ciborium::Value as CborValue;
+use serde_json::{Map, Value, json};
+
+/// バイナリ形式の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryFormat {
+    /// MessagePack
+    MessagePack,
+    /// CBOR
+    Cbor,
+}
+
+impl BinaryFormat {
+    /// MIMEタイプからバイナリ形式を判定
+    /// 
+    /// # Arguments
+    /// * `mime_type` - MIMEタイプ
+    /// 
+    /// # Returns
+    /// * `Option<BinaryFormat>` - バイナリ形式（MessagePack・CBORでない場合はNone）
+    pub fn from_mime(mime_type: &str) -> Option<Self> {
+        let mime_type = mime_type.to_ascii_lowercase();
+        let essence = mime_type.split(';').next().unwrap_or_default().trim();
+        if essence.ends_with("msgpack") || essence.ends_with("messagepack") {
+            Some(BinaryFormat::MessagePack)
+        } else if essence.ends_with("/cbor") || essence.ends_with("+cbor") {
+            Some(BinaryFormat::Cbor)
+        } else {
+            None
+        }
+    }
+}
+
+/// バイナリ形式のボディを整形したJSONに変換
+/// 
+/// # Arguments
+/// * `bytes` - ボディのバイト列
+/// * `format` - バイナリ形式
+/// 
+/// # Returns
+/// * `Option<String>` - 整形したJSON（デコードできない場合はNone）
+pub fn to_json(bytes: &[u8], format: BinaryFormat) -> Option<String> {
+    // MessagePackもCBORの値として読み込み、文字列以外のキーやバイト列を同じ規則でJSONに変換する
+    let value: CborValue = match format {
+        BinaryFormat::MessagePack => rmp_serde::from_slice(bytes).ok()?,
+        BinaryFormat::Cbor => ciborium::from_reader(bytes).ok()?,
+    };
+    serde_json::to_string_pretty(&cbor_to_json(value)).ok()
+}
+
+/// CBORの値をJSONの値に変換
+/// 
+/// バイト列はBase64の文字列、文字列以外のマップのキーはJSONとして表記した文字列、タグはタグ内の値に変換する
+/// 
+/// # Arguments
+/// * `value` - CBORの値
+/// 
+/// # Returns
+/// * `Value` - JSONの値
+fn cbor_to_json(value: CborValue) -> Value {
+    match value {
+        CborValue::Null => Value::Null,
+        CborValue::Bool(b) => json!(b),
+        CborValue::Integer(i) => {
+            let i = i128::from(i);
+            i64::try_from(i)
+                .map(|i| json!(i))
+                .or_else(|_| u64::try_from(i).map(|u| json!(u)))
+                .unwrap_or_else(|_| json!(i.to_string()))
+        }
+        CborValue::Float(f) => json!(f),
+        CborValue::Text(text) => json!(text),
+        CborValue::Bytes(bytes) => json!(general_purpose::STANDARD.encode(bytes)),
+        CborValue::Tag(_, inner) => cbor_to_json(*inner),
+        CborValue::Array(items) => Value::Array(items.into_iter().map(cbor_to_json).collect()),
+        CborValue::Map(entries) => {
+            let map: Map<String, Value> = entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        CborValue::Text(text) => text,
+                        other => cbor_to_json(other).to_string(),
+                    };
+                    (key, cbor_to_json(value))
+                })
+                .collect();
+            Value::Object(map)
+        }
+        _ => Value::Null,
+    }
+}
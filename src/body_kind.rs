@@ -0,0 +1,260 @@
+//! レスポンスボディの内容の種類の判定（`body-kind`列）を実装
+//! 
+//! MIMEタイプは誤っていたり省略されていたりするため、ボディの先頭の内容からJSON・XML・HTML・JavaScript・CSS・
+//! テキスト・バイナリを判定する（コンテンツスニッフィング）。判定できない場合はMIMEタイプに従う。
+//! 条件式の絞り込み・キャッシュの規則・圧縮の見積もり・レンダリングのブロックの判定はこの種類を使用する
+
+use crate::har_types::{Content, Entry};
+use base64::Engine;
+use std::borrow::Cow;
+
+/// 内容を判定するボディの先頭のバイト数
+const SNIFF_BYTES: usize = 4096;
+
+/// JavaScriptの先頭によく現れる記述
+const SCRIPT_PREFIXES: &[&str] = &[
+    "(function", "!function", "(()=>", "(() =>", "\"use strict\"", "'use strict'", "var ", "let ", "const ",
+    "function ", "import ", "export ", "window.", "self.", "globalThis.", "define(", "webpackjsonp", "/*!",
+];
+
+/// レスポンスボディの内容の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BodyKind {
+    /// ボディが無い
+    #[default]
+    Empty,
+    /// JSON
+    Json,
+    /// XML（SVGを含む）
+    Xml,
+    /// HTML
+    Html,
+    /// JavaScript（JSONPを含む）
+    JavaScript,
+    /// CSS
+    Css,
+    /// その他のテキスト
+    Text,
+    /// バイナリ
+    Binary,
+}
+
+impl BodyKind {
+    /// エントリのレスポンスボディの種類を判定
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `BodyKind` - ボディの種類
+    pub fn of_response(entry: &Entry) -> Self {
+        Self::detect(&entry.response.content)
+    }
+
+    /// 記録されたボディの種類を判定
+    /// 
+    /// ボディが記録されていない場合（サイズのみの場合を含む）はMIMEタイプから判定する
+    /// 
+    /// # Arguments
+    /// * `content` - レスポンスのコンテンツ
+    /// 
+    /// # Returns
+    /// * `BodyKind` - ボディの種類
+    pub fn detect(content: &Content) -> Self {
+        let Some(text) = content.text.as_deref().filter(|text| !text.is_empty()) else {
+            return if content.size > 0 { Self::from_mime(&content.mime_type) } else { BodyKind::Empty };
+        };
+        let body: Cow<[u8]> = match content.encoding.as_deref() {
+            Some(encoding) if encoding.eq_ignore_ascii_case("base64") => {
+                match base64::engine::general_purpose::STANDARD.decode(text.trim()) {
+                    Ok(body) => Cow::Owned(body),
+                    Err(_) => return Self::from_mime(&content.mime_type),
+                }
+            }
+            _ => Cow::Borrowed(text.as_bytes()),
+        };
+        Self::sniff(&body, &content.mime_type)
+    }
+
+    /// ボディの先頭の内容から種類を判定
+    /// 
+    /// JSON・HTML・XMLは内容の構造を優先し、JavaScript・CSSはMIMEタイプが一致すればそれに従い、
+    /// それ以外は先頭の記述から推定する
+    /// 
+    /// # Arguments
+    /// * `body` - デコード後のボディ
+    /// * `mime_type` - MIMEタイプ
+    /// 
+    /// # Returns
+    /// * `BodyKind` - ボディの種類
+    pub fn sniff(body: &[u8], mime_type: &str) -> Self {
+        if body.is_empty() {
+            return BodyKind::Empty;
+        }
+        let head = &body[..body.len().min(SNIFF_BYTES)];
+        if is_binary(head) {
+            return BodyKind::Binary;
+        }
+        let head = String::from_utf8_lossy(head);
+        let head = head.trim_start_matches('\u{feff}').trim_start();
+        let lower = head.to_ascii_lowercase();
+        let by_mime = Self::from_mime(mime_type);
+
+        if (head.starts_with('{') || head.starts_with('['))
+            && serde_json::from_slice::<serde::de::IgnoredAny>(body).is_ok()
+        {
+            return BodyKind::Json;
+        }
+        if lower.starts_with("<!doctype html") || lower.starts_with("<html") {
+            return BodyKind::Html;
+        }
+        if lower.starts_with("<?xml") || lower.starts_with("<svg") || lower.starts_with("<rss") || lower.starts_with("<feed") {
+            return BodyKind::Xml;
+        }
+        if head.starts_with('<') {
+            // 断片のHTML（<div>等）と独自のXMLはMIMEタイプか代表的なタグで区別する
+            let html_tags = ["<head", "<body", "<div", "<script", "<meta", "<link", "<p>", "<span", "<table"];
+            return match by_mime {
+                BodyKind::Xml => BodyKind::Xml,
+                BodyKind::Html => BodyKind::Html,
+                _ if html_tags.iter().any(|tag| lower.contains(tag)) => BodyKind::Html,
+                _ => BodyKind::Xml,
+            };
+        }
+        if matches!(by_mime, BodyKind::JavaScript | BodyKind::Css) {
+            return by_mime;
+        }
+        if SCRIPT_PREFIXES.iter().any(|prefix| lower.starts_with(prefix)) || is_jsonp(head) {
+            return BodyKind::JavaScript;
+        }
+        if looks_like_css(head) {
+            return BodyKind::Css;
+        }
+        BodyKind::Text
+    }
+
+    /// MIMEタイプから種類を判定
+    /// 
+    /// # Arguments
+    /// * `mime_type` - MIMEタイプ（charset等のパラメータを含んでもよい）
+    /// 
+    /// # Returns
+    /// * `BodyKind` - ボディの種類（MIMEタイプが無い場合はEmpty）
+    pub fn from_mime(mime_type: &str) -> Self {
+        let mime_type = mime_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        if mime_type.is_empty() {
+            BodyKind::Empty
+        } else if mime_type.contains("javascript") || mime_type.contains("ecmascript") {
+            BodyKind::JavaScript
+        } else if mime_type.contains("css") {
+            BodyKind::Css
+        } else if mime_type.ends_with("json") || mime_type.contains("+json") {
+            BodyKind::Json
+        } else if mime_type.contains("html") {
+            BodyKind::Html
+        } else if mime_type.ends_with("xml") || mime_type.contains("+xml") {
+            BodyKind::Xml
+        } else if mime_type.starts_with("text/") {
+            BodyKind::Text
+        } else {
+            BodyKind::Binary
+        }
+    }
+
+    /// テキストの種類かどうか
+    /// 
+    /// # Returns
+    /// * `bool` - JSON・XML・HTML・JavaScript・CSS・テキストの場合はtrue
+    pub fn is_text(&self) -> bool {
+        !matches!(self, BodyKind::Empty | BodyKind::Binary)
+    }
+
+    /// 列に表示する名前（条件式の`body-kind`の値）
+    /// 
+    /// # Returns
+    /// * `&'static str` - 名前（ボディが無い場合は空文字列）
+    pub fn label(&self) -> &'static str {
+        match self {
+            BodyKind::Empty => "",
+            BodyKind::Json => "JSON",
+            BodyKind::Xml => "XML",
+            BodyKind::Html => "HTML",
+            BodyKind::JavaScript => "JavaScript",
+            BodyKind::Css => "CSS",
+            BodyKind::Text => "Text",
+            BodyKind::Binary => "Binary",
+        }
+    }
+}
+
+/// ボディの先頭がバイナリかどうか
+/// 
+/// # Arguments
+/// * `head` - ボディの先頭
+/// 
+/// # Returns
+/// * `bool` - NULを含む、またはUTF-8として不正でタブ・改行以外の制御文字を含む場合はtrue
+fn is_binary(head: &[u8]) -> bool {
+    if head.contains(&0) {
+        return true;
+    }
+    // 先頭の切り出しで途中になった最後の文字はUTF-8の誤りとみなさない
+    match std::str::from_utf8(head) {
+        Ok(_) => false,
+        Err(e) if e.error_len().is_none() => false,
+        Err(_) => head
+            .iter()
+            .any(|byte| byte.is_ascii_control() && !matches!(byte, b'\t' | b'\n' | b'\r' | 0x0c)),
+    }
+}
+
+/// JSONP（`callback({...})`）かどうか
+/// 
+/// # Arguments
+/// * `head` - 先頭の空白を除いたボディの先頭
+/// 
+/// # Returns
+/// * `bool` - 識別子の直後に`(`・`{`または`[`が続く場合はtrue
+fn is_jsonp(head: &str) -> bool {
+    let Some(open) = head.find('(') else {
+        return false;
+    };
+    let callback = &head[..open];
+    !callback.is_empty()
+        && callback.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '$' | '.'))
+        && head[open + 1..].trim_start().starts_with(['{', '['])
+}
+
+/// CSSのように見えるかどうか
+/// 
+/// # Arguments
+/// * `head` - 先頭の空白を除いたボディの先頭
+/// 
+/// # Returns
+/// * `bool` - `@charset`等のアットルールで始まる、または最初の`{`の前がセレクタで`{}`の中に`プロパティ:値`がある場合はtrue
+fn looks_like_css(head: &str) -> bool {
+    let head = head.trim_start_matches(|c: char| c.is_whitespace());
+    let head = match head.strip_prefix("/*") {
+        Some(rest) => rest.split_once("*/").map_or("", |(_, rest)| rest.trim_start()),
+        None => head,
+    };
+    if ["@charset", "@import", "@font-face", "@media", ":root"].iter().any(|rule| head.starts_with(rule)) {
+        return true;
+    }
+    let Some((selector, rest)) = head.split_once('{') else {
+        return false;
+    };
+    let Some((block, _)) = rest.split_once('}') else {
+        return false;
+    };
+    !selector.trim().is_empty()
+        && !selector.contains(['(', ')', ';', '=', '"'])
+        && block.split(';').next().is_some_and(|declaration| {
+            declaration
+                .split_once(':')
+                .is_some_and(|(property, _)| {
+                    let property = property.trim();
+                    !property.is_empty() && property.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                })
+        })
+}
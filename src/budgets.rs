@@ -0,0 +1,199 @@
+//! リクエストヘッダー・Cookie・JSONのレスポンスボディのサイズの予算の検査を実装
+//! 
+//! 指定した上限を超えたエントリをBudgetsシートに出力し、`--fail-on-budget`では終了コードで検出できるようにする
+
+use crate::analyzer::HarAnalyzer;
+use crate::har_types::{Entry, NameValue};
+use crate::mime_map::MimeMap;
+
+/// サイズの予算（Noneの項目は検査しない）
+#[derive(Debug, Clone, Default)]
+pub struct Budgets {
+    /// リクエストヘッダーの合計サイズの上限（バイト）
+    pub request_header_bytes: Option<u64>,
+    /// 1件のCookie（名前と値）のサイズの上限（バイト）
+    pub cookie_bytes: Option<u64>,
+    /// JSONのレスポンスボディのサイズの上限（バイト）
+    pub json_response_bytes: Option<u64>,
+}
+
+impl Budgets {
+    /// 検査する予算が無いかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - 全ての上限が指定されていない場合はtrue
+    pub fn is_empty(&self) -> bool {
+        self.request_header_bytes.is_none() && self.cookie_bytes.is_none() && self.json_response_bytes.is_none()
+    }
+
+    /// 1件のエントリを予算と照合
+    /// 
+    /// # Arguments
+    /// * `index` - エントリの位置（0始まり）
+    /// * `entry` - HARエントリ
+    /// * `mime_map` - MIMEタイプの分類（JSONのレスポンスの判定に使用）
+    /// 
+    /// # Returns
+    /// * `Vec<BudgetViolation>` - 超過した予算（超過していない場合は空）
+    fn check(&self, index: usize, entry: &Entry, mime_map: &MimeMap) -> Vec<BudgetViolation> {
+        let violation = |kind: BudgetKind, actual: u64, limit: u64, detail: String| BudgetViolation {
+            entry_number: index + 1,
+            url: entry.request.url.clone(),
+            kind,
+            actual,
+            limit,
+            detail,
+        };
+
+        let mut violations = Vec::new();
+        if let Some(limit) = self.request_header_bytes {
+            let size = request_header_size(entry);
+            if size > limit {
+                violations.push(violation(
+                    BudgetKind::RequestHeaders,
+                    size,
+                    limit,
+                    format!("{}個のヘッダー", entry.request.headers.len()),
+                ));
+            }
+        }
+        if let Some(limit) = self.cookie_bytes {
+            for (source, pair) in cookie_pairs(entry) {
+                let size = pair.len() as u64;
+                if size > limit {
+                    let name = pair.split_once('=').map_or(pair, |(name, _)| name).trim();
+                    violations.push(violation(BudgetKind::Cookie, size, limit, format!("{}: {}", source, name)));
+                }
+            }
+        }
+        if let Some(limit) = self.json_response_bytes
+            && mime_map.is_json(&entry.response.content.mime_type)
+        {
+            let size = HarAnalyzer::response_size(entry).max(0) as u64;
+            if size > limit {
+                violations.push(violation(
+                    BudgetKind::JsonResponse,
+                    size,
+                    limit,
+                    entry.response.content.mime_type.clone(),
+                ));
+            }
+        }
+        violations
+    }
+}
+
+/// 予算の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetKind {
+    /// リクエストヘッダーの合計サイズ
+    RequestHeaders,
+    /// 1件のCookieのサイズ
+    Cookie,
+    /// JSONのレスポンスボディのサイズ
+    JsonResponse,
+}
+
+impl BudgetKind {
+    /// シートに表示する種類の名前
+    /// 
+    /// # Returns
+    /// * `&'static str` - 種類の名前
+    pub fn label(&self) -> &'static str {
+        match self {
+            BudgetKind::RequestHeaders => "リクエストヘッダー",
+            BudgetKind::Cookie => "Cookie",
+            BudgetKind::JsonResponse => "JSONのレスポンスボディ",
+        }
+    }
+}
+
+/// 予算の超過
+#[derive(Debug, Clone)]
+pub struct BudgetViolation {
+    /// エントリ番号（HAR内の順序で1始まり）
+    pub entry_number: usize,
+    /// リクエストURL
+    pub url: String,
+    /// 予算の種類
+    pub kind: BudgetKind,
+    /// 実際のサイズ（バイト）
+    pub actual: u64,
+    /// 上限（バイト）
+    pub limit: u64,
+    /// 超過した対象の説明（Cookieの名前等）
+    pub detail: String,
+}
+
+/// エントリを予算と照合
+/// 
+/// # Arguments
+/// * `entries` - (エントリの位置（0始まり）, HARエントリ)のイテレータ
+/// * `budgets` - サイズの予算
+/// * `mime_map` - MIMEタイプの分類（JSONのレスポンスの判定に使用）
+/// 
+/// # Returns
+/// * `Vec<BudgetViolation>` - エントリ順の超過
+pub fn check<'a>(
+    entries: impl Iterator<Item = (usize, &'a Entry)>,
+    budgets: &Budgets,
+    mime_map: &MimeMap,
+) -> Vec<BudgetViolation> {
+    if budgets.is_empty() {
+        return Vec::new();
+    }
+    entries.flat_map(|(index, entry)| budgets.check(index, entry, mime_map)).collect()
+}
+
+/// リクエストヘッダーの合計サイズを取得
+/// 
+/// `headersSize`が記録されていない場合は「名前: 値」と改行の長さの合計とする
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `u64` - サイズ（バイト）
+fn request_header_size(entry: &Entry) -> u64 {
+    if entry.request.headers_size > 0 {
+        return entry.request.headers_size as u64;
+    }
+    entry
+        .request
+        .headers
+        .iter()
+        .map(|header| (header.name.len() + header.value.len() + 4) as u64)
+        .sum()
+}
+
+/// リクエストのCookieヘッダーとレスポンスのSet-Cookieヘッダーの各Cookieを取得
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `Vec<(&'static str, &str)>` - (ヘッダーの名前, Cookieの「名前=値」)のリスト
+fn cookie_pairs(entry: &Entry) -> Vec<(&'static str, &str)> {
+    let request = header_values(&entry.request.headers, "cookie")
+        .flat_map(|value| value.split(';'))
+        .map(|pair| ("Cookie", pair.trim()));
+    // Set-Cookieヘッダーの属性（Path・Expires等）はブラウザが保持するCookieのサイズに含まれない
+    let response = header_values(&entry.response.headers, "set-cookie")
+        .map(|value| ("Set-Cookie", value.split(';').next().unwrap_or_default().trim()));
+    request.chain(response).filter(|(_, pair)| !pair.is_empty()).collect()
+}
+
+/// 指定した名前のヘッダーの値を取得
+/// 
+/// # Arguments
+/// * `headers` - ヘッダーのリスト
+/// * `name` - ヘッダーの名前（大文字・小文字を区別しない）
+/// 
+/// # Returns
+/// * `impl Iterator<Item = &str>` - ヘッダーの値のイテレータ
+fn header_values<'a>(headers: &'a [NameValue], name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    headers
+        .iter()
+        .filter(move |header| header.name.eq_ignore_ascii_case(name))
+        .map(|header| header.value.as_str())
+}
@@ -0,0 +1,267 @@
+//! 営業時間の内外によるリクエストの分類（`--business-hours`）を実装
+//! 
+//! 指定したタイムゾーンの曜日・時刻の範囲でリクエストの開始時刻を営業時間内・時間外に分け、
+//! 営業時間列とサマリーの集計に出力する。キャプチャを当番の時間帯と突き合わせる運用向け
+
+use crate::error::{AnalyzerError, Result};
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveTime, TimeZone, Weekday};
+use chrono_tz::Tz;
+
+/// 曜日を指定しない場合の営業日（月曜日から金曜日）
+const DEFAULT_DAYS: [bool; 7] = [true, true, true, true, true, false, false];
+
+/// 営業時間を判定するタイムゾーン
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BusinessTimezone {
+    /// 実行環境のタイムゾーン
+    Local,
+    /// IANAのタイムゾーン（例: Asia/Tokyo）
+    Named(Tz),
+    /// UTCからの固定の時差（例: +09:00）
+    Fixed(FixedOffset),
+}
+
+impl BusinessTimezone {
+    /// タイムゾーンの指定を解析
+    /// 
+    /// # Arguments
+    /// * `spec` - IANAのタイムゾーン名（`Asia/Tokyo`・`UTC`）またはUTCからの時差（`+09:00`）
+    /// 
+    /// # Returns
+    /// * `Result<BusinessTimezone>` - 成功時はタイムゾーン、失敗時はエラー
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        if spec.eq_ignore_ascii_case("local") {
+            return Ok(BusinessTimezone::Local);
+        }
+        if let Ok(tz) = spec.parse::<Tz>() {
+            return Ok(BusinessTimezone::Named(tz));
+        }
+        spec.parse::<FixedOffset>().map(BusinessTimezone::Fixed).map_err(|_| {
+            AnalyzerError::InvalidArgument(format!(
+                "タイムゾーンが不正です（Asia/Tokyo等のIANAのタイムゾーン名、または+09:00等のUTCからの時差）: {}",
+                spec
+            ))
+        })
+    }
+
+    /// 日時をこのタイムゾーンの曜日と時刻に変換
+    /// 
+    /// # Arguments
+    /// * `datetime` - 日時
+    /// 
+    /// # Returns
+    /// * `(Weekday, NaiveTime)` - 曜日と時刻
+    fn local_time(&self, datetime: &DateTime<FixedOffset>) -> (Weekday, NaiveTime) {
+        let naive = match self {
+            BusinessTimezone::Local => datetime.with_timezone(&Local).naive_local(),
+            BusinessTimezone::Named(tz) => datetime.with_timezone(tz).naive_local(),
+            BusinessTimezone::Fixed(offset) => offset.from_utc_datetime(&datetime.naive_utc()).naive_local(),
+        };
+        (naive.weekday(), naive.time())
+    }
+
+    /// 表示名
+    /// 
+    /// # Returns
+    /// * `String` - タイムゾーン名
+    pub fn name(&self) -> String {
+        match self {
+            BusinessTimezone::Local => "実行環境のタイムゾーン".to_string(),
+            BusinessTimezone::Named(tz) => tz.name().to_string(),
+            BusinessTimezone::Fixed(offset) => offset.to_string(),
+        }
+    }
+}
+
+/// 営業時間の定義
+#[derive(Debug, Clone, PartialEq)]
+pub struct BusinessHours {
+    /// 月曜日から日曜日の順の営業日
+    days: [bool; 7],
+    /// 開始時刻
+    start: NaiveTime,
+    /// 終了時刻（開始時刻以前の場合は翌日の時刻とみなす）
+    end: NaiveTime,
+    /// 判定するタイムゾーン
+    timezone: BusinessTimezone,
+}
+
+impl BusinessHours {
+    /// 営業時間の指定を解析
+    /// 
+    /// # Arguments
+    /// * `spec` - `[曜日] 開始時刻-終了時刻`（例: `Mon-Fri 09:00-18:00`・`Sat,Sun 10:00-17:00`・`22:00-06:00`）。
+    ///   曜日を省略した場合は月曜日から金曜日
+    /// 
+    /// # Returns
+    /// * `Result<BusinessHours>` - 成功時は営業時間（タイムゾーンは実行環境のもの）、失敗時はエラー
+    pub fn parse(spec: &str) -> Result<Self> {
+        let invalid = |reason: &str| {
+            AnalyzerError::InvalidArgument(format!(
+                "営業時間の指定が不正です（例: Mon-Fri 09:00-18:00）: {}: {}",
+                spec, reason
+            ))
+        };
+        let mut parts = spec.split_whitespace();
+        let (days, hours) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(hours), None, None) => (DEFAULT_DAYS, hours),
+            (Some(days), Some(hours), None) => (Self::parse_days(days).map_err(|reason| invalid(&reason))?, hours),
+            _ => return Err(invalid("曜日と時刻の範囲を空白で区切って指定してください")),
+        };
+        let (start, end) = hours.split_once('-').ok_or_else(|| invalid("時刻の範囲を-で区切って指定してください"))?;
+        let parse_time = |time: &str| {
+            NaiveTime::parse_from_str(time, "%H:%M").map_err(|_| invalid(&format!("時刻はHH:MM形式で指定してください: {}", time)))
+        };
+        let (start, end) = (parse_time(start)?, parse_time(end)?);
+        if start == end {
+            return Err(invalid("開始時刻と終了時刻が同じです"));
+        }
+        Ok(BusinessHours { days, start, end, timezone: BusinessTimezone::Local })
+    }
+
+    /// 曜日の指定を解析
+    /// 
+    /// # Arguments
+    /// * `spec` - カンマ区切りの曜日または曜日の範囲（例: `Mon-Fri`・`Sat,Sun`・`Fri-Mon`）
+    /// 
+    /// # Returns
+    /// * `std::result::Result<[bool; 7], String>` - 月曜日から日曜日の順の営業日、失敗時は理由
+    fn parse_days(spec: &str) -> std::result::Result<[bool; 7], String> {
+        let parse_day = |day: &str| day.trim().parse::<Weekday>().map_err(|_| format!("曜日が不正です: {}", day));
+        let mut days = [false; 7];
+        for range in spec.split(',') {
+            let (first, last) = match range.split_once('-') {
+                Some((first, last)) => (parse_day(first)?, parse_day(last)?),
+                None => (parse_day(range)?, parse_day(range)?),
+            };
+            // 日曜日から月曜日のように週をまたぐ範囲も指定できる
+            let mut day = first;
+            loop {
+                days[day.num_days_from_monday() as usize] = true;
+                if day == last {
+                    break;
+                }
+                day = day.succ();
+            }
+        }
+        Ok(days)
+    }
+
+    /// 判定するタイムゾーンを指定
+    /// 
+    /// # Arguments
+    /// * `timezone` - タイムゾーン
+    /// 
+    /// # Returns
+    /// * `BusinessHours` - タイムゾーンを指定した営業時間
+    pub fn with_timezone(mut self, timezone: BusinessTimezone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// 日時が営業時間内かどうか
+    /// 
+    /// 終了時刻が開始時刻以前の場合（夜間の当番等）は、開始した日の営業日で判定する
+    /// 
+    /// # Arguments
+    /// * `datetime` - 日時
+    /// 
+    /// # Returns
+    /// * `bool` - 営業時間内の場合はtrue
+    pub fn contains(&self, datetime: &DateTime<FixedOffset>) -> bool {
+        let (weekday, time) = self.timezone.local_time(datetime);
+        let is_business_day = |day: Weekday| self.days[day.num_days_from_monday() as usize];
+        if self.start < self.end {
+            is_business_day(weekday) && self.start <= time && time < self.end
+        } else {
+            (is_business_day(weekday) && self.start <= time) || (is_business_day(weekday.pred()) && time < self.end)
+        }
+    }
+
+    /// 記録された開始時刻が営業時間内かどうか
+    /// 
+    /// # Arguments
+    /// * `started_date_time` - ISO 8601形式の開始時刻
+    /// 
+    /// # Returns
+    /// * `Option<bool>` - 営業時間内の場合はtrue（開始時刻を解析できない場合はNone）
+    pub fn contains_started(&self, started_date_time: &str) -> Option<bool> {
+        DateTime::parse_from_rfc3339(started_date_time)
+            .ok()
+            .map(|started| self.contains(&started))
+    }
+
+    /// 実行情報に記録する説明
+    /// 
+    /// # Returns
+    /// * `String` - 説明（例: `月火水木金 09:00-18:00（Asia/Tokyo）`）
+    pub fn describe(&self) -> String {
+        const NAMES: [&str; 7] = ["月", "火", "水", "木", "金", "土", "日"];
+        let days: String = NAMES
+            .iter()
+            .zip(self.days)
+            .filter(|(_, business_day)| *business_day)
+            .map(|(name, _)| *name)
+            .collect();
+        format!(
+            "{} {}-{}（{}）",
+            days,
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M"),
+            self.timezone.name()
+        )
+    }
+}
+
+/// 営業時間の内外ごとのリクエスト数とエラー数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BusinessHoursCounts {
+    /// 営業時間内のリクエスト数
+    pub inside: usize,
+    /// 営業時間内のエラー（ステータスコードが400以上または応答なし）の数
+    pub inside_errors: usize,
+    /// 営業時間外のリクエスト数
+    pub outside: usize,
+    /// 営業時間外のエラーの数
+    pub outside_errors: usize,
+}
+
+impl BusinessHoursCounts {
+    /// リクエストを1件集計に加える
+    /// 
+    /// # Arguments
+    /// * `inside` - 営業時間内の場合はtrue
+    /// * `status_code` - ステータスコード
+    pub fn add(&mut self, inside: bool, status_code: i32) {
+        let failed = !(1..400).contains(&status_code);
+        let (requests, errors) = if inside {
+            (&mut self.inside, &mut self.inside_errors)
+        } else {
+            (&mut self.outside, &mut self.outside_errors)
+        };
+        *requests += 1;
+        if failed {
+            *errors += 1;
+        }
+    }
+
+    /// 別の集計を合算する
+    /// 
+    /// # Arguments
+    /// * `other` - 合算する集計
+    pub fn merge(&mut self, other: &BusinessHoursCounts) {
+        self.inside += other.inside;
+        self.inside_errors += other.inside_errors;
+        self.outside += other.outside;
+        self.outside_errors += other.outside_errors;
+    }
+
+    /// 集計したリクエストが無いかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - リクエストが無い場合はtrue
+    pub fn is_empty(&self) -> bool {
+        self.inside + self.outside == 0
+    }
+}
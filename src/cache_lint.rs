@@ -0,0 +1,369 @@
+//! レスポンスのキャッシュの設定（Cache-Controlヘッダー）のリソースの種類ごとの検査を実装
+//! 
+//! HTML・ハッシュ付きの静的アセット・その他の静的アセット・APIの規則（同梱の規則または`--cache-rules`のTOML）に
+//! 違反するレスポンスを、推奨するヘッダーの値とともにCache Lintシートに出力する
+
+use crate::body_kind::BodyKind;
+use crate::error::{AnalyzerError, Result};
+use crate::har_types::Entry;
+use regex::Regex;
+use serde::Deserialize;
+use url::Url;
+
+/// 1年（秒）
+const ONE_YEAR_SECONDS: u64 = 31_536_000;
+
+/// 静的アセットとみなすファイルの拡張子（小文字）
+const ASSET_EXTENSIONS: &[&str] = &[
+    "js", "mjs", "css", "woff", "woff2", "ttf", "otf", "eot", "png", "jpg", "jpeg", "gif", "webp", "avif", "svg", "ico",
+    "wasm", "map",
+];
+
+/// キャッシュの規則を適用するリソースの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceClass {
+    /// HTMLのドキュメント
+    Html,
+    /// ファイル名にコンテンツのハッシュを含む静的アセット
+    HashedAsset,
+    /// その他の静的アセット（スクリプト・スタイルシート・フォント・画像等）
+    Asset,
+    /// JSON・XMLを返すAPI
+    Api,
+}
+
+impl ResourceClass {
+    /// シートに表示する種類の名前
+    /// 
+    /// # Returns
+    /// * `&'static str` - 種類の名前
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResourceClass::Html => "HTML",
+            ResourceClass::HashedAsset => "ハッシュ付きの静的アセット",
+            ResourceClass::Asset => "静的アセット",
+            ResourceClass::Api => "API",
+        }
+    }
+}
+
+/// リソースの種類ごとのキャッシュの規則
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct CacheRule {
+    /// いずれか1つを含むべきディレクティブ（`no-store`のような名前、または`max-age=0`のような名前と値）
+    pub any_of: Vec<String>,
+    /// 含むべきでないディレクティブ
+    pub forbidden: Vec<String>,
+    /// max-ageの最小値（秒）
+    pub min_max_age: Option<u64>,
+    /// 違反した場合に推奨するCache-Controlヘッダーの値
+    pub suggested: String,
+}
+
+impl CacheRule {
+    /// 規則を作成
+    /// 
+    /// # Arguments
+    /// * `any_of` - いずれか1つを含むべきディレクティブ
+    /// * `forbidden` - 含むべきでないディレクティブ
+    /// * `min_max_age` - max-ageの最小値（秒）
+    /// * `suggested` - 推奨するCache-Controlヘッダーの値
+    /// 
+    /// # Returns
+    /// * `CacheRule` - 規則
+    fn new(any_of: &[&str], forbidden: &[&str], min_max_age: Option<u64>, suggested: &str) -> Self {
+        CacheRule {
+            any_of: any_of.iter().map(|directive| directive.to_string()).collect(),
+            forbidden: forbidden.iter().map(|directive| directive.to_string()).collect(),
+            min_max_age,
+            suggested: suggested.to_string(),
+        }
+    }
+
+    /// Cache-Controlヘッダーの値を規則と照合
+    /// 
+    /// # Arguments
+    /// * `cache_control` - Cache-Controlヘッダーの値（無い場合はNone）
+    /// 
+    /// # Returns
+    /// * `Vec<String>` - 違反の内容（規則を満たす場合は空）
+    fn check(&self, cache_control: Option<&str>) -> Vec<String> {
+        let Some(cache_control) = cache_control else {
+            return vec!["Cache-Controlヘッダーがありません".to_string()];
+        };
+        let directives = parse_directives(cache_control);
+        let contains = |spec: &str| {
+            let (name, value) = match spec.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim())),
+                None => (spec.trim(), None),
+            };
+            directives.iter().any(|(directive, directive_value)| {
+                directive.eq_ignore_ascii_case(name) && value.is_none_or(|value| directive_value.as_deref() == Some(value))
+            })
+        };
+
+        let mut problems = Vec::new();
+        if !self.any_of.is_empty() && !self.any_of.iter().any(|spec| contains(spec)) {
+            problems.push(format!("次のいずれかがありません: {}", self.any_of.join(", ")));
+        }
+        let forbidden: Vec<&str> = self
+            .forbidden
+            .iter()
+            .filter(|spec| contains(spec))
+            .map(String::as_str)
+            .collect();
+        if !forbidden.is_empty() {
+            problems.push(format!("指定すべきでないディレクティブがあります: {}", forbidden.join(", ")));
+        }
+        if let Some(min_max_age) = self.min_max_age {
+            let max_age = directives
+                .iter()
+                .find(|(name, _)| name == "max-age")
+                .and_then(|(_, value)| value.as_deref()?.parse::<u64>().ok());
+            match max_age {
+                Some(max_age) if max_age >= min_max_age => {}
+                Some(max_age) => problems.push(format!("max-ageが{}秒未満です（{}秒）", min_max_age, max_age)),
+                None => problems.push(format!("max-ageがありません（{}秒以上を推奨）", min_max_age)),
+            }
+        }
+        problems
+    }
+}
+
+/// キャッシュの規則の設定ファイルの内容
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct CacheRulesFile {
+    /// ハッシュ付きの静的アセットとみなすURLのパスの正規表現
+    hashed_asset_pattern: Option<String>,
+    html: Option<CacheRule>,
+    hashed_asset: Option<CacheRule>,
+    asset: Option<CacheRule>,
+    api: Option<CacheRule>,
+}
+
+/// リソースの種類ごとのキャッシュの規則
+#[derive(Debug, Clone)]
+pub struct CacheRules {
+    /// ハッシュ付きの静的アセットとみなすURLのパスの正規表現（Noneの場合はファイル名から判定）
+    hashed_asset_pattern: Option<Regex>,
+    /// HTMLの規則
+    html: CacheRule,
+    /// ハッシュ付きの静的アセットの規則
+    hashed_asset: CacheRule,
+    /// その他の静的アセットの規則
+    asset: CacheRule,
+    /// APIの規則
+    api: CacheRule,
+}
+
+impl Default for CacheRules {
+    /// 同梱の規則を作成
+    /// 
+    /// # Returns
+    /// * `CacheRules` - HTMLはキャッシュしないか再検証、ハッシュ付きの静的アセットはimmutableで1年以上、
+    ///   その他の静的アセットはimmutableを指定しない、APIはprivateまたはno-store
+    fn default() -> Self {
+        CacheRules {
+            hashed_asset_pattern: None,
+            html: CacheRule::new(&["no-store", "no-cache", "max-age=0"], &["immutable"], None, "no-cache"),
+            hashed_asset: CacheRule::new(
+                &["immutable"],
+                &["no-store", "no-cache"],
+                Some(ONE_YEAR_SECONDS),
+                "public, max-age=31536000, immutable",
+            ),
+            asset: CacheRule::new(&[], &["immutable"], None, "public, no-cache"),
+            api: CacheRule::new(&["private", "no-store"], &["public", "immutable"], None, "private, no-cache"),
+        }
+    }
+}
+
+impl CacheRules {
+    /// 規則のファイル（TOML）を読み込み
+    /// 
+    /// ファイルで指定したリソースの種類のテーブルは同梱の規則を置き換え、指定しない種類は同梱の規則を使用する
+    /// 
+    /// # Arguments
+    /// * `path` - 規則のファイルのパス
+    /// 
+    /// # Returns
+    /// * `Result<CacheRules>` - 成功時は規則、失敗時はエラー
+    pub fn load(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| AnalyzerError::InvalidArgument(format!("キャッシュの規則のファイルを読み込めません: {}: {}", path, e)))?;
+        let file: CacheRulesFile = toml::from_str(&content)
+            .map_err(|e| AnalyzerError::InvalidArgument(format!("キャッシュの規則のファイルが不正です: {}: {}", path, e)))?;
+
+        let hashed_asset_pattern = file
+            .hashed_asset_pattern
+            .map(|pattern| {
+                Regex::new(&pattern).map_err(|e| AnalyzerError::InvalidArgument(format!("正規表現が不正です: {}", e)))
+            })
+            .transpose()?;
+        let defaults = CacheRules::default();
+        Ok(CacheRules {
+            hashed_asset_pattern,
+            html: file.html.unwrap_or(defaults.html),
+            hashed_asset: file.hashed_asset.unwrap_or(defaults.hashed_asset),
+            asset: file.asset.unwrap_or(defaults.asset),
+            api: file.api.unwrap_or(defaults.api),
+        })
+    }
+
+    /// リソースの種類の規則を取得
+    /// 
+    /// # Arguments
+    /// * `class` - リソースの種類
+    /// 
+    /// # Returns
+    /// * `&CacheRule` - 規則
+    pub fn rule(&self, class: ResourceClass) -> &CacheRule {
+        match class {
+            ResourceClass::Html => &self.html,
+            ResourceClass::HashedAsset => &self.hashed_asset,
+            ResourceClass::Asset => &self.asset,
+            ResourceClass::Api => &self.api,
+        }
+    }
+
+    /// レスポンスのリソースの種類を判定
+    /// 
+    /// HTML・スクリプト・スタイルシート・APIはMIMEタイプではなくボディの内容から判定した種類で区別する
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `Option<ResourceClass>` - リソースの種類（いずれにも該当しない場合はNone）
+    pub fn classify(&self, entry: &Entry) -> Option<ResourceClass> {
+        let mime_type = entry.response.content.mime_type.to_ascii_lowercase();
+        let mime_type = mime_type.split(';').next().unwrap_or_default().trim();
+        let path = Url::parse(&entry.request.url)
+            .map(|url| url.path().to_string())
+            .unwrap_or_default();
+        let extension = path
+            .rsplit('/')
+            .next()
+            .and_then(|file_name| file_name.rsplit_once('.'))
+            .map(|(_, extension)| extension.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        let body_kind = BodyKind::of_response(entry);
+
+        if body_kind == BodyKind::Html {
+            return Some(ResourceClass::Html);
+        }
+        let is_asset = mime_type.starts_with("image/")
+            || mime_type.starts_with("font/")
+            || matches!(body_kind, BodyKind::JavaScript | BodyKind::Css)
+            || mime_type == "application/wasm"
+            || ASSET_EXTENSIONS.contains(&extension.as_str());
+        if is_asset {
+            let hashed = match &self.hashed_asset_pattern {
+                Some(pattern) => pattern.is_match(&path),
+                None => has_content_hash(&path),
+            };
+            return Some(if hashed { ResourceClass::HashedAsset } else { ResourceClass::Asset });
+        }
+        let is_api = matches!(body_kind, BodyKind::Json | BodyKind::Xml);
+        is_api.then_some(ResourceClass::Api)
+    }
+}
+
+/// キャッシュの規則の違反
+#[derive(Debug, Clone)]
+pub struct CacheViolation {
+    /// エントリ番号（HAR内の順序で1始まり）
+    pub entry_number: usize,
+    /// リクエストURL
+    pub url: String,
+    /// リソースの種類
+    pub class: ResourceClass,
+    /// レスポンスのCache-Controlヘッダーの値（無い場合は空文字列）
+    pub cache_control: String,
+    /// 違反の内容
+    pub problems: Vec<String>,
+    /// 推奨するCache-Controlヘッダーの値
+    pub suggested: String,
+}
+
+/// エントリのキャッシュの設定を規則と照合
+/// 
+/// ステータスコードが200のレスポンスのみを対象とする
+/// 
+/// # Arguments
+/// * `entries` - (エントリの位置（0始まり）, HARエントリ)のイテレータ
+/// * `rules` - キャッシュの規則
+/// 
+/// # Returns
+/// * `Vec<CacheViolation>` - エントリ順の違反
+pub fn lint<'a>(entries: impl Iterator<Item = (usize, &'a Entry)>, rules: &CacheRules) -> Vec<CacheViolation> {
+    entries
+        .filter(|(_, entry)| entry.response.status == 200)
+        .filter_map(|(index, entry)| {
+            let class = rules.classify(entry)?;
+            let values: Vec<&str> = entry
+                .response
+                .headers
+                .iter()
+                .filter(|header| header.name.eq_ignore_ascii_case("cache-control"))
+                .map(|header| header.value.as_str())
+                .collect();
+            let cache_control = (!values.is_empty()).then(|| values.join(", "));
+            let rule = rules.rule(class);
+            let problems = rule.check(cache_control.as_deref());
+            (!problems.is_empty()).then(|| CacheViolation {
+                entry_number: index + 1,
+                url: entry.request.url.clone(),
+                class,
+                cache_control: cache_control.unwrap_or_default(),
+                problems,
+                suggested: rule.suggested.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Cache-Controlヘッダーの値をディレクティブに分割
+/// 
+/// # Arguments
+/// * `value` - Cache-Controlヘッダーの値
+/// 
+/// # Returns
+/// * `Vec<(String, Option<String>)>` - (小文字のディレクティブ名, 値)のリスト
+fn parse_directives(value: &str) -> Vec<(String, Option<String>)> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .map(|directive| match directive.split_once('=') {
+            Some((name, value)) => (
+                name.trim().to_ascii_lowercase(),
+                Some(value.trim().trim_matches('"').to_string()),
+            ),
+            None => (directive.to_ascii_lowercase(), None),
+        })
+        .collect()
+}
+
+/// ファイル名にコンテンツのハッシュを含むかどうか
+/// 
+/// 拡張子を除いたファイル名を`.`・`-`・`_`で区切った部分に、数字を含む8文字以上の英数字
+/// （webpack・Vite等のハッシュ）があるものをハッシュ付きとする
+/// 
+/// # Arguments
+/// * `path` - URLのパス
+/// 
+/// # Returns
+/// * `bool` - ハッシュを含む場合はtrue
+fn has_content_hash(path: &str) -> bool {
+    let file_name = path.rsplit('/').next().unwrap_or_default();
+    let stem = file_name.rsplit_once('.').map_or(file_name, |(stem, _)| stem);
+    stem.split(['.', '-', '_']).any(|part| {
+        part.len() >= 8
+            && part.chars().all(|c| c.is_ascii_alphanumeric())
+            && part.chars().any(|c| c.is_ascii_digit())
+    })
+}
@@ -0,0 +1,719 @@
+//! HARファイルを解析してExcelファイル等に出力するanalyzeサブコマンドを実装
+
+use anyhow::Result;
+use clap::parser::ValueSource;
+use clap::{Arg, ArgMatches, Command};
+use encoding_rs::{Encoding, UTF_8};
+use log::{info, warn};
+use regex::Regex;
+use rs_har_analyzer::analyzer::{HarAnalyzer, ReadMode, TARGET_METHODS};
+use rs_har_analyzer::annotations::Annotations;
+use rs_har_analyzer::archive;
+use rs_har_analyzer::budgets::Budgets;
+use rs_har_analyzer::business_hours::BusinessHours;
+use rs_har_analyzer::cache_lint::CacheRules;
+use rs_har_analyzer::columns::Column;
+use rs_har_analyzer::compression;
+use rs_har_analyzer::conditional;
+use rs_har_analyzer::consent::ConsentReport;
+use rs_har_analyzer::csv_export::CsvOptions;
+use rs_har_analyzer::dependency_graph::{DependencyGraph, GraphFormat};
+use rs_har_analyzer::encryption::OutputEncryption;
+use rs_har_analyzer::error::AnalyzerError;
+use rs_har_analyzer::events::TrafficEvent;
+use rs_har_analyzer::excel_exporter::{ExcelExporter, ExportOptions};
+use rs_har_analyzer::filter::EntryFilter;
+use rs_har_analyzer::flags::EntryFlags;
+use rs_har_analyzer::hunt;
+use rs_har_analyzer::notify::{self, RunNotification};
+use rs_har_analyzer::outliers;
+use rs_har_analyzer::payload::PayloadOptions;
+use rs_har_analyzer::payload_cache::{PayloadCache, PayloadSides};
+use rs_har_analyzer::report::{self, ReportFormat};
+use rs_har_analyzer::run_info::RunInfo;
+use rs_har_analyzer::security;
+use rs_har_analyzer::source_maps;
+use rs_har_analyzer::stubs::{self, StubFormat};
+use rs_har_analyzer::success_rate::SuccessTracker;
+use rs_har_analyzer::summary::Summary;
+use rs_har_analyzer::threat_intel::ThreatIntel;
+use rs_har_analyzer::trackers::Blocklist;
+use rs_har_analyzer::upload::UploadTarget;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use super::options::{
+    filter_args,
+    filter_from_matches,
+    flags_from_matches,
+    blocklist_from_matches,
+    threat_intel_from_matches,
+    business_hours_from_matches,
+    export_args,
+    profile_args,
+    payload_options_from_matches,
+    export_options_from_matches,
+    budgets_from_matches,
+    encryption_from_matches,
+};
+use super::{read_mode_from_matches, parse_charset};
+
+/// analyzeサブコマンドの設定
+#[derive(Debug, Clone)]
+pub(crate) struct AnalyzeConfig {
+    pub(crate) input_file: String,
+    pub(crate) output_file: String,
+    pub(crate) graph_file: Option<String>,
+    pub(crate) archive_file: Option<String>,
+    pub(crate) stub_format: Option<StubFormat>,
+    pub(crate) report_format: Option<ReportFormat>,
+    pub(crate) export_options: ExportOptions,
+    pub(crate) streaming: bool,
+    pub(crate) payload_options: PayloadOptions,
+    pub(crate) cache_dir: Option<String>,
+    pub(crate) filter: EntryFilter,
+    pub(crate) annotations: Annotations,
+    pub(crate) flags: EntryFlags,
+    pub(crate) blocklist: Blocklist,
+    pub(crate) threat_intel: ThreatIntel,
+    pub(crate) hunt: bool,
+    pub(crate) sort_by_time: bool,
+    pub(crate) business_hours: Option<BusinessHours>,
+    pub(crate) consent_url_regexes: Vec<Regex>,
+    pub(crate) retry_window_ms: u64,
+    pub(crate) cache_rules: CacheRules,
+    pub(crate) budgets: Budgets,
+    pub(crate) fail_on_budget: bool,
+    pub(crate) notify_webhook: Option<String>,
+    pub(crate) upload: Option<UploadTarget>,
+    pub(crate) encryption: Option<OutputEncryption>,
+    pub(crate) read_mode: ReadMode,
+}
+
+/// analyzeサブコマンドの定義を作成
+/// 
+/// # Returns
+/// * `Command` - サブコマンドの定義
+pub(crate) fn command() -> Command {
+    Command::new("analyze")
+        .about("HARファイルを解析してExcelファイル（--formatでWord形式のレポート・スタブ定義）に出力（サブコマンドを省略した場合も同じ）")
+        .args(AnalyzeConfig::args())
+}
+
+impl AnalyzeConfig {
+    /// analyzeサブコマンドの引数定義
+    /// 
+    /// # Returns
+    /// * `Vec<Arg>` - 引数定義のリスト
+    pub(crate) fn args() -> Vec<Arg> {
+        let mut args = vec![
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("FILE")
+                .help("入力するHARファイルのパス")
+                .required(true),
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("FILE")
+                .help("出力するExcelファイルのパス（wiremock形式では出力先のディレクトリ）")
+                .default_value("har_analysis.xlsx"),
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .help("出力形式（xlsx: Excelファイル、docx: Word形式のレポート、csv・tsv: カンマ・タブ区切りの解析結果、template: --templateのTeraテンプレートで作成したテキスト、wiremock: WireMockのスタブマッピング、playwright-routes: Playwrightのルート定義）")
+                .value_parser(["xlsx", "docx", "csv", "tsv", "template", "wiremock", "playwright-routes"])
+                .default_value("xlsx"),
+            Arg::new("graph")
+                .short('g')
+                .long("graph")
+                .value_name("FILE")
+                .help("リソース依存関係グラフの出力先（.dot/.gv/.graphml）"),
+            Arg::new("archive")
+                .long("archive")
+                .value_name("FILE")
+                .help("出力したファイル（外部ファイルに退避したペイロード・依存関係グラフを含む）をマニフェスト付きのZIPファイルにまとめる"),
+            Arg::new("append")
+                .long("append")
+                .help("出力ファイルが存在する場合は既存のシートを残し、日付のシートとして追記する")
+                .action(clap::ArgAction::SetTrue),
+            Arg::new("template")
+                .long("template")
+                .value_name("FILE")
+                .help("--format templateで使用するTeraテンプレートのパス（解析結果・サマリー・実行情報を受け取る）"),
+            Arg::new("csv-delimiter")
+                .long("csv-delimiter")
+                .value_name("CHAR")
+                .help("CSV・TSV形式の区切り文字（1文字のASCII文字、タブは\\tまたはtab。省略時: csvはカンマ、tsvはタブ）")
+                .value_parser(parse_delimiter),
+            Arg::new("csv-encoding")
+                .long("csv-encoding")
+                .value_name("ENCODING")
+                .help("CSV・TSV形式の文字コード（例: UTF-8, Shift_JIS, CP932。デフォルト: UTF-8）")
+                .value_parser(parse_csv_encoding),
+            Arg::new("csv-bom")
+                .long("csv-bom")
+                .help("CSV・TSV形式の先頭にBOMを付ける（UTF-8の場合のみ）")
+                .action(clap::ArgAction::SetTrue),
+            Arg::new("csv-line-ending")
+                .long("csv-line-ending")
+                .value_name("EOL")
+                .help("CSV・TSV形式の改行コード")
+                .value_parser(["crlf", "lf"])
+                .default_value("crlf"),
+        ];
+        args.extend(filter_args());
+        args.extend(export_args());
+        args.extend(profile_args());
+        args
+    }
+
+    /// コマンドライン引数から設定を作成
+    /// 
+    /// # Arguments
+    /// * `matches` - 解析済みのコマンドライン引数
+    /// 
+    /// # Returns
+    /// * `AnalyzeConfig` - アプリケーション設定
+    pub(crate) fn from_matches(matches: &ArgMatches) -> Self {
+        let format = matches.get_one::<String>("format").unwrap();
+        let stub_format = StubFormat::from_name(format);
+        let report_format = ReportFormat::from_name(format);
+        // スタブ定義・レポートの形式で出力先が指定されていない場合は形式ごとの既定の出力先に出力
+        let default_output = stub_format
+            .map(|format| format.default_output())
+            .or(report_format.map(|format| format.default_output()));
+        let output_file = match (default_output, matches.value_source("output")) {
+            (Some(output), Some(ValueSource::DefaultValue)) => output.to_string(),
+            _ => matches.get_one::<String>("output").unwrap().clone(),
+        };
+        AnalyzeConfig {
+            input_file: matches.get_one::<String>("input").unwrap().clone(),
+            output_file,
+            graph_file: matches.get_one::<String>("graph").cloned(),
+            archive_file: matches.get_one::<String>("archive").cloned(),
+            stub_format,
+            report_format,
+            export_options: ExportOptions {
+                append: matches.get_flag("append"),
+                csv: CsvOptions {
+                    delimiter: matches.get_one::<u8>("csv-delimiter").copied(),
+                    encoding: matches.get_one::<&'static Encoding>("csv-encoding").copied().unwrap_or(UTF_8),
+                    bom: matches.get_flag("csv-bom"),
+                    crlf: matches.get_one::<String>("csv-line-ending").unwrap() == "crlf",
+                },
+                template: matches.get_one::<String>("template").cloned(),
+                ..export_options_from_matches(matches)
+            },
+            streaming: matches.get_flag("streaming"),
+            payload_options: payload_options_from_matches(matches),
+            cache_dir: matches.get_one::<String>("cache").cloned(),
+            filter: filter_from_matches(matches),
+            annotations: matches.get_one::<Annotations>("annotate").cloned().unwrap_or_default(),
+            flags: flags_from_matches(matches),
+            blocklist: blocklist_from_matches(matches),
+            threat_intel: threat_intel_from_matches(matches),
+            hunt: matches.get_flag("hunt"),
+            sort_by_time: matches.get_flag("sort-by-time"),
+            business_hours: business_hours_from_matches(matches),
+            consent_url_regexes: matches
+                .get_many::<Regex>("consent-url-regex")
+                .map(|regexes| regexes.cloned().collect())
+                .unwrap_or_default(),
+            retry_window_ms: *matches.get_one::<u64>("retry-window").unwrap(),
+            cache_rules: matches.get_one::<CacheRules>("cache-rules").cloned().unwrap_or_default(),
+            budgets: budgets_from_matches(matches),
+            fail_on_budget: matches.get_flag("fail-on-budget"),
+            notify_webhook: matches.get_one::<String>("notify-webhook").cloned(),
+            upload: matches.get_one::<UploadTarget>("upload").cloned(),
+            encryption: encryption_from_matches(matches),
+            read_mode: read_mode_from_matches(matches),
+        }
+    }
+
+    /// 設定の妥当性を検証
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub(crate) fn validate(&self) -> Result<()> {
+        // 入力ファイルの存在確認
+        if !Path::new(&self.input_file).exists() {
+            return Err(anyhow::anyhow!("入力ファイルが見つかりません: {}", self.input_file));
+        }
+
+        // 入力ファイルの拡張子確認
+        if !self.input_file.to_lowercase().ends_with(".har") {
+            return Err(anyhow::anyhow!("入力ファイルはHARファイル(.har)である必要があります"));
+        }
+
+        // 出力ファイルの拡張子確認
+        if self.stub_format.is_some() {
+            if self.output_file.to_lowercase().ends_with(".xlsx") {
+                return Err(anyhow::anyhow!("スタブ定義の形式ではExcelファイル以外の出力先を指定してください"));
+            }
+        } else if let Some(format) = self.report_format {
+            if let Some(extension) = format.extension()
+                && !self.output_file.to_lowercase().ends_with(extension)
+            {
+                return Err(anyhow::anyhow!("レポートの出力ファイルは{}ファイルである必要があります", extension));
+            }
+        } else if !self.output_file.to_lowercase().ends_with(".xlsx") {
+            return Err(anyhow::anyhow!("出力ファイルはExcelファイル(.xlsx)である必要があります"));
+        }
+
+        match (&self.export_options.template, self.report_format) {
+            (None, Some(ReportFormat::Template)) => {
+                return Err(anyhow::anyhow!("--format templateでは--templateでテンプレートを指定してください"));
+            }
+            (Some(_), format) if format != Some(ReportFormat::Template) => {
+                return Err(anyhow::anyhow!("--templateは--format templateと同時に指定してください"));
+            }
+            (Some(template), _) if !Path::new(template).is_file() => {
+                return Err(anyhow::anyhow!("テンプレートが見つかりません: {}", template));
+            }
+            _ => {}
+        }
+
+        if self.export_options.csv.bom && self.export_options.csv.encoding != UTF_8 {
+            return Err(anyhow::anyhow!(
+                "--csv-bomはUTF-8の場合のみ指定できます: {}",
+                self.export_options.csv.encoding.name()
+            ));
+        }
+
+        // スタブ定義は記録したURL・ボディと照合して再生するため、匿名化すると使用できない
+        if self.stub_format.is_some() && self.export_options.privacy.is_active() {
+            return Err(anyhow::anyhow!(
+                "--privacyはスタブ定義の形式（wiremock・playwright-routes）と同時に指定できません（匿名化したURL・ボディでは再生できないため）"
+            ));
+        }
+
+        self.export_options.validate(self.streaming)?;
+
+        if let Some(archive_file) = &self.archive_file {
+            validate_archive_file(archive_file)?;
+        }
+
+        // グラフ出力ファイルの拡張子確認
+        if let Some(graph_file) = &self.graph_file
+            && GraphFormat::from_path(graph_file).is_none()
+        {
+            return Err(anyhow::anyhow!("グラフ出力ファイルは.dot/.gv/.graphmlである必要があります"));
+        }
+
+        Ok(())
+    }
+}
+
+/// アプリケーションを実行
+/// 
+/// # Arguments
+/// * `config` - アプリケーション設定
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub(crate) async fn run(config: AnalyzeConfig) -> Result<()> {
+    info!("HARファイル解析を開始します");
+    let (summary, mut outputs) = match config.stub_format {
+        Some(format) => {
+            let analyzer = HarAnalyzer::with_read_mode(&config.input_file, config.read_mode)?
+                .with_filter(config.filter.clone());
+            let count = stubs::export(analyzer.target_entries(), format, &config.output_file)?;
+            if count == 0 {
+                info!("解析対象のGET/POSTリクエストが見つかりませんでした");
+            }
+            (None, vec![PathBuf::from(&config.output_file)])
+        }
+        None => {
+            let (summary, written) = analyze_file(&config)?;
+            (Some(summary), written)
+        }
+    };
+    // 解析対象のエントリが無く、ファイルを作成しなかった場合は暗号化・アーカイブ・アップロードを行わない
+    let mut locations = Vec::new();
+    if outputs.is_empty() {
+        info!("作成したファイルが無いため、暗号化・アーカイブ・アップロードを行いません");
+    } else {
+        if let Some(encryption) = &config.encryption {
+            outputs = encryption.apply(outputs, &[PathBuf::from(&config.output_file)])?;
+        }
+        if let Some(archive_file) = &config.archive_file {
+            archive::create(archive_file, &outputs, config.export_options.deterministic)?;
+            outputs = vec![PathBuf::from(archive_file)];
+        }
+        locations = match &config.upload {
+            Some(target) => target.upload(&outputs).await?,
+            None => outputs.iter().map(|path| notify::output_location(path)).collect(),
+        };
+    }
+
+    if let Some(summary) = &summary {
+        if let Some(url) = &config.notify_webhook {
+            let notification = RunNotification {
+                subject: config.input_file.clone(),
+                summary,
+                outputs: locations.into_iter().take(1).collect(),
+                failures: 0,
+            };
+            // 通知に失敗しても解析・出力の結果は有効なため、警告のみとする
+            if let Err(e) = notification.post(url) {
+                warn!("{}", e);
+            }
+        }
+        // 出力ファイルで超過したエントリを確認できるように、出力・アップロードした後で失敗とする
+        check_budgets(&config, summary)?;
+    }
+    info!("HARファイル解析が完了しました");
+    Ok(())
+}
+
+/// 1件のHARファイルを解析してExcelファイルに出力
+/// 
+/// # Arguments
+/// * `config` - アプリケーション設定
+/// 
+/// # Returns
+/// * `Result<(Summary, Vec<PathBuf>)>` - 成功時は解析結果のサマリーと作成したファイル（出力ファイル・グラフ・外部ファイル）のパス、失敗時はエラー
+/// 
+/// 解析対象のエントリが無い場合は出力ファイルを作成しないため、パスに含めない
+pub(crate) fn analyze_file(config: &AnalyzeConfig) -> Result<(Summary, Vec<PathBuf>)> {
+    info!("入力ファイル: {}", config.input_file);
+    info!("出力ファイル: {}", config.output_file);
+
+    // HARファイルの解析
+    let mut analyzer = HarAnalyzer::with_read_mode(&config.input_file, config.read_mode)?
+        .with_payload_options(config.payload_options.clone())
+        .with_filter(config.filter.clone())
+        .with_annotations(config.annotations.clone())
+        .with_blocklist(config.blocklist.clone())
+        .with_business_hours(config.business_hours.clone())
+        .with_entry_ids(config.export_options.columns.contains(&Column::EntryId));
+    // 出力する順序のみを並べ替え、エントリ番号は記録順のまま保つ
+    let sorted_entries = if config.sort_by_time { analyzer.sort_by_time() } else { 0 };
+    // 脅威インテリジェンスの指標との照合はキャプチャ全体に対して行い、一致したエントリを強調表示する
+    let threat_findings = config.threat_intel.scan(analyzer.har_data());
+    let mut flags = config.flags.clone();
+    for finding in &threat_findings {
+        flags
+            .reasons
+            .entry(finding.entry_number - 1)
+            .or_default()
+            .push(format!("{}: {}", finding.kind.label(), finding.detail));
+    }
+    let mut analyzer = analyzer.with_flags(flags);
+    if config.export_options.cell_notes && config.export_options.privacy.is_active() {
+        warn!("--privacyを指定した場合はセルのメモ（--cell-notes）を付けません");
+    }
+    if let Some(cache_dir) = &config.cache_dir {
+        // キャッシュには加工済みの平文のボディが残るため、匿名化・暗号化する場合は使用しない
+        if config.export_options.privacy.is_active() || config.encryption.is_some() {
+            warn!("--privacy・--encrypt-outputを指定した場合はボディのキャッシュ（--cache）を使用しません");
+        } else {
+            let columns = &config.export_options.columns;
+            let sides = PayloadSides {
+                request: columns.contains(&Column::RequestPayload) || config.filter.filters_body(),
+                response: columns.contains(&Column::ResponsePayload) || config.filter.filters_body(),
+            };
+            analyzer = analyzer.with_payload_cache(PayloadCache::open(cache_dir, &config.payload_options, sides)?);
+        }
+    }
+
+    // 依存関係グラフの出力
+    let mut written = Vec::new();
+    if let Some(graph_file) = &config.graph_file {
+        DependencyGraph::build(analyzer.har_data())
+            .with_privacy(&config.export_options.privacy)
+            .export(graph_file)?;
+        written.push(PathBuf::from(graph_file));
+    }
+
+    // レポートの作成経緯を実行情報シートに記録
+    let mut filters = vec![format!("メソッド: {}", TARGET_METHODS.join(", "))];
+    filters.extend(config.filter.describe());
+    if let Some(business_hours) = &config.business_hours {
+        filters.push(format!("営業時間: {}", business_hours.describe()));
+    }
+    if config.sort_by_time {
+        filters.push(format!("並べ替え: 開始時刻順（位置が変わったエントリ: {}件）", sorted_entries));
+    }
+    if config.export_options.privacy.is_active() {
+        filters.push(format!("匿名化: {}", config.export_options.privacy.name()));
+    }
+    // セキュリティの指摘事項はキャプチャ全体から検出
+    // 脅威インテリジェンスの指標に一致したエントリは先頭に出力する
+    let mut security_findings = threat_findings;
+    security_findings.extend(security::scan(analyzer.har_data()));
+    security_findings.extend(source_maps::scan(analyzer.har_data()));
+    if config.hunt {
+        security_findings.extend(hunt::scan(analyzer.har_data()));
+    }
+    security::log_findings(&security_findings);
+    let consent_report = ConsentReport::scan(analyzer.har_data(), &config.blocklist, &config.consent_url_regexes);
+    consent_report.log();
+    let page_sequences = analyzer.page_sequences();
+    if !page_sequences.is_empty() {
+        info!("ページ送りの一連のリクエスト: {}件（Paginationシートを参照）", page_sequences.len());
+    }
+    let cache_violations = analyzer.cache_violations(&config.cache_rules);
+    if !cache_violations.is_empty() {
+        warn!("キャッシュの規則に違反するレスポンス: {}件（Cache Lintシートを参照）", cache_violations.len());
+    }
+    let budget_violations = analyzer.budget_violations(&config.budgets);
+    if !budget_violations.is_empty() {
+        warn!("サイズの予算の超過: {}件（Budgetsシートを参照）", budget_violations.len());
+    }
+    let exceeded_budgets = budget_violations.len();
+    let duplicate_responses = analyzer.duplicate_responses();
+    if !duplicate_responses.is_empty() {
+        let savings: usize = duplicate_responses.iter().map(|group| group.savings()).sum();
+        info!(
+            "異なるURLで同じ内容のレスポンス: {}件（削減できるサイズ: {} bytes、Duplicatesシートを参照）",
+            duplicate_responses.len(),
+            savings
+        );
+    }
+    let compression_savings = analyzer.compression_savings();
+    if !compression_savings.is_empty() {
+        let total = compression::total(&compression_savings);
+        info!(
+            "圧縮せずに配信されたテキストのレスポンス: {}件 {} bytes（gzipで{} bytes、brotliで{} bytes削減可能、Compressionシートを参照）",
+            total.responses,
+            total.original_bytes,
+            total.gzip_savings(),
+            total.brotli_savings()
+        );
+    }
+    let server_timings = analyzer.server_timings();
+    if !server_timings.is_empty() {
+        let overheads: Vec<f64> = server_timings.iter().filter_map(|entry| entry.overhead()).collect();
+        info!(
+            "Server-Timingヘッダーを記録したレスポンス: {}件（待機時間とサーバーの処理時間の差の平均: {:.1} ms、Server Timingシートを参照）",
+            server_timings.len(),
+            overheads.iter().sum::<f64>() / overheads.len().max(1) as f64
+        );
+    }
+    let conditional_requests = analyzer.conditional_requests();
+    if !conditional_requests.is_empty() {
+        let total = conditional::total(&conditional_requests);
+        info!(
+            "条件付きリクエスト: {}件（304: {}件、200: {}件、うち検証子が一致したのに200: {}件、Conditionalシートを参照）",
+            total.conditional,
+            total.not_modified,
+            total.full,
+            total.ignored
+        );
+        let ignoring = conditional_requests.iter().filter(|url| url.ignored > 0).count();
+        if ignoring > 0 {
+            warn!("検証子を無視して完全なレスポンスを返したURL: {}件", ignoring);
+        }
+    }
+    let connections = analyzer.connection_usage();
+    if !connections.is_empty() {
+        let requests: usize = connections.iter().map(|connection| connection.requests()).sum();
+        let single = connections.iter().filter(|connection| connection.requests() == 1).count();
+        info!(
+            "接続: {}本（1接続あたり平均{:.1}件のリクエスト、1件のみで終わった接続: {}本、Connectionsシートを参照）",
+            connections.len(),
+            requests as f64 / connections.len() as f64,
+            single
+        );
+        let multiplexed: Vec<_> = connections.iter().filter(|connection| connection.is_multiplexed()).collect();
+        if !multiplexed.is_empty() {
+            info!(
+                "多重化した接続: {}本（最大同時ストリーム数: {}）",
+                multiplexed.len(),
+                multiplexed.iter().map(|connection| connection.max_concurrent).max().unwrap_or_default()
+            );
+        }
+    }
+    let infrastructure = analyzer.infrastructure();
+    if !infrastructure.is_empty() {
+        let cdns: BTreeSet<&str> = infrastructure.iter().flat_map(|host| host.cdns.iter().copied()).collect();
+        info!(
+            "基盤を推定したホスト: {}件（CDN: {}、Infrastructureシートを参照）",
+            infrastructure.len(),
+            if cdns.is_empty() { "なし".to_string() } else { cdns.into_iter().collect::<Vec<_>>().join(", ") }
+        );
+    }
+    let header_inventory = config
+        .export_options
+        .header_examples
+        .map(|max_examples| analyzer.header_inventory(max_examples))
+        .unwrap_or_default();
+    if !header_inventory.is_empty() {
+        let inconsistent = header_inventory.iter().filter(|usage| usage.spellings.len() > 1).count();
+        info!(
+            "ヘッダー: {}種類（名前の表記が揃っていないヘッダー: {}種類、Headers Inventoryシートを参照）",
+            header_inventory.len(),
+            inconsistent
+        );
+    }
+    let egress_cost = config
+        .export_options
+        .egress_pricing
+        .as_ref()
+        .map(|pricing| analyzer.egress_costs(pricing));
+    let run_info = RunInfo::collect(&config.input_file, analyzer.har_data(), filters)?;
+    let export_options = ExportOptions {
+        run_info: Some(if config.export_options.deterministic { run_info.deterministic() } else { run_info }),
+        security_findings,
+        consent_report,
+        page_sequences,
+        cache_violations,
+        budget_violations,
+        duplicate_responses,
+        compression_savings,
+        server_timings,
+        conditional_requests,
+        connections,
+        infrastructure,
+        header_inventory,
+        ..config.export_options.clone()
+    };
+
+    if config.streaming && config.report_format.is_some() {
+        warn!("レポートの形式では--streamingを使用せず、解析結果を保持して集計します");
+    }
+    let summary = if config.streaming && config.report_format.is_none() {
+        if export_options.columns.contains(&Column::Outlier) {
+            warn!("--streamingでは所要時間の外れ値を判定しないため、外れ値列は空欄になります");
+        }
+        if export_options.row_grouping.is_some() {
+            warn!("--streamingでは行を並べ替えないため、連続する同じページ・ホストの行のみをグループ化します");
+        }
+
+        // 解析結果を保持せずにExcelファイルへ書き込みながら集計
+        let mut summary = Summary::default();
+        let mut success = SuccessTracker::new(config.retry_window_ms);
+        let events = analyzer.events().inspect(|event| {
+            if let Ok(TrafficEvent::HttpExchange(result)) = event {
+                summary.add(result);
+                success.add(result);
+            }
+        });
+        let exported = ExcelExporter::export_streaming(events, &config.output_file, &export_options)?;
+        written.push(PathBuf::from(&config.output_file));
+        written.extend(exported.sidecars);
+
+        if summary.total == 0 {
+            info!("解析対象のGET/POSTリクエストが見つかりませんでした");
+            return Ok((summary, written));
+        }
+
+        summary.sampling = config.filter.sampling.describe();
+        summary.endpoint_success = success.finish();
+        summary.retry_window_ms = config.retry_window_ms;
+        summary.egress_cost = egress_cost;
+        summary.budget_violations = exceeded_budgets;
+        summary.log();
+        summary
+    } else {
+        let mut events = analyzer.analyze_events()?;
+        let outliers = outliers::mark_outliers(&mut events);
+        let mut success = SuccessTracker::new(config.retry_window_ms);
+        events.iter().filter_map(TrafficEvent::as_http).for_each(|result| success.add(result));
+        let summary = Summary {
+            outliers,
+            endpoint_success: success.finish(),
+            retry_window_ms: config.retry_window_ms,
+            sampling: config.filter.sampling.describe(),
+            egress_cost,
+            budget_violations: exceeded_budgets,
+            ..Summary::from_events(&events)
+        };
+
+        if summary.total == 0 {
+            info!("解析対象のGET/POSTリクエストが見つかりませんでした");
+            return Ok((summary, written));
+        }
+
+        // 解析結果のサマリーを出力
+        summary.log();
+
+        // Excelファイルまたはレポートに出力
+        match config.report_format {
+            Some(format) => {
+                report::export(&events, &summary, &export_options, format, &config.output_file)?;
+                written.push(PathBuf::from(&config.output_file));
+            }
+            None => {
+                let exported = ExcelExporter::export(&events, &config.output_file, &export_options)?;
+                written.push(PathBuf::from(&config.output_file));
+                written.extend(exported.sidecars);
+            }
+        }
+        summary
+    };
+
+    if let Some(cache) = analyzer.payload_cache() {
+        cache.log();
+    }
+
+    Ok((summary, written))
+}
+
+/// `--fail-on-budget`を指定した場合にサイズの予算の超過を確認
+/// 
+/// # Arguments
+/// * `config` - アプリケーション設定
+/// * `summary` - 解析結果のサマリー
+/// 
+/// # Returns
+/// * `Result<()>` - 超過が無い場合はOk、超過がある場合はエラー
+pub(crate) fn check_budgets(config: &AnalyzeConfig, summary: &Summary) -> Result<()> {
+    if config.fail_on_budget && summary.budget_violations > 0 {
+        return Err(AnalyzerError::BudgetExceeded(summary.budget_violations).into());
+    }
+    Ok(())
+}
+
+/// ZIPファイルの出力先を検証
+/// 
+/// # Arguments
+/// * `archive_file` - ZIPファイルのパス
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub(crate) fn validate_archive_file(archive_file: &str) -> Result<()> {
+    if !archive_file.to_lowercase().ends_with(".zip") {
+        return Err(anyhow::anyhow!("--archiveの出力ファイルはZIPファイル(.zip)である必要があります"));
+    }
+    Ok(())
+}
+
+/// CSV・TSV形式の区切り文字を解析
+/// 
+/// # Arguments
+/// * `value` - 区切り文字（`\t`・`tab`はタブ）
+/// 
+/// # Returns
+/// * `Result<u8, String>` - 成功時は区切り文字、失敗時はエラーメッセージ
+fn parse_delimiter(value: &str) -> std::result::Result<u8, String> {
+    match value {
+        "\\t" | "tab" | "\t" => Ok(b'\t'),
+        _ if value.len() == 1 && value.is_ascii() && value != "\"" && value != "\n" && value != "\r" => {
+            Ok(value.as_bytes()[0])
+        }
+        _ => Err(format!("区切り文字は1文字のASCII文字（引用符・改行を除く）で指定してください: {}", value)),
+    }
+}
+
+/// CSV・TSV形式の文字コードの名前を解析
+/// 
+/// # Arguments
+/// * `value` - 文字コードの名前（WHATWG Encoding Standardのラベル、またはCP932）
+/// 
+/// # Returns
+/// * `Result<&'static Encoding, String>` - 成功時は文字コード、失敗時はエラーメッセージ
+fn parse_csv_encoding(value: &str) -> std::result::Result<&'static Encoding, String> {
+    // Windowsの名前（CP932）はWHATWGのラベルに含まれないため、同じ文字集合のShift_JISとして扱う
+    if value.trim().eq_ignore_ascii_case("cp932") {
+        return Ok(encoding_rs::SHIFT_JIS);
+    }
+    let encoding = parse_charset(value)?;
+    // UTF-16はエンコーダーが無くUTF-8で出力されるため受け付けない
+    if encoding.output_encoding() != encoding {
+        return Err(format!("CSV・TSV形式の文字コードには指定できません: {}", value));
+    }
+    Ok(encoding)
+}
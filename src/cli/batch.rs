@@ -0,0 +1,379 @@
+//! 複数のHARファイルを並列に解析するbatchサブコマンドを実装
+
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+use log::{error, info, warn};
+use regex::Regex;
+use rs_har_analyzer::analyzer::ReadMode;
+use rs_har_analyzer::annotations::Annotations;
+use rs_har_analyzer::archive;
+use rs_har_analyzer::budgets::Budgets;
+use rs_har_analyzer::business_hours::BusinessHours;
+use rs_har_analyzer::cache_lint::CacheRules;
+use rs_har_analyzer::encryption::OutputEncryption;
+use rs_har_analyzer::excel_exporter::ExportOptions;
+use rs_har_analyzer::filter::EntryFilter;
+use rs_har_analyzer::flags::EntryFlags;
+use rs_har_analyzer::notify::{self, RunNotification};
+use rs_har_analyzer::payload::PayloadOptions;
+use rs_har_analyzer::summary::Summary;
+use rs_har_analyzer::threat_intel::ThreatIntel;
+use rs_har_analyzer::trackers::Blocklist;
+use rs_har_analyzer::upload::UploadTarget;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use super::analyze::{AnalyzeConfig, analyze_file, check_budgets, validate_archive_file};
+use super::options::{
+    filter_args,
+    filter_from_matches,
+    flags_from_matches,
+    blocklist_from_matches,
+    threat_intel_from_matches,
+    business_hours_from_matches,
+    export_args,
+    profile_args,
+    payload_options_from_matches,
+    export_options_from_matches,
+    budgets_from_matches,
+    encryption_from_matches,
+};
+use super::read_mode_from_matches;
+
+/// batchサブコマンドの設定
+#[derive(Debug)]
+pub(crate) struct BatchConfig {
+    inputs: Vec<String>,
+    output_dir: String,
+    archive_file: Option<String>,
+    jobs: usize,
+    export_options: ExportOptions,
+    streaming: bool,
+    payload_options: PayloadOptions,
+    cache_dir: Option<String>,
+    filter: EntryFilter,
+    annotations: Annotations,
+    flags: EntryFlags,
+    blocklist: Blocklist,
+    threat_intel: ThreatIntel,
+    hunt: bool,
+    sort_by_time: bool,
+    business_hours: Option<BusinessHours>,
+    consent_url_regexes: Vec<Regex>,
+    retry_window_ms: u64,
+    cache_rules: CacheRules,
+    budgets: Budgets,
+    fail_on_budget: bool,
+    notify_webhook: Option<String>,
+    upload: Option<UploadTarget>,
+    encryption: Option<OutputEncryption>,
+    read_mode: ReadMode,
+}
+
+/// batchサブコマンドの定義を作成
+/// 
+/// # Returns
+/// * `Command` - サブコマンドの定義
+pub(crate) fn command() -> Command {
+    Command::new("batch")
+        .about("複数のHARファイルを並列に解析し、ファイルごとにExcelファイルを出力")
+        .args(BatchConfig::args())
+}
+
+impl BatchConfig {
+    /// batchサブコマンドの引数定義
+    /// 
+    /// # Returns
+    /// * `Vec<Arg>` - 引数定義のリスト
+    pub(crate) fn args() -> Vec<Arg> {
+        let mut args = vec![
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("PATH")
+                .help("入力するHARファイルまたはHARファイルを含むディレクトリのパス（複数指定可）")
+                .num_args(1..)
+                .required(true),
+            Arg::new("output-dir")
+                .short('o')
+                .long("output-dir")
+                .value_name("DIR")
+                .help("Excelファイルの出力先ディレクトリ")
+                .default_value("har_analysis"),
+            Arg::new("archive")
+                .long("archive")
+                .value_name("FILE")
+                .help("出力したExcelファイル（外部ファイルに退避したペイロードを含む）をマニフェスト付きのZIPファイルにまとめる"),
+            Arg::new("jobs")
+                .short('j')
+                .long("jobs")
+                .value_name("N")
+                .help("同時に処理するファイル数の上限（既定: CPUのコア数）")
+                .value_parser(clap::value_parser!(u16).range(1..)),
+        ];
+        args.extend(filter_args());
+        args.extend(export_args());
+        args.extend(profile_args());
+        args
+    }
+
+    /// コマンドライン引数から設定を作成
+    /// 
+    /// # Arguments
+    /// * `matches` - 解析済みのコマンドライン引数
+    /// 
+    /// # Returns
+    /// * `BatchConfig` - batchサブコマンドの設定
+    pub(crate) fn from_matches(matches: &ArgMatches) -> Self {
+        BatchConfig {
+            inputs: matches.get_many::<String>("input").unwrap().cloned().collect(),
+            output_dir: matches.get_one::<String>("output-dir").unwrap().clone(),
+            archive_file: matches.get_one::<String>("archive").cloned(),
+            jobs: matches
+                .get_one::<u16>("jobs")
+                .map(|jobs| *jobs as usize)
+                .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get())),
+            export_options: export_options_from_matches(matches),
+            streaming: matches.get_flag("streaming"),
+            payload_options: payload_options_from_matches(matches),
+            cache_dir: matches.get_one::<String>("cache").cloned(),
+            filter: filter_from_matches(matches),
+            annotations: matches.get_one::<Annotations>("annotate").cloned().unwrap_or_default(),
+            flags: flags_from_matches(matches),
+            blocklist: blocklist_from_matches(matches),
+            threat_intel: threat_intel_from_matches(matches),
+            hunt: matches.get_flag("hunt"),
+            sort_by_time: matches.get_flag("sort-by-time"),
+            business_hours: business_hours_from_matches(matches),
+            consent_url_regexes: matches
+                .get_many::<Regex>("consent-url-regex")
+                .map(|regexes| regexes.cloned().collect())
+                .unwrap_or_default(),
+            retry_window_ms: *matches.get_one::<u64>("retry-window").unwrap(),
+            cache_rules: matches.get_one::<CacheRules>("cache-rules").cloned().unwrap_or_default(),
+            budgets: budgets_from_matches(matches),
+            fail_on_budget: matches.get_flag("fail-on-budget"),
+            notify_webhook: matches.get_one::<String>("notify-webhook").cloned(),
+            upload: matches.get_one::<UploadTarget>("upload").cloned(),
+            encryption: encryption_from_matches(matches),
+            read_mode: read_mode_from_matches(matches),
+        }
+    }
+
+    /// 設定の妥当性を検証
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub(crate) fn validate(&self) -> Result<()> {
+        for input in &self.inputs {
+            if !Path::new(input).exists() {
+                return Err(anyhow::anyhow!("入力ファイルが見つかりません: {}", input));
+            }
+        }
+
+        if Path::new(&self.output_dir).is_file() {
+            return Err(anyhow::anyhow!("出力先はディレクトリである必要があります: {}", self.output_dir));
+        }
+
+        self.export_options.validate(self.streaming)?;
+
+        if let Some(archive_file) = &self.archive_file {
+            validate_archive_file(archive_file)?;
+        }
+
+        Ok(())
+    }
+
+    /// 入力パスを展開し、処理するファイルごとの設定を作成
+    /// 
+    /// ディレクトリが指定された場合は直下の.harファイルを対象とする。
+    /// 出力ファイル名は入力ファイル名の拡張子を.xlsxに置き換えたもの（重複する場合は連番を付加）
+    /// 
+    /// # Returns
+    /// * `Result<Vec<AnalyzeConfig>>` - ファイルごとの設定のリスト
+    fn file_configs(&self) -> Result<Vec<AnalyzeConfig>> {
+        let mut inputs = Vec::new();
+        for input in &self.inputs {
+            let path = Path::new(input);
+            if path.is_dir() {
+                let mut entries: Vec<_> = std::fs::read_dir(path)?
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|p| {
+                        p.is_file()
+                            && p.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("har"))
+                    })
+                    .collect();
+                entries.sort();
+                inputs.extend(entries);
+            } else {
+                inputs.push(path.to_path_buf());
+            }
+        }
+
+        let mut used_names = std::collections::HashSet::new();
+        let configs = inputs
+            .into_iter()
+            .map(|input| {
+                let stem = input
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("har_analysis")
+                    .to_string();
+                let mut name = stem.clone();
+                let mut suffix = 2;
+                while !used_names.insert(name.clone()) {
+                    name = format!("{}_{}", stem, suffix);
+                    suffix += 1;
+                }
+
+                AnalyzeConfig {
+                    input_file: input.to_string_lossy().into_owned(),
+                    output_file: Path::new(&self.output_dir)
+                        .join(format!("{}.xlsx", name))
+                        .to_string_lossy()
+                        .into_owned(),
+                    graph_file: None,
+                    archive_file: None,
+                    stub_format: None,
+                    report_format: None,
+                    export_options: self.export_options.clone(),
+                    streaming: self.streaming,
+                    payload_options: self.payload_options.clone(),
+                    cache_dir: self.cache_dir.clone(),
+                    filter: self.filter.clone(),
+                    annotations: self.annotations.clone(),
+                    flags: self.flags.clone(),
+                    blocklist: self.blocklist.clone(),
+                    threat_intel: self.threat_intel.clone(),
+                    hunt: self.hunt,
+                    sort_by_time: self.sort_by_time,
+                    business_hours: self.business_hours.clone(),
+                    consent_url_regexes: self.consent_url_regexes.clone(),
+                    retry_window_ms: self.retry_window_ms,
+                    cache_rules: self.cache_rules.clone(),
+                    budgets: self.budgets.clone(),
+                    fail_on_budget: self.fail_on_budget,
+                    // バッチ処理の全体の結果をまとめて1回暗号化・アップロード・通知する
+                    notify_webhook: None,
+                    upload: None,
+                    encryption: None,
+                    read_mode: self.read_mode,
+                }
+            })
+            .collect();
+
+        Ok(configs)
+    }
+}
+
+/// 複数のHARファイルを並列に解析
+/// 
+/// 各ファイルの解析はブロッキング処理のため専用のスレッドで実行し、同時に処理する数を`--jobs`で制限する。
+/// 一部のファイルで失敗しても残りのファイルの処理は継続する
+/// 
+/// # Arguments
+/// * `config` - batchサブコマンドの設定
+/// 
+/// # Returns
+/// * `Result<()>` - 全てのファイルの処理に成功した場合はOk、失敗したファイルがある場合はエラー
+pub(crate) async fn run(config: BatchConfig) -> Result<()> {
+    let file_configs = config.file_configs()?;
+    if file_configs.is_empty() {
+        info!("処理対象のHARファイルが見つかりませんでした");
+        return Ok(());
+    }
+    info!("{}件のHARファイルを最大{}件ずつ並列に解析します", file_configs.len(), config.jobs);
+
+    let semaphore = Arc::new(Semaphore::new(config.jobs));
+    let mut tasks = JoinSet::new();
+    for (index, file_config) in file_configs.into_iter().enumerate() {
+        let permit = semaphore.clone().acquire_owned().await?;
+        tasks.spawn_blocking(move || {
+            let _permit = permit;
+            let result = analyze_file(&file_config).and_then(|(summary, written)| {
+                check_budgets(&file_config, &summary)?;
+                Ok((summary, written))
+            });
+            (index, file_config, result)
+        });
+    }
+
+    let mut reports = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        reports.push(joined?);
+    }
+    reports.sort_by_key(|(index, _, _)| *index);
+
+    // ファイルごとの結果を集計
+    info!("バッチ処理の結果:");
+    let mut total = Summary::default();
+    let mut failures = 0;
+    for (_, file_config, result) in &reports {
+        match result {
+            Ok((summary, _)) => {
+                info!("  - {}: {}件 -> {}", file_config.input_file, summary.total, file_config.output_file);
+                total.merge(summary);
+            }
+            Err(e) => {
+                error!("  - {}: 失敗 ({})", file_config.input_file, e);
+                failures += 1;
+            }
+        }
+    }
+    total.log();
+
+    // 失敗したファイルがあっても出力できたファイルはまとめる
+    let mut outputs = Vec::new();
+    let mut workbooks = Vec::new();
+    for (_, file_config, result) in &reports {
+        if let Ok((_, written)) = result {
+            workbooks.push(PathBuf::from(&file_config.output_file));
+            outputs.extend(written.iter().cloned());
+        }
+    }
+    // 同じファイルを重ねて暗号化・アーカイブしないように、重複したパスは最初の1件のみとする
+    let mut seen = HashSet::new();
+    outputs.retain(|path| seen.insert(path.clone()));
+    if let Some(encryption) = &config.encryption {
+        outputs = encryption.apply(outputs, &workbooks)?;
+    }
+    if let Some(archive_file) = &config.archive_file {
+        archive::create(archive_file, &outputs, config.export_options.deterministic)?;
+        outputs = vec![PathBuf::from(archive_file)];
+    }
+    let location = match &config.upload {
+        Some(target) => {
+            let urls = target.upload(&outputs).await?;
+            // アーカイブしない場合はキーの接頭辞（出力ディレクトリに相当）を通知する
+            if config.archive_file.is_some() { urls.into_iter().next() } else { Some(target.to_string()) }
+        }
+        None => Some(notify::output_location(Path::new(
+            config.archive_file.as_ref().unwrap_or(&config.output_dir),
+        ))),
+    };
+
+    if let Some(url) = &config.notify_webhook {
+        let notification = RunNotification {
+            subject: format!("バッチ処理 {}件", reports.len()),
+            summary: &total,
+            outputs: location.into_iter().collect(),
+            failures,
+        };
+        if let Err(e) = notification.post(url) {
+            warn!("{}", e);
+        }
+    }
+
+    if failures > 0 {
+        return Err(anyhow::anyhow!(
+            "{}件中{}件のファイルの処理に失敗しました",
+            reports.len(),
+            failures
+        ));
+    }
+
+    info!("バッチ処理が完了しました");
+    Ok(())
+}
@@ -0,0 +1,127 @@
+//! 合成HARで処理の段階ごとの時間を計測するbenchサブコマンドを実装
+//! 
+//! 同じ内容の合成HARをメモリ上で作成し、読み込み・解析・Excelファイルの出力の処理時間と
+//! スループットを標準出力に表示する。バージョン間で性能の劣化を確認するために使用する
+
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+use rs_har_analyzer::analyzer::HarAnalyzer;
+use rs_har_analyzer::excel_exporter::{ExcelExporter, ExportOptions};
+use rs_har_analyzer::synthetic::synthetic_har;
+use std::fs;
+use std::time::{Duration, Instant};
+
+/// benchサブコマンドの設定
+#[derive(Debug)]
+pub(crate) struct BenchConfig {
+    entries: usize,
+    streaming: bool,
+}
+
+/// benchサブコマンドの定義を作成
+/// 
+/// # Returns
+/// * `Command` - サブコマンドの定義
+pub(crate) fn command() -> Command {
+    Command::new("bench")
+        .about("合成HARをメモリ上で作成し、読み込み・解析・Excelファイルの出力の処理時間とスループットを標準出力に表示")
+        .args(BenchConfig::args())
+}
+
+impl BenchConfig {
+    /// benchサブコマンドの引数定義
+    /// 
+    /// # Returns
+    /// * `Vec<Arg>` - 引数定義のリスト
+    pub(crate) fn args() -> Vec<Arg> {
+        vec![
+            Arg::new("entries")
+                .short('n')
+                .long("entries")
+                .value_name("N")
+                .help("合成するエントリ数")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .default_value("10000"),
+            Arg::new("streaming")
+                .long("streaming")
+                .help("解析結果を保持せず1件ずつ定メモリモードでExcelに書き込む処理を計測（解析と出力を合わせて計測）")
+                .action(clap::ArgAction::SetTrue),
+        ]
+    }
+
+    /// コマンドライン引数から設定を作成
+    /// 
+    /// # Arguments
+    /// * `matches` - 解析済みのコマンドライン引数
+    /// 
+    /// # Returns
+    /// * `BenchConfig` - benchサブコマンドの設定
+    pub(crate) fn from_matches(matches: &ArgMatches) -> Self {
+        BenchConfig {
+            entries: *matches.get_one::<u64>("entries").unwrap() as usize,
+            streaming: matches.get_flag("streaming"),
+        }
+    }
+}
+
+/// 合成HARで処理の段階ごとの時間を計測
+/// 
+/// # Arguments
+/// * `config` - benchサブコマンドの設定
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub(crate) async fn run(config: BenchConfig) -> Result<()> {
+    let mut stages: Vec<(&str, Duration)> = Vec::new();
+
+    let start = Instant::now();
+    let content = synthetic_har(config.entries);
+    stages.push(("generate", start.elapsed()));
+    let har_size = content.len();
+
+    let start = Instant::now();
+    let analyzer = HarAnalyzer::from_bytes(content.as_bytes())?;
+    stages.push(("parse", start.elapsed()));
+    drop(content);
+
+    let output = std::env::temp_dir().join(format!("rs_har_analyzer_bench_{}.xlsx", std::process::id()));
+    let output_path = output.to_string_lossy().into_owned();
+    let options = ExportOptions::default();
+    if config.streaming {
+        let start = Instant::now();
+        ExcelExporter::export_streaming(analyzer.events(), &output_path, &options)?;
+        stages.push(("analyze+export", start.elapsed()));
+    } else {
+        let start = Instant::now();
+        let events = analyzer.analyze_events()?;
+        stages.push(("analyze", start.elapsed()));
+
+        let start = Instant::now();
+        ExcelExporter::export(&events, &output_path, &options)?;
+        stages.push(("export", start.elapsed()));
+    }
+    let _ = fs::remove_file(&output);
+
+    let total: Duration = stages.iter().skip(1).map(|(_, elapsed)| *elapsed).sum();
+    stages.push(("total", total));
+
+    println!(
+        "{} {} / entries: {}, HAR size: {:.1} MiB",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        config.entries,
+        har_size as f64 / 1024.0 / 1024.0
+    );
+    println!("{:<16} {:>12} {:>14} {:>10}", "stage", "time (ms)", "entries/s", "MiB/s");
+    for (name, elapsed) in stages {
+        let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+        println!(
+            "{:<16} {:>12.1} {:>14.0} {:>10.1}",
+            name,
+            seconds * 1000.0,
+            config.entries as f64 / seconds,
+            har_size as f64 / 1024.0 / 1024.0 / seconds
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! 2つのHARファイルのレスポンスを比較してExcelファイルに出力するcompareサブコマンドを実装
+
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+use log::info;
+use rs_har_analyzer::analyzer::{HarAnalyzer, ReadMode};
+use rs_har_analyzer::compare;
+use std::path::Path;
+use super::read_mode_from_matches;
+
+/// compareサブコマンドの設定
+#[derive(Debug)]
+pub(crate) struct CompareConfig {
+    input_file: String,
+    against_file: String,
+    output_file: String,
+    read_mode: ReadMode,
+}
+
+/// compareサブコマンドの定義を作成
+/// 
+/// # Returns
+/// * `Command` - サブコマンドの定義
+pub(crate) fn command() -> Command {
+    Command::new("compare")
+        .about("2つのHARファイルのエンドポイントごとの呼び出しを対応付け、ステータス・所要時間の変化とJSONの差分をExcelファイルに出力")
+        .args(CompareConfig::args())
+}
+
+impl CompareConfig {
+    /// compareサブコマンドの引数定義
+    /// 
+    /// # Returns
+    /// * `Vec<Arg>` - 引数定義のリスト
+    pub(crate) fn args() -> Vec<Arg> {
+        vec![
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("FILE")
+                .help("比較元のHARファイルのパス")
+                .required(true),
+            Arg::new("against")
+                .long("against")
+                .value_name("FILE")
+                .help("比較先のHARファイルのパス")
+                .required(true),
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("FILE")
+                .help("出力するExcelファイルのパス")
+                .default_value("har_compare.xlsx"),
+        ]
+    }
+
+    /// コマンドライン引数から設定を作成
+    /// 
+    /// # Arguments
+    /// * `matches` - 解析済みのコマンドライン引数
+    /// 
+    /// # Returns
+    /// * `CompareConfig` - compareサブコマンドの設定
+    pub(crate) fn from_matches(matches: &ArgMatches) -> Self {
+        CompareConfig {
+            input_file: matches.get_one::<String>("input").unwrap().clone(),
+            against_file: matches.get_one::<String>("against").unwrap().clone(),
+            output_file: matches.get_one::<String>("output").unwrap().clone(),
+            read_mode: read_mode_from_matches(matches),
+        }
+    }
+
+    /// 設定の妥当性を検証
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub(crate) fn validate(&self) -> Result<()> {
+        for file in [&self.input_file, &self.against_file] {
+            if !Path::new(file).exists() {
+                return Err(anyhow::anyhow!("入力ファイルが見つかりません: {}", file));
+            }
+        }
+
+        if !self.output_file.to_lowercase().ends_with(".xlsx") {
+            return Err(anyhow::anyhow!("出力ファイルはExcelファイル(.xlsx)である必要があります"));
+        }
+
+        Ok(())
+    }
+}
+
+/// 2つのHARファイルのレスポンスを比較してExcelファイルに出力
+/// 
+/// # Arguments
+/// * `config` - compareサブコマンドの設定
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub(crate) async fn run(config: CompareConfig) -> Result<()> {
+    info!("HARファイルの比較を開始します");
+
+    let before = HarAnalyzer::with_read_mode(&config.input_file, config.read_mode)?;
+    let after = HarAnalyzer::with_read_mode(&config.against_file, config.read_mode)?;
+    let compared = compare::compare(&before.har_data().log.entries, &after.har_data().log.entries);
+
+    let unmatched = compared
+        .iter()
+        .filter(|response| response.before.is_none() || response.after.is_none())
+        .count();
+    let status_changed = compared.iter().filter(|response| response.status_changed()).count();
+    let body_changed = compared.iter().filter(|response| response.body.is_some()).count();
+    info!(
+        "比較: {}件の呼び出し（一方にのみ存在: {}件、ステータスの変化: {}件、レスポンスボディの変化: {}件）",
+        compared.len(),
+        unmatched,
+        status_changed,
+        body_changed
+    );
+
+    compare::export(&compared, &config.output_file)?;
+
+    info!("HARファイルの比較が完了しました");
+    Ok(())
+}
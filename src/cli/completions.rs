@@ -0,0 +1,78 @@
+//! シェルの補完スクリプトとmanページを出力するcompletionsサブコマンドを実装
+
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+use clap_complete::Shell;
+use super::Cli;
+
+/// completionsサブコマンドの設定
+#[derive(Debug)]
+pub(crate) struct CompletionsConfig {
+    /// 補完スクリプトを出力するシェル（Noneの場合はmanページを出力）
+    shell: Option<Shell>,
+}
+
+/// completionsサブコマンドの定義を作成
+/// 
+/// # Returns
+/// * `Command` - サブコマンドの定義
+pub(crate) fn command() -> Command {
+    Command::new("completions")
+        .about("シェルの補完スクリプト（bash・zsh・fish・PowerShell・elvish）またはmanページを標準出力に出力")
+        .args(CompletionsConfig::args())
+}
+
+impl CompletionsConfig {
+    /// completionsサブコマンドの引数定義
+    /// 
+    /// # Returns
+    /// * `Vec<Arg>` - 引数定義のリスト
+    pub(crate) fn args() -> Vec<Arg> {
+        vec![
+            Arg::new("shell")
+                .value_name("SHELL")
+                .help("補完スクリプトを出力するシェル")
+                .value_parser(clap::value_parser!(Shell))
+                .required_unless_present("man")
+                .index(1),
+            Arg::new("man")
+                .long("man")
+                .help("補完スクリプトの代わりにmanページ（roff形式）を出力")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("shell"),
+        ]
+    }
+
+    /// コマンドライン引数から設定を作成
+    /// 
+    /// # Arguments
+    /// * `matches` - 解析済みのコマンドライン引数
+    /// 
+    /// # Returns
+    /// * `CompletionsConfig` - completionsサブコマンドの設定
+    pub(crate) fn from_matches(matches: &ArgMatches) -> Self {
+        CompletionsConfig {
+            shell: matches.get_one::<Shell>("shell").copied(),
+        }
+    }
+}
+
+/// シェルの補完スクリプトまたはmanページを標準出力に出力
+/// 
+/// # Arguments
+/// * `config` - completionsサブコマンドの設定
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub(crate) async fn run(config: CompletionsConfig) -> Result<()> {
+    let mut command = Cli::command();
+    let mut stdout = std::io::stdout();
+    match config.shell {
+        Some(shell) => {
+            let name = command.get_name().to_string();
+            clap_complete::generate(shell, &mut command, name, &mut stdout);
+        }
+        None => clap_mangen::Man::new(command).render(&mut stdout)?,
+    }
+    Ok(())
+}
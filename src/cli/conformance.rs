@@ -0,0 +1,115 @@
+//! HARファイルをOpenAPI仕様書と突き合わせるconformanceサブコマンドを実装
+
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+use log::info;
+use rs_har_analyzer::analyzer::{HarAnalyzer, ReadMode};
+use rs_har_analyzer::conformance::ConformanceReport;
+use rs_har_analyzer::openapi::OpenApiSpec;
+use std::path::Path;
+use super::read_mode_from_matches;
+
+/// conformanceサブコマンドの設定
+#[derive(Debug)]
+pub(crate) struct ConformanceConfig {
+    input_file: String,
+    spec_file: String,
+    output_file: String,
+    read_mode: ReadMode,
+}
+
+/// conformanceサブコマンドの定義を作成
+/// 
+/// # Returns
+/// * `Command` - サブコマンドの定義
+pub(crate) fn command() -> Command {
+    Command::new("conformance")
+        .about("HARファイルをOpenAPI仕様書と突き合わせ、未記載のエンドポイント・想定外のステータスコード・スキーマ違反を出力")
+        .args(ConformanceConfig::args())
+}
+
+impl ConformanceConfig {
+    /// conformanceサブコマンドの引数定義
+    /// 
+    /// # Returns
+    /// * `Vec<Arg>` - 引数定義のリスト
+    pub(crate) fn args() -> Vec<Arg> {
+        vec![
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("FILE")
+                .help("入力するHARファイルのパス")
+                .required(true),
+            Arg::new("spec")
+                .short('s')
+                .long("spec")
+                .value_name("FILE")
+                .help("OpenAPI仕様書（YAML/JSON）のパス")
+                .required(true),
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("FILE")
+                .help("不一致の一覧を出力するExcelファイルのパス")
+                .default_value("conformance.xlsx"),
+        ]
+    }
+
+    /// コマンドライン引数から設定を作成
+    /// 
+    /// # Arguments
+    /// * `matches` - 解析済みのコマンドライン引数
+    /// 
+    /// # Returns
+    /// * `ConformanceConfig` - conformanceサブコマンドの設定
+    pub(crate) fn from_matches(matches: &ArgMatches) -> Self {
+        ConformanceConfig {
+            input_file: matches.get_one::<String>("input").unwrap().clone(),
+            spec_file: matches.get_one::<String>("spec").unwrap().clone(),
+            output_file: matches.get_one::<String>("output").unwrap().clone(),
+            read_mode: read_mode_from_matches(matches),
+        }
+    }
+
+    /// 設定の妥当性を検証
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !Path::new(&self.input_file).exists() {
+            return Err(anyhow::anyhow!("入力ファイルが見つかりません: {}", self.input_file));
+        }
+
+        if !Path::new(&self.spec_file).exists() {
+            return Err(anyhow::anyhow!("OpenAPI仕様書が見つかりません: {}", self.spec_file));
+        }
+
+        if !self.output_file.to_lowercase().ends_with(".xlsx") {
+            return Err(anyhow::anyhow!("出力ファイルはExcelファイル(.xlsx)である必要があります"));
+        }
+
+        Ok(())
+    }
+}
+
+/// HARファイルをOpenAPI仕様書と突き合わせ
+/// 
+/// # Arguments
+/// * `config` - conformanceサブコマンドの設定
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub(crate) async fn run(config: ConformanceConfig) -> Result<()> {
+    info!("OpenAPI仕様書との突き合わせを開始します");
+
+    let spec = OpenApiSpec::load(&config.spec_file)?;
+    let analyzer = HarAnalyzer::with_read_mode(&config.input_file, config.read_mode)?;
+
+    let report = ConformanceReport::check(&spec, analyzer.har_data());
+    report.log();
+    report.export(&config.output_file)?;
+
+    info!("OpenAPI仕様書との突き合わせが完了しました");
+    Ok(())
+}
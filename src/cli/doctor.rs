@@ -0,0 +1,255 @@
+//! 実行環境を確認するdoctorサブコマンドを実装
+//! 
+//! 出力先・一時ディレクトリへの書き込み、設定ファイル等の読み込みを確認し、
+//! 組み込みのサンプルのHARを解析からExcelファイルの出力まで通して実行する。
+//! 制限の厳しい環境に導入した際に、ツールが動作するかを1回のコマンドで確かめるために使用する
+
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+use log::{error, info};
+use rs_har_analyzer::config::FileConfig;
+use rs_har_analyzer::grpc_web;
+use rs_har_analyzer::trackers::Blocklist;
+use std::fs;
+use std::path::{Path, PathBuf};
+use super::analyze::{AnalyzeConfig, analyze_file};
+use super::Cli;
+
+/// 解析を確認するための組み込みのサンプルのHAR（JSONのGETとPOSTの2件）
+const SAMPLE_HAR: &str = r#"{"log":{"version":"1.2","creator":{"name":"WebInspector","version":"537.36"},
+"pages":[{"startedDateTime":"2024-01-01T00:00:00.000Z","id":"page_1","title":"https://example.com/","pageTimings":{"onContentLoad":100,"onLoad":200}}],
+"entries":[
+{"pageref":"page_1","startedDateTime":"2024-01-01T00:00:00.010Z","time":50,"request":{"method":"GET","url":"https://example.com/api/items?q=a","httpVersion":"HTTP/1.1","headers":[{"name":"Accept","value":"application/json"}],"queryString":[{"name":"q","value":"a"}],"cookies":[],"headersSize":-1,"bodySize":0},"response":{"status":200,"statusText":"OK","httpVersion":"HTTP/1.1","headers":[{"name":"Content-Type","value":"application/json"}],"cookies":[],"content":{"size":24,"mimeType":"application/json","text":"{\"items\":[{\"id\":1}],\"n\":1}"},"redirectURL":"","headersSize":-1,"bodySize":24},"cache":{},"timings":{"send":1,"wait":40,"receive":9}},
+{"pageref":"page_1","startedDateTime":"2024-01-01T00:00:00.100Z","time":80,"request":{"method":"POST","url":"https://example.com/api/items","httpVersion":"HTTP/1.1","headers":[{"name":"Content-Type","value":"application/json"}],"queryString":[],"cookies":[],"headersSize":-1,"bodySize":10,"postData":{"mimeType":"application/json","text":"{\"id\":2}"}},"response":{"status":201,"statusText":"Created","httpVersion":"HTTP/1.1","headers":[{"name":"Content-Type","value":"application/json"}],"cookies":[],"content":{"size":8,"mimeType":"application/json","text":"{\"id\":2}"},"redirectURL":"","headersSize":-1,"bodySize":8},"cache":{},"timings":{"send":1,"wait":70,"receive":9}}
+]}}"#;
+
+/// doctorサブコマンドの設定
+#[derive(Debug)]
+pub(crate) struct DoctorConfig {
+    output_dir: String,
+    config_file: Option<String>,
+    proto_descriptors: Vec<String>,
+    blocklists: Vec<String>,
+}
+
+/// doctorサブコマンドの定義を作成
+/// 
+/// # Returns
+/// * `Command` - サブコマンドの定義
+pub(crate) fn command() -> Command {
+    Command::new("doctor")
+        .about("出力先への書き込み・設定ファイル等の読み込みを確認し、組み込みのサンプルのHARを解析して導入した環境で動作するかを確認")
+        .args(DoctorConfig::args())
+}
+
+impl DoctorConfig {
+    /// doctorサブコマンドの引数定義
+    /// 
+    /// # Returns
+    /// * `Vec<Arg>` - 引数定義のリスト
+    pub(crate) fn args() -> Vec<Arg> {
+        vec![
+            Arg::new("output-dir")
+                .short('d')
+                .long("output-dir")
+                .value_name("DIR")
+                .help("書き込みを確認する出力先のディレクトリ")
+                .default_value("."),
+            Arg::new("config")
+                .short('c')
+                .long("config")
+                .value_name("FILE")
+                .help("読み込みを確認する設定ファイル（TOML）のパス"),
+            Arg::new("proto-descriptor")
+                .long("proto-descriptor")
+                .value_name("FILE")
+                .help("読み込みを確認するgRPC-webの記述子セットのパス（複数指定可）")
+                .action(clap::ArgAction::Append),
+            Arg::new("blocklist")
+                .long("blocklist")
+                .value_name("[CATEGORY=]FILE")
+                .help("読み込みを確認するフィルタリストのパス（複数指定可）")
+                .action(clap::ArgAction::Append),
+        ]
+    }
+
+    /// コマンドライン引数から設定を作成
+    /// 
+    /// # Arguments
+    /// * `matches` - 解析済みのコマンドライン引数
+    /// 
+    /// # Returns
+    /// * `DoctorConfig` - doctorサブコマンドの設定
+    pub(crate) fn from_matches(matches: &ArgMatches) -> Self {
+        let values = |id: &str| -> Vec<String> {
+            matches
+                .get_many::<String>(id)
+                .map(|values| values.cloned().collect())
+                .unwrap_or_default()
+        };
+        DoctorConfig {
+            output_dir: matches.get_one::<String>("output-dir").unwrap().clone(),
+            config_file: matches.get_one::<String>("config").cloned(),
+            proto_descriptors: values("proto-descriptor"),
+            blocklists: values("blocklist"),
+        }
+    }
+
+    /// 設定の妥当性を検証
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub(crate) fn validate(&self) -> Result<()> {
+        if Path::new(&self.output_dir).is_file() {
+            return Err(anyhow::anyhow!("出力先はディレクトリである必要があります: {}", self.output_dir));
+        }
+        Ok(())
+    }
+}
+
+/// 確認の結果
+struct Checks {
+    /// 失敗した確認の数
+    failures: usize,
+}
+
+impl Checks {
+    /// 確認の結果を出力
+    /// 
+    /// # Arguments
+    /// * `name` - 確認した項目
+    /// * `result` - 確認の結果
+    fn report(&mut self, name: &str, result: Result<String>) {
+        match result {
+            Ok(detail) => info!("[OK] {}: {}", name, detail),
+            Err(e) => {
+                error!("[NG] {}: {}", name, e);
+                self.failures += 1;
+            }
+        }
+    }
+}
+
+/// 実行環境を確認
+/// 
+/// # Arguments
+/// * `config` - doctorサブコマンドの設定
+/// 
+/// # Returns
+/// * `Result<()>` - 全ての確認に成功した場合はOk、失敗した確認がある場合はエラー
+pub(crate) async fn run(config: DoctorConfig) -> Result<()> {
+    info!("実行環境を確認しています");
+    let mut checks = Checks { failures: 0 };
+
+    checks.report(
+        "バージョン",
+        Ok(format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))),
+    );
+    checks.report("出力先への書き込み", check_writable(Path::new(&config.output_dir)));
+    // --streaming・Excelファイルの作成は一時ディレクトリに作業ファイルを書き込む
+    checks.report("一時ディレクトリへの書き込み", check_writable(&std::env::temp_dir()));
+
+    if let Some(config_file) = &config.config_file {
+        let result = FileConfig::load(config_file)
+            .map(|file_config| {
+                format!(
+                    "{} (プロファイル{}件、MIMEタイプの分類{}件、変換処理{}件)",
+                    config_file,
+                    file_config.profile.len(),
+                    file_config.mime_types.len(),
+                    file_config.transform.len()
+                )
+            })
+            .map_err(Into::into);
+        checks.report("設定ファイル", result);
+    }
+    for path in &config.proto_descriptors {
+        let result = grpc_web::load_descriptor_pool(path)
+            .map(|pool| format!("{} (サービス{}件)", path, pool.services().count()))
+            .map_err(Into::into);
+        checks.report("gRPC-webの記述子セット", result);
+    }
+    for spec in &config.blocklists {
+        let result = Blocklist::load(spec).map(|_| spec.clone()).map_err(Into::into);
+        checks.report("フィルタリスト", result);
+    }
+
+    checks.report("サンプルのHARの解析", check_pipeline(false));
+    checks.report("サンプルのHARの解析（--streaming）", check_pipeline(true));
+
+    if checks.failures > 0 {
+        return Err(anyhow::anyhow!("{}件の確認に失敗しました", checks.failures));
+    }
+    info!("全ての確認に成功しました");
+    Ok(())
+}
+
+/// ディレクトリにファイルを書き込めるかを確認
+/// 
+/// # Arguments
+/// * `dir` - ディレクトリのパス
+/// 
+/// # Returns
+/// * `Result<String>` - 成功時は確認したディレクトリ、失敗時はエラー
+fn check_writable(dir: &Path) -> Result<String> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!(".rs_har_analyzer_doctor_{}", std::process::id()));
+    fs::write(&path, b"doctor")?;
+    fs::remove_file(&path)?;
+    Ok(dir.display().to_string())
+}
+
+/// 組み込みのサンプルのHARを解析してExcelファイルに出力できるかを確認
+/// 
+/// 一時ディレクトリにHARを書き出し、analyzeサブコマンドの既定のオプションで解析する
+/// 
+/// # Arguments
+/// * `streaming` - `--streaming`で出力するかどうか
+/// 
+/// # Returns
+/// * `Result<String>` - 成功時は出力した行数、失敗時はエラー
+fn check_pipeline(streaming: bool) -> Result<String> {
+    let dir = std::env::temp_dir().join(format!("rs_har_analyzer_doctor_{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let result = run_sample(&dir, streaming);
+    fs::remove_dir_all(&dir)?;
+    result
+}
+
+/// サンプルのHARを解析
+/// 
+/// # Arguments
+/// * `dir` - 作業ディレクトリ
+/// * `streaming` - `--streaming`で出力するかどうか
+/// 
+/// # Returns
+/// * `Result<String>` - 成功時は出力した行数、失敗時はエラー
+fn run_sample(dir: &Path, streaming: bool) -> Result<String> {
+    let input: PathBuf = dir.join("sample.har");
+    let output: PathBuf = dir.join("sample.xlsx");
+    fs::write(&input, SAMPLE_HAR)?;
+
+    let mut args = vec![
+        "rs_har_analyzer".to_string(),
+        "analyze".to_string(),
+        "-i".to_string(),
+        input.display().to_string(),
+        "-o".to_string(),
+        output.display().to_string(),
+    ];
+    if streaming {
+        args.push("--streaming".to_string());
+    }
+    let matches = Cli::command().try_get_matches_from(args)?;
+    let Some(("analyze", sub_matches)) = matches.subcommand() else {
+        return Err(anyhow::anyhow!("analyzeサブコマンドの引数を解析できませんでした"));
+    };
+    let analyze_config = AnalyzeConfig::from_matches(sub_matches);
+    analyze_config.validate()?;
+
+    let (summary, _) = analyze_file(&analyze_config)?;
+    if !output.exists() {
+        return Err(anyhow::anyhow!("Excelファイルが出力されませんでした"));
+    }
+    Ok(format!("{}件のリクエストを出力しました", summary.total))
+}
@@ -0,0 +1,167 @@
+//! URL・ヘッダー・ボディを正規表現で検索するgrepサブコマンドを実装
+
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+use encoding_rs::Encoding;
+use log::info;
+use regex::{Regex, RegexBuilder};
+use rs_har_analyzer::analyzer::{HarAnalyzer, ReadMode};
+use rs_har_analyzer::error::AnalyzerError;
+use rs_har_analyzer::payload::PayloadOptions;
+use rs_har_analyzer::search::{self, OutputMode};
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use super::{read_mode_from_matches, parse_charset};
+
+/// 終了コード: 一致するエントリが無い（grepコマンドと同じ）
+const EXIT_NO_MATCH: i32 = 1;
+
+/// grepサブコマンドの設定
+#[derive(Debug)]
+pub(crate) struct GrepConfig {
+    input_file: String,
+    pattern: String,
+    ignore_case: bool,
+    mode: OutputMode,
+    color: bool,
+    payload_options: PayloadOptions,
+    read_mode: ReadMode,
+}
+
+/// grepサブコマンドの定義を作成
+/// 
+/// # Returns
+/// * `Command` - サブコマンドの定義
+pub(crate) fn command() -> Command {
+    Command::new("grep")
+        .about("URL・ヘッダー・デコードしたボディを正規表現で検索し、一致したエントリと行を端末に表示")
+        .after_help("一致するエントリがある場合は終了コード0、無い場合は1で終了します（エラーの場合は2以上）")
+        .args(GrepConfig::args())
+}
+
+impl GrepConfig {
+    /// grepサブコマンドの引数定義
+    /// 
+    /// # Returns
+    /// * `Vec<Arg>` - 引数定義のリスト
+    pub(crate) fn args() -> Vec<Arg> {
+        vec![
+            Arg::new("pattern")
+                .value_name("PATTERN")
+                .help("検索する正規表現")
+                .required(true)
+                .index(1),
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("FILE")
+                .help("入力するHARファイルのパス")
+                .required(true),
+            Arg::new("count")
+                .short('c')
+                .long("count")
+                .help("一致したエントリの数のみを表示")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("urls-only"),
+            Arg::new("urls-only")
+                .short('l')
+                .long("urls-only")
+                .help("一致したエントリのURLのみを表示")
+                .action(clap::ArgAction::SetTrue),
+            Arg::new("ignore-case")
+                .long("ignore-case")
+                .help("大文字と小文字を区別せずに検索")
+                .action(clap::ArgAction::SetTrue),
+            Arg::new("no-color")
+                .long("no-color")
+                .help("色を付けずに出力（環境変数NO_COLORが設定されている場合と出力先が端末ではない場合も色を付けない）")
+                .action(clap::ArgAction::SetTrue),
+            Arg::new("charset-override")
+                .long("charset-override")
+                .value_name("CHARSET")
+                .help("レスポンスボディの文字コードを指定（Content-Typeのcharsetより優先。例: Shift_JIS, EUC-JP）")
+                .value_parser(parse_charset),
+        ]
+    }
+
+    /// コマンドライン引数から設定を作成
+    /// 
+    /// # Arguments
+    /// * `matches` - 解析済みのコマンドライン引数
+    /// 
+    /// # Returns
+    /// * `GrepConfig` - grepサブコマンドの設定
+    pub(crate) fn from_matches(matches: &ArgMatches) -> Self {
+        let mode = if matches.get_flag("count") {
+            OutputMode::Count
+        } else if matches.get_flag("urls-only") {
+            OutputMode::UrlsOnly
+        } else {
+            OutputMode::Lines
+        };
+        GrepConfig {
+            input_file: matches.get_one::<String>("input").unwrap().clone(),
+            pattern: matches.get_one::<String>("pattern").unwrap().clone(),
+            ignore_case: matches.get_flag("ignore-case"),
+            mode,
+            color: !matches.get_flag("no-color")
+                && std::env::var_os("NO_COLOR").is_none()
+                && std::io::stdout().is_terminal(),
+            payload_options: PayloadOptions {
+                charset_override: matches.get_one::<&'static Encoding>("charset-override").copied(),
+                ..PayloadOptions::default()
+            },
+            read_mode: read_mode_from_matches(matches),
+        }
+    }
+
+    /// 検索する正規表現を作成
+    /// 
+    /// # Returns
+    /// * `Result<Regex, AnalyzerError>` - 成功時は正規表現、失敗時は引数エラー（終了コード2）
+    fn regex(&self) -> Result<Regex, AnalyzerError> {
+        RegexBuilder::new(&self.pattern)
+            .case_insensitive(self.ignore_case)
+            .build()
+            .map_err(|e| AnalyzerError::InvalidArgument(format!("正規表現が不正です: {}: {}", self.pattern, e)))
+    }
+
+    /// 設定の妥当性を検証
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !Path::new(&self.input_file).exists() {
+            return Err(AnalyzerError::InvalidArgument(format!("入力ファイルが見つかりません: {}", self.input_file)).into());
+        }
+
+        self.regex()?;
+        Ok(())
+    }
+}
+
+/// URL・ヘッダー・デコードしたボディを正規表現で検索して端末に表示
+/// 
+/// 一致しない場合の終了コード1と区別できるように、エラーは全て`AnalyzerError`（終了コード2以上）で返す
+/// 
+/// # Arguments
+/// * `config` - grepサブコマンドの設定
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub(crate) async fn run(config: GrepConfig) -> Result<()> {
+    let regex = config.regex()?;
+    let analyzer = HarAnalyzer::with_read_mode(&config.input_file, config.read_mode)?
+        .with_payload_options(config.payload_options);
+    let matches = search::search(&analyzer, &regex);
+    let mut stdout = std::io::stdout();
+    write!(stdout, "{}", search::render(&matches, &regex, config.mode, config.color))
+        .and_then(|_| stdout.flush())
+        .map_err(AnalyzerError::io("検索結果の出力に失敗しました"))?;
+    if matches.is_empty() {
+        // grepコマンドと同様に、一致しない場合はエラーを出力せずに終了コード1で終了する
+        info!("一致するエントリはありません: {}", config.pattern);
+        std::process::exit(EXIT_NO_MATCH);
+    }
+    Ok(())
+}
@@ -0,0 +1,132 @@
+//! HARファイルに記録されたレスポンスを返すモックサーバーを起動するmockサブコマンドを実装
+
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+use log::{info, warn};
+use rs_har_analyzer::analyzer::{HarAnalyzer, ReadMode};
+use rs_har_analyzer::mock_server::{self, MockOptions, MockRoutes};
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use super::read_mode_from_matches;
+
+/// mockサブコマンドの設定
+#[derive(Debug)]
+pub(crate) struct MockConfig {
+    input_file: String,
+    host: String,
+    port: u16,
+    options: MockOptions,
+    read_mode: ReadMode,
+}
+
+/// mockサブコマンドの定義を作成
+/// 
+/// # Returns
+/// * `Command` - サブコマンドの定義
+pub(crate) fn command() -> Command {
+    Command::new("mock")
+        .visible_alias("serve")
+        .about("HARファイルに記録されたレスポンスをHTTPで返すモックサーバーを起動")
+        .args(MockConfig::args())
+}
+
+impl MockConfig {
+    /// mockサブコマンドの引数定義
+    /// 
+    /// # Returns
+    /// * `Vec<Arg>` - 引数定義のリスト
+    pub(crate) fn args() -> Vec<Arg> {
+        vec![
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("FILE")
+                .help("入力するHARファイルのパス")
+                .required(true),
+            Arg::new("host")
+                .long("host")
+                .value_name("ADDRESS")
+                .help("待ち受けるアドレス")
+                .default_value("127.0.0.1"),
+            Arg::new("port")
+                .short('p')
+                .long("port")
+                .value_name("PORT")
+                .help("待ち受けるポート番号")
+                .value_parser(clap::value_parser!(u16))
+                .default_value("8080"),
+            Arg::new("match-body")
+                .long("match-body")
+                .help("メソッドとパスに加えてリクエストボディも記録と照合")
+                .action(clap::ArgAction::SetTrue),
+            Arg::new("match-host")
+                .long("match-host")
+                .help("メソッドとパスに加えてホスト名（Hostヘッダー、ポート番号を除く）も記録と照合。複数のホストで同じパスを使う場合に指定")
+                .action(clap::ArgAction::SetTrue),
+            Arg::new("latency")
+                .long("latency")
+                .help("記録された所要時間だけ待ってから応答")
+                .action(clap::ArgAction::SetTrue),
+        ]
+    }
+
+    /// コマンドライン引数から設定を作成
+    /// 
+    /// # Arguments
+    /// * `matches` - 解析済みのコマンドライン引数
+    /// 
+    /// # Returns
+    /// * `MockConfig` - mockサブコマンドの設定
+    pub(crate) fn from_matches(matches: &ArgMatches) -> Self {
+        MockConfig {
+            input_file: matches.get_one::<String>("input").unwrap().clone(),
+            host: matches.get_one::<String>("host").unwrap().clone(),
+            port: *matches.get_one::<u16>("port").unwrap(),
+            options: MockOptions {
+                match_body: matches.get_flag("match-body"),
+                match_host: matches.get_flag("match-host"),
+                simulate_latency: matches.get_flag("latency"),
+            },
+            read_mode: read_mode_from_matches(matches),
+        }
+    }
+
+    /// 設定の妥当性を検証
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !Path::new(&self.input_file).exists() {
+            return Err(anyhow::anyhow!("入力ファイルが見つかりません: {}", self.input_file));
+        }
+
+        if self.host.parse::<IpAddr>().is_err() {
+            return Err(anyhow::anyhow!("待ち受けるアドレスはIPアドレスで指定してください: {}", self.host));
+        }
+
+        Ok(())
+    }
+}
+
+/// HARファイルに記録されたレスポンスを返すモックサーバーを起動
+/// 
+/// # Arguments
+/// * `config` - mockサブコマンドの設定
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub(crate) async fn run(config: MockConfig) -> Result<()> {
+    let analyzer = HarAnalyzer::with_read_mode(&config.input_file, config.read_mode)?;
+    let routes = MockRoutes::from_har(analyzer.har_data());
+    drop(analyzer);
+
+    if routes.is_empty() {
+        warn!("応答できる記録がありません。全てのリクエストに404を返します");
+    } else {
+        info!("{}件のメソッドとパスの組み合わせを読み込みました", routes.len());
+    }
+
+    let host: IpAddr = config.host.parse()?;
+    mock_server::serve(routes, SocketAddr::new(host, config.port), config.options).await?;
+    Ok(())
+}
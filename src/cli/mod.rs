@@ -0,0 +1,261 @@
+//! コマンドライン引数の定義と解析、サブコマンドの振り分けを実装
+//! 
+//! サブコマンドごとのモジュールが引数の定義・設定の作成・検証・実行を持ち、
+//! このモジュールは全体で共通の引数（-v・--no-mmap）とサブコマンドの一覧を管理する
+
+mod analyze;
+mod batch;
+mod bench;
+mod compare;
+mod completions;
+mod conformance;
+mod doctor;
+mod grep;
+mod mock;
+mod options;
+mod pair_diff;
+mod profile;
+mod show;
+mod transform;
+mod trend;
+
+use analyze::AnalyzeConfig;
+use anyhow::Result;
+use batch::BatchConfig;
+use bench::BenchConfig;
+use clap::{Arg, ArgMatches, Command};
+use compare::CompareConfig;
+use completions::CompletionsConfig;
+use conformance::ConformanceConfig;
+use doctor::DoctorConfig;
+use encoding_rs::Encoding;
+use grep::GrepConfig;
+use mock::MockConfig;
+use pair_diff::PairDiffConfig;
+use rs_har_analyzer::analyzer::ReadMode;
+use show::ShowConfig;
+use transform::TransformConfig;
+use trend::TrendConfig;
+
+/// コマンドライン引数の解析結果
+#[derive(Debug)]
+pub(crate) struct Cli {
+    pub(crate) verbose: bool,
+    pub(crate) command: CliCommand,
+}
+
+/// 実行するコマンド
+#[derive(Debug)]
+pub(crate) enum CliCommand {
+    /// HARファイルを解析してExcelファイルに出力
+    Analyze(Box<AnalyzeConfig>),
+    /// HARファイルを変換して新しいHARファイルに出力
+    Transform(TransformConfig),
+    /// 複数のHARファイルを並列に解析してExcelファイルに出力
+    Batch(Box<BatchConfig>),
+    /// HARファイルをOpenAPI仕様書と突き合わせて不一致をExcelファイルに出力
+    Conformance(ConformanceConfig),
+    /// 2つのHARファイルのレスポンスを比較してExcelファイルに出力
+    Compare(CompareConfig),
+    /// HARファイルに記録されたレスポンスを返すモックサーバーを起動
+    Mock(MockConfig),
+    /// 1件のエントリのリクエスト・レスポンスを端末に表示
+    Show(ShowConfig),
+    /// URL・ヘッダー・デコードしたボディを正規表現で検索して端末に表示
+    Grep(GrepConfig),
+    /// 同じエンドポイントへの連続した呼び出しのレスポンスボディの変化を端末に表示
+    PairDiff(PairDiffConfig),
+    /// エンドポイントごとの集計をデータベースに追記して推移をExcelファイルに出力
+    Trend(TrendConfig),
+    /// シェルの補完スクリプトまたはmanページを出力
+    Completions(CompletionsConfig),
+    /// 出力先への書き込み・設定ファイル等の読み込み・サンプルのHARの解析を確認
+    Doctor(DoctorConfig),
+    /// 合成HARで読み込み・解析・出力の処理時間を計測して端末に表示
+    Bench(BenchConfig),
+}
+
+impl Cli {
+    /// コマンドライン引数の定義を作成（補完スクリプト・manページの生成にも使用）
+    /// 
+    /// サブコマンドを省略した場合はanalyzeサブコマンドとして解析する（以前のバージョンとの互換性のため）
+    /// 
+    /// # Returns
+    /// * `Command` - コマンドライン引数の定義
+    pub(crate) fn command() -> Command {
+        Command::new("rs_har_analyzer")
+            .version("0.1.0")
+            .author("Your Name <your.email@example.com>")
+            .about("HARファイルを解析してExcelファイルに出力するツール")
+            .subcommand_negates_reqs(true)
+            .args_conflicts_with_subcommands(true)
+            .args(AnalyzeConfig::args())
+            .arg(
+                Arg::new("verbose")
+                    .short('v')
+                    .long("verbose")
+                    .help("詳細ログを出力")
+                    .global(true)
+                    .action(clap::ArgAction::SetTrue)
+            )
+            .arg(
+                Arg::new("no-mmap")
+                    .long("no-mmap")
+                    .help("HARファイルをメモリマップせずに読み込む（ネットワークドライブ等で使用）")
+                    .global(true)
+                    .action(clap::ArgAction::SetTrue)
+            )
+            .subcommand(analyze::command())
+            .subcommand(transform::command())
+            .subcommand(batch::command())
+            .subcommand(conformance::command())
+            .subcommand(compare::command())
+            .subcommand(mock::command())
+            .subcommand(show::command())
+            .subcommand(grep::command())
+            .subcommand(pair_diff::command())
+            .subcommand(trend::command())
+            .subcommand(completions::command())
+            .subcommand(doctor::command())
+            .subcommand(bench::command())
+    }
+
+    /// コマンドライン引数を解析
+    /// 
+    /// # Returns
+    /// * `Cli` - 解析結果
+    pub(crate) fn from_args() -> Self {
+        let matches = profile::apply(Self::command().get_matches());
+
+        let command = match matches.subcommand() {
+            Some(("analyze", sub_matches)) => {
+                CliCommand::Analyze(Box::new(AnalyzeConfig::from_matches(sub_matches)))
+            }
+            Some(("transform", sub_matches)) => {
+                CliCommand::Transform(TransformConfig::from_matches(sub_matches))
+            }
+            Some(("batch", sub_matches)) => {
+                CliCommand::Batch(Box::new(BatchConfig::from_matches(sub_matches)))
+            }
+            Some(("conformance", sub_matches)) => {
+                CliCommand::Conformance(ConformanceConfig::from_matches(sub_matches))
+            }
+            Some(("compare", sub_matches)) => {
+                CliCommand::Compare(CompareConfig::from_matches(sub_matches))
+            }
+            Some(("mock", sub_matches)) => {
+                CliCommand::Mock(MockConfig::from_matches(sub_matches))
+            }
+            Some(("show", sub_matches)) => {
+                CliCommand::Show(ShowConfig::from_matches(sub_matches))
+            }
+            Some(("grep", sub_matches)) => {
+                CliCommand::Grep(GrepConfig::from_matches(sub_matches))
+            }
+            Some(("pair-diff", sub_matches)) => {
+                CliCommand::PairDiff(PairDiffConfig::from_matches(sub_matches))
+            }
+            Some(("trend", sub_matches)) => {
+                CliCommand::Trend(TrendConfig::from_matches(sub_matches))
+            }
+            Some(("completions", sub_matches)) => {
+                CliCommand::Completions(CompletionsConfig::from_matches(sub_matches))
+            }
+            Some(("doctor", sub_matches)) => {
+                CliCommand::Doctor(DoctorConfig::from_matches(sub_matches))
+            }
+            Some(("bench", sub_matches)) => {
+                CliCommand::Bench(BenchConfig::from_matches(sub_matches))
+            }
+            _ => CliCommand::Analyze(Box::new(AnalyzeConfig::from_matches(&matches))),
+        };
+
+        Cli {
+            verbose: matches.get_flag("verbose"),
+            command,
+        }
+    }
+}
+
+impl CliCommand {
+    /// 結果を標準出力に出力するコマンドかどうか（ログを標準エラー出力に分けるために使用）
+    /// 
+    /// # Returns
+    /// * `bool` - show・grep・pair-diff・completions・benchサブコマンドの場合はtrue
+    pub(crate) fn writes_to_stdout(&self) -> bool {
+        matches!(
+            self,
+            CliCommand::Show(_) | CliCommand::Grep(_) | CliCommand::PairDiff(_) | CliCommand::Completions(_) | CliCommand::Bench(_)
+        )
+    }
+
+    /// 設定の妥当性を検証
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub(crate) fn validate(&self) -> Result<()> {
+        match self {
+            CliCommand::Analyze(config) => config.validate(),
+            CliCommand::Transform(config) => config.validate(),
+            CliCommand::Batch(config) => config.validate(),
+            CliCommand::Conformance(config) => config.validate(),
+            CliCommand::Compare(config) => config.validate(),
+            CliCommand::Mock(config) => config.validate(),
+            CliCommand::Show(config) => config.validate(),
+            CliCommand::Grep(config) => config.validate(),
+            CliCommand::PairDiff(config) => config.validate(),
+            CliCommand::Trend(config) => config.validate(),
+            CliCommand::Completions(_) => Ok(()),
+            CliCommand::Doctor(config) => config.validate(),
+            CliCommand::Bench(_) => Ok(()),
+        }
+    }
+
+    /// コマンドを実行
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub(crate) async fn run(self) -> Result<()> {
+        match self {
+            CliCommand::Analyze(config) => analyze::run(*config).await,
+            CliCommand::Transform(config) => transform::run(config).await,
+            CliCommand::Batch(config) => batch::run(*config).await,
+            CliCommand::Conformance(config) => conformance::run(config).await,
+            CliCommand::Compare(config) => compare::run(config).await,
+            CliCommand::Mock(config) => mock::run(config).await,
+            CliCommand::Show(config) => show::run(config).await,
+            CliCommand::Grep(config) => grep::run(config).await,
+            CliCommand::PairDiff(config) => pair_diff::run(config).await,
+            CliCommand::Trend(config) => trend::run(config).await,
+            CliCommand::Completions(config) => completions::run(config).await,
+            CliCommand::Doctor(config) => doctor::run(config).await,
+            CliCommand::Bench(config) => bench::run(config).await,
+        }
+    }
+}
+
+/// コマンドライン引数からHARファイルの読み込み方式を決定
+/// 
+/// # Arguments
+/// * `matches` - 解析済みのコマンドライン引数
+/// 
+/// # Returns
+/// * `ReadMode` - 読み込み方式
+fn read_mode_from_matches(matches: &ArgMatches) -> ReadMode {
+    if matches.get_flag("no-mmap") {
+        ReadMode::Read
+    } else {
+        ReadMode::Mmap
+    }
+}
+
+/// 文字コードの名前を解析
+/// 
+/// # Arguments
+/// * `value` - 文字コードの名前（WHATWG Encoding Standardのラベル）
+/// 
+/// # Returns
+/// * `Result<&'static Encoding, String>` - 成功時は文字コード、失敗時はエラーメッセージ
+fn parse_charset(value: &str) -> std::result::Result<&'static Encoding, String> {
+    Encoding::for_label(value.trim().as_bytes()).ok_or_else(|| format!("不明な文字コードです: {}", value))
+}
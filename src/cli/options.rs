@@ -0,0 +1,654 @@
+//! analyzeサブコマンドとbatchサブコマンドで共通の引数の定義と解析を実装
+
+use age::x25519::Recipient;
+use clap::error::ErrorKind;
+use clap::{Arg, ArgMatches};
+use encoding_rs::Encoding;
+use prost_reflect::DescriptorPool;
+use regex::Regex;
+use rs_har_analyzer::annotations::Annotations;
+use rs_har_analyzer::beautify::JsCssMode;
+use rs_har_analyzer::budgets::Budgets;
+use rs_har_analyzer::business_hours::{BusinessHours, BusinessTimezone};
+use rs_har_analyzer::cache_lint::CacheRules;
+use rs_har_analyzer::columns::Column;
+use rs_har_analyzer::config::FileConfig;
+use rs_har_analyzer::encryption::OutputEncryption;
+use rs_har_analyzer::excel_exporter::{ExportOptions, AUXILIARY_SHEETS};
+use rs_har_analyzer::expression::FilterExpression;
+use rs_har_analyzer::filter::{EntryFilter, HeaderPattern, Sampling};
+use rs_har_analyzer::flags::EntryFlags;
+use rs_har_analyzer::grpc_web;
+use rs_har_analyzer::html::HtmlMode;
+use rs_har_analyzer::mime_map::MimeMap;
+use rs_har_analyzer::payload::PayloadOptions;
+use rs_har_analyzer::payload_cache::DEFAULT_CACHE_DIR;
+use rs_har_analyzer::privacy::PrivacyPolicy;
+use rs_har_analyzer::row_grouping::RowGrouping;
+use rs_har_analyzer::threat_intel::ThreatIntel;
+use rs_har_analyzer::trackers::Blocklist;
+use rs_har_analyzer::upload::UploadTarget;
+use super::{Cli, parse_charset};
+
+/// 解析対象のエントリを絞り込む引数定義（analyzeサブコマンドとbatchサブコマンドで共通）
+/// 
+/// # Returns
+/// * `Vec<Arg>` - 引数定義のリスト
+pub(crate) fn filter_args() -> Vec<Arg> {
+    vec![
+        Arg::new("header-filter")
+            .long("header-filter")
+            .value_name("NAME=PATTERN")
+            .help("リクエストまたはレスポンスのヘッダーの値がパターン（*・?を使用可能）に一致するエントリのみを対象にする（複数指定可）")
+            .action(clap::ArgAction::Append)
+            .value_parser(|spec: &str| HeaderPattern::parse(spec).map_err(|e| e.to_string())),
+        Arg::new("has-header")
+            .long("has-header")
+            .value_name("NAME")
+            .help("リクエストまたはレスポンスにヘッダーが含まれるエントリのみを対象にする（複数指定可）")
+            .action(clap::ArgAction::Append),
+        Arg::new("body-contains")
+            .long("body-contains")
+            .value_name("TEXT")
+            .help("デコード後のリクエストまたはレスポンスのペイロード（HTMLの要約・JavaScript・CSSの整形の前）に文字列を含むエントリのみを対象にする（複数指定可）")
+            .action(clap::ArgAction::Append),
+        Arg::new("body-regex")
+            .long("body-regex")
+            .value_name("REGEX")
+            .help("デコード後のリクエストまたはレスポンスのペイロード（HTMLの要約・JavaScript・CSSの整形の前）が正規表現に一致するエントリのみを対象にする（複数指定可）")
+            .action(clap::ArgAction::Append)
+            .value_parser(|pattern: &str| Regex::new(pattern).map_err(|e| format!("正規表現が不正です: {}", e))),
+        Arg::new("where")
+            .long("where")
+            .value_name("EXPR")
+            .help("条件式（例: method==POST && status>=500 && host~\"api\\.\"）を満たすエントリのみを対象にする（複数指定可）")
+            .action(clap::ArgAction::Append)
+            .value_parser(|source: &str| FilterExpression::parse(source).map_err(|e| e.to_string())),
+        Arg::new("resource-type")
+            .long("resource-type")
+            .value_name("TYPES")
+            .help("DevToolsが記録したリソースの種類（_resourceType）がカンマ区切りの種類（例: xhr,fetch）のいずれかのエントリのみを対象にする")
+            .value_delimiter(',')
+            .action(clap::ArgAction::Append),
+        Arg::new("collapse-assets")
+            .long("collapse-assets")
+            .help("URLとETagが同じ静的アセットへの繰り返しのGET（200・304）を1行に集約し、繰り返し回数列を出力する")
+            .action(clap::ArgAction::SetTrue),
+        Arg::new("collapse-pages")
+            .long("collapse-pages")
+            .help("page・cursor・offset等のクエリパラメータのみが異なる一連のリクエスト（ページ送り）を1行に集約し、繰り返し回数列を出力する")
+            .action(clap::ArgAction::SetTrue),
+        Arg::new("sample")
+            .long("sample")
+            .value_name("PERCENT")
+            .help("絞り込んだエントリから指定した割合（例: 10%）を等間隔に抽出する")
+            .value_parser(|spec: &str| Sampling::parse_rate(spec).map_err(|e| e.to_string())),
+        Arg::new("sample-per-endpoint")
+            .long("sample-per-endpoint")
+            .value_name("N")
+            .help("IDを正規化したエンドポイントごとに最大N件（エラー・所要時間の長いエントリを優先）を抽出する")
+            .value_parser(clap::value_parser!(u64).range(1..)),
+        Arg::new("offset")
+            .long("offset")
+            .value_name("N")
+            .help("絞り込み・抽出したエントリの先頭N件を読み飛ばす")
+            .value_parser(clap::value_parser!(usize)),
+        Arg::new("limit")
+            .long("limit")
+            .value_name("N")
+            .help("解析するエントリの件数の上限")
+            .value_parser(clap::value_parser!(usize)),
+    ]
+}
+
+/// コマンドライン引数から解析対象のエントリを絞り込むフィルタを作成
+/// 
+/// # Arguments
+/// * `matches` - 解析済みのコマンドライン引数
+/// 
+/// # Returns
+/// * `EntryFilter` - フィルタ
+pub(crate) fn filter_from_matches(matches: &ArgMatches) -> EntryFilter {
+    EntryFilter {
+        header_patterns: matches
+            .get_many::<HeaderPattern>("header-filter")
+            .map(|patterns| patterns.cloned().collect())
+            .unwrap_or_default(),
+        required_headers: matches
+            .get_many::<String>("has-header")
+            .map(|names| names.cloned().collect())
+            .unwrap_or_default(),
+        body_contains: matches
+            .get_many::<String>("body-contains")
+            .map(|texts| texts.cloned().collect())
+            .unwrap_or_default(),
+        body_regexes: matches
+            .get_many::<Regex>("body-regex")
+            .map(|regexes| regexes.cloned().collect())
+            .unwrap_or_default(),
+        expressions: matches
+            .get_many::<FilterExpression>("where")
+            .map(|expressions| expressions.cloned().collect())
+            .unwrap_or_default(),
+        resource_types: matches
+            .get_many::<String>("resource-type")
+            .map(|types| {
+                types
+                    .map(|resource_type| resource_type.trim().to_lowercase())
+                    .filter(|resource_type| !resource_type.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        sampling: Sampling {
+            rate: matches.get_one::<f64>("sample").copied(),
+            offset: matches.get_one::<usize>("offset").copied().unwrap_or(0),
+            limit: matches.get_one::<usize>("limit").copied(),
+            per_endpoint: matches.get_one::<u64>("sample-per-endpoint").map(|n| *n as usize),
+        },
+        collapse_assets: matches.get_flag("collapse-assets"),
+        collapse_pages: matches.get_flag("collapse-pages"),
+    }
+}
+
+/// コマンドライン引数から注目させるエントリの条件を作成
+/// 
+/// # Arguments
+/// * `matches` - 解析済みのコマンドライン引数
+/// 
+/// # Returns
+/// * `EntryFlags` - 注目させるエントリの条件
+pub(crate) fn flags_from_matches(matches: &ArgMatches) -> EntryFlags {
+    EntryFlags {
+        url_regexes: matches
+            .get_many::<Regex>("flag-url-regex")
+            .map(|regexes| regexes.cloned().collect())
+            .unwrap_or_default(),
+        indices: matches
+            .get_many::<Vec<usize>>("flag-index")
+            .map(|lists| lists.flatten().copied().collect())
+            .unwrap_or_default(),
+        ..EntryFlags::default()
+    }
+}
+
+/// コマンドライン引数からドメインの分類のリストを作成
+/// 
+/// # Arguments
+/// * `matches` - 解析済みのコマンドライン引数
+/// 
+/// # Returns
+/// * `Blocklist` - 同梱のリストに指定したフィルタリストを追加したリスト
+pub(crate) fn blocklist_from_matches(matches: &ArgMatches) -> Blocklist {
+    let mut blocklist = Blocklist::default();
+    for list in matches.get_many::<Blocklist>("blocklist").into_iter().flatten() {
+        blocklist.extend(list);
+    }
+    blocklist
+}
+
+/// コマンドライン引数から脅威インテリジェンスの指標の照合の設定を作成
+/// 
+/// # Arguments
+/// * `matches` - 解析済みのコマンドライン引数
+/// 
+/// # Returns
+/// * `ThreatIntel` - 指定した全てのファイルの指標と照会のURLを含む設定
+pub(crate) fn threat_intel_from_matches(matches: &ArgMatches) -> ThreatIntel {
+    let mut threat_intel = ThreatIntel::default();
+    for indicators in matches.get_many::<ThreatIntel>("threat-intel").into_iter().flatten() {
+        threat_intel.extend(indicators);
+    }
+    threat_intel.with_lookup(matches.get_one::<String>("threat-intel-lookup").cloned())
+}
+
+/// コマンドライン引数から営業時間を作成
+/// 
+/// # Arguments
+/// * `matches` - 解析済みのコマンドライン引数
+/// 
+/// # Returns
+/// * `Option<BusinessHours>` - `--business-timezone`のタイムゾーンの営業時間（`--business-hours`を指定していない場合はNone）
+pub(crate) fn business_hours_from_matches(matches: &ArgMatches) -> Option<BusinessHours> {
+    let business_hours = matches.get_one::<BusinessHours>("business-hours")?.clone();
+    Some(match matches.get_one::<BusinessTimezone>("business-timezone") {
+        Some(timezone) => business_hours.with_timezone(*timezone),
+        None => business_hours,
+    })
+}
+
+/// Excel出力に関する引数定義（analyzeサブコマンドとbatchサブコマンドで共通）
+/// 
+/// # Returns
+/// * `Vec<Arg>` - 引数定義のリスト
+pub(crate) fn export_args() -> Vec<Arg> {
+    vec![
+        Arg::new("columns")
+            .long("columns")
+            .value_name("LIST")
+            .help("出力する列をカンマ区切りで指定（例: timestamp,method,status,duration）")
+            .value_parser(|s: &str| Column::parse_list(s).map_err(|e| e.to_string())),
+        Arg::new("sheets")
+            .long("sheets")
+            .value_name("LIST")
+            .help("出力するデータシート以外のシートをカンマ区切りで指定（例: \"Security Findings,Cache Lint\"、既定は全て）")
+            .value_parser(parse_sheet_list),
+        Arg::new("privacy")
+            .long("privacy")
+            .value_name("NAME")
+            .help("列ごとの匿名化の設定（同梱のstrictまたは設定ファイルの[privacy.NAME]）を全ての出力形式に適用し、データシート以外のシートを出力しない（--sheetsとは同時に指定できない）"),
+        Arg::new("protect")
+            .long("protect")
+            .value_name("PASSWORD")
+            .help("出力するワークシートを保護（パスワードは省略可能）")
+            .num_args(0..=1)
+            .default_missing_value(""),
+        Arg::new("no-color")
+            .long("no-color")
+            .help("メソッド列・ステータスコード列を色分けしない")
+            .action(clap::ArgAction::SetTrue),
+        Arg::new("bucket-interval")
+            .long("bucket-interval")
+            .value_name("SECONDS")
+            .help("Trafficシートでリクエスト数と転送量を集計する時間帯の間隔（秒、小数可）")
+            .value_parser(parse_bucket_interval)
+            .default_value("1"),
+        Arg::new("cell-notes")
+            .long("cell-notes")
+            .help("URLのセルにリクエストヘッダー、ステータスコードのセルにレスポンスヘッダー、ペイロードのセルに先頭1KBのペイロードのメモを付ける（--privacyで匿名化する場合は付けない）")
+            .action(clap::ArgAction::SetTrue),
+        Arg::new("chunk-rows")
+            .long("chunk-rows")
+            .value_name("N")
+            .help("N行ごとに番号付きのワークブック（<出力ファイル名>_001.xlsx等）に分けて出力し、出力ファイルには分割したワークブックへのリンクの一覧を出力する")
+            .value_parser(clap::value_parser!(u64).range(1..)),
+        Arg::new("deterministic")
+            .long("deterministic")
+            .help("作成日時・コマンドライン引数の絶対パス等の実行するたびに変わる情報を固定し、同じ入力から同じ内容のファイルを出力する（スナップショットテスト向け）")
+            .action(clap::ArgAction::SetTrue),
+        Arg::new("group-rows")
+            .long("group-rows")
+            .value_name("UNIT")
+            .help("同じページ（page）またはホスト（host）の行を連続させ、Excelのアウトラインで折りたたみ・展開できるようにグループ化する")
+            .value_parser(["page", "host"]),
+        Arg::new("headers-inventory")
+            .long("headers-inventory")
+            .value_name("EXAMPLES")
+            .help("リクエスト・レスポンスのヘッダーの名前ごとに設定したエントリ数・異なる値の数・値の例（最大EXAMPLES件、省略時は5件）をHeaders Inventoryシートに出力")
+            .value_parser(clap::value_parser!(usize))
+            .num_args(0..=1)
+            .require_equals(true)
+            .default_missing_value("5"),
+        Arg::new("retry-window")
+            .long("retry-window")
+            .value_name("SECONDS")
+            .help("失敗したリクエストから同じエンドポイントへの再試行とみなす間隔（秒、小数可、0で再試行をまとめない）。サマリーの成功率の集計に使用")
+            .value_parser(parse_retry_window)
+            .default_value("5"),
+        Arg::new("streaming")
+            .long("streaming")
+            .help("解析結果を保持せず1件ずつ定メモリモードでExcelに書き込む（巨大なHAR向け）")
+            .action(clap::ArgAction::SetTrue),
+        Arg::new("charset-override")
+            .long("charset-override")
+            .value_name("CHARSET")
+            .help("レスポンスボディの文字コードを指定（Content-Typeのcharsetより優先。例: Shift_JIS, EUC-JP）")
+            .value_parser(parse_charset),
+        Arg::new("html-mode")
+            .long("html-mode")
+            .value_name("MODE")
+            .help("HTMLのレスポンスボディの出力方法（title: タイトルと説明、excerpt: 本文の抜粋も出力、full: そのまま出力）")
+            .value_parser(["title", "excerpt", "full"])
+            .default_value("full"),
+        Arg::new("js-css-mode")
+            .long("js-css-mode")
+            .value_name("MODE")
+            .help("圧縮されたJavaScript・CSSのレスポンスボディの出力方法（wrap: 120文字で折り返す、pretty: 改行とインデントで整形、full: そのまま出力）")
+            .value_parser(["wrap", "pretty", "full"])
+            .default_value("full"),
+        Arg::new("sniff-json")
+            .long("sniff-json")
+            .help("MIMEタイプがtext/plainまたは未指定でも、ボディが{または[で始まる場合はJSONとして整形")
+            .action(clap::ArgAction::SetTrue),
+        Arg::new("proto-descriptor")
+            .long("proto-descriptor")
+            .value_name("FILE")
+            .help("gRPC-webのメッセージをJSONに変換するための記述子セット（protoc --descriptor_set_outで作成）")
+            .value_parser(|path: &str| grpc_web::load_descriptor_pool(path).map_err(|e| e.to_string())),
+        Arg::new("cache")
+            .long("cache")
+            .value_name("DIR")
+            .help("デコード・整形したボディをエントリの内容のハッシュごとにディレクトリ（省略時は.cache）に保存し、同じHARの再実行で再利用")
+            .num_args(0..=1)
+            .require_equals(true)
+            .default_missing_value(DEFAULT_CACHE_DIR),
+        Arg::new("annotate")
+            .long("annotate")
+            .value_name("FILE")
+            .help("エントリ番号（1始まり）またはURLとメモを記述したCSVファイル（メモをコメント列に出力）")
+            .value_parser(|path: &str| Annotations::load(path).map_err(|e| e.to_string())),
+        Arg::new("flag-url-regex")
+            .long("flag-url-regex")
+            .value_name("REGEX")
+            .help("リクエストURLが正規表現に一致する行を強調表示し、Flaggedシートに一覧を出力する（複数指定可）")
+            .action(clap::ArgAction::Append)
+            .value_parser(|pattern: &str| Regex::new(pattern).map_err(|e| format!("正規表現が不正です: {}", e))),
+        Arg::new("flag-index")
+            .long("flag-index")
+            .value_name("LIST")
+            .help("カンマ区切りのエントリ番号（HAR内の順序で1始まり、例: 12,45）の行を強調表示し、Flaggedシートに一覧を出力する")
+            .action(clap::ArgAction::Append)
+            .value_parser(|spec: &str| EntryFlags::parse_indices(spec).map_err(|e| e.to_string())),
+        Arg::new("blocklist")
+            .long("blocklist")
+            .value_name("[CATEGORY=]FILE")
+            .help("ドメインの分類に追加するフィルタリスト（EasyList・EasyPrivacy形式またはhosts形式。CATEGORYはtracker・ad・cdnで既定はtracker、複数指定可）")
+            .action(clap::ArgAction::Append)
+            .value_parser(|spec: &str| Blocklist::load(spec).map_err(|e| e.to_string())),
+        Arg::new("threat-intel")
+            .long("threat-intel")
+            .value_name("FILE")
+            .help("リクエスト先のホスト・IPアドレスと照合する脅威インテリジェンスの指標（IPアドレス・CIDR・ドメイン、説明、情報源）のCSVファイル。一致したエントリをSecurity Findingsシートに出力して強調表示する（複数指定可）")
+            .action(clap::ArgAction::Append)
+            .value_parser(|path: &str| ThreatIntel::load(path).map_err(|e| e.to_string())),
+        Arg::new("hunt")
+            .long("hunt")
+            .help("不審な通信（IPアドレスへの直接の接続・Punycodeのドメイン・一般的でないポート・エントロピーの高いURLやリクエストボディ・一定間隔のビーコン）を検出してSecurity Findingsシートに出力する")
+            .action(clap::ArgAction::SetTrue),
+        Arg::new("sort-by-time")
+            .long("sort-by-time")
+            .help("解析結果を開始時刻順に出力する（プロキシの時計のずれ等で記録順と開始時刻の順が異なる場合に時系列を整える。エントリ番号はHAR内の順序のまま）")
+            .action(clap::ArgAction::SetTrue),
+        Arg::new("business-hours")
+            .long("business-hours")
+            .value_name("SPEC")
+            .help("営業時間（例: \"Mon-Fri 09:00-18:00\"、曜日の省略時は月曜日から金曜日、22:00-06:00のように日をまたぐ範囲も指定可）。各リクエストを営業時間内・時間外に分類して営業時間列とサマリーに出力する")
+            .value_parser(|spec: &str| BusinessHours::parse(spec).map_err(|e| e.to_string())),
+        Arg::new("business-timezone")
+            .long("business-timezone")
+            .value_name("TZ")
+            .help("営業時間を判定するタイムゾーン（Asia/Tokyo等のIANAのタイムゾーン名または+09:00等のUTCからの時差。既定: 実行環境のタイムゾーン）")
+            .requires("business-hours")
+            .value_parser(|spec: &str| BusinessTimezone::parse(spec).map_err(|e| e.to_string())),
+        Arg::new("threat-intel-lookup")
+            .long("threat-intel-lookup")
+            .value_name("URL")
+            .help("リクエスト先のホスト・IPアドレスを照会するURL（{indicator}を置き換えてGETし、200番台の応答を一致、404を不一致とみなす）")
+            .value_parser(|template: &str| ThreatIntel::parse_lookup(template).map_err(|e| e.to_string())),
+        Arg::new("consent-url-regex")
+            .long("consent-url-regex")
+            .value_name("REGEX")
+            .help("同梱の同意管理プラットフォームに加えて、同意管理のエンドポイントとみなすURLの正規表現（複数指定可）")
+            .action(clap::ArgAction::Append)
+            .value_parser(|pattern: &str| Regex::new(pattern).map_err(|e| format!("正規表現が不正です: {}", e))),
+        Arg::new("cache-rules")
+            .long("cache-rules")
+            .value_name("FILE")
+            .help("Cache-Controlヘッダーの検査に使用するリソースの種類（html・hashed-asset・asset・api）ごとの規則を記述したTOMLファイル（指定しない種類は同梱の規則）")
+            .value_parser(|path: &str| CacheRules::load(path).map_err(|e| e.to_string())),
+        Arg::new("max-request-header-bytes")
+            .long("max-request-header-bytes")
+            .value_name("BYTES")
+            .help("リクエストヘッダーの合計サイズの予算（超過したエントリをBudgetsシートに出力）")
+            .value_parser(clap::value_parser!(u64)),
+        Arg::new("max-cookie-bytes")
+            .long("max-cookie-bytes")
+            .value_name("BYTES")
+            .help("1件のCookie（Cookie・Set-Cookieヘッダーの名前と値）のサイズの予算")
+            .value_parser(clap::value_parser!(u64)),
+        Arg::new("max-json-response-bytes")
+            .long("max-json-response-bytes")
+            .value_name("BYTES")
+            .help("JSONのレスポンスボディ（展開後）のサイズの予算")
+            .value_parser(clap::value_parser!(u64)),
+        Arg::new("fail-on-budget")
+            .long("fail-on-budget")
+            .help("サイズの予算を超過したエントリがある場合は出力後に終了コード6で終了する")
+            .action(clap::ArgAction::SetTrue),
+        Arg::new("notify-webhook")
+            .long("notify-webhook")
+            .value_name("URL")
+            .help("出力後に解析結果のサマリー（リクエスト数・エラー率・予算の超過・出力先）をWebhook（Slack・Teams）に投稿")
+            .value_parser(parse_webhook_url),
+        Arg::new("upload")
+            .long("upload")
+            .value_name("URL")
+            .help("出力したファイル（--archive指定時はZIPファイル）をs3://bucket/prefix/またはgs://bucket/prefix/にアップロード（認証情報は環境変数から取得）")
+            .value_parser(|spec: &str| UploadTarget::parse(spec).map_err(|e| e.to_string())),
+        Arg::new("encrypt-output")
+            .long("encrypt-output")
+            .value_name("RECIPIENT")
+            .help("外部ファイルに退避したペイロードを受信者の公開鍵（age1...）向けにageで暗号化し、平文のファイルを削除（複数指定可）")
+            .action(clap::ArgAction::Append)
+            .value_parser(|spec: &str| OutputEncryption::parse_recipient(spec).map_err(|e| e.to_string())),
+        Arg::new("encrypt-workbook")
+            .long("encrypt-workbook")
+            .help("--encrypt-outputでワークブック（分割したワークブック・依存関係グラフを含む）も暗号化する")
+            .requires("encrypt-output")
+            .action(clap::ArgAction::SetTrue),
+    ]
+}
+
+/// WebhookのURLを検証
+/// 
+/// # Arguments
+/// * `value` - WebhookのURL
+/// 
+/// # Returns
+/// * `Result<String, String>` - 成功時はURL、失敗時はエラーメッセージ
+fn parse_webhook_url(value: &str) -> Result<String, String> {
+    let url = url::Url::parse(value).map_err(|e| format!("URLが不正です: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("http://またはhttps://で始まるURLを指定してください".to_string());
+    }
+    Ok(value.to_string())
+}
+
+/// コマンドライン引数から出力ファイルの暗号化の設定を作成
+/// 
+/// # Arguments
+/// * `matches` - 解析済みのコマンドライン引数
+/// 
+/// # Returns
+/// * `Option<OutputEncryption>` - 暗号化の設定（`--encrypt-output`を指定していない場合はNone）
+pub(crate) fn encryption_from_matches(matches: &ArgMatches) -> Option<OutputEncryption> {
+    let recipients: Vec<Recipient> = matches.get_many::<Recipient>("encrypt-output")?.cloned().collect();
+    Some(OutputEncryption::new(recipients, matches.get_flag("encrypt-workbook")))
+}
+
+/// コマンドライン引数からサイズの予算を作成
+/// 
+/// # Arguments
+/// * `matches` - 解析済みのコマンドライン引数
+/// 
+/// # Returns
+/// * `Budgets` - サイズの予算
+pub(crate) fn budgets_from_matches(matches: &ArgMatches) -> Budgets {
+    Budgets {
+        request_header_bytes: matches.get_one::<u64>("max-request-header-bytes").copied(),
+        cookie_bytes: matches.get_one::<u64>("max-cookie-bytes").copied(),
+        json_response_bytes: matches.get_one::<u64>("max-json-response-bytes").copied(),
+    }
+}
+
+/// 解析対象のエントリ・出力の設定をまとめたプロファイルを選択する引数定義（analyzeサブコマンドとbatchサブコマンドで共通）
+/// 
+/// # Returns
+/// * `Vec<Arg>` - 引数定義のリスト
+pub(crate) fn profile_args() -> Vec<Arg> {
+    vec![
+        Arg::new("config")
+            .short('c')
+            .long("config")
+            .value_name("FILE")
+            .help("プロファイル・MIMEタイプの分類を記述した設定ファイル（TOML）のパス")
+            .value_parser(|path: &str| FileConfig::load(path).map_err(|e| e.to_string())),
+        Arg::new("profile")
+            .long("profile")
+            .value_name("NAME")
+            .help("設定ファイルの[profile.NAME]に記述したオプションを適用（コマンドラインで指定したオプションが優先）")
+            .requires("config"),
+    ]
+}
+
+/// 出力するシートのカンマ区切りのリストを解析
+/// 
+/// # Arguments
+/// * `value` - シートの名前のカンマ区切りのリスト（大文字・小文字を区別しない）
+/// 
+/// # Returns
+/// * `Result<Vec<String>, String>` - 成功時はシートの名前のリスト、失敗時はエラーメッセージ
+pub(crate) fn parse_sheet_list(value: &str) -> std::result::Result<Vec<String>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            AUXILIARY_SHEETS
+                .iter()
+                .find(|sheet| sheet.eq_ignore_ascii_case(name))
+                .map(|sheet| sheet.to_string())
+                .ok_or_else(|| format!("不明なシートです: {}（指定できるシート: {}）", name, AUXILIARY_SHEETS.join(", ")))
+        })
+        .collect()
+}
+
+/// 時間帯の間隔（秒）を解析してミリ秒に変換
+/// 
+/// # Arguments
+/// * `value` - 時間帯の間隔（秒、小数可）
+/// 
+/// # Returns
+/// * `Result<u64, String>` - 成功時はミリ秒、失敗時はエラーメッセージ
+pub(crate) fn parse_bucket_interval(value: &str) -> std::result::Result<u64, String> {
+    let seconds: f64 = value
+        .parse()
+        .map_err(|_| format!("数値を指定してください: {}", value))?;
+    let millis = (seconds * 1000.0).round();
+    if !millis.is_finite() || millis < 1.0 {
+        return Err("0.001秒以上の間隔を指定してください".to_string());
+    }
+    Ok(millis as u64)
+}
+
+/// 再試行とみなす間隔（秒）を解析してミリ秒に変換
+/// 
+/// # Arguments
+/// * `value` - 再試行とみなす間隔（秒、小数可）
+/// 
+/// # Returns
+/// * `Result<u64, String>` - 成功時はミリ秒、失敗時はエラーメッセージ
+pub(crate) fn parse_retry_window(value: &str) -> std::result::Result<u64, String> {
+    let seconds: f64 = value
+        .parse()
+        .map_err(|_| format!("数値を指定してください: {}", value))?;
+    let millis = (seconds * 1000.0).round();
+    if !millis.is_finite() || millis < 0.0 {
+        return Err("0秒以上の間隔を指定してください".to_string());
+    }
+    Ok(millis as u64)
+}
+
+/// コマンドライン引数からボディの加工方法のオプションを作成
+/// 
+/// # Arguments
+/// * `matches` - 解析済みのコマンドライン引数
+/// 
+/// # Returns
+/// * `PayloadOptions` - ボディの加工方法のオプション
+pub(crate) fn payload_options_from_matches(matches: &ArgMatches) -> PayloadOptions {
+    PayloadOptions {
+        charset_override: matches.get_one::<&'static Encoding>("charset-override").copied(),
+        html_mode: HtmlMode::from_name(matches.get_one::<String>("html-mode").unwrap()).unwrap_or_default(),
+        js_css_mode: JsCssMode::from_name(matches.get_one::<String>("js-css-mode").unwrap()).unwrap_or_default(),
+        proto_descriptor: matches.get_one::<DescriptorPool>("proto-descriptor").cloned(),
+        sniff_json: matches.get_flag("sniff-json"),
+        mime_map: matches
+            .get_one::<FileConfig>("config")
+            .map(|config| MimeMap::new(&config.mime_types))
+            .unwrap_or_default(),
+    }
+}
+
+/// コマンドライン引数からExcel出力のオプションを作成
+/// 
+/// # Arguments
+/// * `matches` - 解析済みのコマンドライン引数
+/// 
+/// # Returns
+/// * `ExportOptions` - Excel出力のオプション
+pub(crate) fn export_options_from_matches(matches: &ArgMatches) -> ExportOptions {
+    let mut columns = matches
+        .get_one::<Vec<Column>>("columns")
+        .cloned()
+        .unwrap_or_else(|| Column::DEFAULT.to_vec());
+    // 集約した場合は繰り返し回数が分かるよう列を追加
+    let collapses = matches.get_flag("collapse-assets") || matches.get_flag("collapse-pages");
+    if collapses && !columns.contains(&Column::RepeatCount) {
+        columns.push(Column::RepeatCount);
+    }
+    // メモを指定した場合はコメント列を追加
+    if matches.contains_id("annotate") && !columns.contains(&Column::Comments) {
+        columns.push(Column::Comments);
+    }
+    // 営業時間を指定した場合は営業時間列を追加
+    if matches.contains_id("business-hours") && !columns.contains(&Column::BusinessHours) {
+        columns.push(Column::BusinessHours);
+    }
+    let privacy = privacy_from_matches(matches);
+    // データシート以外のシート・グラフはURL・ホスト名・ヘッダーをそのまま含むため、匿名化する場合は出力しない
+    if privacy.is_active() && matches.contains_id("sheets") {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "--sheetsは匿名化の設定（--privacy {}）と同時に指定できません（データシート以外のシートはURL・ホスト名を匿名化せずに含むため）",
+                    privacy.name()
+                ),
+            )
+            .exit();
+    }
+    ExportOptions {
+        columns,
+        protect: matches.get_one::<String>("protect").cloned(),
+        colored: !matches.get_flag("no-color"),
+        cell_notes: matches.get_flag("cell-notes"),
+        deterministic: matches.get_flag("deterministic"),
+        chunk_rows: matches.get_one::<u64>("chunk-rows").map(|rows| *rows as usize),
+        row_grouping: matches
+            .get_one::<String>("group-rows")
+            .and_then(|name| RowGrouping::from_name(name)),
+        bucket_interval_ms: *matches.get_one::<u64>("bucket-interval").unwrap(),
+        header_examples: matches.get_one::<usize>("headers-inventory").copied(),
+        egress_pricing: matches
+            .get_one::<FileConfig>("config")
+            .and_then(|config| config.egress_pricing.clone()),
+        // 匿名化する場合はデータシートのみ出力する
+        sheets: matches
+            .get_one::<Vec<String>>("sheets")
+            .cloned()
+            .or_else(|| privacy.is_active().then(Vec::new)),
+        privacy,
+        ..ExportOptions::default()
+    }
+}
+
+/// コマンドライン引数から列ごとの匿名化の設定を作成
+/// 
+/// 設定の名前が不明な場合は引数の誤りと同様にエラーメッセージを出力して終了する
+/// 
+/// # Arguments
+/// * `matches` - 解析済みのコマンドライン引数
+/// 
+/// # Returns
+/// * `PrivacyPolicy` - 匿名化の設定（`--privacy`を指定していない場合は匿名化しない）
+fn privacy_from_matches(matches: &ArgMatches) -> PrivacyPolicy {
+    let Some(name) = matches.get_one::<String>("privacy") else {
+        return PrivacyPolicy::default();
+    };
+    let defined = matches
+        .get_one::<FileConfig>("config")
+        .map(|config| config.privacy.clone())
+        .unwrap_or_default();
+    PrivacyPolicy::resolve(name, &defined)
+        .unwrap_or_else(|e| Cli::command().error(ErrorKind::InvalidValue, e.to_string()).exit())
+}
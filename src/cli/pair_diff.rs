@@ -0,0 +1,124 @@
+//! 同じエンドポイントへの連続した呼び出しのレスポンスボディの変化を表示するpair-diffサブコマンドを実装
+
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+use log::info;
+use regex::{Regex, RegexBuilder};
+use rs_har_analyzer::analyzer::{HarAnalyzer, ReadMode};
+use rs_har_analyzer::pair_diff;
+use std::io::IsTerminal;
+use std::path::Path;
+use super::read_mode_from_matches;
+
+/// pair-diffサブコマンドの設定
+#[derive(Debug)]
+pub(crate) struct PairDiffConfig {
+    input_file: String,
+    url_regex: String,
+    ignore_case: bool,
+    color: bool,
+    read_mode: ReadMode,
+}
+
+/// pair-diffサブコマンドの定義を作成
+/// 
+/// # Returns
+/// * `Command` - サブコマンドの定義
+pub(crate) fn command() -> Command {
+    Command::new("pair-diff")
+        .about("同じエンドポイントへの連続した呼び出しのレスポンスボディを比較し、変化したJSONのキーを端末に表示")
+        .args(PairDiffConfig::args())
+}
+
+impl PairDiffConfig {
+    /// pair-diffサブコマンドの引数定義
+    /// 
+    /// # Returns
+    /// * `Vec<Arg>` - 引数定義のリスト
+    pub(crate) fn args() -> Vec<Arg> {
+        vec![
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("FILE")
+                .help("入力するHARファイルのパス")
+                .required(true),
+            Arg::new("url-regex")
+                .long("url-regex")
+                .value_name("REGEX")
+                .help("比較するエントリのURLの正規表現（例: \"/api/config\"）")
+                .required(true),
+            Arg::new("ignore-case")
+                .long("ignore-case")
+                .help("大文字と小文字を区別せずにURLを照合")
+                .action(clap::ArgAction::SetTrue),
+            Arg::new("no-color")
+                .long("no-color")
+                .help("色を付けずに出力（環境変数NO_COLORが設定されている場合と出力先が端末ではない場合も色を付けない）")
+                .action(clap::ArgAction::SetTrue),
+        ]
+    }
+
+    /// コマンドライン引数から設定を作成
+    /// 
+    /// # Arguments
+    /// * `matches` - 解析済みのコマンドライン引数
+    /// 
+    /// # Returns
+    /// * `PairDiffConfig` - pair-diffサブコマンドの設定
+    pub(crate) fn from_matches(matches: &ArgMatches) -> Self {
+        PairDiffConfig {
+            input_file: matches.get_one::<String>("input").unwrap().clone(),
+            url_regex: matches.get_one::<String>("url-regex").unwrap().clone(),
+            ignore_case: matches.get_flag("ignore-case"),
+            color: !matches.get_flag("no-color")
+                && std::env::var_os("NO_COLOR").is_none()
+                && std::io::stdout().is_terminal(),
+            read_mode: read_mode_from_matches(matches),
+        }
+    }
+
+    /// URLを照合する正規表現を作成
+    /// 
+    /// # Returns
+    /// * `Result<Regex>` - 成功時は正規表現、失敗時はエラー
+    fn regex(&self) -> Result<Regex> {
+        RegexBuilder::new(&self.url_regex)
+            .case_insensitive(self.ignore_case)
+            .build()
+            .map_err(|e| anyhow::anyhow!("正規表現が不正です: {}: {}", self.url_regex, e))
+    }
+
+    /// 設定の妥当性を検証
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !Path::new(&self.input_file).exists() {
+            return Err(anyhow::anyhow!("入力ファイルが見つかりません: {}", self.input_file));
+        }
+        self.regex()?;
+        Ok(())
+    }
+}
+
+/// 連続した呼び出しのレスポンスボディを比較して端末に表示
+/// 
+/// # Arguments
+/// * `config` - pair-diffサブコマンドの設定
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub(crate) async fn run(config: PairDiffConfig) -> Result<()> {
+    let regex = config.regex()?;
+    let analyzer = HarAnalyzer::with_read_mode(&config.input_file, config.read_mode)?;
+    let diffs = pair_diff::compare(&analyzer.har_data().log.entries, &regex);
+    if diffs.is_empty() {
+        info!("2回以上呼び出されたエンドポイントはありません: {}", config.url_regex);
+    } else {
+        let changed = diffs.iter().filter(|diff| !diff.changes.is_empty()).count();
+        info!("{}件のエンドポイントのうち{}件でレスポンスボディが変化しました", diffs.len(), changed);
+    }
+    print!("{}", pair_diff::render(&diffs, config.color));
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! 設定ファイルのプロファイル（`[profile.NAME]`）のオプションをコマンドライン引数に適用する処理を実装
+//! 
+//! プロファイルにはanalyze・batchサブコマンドのオプションを長い形式の名前と値で記述する。
+//! コマンドラインで指定していないオプションのみを引数の末尾に追加して再度解析するため、
+//! プロファイルの値もコマンドラインと同じ検証を受け、コマンドラインで指定したオプションが優先される
+
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::ArgMatches;
+use rs_har_analyzer::config::FileConfig;
+use std::ffi::OsString;
+use super::Cli;
+
+/// プロファイルに記述できないオプション（プロファイルの選択に使用するため）
+const RESERVED_OPTIONS: &[&str] = &["config", "profile"];
+
+/// `--profile`が指定されている場合はプロファイルのオプションを適用して引数を解析し直す
+/// 
+/// 設定ファイルやプロファイルの誤りは引数の誤りと同様にエラーメッセージを出力して終了する
+/// 
+/// # Arguments
+/// * `matches` - 解析済みのコマンドライン引数
+/// 
+/// # Returns
+/// * `ArgMatches` - プロファイルを適用した引数（`--profile`が無い場合はそのまま）
+pub(crate) fn apply(matches: ArgMatches) -> ArgMatches {
+    let (subcommand, sub_matches) = match matches.subcommand() {
+        Some((name @ ("analyze" | "batch"), sub_matches)) => (Some(name), sub_matches),
+        Some(_) => return matches,
+        None => (None, &matches),
+    };
+    let Some(profile_name) = sub_matches.get_one::<String>("profile") else {
+        return matches;
+    };
+    let config = sub_matches.get_one::<FileConfig>("config").unwrap();
+
+    let mut command = Cli::command();
+    let target = match subcommand {
+        Some(name) => command.find_subcommand_mut(name).unwrap(),
+        None => &mut command,
+    };
+    let Some(profile) = config.profile.get(profile_name) else {
+        let names = config.profile.keys().map(String::as_str).collect::<Vec<_>>().join(", ");
+        target
+            .error(
+                ErrorKind::InvalidValue,
+                format!("プロファイルが設定ファイルにありません: {}（定義されているプロファイル: {}）", profile_name, names),
+            )
+            .exit();
+    };
+
+    let mut extra_args: Vec<OsString> = Vec::new();
+    for (name, value) in profile {
+        let known = target.get_arguments().any(|arg| arg.get_id() == name.as_str() && arg.get_long().is_some());
+        if !known || RESERVED_OPTIONS.contains(&name.as_str()) {
+            target
+                .error(ErrorKind::UnknownArgument, format!("プロファイル{}に不明なオプションがあります: {}", profile_name, name))
+                .exit();
+        }
+        if sub_matches.value_source(name) == Some(ValueSource::CommandLine) {
+            continue;
+        }
+        match option_args(name, value) {
+            Ok(args) => extra_args.extend(args),
+            Err(message) => target
+                .error(ErrorKind::InvalidValue, format!("プロファイル{}のオプション{}: {}", profile_name, name, message))
+                .exit(),
+        }
+    }
+
+    let mut args: Vec<OsString> = std::env::args_os().collect();
+    args.extend(extra_args);
+    Cli::command().get_matches_from(args)
+}
+
+/// プロファイルの1件のオプションをコマンドライン引数に変換
+/// 
+/// # Arguments
+/// * `name` - オプションの長い形式の名前
+/// * `value` - 値（trueはフラグの指定、falseは指定しない、配列は値ごとに繰り返して指定）
+/// 
+/// # Returns
+/// * `Result<Vec<OsString>, String>` - 成功時はコマンドライン引数のリスト、失敗時はエラーメッセージ
+fn option_args(name: &str, value: &toml::Value) -> Result<Vec<OsString>, String> {
+    let scalar = |value: &toml::Value| match value {
+        toml::Value::String(text) => Ok(text.clone()),
+        toml::Value::Integer(number) => Ok(number.to_string()),
+        toml::Value::Float(number) => Ok(number.to_string()),
+        _ => Err("値には文字列・数値・真偽値またはそれらの配列を指定してください".to_string()),
+    };
+    let values = match value {
+        toml::Value::Boolean(true) => return Ok(vec![OsString::from(format!("--{}", name))]),
+        toml::Value::Boolean(false) => return Ok(Vec::new()),
+        toml::Value::Array(items) => items.iter().map(scalar).collect::<Result<Vec<_>, _>>()?,
+        value => vec![scalar(value)?],
+    };
+    // 値が「-」で始まる場合もオプションと誤認されないよう「--名前=値」の形式で指定する
+    Ok(values.into_iter().map(|value| OsString::from(format!("--{}={}", name, value))).collect())
+}
@@ -0,0 +1,151 @@
+//! 1件のエントリを端末に表示するshowサブコマンドを実装
+
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+use encoding_rs::Encoding;
+use regex::Regex;
+use rs_har_analyzer::analyzer::{HarAnalyzer, ReadMode};
+use rs_har_analyzer::error::AnalyzerError;
+use rs_har_analyzer::payload::PayloadOptions;
+use rs_har_analyzer::show::{self, EntrySelector};
+use std::io::IsTerminal;
+use std::path::Path;
+use super::{read_mode_from_matches, parse_charset};
+
+/// showサブコマンドの設定
+#[derive(Debug)]
+pub(crate) struct ShowConfig {
+    input_file: String,
+    selector: EntrySelector,
+    color: bool,
+    payload_options: PayloadOptions,
+    read_mode: ReadMode,
+}
+
+/// showサブコマンドの定義を作成
+/// 
+/// # Returns
+/// * `Command` - サブコマンドの定義
+pub(crate) fn command() -> Command {
+    Command::new("show")
+        .about("1件のエントリのリクエスト・レスポンス（ヘッダー・デコードしたボディ・タイミング）を端末に表示")
+        .args(ShowConfig::args())
+}
+
+impl ShowConfig {
+    /// showサブコマンドの引数定義
+    /// 
+    /// # Returns
+    /// * `Vec<Arg>` - 引数定義のリスト
+    pub(crate) fn args() -> Vec<Arg> {
+        vec![
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("FILE")
+                .help("入力するHARファイルのパス")
+                .required(true),
+            Arg::new("index")
+                .long("index")
+                .value_name("NUMBER")
+                .help("表示するエントリの番号（HAR内の順序で1始まり）")
+                .value_parser(clap::value_parser!(usize))
+                .required_unless_present_any(["url-regex", "id"])
+                .conflicts_with_all(["url-regex", "id"]),
+            Arg::new("url-regex")
+                .long("url-regex")
+                .value_name("REGEX")
+                .help("リクエストURLが正規表現に最初に一致するエントリを表示")
+                .value_parser(|pattern: &str| Regex::new(pattern).map_err(|e| e.to_string()))
+                .conflicts_with("id"),
+            Arg::new("id")
+                .long("id")
+                .value_name("ID")
+                .help("エントリの内容から求めたID（Excelファイル・CSVのエントリID列の値）が一致するエントリを表示"),
+            Arg::new("no-color")
+                .long("no-color")
+                .help("色を付けずに出力（環境変数NO_COLORが設定されている場合と出力先が端末ではない場合も色を付けない）")
+                .action(clap::ArgAction::SetTrue),
+            Arg::new("charset-override")
+                .long("charset-override")
+                .value_name("CHARSET")
+                .help("レスポンスボディの文字コードを指定（Content-Typeのcharsetより優先。例: Shift_JIS, EUC-JP）")
+                .value_parser(parse_charset),
+        ]
+    }
+
+    /// コマンドライン引数から設定を作成
+    /// 
+    /// # Arguments
+    /// * `matches` - 解析済みのコマンドライン引数
+    /// 
+    /// # Returns
+    /// * `ShowConfig` - showサブコマンドの設定
+    pub(crate) fn from_matches(matches: &ArgMatches) -> Self {
+        let selector = if let Some(regex) = matches.get_one::<Regex>("url-regex") {
+            EntrySelector::UrlRegex(regex.clone())
+        } else if let Some(id) = matches.get_one::<String>("id") {
+            EntrySelector::Id(id.clone())
+        } else {
+            EntrySelector::Number(*matches.get_one::<usize>("index").unwrap())
+        };
+        ShowConfig {
+            input_file: matches.get_one::<String>("input").unwrap().clone(),
+            selector,
+            color: !matches.get_flag("no-color")
+                && std::env::var_os("NO_COLOR").is_none()
+                && std::io::stdout().is_terminal(),
+            payload_options: PayloadOptions {
+                charset_override: matches.get_one::<&'static Encoding>("charset-override").copied(),
+                ..PayloadOptions::default()
+            },
+            read_mode: read_mode_from_matches(matches),
+        }
+    }
+
+    /// 設定の妥当性を検証
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !Path::new(&self.input_file).exists() {
+            return Err(anyhow::anyhow!("入力ファイルが見つかりません: {}", self.input_file));
+        }
+
+        Ok(())
+    }
+}
+
+/// 1件のエントリのリクエスト・レスポンスを端末に表示
+/// 
+/// # Arguments
+/// * `config` - showサブコマンドの設定
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub(crate) async fn run(config: ShowConfig) -> Result<()> {
+    let analyzer = HarAnalyzer::with_read_mode(&config.input_file, config.read_mode)?
+        .with_payload_options(config.payload_options);
+    let entries = &analyzer.har_data().log.entries;
+    let Some(index) = config.selector.find(entries) else {
+        let message = match &config.selector {
+            EntrySelector::Number(number) => {
+                format!("エントリ番号は1〜{}の範囲で指定してください: {}", entries.len(), number)
+            }
+            EntrySelector::UrlRegex(regex) => format!("正規表現に一致するエントリがありません: {}", regex),
+            EntrySelector::Id(id) => format!("IDが一致するエントリがありません: {}", id),
+        };
+        return Err(AnalyzerError::InvalidArgument(message).into());
+    };
+
+    let (request_body, response_body) = analyzer.entry_payloads(index).unwrap_or_default();
+    let text = show::render(
+        &entries[index],
+        index + 1,
+        entries.len(),
+        (&request_body, &response_body),
+        config.color,
+    );
+    print!("{}", text);
+    Ok(())
+}
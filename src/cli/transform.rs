@@ -0,0 +1,120 @@
+//! 設定ファイルに記述した変換処理を適用するtransformサブコマンドを実装
+
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+use log::info;
+use rs_har_analyzer::analyzer::{HarAnalyzer, ReadMode};
+use rs_har_analyzer::config::FileConfig;
+use rs_har_analyzer::transform::TransformPipeline;
+use std::path::Path;
+use super::read_mode_from_matches;
+
+/// transformサブコマンドの設定
+#[derive(Debug)]
+pub(crate) struct TransformConfig {
+    input_file: String,
+    output_file: String,
+    config_file: String,
+    read_mode: ReadMode,
+}
+
+/// transformサブコマンドの定義を作成
+/// 
+/// # Returns
+/// * `Command` - サブコマンドの定義
+pub(crate) fn command() -> Command {
+    Command::new("transform")
+        .about("設定ファイルに記述した変換処理を順に適用して新しいHARファイルを出力")
+        .args(TransformConfig::args())
+}
+
+impl TransformConfig {
+    /// transformサブコマンドの引数定義
+    /// 
+    /// # Returns
+    /// * `Vec<Arg>` - 引数定義のリスト
+    pub(crate) fn args() -> Vec<Arg> {
+        vec![
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("FILE")
+                .help("入力するHARファイルのパス")
+                .required(true),
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("FILE")
+                .help("出力するHARファイルのパス")
+                .required(true),
+            Arg::new("config")
+                .short('c')
+                .long("config")
+                .value_name("FILE")
+                .help("変換処理を記述した設定ファイル（TOML）のパス")
+                .required(true),
+        ]
+    }
+
+    /// コマンドライン引数から設定を作成
+    /// 
+    /// # Arguments
+    /// * `matches` - 解析済みのコマンドライン引数
+    /// 
+    /// # Returns
+    /// * `TransformConfig` - transformサブコマンドの設定
+    pub(crate) fn from_matches(matches: &ArgMatches) -> Self {
+        TransformConfig {
+            input_file: matches.get_one::<String>("input").unwrap().clone(),
+            output_file: matches.get_one::<String>("output").unwrap().clone(),
+            config_file: matches.get_one::<String>("config").unwrap().clone(),
+            read_mode: read_mode_from_matches(matches),
+        }
+    }
+
+    /// 設定の妥当性を検証
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !Path::new(&self.input_file).exists() {
+            return Err(anyhow::anyhow!("入力ファイルが見つかりません: {}", self.input_file));
+        }
+
+        if !Path::new(&self.config_file).exists() {
+            return Err(anyhow::anyhow!("設定ファイルが見つかりません: {}", self.config_file));
+        }
+
+        if !self.output_file.to_lowercase().ends_with(".har") {
+            return Err(anyhow::anyhow!("出力ファイルはHARファイル(.har)である必要があります"));
+        }
+
+        Ok(())
+    }
+}
+
+/// transformサブコマンドを実行
+/// 
+/// # Arguments
+/// * `config` - transformサブコマンドの設定
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub(crate) async fn run(config: TransformConfig) -> Result<()> {
+    info!("HARファイルの変換を開始します");
+
+    let file_config = FileConfig::load(&config.config_file)?;
+    let pipeline = TransformPipeline::new(&file_config.transform)?;
+
+    let mut har_data = HarAnalyzer::with_read_mode(&config.input_file, config.read_mode)?.into_har_data();
+    let before = har_data.log.entries.len();
+
+    pipeline.apply(&mut har_data);
+
+    info!("変換処理の適用が完了しました。エントリ数: {} -> {}", before, har_data.log.entries.len());
+
+    TransformPipeline::write_har(&har_data, &config.output_file)?;
+
+    info!("HARファイルの変換が完了しました");
+    Ok(())
+}
@@ -0,0 +1,130 @@
+//! エンドポイントごとの集計をデータベースに追記して推移を出力するtrendサブコマンドを実装
+
+use anyhow::Result;
+use chrono::Local;
+use clap::{Arg, ArgMatches, Command};
+use log::info;
+use rs_har_analyzer::analyzer::{HarAnalyzer, ReadMode};
+use rs_har_analyzer::trend::{self, TrendDatabase};
+use std::path::Path;
+use super::read_mode_from_matches;
+
+/// trendサブコマンドの設定
+#[derive(Debug)]
+pub(crate) struct TrendConfig {
+    input_file: String,
+    database: String,
+    output_file: String,
+    runs: usize,
+    read_mode: ReadMode,
+}
+
+/// trendサブコマンドの定義を作成
+/// 
+/// # Returns
+/// * `Command` - サブコマンドの定義
+pub(crate) fn command() -> Command {
+    Command::new("trend")
+        .about("エンドポイントごとの所要時間・エラー率をSQLiteデータベースに追記し、直近の実行の推移をExcelファイルに出力")
+        .args(TrendConfig::args())
+}
+
+impl TrendConfig {
+    /// trendサブコマンドの引数定義
+    /// 
+    /// # Returns
+    /// * `Vec<Arg>` - 引数定義のリスト
+    pub(crate) fn args() -> Vec<Arg> {
+        vec![
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("FILE")
+                .help("入力するHARファイルのパス")
+                .required(true),
+            Arg::new("db")
+                .long("db")
+                .value_name("FILE")
+                .help("推移を記録するSQLiteデータベースのパス（無い場合は作成）")
+                .default_value("trends.sqlite"),
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("FILE")
+                .help("推移を出力するExcelファイルのパス")
+                .default_value("trend.xlsx"),
+            Arg::new("runs")
+                .long("runs")
+                .value_name("COUNT")
+                .help("推移に出力する直近の実行の数")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("10"),
+        ]
+    }
+
+    /// コマンドライン引数から設定を作成
+    /// 
+    /// # Arguments
+    /// * `matches` - 解析済みのコマンドライン引数
+    /// 
+    /// # Returns
+    /// * `TrendConfig` - trendサブコマンドの設定
+    pub(crate) fn from_matches(matches: &ArgMatches) -> Self {
+        TrendConfig {
+            input_file: matches.get_one::<String>("input").unwrap().clone(),
+            database: matches.get_one::<String>("db").unwrap().clone(),
+            output_file: matches.get_one::<String>("output").unwrap().clone(),
+            runs: *matches.get_one::<usize>("runs").unwrap(),
+            read_mode: read_mode_from_matches(matches),
+        }
+    }
+
+    /// 設定の妥当性を検証
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !Path::new(&self.input_file).exists() {
+            return Err(anyhow::anyhow!("入力ファイルが見つかりません: {}", self.input_file));
+        }
+
+        if !self.output_file.to_lowercase().ends_with(".xlsx") {
+            return Err(anyhow::anyhow!("出力ファイルはExcelファイル(.xlsx)である必要があります"));
+        }
+
+        if self.runs == 0 {
+            return Err(anyhow::anyhow!("--runsには1以上を指定してください"));
+        }
+
+        Ok(())
+    }
+}
+
+/// HARファイルのエンドポイントごとの集計をデータベースに追記し、推移を出力
+/// 
+/// # Arguments
+/// * `config` - trendサブコマンドの設定
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub(crate) async fn run(config: TrendConfig) -> Result<()> {
+    info!("推移の記録を開始します");
+
+    let analyzer = HarAnalyzer::with_read_mode(&config.input_file, config.read_mode)?;
+    let aggregates = trend::aggregate(&analyzer.analyze()?);
+
+    let mut database = TrendDatabase::open(&config.database)?;
+    let recorded_at = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let run_id = database.record(&config.input_file, &recorded_at, &aggregates)?;
+    info!(
+        "実行#{}として{}件のエンドポイントを記録しました: {}",
+        run_id,
+        aggregates.len(),
+        config.database
+    );
+
+    database.history(config.runs)?.export(&config.output_file)?;
+
+    info!("推移の記録が完了しました");
+    Ok(())
+}
@@ -0,0 +1,121 @@
+//! エントリの開始時刻の前後関係の検証（時刻のずれ・順序の乱れの検出と開始時刻順への並べ替え）を実装
+//! 
+//! プロキシ等の時計のずれや複数のキャプチャの結合により、エントリの開始時刻が記録順と前後したり、
+//! 前後のエントリから大きく離れたりすることがある。該当するエントリを警告としてWarningsシートに出力し、
+//! 指定された場合は開始時刻順に並べ替えて時系列を整える
+
+use crate::entry_warnings::{EntryWarning, WarningKind};
+use crate::har_types::Entry;
+use chrono::DateTime;
+use std::collections::BTreeMap;
+
+/// 前後のエントリから離れているとみなす開始時刻の差（ミリ秒）
+pub const SKEW_THRESHOLD_MS: i64 = 5 * 60 * 1000;
+
+/// 記録順に開始時刻の前後関係を検証
+/// 
+/// 前後のエントリが互いに近いにもかかわらず、どちらからも`SKEW_THRESHOLD_MS`以上離れたエントリを時刻のずれ、
+/// 時刻のずれを除いた直前のエントリより前に開始したエントリを順序の乱れとして警告する
+/// 
+/// # Arguments
+/// * `entries` - HARエントリのリスト
+/// 
+/// # Returns
+/// * `BTreeMap<usize, Vec<EntryWarning>>` - エントリの位置ごとの警告
+pub fn check(entries: &[Entry]) -> BTreeMap<usize, Vec<EntryWarning>> {
+    let starts: Vec<Option<i64>> = entries.iter().map(started_millis).collect();
+    let mut warnings: BTreeMap<usize, Vec<EntryWarning>> = BTreeMap::new();
+
+    // 前後のエントリ（開始時刻を解析できないエントリは飛ばす）
+    let timed: Vec<(usize, i64)> = starts
+        .iter()
+        .enumerate()
+        .filter_map(|(index, start)| start.map(|start| (index, start)))
+        .collect();
+    let mut skewed = vec![false; entries.len()];
+    for window in timed.windows(3) {
+        let [(_, previous), (index, start), (_, next)] = [window[0], window[1], window[2]];
+        if (next - previous).abs() < SKEW_THRESHOLD_MS
+            && (start - previous).abs() >= SKEW_THRESHOLD_MS
+            && (start - next).abs() >= SKEW_THRESHOLD_MS
+        {
+            skewed[index] = true;
+            warnings.entry(index).or_default().push(EntryWarning {
+                kind: WarningKind::ClockSkew,
+                message: format!(
+                    "開始時刻が前後のエントリから大きく離れています（直前から{}、直後まで{}）",
+                    signed_seconds(start - previous),
+                    signed_seconds(next - start)
+                ),
+            });
+        }
+    }
+
+    let mut latest: Option<i64> = None;
+    for (index, start) in timed {
+        if skewed[index] {
+            continue;
+        }
+        if let Some(previous) = latest
+            && start < previous
+        {
+            warnings.entry(index).or_default().push(EntryWarning {
+                kind: WarningKind::ClockSkew,
+                message: format!(
+                    "開始時刻がそれより前に記録されたエントリより{:.3}秒前です（記録順と開始時刻の順が異なります）",
+                    (previous - start) as f64 / 1000.0
+                ),
+            });
+        }
+        latest = Some(latest.map_or(start, |previous| previous.max(start)));
+    }
+    warnings
+}
+
+/// エントリを開始時刻順に並べ替える順序を求める
+/// 
+/// 開始時刻が同じエントリと開始時刻を解析できないエントリは記録順を保つ
+/// （開始時刻を解析できないエントリは直前のエントリの開始時刻とみなす）
+/// 
+/// # Arguments
+/// * `entries` - HARエントリのリスト
+/// 
+/// # Returns
+/// * `Vec<usize>` - 並べ替えた後の順のエントリの位置
+pub fn time_order(entries: &[Entry]) -> Vec<usize> {
+    let mut previous = i64::MIN;
+    let keys: Vec<i64> = entries
+        .iter()
+        .map(|entry| {
+            previous = started_millis(entry).unwrap_or(previous);
+            previous
+        })
+        .collect();
+    let mut order: Vec<usize> = (0..entries.len()).collect();
+    order.sort_by_key(|index| keys[*index]);
+    order
+}
+
+/// エントリの開始時刻
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `Option<i64>` - UNIX時刻（ミリ秒、解析できない場合はNone）
+fn started_millis(entry: &Entry) -> Option<i64> {
+    DateTime::parse_from_rfc3339(&entry.started_date_time)
+        .ok()
+        .map(|started| started.timestamp_millis())
+}
+
+/// 符号付きの秒数の表記
+/// 
+/// # Arguments
+/// * `millis` - 時間（ミリ秒）
+/// 
+/// # Returns
+/// * `String` - 秒数（例: `+3600.000秒`）
+fn signed_seconds(millis: i64) -> String {
+    format!("{:+.3}秒", millis as f64 / 1000.0)
+}
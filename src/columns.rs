@@ -0,0 +1,582 @@
+//! 出力する列の定義を実装
+
+use crate::har_types::{AnalysisResult, NameValue};
+use crate::tls::SecurityDetails;
+use crate::error::{AnalyzerError, Result};
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use rust_xlsxwriter::ExcelDateTime;
+
+/// セルのメモに出力するペイロードの最大バイト数
+const NOTE_PAYLOAD_BYTES: usize = 1024;
+
+/// セルのメモに出力するヘッダーの最大文字数（Excelのメモの上限より十分小さくする）
+const NOTE_HEADER_CHARS: usize = 8000;
+
+/// セルに書き込む値
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue<'a> {
+    Text(&'a str),
+    Number(f64),
+    /// Excelのシリアル値で表した日時
+    DateTime(f64),
+}
+
+/// 出力する列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    EntryNumber,
+    EntryId,
+    Timestamp,
+    SourceIp,
+    DestinationIp,
+    Method,
+    StatusCode,
+    StatusText,
+    RedirectUrl,
+    RequestUrl,
+    DecodedUrl,
+    Scheme,
+    Host,
+    Port,
+    Path,
+    Query,
+    RequestPayload,
+    ResponsePayload,
+    Duration,
+    Wait,
+    ServerTime,
+    ServerTiming,
+    TimestampIso,
+    RequestSize,
+    ResponseSize,
+    Outlier,
+    Gap,
+    HostGap,
+    SoapOperation,
+    RepeatCount,
+    Comments,
+    TlsProtocol,
+    TlsCipher,
+    CertIssuer,
+    CertExpiry,
+    DomainCategory,
+    Protocol,
+    BodyKind,
+    BusinessHours,
+    ResourceType,
+    InitiatorUrl,
+    InitiatorLine,
+    Priority,
+    RenderBlocking,
+    Warnings,
+}
+
+impl Column {
+    /// 選択可能な全ての列
+    pub const ALL: &'static [Column] = &[
+        Column::EntryNumber,
+        Column::EntryId,
+        Column::Timestamp,
+        Column::SourceIp,
+        Column::DestinationIp,
+        Column::Method,
+        Column::StatusCode,
+        Column::StatusText,
+        Column::RedirectUrl,
+        Column::RequestUrl,
+        Column::DecodedUrl,
+        Column::Scheme,
+        Column::Host,
+        Column::Port,
+        Column::Path,
+        Column::Query,
+        Column::RequestPayload,
+        Column::ResponsePayload,
+        Column::Duration,
+        Column::Wait,
+        Column::ServerTime,
+        Column::ServerTiming,
+        Column::TimestampIso,
+        Column::RequestSize,
+        Column::ResponseSize,
+        Column::Outlier,
+        Column::Gap,
+        Column::HostGap,
+        Column::SoapOperation,
+        Column::RepeatCount,
+        Column::Comments,
+        Column::TlsProtocol,
+        Column::TlsCipher,
+        Column::CertIssuer,
+        Column::CertExpiry,
+        Column::DomainCategory,
+        Column::Protocol,
+        Column::BodyKind,
+        Column::BusinessHours,
+        Column::ResourceType,
+        Column::InitiatorUrl,
+        Column::InitiatorLine,
+        Column::Priority,
+        Column::RenderBlocking,
+        Column::Warnings,
+    ];
+
+    /// 既定で出力する列
+    pub const DEFAULT: &'static [Column] = &[
+        Column::EntryNumber,
+        Column::EntryId,
+        Column::Timestamp,
+        Column::SourceIp,
+        Column::DestinationIp,
+        Column::Method,
+        Column::StatusCode,
+        Column::RequestUrl,
+        Column::DecodedUrl,
+        Column::RequestPayload,
+        Column::ResponsePayload,
+        Column::Warnings,
+    ];
+
+    /// ピボットテーブル向けの列（数値列とISO 8601形式の時刻）
+    pub const PIVOT: &'static [Column] = &[
+        Column::EntryNumber,
+        Column::EntryId,
+        Column::TimestampIso,
+        Column::DestinationIp,
+        Column::Method,
+        Column::StatusCode,
+        Column::Duration,
+        Column::RequestSize,
+        Column::ResponseSize,
+        Column::RequestUrl,
+        Column::Host,
+        Column::Path,
+    ];
+
+    /// コマンドラインで指定する列名
+    ///
+    /// # Returns
+    /// * `&'static str` - 列名
+    pub fn key(&self) -> &'static str {
+        match self {
+            Column::EntryNumber => "entry-number",
+            Column::EntryId => "entry-id",
+            Column::Timestamp => "timestamp",
+            Column::SourceIp => "source-ip",
+            Column::DestinationIp => "destination-ip",
+            Column::Method => "method",
+            Column::StatusCode => "status",
+            Column::StatusText => "status-text",
+            Column::RedirectUrl => "redirect-url",
+            Column::RequestUrl => "url",
+            Column::DecodedUrl => "decoded-url",
+            Column::Scheme => "scheme",
+            Column::Host => "host",
+            Column::Port => "port",
+            Column::Path => "path",
+            Column::Query => "query",
+            Column::RequestPayload => "request-payload",
+            Column::ResponsePayload => "response-payload",
+            Column::Duration => "duration",
+            Column::Wait => "wait",
+            Column::ServerTime => "server-time",
+            Column::ServerTiming => "server-timing",
+            Column::TimestampIso => "timestamp-iso",
+            Column::RequestSize => "request-size",
+            Column::ResponseSize => "response-size",
+            Column::Outlier => "outlier",
+            Column::Gap => "gap",
+            Column::HostGap => "host-gap",
+            Column::SoapOperation => "soap-operation",
+            Column::RepeatCount => "repeat-count",
+            Column::Comments => "comments",
+            Column::TlsProtocol => "tls-protocol",
+            Column::TlsCipher => "tls-cipher",
+            Column::CertIssuer => "cert-issuer",
+            Column::CertExpiry => "cert-expiry",
+            Column::DomainCategory => "domain-category",
+            Column::Protocol => "protocol",
+            Column::BodyKind => "body-kind",
+            Column::BusinessHours => "business-hours",
+            Column::ResourceType => "resource-type",
+            Column::InitiatorUrl => "initiator-url",
+            Column::InitiatorLine => "initiator-line",
+            Column::Priority => "priority",
+            Column::RenderBlocking => "render-blocking",
+            Column::Warnings => "warnings",
+        }
+    }
+
+    /// ヘッダー行に表示する列名
+    ///
+    /// # Returns
+    /// * `&'static str` - 表示名
+    pub fn header(&self) -> &'static str {
+        match self {
+            Column::EntryNumber => "エントリ番号",
+            Column::EntryId => "エントリID",
+            Column::Timestamp => "時刻",
+            Column::SourceIp => "送信元IP",
+            Column::DestinationIp => "送信先IP",
+            Column::Method => "メソッド",
+            Column::StatusCode => "ステータスコード",
+            Column::StatusText => "ステータステキスト",
+            Column::RedirectUrl => "リダイレクト先",
+            Column::RequestUrl => "リクエストURL",
+            Column::DecodedUrl => "デコード済みURL",
+            Column::Scheme => "スキーム",
+            Column::Host => "ホスト",
+            Column::Port => "ポート",
+            Column::Path => "パス",
+            Column::Query => "クエリ",
+            Column::RequestPayload => "リクエストペイロード",
+            Column::ResponsePayload => "レスポンスペイロード",
+            Column::Duration => "所要時間(ms)",
+            Column::Wait => "待機時間(ms)",
+            Column::ServerTime => "サーバーの処理時間(ms)",
+            Column::ServerTiming => "Server-Timing",
+            Column::TimestampIso => "時刻(ISO 8601)",
+            Column::RequestSize => "リクエストサイズ(bytes)",
+            Column::ResponseSize => "レスポンスサイズ(bytes)",
+            Column::Outlier => "外れ値",
+            Column::Gap => "前の通信からの間隔(ms)",
+            Column::HostGap => "同一ホストの前の通信からの間隔(ms)",
+            Column::SoapOperation => "SOAP操作",
+            Column::RepeatCount => "繰り返し回数",
+            Column::Comments => "コメント",
+            Column::TlsProtocol => "TLSプロトコル",
+            Column::TlsCipher => "暗号スイート",
+            Column::CertIssuer => "証明書の発行者",
+            Column::CertExpiry => "証明書の有効期限",
+            Column::DomainCategory => "ドメインの分類",
+            Column::Protocol => "プロトコル",
+            Column::BodyKind => "ボディの種類",
+            Column::BusinessHours => "営業時間",
+            Column::ResourceType => "リソースの種類",
+            Column::InitiatorUrl => "発生元のURL",
+            Column::InitiatorLine => "発生元の行",
+            Column::Priority => "優先度",
+            Column::RenderBlocking => "レンダリングのブロック",
+            Column::Warnings => "警告",
+        }
+    }
+
+    /// 列幅
+    ///
+    /// # Returns
+    /// * `f64` - 列幅
+    pub fn width(&self) -> f64 {
+        match self {
+            Column::EntryNumber => 12.0,
+            Column::EntryId => 20.0,
+            Column::Timestamp => 20.0,
+            Column::SourceIp => 15.0,
+            Column::DestinationIp => 20.0,
+            Column::Method => 10.0,
+            Column::StatusCode => 15.0,
+            Column::StatusText => 20.0,
+            Column::RedirectUrl => 50.0,
+            Column::RequestUrl => 50.0,
+            Column::DecodedUrl => 50.0,
+            Column::Scheme => 10.0,
+            Column::Host => 30.0,
+            Column::Port => 10.0,
+            Column::Path => 40.0,
+            Column::Query => 40.0,
+            Column::RequestPayload => 30.0,
+            Column::ResponsePayload => 30.0,
+            Column::Duration => 15.0,
+            Column::Wait => 15.0,
+            Column::ServerTime => 24.0,
+            Column::ServerTiming => 40.0,
+            Column::TimestampIso => 30.0,
+            Column::RequestSize => 22.0,
+            Column::ResponseSize => 22.0,
+            Column::Outlier => 10.0,
+            Column::Gap => 24.0,
+            Column::HostGap => 36.0,
+            Column::SoapOperation => 24.0,
+            Column::RepeatCount => 15.0,
+            Column::Comments => 40.0,
+            Column::TlsProtocol => 15.0,
+            Column::TlsCipher => 20.0,
+            Column::CertIssuer => 30.0,
+            Column::CertExpiry => 22.0,
+            Column::DomainCategory => 18.0,
+            Column::Protocol => 14.0,
+            Column::BodyKind => 13.0,
+            Column::BusinessHours => 12.0,
+            Column::ResourceType => 15.0,
+            Column::InitiatorUrl => 50.0,
+            Column::InitiatorLine => 12.0,
+            Column::Priority => 12.0,
+            Column::RenderBlocking => 22.0,
+            Column::Warnings => 40.0,
+        }
+    }
+
+    /// 大きくなり得る列かどうか（外部ファイルへの退避対象）
+    ///
+    /// # Returns
+    /// * `bool` - 大きくなり得る列の場合はtrue
+    pub fn is_large(&self) -> bool {
+        matches!(
+            self,
+            Column::RequestUrl
+                | Column::DecodedUrl
+                | Column::InitiatorUrl
+                | Column::Query
+                | Column::RedirectUrl
+                | Column::RequestPayload
+                | Column::ResponsePayload
+        )
+    }
+
+    /// ペイロード列かどうか（等幅フォントで表示する）
+    ///
+    /// # Returns
+    /// * `bool` - ペイロード列の場合はtrue
+    pub fn is_payload(&self) -> bool {
+        matches!(self, Column::RequestPayload | Column::ResponsePayload)
+    }
+
+    /// セルの表示形式
+    ///
+    /// # Returns
+    /// * `Option<&'static str>` - Excelの表示形式（標準の表示形式の場合はNone）
+    pub fn number_format(&self) -> Option<&'static str> {
+        match self {
+            Column::Timestamp => Some("yyyy-mm-dd hh:mm:ss.000"),
+            Column::Duration | Column::Wait | Column::ServerTime | Column::Gap | Column::HostGap => Some("#,##0.000"),
+            Column::RequestSize | Column::ResponseSize => Some("#,##0"),
+            _ => None,
+        }
+    }
+
+    /// 解析結果から列の値を取得
+    ///
+    /// ペイロード列の値は参照された時点で加工される
+    ///
+    /// # Arguments
+    /// * `result` - 解析結果
+    ///
+    /// # Returns
+    /// * `CellValue` - セルに書き込む値
+    pub fn value<'a>(&self, result: &'a AnalysisResult) -> CellValue<'a> {
+        match self {
+            Column::EntryNumber => CellValue::Number((result.entry_index + 1) as f64),
+            Column::EntryId => CellValue::Text(&result.entry_id),
+            Column::Timestamp => result
+                .started_at
+                .as_ref()
+                .and_then(Self::excel_datetime)
+                .map_or(CellValue::Text(&result.timestamp), CellValue::DateTime),
+            Column::SourceIp => CellValue::Text(&result.source_ip),
+            Column::DestinationIp => CellValue::Text(&result.destination_ip),
+            Column::Method => CellValue::Text(&result.method),
+            Column::StatusCode => CellValue::Number(result.status_code as f64),
+            Column::StatusText => CellValue::Text(&result.status_text),
+            Column::RedirectUrl => CellValue::Text(&result.redirect_url),
+            Column::RequestUrl => CellValue::Text(&result.request_url),
+            Column::DecodedUrl => CellValue::Text(result.decoded_url.as_str()),
+            Column::Scheme => CellValue::Text(&result.url_parts.scheme),
+            Column::Host => CellValue::Text(&result.url_parts.host),
+            Column::Port => result.url_parts.port.map_or(CellValue::Text(""), |port| CellValue::Number(port as f64)),
+            Column::Path => CellValue::Text(&result.url_parts.path),
+            Column::Query => CellValue::Text(&result.url_parts.query),
+            Column::RequestPayload => CellValue::Text(result.request_payload.as_str()),
+            Column::ResponsePayload => CellValue::Text(result.response_payload.as_str()),
+            Column::Duration => CellValue::Number(result.duration),
+            Column::Wait => CellValue::Number(result.wait),
+            Column::ServerTime => result.server_time.map_or(CellValue::Text(""), CellValue::Number),
+            Column::ServerTiming => CellValue::Text(&result.server_timing),
+            Column::TimestampIso => CellValue::Text(&result.timestamp_iso),
+            Column::RequestSize => CellValue::Number(result.request_size as f64),
+            Column::ResponseSize => CellValue::Number(result.response_size as f64),
+            Column::Outlier => CellValue::Text(if result.latency_outlier { "外れ値" } else { "" }),
+            Column::Gap => result.gap.map_or(CellValue::Text(""), CellValue::Number),
+            Column::HostGap => result.host_gap.map_or(CellValue::Text(""), CellValue::Number),
+            Column::SoapOperation => CellValue::Text(result.soap_operation.as_deref().unwrap_or_default()),
+            Column::RepeatCount => CellValue::Number(result.repeat_count as f64),
+            Column::Comments => CellValue::Text(&result.comments),
+            Column::TlsProtocol => CellValue::Text(Self::tls_text(result, |details| details.protocol.as_deref())),
+            Column::TlsCipher => CellValue::Text(Self::tls_text(result, |details| details.cipher.as_deref())),
+            Column::CertIssuer => CellValue::Text(Self::tls_text(result, |details| details.issuer.as_deref())),
+            Column::CertExpiry => CellValue::Text(result.cert_expiry.as_deref().unwrap_or_default()),
+            Column::DomainCategory => CellValue::Text(result.domain_category.label()),
+            Column::Protocol => CellValue::Text(result.protocol.label()),
+            Column::BodyKind => CellValue::Text(result.body_kind.label()),
+            Column::BusinessHours => CellValue::Text(match result.business_hours {
+                Some(true) => "時間内",
+                Some(false) => "時間外",
+                None => "",
+            }),
+            Column::ResourceType => CellValue::Text(&result.resource_type),
+            Column::InitiatorUrl => CellValue::Text(&result.initiator_url),
+            Column::InitiatorLine => result.initiator_line.map_or(CellValue::Text(""), |line| CellValue::Number(line as f64)),
+            Column::Priority => CellValue::Text(&result.priority),
+            Column::RenderBlocking => CellValue::Text(if result.render_blocking { "ブロック" } else { "" }),
+            Column::Warnings => CellValue::Text(result.warnings.text()),
+        }
+    }
+
+    /// 日時をExcelの日時に変換
+    ///
+    /// # Arguments
+    /// * `datetime` - 日時
+    ///
+    /// # Returns
+    /// * `Option<f64>` - Excelのシリアル値（Excelで扱えない1900年より前の日時等の場合はNone）
+    fn excel_datetime(datetime: &NaiveDateTime) -> Option<f64> {
+        let seconds = datetime.second() as f64 + datetime.nanosecond() as f64 / 1_000_000_000.0;
+        ExcelDateTime::from_ymd(u16::try_from(datetime.year()).ok()?, datetime.month() as u8, datetime.day() as u8)
+            .and_then(|date| date.and_hms(datetime.hour() as u16, datetime.minute() as u8, seconds))
+            .map(|datetime| datetime.to_excel())
+            .ok()
+    }
+
+    /// TLSの接続情報の項目を取得
+    ///
+    /// # Arguments
+    /// * `result` - 解析結果
+    /// * `field` - 接続情報から項目を取り出す関数
+    ///
+    /// # Returns
+    /// * `&str` - 項目の値（記録されていない場合は空文字列）
+    fn tls_text<'a>(result: &'a AnalysisResult, field: impl Fn(&'a SecurityDetails) -> Option<&'a str>) -> &'a str {
+        result.security_details.as_ref().and_then(field).unwrap_or_default()
+    }
+
+    /// セルのメモ（`--cell-notes`）に出力する詳細
+    ///
+    /// URL列はリクエストヘッダー、ステータスコード列はレスポンスヘッダー、
+    /// ペイロード列は列幅や外部ファイルへの退避に関わらず先頭1KBのペイロードを出力する
+    ///
+    /// # Arguments
+    /// * `result` - 解析結果
+    ///
+    /// # Returns
+    /// * `Option<String>` - メモの文字列（メモを付けない列・値が無い場合はNone）
+    pub fn note(&self, result: &AnalysisResult) -> Option<String> {
+        match self {
+            Column::RequestUrl | Column::DecodedUrl => Self::headers_note("リクエストヘッダー", &result.request_headers),
+            Column::StatusCode => Self::headers_note("レスポンスヘッダー", &result.response_headers),
+            Column::RequestPayload => Self::payload_note(result.request_payload.as_str()),
+            Column::ResponsePayload => Self::payload_note(result.response_payload.as_str()),
+            _ => None,
+        }
+    }
+
+    /// ヘッダーのメモを作成
+    ///
+    /// # Arguments
+    /// * `title` - メモの見出し
+    /// * `headers` - ヘッダーのリスト
+    ///
+    /// # Returns
+    /// * `Option<String>` - 1行1ヘッダーのメモ（ヘッダーが無い場合はNone）
+    fn headers_note(title: &str, headers: &[NameValue]) -> Option<String> {
+        if headers.is_empty() {
+            return None;
+        }
+        let mut note = format!("{}:", title);
+        for header in headers {
+            note.push_str(&format!("\n{}: {}", header.name, header.value));
+        }
+        if note.chars().count() > NOTE_HEADER_CHARS {
+            note = note.chars().take(NOTE_HEADER_CHARS).collect::<String>() + "…";
+        }
+        Some(note)
+    }
+
+    /// ペイロードのメモを作成
+    ///
+    /// # Arguments
+    /// * `payload` - ペイロード
+    ///
+    /// # Returns
+    /// * `Option<String>` - 先頭1KBのペイロード（空の場合はNone）
+    fn payload_note(payload: &str) -> Option<String> {
+        if payload.is_empty() {
+            return None;
+        }
+        if payload.len() <= NOTE_PAYLOAD_BYTES {
+            return Some(payload.to_string());
+        }
+        let mut end = NOTE_PAYLOAD_BYTES;
+        while !payload.is_char_boundary(end) {
+            end -= 1;
+        }
+        Some(format!("{}…（全{}バイト）", &payload[..end], payload.len()))
+    }
+
+    /// 名前付き範囲の名前（実行ごとに変わらない）
+    ///
+    /// # Returns
+    /// * `String` - 名前付き範囲の名前（例: "har_status"）
+    pub fn range_name(&self) -> String {
+        format!("har_{}", self.key().replace('-', "_"))
+    }
+
+    /// プリセット名から列のリストを取得
+    ///
+    /// # Arguments
+    /// * `name` - プリセット名（default, all, pivot）
+    ///
+    /// # Returns
+    /// * `Option<&'static [Column]>` - 対応する列のリスト
+    pub fn preset(name: &str) -> Option<&'static [Column]> {
+        match name {
+            "default" => Some(Column::DEFAULT),
+            "all" => Some(Column::ALL),
+            "pivot" => Some(Column::PIVOT),
+            _ => None,
+        }
+    }
+
+    /// 列名から列を取得
+    ///
+    /// # Arguments
+    /// * `key` - 列名
+    ///
+    /// # Returns
+    /// * `Option<Column>` - 対応する列
+    pub fn from_key(key: &str) -> Option<Column> {
+        Column::ALL.iter().copied().find(|c| c.key() == key)
+    }
+
+    /// カンマ区切りの列名リストを解析
+    ///
+    /// # Arguments
+    /// * `spec` - カンマ区切りの列名（例: "timestamp,method,status"）
+    ///
+    /// # Returns
+    /// * `Result<Vec<Column>>` - 成功時は列のリスト、失敗時はエラー
+    pub fn parse_list(spec: &str) -> Result<Vec<Column>> {
+        if let Some(columns) = Column::preset(spec.trim()) {
+            return Ok(columns.to_vec());
+        }
+
+        let columns = spec
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(|key| {
+                Column::from_key(key).ok_or_else(|| {
+                    let available: Vec<&str> = Column::ALL.iter().map(|c| c.key()).collect();
+                    AnalyzerError::InvalidArgument(format!(
+                        "不明な列名です: {} (指定可能な列: {})",
+                        key,
+                        available.join(", ")
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if columns.is_empty() {
+            return Err(AnalyzerError::InvalidArgument("出力する列が指定されていません".to_string()));
+        }
+
+        Ok(columns)
+    }
+}
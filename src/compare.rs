@@ -0,0 +1,229 @@
+//! 2つのHARファイルのレスポンスの比較を実装
+//! 
+//! エンドポイント（メソッドとクエリ文字列・フラグメントを除いたURL）ごとに同じ回数目の呼び出しを対応付け、
+//! ステータスコード・所要時間の変化とレスポンスボディの構造的な差分（JSONの追加・削除・変更されたキー）をCompareシートに出力する。
+//! 所要時間だけでなく、ステータスコードを変えずにレスポンスの形式が変わった（暗黙の契約変更）箇所を確認できるようにする
+
+use crate::error::{AnalyzerError, Result};
+use crate::excel_styles::ExcelStyles;
+use crate::har_types::Entry;
+use crate::json_diff;
+use crate::pair_diff::{self, BodyDifference};
+use log::info;
+use rust_xlsxwriter::Workbook;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Compareシートの名前
+pub const COMPARE_SHEET_NAME: &str = "Compare";
+
+/// 差分の列に含めるJSONの差分の最大数
+const MAX_SUMMARY_CHANGES: usize = 10;
+
+/// 一方のHARファイルのレスポンス
+#[derive(Debug, Clone)]
+pub struct ComparedSide {
+    /// エントリ番号（HAR内の順序で1始まり）
+    pub entry_number: usize,
+    /// ステータスコード
+    pub status: i32,
+    /// 所要時間（ミリ秒）
+    pub time_ms: f64,
+}
+
+/// 対応付けた呼び出しの比較結果
+#[derive(Debug, Clone)]
+pub struct ComparedResponse {
+    /// エンドポイント（メソッドとクエリ文字列・フラグメントを除いたURL）
+    pub endpoint: String,
+    /// エンドポイントへの何回目の呼び出しか（1始まり）
+    pub occurrence: usize,
+    /// 比較元のHARファイルのレスポンス（比較先にのみ存在する場合はNone）
+    pub before: Option<ComparedSide>,
+    /// 比較先のHARファイルのレスポンス（比較元にのみ存在する場合はNone）
+    pub after: Option<ComparedSide>,
+    /// レスポンスボディの違い（同じ場合・一方にのみ存在する場合はNone）
+    pub body: Option<BodyDifference>,
+}
+
+impl ComparedResponse {
+    /// 所要時間の変化
+    /// 
+    /// # Returns
+    /// * `Option<f64>` - 比較先から比較元を引いた所要時間（ミリ秒、一方にのみ存在する場合はNone）
+    pub fn time_delta_ms(&self) -> Option<f64> {
+        Some(self.after.as_ref()?.time_ms - self.before.as_ref()?.time_ms)
+    }
+
+    /// ステータスコードが変化したかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - 両方に存在してステータスコードが異なる場合はtrue
+    pub fn status_changed(&self) -> bool {
+        matches!((&self.before, &self.after), (Some(before), Some(after)) if before.status != after.status)
+    }
+
+    /// レスポンスボディの差分のテキスト
+    /// 
+    /// # Returns
+    /// * `String` - JSONの差分の要約（JSON以外はサイズの変化、差分が無い場合は空文字列）
+    pub fn body_diff_text(&self) -> String {
+        match &self.body {
+            Some(BodyDifference::Json(changes)) => json_diff::summarize(changes, MAX_SUMMARY_CHANGES),
+            Some(BodyDifference::Text { before_size, after_size }) => {
+                format!("~ ボディ（JSON以外）: {} → {} バイト", before_size, after_size)
+            }
+            None => String::new(),
+        }
+    }
+}
+
+/// 2つのHARファイルのエントリを比較
+/// 
+/// エンドポイントごとにn回目の呼び出し同士を対応付け、レスポンスボディは両方に記録されている場合のみ比較する
+/// 
+/// # Arguments
+/// * `before` - 比較元のHARエントリ
+/// * `after` - 比較先のHARエントリ
+/// 
+/// # Returns
+/// * `Vec<ComparedResponse>` - 比較元のHAR内の順序の比較結果（比較先にのみ存在する呼び出しは末尾）
+pub fn compare(before: &[Entry], after: &[Entry]) -> Vec<ComparedResponse> {
+    let mut after_calls: HashMap<(String, usize), (usize, &Entry)> = HashMap::new();
+    let mut after_order = Vec::new();
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for (index, entry) in after.iter().enumerate() {
+        let endpoint = pair_diff::endpoint_key(entry);
+        let occurrence = counts.entry(endpoint.clone()).or_default();
+        *occurrence += 1;
+        after_order.push((endpoint.clone(), *occurrence));
+        after_calls.insert((endpoint, *occurrence), (index, entry));
+    }
+
+    let mut compared = Vec::new();
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for (index, entry) in before.iter().enumerate() {
+        let endpoint = pair_diff::endpoint_key(entry);
+        let occurrence = counts.entry(endpoint.clone()).or_default();
+        *occurrence += 1;
+        let occurrence = *occurrence;
+        let matched = after_calls.remove(&(endpoint.clone(), occurrence));
+        let body = matched.and_then(|(_, after_entry)| {
+            (entry.response.content.text.is_some() && after_entry.response.content.text.is_some())
+                .then(|| pair_diff::body_difference(entry, after_entry))
+                .flatten()
+        });
+        compared.push(ComparedResponse {
+            endpoint,
+            occurrence,
+            before: Some(side(index, entry)),
+            after: matched.map(|(after_index, after_entry)| side(after_index, after_entry)),
+            body,
+        });
+    }
+    for key in after_order {
+        if let Some((index, entry)) = after_calls.remove(&key) {
+            compared.push(ComparedResponse {
+                endpoint: key.0,
+                occurrence: key.1,
+                before: None,
+                after: Some(side(index, entry)),
+                body: None,
+            });
+        }
+    }
+    compared
+}
+
+/// 比較結果をExcelファイルに出力
+/// 
+/// # Arguments
+/// * `compared` - 比較結果
+/// * `output_path` - 出力するExcelファイルのパス
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn export(compared: &[ComparedResponse], output_path: &str) -> Result<()> {
+    info!("Excelファイルに出力しています: {}", output_path);
+
+    if let Some(parent) = Path::new(output_path).parent() {
+        fs::create_dir_all(parent)
+            .map_err(AnalyzerError::io("出力ディレクトリの作成に失敗しました"))?;
+    }
+
+    let styles = ExcelStyles::new(true);
+    let milliseconds = styles.cell.clone().set_num_format("#,##0.000");
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name(COMPARE_SHEET_NAME)?;
+
+    let headers = [
+        ("エンドポイント", 60.0),
+        ("回", 6.0),
+        ("比較元のエントリ番号", 12.0),
+        ("比較先のエントリ番号", 12.0),
+        ("比較元のステータス", 12.0),
+        ("比較先のステータス", 12.0),
+        ("比較元の所要時間(ms)", 16.0),
+        ("比較先の所要時間(ms)", 16.0),
+        ("所要時間の変化(ms)", 16.0),
+        ("レスポンスボディの差分", 80.0),
+    ];
+    for (col, (header, width)) in headers.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, *header, &styles.header)?;
+        worksheet.set_column_width(col as u16, *width)?;
+    }
+    worksheet.set_freeze_panes(1, 1)?;
+
+    for (index, response) in compared.iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_string_with_format(row, 0, &response.endpoint, &styles.cell)?;
+        worksheet.write_number_with_format(row, 1, response.occurrence as f64, &styles.cell)?;
+        for (offset, side) in [&response.before, &response.after].into_iter().enumerate() {
+            let offset = offset as u16;
+            match side {
+                Some(side) => {
+                    worksheet.write_number_with_format(row, 2 + offset, side.entry_number as f64, &styles.cell)?;
+                    worksheet.write_number_with_format(row, 4 + offset, side.status as f64, &styles.cell)?;
+                    worksheet.write_number_with_format(row, 6 + offset, side.time_ms, &milliseconds)?;
+                }
+                None => {
+                    for col in [2, 4, 6] {
+                        worksheet.write_string_with_format(row, col + offset, "", &styles.cell)?;
+                    }
+                }
+            }
+        }
+        match response.time_delta_ms() {
+            Some(delta) => worksheet.write_number_with_format(row, 8, delta, &milliseconds)?,
+            None => worksheet.write_string_with_format(row, 8, "", &styles.cell)?,
+        };
+        worksheet.write_string_with_format(row, 9, response.body_diff_text(), &styles.cell)?;
+    }
+    if !compared.is_empty() {
+        worksheet.autofilter(0, 0, compared.len() as u32, headers.len() as u16 - 1)?;
+    }
+
+    workbook.save(output_path)
+        .map_err(AnalyzerError::export("Excelファイルの保存に失敗しました"))?;
+
+    info!("Excelファイルの出力が完了しました: {} ({}件の呼び出し)", output_path, compared.len());
+    Ok(())
+}
+
+/// 一方のHARファイルのレスポンスを作成
+/// 
+/// # Arguments
+/// * `index` - エントリの位置（0始まり）
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `ComparedSide` - レスポンス
+fn side(index: usize, entry: &Entry) -> ComparedSide {
+    ComparedSide {
+        entry_number: index + 1,
+        status: entry.response.status,
+        time_ms: entry.time,
+    }
+}
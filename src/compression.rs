@@ -0,0 +1,171 @@
+//! 圧縮せずに配信されたテキストのレスポンスについて、gzip・brotliで圧縮した場合に削減できる転送量の見積もりを実装
+//! 
+//! レスポンスボディを実際にメモリ上で圧縮し、ホストごとに集計してCompressionシートに出力する
+
+use crate::body_kind::BodyKind;
+use crate::har_types::Entry;
+use crate::mime_map::MimeMap;
+use crate::mock_server;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use std::collections::BTreeMap;
+use std::io::Write;
+use url::Url;
+
+/// 見積もりの対象とするレスポンスボディの最小サイズ（バイト、これより小さいボディは圧縮の効果が小さい）
+const MIN_BODY_SIZE: usize = 1024;
+
+/// gzipの圧縮レベル（一般的なWebサーバーの既定値）
+const GZIP_LEVEL: u32 = 6;
+
+/// brotliの品質（動的な圧縮で一般的な値）
+const BROTLI_QUALITY: u32 = 6;
+
+/// brotliのウィンドウサイズ（2の対数）
+const BROTLI_WINDOW: u32 = 22;
+
+/// ホストごとの圧縮による削減量の見積もり
+#[derive(Debug, Clone, Default)]
+pub struct HostSavings {
+    /// ホスト名
+    pub host: String,
+    /// 圧縮せずに配信されたテキストのレスポンスの数
+    pub responses: usize,
+    /// 元のサイズの合計（バイト）
+    pub original_bytes: usize,
+    /// gzipで圧縮した場合のサイズの合計（バイト）
+    pub gzip_bytes: usize,
+    /// brotliで圧縮した場合のサイズの合計（バイト）
+    pub brotli_bytes: usize,
+}
+
+impl HostSavings {
+    /// gzipで削減できるサイズ
+    /// 
+    /// # Returns
+    /// * `usize` - 元のサイズとの差（バイト）
+    pub fn gzip_savings(&self) -> usize {
+        self.original_bytes.saturating_sub(self.gzip_bytes)
+    }
+
+    /// brotliで削減できるサイズ
+    /// 
+    /// # Returns
+    /// * `usize` - 元のサイズとの差（バイト）
+    pub fn brotli_savings(&self) -> usize {
+        self.original_bytes.saturating_sub(self.brotli_bytes)
+    }
+}
+
+/// 圧縮せずに配信されたテキストのレスポンスを圧縮し、ホストごとの削減量を見積もる
+/// 
+/// # Arguments
+/// * `entries` - HARエントリのイテレータ
+/// * `mime_map` - MIMEタイプの分類（対応付けたMIMEタイプはテキストの分類のみを対象とする）
+/// 
+/// # Returns
+/// * `Vec<HostSavings>` - brotliで削減できるサイズの大きい順のホストごとの見積もり
+pub fn estimate<'a>(entries: impl Iterator<Item = &'a Entry>, mime_map: &MimeMap) -> Vec<HostSavings> {
+    let mut hosts: BTreeMap<String, HostSavings> = BTreeMap::new();
+    for entry in entries {
+        let mime_type = &entry.response.content.mime_type;
+        let is_text = mime_map.mapped(mime_type).map_or_else(|| is_text_content(entry), |category| category.is_text());
+        if !is_text || is_encoded(entry) {
+            continue;
+        }
+        let body = mock_server::response_body(entry);
+        if body.len() < MIN_BODY_SIZE {
+            continue;
+        }
+        let Some(host) = Url::parse(&entry.request.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        else {
+            continue;
+        };
+        let (Some(gzip_bytes), Some(brotli_bytes)) = (gzip_size(&body), brotli_size(&body)) else {
+            continue;
+        };
+        let savings = hosts
+            .entry(host.clone())
+            .or_insert_with(|| HostSavings { host, ..HostSavings::default() });
+        savings.responses += 1;
+        savings.original_bytes += body.len();
+        savings.gzip_bytes += gzip_bytes;
+        savings.brotli_bytes += brotli_bytes;
+    }
+
+    let mut savings: Vec<HostSavings> = hosts.into_values().collect();
+    savings.sort_by(|a, b| b.brotli_savings().cmp(&a.brotli_savings()).then_with(|| a.host.cmp(&b.host)));
+    savings
+}
+
+/// 全てのホストの見積もりを合計
+/// 
+/// # Arguments
+/// * `savings` - ホストごとの見積もり
+/// 
+/// # Returns
+/// * `HostSavings` - 合計（ホスト名は空文字列）
+pub fn total(savings: &[HostSavings]) -> HostSavings {
+    HostSavings {
+        host: String::new(),
+        responses: savings.iter().map(|host| host.responses).sum(),
+        original_bytes: savings.iter().map(|host| host.original_bytes).sum(),
+        gzip_bytes: savings.iter().map(|host| host.gzip_bytes).sum(),
+        brotli_bytes: savings.iter().map(|host| host.brotli_bytes).sum(),
+    }
+}
+
+/// 圧縮の効果があるレスポンスかどうか
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `bool` - ボディの種類がテキスト、またはMIMEタイプが圧縮の効果があるバイナリ（WebAssembly・非圧縮のフォント）の場合はtrue
+fn is_text_content(entry: &Entry) -> bool {
+    let mime_type = entry.response.content.mime_type.to_ascii_lowercase();
+    let mime_type = mime_type.split(';').next().unwrap_or_default().trim();
+    BodyKind::of_response(entry).is_text()
+        || matches!(mime_type, "application/wasm" | "font/ttf" | "font/otf" | "application/vnd.ms-fontobject")
+}
+
+/// レスポンスが圧縮して配信されたかどうか
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `bool` - identity以外のContent-Encodingヘッダーがある場合はtrue
+fn is_encoded(entry: &Entry) -> bool {
+    entry.response.headers.iter().any(|header| {
+        header.name.eq_ignore_ascii_case("content-encoding") && !header.value.trim().eq_ignore_ascii_case("identity")
+    })
+}
+
+/// gzipで圧縮した場合のサイズを求める
+/// 
+/// # Arguments
+/// * `body` - レスポンスボディ
+/// 
+/// # Returns
+/// * `Option<usize>` - 圧縮後のサイズ（圧縮に失敗した場合はNone）
+fn gzip_size(body: &[u8]) -> Option<usize> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::new(GZIP_LEVEL));
+    encoder.write_all(body).ok()?;
+    encoder.finish().ok().map(|compressed| compressed.len())
+}
+
+/// brotliで圧縮した場合のサイズを求める
+/// 
+/// # Arguments
+/// * `body` - レスポンスボディ
+/// 
+/// # Returns
+/// * `Option<usize>` - 圧縮後のサイズ（圧縮に失敗した場合はNone）
+fn brotli_size(body: &[u8]) -> Option<usize> {
+    let mut writer = brotli::CompressorWriter::new(Vec::new(), 4096, BROTLI_QUALITY, BROTLI_WINDOW);
+    writer.write_all(body).ok()?;
+    Some(writer.into_inner().len())
+}
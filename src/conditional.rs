@@ -0,0 +1,170 @@
+//! 条件付きリクエスト（If-None-Match・If-Modified-Since）の効果の集計を実装
+//! 
+//! URLごとに条件付きリクエストの数と304・200の内訳を集計し、検証子が一致しているのに
+//! 完全なレスポンスを返したURL（検証子を無視するエンドポイント）をConditionalシートに出力する
+
+use crate::har_types::{Entry, NameValue};
+use chrono::DateTime;
+use std::collections::HashMap;
+
+/// URLごとの条件付きリクエストの集計
+#[derive(Debug, Clone, Default)]
+pub struct ConditionalStats {
+    /// リクエストURL
+    pub url: String,
+    /// リクエストの数
+    pub requests: usize,
+    /// 検証子（ETag・Last-Modified）を返したレスポンスの数
+    pub validated_responses: usize,
+    /// If-None-Match・If-Modified-Sinceを送信したリクエストの数
+    pub conditional: usize,
+    /// 条件付きリクエストのうち304を返した数
+    pub not_modified: usize,
+    /// 条件付きリクエストのうち200を返した数
+    pub full: usize,
+    /// 200を返したうち、送信した検証子がレスポンスの検証子と一致していた数
+    pub ignored: usize,
+}
+
+impl ConditionalStats {
+    /// 条件付きリクエストのうち304を返した割合
+    /// 
+    /// # Returns
+    /// * `f64` - 割合（0.0〜1.0、条件付きリクエストが無い場合は0.0）
+    pub fn not_modified_rate(&self) -> f64 {
+        if self.conditional == 0 {
+            0.0
+        } else {
+            self.not_modified as f64 / self.conditional as f64
+        }
+    }
+
+    /// 別の集計を合算する
+    /// 
+    /// # Arguments
+    /// * `other` - 合算する集計
+    fn merge(&mut self, other: &ConditionalStats) {
+        self.requests += other.requests;
+        self.validated_responses += other.validated_responses;
+        self.conditional += other.conditional;
+        self.not_modified += other.not_modified;
+        self.full += other.full;
+        self.ignored += other.ignored;
+    }
+}
+
+/// URLごとに条件付きリクエストを集計
+/// 
+/// # Arguments
+/// * `entries` - HARエントリのイテレータ
+/// 
+/// # Returns
+/// * `Vec<ConditionalStats>` - 条件付きリクエストを送信したURLの集計（検証子を無視した数・200を返した数の多い順）
+pub fn analyze<'a>(entries: impl Iterator<Item = &'a Entry>) -> Vec<ConditionalStats> {
+    let mut urls: HashMap<&str, ConditionalStats> = HashMap::new();
+    for entry in entries {
+        let stats = urls.entry(entry.request.url.as_str()).or_insert_with(|| ConditionalStats {
+            url: entry.request.url.clone(),
+            ..ConditionalStats::default()
+        });
+        stats.requests += 1;
+
+        let etag = header_value(&entry.response.headers, "etag");
+        let last_modified = header_value(&entry.response.headers, "last-modified");
+        if etag.is_some() || last_modified.is_some() {
+            stats.validated_responses += 1;
+        }
+
+        let if_none_match = header_value(&entry.request.headers, "if-none-match");
+        let if_modified_since = header_value(&entry.request.headers, "if-modified-since");
+        if if_none_match.is_none() && if_modified_since.is_none() {
+            continue;
+        }
+        stats.conditional += 1;
+        match entry.response.status {
+            304 => stats.not_modified += 1,
+            200 => {
+                stats.full += 1;
+                let etag_matched = if_none_match.zip(etag).is_some_and(|(tags, etag)| etag_matches(tags, etag));
+                let unmodified = if_modified_since
+                    .zip(last_modified)
+                    .is_some_and(|(since, modified)| not_modified_since(since, modified));
+                if etag_matched || unmodified {
+                    stats.ignored += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut stats: Vec<ConditionalStats> = urls.into_values().filter(|stats| stats.conditional > 0).collect();
+    stats.sort_by(|a, b| {
+        b.ignored
+            .cmp(&a.ignored)
+            .then_with(|| b.full.cmp(&a.full))
+            .then_with(|| a.url.cmp(&b.url))
+    });
+    stats
+}
+
+/// 全てのURLの集計を合計
+/// 
+/// # Arguments
+/// * `stats` - URLごとの集計
+/// 
+/// # Returns
+/// * `ConditionalStats` - 合計（URLは空文字列）
+pub fn total(stats: &[ConditionalStats]) -> ConditionalStats {
+    let mut total = ConditionalStats::default();
+    for url in stats {
+        total.merge(url);
+    }
+    total
+}
+
+/// 指定した名前の最初のヘッダーの値を取得
+/// 
+/// # Arguments
+/// * `headers` - ヘッダーのリスト
+/// * `name` - ヘッダーの名前（小文字）
+/// 
+/// # Returns
+/// * `Option<&str>` - ヘッダーの値（無い場合や空の場合はNone）
+fn header_value<'a>(headers: &'a [NameValue], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case(name))
+        .map(|header| header.value.trim())
+        .filter(|value| !value.is_empty())
+}
+
+/// If-None-MatchのいずれかのエンティティタグがレスポンスのETagと一致するかどうか（弱い比較）
+/// 
+/// # Arguments
+/// * `if_none_match` - If-None-Matchヘッダーの値
+/// * `etag` - ETagヘッダーの値
+/// 
+/// # Returns
+/// * `bool` - 一致する場合はtrue
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag);
+    if_none_match
+        .split(',')
+        .any(|tag| tag.trim() == "*" || opaque(tag) == etag)
+}
+
+/// If-Modified-Since以降にレスポンスが更新されていないかどうか
+/// 
+/// # Arguments
+/// * `if_modified_since` - If-Modified-Sinceヘッダーの値（HTTP日付）
+/// * `last_modified` - Last-Modifiedヘッダーの値（HTTP日付）
+/// 
+/// # Returns
+/// * `bool` - 更新されていない場合はtrue（日付を解析できない場合はfalse）
+fn not_modified_since(if_modified_since: &str, last_modified: &str) -> bool {
+    match (DateTime::parse_from_rfc2822(if_modified_since), DateTime::parse_from_rfc2822(last_modified)) {
+        (Ok(since), Ok(modified)) => modified <= since,
+        _ => false,
+    }
+}
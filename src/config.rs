@@ -0,0 +1,50 @@
+//! 設定ファイル（TOML）の読み込みを実装
+
+use crate::egress_cost::EgressPricing;
+use crate::mime_map::MimeCategory;
+use crate::privacy::PrivacyConfig;
+use crate::transform::TransformStep;
+use crate::error::{AnalyzerError, Result};
+use log::info;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+
+/// 設定ファイルの内容
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FileConfig {
+    /// transformサブコマンドで順に適用する変換処理
+    #[serde(default)]
+    pub transform: Vec<TransformStep>,
+    /// analyze・batchサブコマンドのオプション（長い形式の名前と値）をまとめたプロファイル
+    #[serde(default)]
+    pub profile: BTreeMap<String, toml::Table>,
+    /// MIMEタイプから分類への対応付け（JSONの判定・ボディの整形・圧縮の見積もりに使用）
+    #[serde(default)]
+    pub mime_types: BTreeMap<String, MimeCategory>,
+    /// 転送量の費用の見積もりに使用するGBあたりの単価
+    #[serde(default)]
+    pub egress_pricing: Option<EgressPricing>,
+    /// `--privacy NAME`で選択する列ごとの匿名化の設定（列名からレベルへの対応付けとhashの鍵）
+    #[serde(default)]
+    pub privacy: BTreeMap<String, PrivacyConfig>,
+}
+
+impl FileConfig {
+    /// 設定ファイルを読み込み
+    /// 
+    /// # Arguments
+    /// * `file_path` - 設定ファイルのパス
+    /// 
+    /// # Returns
+    /// * `Result<FileConfig>` - 成功時は設定、失敗時はエラー
+    pub fn load(file_path: &str) -> Result<Self> {
+        info!("設定ファイルを読み込んでいます: {}", file_path);
+
+        let content = fs::read_to_string(file_path)
+            .map_err(AnalyzerError::io("設定ファイルの読み込みに失敗しました"))?;
+
+        toml::from_str(&content)
+            .map_err(|e| AnalyzerError::Config(e.to_string()))
+    }
+}
@@ -0,0 +1,262 @@
+//! キャプチャとOpenAPI仕様書の突き合わせ（契約テスト）を実装
+
+use crate::error::{AnalyzerError, Result};
+use crate::excel_styles::ExcelStyles;
+use crate::har_types::{Entry, HarFile};
+use crate::openapi::OpenApiSpec;
+use crate::payload::is_json_content;
+use base64::Engine;
+use log::{info, warn};
+use rust_xlsxwriter::Workbook;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use url::Url;
+
+/// 結果を出力するシートの名前
+const CONFORMANCE_SHEET_NAME: &str = "Conformance";
+
+/// 仕様書との不一致の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IssueKind {
+    /// 仕様書に記載されていないパス
+    UndocumentedEndpoint,
+    /// パスは記載されているがメソッドが記載されていない
+    UndocumentedMethod,
+    /// 仕様書に記載されていないステータスコード
+    UnexpectedStatus,
+    /// リクエストボディがスキーマに違反
+    RequestSchema,
+    /// レスポンスボディがスキーマに違反
+    ResponseSchema,
+}
+
+impl IssueKind {
+    /// 表示名
+    /// 
+    /// # Returns
+    /// * `&'static str` - 表示名
+    pub fn label(&self) -> &'static str {
+        match self {
+            IssueKind::UndocumentedEndpoint => "未記載のエンドポイント",
+            IssueKind::UndocumentedMethod => "未記載のメソッド",
+            IssueKind::UnexpectedStatus => "想定外のステータスコード",
+            IssueKind::RequestSchema => "リクエストのスキーマ違反",
+            IssueKind::ResponseSchema => "レスポンスのスキーマ違反",
+        }
+    }
+}
+
+/// 仕様書との不一致
+#[derive(Debug, Clone)]
+pub struct ConformanceIssue {
+    /// 不一致の種類
+    pub kind: IssueKind,
+    /// HTTPメソッド
+    pub method: String,
+    /// リクエストURL
+    pub url: String,
+    /// 一致した操作のパス（一致しなかった場合は空）
+    pub operation: String,
+    /// ステータスコード
+    pub status: i32,
+    /// 不一致の内容
+    pub message: String,
+}
+
+/// 突き合わせの結果
+#[derive(Debug, Default)]
+pub struct ConformanceReport {
+    /// 仕様書の対象のホストへのリクエスト数
+    pub checked: usize,
+    /// 仕様書の操作に一致したリクエスト数
+    pub matched: usize,
+    /// 不一致のリスト
+    pub issues: Vec<ConformanceIssue>,
+}
+
+impl ConformanceReport {
+    /// HARファイルの全てのエントリを仕様書と突き合わせ
+    /// 
+    /// # Arguments
+    /// * `spec` - OpenAPI仕様書
+    /// * `har` - HARファイル
+    /// 
+    /// # Returns
+    /// * `ConformanceReport` - 突き合わせの結果
+    pub fn check(spec: &OpenApiSpec, har: &HarFile) -> Self {
+        let mut report = ConformanceReport::default();
+        for entry in &har.log.entries {
+            report.check_entry(spec, entry);
+        }
+        report
+    }
+
+    /// 1件のエントリを仕様書と突き合わせ
+    /// 
+    /// # Arguments
+    /// * `spec` - OpenAPI仕様書
+    /// * `entry` - HARエントリ
+    fn check_entry(&mut self, spec: &OpenApiSpec, entry: &Entry) {
+        let Ok(url) = Url::parse(&entry.request.url) else {
+            return;
+        };
+        if !spec.covers(&url) {
+            return;
+        }
+        self.checked += 1;
+
+        let method = entry.request.method.as_str();
+        let status = entry.response.status;
+        let mut issue = |kind: IssueKind, operation: &str, message: String| {
+            self.issues.push(ConformanceIssue {
+                kind,
+                method: method.to_string(),
+                url: entry.request.url.clone(),
+                operation: operation.to_string(),
+                status,
+                message,
+            });
+        };
+
+        let Some(operation) = spec.find_operation(method, url.path()) else {
+            if spec.has_path(url.path()) {
+                issue(IssueKind::UndocumentedMethod, "", format!("パス{}に{}は記載されていません", url.path(), method));
+            } else {
+                issue(IssueKind::UndocumentedEndpoint, "", format!("パス{}は記載されていません", url.path()));
+            }
+            return;
+        };
+        self.matched += 1;
+
+        // リクエストボディ
+        if let Some(schema) = &operation.request_schema
+            && let Some(post_data) = &entry.request.post_data
+            && is_json_content(&post_data.mime_type)
+            && let Some(body) = post_data.text.as_deref().filter(|text| !text.is_empty())
+        {
+            match serde_json::from_str::<Value>(body) {
+                Ok(value) => {
+                    for violation in spec.validate(schema, &value) {
+                        issue(IssueKind::RequestSchema, &operation.path, violation);
+                    }
+                }
+                Err(e) => issue(IssueKind::RequestSchema, &operation.path, format!("JSONとして解析できません: {}", e)),
+            }
+        }
+
+        // ステータスコードとレスポンスボディ
+        let Some(response_schema) = operation.response_schema(status) else {
+            let documented: Vec<&str> = operation.responses.iter().map(|(code, _)| code.as_str()).collect();
+            issue(
+                IssueKind::UnexpectedStatus,
+                &operation.path,
+                format!("ステータスコード{}は記載されていません（記載: {}）", status, documented.join(", ")),
+            );
+            return;
+        };
+        if let Some(schema) = response_schema
+            && is_json_content(&entry.response.content.mime_type)
+            && let Some(body) = Self::response_body(entry)
+        {
+            match serde_json::from_str::<Value>(&body) {
+                Ok(value) => {
+                    for violation in spec.validate(schema, &value) {
+                        issue(IssueKind::ResponseSchema, &operation.path, violation);
+                    }
+                }
+                Err(e) => issue(IssueKind::ResponseSchema, &operation.path, format!("JSONとして解析できません: {}", e)),
+            }
+        }
+    }
+
+    /// レスポンスボディを取得（Base64でエンコードされている場合はデコード）
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `Option<String>` - レスポンスボディ（記録されていない場合はNone）
+    fn response_body(entry: &Entry) -> Option<String> {
+        let text = entry.response.content.text.as_deref().filter(|text| !text.is_empty())?;
+        if entry.response.content.encoding.as_deref() == Some("base64") {
+            let bytes = base64::engine::general_purpose::STANDARD.decode(text).ok()?;
+            String::from_utf8(bytes).ok()
+        } else {
+            Some(text.to_string())
+        }
+    }
+
+    /// 結果をログに出力
+    pub fn log(&self) {
+        info!("OpenAPI仕様書との突き合わせ結果:");
+        info!("  - 対象のリクエスト数: {}", self.checked);
+        info!("  - 操作に一致したリクエスト数: {}", self.matched);
+
+        let mut counts: BTreeMap<IssueKind, usize> = BTreeMap::new();
+        for issue in &self.issues {
+            *counts.entry(issue.kind).or_insert(0) += 1;
+        }
+        if counts.is_empty() {
+            info!("  - 仕様書との不一致はありません");
+        }
+        for (kind, count) in counts {
+            warn!("  - {}: {}件", kind.label(), count);
+        }
+    }
+
+    /// 結果をExcelファイルに出力
+    /// 
+    /// # Arguments
+    /// * `output_path` - 出力ファイルのパス
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub fn export(&self, output_path: &str) -> Result<()> {
+        info!("Excelファイルに出力しています: {}", output_path);
+
+        if let Some(parent) = Path::new(output_path).parent() {
+            fs::create_dir_all(parent)
+                .map_err(AnalyzerError::io("出力ディレクトリの作成に失敗しました"))?;
+        }
+
+        let styles = ExcelStyles::new(true);
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(CONFORMANCE_SHEET_NAME)?;
+
+        let headers = [
+            ("種類", 24.0),
+            ("メソッド", 10.0),
+            ("URL", 50.0),
+            ("操作", 30.0),
+            ("ステータスコード", 15.0),
+            ("内容", 60.0),
+        ];
+        for (col, (header, width)) in headers.iter().enumerate() {
+            worksheet.write_string_with_format(0, col as u16, *header, &styles.header)?;
+            worksheet.set_column_width(col as u16, *width)?;
+        }
+        worksheet.set_freeze_panes(1, 0)?;
+
+        for (index, issue) in self.issues.iter().enumerate() {
+            let row = index as u32 + 1;
+            worksheet.write_string_with_format(row, 0, issue.kind.label(), &styles.cell)?;
+            worksheet.write_string_with_format(row, 1, &issue.method, &styles.cell)?;
+            worksheet.write_string_with_format(row, 2, &issue.url, &styles.cell)?;
+            worksheet.write_string_with_format(row, 3, &issue.operation, &styles.cell)?;
+            worksheet.write_number_with_format(row, 4, issue.status as f64, &styles.cell)?;
+            worksheet.write_string_with_format(row, 5, &issue.message, &styles.cell)?;
+        }
+        if !self.issues.is_empty() {
+            worksheet.autofilter(0, 0, self.issues.len() as u32, headers.len() as u16 - 1)?;
+        }
+
+        workbook.save(output_path)
+            .map_err(AnalyzerError::export("Excelファイルの保存に失敗しました"))?;
+
+        info!("Excelファイルの出力が完了しました: {} ({}件)", output_path, self.issues.len());
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+//! 接続（`connection`フィールド）ごとの再利用の集計を実装
+//! 
+//! 接続ごとのリクエスト数とリクエストの間隔、時間帯ごとに開始した接続の数をConnectionsシートに出力し、
+//! Keep-Aliveが効かずに接続を張り直している（接続のチャーン）箇所を確認できるようにする。
+//! HTTP/2以降の接続では同時に処理したストリームの最大数と優先度（`_priority`）の内訳も集計し、多重化の状況を確認できるようにする
+
+use crate::har_types::Entry;
+use crate::traffic::MAX_BUCKETS;
+use chrono::{DateTime, FixedOffset, TimeDelta};
+use std::collections::{BTreeMap, HashMap};
+use url::Url;
+
+/// 1件のリクエスト（開始時刻, エントリの位置（0始まり）, HARエントリ）
+type TimedEntry<'a> = (DateTime<FixedOffset>, usize, &'a Entry);
+
+/// 1本の接続の利用状況
+#[derive(Debug, Clone)]
+pub struct ConnectionUsage {
+    /// 接続のID（HARの`connection`フィールド）
+    pub connection: String,
+    /// 最初のリクエストのホスト
+    pub host: String,
+    /// サーバーのIPアドレス（記録されていない場合は空文字列）
+    pub server_ip_address: String,
+    /// 最初のリクエストの開始時刻
+    pub opened_at: DateTime<FixedOffset>,
+    /// 最後のリクエストの完了時刻
+    pub closed_at: DateTime<FixedOffset>,
+    /// 最初のリクエストの接続の確立に要した時間（ミリ秒、記録されていない場合はNone）
+    pub connect_ms: Option<f64>,
+    /// 最初のレスポンスのHTTPバージョン（例: "h2"、"HTTP/1.1"）
+    pub http_version: String,
+    /// 同時に処理していたリクエスト（ストリーム）の最大数
+    pub max_concurrent: usize,
+    /// 優先度ごとのリクエストの数（優先度の高い順、`_priority`が無いリクエストは含めない）
+    pub priorities: Vec<(String, usize)>,
+    /// この接続を使用したリクエストのエントリ番号（開始時刻の順、1始まり）
+    pub entry_numbers: Vec<usize>,
+    /// 前のリクエストの完了から次のリクエストの開始までの間隔（ミリ秒、多重化で重なる場合は0）
+    pub gaps_ms: Vec<f64>,
+}
+
+impl ConnectionUsage {
+    /// この接続を使用したリクエストの数
+    /// 
+    /// # Returns
+    /// * `usize` - リクエストの数
+    pub fn requests(&self) -> usize {
+        self.entry_numbers.len()
+    }
+
+    /// リクエストの間隔の平均
+    /// 
+    /// # Returns
+    /// * `Option<f64>` - 平均（ミリ秒、リクエストが1件の場合はNone）
+    pub fn average_gap_ms(&self) -> Option<f64> {
+        if self.gaps_ms.is_empty() {
+            None
+        } else {
+            Some(self.gaps_ms.iter().sum::<f64>() / self.gaps_ms.len() as f64)
+        }
+    }
+
+    /// リクエストの間隔の最大
+    /// 
+    /// # Returns
+    /// * `Option<f64>` - 最大（ミリ秒、リクエストが1件の場合はNone）
+    pub fn max_gap_ms(&self) -> Option<f64> {
+        self.gaps_ms.iter().copied().reduce(f64::max)
+    }
+
+    /// 複数のリクエストを同時に処理した（多重化した）かどうか
+    /// 
+    /// # Returns
+    /// * `bool` - 同時に処理したリクエストが2件以上の場合はtrue
+    pub fn is_multiplexed(&self) -> bool {
+        self.max_concurrent > 1
+    }
+
+    /// 優先度の内訳のテキスト
+    /// 
+    /// # Returns
+    /// * `String` - 「優先度: 件数」をカンマ区切りで連結したテキスト（優先度が無い場合は空文字列）
+    pub fn priorities_text(&self) -> String {
+        self.priorities
+            .iter()
+            .map(|(priority, count)| format!("{}: {}", priority, count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// エントリを接続ごとに集計
+/// 
+/// `connection`フィールドが無いエントリと開始時刻を解析できないエントリは集計しない
+/// 
+/// # Arguments
+/// * `entries` - (エントリの位置（0始まり）, HARエントリ)のイテレータ
+/// 
+/// # Returns
+/// * `Vec<ConnectionUsage>` - 開始時刻の順の接続ごとの利用状況
+pub fn analyze<'a>(entries: impl Iterator<Item = (usize, &'a Entry)>) -> Vec<ConnectionUsage> {
+    let mut groups: HashMap<&str, Vec<TimedEntry>> = HashMap::new();
+    for (index, entry) in entries {
+        let Some(connection) = entry.connection.as_deref().map(str::trim).filter(|id| !id.is_empty()) else {
+            continue;
+        };
+        let Ok(started) = DateTime::parse_from_rfc3339(&entry.started_date_time) else {
+            continue;
+        };
+        groups.entry(connection).or_default().push((started, index, entry));
+    }
+
+    let mut connections: Vec<ConnectionUsage> = groups
+        .into_iter()
+        .filter_map(|(connection, mut requests)| {
+            requests.sort_by_key(|(started, index, _)| (*started, *index));
+            let &(opened_at, _, first) = requests.first()?;
+
+            let mut gaps_ms = Vec::with_capacity(requests.len() - 1);
+            let mut closed_at = opened_at;
+            // (時刻, 開始の場合は1・完了の場合は-1)のリスト
+            let mut boundaries = Vec::with_capacity(requests.len() * 2);
+            let mut priorities: BTreeMap<(usize, &str), usize> = BTreeMap::new();
+            for (position, (started, _, entry)) in requests.iter().enumerate() {
+                if position > 0 {
+                    let gap = (*started - closed_at).num_microseconds().unwrap_or(0) as f64 / 1000.0;
+                    gaps_ms.push(gap.max(0.0));
+                }
+                let finished = *started + TimeDelta::microseconds((entry.time.max(0.0) * 1000.0) as i64);
+                closed_at = closed_at.max(finished);
+                boundaries.push((*started, 1));
+                boundaries.push((finished, -1));
+                if let Some(priority) = entry.priority.as_deref().map(str::trim).filter(|priority| !priority.is_empty()) {
+                    *priorities.entry((priority_rank(priority), priority)).or_default() += 1;
+                }
+            }
+
+            Some(ConnectionUsage {
+                connection: connection.to_string(),
+                host: Url::parse(&first.request.url)
+                    .ok()
+                    .and_then(|url| url.host_str().map(str::to_string))
+                    .unwrap_or_default(),
+                server_ip_address: first.server_ip_address.clone().unwrap_or_default(),
+                opened_at,
+                closed_at,
+                connect_ms: first.timings.connect.filter(|connect| *connect >= 0.0),
+                http_version: first.response.http_version.clone(),
+                max_concurrent: max_concurrent(boundaries),
+                priorities: priorities
+                    .into_iter()
+                    .map(|((_, priority), count)| (priority.to_string(), count))
+                    .collect(),
+                entry_numbers: requests.iter().map(|(_, index, _)| index + 1).collect(),
+                gaps_ms,
+            })
+        })
+        .collect();
+    connections.sort_by(|a, b| a.opened_at.cmp(&b.opened_at).then_with(|| a.connection.cmp(&b.connection)));
+    connections
+}
+
+/// 時間帯ごとに開始した接続の数を集計
+/// 
+/// # Arguments
+/// * `connections` - 接続ごとの利用状況
+/// * `interval_ms` - 時間帯の間隔（ミリ秒）
+/// 
+/// # Returns
+/// * `Vec<(DateTime<FixedOffset>, usize)>` - 最初から最後の時間帯までの(時間帯の開始時刻, 開始した接続の数)のリスト
+///   （接続の無い時間帯は0件として含め、時間帯の数が上限を超える場合は空）
+pub fn opened_timeline(connections: &[ConnectionUsage], interval_ms: u64) -> Vec<(DateTime<FixedOffset>, usize)> {
+    let Some(offset) = connections.first().map(|connection| *connection.opened_at.offset()) else {
+        return Vec::new();
+    };
+    let interval_ms = interval_ms.max(1) as i64;
+    let mut buckets: BTreeMap<i64, usize> = BTreeMap::new();
+    for connection in connections {
+        let millis = connection.opened_at.timestamp_millis();
+        // 時間帯の区切りを表示するタイムゾーンの時刻に揃える
+        let local_millis = millis + offset.local_minus_utc() as i64 * 1000;
+        *buckets.entry(millis - local_millis.rem_euclid(interval_ms)).or_default() += 1;
+    }
+
+    let (Some(&first), Some(&last)) = (buckets.keys().next(), buckets.keys().next_back()) else {
+        return Vec::new();
+    };
+    let len = ((last - first) / interval_ms) as usize + 1;
+    if len > MAX_BUCKETS {
+        return Vec::new();
+    }
+    (0..len)
+        .filter_map(|index| {
+            let start = first + index as i64 * interval_ms;
+            let opened = buckets.get(&start).copied().unwrap_or_default();
+            DateTime::from_timestamp_millis(start).map(|time| (time.with_timezone(&offset), opened))
+        })
+        .collect()
+}
+
+/// 同時に処理していたリクエストの最大数を取得
+/// 
+/// # Arguments
+/// * `boundaries` - (時刻, 開始の場合は1・完了の場合は-1)のリスト
+/// 
+/// # Returns
+/// * `usize` - 最大数
+fn max_concurrent(mut boundaries: Vec<(DateTime<FixedOffset>, i32)>) -> usize {
+    // 同じ時刻に完了と開始がある場合は完了を先に数え、続けて送信したリクエストを同時とみなさない
+    boundaries.sort();
+    let mut current = 0i32;
+    let mut max = 0i32;
+    for (_, delta) in boundaries {
+        current += delta;
+        max = max.max(current);
+    }
+    max as usize
+}
+
+/// 優先度の並び順を取得
+/// 
+/// # Arguments
+/// * `priority` - 優先度（Chrome DevToolsの`_priority`）
+/// 
+/// # Returns
+/// * `usize` - 優先度の高い順の位置（既知でない優先度は末尾）
+fn priority_rank(priority: &str) -> usize {
+    const PRIORITIES: [&str; 5] = ["veryhigh", "high", "medium", "low", "verylow"];
+    let normalized = priority.to_ascii_lowercase().replace(['-', '_', ' '], "");
+    PRIORITIES
+        .iter()
+        .position(|known| *known == normalized)
+        .unwrap_or(PRIORITIES.len())
+}
@@ -0,0 +1,238 @@
+//! Cookieの同意取得の監査（同意管理のエンドポイントへのリクエストより前に設定されたサードパーティのCookie）を実装
+//! 
+//! 解析対象の絞り込みに関わらずキャプチャ全体のエントリを調べ、Consentシートに出力する
+
+use crate::har_types::{Entry, HarFile};
+use crate::trackers::{self, Blocklist, DomainCategory};
+use chrono::{DateTime, FixedOffset};
+use log::{info, warn};
+use regex::Regex;
+use url::Url;
+
+/// 同梱する同意管理プラットフォーム（CMP）のドメイン
+const BUNDLED_CONSENT_DOMAINS: &[&str] = &[
+    "onetrust.com",
+    "cookielaw.org",
+    "cookiebot.com",
+    "consensu.org",
+    "trustarc.com",
+    "didomi.io",
+    "usercentrics.eu",
+    "privacy-mgmt.com",
+    "osano.com",
+    "termly.io",
+    "iubenda.com",
+    "cookieyes.com",
+    "consentmanager.net",
+    "cookie-script.com",
+];
+
+/// 同意管理のエンドポイントへの最初のリクエスト
+#[derive(Debug, Clone)]
+pub struct ConsentRequest {
+    /// エントリ番号（HAR内の順序で1始まり）
+    pub entry_number: usize,
+    /// リクエストの開始時刻
+    pub timestamp: String,
+    /// リクエストURL
+    pub url: String,
+}
+
+/// 同意より前に設定されたサードパーティのCookie
+#[derive(Debug, Clone)]
+pub struct EarlyCookie {
+    /// Cookieを設定したレスポンスのエントリ番号（HAR内の順序で1始まり）
+    pub entry_number: usize,
+    /// Cookieを設定したリクエストの開始時刻
+    pub timestamp: String,
+    /// Cookieを設定したホスト
+    pub host: String,
+    /// ホストの分類
+    pub category: DomainCategory,
+    /// Cookieの名前
+    pub name: String,
+    /// Cookieのドメイン（Domain属性が無い場合は設定したホスト）
+    pub domain: String,
+    /// Cookieの有効期限（セッションCookieの場合は空文字列）
+    pub expires: String,
+}
+
+/// Cookieの同意取得の監査結果
+#[derive(Debug, Clone, Default)]
+pub struct ConsentReport {
+    /// 同意管理のエンドポイントへの最初のリクエスト（見つからない場合はNone）
+    pub consent: Option<ConsentRequest>,
+    /// 同意より前（同意管理のリクエストが無い場合はキャプチャ全体）に設定されたサードパーティのCookie
+    pub cookies: Vec<EarlyCookie>,
+}
+
+impl ConsentReport {
+    /// キャプチャ全体から同意より前に設定されたサードパーティのCookieを検出
+    /// 
+    /// # Arguments
+    /// * `har` - HARデータ
+    /// * `blocklist` - ドメインの分類のリスト
+    /// * `consent_url_regexes` - 同梱のドメインに加えて同意管理のエンドポイントとみなすURLの正規表現
+    /// 
+    /// # Returns
+    /// * `ConsentReport` - 監査結果（Cookieは設定された順）
+    pub fn scan(har: &HarFile, blocklist: &Blocklist, consent_url_regexes: &[Regex]) -> Self {
+        let entries = &har.log.entries;
+        let first_party_site = trackers::first_party_site(&har.log);
+
+        let consent_index = (0..entries.len())
+            .filter(|&index| is_consent_request(&entries[index], consent_url_regexes))
+            .min_by_key(|&index| (started_at(&entries[index]), index));
+        let consent_started = consent_index.and_then(|index| started_at(&entries[index]));
+
+        let mut order: Vec<usize> = (0..entries.len()).collect();
+        order.sort_by_key(|&index| (started_at(&entries[index]), index));
+
+        let mut cookies = Vec::new();
+        for index in order {
+            let entry = &entries[index];
+            let before_consent = match (consent_index, consent_started, started_at(entry)) {
+                (None, _, _) => true,
+                (Some(_), Some(consent), Some(started)) => started < consent,
+                (Some(consent), _, _) => index < consent,
+            };
+            if !before_consent {
+                continue;
+            }
+            let Some(host) = Url::parse(&entry.request.url)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+            else {
+                continue;
+            };
+            for (name, domain, expires) in set_cookies(entry) {
+                let domain = domain.map_or_else(|| host.clone(), |domain| domain.trim_start_matches('.').to_ascii_lowercase());
+                if first_party_site.as_deref() == Some(trackers::site_of(&domain).as_str()) {
+                    continue;
+                }
+                cookies.push(EarlyCookie {
+                    entry_number: index + 1,
+                    timestamp: entry.started_date_time.clone(),
+                    category: blocklist.classify(&domain, first_party_site.as_deref()),
+                    host: host.clone(),
+                    name,
+                    domain,
+                    expires,
+                });
+            }
+        }
+
+        ConsentReport {
+            consent: consent_index.map(|index| ConsentRequest {
+                entry_number: index + 1,
+                timestamp: entries[index].started_date_time.clone(),
+                url: entries[index].request.url.clone(),
+            }),
+            cookies,
+        }
+    }
+
+    /// 監査結果をログに出力
+    pub fn log(&self) {
+        match &self.consent {
+            Some(consent) => info!(
+                "同意管理のリクエスト: エントリ{} {} {}",
+                consent.entry_number, consent.timestamp, consent.url
+            ),
+            None => info!("同意管理のエンドポイントへのリクエストが見つかりません"),
+        }
+        if self.cookies.is_empty() {
+            return;
+        }
+        let when = if self.consent.is_some() { "同意より前に" } else { "同意管理のリクエスト無しで" };
+        warn!(
+            "{}設定されたサードパーティのCookie: {}件（Consentシートを参照）",
+            when,
+            self.cookies.len()
+        );
+    }
+}
+
+/// 同意管理のエンドポイントへのリクエストかどうか
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// * `consent_url_regexes` - 同意管理のエンドポイントとみなすURLの正規表現
+/// 
+/// # Returns
+/// * `bool` - 同梱のドメインまたは正規表現に一致する場合はtrue
+fn is_consent_request(entry: &Entry, consent_url_regexes: &[Regex]) -> bool {
+    let url = &entry.request.url;
+    if consent_url_regexes.iter().any(|regex| regex.is_match(url)) {
+        return true;
+    }
+    Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        .is_some_and(|host| {
+            BUNDLED_CONSENT_DOMAINS
+                .iter()
+                .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)))
+        })
+}
+
+/// リクエストの開始時刻を取得
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `Option<DateTime<FixedOffset>>` - 開始時刻（解析できない場合はNone）
+fn started_at(entry: &Entry) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(&entry.started_date_time).ok()
+}
+
+/// レスポンスで設定されたCookieを取得
+/// 
+/// HARの`cookies`を優先し、記録されていない場合はSet-Cookieヘッダーを解析する
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `Vec<(String, Option<String>, String)>` - (名前, Domain属性, 有効期限)のリスト
+fn set_cookies(entry: &Entry) -> Vec<(String, Option<String>, String)> {
+    if !entry.response.cookies.is_empty() {
+        return entry
+            .response
+            .cookies
+            .iter()
+            .map(|cookie| {
+                let domain = cookie.domain.clone().filter(|domain| !domain.is_empty());
+                (cookie.name.clone(), domain, cookie.expires.clone().unwrap_or_default())
+            })
+            .collect();
+    }
+    entry
+        .response
+        .headers
+        .iter()
+        .filter(|header| header.name.eq_ignore_ascii_case("set-cookie"))
+        // 1つのヘッダーに改行区切りで複数のCookieを記録するツールがある
+        .flat_map(|header| header.value.lines())
+        .filter_map(|line| {
+            let mut parts = line.split(';');
+            let name = parts.next()?.split_once('=')?.0.trim();
+            if name.is_empty() {
+                return None;
+            }
+            let mut domain = None;
+            let mut expires = String::new();
+            for attribute in parts {
+                let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+                match key.trim().to_ascii_lowercase().as_str() {
+                    "domain" if !value.trim().is_empty() => domain = Some(value.trim().to_string()),
+                    "expires" => expires = value.trim().to_string(),
+                    "max-age" if expires.is_empty() => expires = format!("Max-Age={}", value.trim()),
+                    _ => {}
+                }
+            }
+            Some((name.to_string(), domain, expires))
+        })
+        .collect()
+}
@@ -0,0 +1,107 @@
+//! ページごとのloadまでのクリティカルパスの推定を実装
+//! 
+//! loadまでに最後に完了したリクエストから、発生元（`_initiator`、無い場合はRefererヘッダー）を
+//! 辿ってページの起点まで遡り、loadを決めたリクエストの連鎖を求める
+
+use crate::dependency_graph::DependencyGraph;
+use crate::har_types::{Entry, Page};
+use chrono::DateTime;
+use std::collections::{HashMap, HashSet};
+
+/// クリティカルパス上のリクエスト
+#[derive(Debug, Clone)]
+pub struct CriticalPathStep {
+    /// HTTPメソッド
+    pub method: String,
+    /// リクエストURL
+    pub url: String,
+    /// ページの開始からリクエストを開始するまでの時間（ミリ秒）
+    pub start: f64,
+    /// リクエストの所要時間（ミリ秒）
+    pub duration: f64,
+    /// 発生元のリクエストの完了からリクエストを開始するまでの待ち時間（ミリ秒、起点の場合はNone）
+    pub wait: Option<f64>,
+    /// ページの開始からリクエストが完了するまでの累積時間（ミリ秒）
+    pub cumulative: f64,
+}
+
+/// ページのloadまでのクリティカルパスを推定
+/// 
+/// # Arguments
+/// * `page` - ページ情報
+/// * `entries` - ページに属するエントリのリスト
+/// * `duration` - エントリの所要時間（ミリ秒）を取得する関数
+/// 
+/// # Returns
+/// * `Vec<CriticalPathStep>` - 起点からloadを決めたリクエストまでの順のリスト（推定できない場合は空）
+pub fn estimate(page: &Page, entries: &[&Entry], duration: impl Fn(&Entry) -> f64) -> Vec<CriticalPathStep> {
+    let Ok(page_start) = DateTime::parse_from_rfc3339(&page.started_date_time) else {
+        return Vec::new();
+    };
+    // loadが記録されていない場合は最後に完了したリクエストまでを対象にする
+    let on_load = page.page_timings.on_load.filter(|t| *t >= 0.0).unwrap_or(f64::INFINITY);
+
+    // (開始, 完了)の時間をページの開始からのミリ秒で求める
+    let offsets: Vec<Option<(f64, f64)>> = entries
+        .iter()
+        .map(|entry| {
+            DateTime::parse_from_rfc3339(&entry.started_date_time).ok().map(|started| {
+                let start = (started - page_start).num_milliseconds() as f64;
+                (start, start + duration(entry).max(0.0))
+            })
+        })
+        .collect();
+
+    // loadまでに最後に完了したリクエストを終点とする
+    let Some(last) = offsets
+        .iter()
+        .enumerate()
+        .filter_map(|(index, offset)| offset.map(|(_, end)| (index, end)))
+        .filter(|(_, end)| *end <= on_load)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(index, _)| index)
+    else {
+        return Vec::new();
+    };
+
+    // URLごとに最初に開始したエントリを発生元の候補とする
+    let mut url_index: HashMap<&str, usize> = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        url_index.entry(entry.request.url.as_str()).or_insert(index);
+    }
+
+    let mut chain = vec![last];
+    let mut visited: HashSet<usize> = HashSet::from([last]);
+    let mut current = last;
+    while let Some(parent) = DependencyGraph::find_parent_url(entries[current])
+        .and_then(|url| url_index.get(url).copied())
+        .filter(|parent| !visited.contains(parent))
+        .filter(|parent| match (offsets[*parent], offsets[current]) {
+            (Some((parent_start, _)), Some((start, _))) => parent_start <= start,
+            _ => false,
+        })
+    {
+        visited.insert(parent);
+        chain.push(parent);
+        current = parent;
+    }
+    chain.reverse();
+
+    let mut previous_end: Option<f64> = None;
+    chain
+        .into_iter()
+        .filter_map(|index| {
+            let (start, end) = offsets[index]?;
+            let step = CriticalPathStep {
+                method: entries[index].request.method.clone(),
+                url: entries[index].request.url.clone(),
+                start,
+                duration: end - start,
+                wait: previous_end.map(|previous| (start - previous).max(0.0)),
+                cumulative: end,
+            };
+            previous_end = Some(end);
+            Some(step)
+        })
+        .collect()
+}
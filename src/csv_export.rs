@@ -0,0 +1,118 @@
+//! 解析結果のCSV・TSV形式での出力を実装
+//! 
+//! Excelのシートと同じ列を1行1エントリで出力する。Shift_JIS（CP932）とCRLFを前提とする
+//! 国内の業務システムに取り込めるように、区切り文字・文字コード・BOM・改行コードを指定できる
+
+use crate::columns::{CellValue, Column};
+use crate::error::{AnalyzerError, Result};
+use crate::har_types::AnalysisResult;
+use crate::privacy::PrivacyPolicy;
+use encoding_rs::{Encoding, UTF_8};
+use log::{info, warn};
+use std::fs;
+use std::path::Path;
+
+/// UTF-8のBOM
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// CSV・TSV形式の出力オプション
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// 区切り文字（Noneの場合はCSVはカンマ、TSVはタブ）
+    pub delimiter: Option<u8>,
+    /// 出力する文字コード
+    pub encoding: &'static Encoding,
+    /// 先頭にBOMを付けるかどうか（UTF-8の場合のみ）
+    pub bom: bool,
+    /// 改行コードをCRLFにするかどうか（falseの場合はLF）
+    pub crlf: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: None,
+            encoding: UTF_8,
+            bom: false,
+            crlf: true,
+        }
+    }
+}
+
+/// 解析結果をCSV・TSV形式で出力
+/// 
+/// # Arguments
+/// * `results` - 解析結果のリスト
+/// * `columns` - 出力する列
+/// * `delimiter` - 区切り文字（`options.delimiter`の指定が優先）
+/// * `options` - CSV・TSV形式の出力オプション
+/// * `privacy` - 列ごとの匿名化の設定
+/// * `output_path` - 出力先のパス
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn export(
+    results: &[&AnalysisResult],
+    columns: &[Column],
+    delimiter: u8,
+    options: &CsvOptions,
+    privacy: &PrivacyPolicy,
+    output_path: &str,
+) -> Result<()> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(options.delimiter.unwrap_or(delimiter))
+        .terminator(if options.crlf { csv::Terminator::CRLF } else { csv::Terminator::Any(b'\n') })
+        .from_writer(Vec::new());
+    writer
+        .write_record(columns.iter().map(|column| column.header()))
+        .map_err(AnalyzerError::export("CSVの作成に失敗しました"))?;
+    for result in results {
+        writer
+            .write_record(columns.iter().map(|column| cell_text(*column, result, privacy)))
+            .map_err(AnalyzerError::export("CSVの作成に失敗しました"))?;
+    }
+    let text = writer
+        .into_inner()
+        .map_err(AnalyzerError::export("CSVの作成に失敗しました"))?;
+    let text = String::from_utf8(text).map_err(AnalyzerError::export("CSVの作成に失敗しました"))?;
+
+    let (encoded, _, unmappable) = options.encoding.encode(&text);
+    if unmappable {
+        warn!(
+            "{}で表せない文字を数値文字参照（&#...;）に置き換えました",
+            options.encoding.name()
+        );
+    }
+    let mut bytes = Vec::with_capacity(encoded.len() + UTF8_BOM.len());
+    if options.bom && options.encoding == UTF_8 {
+        bytes.extend_from_slice(UTF8_BOM);
+    }
+    bytes.extend_from_slice(&encoded);
+
+    if let Some(parent) = Path::new(output_path).parent() {
+        fs::create_dir_all(parent)
+            .map_err(AnalyzerError::io("出力ディレクトリの作成に失敗しました"))?;
+    }
+    fs::write(output_path, bytes)
+        .map_err(AnalyzerError::io("CSVファイルの書き込みに失敗しました"))?;
+    info!("{}件のエントリを{}で出力しました", results.len(), options.encoding.name());
+    Ok(())
+}
+
+/// セルに書き込む値を文字列にする
+/// 
+/// # Arguments
+/// * `column` - 列
+/// * `result` - 解析結果
+/// * `privacy` - 列ごとの匿名化の設定
+/// 
+/// # Returns
+/// * `String` - セルの文字列（日時は時刻列の表記）
+fn cell_text(column: Column, result: &AnalysisResult, privacy: &PrivacyPolicy) -> String {
+    let mut buffer = String::new();
+    match privacy.value(column, result, &mut buffer) {
+        CellValue::Text(text) => text.to_string(),
+        CellValue::Number(number) => number.to_string(),
+        CellValue::DateTime(_) => result.timestamp.clone(),
+    }
+}
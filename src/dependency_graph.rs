@@ -0,0 +1,427 @@
+//! ページ毎のリソース依存関係グラフを構築・出力
+
+use crate::columns::Column;
+use crate::har_types::{Entry, HarFile};
+use crate::privacy::PrivacyPolicy;
+use crate::error::{AnalyzerError, Result};
+use log::{debug, info};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// ラベルに表示するURLの最大文字数
+const MAX_LABEL_URL_LENGTH: usize = 80;
+
+/// グラフの出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    GraphMl,
+}
+
+impl GraphFormat {
+    /// ファイルの拡張子から出力形式を判定
+    /// 
+    /// # Arguments
+    /// * `path` - 出力ファイルのパス
+    /// 
+    /// # Returns
+    /// * `Option<GraphFormat>` - 対応する形式。未対応の拡張子の場合はNone
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())?;
+
+        match extension.as_str() {
+            "dot" | "gv" => Some(GraphFormat::Dot),
+            "graphml" => Some(GraphFormat::GraphMl),
+            _ => None,
+        }
+    }
+}
+
+/// グラフのノード（1つのリソース）
+#[derive(Debug)]
+struct GraphNode {
+    url: String,
+    method: String,
+    pageref: Option<String>,
+    /// HARエントリのインデックス（HAR内に存在しない発生元の場合はNone）
+    entry_index: Option<usize>,
+}
+
+/// リソースの依存関係グラフ
+#[derive(Debug)]
+pub struct DependencyGraph {
+    nodes: Vec<GraphNode>,
+    /// (発生元ノード, 発生先ノード)
+    edges: Vec<(usize, usize)>,
+    page_titles: HashMap<String, String>,
+}
+
+impl DependencyGraph {
+    /// HARファイルから依存関係グラフを構築
+    /// 
+    /// 発生元は`_initiator`フィールドから取得し、存在しない場合はRefererヘッダーで代用する
+    /// 
+    /// # Arguments
+    /// * `har` - HARファイル
+    /// 
+    /// # Returns
+    /// * `DependencyGraph` - 構築されたグラフ
+    pub fn build(har: &HarFile) -> Self {
+        let mut nodes = Vec::with_capacity(har.log.entries.len());
+        let mut url_index: HashMap<String, usize> = HashMap::new();
+
+        for (index, entry) in har.log.entries.iter().enumerate() {
+            url_index.entry(entry.request.url.clone()).or_insert(nodes.len());
+            nodes.push(GraphNode {
+                url: entry.request.url.clone(),
+                method: entry.request.method.clone(),
+                pageref: entry.pageref.clone(),
+                entry_index: Some(index),
+            });
+        }
+
+        let mut edges = Vec::new();
+        for (index, entry) in har.log.entries.iter().enumerate() {
+            let Some(parent_url) = Self::find_parent_url(entry) else {
+                continue;
+            };
+
+            if parent_url == entry.request.url {
+                continue;
+            }
+
+            let parent = match url_index.get(parent_url) {
+                Some(&parent) => parent,
+                None => {
+                    // HAR内に記録されていない発生元は外部ノードとして追加
+                    let parent = nodes.len();
+                    nodes.push(GraphNode {
+                        url: parent_url.to_string(),
+                        method: String::new(),
+                        pageref: entry.pageref.clone(),
+                        entry_index: None,
+                    });
+                    url_index.insert(parent_url.to_string(), parent);
+                    parent
+                }
+            };
+
+            edges.push((parent, index));
+        }
+
+        let page_titles = har
+            .log
+            .pages
+            .iter()
+            .map(|page| (page.id.clone(), page.title.clone()))
+            .collect();
+
+        debug!("依存関係グラフ: ノード数 {}, エッジ数 {}", nodes.len(), edges.len());
+
+        DependencyGraph {
+            nodes,
+            edges,
+            page_titles,
+        }
+    }
+
+    /// ノードのURLとページのタイトルを匿名化
+    /// 
+    /// ページのタイトルはブラウザがURLを記録するため、URLと同じレベルを適用する
+    /// 
+    /// # Arguments
+    /// * `privacy` - 列ごとの匿名化の設定（リクエストURL列のレベルを適用する）
+    /// 
+    /// # Returns
+    /// * `DependencyGraph` - 匿名化したグラフ
+    pub fn with_privacy(mut self, privacy: &PrivacyPolicy) -> Self {
+        for node in &mut self.nodes {
+            node.url = privacy.text(Column::RequestUrl, &node.url).into_owned();
+        }
+        for title in self.page_titles.values_mut() {
+            *title = privacy.text(Column::RequestUrl, title).into_owned();
+        }
+        self
+    }
+
+    /// エントリの発生元URLを取得
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `Option<&str>` - 発生元のURL
+    pub(crate) fn find_parent_url(entry: &Entry) -> Option<&str> {
+        if let Some(url) = entry.initiator.as_ref().and_then(|i| i.source_url()) {
+            return Some(url);
+        }
+
+        entry
+            .request
+            .headers
+            .iter()
+            .find(|h| h.name.eq_ignore_ascii_case("referer"))
+            .map(|h| h.value.as_str())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// 各ノードから（間接的に）発生したリクエスト数を計算
+    /// 
+    /// # Returns
+    /// * `Vec<usize>` - ノード毎の子孫ノード数
+    fn descendant_counts(&self) -> Vec<usize> {
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); self.nodes.len()];
+        for &(parent, child) in &self.edges {
+            children[parent].push(child);
+        }
+
+        let mut counts = vec![0; self.nodes.len()];
+        let mut visited = vec![usize::MAX; self.nodes.len()];
+        for (root, count) in counts.iter_mut().enumerate() {
+            if children[root].is_empty() {
+                continue;
+            }
+
+            // Refererによる循環に備えて訪問済みノードを記録する
+            visited[root] = root;
+            let mut stack = children[root].clone();
+            while let Some(node) = stack.pop() {
+                if visited[node] == root {
+                    continue;
+                }
+                visited[node] = root;
+                *count += 1;
+                stack.extend(children[node].iter().copied());
+            }
+        }
+
+        counts
+    }
+
+    /// 最も多くのリクエストを発生させたリソースを取得
+    /// 
+    /// # Arguments
+    /// * `limit` - 取得する最大件数
+    /// 
+    /// # Returns
+    /// * `Vec<(&str, usize)>` - (URL, 発生させたリクエスト数)のリスト
+    pub fn top_initiators(&self, limit: usize) -> Vec<(&str, usize)> {
+        let mut ranking: Vec<(&str, usize)> = self
+            .descendant_counts()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, count)| *count > 0)
+            .map(|(node, count)| (self.nodes[node].url.as_str(), count))
+            .collect();
+
+        ranking.sort_by_key(|r| std::cmp::Reverse(r.1));
+        ranking.truncate(limit);
+        ranking
+    }
+
+    /// グラフをファイルに出力
+    /// 
+    /// # Arguments
+    /// * `output_path` - 出力ファイルのパス（拡張子で形式を判定）
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub fn export(&self, output_path: &str) -> Result<()> {
+        let format = GraphFormat::from_path(output_path)
+            .ok_or_else(|| AnalyzerError::UnsupportedFormat(output_path.to_string()))?;
+
+        info!("依存関係グラフを出力しています: {}", output_path);
+
+        let content = match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::GraphMl => self.to_graphml(),
+        };
+
+        if let Some(parent) = Path::new(output_path).parent() {
+            fs::create_dir_all(parent)
+                .map_err(AnalyzerError::io("出力ディレクトリの作成に失敗しました"))?;
+        }
+
+        fs::write(output_path, content)
+            .map_err(AnalyzerError::io("依存関係グラフの書き込みに失敗しました"))?;
+
+        for (url, count) in self.top_initiators(5) {
+            info!("  - {} が {} 件のリクエストを発生させました", url, count);
+        }
+
+        info!("依存関係グラフの出力が完了しました: {}", output_path);
+        Ok(())
+    }
+
+    /// ノードの表示ラベルを作成
+    /// 
+    /// # Arguments
+    /// * `node` - ノード
+    /// * `descendants` - 子孫ノード数
+    /// 
+    /// # Returns
+    /// * `String` - 表示ラベル
+    fn node_label(node: &GraphNode, descendants: usize) -> String {
+        let url = if node.url.chars().count() > MAX_LABEL_URL_LENGTH {
+            let truncated: String = node.url.chars().take(MAX_LABEL_URL_LENGTH).collect();
+            format!("{}...", truncated)
+        } else {
+            node.url.clone()
+        };
+
+        let mut label = if node.method.is_empty() {
+            url
+        } else {
+            format!("{} {}", node.method, url)
+        };
+
+        if descendants > 0 {
+            let _ = write!(label, " (+{})", descendants);
+        }
+
+        label
+    }
+
+    /// DOT形式の文字列に変換
+    /// 
+    /// # Returns
+    /// * `String` - DOT形式のグラフ
+    fn to_dot(&self) -> String {
+        let descendants = self.descendant_counts();
+        let mut dot = String::new();
+
+        dot.push_str("digraph dependencies {\n");
+        dot.push_str("  rankdir=LR;\n");
+        dot.push_str("  node [shape=box, fontsize=10];\n");
+
+        // ページ毎にクラスタとしてまとめる
+        let mut pages: Vec<&str> = Vec::new();
+        for node in &self.nodes {
+            if let Some(pageref) = node.pageref.as_deref()
+                && !pages.contains(&pageref)
+            {
+                pages.push(pageref);
+            }
+        }
+
+        for (cluster, pageref) in pages.iter().enumerate() {
+            let title = self
+                .page_titles
+                .get(*pageref)
+                .filter(|t| !t.is_empty())
+                .map(String::as_str)
+                .unwrap_or(pageref);
+
+            let _ = writeln!(dot, "  subgraph cluster_{} {{", cluster);
+            let _ = writeln!(dot, "    label=\"{}\";", Self::escape_dot(title));
+            for (index, node) in self.nodes.iter().enumerate() {
+                if node.pageref.as_deref() == Some(*pageref) {
+                    let _ = write!(dot, "  ");
+                    Self::write_dot_node(&mut dot, index, node, descendants[index]);
+                }
+            }
+            dot.push_str("  }\n");
+        }
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            if node.pageref.is_none() {
+                Self::write_dot_node(&mut dot, index, node, descendants[index]);
+            }
+        }
+
+        for (parent, child) in &self.edges {
+            let _ = writeln!(dot, "  n{} -> n{};", parent, child);
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// DOT形式のノード定義を書き込み
+    /// 
+    /// # Arguments
+    /// * `dot` - 書き込み先
+    /// * `index` - ノード番号
+    /// * `node` - ノード
+    /// * `descendants` - 子孫ノード数
+    fn write_dot_node(dot: &mut String, index: usize, node: &GraphNode, descendants: usize) {
+        let style = if node.entry_index.is_none() {
+            ", style=dashed"
+        } else {
+            ""
+        };
+
+        let _ = writeln!(
+            dot,
+            "  n{} [label=\"{}\"{}];",
+            index,
+            Self::escape_dot(&Self::node_label(node, descendants)),
+            style
+        );
+    }
+
+    /// GraphML形式の文字列に変換
+    /// 
+    /// # Returns
+    /// * `String` - GraphML形式のグラフ
+    fn to_graphml(&self) -> String {
+        let descendants = self.descendant_counts();
+        let mut xml = String::new();
+
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        xml.push_str("  <key id=\"url\" for=\"node\" attr.name=\"url\" attr.type=\"string\"/>\n");
+        xml.push_str("  <key id=\"method\" for=\"node\" attr.name=\"method\" attr.type=\"string\"/>\n");
+        xml.push_str("  <key id=\"page\" for=\"node\" attr.name=\"page\" attr.type=\"string\"/>\n");
+        xml.push_str("  <key id=\"entry\" for=\"node\" attr.name=\"entry\" attr.type=\"int\"/>\n");
+        xml.push_str("  <key id=\"triggered\" for=\"node\" attr.name=\"triggered\" attr.type=\"int\"/>\n");
+        xml.push_str("  <graph id=\"dependencies\" edgedefault=\"directed\">\n");
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            let _ = writeln!(xml, "    <node id=\"n{}\">", index);
+            let _ = writeln!(xml, "      <data key=\"url\">{}</data>", Self::escape_xml(&node.url));
+            if !node.method.is_empty() {
+                let _ = writeln!(xml, "      <data key=\"method\">{}</data>", Self::escape_xml(&node.method));
+            }
+            if let Some(pageref) = &node.pageref {
+                let _ = writeln!(xml, "      <data key=\"page\">{}</data>", Self::escape_xml(pageref));
+            }
+            if let Some(entry_index) = node.entry_index {
+                let _ = writeln!(xml, "      <data key=\"entry\">{}</data>", entry_index);
+            }
+            let _ = writeln!(xml, "      <data key=\"triggered\">{}</data>", descendants[index]);
+            xml.push_str("    </node>\n");
+        }
+
+        for (id, (parent, child)) in self.edges.iter().enumerate() {
+            let _ = writeln!(
+                xml,
+                "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\"/>",
+                id, parent, child
+            );
+        }
+
+        xml.push_str("  </graph>\n");
+        xml.push_str("</graphml>\n");
+        xml
+    }
+
+    /// DOT形式の文字列リテラル用にエスケープ
+    fn escape_dot(value: &str) -> String {
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    /// XML用にエスケープ
+    fn escape_xml(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+}
@@ -0,0 +1,81 @@
+//! 異なるURLから同じ内容が返されたレスポンス（2つのパスから配信された同じバンドル等）の検出を実装
+//! 
+//! デコードしたレスポンスボディのSHA-256ハッシュでまとめ、Duplicatesシートに削減できるサイズとともに出力する
+
+use crate::har_types::Entry;
+use crate::mock_server;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// 比較するレスポンスボディの最小サイズ（バイト、`{}`等の短いボディの一致を除外する）
+const MIN_BODY_SIZE: usize = 256;
+
+/// 同じ内容のレスポンスを返したURLのグループ
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    /// レスポンスボディのSHA-256ハッシュ（16進数）
+    pub sha256: String,
+    /// レスポンスボディのサイズ（バイト）
+    pub size: usize,
+    /// 最初のレスポンスのMIMEタイプ
+    pub mime_type: String,
+    /// 異なるURL（最初に出現した順）
+    pub urls: Vec<String>,
+    /// 各URLの最初のエントリ番号（HAR内の順序で1始まり）
+    pub entry_numbers: Vec<usize>,
+}
+
+impl DuplicateGroup {
+    /// 1つのURLから配信した場合に削減できるサイズ
+    /// 
+    /// # Returns
+    /// * `usize` - ボディのサイズ×（URLの数-1）
+    pub fn savings(&self) -> usize {
+        self.size * (self.urls.len() - 1)
+    }
+}
+
+/// 異なるURLから同じ内容が返されたレスポンスを検出
+/// 
+/// # Arguments
+/// * `entries` - (エントリの位置（0始まり）, HARエントリ)のイテレータ
+/// 
+/// # Returns
+/// * `Vec<DuplicateGroup>` - 削減できるサイズの大きい順のグループ
+pub fn detect<'a>(entries: impl Iterator<Item = (usize, &'a Entry)>) -> Vec<DuplicateGroup> {
+    let mut groups: Vec<DuplicateGroup> = Vec::new();
+    let mut by_hash: HashMap<String, usize> = HashMap::new();
+    for (index, entry) in entries {
+        let body = mock_server::response_body(entry);
+        if body.len() < MIN_BODY_SIZE {
+            continue;
+        }
+        let sha256: String = Sha256::digest(&body).iter().map(|b| format!("{:02x}", b)).collect();
+        // フラグメントは同じリソースを指すため区別しない
+        let url = entry.request.url.split('#').next().unwrap_or_default().to_string();
+
+        match by_hash.get(&sha256) {
+            Some(&group) => {
+                let group = &mut groups[group];
+                if !group.urls.contains(&url) {
+                    group.urls.push(url);
+                    group.entry_numbers.push(index + 1);
+                }
+            }
+            None => {
+                by_hash.insert(sha256.clone(), groups.len());
+                groups.push(DuplicateGroup {
+                    sha256,
+                    size: body.len(),
+                    mime_type: entry.response.content.mime_type.clone(),
+                    urls: vec![url],
+                    entry_numbers: vec![index + 1],
+                });
+            }
+        }
+    }
+
+    let mut duplicates: Vec<DuplicateGroup> = groups.into_iter().filter(|group| group.urls.len() >= 2).collect();
+    duplicates.sort_by(|a, b| b.savings().cmp(&a.savings()).then_with(|| a.entry_numbers.cmp(&b.entry_numbers)));
+    duplicates
+}
@@ -0,0 +1,239 @@
+//! 設定ファイルに記述したGBあたりの単価（`[egress_pricing]`）による転送量の費用の見積もりを実装
+//! 
+//! レスポンスの転送量（ヘッダーと転送時のボディのサイズ）をホスト・コンテンツタイプごとに集計し、
+//! ホストごとの単価（CDN・オリジン等）を掛けてキャプチャした通信の配信費用を見積もる
+
+use crate::har_types::Entry;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use url::Url;
+
+/// 単価の基準とする1GBのバイト数（クラウドの料金表と同じく1GB = 1024^3バイトとする）
+pub const BYTES_PER_GB: f64 = 1_073_741_824.0;
+
+/// 1回分の費用は小さいため、キャプチャした通信を繰り返した場合の費用も示す回数（100万回）
+pub const REPETITIONS: u64 = 1_000_000;
+
+/// 転送量の単価の設定
+#[derive(Debug, Clone, Deserialize)]
+pub struct EgressPricing {
+    /// 既定のGBあたりの単価
+    pub per_gb: f64,
+    /// 通貨の表記
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    /// ホストごとのGBあたりの単価（`*.example.com`はサブドメインに一致）
+    #[serde(default)]
+    pub hosts: BTreeMap<String, f64>,
+}
+
+/// 通貨の表記の既定値
+/// 
+/// # Returns
+/// * `String` - 通貨の表記
+fn default_currency() -> String {
+    "USD".to_string()
+}
+
+impl EgressPricing {
+    /// ホストに適用する単価を取得
+    /// 
+    /// # Arguments
+    /// * `host` - ホスト名
+    /// 
+    /// # Returns
+    /// * `f64` - 完全に一致するホスト、最も長く一致するワイルドカード、既定の順で決めたGBあたりの単価
+    pub fn price_per_gb(&self, host: &str) -> f64 {
+        let host = host.to_ascii_lowercase();
+        let mut wildcard: Option<(usize, f64)> = None;
+        for (pattern, price) in &self.hosts {
+            let pattern = pattern.to_ascii_lowercase();
+            if pattern == host {
+                return *price;
+            }
+            if let Some(suffix) = pattern.strip_prefix("*.")
+                && host.ends_with(&format!(".{}", suffix))
+                && wildcard.is_none_or(|(length, _)| suffix.len() > length)
+            {
+                wildcard = Some((suffix.len(), *price));
+            }
+        }
+        wildcard.map_or(self.per_gb, |(_, price)| price)
+    }
+}
+
+/// ホスト・コンテンツタイプごとの転送量と費用
+#[derive(Debug, Clone)]
+pub struct EgressCost {
+    /// ホスト名
+    pub host: String,
+    /// コンテンツタイプ（パラメータを除く小文字のMIMEタイプ）
+    pub content_type: String,
+    /// レスポンスの数
+    pub responses: usize,
+    /// 転送量（バイト）
+    pub bytes: u64,
+    /// 適用したGBあたりの単価
+    pub price_per_gb: f64,
+}
+
+impl EgressCost {
+    /// 見積もった費用
+    /// 
+    /// # Returns
+    /// * `f64` - 転送量（GB）×単価
+    pub fn cost(&self) -> f64 {
+        self.bytes as f64 / BYTES_PER_GB * self.price_per_gb
+    }
+}
+
+/// 転送量の費用の見積もり
+#[derive(Debug, Clone, Default)]
+pub struct EgressCostReport {
+    /// 通貨の表記
+    pub currency: String,
+    /// ホスト・コンテンツタイプの順の転送量と費用
+    pub costs: Vec<EgressCost>,
+}
+
+impl EgressCostReport {
+    /// 転送量の合計
+    /// 
+    /// # Returns
+    /// * `u64` - 転送量（バイト）
+    pub fn total_bytes(&self) -> u64 {
+        self.costs.iter().map(|cost| cost.bytes).sum()
+    }
+
+    /// 費用の合計
+    /// 
+    /// # Returns
+    /// * `f64` - 見積もった費用
+    pub fn total_cost(&self) -> f64 {
+        self.costs.iter().map(EgressCost::cost).sum()
+    }
+
+    /// ホストごとの転送量と費用
+    /// 
+    /// # Returns
+    /// * `Vec<(String, u64, f64)>` - 費用の大きい順の(ホスト名, 転送量, 費用)のリスト
+    pub fn by_host(&self) -> Vec<(String, u64, f64)> {
+        group(&self.costs, |cost| &cost.host)
+    }
+
+    /// コンテンツタイプごとの転送量と費用
+    /// 
+    /// # Returns
+    /// * `Vec<(String, u64, f64)>` - 費用の大きい順の(コンテンツタイプ, 転送量, 費用)のリスト
+    pub fn by_content_type(&self) -> Vec<(String, u64, f64)> {
+        group(&self.costs, |cost| &cost.content_type)
+    }
+
+    /// 別の見積もりを合算する
+    /// 
+    /// # Arguments
+    /// * `other` - 合算する見積もり
+    pub fn merge(&mut self, other: &EgressCostReport) {
+        if self.currency.is_empty() {
+            self.currency = other.currency.clone();
+        }
+        for cost in &other.costs {
+            match self
+                .costs
+                .iter_mut()
+                .find(|own| own.host == cost.host && own.content_type == cost.content_type)
+            {
+                Some(own) => {
+                    own.responses += cost.responses;
+                    own.bytes += cost.bytes;
+                }
+                None => self.costs.push(cost.clone()),
+            }
+        }
+        self.costs
+            .sort_by(|a, b| a.host.cmp(&b.host).then_with(|| a.content_type.cmp(&b.content_type)));
+    }
+}
+
+/// レスポンスの転送量をホスト・コンテンツタイプごとに集計して費用を見積もる
+/// 
+/// # Arguments
+/// * `entries` - HARエントリのイテレータ
+/// * `pricing` - 転送量の単価の設定
+/// 
+/// # Returns
+/// * `EgressCostReport` - 転送量の費用の見積もり
+pub fn estimate<'a>(entries: impl Iterator<Item = &'a Entry>, pricing: &EgressPricing) -> EgressCostReport {
+    let mut totals: BTreeMap<(String, String), (usize, u64)> = BTreeMap::new();
+    for entry in entries {
+        let host = Url::parse(&entry.request.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+        let content_type = entry
+            .response
+            .content
+            .mime_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let total = totals.entry((host, content_type)).or_default();
+        total.0 += 1;
+        total.1 += transfer_size(entry);
+    }
+
+    EgressCostReport {
+        currency: pricing.currency.clone(),
+        costs: totals
+            .into_iter()
+            .map(|((host, content_type), (responses, bytes))| EgressCost {
+                price_per_gb: pricing.price_per_gb(&host),
+                host,
+                content_type,
+                responses,
+                bytes,
+            })
+            .collect(),
+    }
+}
+
+/// レスポンスの転送量を取得
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `u64` - ヘッダーと転送時のボディのサイズの合計（転送時のサイズが不明の場合はデコード後のサイズ）
+fn transfer_size(entry: &Entry) -> u64 {
+    let body = if entry.response.body_size >= 0 {
+        entry.response.body_size
+    } else {
+        entry.response.content.size.max(0)
+    };
+    (entry.response.headers_size.max(0) + body) as u64
+}
+
+/// 転送量と費用を項目ごとに合算する
+/// 
+/// # Arguments
+/// * `costs` - ホスト・コンテンツタイプごとの転送量と費用
+/// * `key` - 合算する項目を取得する関数
+/// 
+/// # Returns
+/// * `Vec<(String, u64, f64)>` - 費用の大きい順の(項目, 転送量, 費用)のリスト
+fn group(costs: &[EgressCost], key: impl Fn(&EgressCost) -> &String) -> Vec<(String, u64, f64)> {
+    let mut groups: BTreeMap<String, (u64, f64)> = BTreeMap::new();
+    for cost in costs {
+        let group = groups.entry(key(cost).clone()).or_default();
+        group.0 += cost.bytes;
+        group.1 += cost.cost();
+    }
+    let mut groups: Vec<(String, u64, f64)> = groups
+        .into_iter()
+        .map(|(name, (bytes, cost))| (name, bytes, cost))
+        .collect();
+    groups.sort_by(|a, b| b.2.total_cmp(&a.2).then_with(|| b.1.cmp(&a.1)));
+    groups
+}
@@ -0,0 +1,204 @@
+//! 出力ファイルのage形式での暗号化を実装
+//! 
+//! 外部ファイルに退避したペイロードには本番のデータがそのまま含まれるため、出力した後で
+//! 指定した受信者（`age1...`の公開鍵）向けにage形式で暗号化し、平文のファイルを削除する。
+//! ワークブックの暗号化は`--encrypt-workbook`を指定した場合のみ行う
+
+use crate::error::{AnalyzerError, Result};
+use age::Encryptor;
+use age::x25519::Recipient;
+use log::info;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// 暗号化したファイルに付加する拡張子
+pub const ENCRYPTED_EXTENSION: &str = "age";
+
+/// 出力ファイルの暗号化の設定
+#[derive(Clone)]
+pub struct OutputEncryption {
+    /// 受信者の公開鍵
+    recipients: Vec<Recipient>,
+    /// ワークブック（分割したワークブック・スタブ定義・レポートを含む）も暗号化するかどうか
+    workbook: bool,
+}
+
+impl OutputEncryption {
+    /// 受信者を指定して暗号化の設定を作成
+    /// 
+    /// # Arguments
+    /// * `recipients` - 受信者の公開鍵
+    /// * `workbook` - ワークブックも暗号化するかどうか
+    /// 
+    /// # Returns
+    /// * `OutputEncryption` - 暗号化の設定
+    pub fn new(recipients: Vec<Recipient>, workbook: bool) -> Self {
+        OutputEncryption { recipients, workbook }
+    }
+
+    /// `age1...`形式の受信者の公開鍵を解析
+    /// 
+    /// # Arguments
+    /// * `spec` - 公開鍵
+    /// 
+    /// # Returns
+    /// * `Result<Recipient>` - 成功時は受信者、失敗時はエラー
+    pub fn parse_recipient(spec: &str) -> Result<Recipient> {
+        spec.parse::<Recipient>().map_err(|e| {
+            AnalyzerError::InvalidArgument(format!("ageの受信者の公開鍵（age1...）が不正です: {}: {}", spec, e))
+        })
+    }
+
+    /// 出力したファイルを暗号化し、平文のファイルを削除
+    /// 
+    /// 退避したペイロード（`.txt`）は常に暗号化し、それ以外のファイルは`--encrypt-workbook`を指定した場合のみ暗号化する。
+    /// 存在しないファイル（解析対象のエントリが無く作成しなかったワークブック等）は除く
+    /// 
+    /// # Arguments
+    /// * `paths` - 出力したファイルのパス
+    /// * `workbooks` - ワークブックとして扱うファイルのパス（拡張子に関わらず`--encrypt-workbook`の指定に従う）
+    /// 
+    /// # Returns
+    /// * `Result<Vec<PathBuf>>` - 成功時は暗号化後のファイルのパス（暗号化しなかったファイルは元のパス）、失敗時はエラー
+    pub fn apply(&self, paths: Vec<PathBuf>, workbooks: &[PathBuf]) -> Result<Vec<PathBuf>> {
+        let mut encrypted = 0;
+        let paths = paths
+            .into_iter()
+            .filter(|path| path.is_file())
+            .map(|path| {
+                let is_payload = !workbooks.contains(&path)
+                    && path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("txt"));
+                if !(is_payload || self.workbook) {
+                    return Ok(path);
+                }
+                encrypted += 1;
+                self.encrypt_file(&path)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if encrypted > 0 {
+            info!("出力ファイルをageで暗号化しました: {}件（受信者: {}件）", encrypted, self.recipients.len());
+        }
+        Ok(paths)
+    }
+
+    /// 1件のファイルを`<ファイル名>.age`に暗号化し、平文のファイルを削除
+    /// 
+    /// # Arguments
+    /// * `path` - 暗号化するファイルのパス
+    /// 
+    /// # Returns
+    /// * `Result<PathBuf>` - 成功時は暗号化したファイルのパス、失敗時はエラー
+    fn encrypt_file(&self, path: &Path) -> Result<PathBuf> {
+        let mut encrypted_name = path.as_os_str().to_owned();
+        encrypted_name.push(format!(".{}", ENCRYPTED_EXTENSION));
+        let encrypted_path = PathBuf::from(encrypted_name);
+
+        let encryptor = Encryptor::with_recipients(self.recipients.iter().map(|recipient| recipient as _))
+            .map_err(AnalyzerError::export("暗号化の準備に失敗しました"))?;
+        let mut input = BufReader::new(File::open(path).map_err(AnalyzerError::io("暗号化するファイルを開けませんでした"))?);
+        let output = File::create(&encrypted_path).map_err(AnalyzerError::io("暗号化したファイルの作成に失敗しました"))?;
+        let write = || -> io::Result<()> {
+            let mut writer = encryptor.wrap_output(BufWriter::new(output))?;
+            io::copy(&mut input, &mut writer)?;
+            writer.finish()?.flush()
+        };
+        if let Err(e) = write() {
+            let _ = fs::remove_file(&encrypted_path);
+            return Err(AnalyzerError::io(format!("{}の暗号化に失敗しました", path.display()))(e));
+        }
+
+        fs::remove_file(path).map_err(AnalyzerError::io(format!("平文のファイルの削除に失敗しました: {}", path.display())))?;
+        Ok(encrypted_path)
+    }
+}
+
+impl fmt::Debug for OutputEncryption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let recipients: Vec<String> = self.recipients.iter().map(ToString::to_string).collect();
+        f.debug_struct("OutputEncryption")
+            .field("recipients", &recipients)
+            .field("workbook", &self.workbook)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use age::Decryptor;
+    use age::x25519::Identity;
+    use std::io::Read;
+
+    fn work_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rs_har_analyzer_encryption_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn apply_skips_files_that_were_not_written() {
+        let dir = work_dir("missing");
+        let payload = dir.join("out_R2C3.txt");
+        fs::write(&payload, "secret").unwrap();
+        let workbook = dir.join("out.xlsx");
+        let encryption = OutputEncryption::new(vec![Identity::generate().to_public()], true);
+
+        let paths = encryption.apply(vec![workbook.clone(), payload.clone()], std::slice::from_ref(&workbook)).unwrap();
+        assert_eq!(paths, vec![dir.join("out_R2C3.txt.age")]);
+        assert!(!workbook.exists() && !payload.exists());
+    }
+
+    fn decrypt(path: &Path, identity: &Identity) -> String {
+        let decryptor = Decryptor::new(File::open(path).unwrap()).unwrap();
+        let mut reader = decryptor.decrypt(std::iter::once(identity as &dyn age::Identity)).unwrap();
+        let mut content = String::new();
+        reader.read_to_string(&mut content).unwrap();
+        content
+    }
+
+    #[test]
+    fn apply_encrypts_payloads_for_every_recipient_and_keeps_workbooks_by_default() {
+        let dir = work_dir("payload");
+        let payload = dir.join("out_R2C3.txt");
+        fs::write(&payload, "secret body").unwrap();
+        let workbook = dir.join("out.xlsx");
+        fs::write(&workbook, "workbook").unwrap();
+        let (first, second) = (Identity::generate(), Identity::generate());
+        let encryption = OutputEncryption::new(vec![first.to_public(), second.to_public()], false);
+
+        let paths = encryption.apply(vec![workbook.clone(), payload.clone()], std::slice::from_ref(&workbook)).unwrap();
+        let encrypted = dir.join("out_R2C3.txt.age");
+        assert_eq!(paths, vec![workbook.clone(), encrypted.clone()]);
+        assert!(!payload.exists());
+        assert_eq!(fs::read_to_string(&workbook).unwrap(), "workbook");
+        assert_eq!(decrypt(&encrypted, &first), "secret body");
+        assert_eq!(decrypt(&encrypted, &second), "secret body");
+
+        let other = Decryptor::new(File::open(&encrypted).unwrap()).unwrap();
+        assert!(other.decrypt(std::iter::once(&Identity::generate() as &dyn age::Identity)).is_err());
+    }
+
+    #[test]
+    fn apply_encrypts_workbooks_with_encrypt_workbook() {
+        let dir = work_dir("workbook");
+        // ワークブックとして渡したファイルは拡張子が.txtでも--encrypt-workbookに従う
+        let workbook = dir.join("report.txt");
+        fs::write(&workbook, "report").unwrap();
+        let identity = Identity::generate();
+
+        let kept = OutputEncryption::new(vec![identity.to_public()], false)
+            .apply(vec![workbook.clone()], std::slice::from_ref(&workbook))
+            .unwrap();
+        assert_eq!(kept, vec![workbook.clone()]);
+
+        let paths = OutputEncryption::new(vec![identity.to_public()], true)
+            .apply(vec![workbook.clone()], std::slice::from_ref(&workbook))
+            .unwrap();
+        assert_eq!(paths, vec![dir.join("report.txt.age")]);
+        assert!(!workbook.exists());
+        assert_eq!(decrypt(&paths[0], &identity), "report");
+    }
+}
@@ -0,0 +1,103 @@
+//! エンドポイントごとの層別抽出（`--sample-per-endpoint`）を実装
+//! 
+//! 巨大なキャプチャから、IDを正規化したエンドポイントごとに最大N件の代表的なエントリを選び、
+//! 全てのエンドポイントを網羅した小さなレポートを作成する。エラーのエントリと所要時間の長いエントリを優先して残す
+
+use crate::har_types::Entry;
+use std::collections::HashMap;
+use url::Url;
+
+/// IDとみなす16進数の値の最小の文字数
+const MIN_HEX_ID_CHARS: usize = 16;
+
+/// エンドポイントごとに残すエントリを選ぶ
+/// 
+/// 同じエンドポイントのエントリは、エラー（ステータスコード0・400以上）、所要時間の長い順、記録順に優先する
+/// 
+/// # Arguments
+/// * `entries` - HARエントリのリスト
+/// * `per_endpoint` - エンドポイントごとに残す最大の件数
+/// * `is_candidate` - 抽出の対象のエントリかどうかを判定する関数（エントリの位置とエントリ）
+/// * `duration` - エントリの所要時間（ミリ秒）を取得する関数
+/// 
+/// # Returns
+/// * `Vec<bool>` - エントリと同じ順序の、残すエントリの場合はtrue
+pub fn select(
+    entries: &[Entry],
+    per_endpoint: usize,
+    is_candidate: impl Fn(usize, &Entry) -> bool,
+    duration: impl Fn(&Entry) -> f64,
+) -> Vec<bool> {
+    let mut groups: HashMap<String, Vec<(usize, bool, f64)>> = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        if !is_candidate(index, entry) {
+            continue;
+        }
+        let failed = !(1..400).contains(&entry.response.status);
+        groups
+            .entry(endpoint_key(entry))
+            .or_default()
+            .push((index, failed, duration(entry)));
+    }
+
+    let mut selected = vec![false; entries.len()];
+    for mut group in groups.into_values() {
+        group.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.total_cmp(&a.2)).then(a.0.cmp(&b.0)));
+        for (index, _, _) in group.into_iter().take(per_endpoint) {
+            selected[index] = true;
+        }
+    }
+    selected
+}
+
+/// エントリの正規化したエンドポイント
+/// 
+/// クエリ文字列・フラグメントを除き、数値・UUID・16文字以上の16進数のパスのセグメントを`{id}`に置き換える
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `String` - メソッドと正規化したURL（例: `GET https://example.com/users/{id}/orders`）
+pub fn endpoint_key(entry: &Entry) -> String {
+    let url = entry.request.url.as_str();
+    let normalized = match Url::parse(url) {
+        Ok(mut parsed) => {
+            let path: Vec<&str> = parsed
+                .path()
+                .split('/')
+                .map(|segment| if is_id(segment) { "{id}" } else { segment })
+                .collect();
+            let path = path.join("/");
+            parsed.set_path(&path);
+            parsed.set_query(None);
+            parsed.set_fragment(None);
+            // set_pathは`{`・`}`をパーセントエンコードするため元に戻す
+            parsed.to_string().replace("%7Bid%7D", "{id}")
+        }
+        Err(_) => url.split(['?', '#']).next().unwrap_or(url).to_string(),
+    };
+    format!("{} {}", entry.request.method, normalized)
+}
+
+/// パスのセグメントがIDかどうか
+/// 
+/// # Arguments
+/// * `segment` - パスのセグメント
+/// 
+/// # Returns
+/// * `bool` - 数値・UUID・16文字以上の16進数の場合はtrue
+fn is_id(segment: &str) -> bool {
+    if segment.is_empty() {
+        return false;
+    }
+    if segment.bytes().all(|byte| byte.is_ascii_digit()) {
+        return true;
+    }
+    let is_uuid = segment.len() == 36
+        && segment.char_indices().all(|(position, c)| match position {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        });
+    is_uuid || (segment.len() >= MIN_HEX_ID_CHARS && segment.bytes().all(|byte| byte.is_ascii_hexdigit()))
+}
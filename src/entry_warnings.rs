@@ -0,0 +1,262 @@
+//! エントリごとの解析・デコードの警告（時刻の形式、Base64のデコード、MIMEタイプと内容の不一致等）を実装
+//! 
+//! ログに流れるだけでは見落とされるため、解析結果に保持して警告列とWarningsシートに出力する
+
+use crate::har_types::Entry;
+use crate::mime_map::MimeMap;
+use base64::Engine;
+use chrono::DateTime;
+use std::borrow::Cow;
+
+/// 警告の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum WarningKind {
+    /// 開始時刻を解析できない
+    Timestamp,
+    /// 開始時刻が記録順と前後している、または前後のエントリから大きく離れている
+    ClockSkew,
+    /// Base64のボディをデコードできない
+    Base64,
+    /// 未対応のボディのエンコーディング
+    Encoding,
+    /// MIMEタイプとボディの内容が一致しない
+    MimeMismatch,
+    /// JSONのMIMEタイプのボディをJSONとして解析できない
+    InvalidJson,
+}
+
+impl WarningKind {
+    /// 表示名を取得
+    /// 
+    /// # Returns
+    /// * `&'static str` - 表示名
+    pub fn label(&self) -> &'static str {
+        match self {
+            WarningKind::Timestamp => "時刻の形式",
+            WarningKind::ClockSkew => "時刻の順序",
+            WarningKind::Base64 => "Base64のデコード",
+            WarningKind::Encoding => "エンコーディング",
+            WarningKind::MimeMismatch => "MIMEタイプの不一致",
+            WarningKind::InvalidJson => "不正なJSON",
+        }
+    }
+}
+
+/// 1件の警告
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryWarning {
+    /// 警告の種類
+    pub kind: WarningKind,
+    /// 警告の内容
+    pub message: String,
+}
+
+/// 1件のエントリの警告の一覧
+#[derive(Debug, Clone, Default)]
+pub struct EntryWarnings {
+    items: Vec<EntryWarning>,
+    /// 警告列に出力するテキスト（1行に1件）
+    text: String,
+}
+
+impl EntryWarnings {
+    /// 警告のリストから作成
+    /// 
+    /// # Arguments
+    /// * `items` - 警告のリスト
+    /// 
+    /// # Returns
+    /// * `EntryWarnings` - 警告の一覧
+    pub fn new(items: Vec<EntryWarning>) -> Self {
+        let text = items.iter().map(|item| item.message.as_str()).collect::<Vec<_>>().join("\n");
+        EntryWarnings { items, text }
+    }
+
+    /// 警告のリストを取得
+    /// 
+    /// # Returns
+    /// * `&[EntryWarning]` - 警告のリスト
+    pub fn items(&self) -> &[EntryWarning] {
+        &self.items
+    }
+
+    /// 警告列に出力するテキストを取得
+    /// 
+    /// # Returns
+    /// * `&str` - 1行に1件ずつ記述した警告（無い場合は空文字列）
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// 警告が無いかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - 警告が無い場合はtrue
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// ボディの先頭から判定した内容の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SniffedKind {
+    Json,
+    Html,
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Pdf,
+}
+
+impl SniffedKind {
+    /// 表示名を取得
+    /// 
+    /// # Returns
+    /// * `&'static str` - 表示名
+    fn label(&self) -> &'static str {
+        match self {
+            SniffedKind::Json => "JSON",
+            SniffedKind::Html => "HTML",
+            SniffedKind::Png => "PNG",
+            SniffedKind::Jpeg => "JPEG",
+            SniffedKind::Gif => "GIF",
+            SniffedKind::Webp => "WebP",
+            SniffedKind::Pdf => "PDF",
+        }
+    }
+
+    /// MIMEタイプがこの内容と矛盾しないかどうか
+    /// 
+    /// # Arguments
+    /// * `mime_type` - 小文字のMIMEタイプ（パラメータを除く）
+    /// 
+    /// # Returns
+    /// * `bool` - 矛盾しない場合はtrue
+    fn matches(&self, mime_type: &str) -> bool {
+        // 汎用のMIMEタイプはどの内容とも矛盾しないものとする
+        if mime_type.is_empty() || matches!(mime_type, "text/plain" | "application/octet-stream") {
+            return true;
+        }
+        match self {
+            SniffedKind::Json => mime_type.contains("json") || mime_type.contains("javascript"),
+            SniffedKind::Html => mime_type.contains("html") || mime_type.contains("xml"),
+            SniffedKind::Png => mime_type == "image/png" || mime_type == "image/apng",
+            SniffedKind::Jpeg => matches!(mime_type, "image/jpeg" | "image/jpg" | "image/pjpeg"),
+            SniffedKind::Gif => mime_type == "image/gif",
+            SniffedKind::Webp => mime_type == "image/webp",
+            SniffedKind::Pdf => mime_type == "application/pdf",
+        }
+    }
+}
+
+/// エントリの警告を検出
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// * `mime_map` - MIMEタイプの分類（JSONの判定に使用）
+/// 
+/// # Returns
+/// * `EntryWarnings` - 警告の一覧
+pub fn check(entry: &Entry, mime_map: &MimeMap) -> EntryWarnings {
+    let mut items = Vec::new();
+    let mut push = |kind: WarningKind, message: String| items.push(EntryWarning { kind, message });
+
+    if DateTime::parse_from_rfc3339(&entry.started_date_time).is_err() {
+        push(
+            WarningKind::Timestamp,
+            format!("開始時刻をISO 8601形式として解析できません: {}", entry.started_date_time),
+        );
+    }
+
+    if let Some(post_data) = &entry.request.post_data
+        && let Some(text) = &post_data.text
+        && mime_map.is_json(&post_data.mime_type)
+        && !text.trim().is_empty()
+        && serde_json::from_str::<serde::de::IgnoredAny>(text).is_err()
+    {
+        push(
+            WarningKind::InvalidJson,
+            format!("リクエストボディのMIMEタイプは{}ですが、JSONとして解析できません", post_data.mime_type),
+        );
+    }
+
+    let content = &entry.response.content;
+    let Some(text) = &content.text else {
+        return EntryWarnings::new(items);
+    };
+    let body = match content.encoding.as_deref() {
+        None | Some("") => Cow::Borrowed(text.as_bytes()),
+        Some(encoding) if encoding.eq_ignore_ascii_case("base64") => {
+            match base64::engine::general_purpose::STANDARD.decode(text.trim()) {
+                Ok(body) => Cow::Owned(body),
+                Err(e) => {
+                    push(WarningKind::Base64, format!("レスポンスボディのBase64をデコードできません: {}", e));
+                    return EntryWarnings::new(items);
+                }
+            }
+        }
+        Some(encoding) => {
+            push(WarningKind::Encoding, format!("未対応のレスポンスボディのエンコーディングです: {}", encoding));
+            return EntryWarnings::new(items);
+        }
+    };
+    if body.is_empty() {
+        return EntryWarnings::new(items);
+    }
+
+    let mime_type = content.mime_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    if let Some(kind) = sniff(&body)
+        && !kind.matches(&mime_type)
+    {
+        push(
+            WarningKind::MimeMismatch,
+            format!("レスポンスのMIMEタイプは{}ですが、ボディの内容は{}です", content.mime_type, kind.label()),
+        );
+    } else if mime_map.is_json(&mime_type) && serde_json::from_slice::<serde::de::IgnoredAny>(&body).is_err() {
+        push(
+            WarningKind::InvalidJson,
+            format!("レスポンスのMIMEタイプは{}ですが、ボディをJSONとして解析できません", content.mime_type),
+        );
+    }
+    EntryWarnings::new(items)
+}
+
+/// ボディの先頭から内容の種類を判定
+/// 
+/// # Arguments
+/// * `body` - デコード後のボディ
+/// 
+/// # Returns
+/// * `Option<SniffedKind>` - 判定できない場合はNone
+fn sniff(body: &[u8]) -> Option<SniffedKind> {
+    if body.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some(SniffedKind::Png);
+    }
+    if body.starts_with(b"\xff\xd8\xff") {
+        return Some(SniffedKind::Jpeg);
+    }
+    if body.starts_with(b"GIF87a") || body.starts_with(b"GIF89a") {
+        return Some(SniffedKind::Gif);
+    }
+    if body.len() >= 12 && body.starts_with(b"RIFF") && &body[8..12] == b"WEBP" {
+        return Some(SniffedKind::Webp);
+    }
+    if body.starts_with(b"%PDF-") {
+        return Some(SniffedKind::Pdf);
+    }
+
+    let head = String::from_utf8_lossy(&body[..body.len().min(512)]);
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    let lower = head.to_ascii_lowercase();
+    if lower.starts_with("<!doctype html") || lower.starts_with("<html") {
+        return Some(SniffedKind::Html);
+    }
+    // 数値や文字列だけのボディはJSONとしても有効だが、テキストと区別できないため判定しない
+    if (head.starts_with('{') || head.starts_with('['))
+        && serde_json::from_slice::<serde::de::IgnoredAny>(body).is_ok()
+    {
+        return Some(SniffedKind::Json);
+    }
+    None
+}
@@ -0,0 +1,129 @@
+//! ライブラリのエラー型を定義
+
+use thiserror::Error;
+
+/// ライブラリの処理結果
+pub type Result<T> = std::result::Result<T, AnalyzerError>;
+
+/// ライブラリで発生するエラー
+#[derive(Debug, Error)]
+pub enum AnalyzerError {
+    /// ファイルの読み書きに失敗した
+    #[error("{context}: {source}")]
+    Io {
+        /// 失敗した処理の説明
+        context: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// HARファイル（JSON）の解析に失敗した
+    #[error("JSONの解析に失敗しました（{line}行{column}列）: {message}")]
+    Parse {
+        /// エラーが発生した行（1から開始）
+        line: usize,
+        /// エラーが発生した列（1から開始）
+        column: usize,
+        /// エラーの内容
+        message: String,
+    },
+
+    /// 対応していない形式が指定された
+    #[error("対応していない形式です: {0}")]
+    UnsupportedFormat(String),
+
+    /// 引数や設定値が不正
+    #[error("{0}")]
+    InvalidArgument(String),
+
+    /// 設定ファイルの内容が不正
+    #[error("設定ファイルの解析に失敗しました: {0}")]
+    Config(String),
+
+    /// ファイルの出力に失敗した
+    #[error("{context}: {message}")]
+    Export {
+        /// 失敗した処理の説明
+        context: String,
+        /// エラーの内容
+        message: String,
+    },
+
+    /// オブジェクトストレージへのアップロードに失敗した
+    #[error("{context}: {message}")]
+    Upload {
+        /// 失敗した処理の説明
+        context: String,
+        /// エラーの内容
+        message: String,
+    },
+
+    /// サイズの予算を超過したエントリがある（`--fail-on-budget`を指定した場合）
+    #[error("サイズの予算を超過しました: {0}件")]
+    BudgetExceeded(usize),
+
+    /// 推移のデータベース（SQLite）の操作に失敗した
+    #[error("データベースの操作に失敗しました: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    /// ログの初期化に失敗した
+    #[error("ログの初期化に失敗しました: {0}")]
+    Logger(#[from] log::SetLoggerError),
+}
+
+impl AnalyzerError {
+    /// 入出力エラーに変換する関数を作成（`map_err`で使用）
+    /// 
+    /// # Arguments
+    /// * `context` - 失敗した処理の説明
+    /// 
+    /// # Returns
+    /// * `impl FnOnce(std::io::Error) -> AnalyzerError` - 変換する関数
+    pub fn io(context: impl Into<String>) -> impl FnOnce(std::io::Error) -> AnalyzerError {
+        let context = context.into();
+        move |source| AnalyzerError::Io { context, source }
+    }
+
+    /// 出力エラーに変換する関数を作成（`map_err`で使用）
+    /// 
+    /// # Arguments
+    /// * `context` - 失敗した処理の説明
+    /// 
+    /// # Returns
+    /// * `impl FnOnce(E) -> AnalyzerError` - 変換する関数
+    pub fn export<E: std::fmt::Display>(context: impl Into<String>) -> impl FnOnce(E) -> AnalyzerError {
+        let context = context.into();
+        move |e| AnalyzerError::Export { context, message: e.to_string() }
+    }
+
+    /// アップロードエラーに変換する関数を作成（`map_err`で使用）
+    /// 
+    /// # Arguments
+    /// * `context` - 失敗した処理の説明
+    /// 
+    /// # Returns
+    /// * `impl FnOnce(E) -> AnalyzerError` - 変換する関数
+    pub fn upload<E: std::fmt::Display>(context: impl Into<String>) -> impl FnOnce(E) -> AnalyzerError {
+        let context = context.into();
+        move |e| AnalyzerError::Upload { context, message: e.to_string() }
+    }
+}
+
+impl From<serde_json::Error> for AnalyzerError {
+    fn from(e: serde_json::Error) -> Self {
+        AnalyzerError::Parse {
+            line: e.line(),
+            column: e.column(),
+            message: e.to_string(),
+        }
+    }
+}
+
+impl From<rust_xlsxwriter::XlsxError> for AnalyzerError {
+    fn from(e: rust_xlsxwriter::XlsxError) -> Self {
+        AnalyzerError::Export {
+            context: "Excelファイルの出力に失敗しました".to_string(),
+            message: e.to_string(),
+        }
+    }
+}
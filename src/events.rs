@@ -0,0 +1,107 @@
+//! HARから取り出す通信イベントの内部モデルを実装
+//! 
+//! HTTPの通信以外（WebSocketのメッセージ、ページの読み込み等）をAnalysisResultに
+//! 詰め込まずに扱うため、イベントの種類ごとに型を分ける
+
+use crate::critical_path::CriticalPathStep;
+use crate::har_types::AnalysisResult;
+use crate::milestones::PageMilestones;
+
+/// HARから取り出した通信イベント
+// イベントの大半はHTTPの通信のため、Box化して1件ごとに割り当てを増やすことはしない
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone)]
+pub enum TrafficEvent {
+    /// HTTPのリクエストとレスポンス
+    HttpExchange(AnalysisResult),
+    /// WebSocketで送受信したメッセージ
+    WebSocketFrame(WebSocketFrame),
+    /// ページの読み込み
+    PageEvent(PageEvent),
+}
+
+impl TrafficEvent {
+    /// HTTPの通信の場合は解析結果を取得
+    /// 
+    /// # Returns
+    /// * `Option<&AnalysisResult>` - HTTPの通信の場合は解析結果
+    pub fn as_http(&self) -> Option<&AnalysisResult> {
+        match self {
+            TrafficEvent::HttpExchange(result) => Some(result),
+            _ => None,
+        }
+    }
+}
+
+/// WebSocketメッセージの送受信の方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameDirection {
+    /// ブラウザからサーバーへの送信
+    Send,
+    /// サーバーからブラウザへの受信
+    Receive,
+}
+
+impl FrameDirection {
+    /// HARのtypeフィールドから方向を判定
+    /// 
+    /// # Arguments
+    /// * `message_type` - HARのtypeフィールド（send/receive）
+    /// 
+    /// # Returns
+    /// * `FrameDirection` - 送受信の方向
+    pub fn from_message_type(message_type: &str) -> Self {
+        if message_type.eq_ignore_ascii_case("send") {
+            FrameDirection::Send
+        } else {
+            FrameDirection::Receive
+        }
+    }
+
+    /// 表示名
+    /// 
+    /// # Returns
+    /// * `&'static str` - 表示名
+    pub fn label(&self) -> &'static str {
+        match self {
+            FrameDirection::Send => "送信",
+            FrameDirection::Receive => "受信",
+        }
+    }
+}
+
+/// WebSocketで送受信したメッセージ
+#[derive(Debug, Clone)]
+pub struct WebSocketFrame {
+    /// 送受信した時刻
+    pub timestamp: String,
+    /// WebSocketの接続先URL
+    pub url: String,
+    /// 送受信の方向
+    pub direction: FrameDirection,
+    /// WebSocketのオペコード
+    pub opcode: i32,
+    /// メッセージの内容
+    pub data: String,
+}
+
+/// ページの読み込み
+#[derive(Debug, Clone)]
+pub struct PageEvent {
+    /// ページID
+    pub page_id: String,
+    /// ページのタイトル
+    pub title: String,
+    /// 読み込みを開始した時刻
+    pub started: String,
+    /// DOMContentLoadedまでの時間（ミリ秒）
+    pub on_content_load: Option<f64>,
+    /// loadイベントまでの時間（ミリ秒）
+    pub on_load: Option<f64>,
+    /// ページに属するリクエスト数
+    pub request_count: usize,
+    /// DOMContentLoaded・loadまでの通信の集計
+    pub milestones: PageMilestones,
+    /// loadまでのクリティカルパス（推定）
+    pub critical_path: Vec<CriticalPathStep>,
+}
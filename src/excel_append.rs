@@ -0,0 +1,157 @@
+//! 既存のワークブックへの追記処理を実装
+//! 
+//! rust_xlsxwriterは既存のファイルを編集できないため、既存のシートの値を読み込んで
+//! 新しいワークブックに書き写した上で、今回の解析結果を日付のシートとして追加する
+
+use crate::columns::Column;
+use crate::excel_exporter::{ExcelExporter, ExportOptions};
+use crate::excel_styles::ExcelStyles;
+use crate::error::{AnalyzerError, Result};
+use calamine::{open_workbook, Data, Reader, Xlsx};
+use chrono::Local;
+use log::info;
+use rust_xlsxwriter::{Format, Workbook};
+
+/// 既存のワークブックのシートを書き写す
+/// 
+/// セルの値のみを引き継ぎ、ヘッダー行（1行目）にはヘッダーのフォーマットを適用する。
+/// 列幅はヘッダーが既知の列名と一致する場合のみ復元する
+/// 
+/// # Arguments
+/// * `workbook` - 書き込み先のワークブック
+/// * `path` - 既存のワークブックのパス
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<Vec<String>>` - 成功時は書き写したシート名のリスト、失敗時はエラー
+pub fn copy_existing_sheets(
+    workbook: &mut Workbook,
+    path: &str,
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<Vec<String>> {
+    info!("既存のExcelファイルに追記します: {}", path);
+
+    let mut existing: Xlsx<_> = open_workbook(path)
+        .map_err(AnalyzerError::export("既存のExcelファイルの読み込みに失敗しました"))?;
+    let sheet_names = existing.sheet_names();
+    let datetime_format = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss");
+
+    for sheet_name in &sheet_names {
+        let range = existing
+            .worksheet_range(sheet_name)
+            .map_err(AnalyzerError::export(format!("シート「{}」の読み込みに失敗しました", sheet_name)))?;
+
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(sheet_name)?;
+        ExcelExporter::apply_protection(worksheet, options);
+
+        let (start_row, start_col) = range.start().unwrap_or((0, 0));
+        for (row, col, value) in range.used_cells() {
+            let row = start_row + row as u32;
+            let col = (start_col as usize + col) as u16;
+
+            match value {
+                Data::String(text) if row == 0 => {
+                    worksheet.write_string_with_format(row, col, text, &styles.header)?;
+                    if let Some(column) = Column::ALL.iter().find(|c| c.header() == text) {
+                        worksheet.set_column_width(col, column.width())?;
+                    }
+                }
+                Data::String(text) | Data::DateTimeIso(text) | Data::DurationIso(text) => {
+                    worksheet.write_string(row, col, text)?;
+                }
+                Data::Int(value) => {
+                    worksheet.write_number(row, col, *value as f64)?;
+                }
+                Data::Float(value) => {
+                    worksheet.write_number(row, col, *value)?;
+                }
+                Data::Bool(value) => {
+                    worksheet.write_boolean(row, col, *value)?;
+                }
+                Data::DateTime(value) => {
+                    worksheet.write_number_with_format(row, col, value.as_f64(), &datetime_format)?;
+                }
+                Data::Error(_) | Data::Empty => {}
+            }
+        }
+    }
+
+    info!("既存のシートを{}件引き継ぎました", sheet_names.len());
+    Ok(sheet_names)
+}
+
+/// Excelのシート名の最大文字数
+pub const MAX_SHEET_NAME_LENGTH: usize = 31;
+
+/// 追記するシートの名前を取得
+/// 
+/// 当日の日付（例: "2024-05-01"）とし、既に存在する場合は"_2"・"_3"…を付加する
+/// 
+/// # Arguments
+/// * `existing` - 既存のシート名のリスト
+/// 
+/// # Returns
+/// * `String` - シート名
+pub fn dated_sheet_name(existing: &[String]) -> String {
+    let date = Local::now().format("%Y-%m-%d").to_string();
+    unused_sheet_name(&date, existing)
+}
+
+/// 既存のシートと重複しないシート名を取得
+/// 
+/// 続きのデータシート（"<名前> (2)"）・データシート以外のシート（"<シートの種類> <名前>"）も
+/// 既存のシートと重複しないように、これらの名前が使われている場合も使用中とみなす
+/// 
+/// # Arguments
+/// * `base` - 基本の名前
+/// * `existing` - 既存のシート名のリスト
+/// 
+/// # Returns
+/// * `String` - 基本の名前、または使用中の場合は連番を付加した名前（例: "2024-05-01_2"）
+fn unused_sheet_name(base: &str, existing: &[String]) -> String {
+    let in_use = |name: &str| {
+        existing.iter().any(|sheet| {
+            sheet == name || sheet.starts_with(&format!("{} (", name)) || sheet.ends_with(&format!(" {}", name))
+        })
+    };
+    if !in_use(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|number| format!("{}_{}", base, number))
+        .find(|name| !in_use(name))
+        .unwrap_or_else(|| base.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn unused_sheet_name_keeps_base_when_free() {
+        assert_eq!(unused_sheet_name("2026-10-16", &names(&["Data", "2026-10-15"])), "2026-10-16");
+    }
+
+    #[test]
+    fn unused_sheet_name_adds_suffix_for_same_day() {
+        let existing = names(&["2026-10-16", "2026-10-16 (2)", "Security Findings 2026-10-16"]);
+        assert_eq!(unused_sheet_name("2026-10-16", &existing), "2026-10-16_2");
+
+        let existing = names(&["2026-10-16", "2026-10-16_2", "Security Findings 2026-10-16_2"]);
+        assert_eq!(unused_sheet_name("2026-10-16", &existing), "2026-10-16_3");
+    }
+
+    #[test]
+    fn unused_sheet_name_detects_auxiliary_sheets() {
+        // データシートを削除した場合もデータシート以外のシートと重複しない名前とする
+        let existing = names(&["Run Info 2026-10-16"]);
+        assert_eq!(unused_sheet_name("2026-10-16", &existing), "2026-10-16_2");
+    }
+}
@@ -0,0 +1,1262 @@
+//! HTTPの通信以外の通信イベントのシート出力を実装
+
+use crate::budgets::BudgetViolation;
+use crate::cache_lint::CacheViolation;
+use crate::compression::{self, HostSavings};
+use crate::conditional::{self, ConditionalStats};
+use crate::connections::{self, ConnectionUsage};
+use crate::consent::ConsentReport;
+use crate::duplicates::DuplicateGroup;
+use crate::entry_warnings::WarningKind;
+use crate::events::{PageEvent, WebSocketFrame};
+use crate::excel_exporter::{ExcelExporter, ExportOptions};
+use crate::excel_styles::ExcelStyles;
+use crate::header_inventory::HeaderUsage;
+use crate::host_latency::{self, HostLatency};
+use crate::infrastructure::HostInfrastructure;
+use crate::pagination::PageSequence;
+use crate::security::SecurityFinding;
+use crate::server_timing::{self, ServerTimingEntry};
+use crate::error::Result;
+use crate::traffic::{self, TrafficTimeline};
+use crate::trackers::CategoryTotals;
+use log::info;
+use rust_xlsxwriter::{
+    Chart, ChartFormat, ChartLine, ChartLineDashType, ChartMarker, ChartMarkerType, ChartSolidFill, ChartType, Color, Url,
+    Workbook, Worksheet,
+};
+use std::path::{Path, PathBuf};
+
+/// Summaryシートの待機時間の分布のグラフに表示するホストの最大数
+const MAX_CHARTED_HOSTS: usize = 20;
+
+/// シートを追加してヘッダー行を書き込み
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `headers` - (列名, 列幅)のリスト
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<&mut Worksheet>` - 追加されたワークシート
+fn add_sheet<'a>(
+    workbook: &'a mut Workbook,
+    sheet_name: &str,
+    headers: &[(&str, f64)],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<&'a mut Worksheet> {
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name(sheet_name)?;
+    ExcelExporter::apply_protection(worksheet, options);
+
+    for (col, (header, width)) in headers.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, *header, &styles.header)?;
+        worksheet.set_column_width(col as u16, *width)?;
+    }
+    worksheet.set_freeze_panes(1, 0)?;
+    Ok(worksheet)
+}
+
+/// ページの読み込みをシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `pages` - ページの読み込みイベントのリスト
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_pages_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    pages: &[PageEvent],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("ページID", 12.0),
+        ("タイトル", 40.0),
+        ("開始時刻", 24.0),
+        ("DOMContentLoaded(ms)", 22.0),
+        ("load(ms)", 12.0),
+        ("リクエスト数", 14.0),
+        ("DOMContentLoadedまでのリクエスト数", 22.0),
+        ("DOMContentLoadedまでの受信サイズ(bytes)", 24.0),
+        ("loadまでのリクエスト数", 16.0),
+        ("loadまでの受信サイズ(bytes)", 18.0),
+        ("loadまでのブロッキングリソース", 60.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    for (index, page) in pages.iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_string_with_format(row, 0, &page.page_id, &styles.cell)?;
+        worksheet.write_string_with_format(row, 1, &page.title, &styles.cell)?;
+        worksheet.write_string_with_format(row, 2, &page.started, &styles.cell)?;
+        for (col, timing) in [(3, page.on_content_load), (4, page.on_load)] {
+            match timing {
+                Some(value) => worksheet.write_number_with_format(row, col, value, &styles.cell)?,
+                None => worksheet.write_blank(row, col, &styles.cell)?,
+            };
+        }
+        worksheet.write_number_with_format(row, 5, page.request_count as f64, &styles.cell)?;
+
+        let milestones = &page.milestones;
+        let counts = [
+            milestones.requests_before_content_load as f64,
+            milestones.bytes_before_content_load as f64,
+            milestones.requests_before_load as f64,
+            milestones.bytes_before_load as f64,
+        ];
+        for (offset, value) in counts.into_iter().enumerate() {
+            worksheet.write_number_with_format(row, 6 + offset as u16, value, &styles.cell)?;
+        }
+        worksheet.write_string_with_format(row, 10, milestones.blocking_resources.join("\n"), &styles.cell)?;
+    }
+    Ok(())
+}
+
+/// WebSocketのメッセージをシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `frames` - WebSocketのメッセージのリスト
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// * `file_prefix` - 大きなメッセージを退避する外部ファイルの接頭辞
+/// * `output_dir` - 出力ディレクトリ
+/// 
+/// # Returns
+/// * `Result<Vec<PathBuf>>` - 成功時は外部ファイルに退避したメッセージのパス、失敗時はエラー
+pub fn write_websocket_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    frames: &[WebSocketFrame],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+    file_prefix: &str,
+    output_dir: &Path,
+) -> Result<Vec<PathBuf>> {
+    let headers = [
+        ("時刻", 24.0),
+        ("URL", 40.0),
+        ("方向", 8.0),
+        ("オペコード", 12.0),
+        ("データ", 60.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    let mut sidecars = Vec::new();
+    for (index, frame) in frames.iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_string_with_format(row, 0, &frame.timestamp, &styles.cell)?;
+        worksheet.write_string_with_format(row, 1, &frame.url, &styles.cell)?;
+        worksheet.write_string_with_format(row, 2, frame.direction.label(), &styles.cell)?;
+        worksheet.write_number_with_format(row, 3, frame.opcode as f64, &styles.cell)?;
+
+        // 長い場合は外部ファイルに退避
+        let data = ExcelExporter::handle_large_content(&frame.data, file_prefix, output_dir, row + 1, 4, &mut sidecars)?;
+        worksheet.write_string_with_format(row, 4, &data, &styles.json)?;
+    }
+    Ok(sidecars)
+}
+
+/// 時間帯ごとのリクエスト数と転送量をシートに出力し、折れ線グラフを追加
+/// 
+/// リクエスト数の移動zスコアが閾値以上の時間帯は異常な時間帯として行を強調し、グラフに印を付ける
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `timeline` - 時間帯ごとの集計
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_traffic_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    timeline: &TrafficTimeline,
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("開始時刻", 24.0),
+        ("リクエスト数", 14.0),
+        ("リクエスト/秒", 14.0),
+        ("転送量(bytes)", 16.0),
+        ("直前の平均", 12.0),
+        ("下限", 10.0),
+        ("上限", 10.0),
+        ("zスコア", 10.0),
+        ("異常", 8.0),
+        ("異常な時間帯のリクエスト数", 16.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    let rows = timeline.rows();
+    let scores = traffic::anomaly_scores(&rows);
+    let seconds = timeline.interval_ms() as f64 / 1000.0;
+    let anomalies = scores.iter().flatten().filter(|score| score.is_anomalous()).count();
+    if anomalies > 0 {
+        info!("リクエスト数が異常な時間帯: {}件", anomalies);
+    }
+    for (index, ((start, bucket), score)) in rows.iter().zip(&scores).enumerate() {
+        let row = index as u32 + 1;
+        let anomalous = score.is_some_and(|score| score.is_anomalous());
+        // 異常な時間帯は行の背景色で強調する
+        let format = if anomalous { styles.flagged_cell() } else { &styles.cell };
+        let started = start.format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+        worksheet.write_string_with_format(row, 0, &started, format)?;
+        worksheet.write_number_with_format(row, 1, bucket.requests as f64, format)?;
+        worksheet.write_number_with_format(row, 2, bucket.requests as f64 / seconds, format)?;
+        worksheet.write_number_with_format(row, 3, bucket.bytes as f64, format)?;
+        match score {
+            Some(score) => {
+                worksheet.write_number_with_format(row, 4, round(score.mean), format)?;
+                worksheet.write_number_with_format(row, 5, round(score.lower), format)?;
+                worksheet.write_number_with_format(row, 6, round(score.upper), format)?;
+                worksheet.write_number_with_format(row, 7, round(score.z_score), format)?;
+            }
+            None => {
+                for col in 4..=7 {
+                    worksheet.write_string_with_format(row, col, "", format)?;
+                }
+            }
+        }
+        worksheet.write_string_with_format(row, 8, if anomalous { "○" } else { "" }, format)?;
+        // グラフで異常な時間帯のみに印を付けるため、異常でない時間帯は空欄にする
+        if anomalous {
+            worksheet.write_number_with_format(row, 9, bucket.requests as f64, format)?;
+        } else {
+            worksheet.write_string_with_format(row, 9, "", format)?;
+        }
+    }
+    if !rows.is_empty() {
+        worksheet.autofilter(0, 0, rows.len() as u32, headers.len() as u16 - 1)?;
+    }
+
+    // リクエスト数と転送量は桁が異なるため、転送量は第2軸に表示する
+    let last_row = rows.len() as u32;
+    let mut chart = Chart::new(ChartType::Line);
+    chart
+        .add_series()
+        .set_name((sheet_name, 0, 1))
+        .set_categories((sheet_name, 1, 0, last_row, 0))
+        .set_values((sheet_name, 1, 1, last_row, 1));
+    for col in [5, 6] {
+        chart
+            .add_series()
+            .set_name((sheet_name, 0, col))
+            .set_categories((sheet_name, 1, 0, last_row, 0))
+            .set_values((sheet_name, 1, col, last_row, col))
+            .set_format(ChartFormat::new().set_line(ChartLine::new().set_color(Color::Gray).set_dash_type(ChartLineDashType::Dash)));
+    }
+    chart
+        .add_series()
+        .set_name((sheet_name, 0, 9))
+        .set_categories((sheet_name, 1, 0, last_row, 0))
+        .set_values((sheet_name, 1, 9, last_row, 9))
+        .set_format(ChartFormat::new().set_no_line())
+        .set_marker(
+            ChartMarker::new()
+                .set_type(ChartMarkerType::Circle)
+                .set_size(7)
+                .set_format(ChartFormat::new().set_solid_fill(ChartSolidFill::new().set_color(Color::Red))),
+        );
+    chart
+        .add_series()
+        .set_name((sheet_name, 0, 3))
+        .set_categories((sheet_name, 1, 0, last_row, 0))
+        .set_values((sheet_name, 1, 3, last_row, 3))
+        .set_secondary_axis(true);
+    chart.title().set_name(&format!("時間帯ごとのリクエスト数と転送量（{}秒間隔）", seconds));
+    chart.y_axis().set_name("リクエスト数");
+    chart.y2_axis().set_name("転送量(bytes)");
+    chart.set_width(960).set_height(360);
+    worksheet.insert_chart(1, headers.len() as u16 + 1, &chart)?;
+    Ok(())
+}
+
+/// 小数点以下2桁に丸める
+/// 
+/// # Arguments
+/// * `value` - 値
+/// 
+/// # Returns
+/// * `f64` - 丸めた値
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// ページごとのloadまでのクリティカルパスをシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `pages` - ページの読み込みイベントのリスト
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_critical_path_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    pages: &[PageEvent],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("ページID", 12.0),
+        ("順序", 8.0),
+        ("メソッド", 10.0),
+        ("URL", 60.0),
+        ("開始(ms)", 12.0),
+        ("所要時間(ms)", 14.0),
+        ("発生元の完了からの待ち(ms)", 26.0),
+        ("累積(ms)", 12.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    let mut row = 0;
+    for page in pages {
+        for (order, step) in page.critical_path.iter().enumerate() {
+            row += 1;
+            worksheet.write_string_with_format(row, 0, &page.page_id, &styles.cell)?;
+            worksheet.write_number_with_format(row, 1, order as f64 + 1.0, &styles.cell)?;
+            worksheet.write_string_with_format(row, 2, &step.method, &styles.cell)?;
+            worksheet.write_string_with_format(row, 3, &step.url, &styles.cell)?;
+            worksheet.write_number_with_format(row, 4, step.start, &styles.cell)?;
+            worksheet.write_number_with_format(row, 5, step.duration, &styles.cell)?;
+            match step.wait {
+                Some(wait) => worksheet.write_number_with_format(row, 6, wait, &styles.cell)?,
+                None => worksheet.write_blank(row, 6, &styles.cell)?,
+            };
+            worksheet.write_number_with_format(row, 7, step.cumulative, &styles.cell)?;
+        }
+    }
+    Ok(())
+}
+
+/// Indexシートに出力する分割したワークブック
+#[derive(Debug, Clone)]
+pub struct ChunkFile {
+    /// ワークブックのファイル名
+    pub file_name: String,
+    /// 出力した行数
+    pub rows: usize,
+    /// 最初のエントリ番号（HAR内の順序で1始まり）
+    pub first_entry_number: usize,
+    /// 最後のエントリ番号（HAR内の順序で1始まり）
+    pub last_entry_number: usize,
+    /// 最初の行の時刻
+    pub first_timestamp: String,
+    /// 最後の行の時刻
+    pub last_timestamp: String,
+}
+
+/// `--chunk-rows`で分割したワークブックの一覧をシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `chunks` - 分割したワークブックのリスト
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_chunk_index_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    chunks: &[ChunkFile],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("ワークブック", 30.0),
+        ("行数", 10.0),
+        ("最初のエントリ番号", 18.0),
+        ("最後のエントリ番号", 18.0),
+        ("最初の時刻", 24.0),
+        ("最後の時刻", 24.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    for (index, chunk) in chunks.iter().enumerate() {
+        let row = index as u32 + 1;
+        // 同じディレクトリのワークブックへの相対パスのリンク
+        let link = Url::new(format!("file:///{}", chunk.file_name)).set_text(&chunk.file_name);
+        worksheet.write_url(row, 0, link)?;
+        worksheet.write_number_with_format(row, 1, chunk.rows as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 2, chunk.first_entry_number as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 3, chunk.last_entry_number as f64, &styles.cell)?;
+        worksheet.write_string_with_format(row, 4, &chunk.first_timestamp, &styles.cell)?;
+        worksheet.write_string_with_format(row, 5, &chunk.last_timestamp, &styles.cell)?;
+    }
+    Ok(())
+}
+
+/// Flaggedシートに出力する行
+#[derive(Debug, Clone)]
+pub struct FlaggedRow {
+    /// データシートの名前
+    pub sheet_name: String,
+    /// データシートの行番号（0始まり）
+    pub row: u32,
+    /// エントリ番号（HAR内の順序で1始まり）
+    pub entry_number: usize,
+    /// 時刻
+    pub timestamp: String,
+    /// HTTPメソッド
+    pub method: String,
+    /// ステータスコード
+    pub status_code: i32,
+    /// リクエストURL
+    pub url: String,
+    /// 注目させる理由
+    pub reason: String,
+}
+
+/// `--flag-url-regex`・`--flag-index`で指定したエントリの一覧をシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `rows` - 注目させる行のリスト
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_flagged_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    rows: &[FlaggedRow],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("データシートの行", 22.0),
+        ("エントリ番号", 12.0),
+        ("時刻", 24.0),
+        ("メソッド", 10.0),
+        ("ステータスコード", 15.0),
+        ("リクエストURL", 60.0),
+        ("理由", 40.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    for (index, flagged) in rows.iter().enumerate() {
+        let row = index as u32 + 1;
+        // データシートの該当行へのリンク
+        let link = Url::new(format!("internal:'{}'!A{}", flagged.sheet_name.replace('\'', "''"), flagged.row + 1))
+            .set_text(format!("{} {}行目", flagged.sheet_name, flagged.row + 1));
+        worksheet.write_url(row, 0, link)?;
+        worksheet.write_number_with_format(row, 1, flagged.entry_number as f64, &styles.cell)?;
+        worksheet.write_string_with_format(row, 2, &flagged.timestamp, &styles.cell)?;
+        worksheet.write_string_with_format(row, 3, &flagged.method, &styles.cell)?;
+        worksheet.write_number_with_format(row, 4, flagged.status_code as f64, &styles.cell)?;
+        worksheet.write_string_with_format(row, 5, &flagged.url, &styles.cell)?;
+        worksheet.write_string_with_format(row, 6, &flagged.reason, &styles.cell)?;
+    }
+    Ok(())
+}
+
+/// Warningsシートに出力する行（警告1件につき1行）
+#[derive(Debug, Clone)]
+pub struct WarningRow {
+    /// データシートの名前
+    pub sheet_name: String,
+    /// データシートの行番号（0始まり）
+    pub row: u32,
+    /// エントリ番号（HAR内の順序で1始まり）
+    pub entry_number: usize,
+    /// リクエストURL
+    pub url: String,
+    /// 警告の種類
+    pub kind: WarningKind,
+    /// 警告の内容
+    pub message: String,
+}
+
+/// エントリごとの解析・デコードの警告の一覧をシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `rows` - 警告の行のリスト
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_warnings_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    rows: &[WarningRow],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("データシートの行", 22.0),
+        ("エントリ番号", 12.0),
+        ("リクエストURL", 60.0),
+        ("種類", 20.0),
+        ("内容", 60.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    for (index, warning) in rows.iter().enumerate() {
+        let row = index as u32 + 1;
+        // データシートの該当行へのリンク
+        let link = Url::new(format!("internal:'{}'!A{}", warning.sheet_name.replace('\'', "''"), warning.row + 1))
+            .set_text(format!("{} {}行目", warning.sheet_name, warning.row + 1));
+        worksheet.write_url(row, 0, link)?;
+        worksheet.write_number_with_format(row, 1, warning.entry_number as f64, &styles.cell)?;
+        worksheet.write_string_with_format(row, 2, &warning.url, &styles.cell)?;
+        worksheet.write_string_with_format(row, 3, warning.kind.label(), &styles.cell)?;
+        worksheet.write_string_with_format(row, 4, &warning.message, &styles.cell)?;
+    }
+    Ok(())
+}
+
+/// セキュリティの指摘事項をシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `findings` - 指摘事項のリスト
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_security_findings_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    findings: &[SecurityFinding],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("種類", 16.0),
+        ("エントリ番号", 12.0),
+        ("時刻", 30.0),
+        ("メソッド", 10.0),
+        ("リクエストURL", 60.0),
+        ("詳細", 60.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    for (index, finding) in findings.iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_string_with_format(row, 0, finding.kind.label(), &styles.cell)?;
+        worksheet.write_number_with_format(row, 1, finding.entry_number as f64, &styles.cell)?;
+        worksheet.write_string_with_format(row, 2, &finding.timestamp, &styles.cell)?;
+        worksheet.write_string_with_format(row, 3, &finding.method, &styles.cell)?;
+        worksheet.write_string_with_format(row, 4, &finding.url, &styles.cell)?;
+        worksheet.write_string_with_format(row, 5, &finding.detail, &styles.cell)?;
+    }
+    worksheet.autofilter(0, 0, findings.len() as u32, headers.len() as u16 - 1)?;
+    Ok(())
+}
+
+/// ページ送りの一連のリクエストをシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `sequences` - ページ送りの一連のリクエスト
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_pagination_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    sequences: &[PageSequence],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("エンドポイント", 60.0),
+        ("ページのパラメータ", 20.0),
+        ("ページ数", 10.0),
+        ("最初のエントリ番号", 18.0),
+        ("最後のエントリ番号", 18.0),
+        ("合計サイズ(bytes)", 18.0),
+        ("所要時間の合計(ms)", 18.0),
+        ("経過時間(ms)", 15.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    for (index, sequence) in sequences.iter().enumerate() {
+        let row = index as u32 + 1;
+        let first = sequence.entry_indices.first().map_or(0, |index| index + 1);
+        let last = sequence.entry_indices.last().map_or(0, |index| index + 1);
+        worksheet.write_string_with_format(row, 0, &sequence.endpoint, &styles.cell)?;
+        worksheet.write_string_with_format(row, 1, sequence.params.join(", "), &styles.cell)?;
+        worksheet.write_number_with_format(row, 2, sequence.pages() as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 3, first as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 4, last as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 5, sequence.bytes as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 6, sequence.total_time, &styles.cell)?;
+        worksheet.write_number_with_format(row, 7, sequence.elapsed, &styles.cell)?;
+    }
+    worksheet.autofilter(0, 0, sequences.len() as u32, headers.len() as u16 - 1)?;
+    Ok(())
+}
+
+/// キャッシュの規則の違反をシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `violations` - キャッシュの規則の違反
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_cache_lint_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    violations: &[CacheViolation],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("エントリ番号", 12.0),
+        ("リソースの種類", 24.0),
+        ("リクエストURL", 60.0),
+        ("Cache-Control", 36.0),
+        ("違反の内容", 60.0),
+        ("推奨する値", 36.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    for (index, violation) in violations.iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_number_with_format(row, 0, violation.entry_number as f64, &styles.cell)?;
+        worksheet.write_string_with_format(row, 1, violation.class.label(), &styles.cell)?;
+        worksheet.write_string_with_format(row, 2, &violation.url, &styles.cell)?;
+        worksheet.write_string_with_format(row, 3, &violation.cache_control, &styles.cell)?;
+        worksheet.write_string_with_format(row, 4, violation.problems.join("\n"), &styles.cell)?;
+        worksheet.write_string_with_format(row, 5, &violation.suggested, &styles.cell)?;
+    }
+    worksheet.autofilter(0, 0, violations.len() as u32, headers.len() as u16 - 1)?;
+    Ok(())
+}
+
+/// サイズの予算の超過をシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `violations` - サイズの予算の超過
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_budgets_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    violations: &[BudgetViolation],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("エントリ番号", 12.0),
+        ("予算の種類", 24.0),
+        ("リクエストURL", 60.0),
+        ("対象", 30.0),
+        ("サイズ(bytes)", 14.0),
+        ("上限(bytes)", 14.0),
+        ("超過(bytes)", 14.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    for (index, violation) in violations.iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_number_with_format(row, 0, violation.entry_number as f64, &styles.cell)?;
+        worksheet.write_string_with_format(row, 1, violation.kind.label(), &styles.cell)?;
+        worksheet.write_string_with_format(row, 2, &violation.url, &styles.cell)?;
+        worksheet.write_string_with_format(row, 3, &violation.detail, &styles.cell)?;
+        worksheet.write_number_with_format(row, 4, violation.actual as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 5, violation.limit as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 6, (violation.actual - violation.limit) as f64, &styles.cell)?;
+    }
+    worksheet.autofilter(0, 0, violations.len() as u32, headers.len() as u16 - 1)?;
+    Ok(())
+}
+
+/// 異なるURLで同じ内容のレスポンスをシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `groups` - 同じ内容のレスポンスを返したURLのグループ
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_duplicates_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    groups: &[DuplicateGroup],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("SHA-256", 20.0),
+        ("MIMEタイプ", 24.0),
+        ("サイズ(bytes)", 14.0),
+        ("URL数", 8.0),
+        ("削減できるサイズ(bytes)", 22.0),
+        ("エントリ番号", 16.0),
+        ("URL", 80.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    for (index, group) in groups.iter().enumerate() {
+        let row = index as u32 + 1;
+        let entry_numbers: Vec<String> = group.entry_numbers.iter().map(|number| number.to_string()).collect();
+        worksheet.write_string_with_format(row, 0, &group.sha256, &styles.cell)?;
+        worksheet.write_string_with_format(row, 1, &group.mime_type, &styles.cell)?;
+        worksheet.write_number_with_format(row, 2, group.size as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 3, group.urls.len() as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 4, group.savings() as f64, &styles.cell)?;
+        worksheet.write_string_with_format(row, 5, entry_numbers.join(", "), &styles.cell)?;
+        worksheet.write_string_with_format(row, 6, group.urls.join("\n"), &styles.cell)?;
+    }
+    worksheet.autofilter(0, 0, groups.len() as u32, headers.len() as u16 - 1)?;
+    Ok(())
+}
+
+/// ホストごとの圧縮による削減量の見積もりをシートに出力
+/// 
+/// 先頭に全てのホストの合計、続けてホストごとの見積もりを出力する
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `savings` - ホストごとの見積もり
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_compression_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    savings: &[HostSavings],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("ホスト", 40.0),
+        ("レスポンス数", 14.0),
+        ("元のサイズ(bytes)", 18.0),
+        ("gzip後のサイズ(bytes)", 20.0),
+        ("gzipで削減できるサイズ(bytes)", 26.0),
+        ("brotli後のサイズ(bytes)", 22.0),
+        ("brotliで削減できるサイズ(bytes)", 28.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    let total = HostSavings {
+        host: "（全体）".to_string(),
+        ..compression::total(savings)
+    };
+    for (index, host) in std::iter::once(&total).chain(savings).enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_string_with_format(row, 0, &host.host, &styles.cell)?;
+        worksheet.write_number_with_format(row, 1, host.responses as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 2, host.original_bytes as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 3, host.gzip_bytes as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 4, host.gzip_savings() as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 5, host.brotli_bytes as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 6, host.brotli_savings() as f64, &styles.cell)?;
+    }
+    worksheet.autofilter(0, 0, savings.len() as u32 + 1, headers.len() as u16 - 1)?;
+    Ok(())
+}
+
+/// Server-Timingヘッダーの指標を待機時間と並べてシートに出力
+/// 
+/// 指標は名前ごとに列を分け、最初に現れた順に待機時間・サーバーの処理時間・差の右に並べる
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `entries` - Server-Timingヘッダーを記録したエントリ
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_server_timing_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    entries: &[ServerTimingEntry],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let metric_names = server_timing::metric_names(entries);
+    let metric_headers: Vec<String> = metric_names.iter().map(|name| format!("{}(ms)", name)).collect();
+    let mut headers = vec![
+        ("エントリ番号", 12.0),
+        ("メソッド", 10.0),
+        ("リクエストURL", 60.0),
+        ("待機時間(ms)", 14.0),
+        ("サーバーの処理時間(ms)", 22.0),
+        ("差(ms)", 12.0),
+    ];
+    let fixed_columns = headers.len() as u16;
+    headers.extend(metric_headers.iter().map(|header| (header.as_str(), 14.0)));
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+    let milliseconds = styles.cell.clone().set_num_format("#,##0.000");
+
+    for (index, entry) in entries.iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_number_with_format(row, 0, entry.entry_number as f64, &styles.cell)?;
+        worksheet.write_string_with_format(row, 1, &entry.method, &styles.cell)?;
+        worksheet.write_string_with_format(row, 2, &entry.url, &styles.cell)?;
+        let values = [Some(entry.wait), entry.server_time(), entry.overhead()]
+            .into_iter()
+            .chain(metric_names.iter().map(|name| entry.duration(name)));
+        for (col, value) in (3..).zip(values) {
+            match value {
+                Some(value) => worksheet.write_number_with_format(row, col, value, &milliseconds)?,
+                None => worksheet.write_string_with_format(row, col, "", &styles.cell)?,
+            };
+        }
+    }
+    worksheet.autofilter(0, 0, entries.len() as u32, fixed_columns + metric_names.len() as u16 - 1)?;
+    Ok(())
+}
+
+/// URLごとの条件付きリクエストの集計をシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `stats` - URLごとの条件付きリクエストの集計
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_conditional_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    stats: &[ConditionalStats],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("URL", 60.0),
+        ("リクエスト数", 14.0),
+        ("検証子を返したレスポンス数", 26.0),
+        ("条件付きリクエスト数", 20.0),
+        ("304", 10.0),
+        ("200", 10.0),
+        ("検証子が一致したのに200", 24.0),
+        ("304の割合", 12.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+    let percent = styles.cell.clone().set_num_format("0.0%");
+
+    let total = ConditionalStats {
+        url: "（全体）".to_string(),
+        ..conditional::total(stats)
+    };
+    for (index, url) in std::iter::once(&total).chain(stats).enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_string_with_format(row, 0, &url.url, &styles.cell)?;
+        worksheet.write_number_with_format(row, 1, url.requests as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 2, url.validated_responses as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 3, url.conditional as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 4, url.not_modified as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 5, url.full as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 6, url.ignored as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 7, url.not_modified_rate(), &percent)?;
+    }
+    worksheet.autofilter(0, 0, stats.len() as u32 + 1, headers.len() as u16 - 1)?;
+    Ok(())
+}
+
+/// 接続ごとのリクエスト数・リクエストの間隔・多重化の状況をシートに出力し、時間帯ごとに開始した接続の数の集計と縦棒グラフを追加
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `usages` - 開始時刻の順の接続ごとの利用状況
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_connections_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    usages: &[ConnectionUsage],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("接続ID", 12.0),
+        ("ホスト", 40.0),
+        ("サーバーのIPアドレス", 20.0),
+        ("開始時刻", 24.0),
+        ("終了時刻", 24.0),
+        ("リクエスト数", 14.0),
+        ("接続の確立(ms)", 16.0),
+        ("平均間隔(ms)", 14.0),
+        ("最大間隔(ms)", 14.0),
+        ("エントリ番号", 30.0),
+        ("間隔(ms)", 40.0),
+        ("HTTPバージョン", 16.0),
+        ("最大同時ストリーム数", 20.0),
+        ("優先度の内訳", 40.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+    let milliseconds = styles.cell.clone().set_num_format("#,##0.000");
+
+    for (index, usage) in usages.iter().enumerate() {
+        let row = index as u32 + 1;
+        let opened = usage.opened_at.format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+        let closed = usage.closed_at.format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+        let entry_numbers = usage.entry_numbers.iter().map(|number| number.to_string()).collect::<Vec<_>>().join(", ");
+        let gaps = usage.gaps_ms.iter().map(|gap| format!("{:.1}", gap)).collect::<Vec<_>>().join(", ");
+        worksheet.write_string_with_format(row, 0, &usage.connection, &styles.cell)?;
+        worksheet.write_string_with_format(row, 1, &usage.host, &styles.cell)?;
+        worksheet.write_string_with_format(row, 2, &usage.server_ip_address, &styles.cell)?;
+        worksheet.write_string_with_format(row, 3, &opened, &styles.cell)?;
+        worksheet.write_string_with_format(row, 4, &closed, &styles.cell)?;
+        worksheet.write_number_with_format(row, 5, usage.requests() as f64, &styles.cell)?;
+        for (col, value) in [(6, usage.connect_ms), (7, usage.average_gap_ms()), (8, usage.max_gap_ms())] {
+            match value {
+                Some(value) => worksheet.write_number_with_format(row, col, value, &milliseconds)?,
+                None => worksheet.write_string_with_format(row, col, "", &styles.cell)?,
+            };
+        }
+        worksheet.write_string_with_format(row, 9, &entry_numbers, &styles.cell)?;
+        worksheet.write_string_with_format(row, 10, &gaps, &styles.cell)?;
+        worksheet.write_string_with_format(row, 11, &usage.http_version, &styles.cell)?;
+        worksheet.write_number_with_format(row, 12, usage.max_concurrent as f64, &styles.cell)?;
+        worksheet.write_string_with_format(row, 13, usage.priorities_text(), &styles.cell)?;
+    }
+    worksheet.autofilter(0, 0, usages.len() as u32, headers.len() as u16 - 1)?;
+
+    // 時間帯ごとに開始した接続の数は接続の一覧の右に出力する
+    let timeline = connections::opened_timeline(usages, options.bucket_interval_ms);
+    if timeline.is_empty() {
+        return Ok(());
+    }
+    let time_col = headers.len() as u16 + 1;
+    let count_col = time_col + 1;
+    for (col, (header, width)) in [(time_col, ("時間帯の開始時刻", 24.0)), (count_col, ("開始した接続数", 16.0))] {
+        worksheet.write_string_with_format(0, col, header, &styles.header)?;
+        worksheet.set_column_width(col, width)?;
+    }
+    for (index, (start, opened)) in timeline.iter().enumerate() {
+        let row = index as u32 + 1;
+        let started = start.format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+        worksheet.write_string_with_format(row, time_col, &started, &styles.cell)?;
+        worksheet.write_number_with_format(row, count_col, *opened as f64, &styles.cell)?;
+    }
+
+    let last_row = timeline.len() as u32;
+    let mut chart = Chart::new(ChartType::Column);
+    chart
+        .add_series()
+        .set_name((sheet_name, 0, count_col))
+        .set_categories((sheet_name, 1, time_col, last_row, time_col))
+        .set_values((sheet_name, 1, count_col, last_row, count_col));
+    chart
+        .title()
+        .set_name(&format!("時間帯ごとに開始した接続数（{}秒間隔）", options.bucket_interval_ms.max(1) as f64 / 1000.0));
+    chart.y_axis().set_name("接続数");
+    chart.set_width(960).set_height(360);
+    worksheet.insert_chart(1, count_col + 1, &chart)?;
+    Ok(())
+}
+
+/// ホストごとに推定したCDN・プロキシ・バックエンドをシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `hosts` - ホストごとの基盤の情報
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_infrastructure_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    hosts: &[HostInfrastructure],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("ホスト", 40.0),
+        ("レスポンス数", 14.0),
+        ("CDN", 24.0),
+        ("CDNの根拠のヘッダー", 28.0),
+        ("Server", 30.0),
+        ("X-Powered-By", 24.0),
+        ("Via", 40.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    let join = |values: Vec<&str>| values.join("\n");
+    for (index, host) in hosts.iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_string_with_format(row, 0, &host.host, &styles.cell)?;
+        worksheet.write_number_with_format(row, 1, host.responses as f64, &styles.cell)?;
+        worksheet.write_string_with_format(row, 2, join(host.cdns.iter().copied().collect()), &styles.cell)?;
+        worksheet.write_string_with_format(row, 3, join(host.evidence.iter().copied().collect()), &styles.cell)?;
+        worksheet.write_string_with_format(row, 4, join(host.servers.iter().map(String::as_str).collect()), &styles.cell)?;
+        worksheet.write_string_with_format(row, 5, join(host.powered_by.iter().map(String::as_str).collect()), &styles.cell)?;
+        worksheet.write_string_with_format(row, 6, join(host.via.iter().map(String::as_str).collect()), &styles.cell)?;
+    }
+    worksheet.autofilter(0, 0, hosts.len() as u32, headers.len() as u16 - 1)?;
+    Ok(())
+}
+
+/// リクエスト・レスポンスのヘッダーの一覧をシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `usages` - ヘッダーの使用状況
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_headers_inventory_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    usages: &[HeaderUsage],
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("方向", 12.0),
+        ("ヘッダー名", 30.0),
+        ("名前の表記", 30.0),
+        ("設定したエントリ数", 16.0),
+        ("異なる値の数", 14.0),
+        ("値の例", 80.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    for (index, usage) in usages.iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_string_with_format(row, 0, usage.direction.label(), &styles.cell)?;
+        worksheet.write_string_with_format(row, 1, &usage.name, &styles.cell)?;
+        worksheet.write_string_with_format(row, 2, usage.spellings.join("\n"), &styles.cell)?;
+        worksheet.write_number_with_format(row, 3, usage.entries as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 4, usage.distinct_values as f64, &styles.cell)?;
+        worksheet.write_string_with_format(row, 5, usage.examples.join("\n"), &styles.cell)?;
+    }
+    worksheet.autofilter(0, 0, usages.len() as u32, headers.len() as u16 - 1)?;
+    Ok(())
+}
+
+/// 同意より前に設定されたサードパーティのCookieをシートに出力
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `report` - Cookieの同意取得の監査結果
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_consent_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    report: &ConsentReport,
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("エントリ番号", 12.0),
+        ("時刻", 30.0),
+        ("ホスト", 30.0),
+        ("分類", 18.0),
+        ("Cookie名", 24.0),
+        ("Cookieのドメイン", 30.0),
+        ("有効期限", 30.0),
+        ("同意管理のリクエスト", 30.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    let consent = report
+        .consent
+        .as_ref()
+        .map_or_else(|| "見つかりません".to_string(), |consent| format!("エントリ{} {}", consent.entry_number, consent.timestamp));
+    for (index, cookie) in report.cookies.iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_number_with_format(row, 0, cookie.entry_number as f64, &styles.cell)?;
+        worksheet.write_string_with_format(row, 1, &cookie.timestamp, &styles.cell)?;
+        worksheet.write_string_with_format(row, 2, &cookie.host, &styles.cell)?;
+        worksheet.write_string_with_format(row, 3, cookie.category.label(), &styles.cell)?;
+        worksheet.write_string_with_format(row, 4, &cookie.name, &styles.cell)?;
+        worksheet.write_string_with_format(row, 5, &cookie.domain, &styles.cell)?;
+        worksheet.write_string_with_format(row, 6, &cookie.expires, &styles.cell)?;
+        worksheet.write_string_with_format(row, 7, &consent, &styles.cell)?;
+    }
+    worksheet.autofilter(0, 0, report.cookies.len() as u32, headers.len() as u16 - 1)?;
+    Ok(())
+}
+
+/// ドメインの分類ごと・ホストごとのリクエスト数と転送量をシートに出力
+/// 
+/// 先頭に分類ごとの集計、1行空けてホストごとの集計を出力する
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `totals` - 分類ごと・ホストごとの集計
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_domains_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    totals: &CategoryTotals,
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let headers = [
+        ("分類", 20.0),
+        ("ホスト", 40.0),
+        ("リクエスト数", 14.0),
+        ("転送量(bytes)", 16.0),
+    ];
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+
+    let categories = totals.categories();
+    for (index, (category, bucket)) in categories.iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_string_with_format(row, 0, category.label(), &styles.cell)?;
+        worksheet.write_string_with_format(row, 1, "（全体）", &styles.cell)?;
+        worksheet.write_number_with_format(row, 2, bucket.requests as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 3, bucket.bytes as f64, &styles.cell)?;
+    }
+
+    let host_header_row = categories.len() as u32 + 2;
+    for (col, (header, _)) in headers.iter().enumerate() {
+        worksheet.write_string_with_format(host_header_row, col as u16, *header, &styles.header)?;
+    }
+    let hosts = totals.hosts();
+    for (index, (category, host, bucket)) in hosts.iter().enumerate() {
+        let row = host_header_row + index as u32 + 1;
+        worksheet.write_string_with_format(row, 0, category.label(), &styles.cell)?;
+        worksheet.write_string_with_format(row, 1, *host, &styles.cell)?;
+        worksheet.write_number_with_format(row, 2, bucket.requests as f64, &styles.cell)?;
+        worksheet.write_number_with_format(row, 3, bucket.bytes as f64, &styles.cell)?;
+    }
+    worksheet.autofilter(host_header_row, 0, host_header_row + hosts.len() as u32, headers.len() as u16 - 1)?;
+
+    let last_row = categories.len() as u32;
+    let mut chart = Chart::new(ChartType::Pie);
+    chart
+        .add_series()
+        .set_name((sheet_name, 0, 3))
+        .set_categories((sheet_name, 1, 0, last_row, 0))
+        .set_values((sheet_name, 1, 3, last_row, 3));
+    chart.title().set_name("分類ごとの転送量");
+    worksheet.insert_chart(1, headers.len() as u16 + 1, &chart)?;
+    Ok(())
+}
+
+/// ホストごとの待機時間の分布をシートに出力
+/// 
+/// パーセンタイルの表の右に、遅いホストから`MAX_CHARTED_HOSTS`件までの棒グラフを配置する
+/// 
+/// # Arguments
+/// * `workbook` - ワークブック
+/// * `sheet_name` - シート名
+/// * `latency` - ホストごとの待機時間
+/// * `styles` - セルのフォーマット
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn write_summary_sheet(
+    workbook: &mut Workbook,
+    sheet_name: &str,
+    latency: &HostLatency,
+    styles: &ExcelStyles,
+    options: &ExportOptions,
+) -> Result<()> {
+    let percentile_headers: Vec<String> = host_latency::PERCENTILES
+        .iter()
+        .map(|p| format!("p{}(ms)", p))
+        .collect();
+    let mut headers = vec![
+        ("ホスト", 40.0),
+        ("リクエスト数", 14.0),
+        ("最小(ms)", 12.0),
+        ("平均(ms)", 12.0),
+    ];
+    headers.extend(percentile_headers.iter().map(|header| (header.as_str(), 12.0)));
+    headers.push(("最大(ms)", 12.0));
+    let worksheet = add_sheet(workbook, sheet_name, &headers, styles, options)?;
+    let milliseconds = styles.cell.clone().set_num_format("#,##0.000");
+
+    let hosts = latency.hosts();
+    for (index, stats) in hosts.iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_string_with_format(row, 0, &stats.host, &styles.cell)?;
+        worksheet.write_number_with_format(row, 1, stats.requests as f64, &styles.cell)?;
+        let values = [stats.min, stats.mean]
+            .into_iter()
+            .chain(stats.percentiles.iter().copied())
+            .chain([stats.max]);
+        for (col, value) in (2..).zip(values) {
+            worksheet.write_number_with_format(row, col, value, &milliseconds)?;
+        }
+    }
+    worksheet.autofilter(0, 0, hosts.len() as u32, headers.len() as u16 - 1)?;
+
+    // 表は90パーセンタイルの遅い順のため、先頭の行から遅いホストをグラフにする
+    let last_row = hosts.len().min(MAX_CHARTED_HOSTS) as u32;
+    let mut chart = Chart::new(ChartType::Bar);
+    for col in 4..4 + host_latency::PERCENTILES.len() as u16 {
+        chart
+            .add_series()
+            .set_name((sheet_name, 0, col))
+            .set_categories((sheet_name, 1, 0, last_row, 0))
+            .set_values((sheet_name, 1, col, last_row, col));
+    }
+    chart.title().set_name("ホストごとの待機時間の分布");
+    chart.x_axis().set_name("待機時間(ms)");
+    // 横棒グラフは下から順に描画されるため、表と同じく遅いホストを上に表示する
+    chart.y_axis().set_reverse();
+    chart.set_width(960).set_height((120 + 40 * last_row).max(360));
+    worksheet.insert_chart(1, headers.len() as u16 + 1, &chart)?;
+    Ok(())
+}
@@ -1,15 +1,291 @@
 //! Excelファイル出力処理を実装
 
-use crate::har_types::AnalysisResult;
-use anyhow::{anyhow, Result};
+use crate::budgets::BudgetViolation;
+use crate::cache_lint::CacheViolation;
+use crate::columns::{CellValue, Column};
+use crate::compression::HostSavings;
+use crate::conditional::ConditionalStats;
+use crate::connections::ConnectionUsage;
+use crate::consent::ConsentReport;
+use crate::duplicates::DuplicateGroup;
+use crate::csv_export::CsvOptions;
+use crate::egress_cost::EgressPricing;
+use crate::entry_warnings::WarningKind;
+use crate::events::{PageEvent, TrafficEvent, WebSocketFrame};
+use crate::excel_append;
+use crate::excel_events::{self, ChunkFile, FlaggedRow, WarningRow};
+use crate::excel_styles::ExcelStyles;
+use crate::header_inventory::HeaderUsage;
+use crate::host_latency::HostLatency;
+use crate::infrastructure::HostInfrastructure;
+use crate::pagination::PageSequence;
+use crate::privacy::PrivacyPolicy;
+use crate::row_grouping::RowGrouping;
+use crate::run_info::RunInfo;
+use crate::security::SecurityFinding;
+use crate::server_timing::ServerTimingEntry;
+use crate::traffic::{TrafficTimeline, MAX_BUCKETS};
+use crate::trackers::CategoryTotals;
+use crate::error::{AnalyzerError, Result};
 use log::{info, warn};
 use rust_xlsxwriter::*;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// データシートの名前
+const DATA_SHEET_NAME: &str = "Data";
+
+/// 実行情報シートの名前
+const RUN_INFO_SHEET_NAME: &str = "Run Info";
+
+/// `--chunk-rows`で分割したワークブックの一覧を出力するシートの名前
+const INDEX_SHEET_NAME: &str = "Index";
+
+/// ページの読み込みを出力するシートの名前
+const PAGES_SHEET_NAME: &str = "Pages";
+
+/// ページごとのクリティカルパスを出力するシートの名前
+const CRITICAL_PATH_SHEET_NAME: &str = "Critical Path";
+
+/// WebSocketのメッセージを出力するシートの名前
+const WEBSOCKET_SHEET_NAME: &str = "WebSocket";
+
+/// 時間帯ごとのリクエスト数と転送量を出力するシートの名前
+const TRAFFIC_SHEET_NAME: &str = "Traffic";
+
+/// ドメインの分類ごとのリクエスト数と転送量を出力するシートの名前
+const DOMAINS_SHEET_NAME: &str = "Domains";
+
+/// セキュリティの指摘事項を出力するシートの名前
+const SECURITY_FINDINGS_SHEET_NAME: &str = "Security Findings";
+
+/// ページ送りの一連のリクエストを出力するシートの名前
+const PAGINATION_SHEET_NAME: &str = "Pagination";
+
+/// キャッシュの規則の違反を出力するシートの名前
+const CACHE_LINT_SHEET_NAME: &str = "Cache Lint";
+
+/// サイズの予算の超過を出力するシートの名前
+const BUDGETS_SHEET_NAME: &str = "Budgets";
+
+/// 異なるURLで同じ内容のレスポンスを出力するシートの名前
+const DUPLICATES_SHEET_NAME: &str = "Duplicates";
+
+/// 圧縮による削減量の見積もりを出力するシートの名前
+const COMPRESSION_SHEET_NAME: &str = "Compression";
+
+/// Server-Timingヘッダーの指標を出力するシートの名前
+const SERVER_TIMING_SHEET_NAME: &str = "Server Timing";
+
+/// URLごとの条件付きリクエストの集計を出力するシートの名前
+const CONDITIONAL_SHEET_NAME: &str = "Conditional";
+
+/// 接続ごとの再利用の状況を出力するシートの名前
+const CONNECTIONS_SHEET_NAME: &str = "Connections";
+
+/// ホストごとのCDN・プロキシ・バックエンドの推定を出力するシートの名前
+const INFRASTRUCTURE_SHEET_NAME: &str = "Infrastructure";
+
+/// リクエスト・レスポンスのヘッダーの一覧を出力するシートの名前
+const HEADERS_INVENTORY_SHEET_NAME: &str = "Headers Inventory";
+
+/// 同意より前に設定されたサードパーティのCookieを出力するシートの名前
+const CONSENT_SHEET_NAME: &str = "Consent";
+
+/// 注目させるエントリの一覧を出力するシートの名前
+const FLAGGED_SHEET_NAME: &str = "Flagged";
+
+/// エントリごとの警告の一覧のシート名
+const WARNINGS_SHEET_NAME: &str = "Warnings";
+
+/// ホストごとの待機時間の分布を出力するシートの名前
+const SUMMARY_SHEET_NAME: &str = "Summary";
+
+/// `--sheets`で選択できるデータシート以外のシートの名前
+pub const AUXILIARY_SHEETS: &[&str] = &[
+    PAGES_SHEET_NAME,
+    CRITICAL_PATH_SHEET_NAME,
+    WEBSOCKET_SHEET_NAME,
+    FLAGGED_SHEET_NAME,
+    WARNINGS_SHEET_NAME,
+    SECURITY_FINDINGS_SHEET_NAME,
+    TRAFFIC_SHEET_NAME,
+    SUMMARY_SHEET_NAME,
+    PAGINATION_SHEET_NAME,
+    CACHE_LINT_SHEET_NAME,
+    BUDGETS_SHEET_NAME,
+    DUPLICATES_SHEET_NAME,
+    COMPRESSION_SHEET_NAME,
+    SERVER_TIMING_SHEET_NAME,
+    CONDITIONAL_SHEET_NAME,
+    CONNECTIONS_SHEET_NAME,
+    INFRASTRUCTURE_SHEET_NAME,
+    HEADERS_INVENTORY_SHEET_NAME,
+    CONSENT_SHEET_NAME,
+    DOMAINS_SHEET_NAME,
+    RUN_INFO_SHEET_NAME,
+];
+
+/// 1シートあたりのデータ行数の上限
+/// 
+/// Excelの最大行数1,048,576からヘッダー行と続きの注記行を除いた数
+const MAX_DATA_ROWS_PER_SHEET: u32 = 1_048_574;
+
+/// 入力規則のドロップダウンに表示するHTTPメソッド
+const HTTP_METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "CONNECT", "TRACE"];
+
+/// セルのメモの幅（ピクセル、ヘッダーの1行が折り返さずに読める程度）
+const NOTE_WIDTH: u32 = 480;
+
+/// セルのメモの高さ（ピクセル）
+const NOTE_HEIGHT: u32 = 320;
+
+/// Excel出力のオプション
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    /// 出力する列
+    pub columns: Vec<Column>,
+    /// シート保護のパスワード（空文字列の場合はパスワード無しで保護、Noneの場合は保護しない）
+    pub protect: Option<String>,
+    /// メソッド列・ステータスコード列を色分けするかどうか
+    pub colored: bool,
+    /// URL・ステータスコード・ペイロードのセルにヘッダーとペイロードの先頭のメモを付けるかどうか
+    pub cell_notes: bool,
+    /// 同じページ・ホストの行をアウトラインでグループ化する単位（Noneの場合はグループ化しない）
+    pub row_grouping: Option<RowGrouping>,
+    /// 1つのワークブックに出力する行数（Noneの場合は分割しない）
+    pub chunk_rows: Option<usize>,
+    /// 既存のワークブックに日付のシートとして追記するかどうか
+    pub append: bool,
+    /// 作成日時等の実行するたびに変わる情報を固定するかどうか
+    pub deterministic: bool,
+    /// 実行情報シートに出力する内容（Noneの場合は出力しない）
+    pub run_info: Option<RunInfo>,
+    /// Trafficシートで集計する時間帯の間隔（ミリ秒）
+    pub bucket_interval_ms: u64,
+    /// Security Findingsシートに出力する指摘事項
+    pub security_findings: Vec<SecurityFinding>,
+    /// Consentシートに出力するCookieの同意取得の監査結果
+    pub consent_report: ConsentReport,
+    /// Paginationシートに出力するページ送りの一連のリクエスト
+    pub page_sequences: Vec<PageSequence>,
+    /// Cache Lintシートに出力するキャッシュの規則の違反
+    pub cache_violations: Vec<CacheViolation>,
+    /// Budgetsシートに出力するサイズの予算の超過
+    pub budget_violations: Vec<BudgetViolation>,
+    /// Duplicatesシートに出力する異なるURLで同じ内容のレスポンス
+    pub duplicate_responses: Vec<DuplicateGroup>,
+    /// Compressionシートに出力するホストごとの圧縮による削減量の見積もり
+    pub compression_savings: Vec<HostSavings>,
+    /// Server Timingシートに出力するServer-Timingヘッダーを記録したエントリ
+    pub server_timings: Vec<ServerTimingEntry>,
+    /// Conditionalシートに出力するURLごとの条件付きリクエストの集計
+    pub conditional_requests: Vec<ConditionalStats>,
+    /// Connectionsシートに出力する接続ごとの利用状況
+    pub connections: Vec<ConnectionUsage>,
+    /// Infrastructureシートに出力するホストごとの基盤の情報
+    pub infrastructure: Vec<HostInfrastructure>,
+    /// Headers Inventoryシートに出力するヘッダーごとの値の例の最大数（Noneの場合はシートを出力しない）
+    pub header_examples: Option<usize>,
+    /// Headers Inventoryシートに出力するヘッダーの使用状況
+    pub header_inventory: Vec<HeaderUsage>,
+    /// 転送量の費用の見積もりに使用する単価（Noneの場合は見積もらない）
+    pub egress_pricing: Option<EgressPricing>,
+    /// CSV・TSV形式の出力オプション
+    pub csv: CsvOptions,
+    /// `--format template`で使用するTeraテンプレートのパス
+    pub template: Option<String>,
+    /// 出力するデータシート以外のシートの名前（Noneの場合は全て出力）
+    pub sheets: Option<Vec<String>>,
+    /// 列ごとの匿名化の設定
+    pub privacy: PrivacyPolicy,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions {
+            columns: Column::DEFAULT.to_vec(),
+            protect: None,
+            colored: true,
+            cell_notes: false,
+            row_grouping: None,
+            chunk_rows: None,
+            append: false,
+            deterministic: false,
+            run_info: None,
+            bucket_interval_ms: 1000,
+            security_findings: Vec::new(),
+            consent_report: ConsentReport::default(),
+            page_sequences: Vec::new(),
+            cache_violations: Vec::new(),
+            budget_violations: Vec::new(),
+            duplicate_responses: Vec::new(),
+            compression_savings: Vec::new(),
+            server_timings: Vec::new(),
+            conditional_requests: Vec::new(),
+            connections: Vec::new(),
+            infrastructure: Vec::new(),
+            header_examples: None,
+            header_inventory: Vec::new(),
+            egress_pricing: None,
+            csv: CsvOptions::default(),
+            template: None,
+            sheets: None,
+            privacy: PrivacyPolicy::default(),
+        }
+    }
+}
+
+impl ExportOptions {
+    /// データシート以外のシートを出力するかどうか
+    /// 
+    /// # Arguments
+    /// * `name` - シートの名前（追記時の日付を除く）
+    /// 
+    /// # Returns
+    /// * `bool` - 出力する場合はtrue
+    pub fn includes_sheet(&self, name: &str) -> bool {
+        self.sheets
+            .as_ref()
+            .is_none_or(|sheets| sheets.iter().any(|sheet| sheet == name))
+    }
+
+    /// 同時に指定できない出力オプションの組み合わせを確認
+    /// 
+    /// # Arguments
+    /// * `streaming` - 解析結果を保持せずに出力するかどうか（`--streaming`）
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 組み合わせが有効な場合はOk、無効な場合はエラー
+    pub fn validate(&self, streaming: bool) -> Result<()> {
+        if self.deterministic && self.append {
+            return Err(AnalyzerError::InvalidArgument(
+                "--deterministicは--appendと同時に指定できません（追記するシートの名前が日付になるため）".to_string(),
+            ));
+        }
+        if self.chunk_rows.is_some() && (streaming || self.append) {
+            return Err(AnalyzerError::InvalidArgument(
+                "--chunk-rowsは--streaming・--appendと同時に指定できません".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
 
 /// Excelエクスポータ
 pub struct ExcelExporter;
 
+/// Excelファイルの出力結果
+#[derive(Debug, Default)]
+pub struct ExportedWorkbook {
+    /// 出力した行数
+    pub rows: usize,
+    /// ワークブックとは別に作成したファイル（外部ファイルに退避したペイロード・分割したワークブック）のパス
+    pub sidecars: Vec<PathBuf>,
+}
+
 impl ExcelExporter {
     /// 行番号と列番号をExcel形式のセル参照に変換
     /// 
@@ -32,25 +308,209 @@ impl ExcelExporter {
         format!("{}{}", column_name, row)
     }
 
-    /// 解析結果をExcelファイルに出力
+    /// 通信イベントをExcelファイルに出力
+    /// 
+    /// HTTPの通信はDataシートに、ページの読み込みとWebSocketのメッセージはそれぞれ専用のシートに出力する
     /// 
     /// # Arguments
-    /// * `results` - 解析結果のリスト
+    /// * `events` - 通信イベントのリスト
     /// * `output_path` - 出力ファイルのパス
+    /// * `options` - 出力オプション
     /// 
     /// # Returns
-    /// * `Result<()>` - 成功時はOk、失敗時はエラー
-    pub fn export(results: &[AnalysisResult], output_path: &str) -> Result<()> {
+    /// * `Result<ExportedWorkbook>` - 成功時は出力した行数と作成したファイル、失敗時はエラー
+    pub fn export(events: &[TrafficEvent], output_path: &str, options: &ExportOptions) -> Result<ExportedWorkbook> {
+        // グループ化する場合は同じページ・ホストの行が連続するように並べ替える
+        let events: Vec<&TrafficEvent> = match options.row_grouping {
+            Some(grouping) => grouping.order(events),
+            None => events.iter().collect(),
+        };
+        match options.chunk_rows {
+            Some(chunk_rows) => Self::export_chunks(&events, output_path, options, chunk_rows),
+            None => Self::write_workbook(events.into_iter().map(Ok), output_path, options, false),
+        }
+    }
+
+    /// 通信イベントを行数ごとに番号付きのワークブックに分けて出力
+    /// 
+    /// 出力先のパスには分割したワークブックへのリンクの一覧（Indexシート）と
+    /// HAR全体を集計したシートを出力し、分割したワークブックにはデータシートと行ごとに集計するシートを出力する
+    /// 
+    /// # Arguments
+    /// * `events` - 通信イベントのリスト
+    /// * `output_path` - 一覧を出力するファイルのパス
+    /// * `options` - 出力オプション
+    /// * `chunk_rows` - 1つのワークブックに出力する行数
+    /// 
+    /// # Returns
+    /// * `Result<ExportedWorkbook>` - 成功時は出力した行数と分割したワークブックを含む作成したファイル、失敗時はエラー
+    fn export_chunks(
+        events: &[&TrafficEvent],
+        output_path: &str,
+        options: &ExportOptions,
+        chunk_rows: usize,
+    ) -> Result<ExportedWorkbook> {
+        // HTTPの通信の件数で分割し、ページ・WebSocketのイベントは直前の通信と同じワークブックに出力する
+        let mut chunks: Vec<Vec<&TrafficEvent>> = vec![Vec::new()];
+        let mut rows = 0;
+        for event in events {
+            if event.as_http().is_some() {
+                if rows == chunk_rows {
+                    chunks.push(Vec::new());
+                    rows = 0;
+                }
+                rows += 1;
+            }
+            if let Some(chunk) = chunks.last_mut() {
+                chunk.push(event);
+            }
+        }
+        info!("{}行ごとに{}個のワークブックに分割して出力します", chunk_rows, chunks.len());
+
+        // HAR全体を集計したシートは一覧のワークブックにのみ出力する
+        let chunk_options = ExportOptions {
+            chunk_rows: None,
+            run_info: None,
+            security_findings: Vec::new(),
+            consent_report: ConsentReport::default(),
+            page_sequences: Vec::new(),
+            cache_violations: Vec::new(),
+            budget_violations: Vec::new(),
+            duplicate_responses: Vec::new(),
+            compression_savings: Vec::new(),
+            server_timings: Vec::new(),
+            conditional_requests: Vec::new(),
+            connections: Vec::new(),
+            infrastructure: Vec::new(),
+            header_inventory: Vec::new(),
+            ..options.clone()
+        };
+        let mut chunk_files: Vec<ChunkFile> = Vec::new();
+        let mut sidecars: Vec<PathBuf> = Vec::new();
+        for (index, chunk) in chunks.iter().enumerate() {
+            let chunk_path = Self::chunk_path(output_path, index + 1);
+            let written = Self::write_workbook(chunk.iter().copied().map(Ok), &chunk_path, &chunk_options, false)?;
+            let rows = written.rows;
+            if rows == 0 {
+                continue;
+            }
+            sidecars.push(PathBuf::from(&chunk_path));
+            sidecars.extend(written.sidecars);
+            let first = chunk.iter().find_map(|event| event.as_http());
+            let last = chunk.iter().rev().find_map(|event| event.as_http());
+            chunk_files.push(ChunkFile {
+                file_name: Path::new(&chunk_path)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                rows,
+                first_entry_number: first.map_or(0, |result| result.entry_index + 1),
+                last_entry_number: last.map_or(0, |result| result.entry_index + 1),
+                first_timestamp: first.map(|result| result.timestamp.clone()).unwrap_or_default(),
+                last_timestamp: last.map(|result| result.timestamp.clone()).unwrap_or_default(),
+            });
+        }
+        if chunk_files.is_empty() {
+            return Ok(ExportedWorkbook::default());
+        }
+
+        let mut workbook = Workbook::new();
+        Self::apply_properties(&mut workbook, options)?;
+        let styles = ExcelStyles::new(options.colored);
+        excel_events::write_chunk_index_sheet(&mut workbook, INDEX_SHEET_NAME, &chunk_files, &styles, options)?;
+        if !options.security_findings.is_empty() && options.includes_sheet(SECURITY_FINDINGS_SHEET_NAME) {
+            excel_events::write_security_findings_sheet(
+                &mut workbook,
+                SECURITY_FINDINGS_SHEET_NAME,
+                &options.security_findings,
+                &styles,
+                options,
+            )?;
+        }
+        Self::write_report_sheets(&mut workbook, &styles, options, DATA_SHEET_NAME)?;
+        let row_count = chunk_files.iter().map(|chunk| chunk.rows).sum();
+        if let Some(run_info) = &options.run_info
+            && options.includes_sheet(RUN_INFO_SHEET_NAME)
+        {
+            Self::write_run_info(&mut workbook, RUN_INFO_SHEET_NAME, run_info, row_count, options, &styles)?;
+        }
+        workbook.save(output_path)
+            .map_err(AnalyzerError::export("Excelファイルの保存に失敗しました"))?;
+
+        info!(
+            "分割したワークブックの一覧を出力しました: {} ({}個のワークブック、{}行)",
+            output_path,
+            chunk_files.len(),
+            row_count
+        );
+        Ok(ExportedWorkbook { rows: row_count, sidecars })
+    }
+
+    /// 分割したワークブックのパスを取得
+    /// 
+    /// # Arguments
+    /// * `output_path` - 一覧を出力するファイルのパス
+    /// * `number` - ワークブックの番号（1から開始）
+    /// 
+    /// # Returns
+    /// * `String` - 番号を付けたパス（例: "har_analysis_001.xlsx"）
+    fn chunk_path(output_path: &str, number: usize) -> String {
+        let path = Path::new(output_path);
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("output");
+        path.with_file_name(format!("{}_{:03}.xlsx", stem, number))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// 通信イベントを1件ずつ受け取りながらExcelファイルに出力
+    /// 
+    /// Dataシートは定メモリモードで作成され、書き込んだ行は順次一時ファイルに書き出される
+    /// 
+    /// # Arguments
+    /// * `events` - 通信イベントのイテレータ
+    /// * `output_path` - 出力ファイルのパス
+    /// * `options` - 出力オプション
+    /// 
+    /// # Returns
+    /// * `Result<ExportedWorkbook>` - 成功時は出力した行数と作成したファイル、失敗時はエラー
+    pub fn export_streaming<I>(events: I, output_path: &str, options: &ExportOptions) -> Result<ExportedWorkbook>
+    where
+        I: Iterator<Item = Result<TrafficEvent>>,
+    {
+        Self::write_workbook(events, output_path, options, true)
+    }
+
+    /// ワークブックを作成して通信イベントを書き込み
+    /// 
+    /// # Arguments
+    /// * `events` - 通信イベントのイテレータ
+    /// * `output_path` - 出力ファイルのパス
+    /// * `options` - 出力オプション
+    /// * `constant_memory` - 定メモリモードでワークシートを作成するかどうか
+    /// 
+    /// # Returns
+    /// * `Result<ExportedWorkbook>` - 成功時は出力した行数と作成したファイル、失敗時はエラー
+    fn write_workbook<I, E>(
+        events: I,
+        output_path: &str,
+        options: &ExportOptions,
+        constant_memory: bool,
+    ) -> Result<ExportedWorkbook>
+    where
+        I: Iterator<Item = Result<E>>,
+        E: Borrow<TrafficEvent>,
+    {
         info!("Excelファイルに出力しています: {}", output_path);
         
         // 出力ディレクトリを作成（必要に応じて）
         if let Some(parent) = Path::new(output_path).parent() {
             fs::create_dir_all(parent)
-                .map_err(|e| anyhow!("出力ディレクトリの作成に失敗しました: {}", e))?;
+                .map_err(AnalyzerError::io("出力ディレクトリの作成に失敗しました"))?;
         }
 
+        let columns = options.columns.as_slice();
         let mut workbook = Workbook::new();
-        let worksheet = workbook.add_worksheet();
+        Self::apply_properties(&mut workbook, options)?;
         
         // ベースファイル名を取得（拡張子なし）
         let base_name = Path::new(output_path)
@@ -62,89 +522,598 @@ impl ExcelExporter {
             .parent()
             .unwrap_or(Path::new("."));
         
-        // ヘッダー行の設定
-        let header_format = Format::new()
-            .set_bold()
-            .set_background_color(Color::RGB(0xD3D3D3))
-            .set_border(FormatBorder::Thin);
-        
-        let headers = vec![
-            "時刻",
-            "送信元IP",
-            "送信先IP",
-            "メソッド",
-            "ステータスコード",
-            "リクエストURL",
-            "リクエストペイロード",
-            "レスポンスペイロード",
-        ];
-        
-        // ヘッダー行を書き込み
-        for (col, header) in headers.iter().enumerate() {
-            worksheet.write_string_with_format(0, col as u16, *header, &header_format)?;
-        }
-        
-        // データ行のフォーマット
-        let cell_format = Format::new()
-            .set_border(FormatBorder::Thin)
-            .set_text_wrap();
-        
-        let json_format = Format::new()
-            .set_border(FormatBorder::Thin)
-            .set_text_wrap()
-            .set_font_name("Consolas")
-            .set_font_size(9);
+        // セルのフォーマット
+        let styles = ExcelStyles::new(options.colored);
+        
+        // 追記モードでは既存のシートを引き継ぎ、日付のシートに書き込む
+        let sheet_base = if !options.append {
+            DATA_SHEET_NAME.to_string()
+        } else if Path::new(output_path).exists() {
+            let existing = excel_append::copy_existing_sheets(&mut workbook, output_path, &styles, options)?;
+            excel_append::dated_sheet_name(&existing)
+        } else {
+            excel_append::dated_sheet_name(&[])
+        };
+        
+        let mut sheet_number = 1;
+        let mut worksheet = Self::add_data_sheet(&mut workbook, &sheet_base, sheet_number, options, constant_memory)?;
+        Self::write_header(worksheet, columns, &styles.header)?;
+        if options.append {
+            worksheet.set_active(true);
+        }
+        let mut file_prefix = base_name.to_string();
+        // 外部ファイルに退避したペイロードのパス
+        let mut sidecars: Vec<PathBuf> = Vec::new();
+        // 直前の行が属するグループ（先頭の行はグループの見出しとして折りたたまない）
+        let mut group_key: Option<String> = None;
+        // 名前付き範囲を定義するためのシート名と行数
+        let mut sheet_rows: Vec<(String, u32)> = Vec::new();
         
         // データ行を書き込み
-        for (row, result) in results.iter().enumerate() {
-            let row_index = (row + 1) as u32;
+        let mut row_count = 0;
+        let mut sheet_row_count = 0;
+        // HTTPの通信以外のイベントは専用のシートに出力する
+        let mut pages: Vec<PageEvent> = Vec::new();
+        let mut frames: Vec<WebSocketFrame> = Vec::new();
+        // 注目させる行はFlaggedシートに一覧として出力する
+        let mut flagged_rows: Vec<FlaggedRow> = Vec::new();
+        // 解析・デコードの警告はWarningsシートに一覧として出力する
+        let mut warning_rows: Vec<WarningRow> = Vec::new();
+        // 時間帯ごとのリクエスト数と転送量は書き込みながら集計する
+        let mut timeline = TrafficTimeline::new(options.bucket_interval_ms);
+        let mut category_totals = CategoryTotals::default();
+        let mut host_latency = HostLatency::default();
+        for event in events {
+            let event = event?;
+            let result = match event.borrow() {
+                TrafficEvent::HttpExchange(result) => result,
+                TrafficEvent::PageEvent(page) => {
+                    pages.push(page.clone());
+                    continue;
+                }
+                TrafficEvent::WebSocketFrame(frame) => {
+                    frames.push(frame.clone());
+                    continue;
+                }
+            };
             
-            // 各列のデータを書き込み
-            worksheet.write_string_with_format(row_index, 0, &result.timestamp, &cell_format)?;
-            worksheet.write_string_with_format(row_index, 1, &result.source_ip, &cell_format)?;
-            worksheet.write_string_with_format(row_index, 2, &result.destination_ip, &cell_format)?;
-            worksheet.write_string_with_format(row_index, 3, &result.method, &cell_format)?;
-            worksheet.write_number_with_format(row_index, 4, result.status_code as f64, &cell_format)?;
+            // シートの行数上限に達した場合は次のシートに切り替える
+            if sheet_row_count == MAX_DATA_ROWS_PER_SHEET {
+                // 定メモリモードでは書き込み済みの行に戻れないため、最終行の次の行に注記する
+                let next_name = Self::data_sheet_name(&sheet_base, sheet_number + 1);
+                Self::write_continuation_note(
+                    worksheet,
+                    &styles.note,
+                    sheet_row_count + 1,
+                    0,
+                    &format!("続きはシート「{}」を参照", next_name),
+                )?;
+                Self::add_data_validations(worksheet, columns, sheet_row_count)?;
+                sheet_rows.push((Self::data_sheet_name(&sheet_base, sheet_number), sheet_row_count));
+                
+                sheet_number += 1;
+                sheet_row_count = 0;
+                file_prefix = format!("{}_Data{}", base_name, sheet_number);
+                
+                warn!("行数がシートの上限に達したためシート「{}」に切り替えます", next_name);
+                worksheet = Self::add_data_sheet(&mut workbook, &sheet_base, sheet_number, options, constant_memory)?;
+                Self::write_header(worksheet, columns, &styles.header)?;
+                group_key = None;
+                Self::write_continuation_note(
+                    worksheet,
+                    &styles.note,
+                    0,
+                    columns.len() as u16,
+                    &format!("シート「{}」からの続き", Self::data_sheet_name(&sheet_base, sheet_number - 1)),
+                )?;
+            }
             
-            // URLの処理（長い場合は切り詰め）
-            let url_content = Self::handle_large_content(
-                &result.request_url, 
-                base_name, 
-                output_dir, 
-                row_index + 1, // ヘッダー行を考慮
-                5
-            )?;
-            worksheet.write_string_with_format(row_index, 5, &url_content, &cell_format)?;
+            row_count += 1;
+            sheet_row_count += 1;
+            let row_index = sheet_row_count;
+            timeline.add(result);
+            category_totals.add(result);
+            host_latency.add(result);
+            if let Some(reason) = &result.flag {
+                flagged_rows.push(FlaggedRow {
+                    sheet_name: Self::data_sheet_name(&sheet_base, sheet_number),
+                    row: row_index,
+                    entry_number: result.entry_index + 1,
+                    timestamp: result.timestamp.clone(),
+                    method: result.method.clone(),
+                    status_code: result.status_code,
+                    url: result.request_url.clone(),
+                    reason: reason.clone(),
+                });
+            }
+            for warning in result.warnings.items() {
+                warning_rows.push(WarningRow {
+                    sheet_name: Self::data_sheet_name(&sheet_base, sheet_number),
+                    row: row_index,
+                    entry_number: result.entry_index + 1,
+                    url: result.request_url.clone(),
+                    kind: warning.kind,
+                    message: warning.message.clone(),
+                });
+            }
             
-            // リクエストペイロードの処理
-            let request_content = Self::handle_large_content(
-                &result.request_payload, 
-                base_name, 
-                output_dir, 
-                row_index + 1, // ヘッダー行を考慮
-                6
-            )?;
-            worksheet.write_string_with_format(row_index, 6, &request_content, &json_format)?;
+            // 定メモリモードでは書き込んだ行に戻れないため、セルより先に行のグループ化を設定する
+            if let Some(grouping) = options.row_grouping {
+                let key = grouping.key(result);
+                if key.is_some() && key == group_key.as_deref() {
+                    worksheet.group_rows(row_index, row_index)?;
+                } else {
+                    group_key = key.map(str::to_string);
+                }
+            }
             
-            // レスポンスペイロードの処理
-            let response_content = Self::handle_large_content(
-                &result.response_payload, 
-                base_name, 
-                output_dir, 
-                row_index + 1, // ヘッダー行を考慮
-                7
-            )?;
-            worksheet.write_string_with_format(row_index, 7, &response_content, &json_format)?;
+            // 各列のデータを書き込み
+            for (col, column) in columns.iter().enumerate() {
+                let col = col as u16;
+                let format = styles.data_format(*column, result);
+                
+                let mut buffer = String::new();
+                match options.privacy.value(*column, result, &mut buffer) {
+                    CellValue::Number(value) | CellValue::DateTime(value) => {
+                        worksheet.write_number_with_format(row_index, col, value, format)?;
+                    }
+                    CellValue::Text(text) if column.is_large() => {
+                        // 長い場合は外部ファイルに退避
+                        let content = Self::handle_large_content(
+                            text,
+                            &file_prefix,
+                            output_dir,
+                            row_index + 1, // ヘッダー行を考慮
+                            col,
+                            &mut sidecars,
+                        )?;
+                        worksheet.write_string_with_format(row_index, col, &content, format)?;
+                    }
+                    CellValue::Text(text) => {
+                        worksheet.write_string_with_format(row_index, col, text, format)?;
+                    }
+                }
+                // メモには匿名化していないヘッダー・ペイロードが含まれるため、匿名化する場合は付けない
+                if options.cell_notes
+                    && !options.privacy.is_active()
+                    && let Some(note) = column.note(result)
+                {
+                    let note = Note::new(note)
+                        .add_author_prefix(false)
+                        .set_width(NOTE_WIDTH)
+                        .set_height(NOTE_HEIGHT);
+                    worksheet.insert_note(row_index, col, &note)?;
+                }
+            }
+        }
+        
+        if row_count == 0 {
+            info!("出力対象の行が無いためExcelファイルを作成しませんでした");
+            return Ok(ExportedWorkbook { rows: 0, sidecars });
+        }
+        
+        Self::add_data_validations(worksheet, columns, sheet_row_count)?;
+        sheet_rows.push((Self::data_sheet_name(&sheet_base, sheet_number), sheet_row_count));
+        Self::define_column_names(&mut workbook, columns, &sheet_rows)?;
+        
+        // イベントの種類ごとのシートを出力
+        if !pages.is_empty() && options.includes_sheet(PAGES_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(PAGES_SHEET_NAME, options, &sheet_base);
+            excel_events::write_pages_sheet(&mut workbook, &sheet_name, &pages, &styles, options)?;
+        }
+        if pages.iter().any(|page| !page.critical_path.is_empty()) && options.includes_sheet(CRITICAL_PATH_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(CRITICAL_PATH_SHEET_NAME, options, &sheet_base);
+            excel_events::write_critical_path_sheet(&mut workbook, &sheet_name, &pages, &styles, options)?;
+        }
+        if !frames.is_empty() && options.includes_sheet(WEBSOCKET_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(WEBSOCKET_SHEET_NAME, options, &sheet_base);
+            let prefix = format!("{}_WebSocket", base_name);
+            sidecars.extend(excel_events::write_websocket_sheet(
+                &mut workbook,
+                &sheet_name,
+                &frames,
+                &styles,
+                options,
+                &prefix,
+                output_dir,
+            )?);
+        }
+        
+        if !flagged_rows.is_empty() && options.includes_sheet(FLAGGED_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(FLAGGED_SHEET_NAME, options, &sheet_base);
+            excel_events::write_flagged_sheet(&mut workbook, &sheet_name, &flagged_rows, &styles, options)?;
+        }
+        
+        if !warning_rows.is_empty() && options.includes_sheet(WARNINGS_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(WARNINGS_SHEET_NAME, options, &sheet_base);
+            excel_events::write_warnings_sheet(&mut workbook, &sheet_name, &warning_rows, &styles, options)?;
+            Self::log_warnings(&warning_rows, &sheet_name);
+        }
+        
+        if !options.security_findings.is_empty() && options.includes_sheet(SECURITY_FINDINGS_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(SECURITY_FINDINGS_SHEET_NAME, options, &sheet_base);
+            excel_events::write_security_findings_sheet(&mut workbook, &sheet_name, &options.security_findings, &styles, options)?;
+        }
+        
+        if timeline.len() > MAX_BUCKETS && options.includes_sheet(TRAFFIC_SHEET_NAME) {
+            warn!(
+                "時間帯の数が上限（{}）を超えるためTrafficシートを出力しません。時間帯の間隔を長くしてください",
+                MAX_BUCKETS
+            );
+        } else if !timeline.is_empty() && options.includes_sheet(TRAFFIC_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(TRAFFIC_SHEET_NAME, options, &sheet_base);
+            excel_events::write_traffic_sheet(&mut workbook, &sheet_name, &timeline, &styles, options)?;
+        }
+        
+        if !host_latency.is_empty() && options.includes_sheet(SUMMARY_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(SUMMARY_SHEET_NAME, options, &sheet_base);
+            excel_events::write_summary_sheet(&mut workbook, &sheet_name, &host_latency, &styles, options)?;
+        }
+        
+        Self::write_report_sheets(&mut workbook, &styles, options, &sheet_base)?;
+        
+        if !category_totals.is_empty() && options.includes_sheet(DOMAINS_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(DOMAINS_SHEET_NAME, options, &sheet_base);
+            excel_events::write_domains_sheet(&mut workbook, &sheet_name, &category_totals, &styles, options)?;
         }
         
-        // 列幅の自動調整
-        Self::auto_fit_columns(worksheet, results)?;
+        // 実行情報シートを出力
+        if let Some(run_info) = &options.run_info
+            && options.includes_sheet(RUN_INFO_SHEET_NAME)
+        {
+            let sheet_name = Self::auxiliary_sheet_name(RUN_INFO_SHEET_NAME, options, &sheet_base);
+            Self::write_run_info(&mut workbook, &sheet_name, run_info, row_count, options, &styles)?;
+        }
         
         // ファイルを保存
         workbook.save(output_path)
-            .map_err(|e| anyhow!("Excelファイルの保存に失敗しました: {}", e))?;
+            .map_err(AnalyzerError::export("Excelファイルの保存に失敗しました"))?;
+        
+        info!("Excelファイルの出力が完了しました: {} ({}行)", output_path, row_count);
+        Ok(ExportedWorkbook { rows: row_count, sidecars })
+    }
+
+    /// HAR全体を集計した結果のシートを出力
+    /// 
+    /// # Arguments
+    /// * `workbook` - ワークブック
+    /// * `styles` - セルのフォーマット
+    /// * `options` - 出力オプション
+    /// * `sheet_base` - 1枚目のデータシートの名前
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    fn write_report_sheets(
+        workbook: &mut Workbook,
+        styles: &ExcelStyles,
+        options: &ExportOptions,
+        sheet_base: &str,
+    ) -> Result<()> {
+        if !options.page_sequences.is_empty() && options.includes_sheet(PAGINATION_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(PAGINATION_SHEET_NAME, options, sheet_base);
+            excel_events::write_pagination_sheet(workbook, &sheet_name, &options.page_sequences, styles, options)?;
+        }
+        
+        if !options.cache_violations.is_empty() && options.includes_sheet(CACHE_LINT_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(CACHE_LINT_SHEET_NAME, options, sheet_base);
+            excel_events::write_cache_lint_sheet(workbook, &sheet_name, &options.cache_violations, styles, options)?;
+        }
+        
+        if !options.budget_violations.is_empty() && options.includes_sheet(BUDGETS_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(BUDGETS_SHEET_NAME, options, sheet_base);
+            excel_events::write_budgets_sheet(workbook, &sheet_name, &options.budget_violations, styles, options)?;
+        }
+        
+        if !options.duplicate_responses.is_empty() && options.includes_sheet(DUPLICATES_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(DUPLICATES_SHEET_NAME, options, sheet_base);
+            excel_events::write_duplicates_sheet(workbook, &sheet_name, &options.duplicate_responses, styles, options)?;
+        }
         
-        info!("Excelファイルの出力が完了しました: {}", output_path);
+        if !options.compression_savings.is_empty() && options.includes_sheet(COMPRESSION_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(COMPRESSION_SHEET_NAME, options, sheet_base);
+            excel_events::write_compression_sheet(workbook, &sheet_name, &options.compression_savings, styles, options)?;
+        }
+        
+        if !options.server_timings.is_empty() && options.includes_sheet(SERVER_TIMING_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(SERVER_TIMING_SHEET_NAME, options, sheet_base);
+            excel_events::write_server_timing_sheet(workbook, &sheet_name, &options.server_timings, styles, options)?;
+        }
+        
+        if !options.conditional_requests.is_empty() && options.includes_sheet(CONDITIONAL_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(CONDITIONAL_SHEET_NAME, options, sheet_base);
+            excel_events::write_conditional_sheet(workbook, &sheet_name, &options.conditional_requests, styles, options)?;
+        }
+        
+        if !options.connections.is_empty() && options.includes_sheet(CONNECTIONS_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(CONNECTIONS_SHEET_NAME, options, sheet_base);
+            excel_events::write_connections_sheet(workbook, &sheet_name, &options.connections, styles, options)?;
+        }
+        
+        if !options.infrastructure.is_empty() && options.includes_sheet(INFRASTRUCTURE_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(INFRASTRUCTURE_SHEET_NAME, options, sheet_base);
+            excel_events::write_infrastructure_sheet(workbook, &sheet_name, &options.infrastructure, styles, options)?;
+        }
+        
+        if !options.header_inventory.is_empty() && options.includes_sheet(HEADERS_INVENTORY_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(HEADERS_INVENTORY_SHEET_NAME, options, sheet_base);
+            excel_events::write_headers_inventory_sheet(workbook, &sheet_name, &options.header_inventory, styles, options)?;
+        }
+        
+        if !options.consent_report.cookies.is_empty() && options.includes_sheet(CONSENT_SHEET_NAME) {
+            let sheet_name = Self::auxiliary_sheet_name(CONSENT_SHEET_NAME, options, sheet_base);
+            excel_events::write_consent_sheet(workbook, &sheet_name, &options.consent_report, styles, options)?;
+        }
+        Ok(())
+    }
+
+    /// データシートの名前を取得
+    /// 
+    /// # Arguments
+    /// * `sheet_base` - 1枚目のデータシートの名前
+    /// * `sheet_number` - シート番号（1から開始）
+    /// 
+    /// # Returns
+    /// * `String` - シート名（例: "Data", "Data (2)"）
+    fn data_sheet_name(sheet_base: &str, sheet_number: usize) -> String {
+        if sheet_number == 1 {
+            sheet_base.to_string()
+        } else {
+            format!("{} ({})", sheet_base, sheet_number)
+        }
+    }
+
+    /// データシートを追加
+    /// 
+    /// # Arguments
+    /// * `workbook` - ワークブック
+    /// * `sheet_base` - 1枚目のデータシートの名前
+    /// * `sheet_number` - シート番号（1から開始）
+    /// * `options` - 出力オプション
+    /// * `constant_memory` - 定メモリモードでワークシートを作成するかどうか
+    /// 
+    /// # Returns
+    /// * `Result<&mut Worksheet>` - 追加されたワークシート
+    fn add_data_sheet<'a>(
+        workbook: &'a mut Workbook,
+        sheet_base: &str,
+        sheet_number: usize,
+        options: &ExportOptions,
+        constant_memory: bool,
+    ) -> Result<&'a mut Worksheet> {
+        let worksheet = if constant_memory {
+            workbook.add_worksheet_with_constant_memory()
+        } else {
+            workbook.add_worksheet()
+        };
+        worksheet.set_name(Self::data_sheet_name(sheet_base, sheet_number))?;
+        if options.row_grouping.is_some() {
+            // グループの先頭の行を見出しとし、折りたたみのボタンを見出しの行に表示する
+            worksheet.group_symbols_above(true);
+        }
+        Self::apply_protection(worksheet, options);
+        Ok(worksheet)
+    }
+
+    /// ワークブックの文書のプロパティを設定
+    /// 
+    /// `--deterministic`の場合は作成日時を固定し、同じ入力から同じ内容のファイルを出力する
+    /// 
+    /// # Arguments
+    /// * `workbook` - ワークブック
+    /// * `options` - 出力オプション
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    fn apply_properties(workbook: &mut Workbook, options: &ExportOptions) -> Result<()> {
+        if options.deterministic {
+            let created = ExcelDateTime::from_ymd(1980, 1, 1)?;
+            workbook.set_properties(&DocProperties::new().set_creation_datetime(&created));
+        }
+        Ok(())
+    }
+
+    /// シート保護を設定
+    /// 
+    /// 保護後も列幅・行の高さの変更、セルの選択、オートフィルタは許可する
+    /// 
+    /// # Arguments
+    /// * `worksheet` - ワークシート
+    /// * `options` - 出力オプション
+    pub(crate) fn apply_protection(worksheet: &mut Worksheet, options: &ExportOptions) {
+        let Some(password) = &options.protect else {
+            return;
+        };
+        
+        let protection_options = ProtectionOptions {
+            format_columns: true,
+            format_rows: true,
+            use_autofilter: true,
+            ..ProtectionOptions::default()
+        };
+        
+        if !password.is_empty() {
+            worksheet.protect_with_password(password);
+        }
+        worksheet.protect_with_options(&protection_options);
+    }
+
+    /// ヘッダー行を書き込み、列幅と行の高さを調整
+    /// 
+    /// # Arguments
+    /// * `worksheet` - ワークシート
+    /// * `columns` - 出力する列
+    /// * `header_format` - ヘッダー行のフォーマット
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    fn write_header(worksheet: &mut Worksheet, columns: &[Column], header_format: &Format) -> Result<()> {
+        for (col, column) in columns.iter().enumerate() {
+            worksheet.write_string_with_format(0, col as u16, column.header(), header_format)?;
+        }
+        
+        // 列幅と行の高さの調整
+        Self::auto_fit_columns(worksheet, columns)
+    }
+
+    /// 入力規則を設定（メソッド列はドロップダウン、ステータスコード列は範囲）
+    /// 
+    /// # Arguments
+    /// * `worksheet` - ワークシート
+    /// * `columns` - 出力する列
+    /// * `rows` - データ行数
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    fn add_data_validations(worksheet: &mut Worksheet, columns: &[Column], rows: u32) -> Result<()> {
+        if rows == 0 {
+            return Ok(());
+        }
+        
+        for (col, column) in columns.iter().enumerate() {
+            let col = col as u16;
+            let validation = match column {
+                Column::Method => DataValidation::new().allow_list_strings(HTTP_METHODS)?,
+                Column::StatusCode => DataValidation::new()
+                    .allow_whole_number(DataValidationRule::Between(100, 599)),
+                _ => continue,
+            };
+            worksheet.add_data_validation(1, col, rows, col, &validation)?;
+        }
+        Ok(())
+    }
+
+    /// 列ごとの名前付き範囲を定義
+    /// 
+    /// 最初のデータシートはブック全体の名前（例: har_status）、
+    /// 2枚目以降のシートはシート内の名前（例: 'Data (2)'!har_status）として定義する
+    /// 
+    /// # Arguments
+    /// * `workbook` - ワークブック
+    /// * `columns` - 出力する列
+    /// * `sheet_rows` - データシートの名前と行数のリスト
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    fn define_column_names(workbook: &mut Workbook, columns: &[Column], sheet_rows: &[(String, u32)]) -> Result<()> {
+        for (index, (sheet_name, rows)) in sheet_rows.iter().enumerate() {
+            if *rows == 0 {
+                continue;
+            }
+            
+            let quoted_sheet_name = format!("'{}'", sheet_name);
+            for (col, column) in columns.iter().enumerate() {
+                let col_name = column_number_to_name(col as u16);
+                let formula = format!(
+                    "={}!${}$2:${}${}",
+                    quoted_sheet_name, col_name, col_name, rows + 1
+                );
+                let name = if index == 0 {
+                    column.range_name()
+                } else {
+                    format!("{}!{}", quoted_sheet_name, column.range_name())
+                };
+                workbook.define_name(name, &formula)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 警告の件数を種類ごとにログに出力
+    /// 
+    /// # Arguments
+    /// * `rows` - 警告の行のリスト
+    /// * `sheet_name` - 警告の一覧を出力したシート名
+    fn log_warnings(rows: &[WarningRow], sheet_name: &str) {
+        let entries: HashSet<usize> = rows.iter().map(|row| row.entry_number).collect();
+        warn!("{}件のエントリに{}件の警告があります（シート「{}」を参照）", entries.len(), rows.len(), sheet_name);
+        let mut counts: BTreeMap<WarningKind, usize> = BTreeMap::new();
+        for row in rows {
+            *counts.entry(row.kind).or_default() += 1;
+        }
+        for (kind, count) in counts {
+            warn!("  - {}: {}件", kind.label(), count);
+        }
+    }
+
+    /// データシート以外のシートの名前を取得
+    /// 
+    /// 追記モードでは既存のシートと重複しないよう、追記先のシート名を付加する
+    /// 
+    /// # Arguments
+    /// * `name` - シートの名前
+    /// * `options` - 出力オプション
+    /// * `sheet_base` - 1枚目のデータシートの名前
+    /// 
+    /// # Returns
+    /// * `String` - シート名（例: "Run Info", "Run Info 2024-05-01"）
+    fn auxiliary_sheet_name(name: &str, options: &ExportOptions, sheet_base: &str) -> String {
+        if options.append {
+            // シート名の上限を超える場合はシートの種類の名前を切り詰める
+            let length = excel_append::MAX_SHEET_NAME_LENGTH.saturating_sub(sheet_base.chars().count() + 1);
+            let name: String = name.chars().take(length).collect();
+            format!("{} {}", name, sheet_base)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// 実行情報シートを書き込み
+    /// 
+    /// # Arguments
+    /// * `workbook` - ワークブック
+    /// * `sheet_name` - シート名
+    /// * `run_info` - 実行情報
+    /// * `row_count` - 出力した行数
+    /// * `options` - 出力オプション
+    /// * `styles` - セルのフォーマット
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    fn write_run_info(
+        workbook: &mut Workbook,
+        sheet_name: &str,
+        run_info: &RunInfo,
+        row_count: usize,
+        options: &ExportOptions,
+        styles: &ExcelStyles,
+    ) -> Result<()> {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(sheet_name)?;
+        Self::apply_protection(worksheet, options);
+        
+        worksheet.write_string_with_format(0, 0, "項目", &styles.header)?;
+        worksheet.write_string_with_format(0, 1, "値", &styles.header)?;
+        worksheet.set_column_width(0, 28)?;
+        worksheet.set_column_width(1, 80)?;
+        
+        let columns: Vec<&str> = options.columns.iter().map(|c| c.key()).collect();
+        for (index, (label, value)) in run_info.rows(row_count, &columns).iter().enumerate() {
+            let row = index as u32 + 1;
+            worksheet.write_string_with_format(row, 0, *label, &styles.cell)?;
+            worksheet.write_string_with_format(row, 1, value, &styles.cell)?;
+        }
+        Ok(())
+    }
+
+    /// シートの続きに関する注記を書き込み
+    /// 
+    /// # Arguments
+    /// * `worksheet` - ワークシート
+    /// * `note_format` - 注記のフォーマット
+    /// * `row` - 行番号（0から開始）
+    /// * `col` - 列番号（0から開始）
+    /// * `note` - 注記
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    fn write_continuation_note(
+        worksheet: &mut Worksheet,
+        note_format: &Format,
+        row: u32,
+        col: u16,
+        note: &str,
+    ) -> Result<()> {
+        worksheet.write_string_with_format(row, col, note, note_format)?;
         Ok(())
     }
 
@@ -156,15 +1125,17 @@ impl ExcelExporter {
     /// * `output_dir` - 出力ディレクトリ
     /// * `row` - 行番号
     /// * `col` - 列番号
+    /// * `sidecars` - 外部ファイルに保存した場合にパスを追加するリスト
     /// 
     /// # Returns
     /// * `Result<String>` - セルに入れる文字列
-    fn handle_large_content(
+    pub(crate) fn handle_large_content(
         content: &str, 
         base_name: &str, 
         output_dir: &Path, 
         row: u32, 
-        col: u16
+        col: u16,
+        sidecars: &mut Vec<PathBuf>,
     ) -> Result<String> {
         const EXCEL_LIMIT: usize = 32000; // 安全マージンを考慮
         
@@ -177,7 +1148,8 @@ impl ExcelExporter {
             let filepath = output_dir.join(&filename);
             
             fs::write(&filepath, content)
-                .map_err(|e| anyhow!("外部ファイルの書き込みに失敗しました: {}", e))?;
+                .map_err(AnalyzerError::io("外部ファイルの書き込みに失敗しました"))?;
+            sidecars.push(filepath);
             
             warn!("大きなコンテンツを外部ファイルに保存しました: {}", filename);
             
@@ -186,40 +1158,80 @@ impl ExcelExporter {
         }
     }
 
-    /// 列幅を自動調整
+    /// 列幅と行の高さを自動調整
     /// 
     /// # Arguments
     /// * `worksheet` - ワークシート
-    /// * `results` - 解析結果のリスト
+    /// * `columns` - 出力する列
     /// 
     /// # Returns
     /// * `Result<()>` - 成功時はOk、失敗時はエラー
-    fn auto_fit_columns(
-        worksheet: &mut Worksheet,
-        results: &[AnalysisResult],
-    ) -> Result<()> {
-        // 各列の適切な幅を計算
-        let column_widths = vec![
-            20.0, // 時刻
-            15.0, // 送信元IP
-            20.0, // 送信先IP
-            10.0, // メソッド
-            15.0, // ステータスコード
-            50.0, // リクエストURL
-            30.0, // リクエストペイロード
-            30.0, // レスポンスペイロード
-        ];
-        
+    fn auto_fit_columns(worksheet: &mut Worksheet, columns: &[Column]) -> Result<()> {
         // 列幅を設定
-        for (col, width) in column_widths.iter().enumerate() {
-            worksheet.set_column_width(col as u16, *width)?;
+        for (col, column) in columns.iter().enumerate() {
+            worksheet.set_column_width(col as u16, column.width())?;
         }
         
         // 行の高さを設定（JSONペイロードの表示のため）
-        for row in 1..=results.len() {
-            worksheet.set_row_height(row as u32, 60.0)?;
+        // 定メモリモードでは書き込み済みの行を変更できないため、既定の行の高さとして設定し、
+        // ヘッダー行のみ標準の高さに戻す
+        if columns.iter().any(|c| c.is_payload()) {
+            worksheet.set_default_row_height(60.0);
+            worksheet.set_row_height(0, 15.0)?;
         }
         
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_rejects_conflicting_options() {
+        assert!(ExportOptions::default().validate(true).is_ok());
+
+        let deterministic_append = ExportOptions { deterministic: true, append: true, ..ExportOptions::default() };
+        assert!(deterministic_append.validate(false).is_err());
+
+        let chunks = ExportOptions { chunk_rows: Some(100), ..ExportOptions::default() };
+        assert!(chunks.validate(false).is_ok());
+        assert!(chunks.validate(true).is_err());
+        assert!(ExportOptions { append: true, ..chunks }.validate(false).is_err());
+    }
+
+    #[test]
+    fn data_sheet_name_numbers_continuation_sheets() {
+        assert_eq!(ExcelExporter::data_sheet_name("Data", 1), "Data");
+        assert_eq!(ExcelExporter::data_sheet_name("2026-10-16_2", 3), "2026-10-16_2 (3)");
+    }
+
+    #[test]
+    fn auxiliary_sheet_name_appends_sheet_base_only_in_append_mode() {
+        let options = ExportOptions::default();
+        assert_eq!(ExcelExporter::auxiliary_sheet_name(RUN_INFO_SHEET_NAME, &options, "Data"), "Run Info");
+
+        let options = ExportOptions { append: true, ..ExportOptions::default() };
+        assert_eq!(
+            ExcelExporter::auxiliary_sheet_name(RUN_INFO_SHEET_NAME, &options, "2026-10-16"),
+            "Run Info 2026-10-16"
+        );
+    }
+
+    #[test]
+    fn auxiliary_sheet_name_fits_excel_limit() {
+        let options = ExportOptions { append: true, ..ExportOptions::default() };
+        for sheet_base in ["2026-10-16", "2026-10-16_2", "2026-10-16_100"] {
+            for name in AUXILIARY_SHEETS {
+                let sheet_name = ExcelExporter::auxiliary_sheet_name(name, &options, sheet_base);
+                assert!(
+                    sheet_name.chars().count() <= excel_append::MAX_SHEET_NAME_LENGTH,
+                    "{} は31文字を超えています",
+                    sheet_name
+                );
+                assert!(sheet_name.ends_with(sheet_base));
+            }
+        }
+    }
+}
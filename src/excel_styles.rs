@@ -0,0 +1,192 @@
+//! Excel出力で使用するセルのフォーマット定義を実装
+
+use crate::columns::Column;
+use crate::har_types::AnalysisResult;
+use rust_xlsxwriter::{Color, Format, FormatBorder};
+
+/// 背景色と文字色の組み合わせ
+struct Palette {
+    background: u32,
+    font: u32,
+}
+
+/// 成功・GETを表す色（緑）
+const GREEN: Palette = Palette { background: 0xC6EFCE, font: 0x006100 };
+/// POST・リダイレクトを表す色（青）
+const BLUE: Palette = Palette { background: 0xDDEBF7, font: 0x1F4E78 };
+/// 更新系メソッド・クライアントエラーを表す色（橙）
+const ORANGE: Palette = Palette { background: 0xFCE4D6, font: 0x833C0C };
+/// DELETE・サーバーエラーを表す色（赤）
+const RED: Palette = Palette { background: 0xFFC7CE, font: 0x9C0006 };
+/// `--flag-url-regex`・`--flag-index`で指定した行の背景色（黄）
+const FLAGGED_BACKGROUND: u32 = 0xFFEB9C;
+
+/// 表示形式を設定したデータセルのフォーマット
+struct NumberFormat {
+    /// Excelの表示形式
+    pattern: &'static str,
+    /// 通常のデータセル
+    plain: Format,
+    /// 注目させる行のデータセル
+    flagged: Format,
+}
+
+/// Excel出力で使用するフォーマット一式
+pub struct ExcelStyles {
+    /// ヘッダー行
+    pub header: Format,
+    /// 通常のデータセル
+    pub cell: Format,
+    /// ペイロード（JSON等）のデータセル
+    pub json: Format,
+    /// シートの続き等の注記
+    pub note: Format,
+    /// 注目させる行のデータセル
+    flagged: Format,
+    /// 注目させる行のペイロードのデータセル
+    flagged_json: Format,
+    /// 日時・所要時間・サイズの列の表示形式ごとのデータセル
+    number_formats: Vec<NumberFormat>,
+    colored: bool,
+    method_get: Format,
+    method_post: Format,
+    status_success: Format,
+    status_redirect: Format,
+    status_client_error: Format,
+    status_server_error: Format,
+}
+
+impl ExcelStyles {
+    /// フォーマット一式を作成
+    /// 
+    /// # Arguments
+    /// * `colored` - メソッド列・ステータスコード列を色分けするかどうか
+    /// 
+    /// # Returns
+    /// * `ExcelStyles` - フォーマット一式
+    pub fn new(colored: bool) -> Self {
+        let cell = Format::new()
+            .set_border(FormatBorder::Thin)
+            .set_text_wrap();
+
+        let colored_cell = |palette: Palette| {
+            cell.clone()
+                .set_background_color(Color::RGB(palette.background))
+                .set_font_color(Color::RGB(palette.font))
+        };
+
+        let json = Format::new()
+            .set_border(FormatBorder::Thin)
+            .set_text_wrap()
+            .set_font_name("Consolas")
+            .set_font_size(9);
+
+        let flagged = cell.clone().set_background_color(Color::RGB(FLAGGED_BACKGROUND));
+        let mut number_formats: Vec<NumberFormat> = Vec::new();
+        for pattern in Column::ALL.iter().filter_map(Column::number_format) {
+            if !number_formats.iter().any(|format| format.pattern == pattern) {
+                number_formats.push(NumberFormat {
+                    pattern,
+                    plain: cell.clone().set_num_format(pattern),
+                    flagged: flagged.clone().set_num_format(pattern),
+                });
+            }
+        }
+
+        ExcelStyles {
+            header: Format::new()
+                .set_bold()
+                .set_background_color(Color::RGB(0xD3D3D3))
+                .set_border(FormatBorder::Thin),
+            flagged,
+            flagged_json: json.clone().set_background_color(Color::RGB(FLAGGED_BACKGROUND)),
+            json,
+            number_formats,
+            note: Format::new()
+                .set_italic()
+                .set_font_color(Color::RGB(0x808080)),
+            colored,
+            method_get: colored_cell(GREEN),
+            method_post: colored_cell(BLUE),
+            status_success: colored_cell(GREEN),
+            status_redirect: colored_cell(BLUE),
+            status_client_error: colored_cell(ORANGE),
+            status_server_error: colored_cell(RED),
+            cell,
+        }
+    }
+
+    /// データセルに適用するフォーマットを取得
+    /// 
+    /// # Arguments
+    /// * `column` - 列
+    /// * `result` - 解析結果
+    /// 
+    /// # Returns
+    /// * `&Format` - 適用するフォーマット
+    pub fn data_format(&self, column: Column, result: &AnalysisResult) -> &Format {
+        // 注目させる行は色分けしない列の背景色で強調する
+        let flagged = result.flag.is_some();
+        if column.is_payload() {
+            return if flagged { &self.flagged_json } else { &self.json };
+        }
+
+        if let Some(pattern) = column.number_format()
+            && let Some(format) = self.number_formats.iter().find(|format| format.pattern == pattern)
+        {
+            return if flagged { &format.flagged } else { &format.plain };
+        }
+
+        let plain = if flagged { &self.flagged } else { &self.cell };
+        if !self.colored {
+            return plain;
+        }
+
+        match column {
+            Column::Method => self.method_format(&result.method),
+            Column::StatusCode => self.status_format(result.status_code),
+            Column::Outlier if result.latency_outlier => &self.status_client_error,
+            _ => plain,
+        }
+    }
+
+    /// 注目させる行のデータセルのフォーマットを取得
+    /// 
+    /// # Returns
+    /// * `&Format` - 背景色を付けたデータセルのフォーマット
+    pub fn flagged_cell(&self) -> &Format {
+        &self.flagged
+    }
+
+    /// メソッドに応じたフォーマットを取得
+    /// 
+    /// # Arguments
+    /// * `method` - HTTPメソッド
+    /// 
+    /// # Returns
+    /// * `&Format` - 適用するフォーマット
+    fn method_format(&self, method: &str) -> &Format {
+        match method {
+            "GET" => &self.method_get,
+            "POST" => &self.method_post,
+            _ => &self.cell,
+        }
+    }
+
+    /// ステータスコードの分類に応じたフォーマットを取得
+    /// 
+    /// # Arguments
+    /// * `status_code` - ステータスコード
+    /// 
+    /// # Returns
+    /// * `&Format` - 適用するフォーマット
+    fn status_format(&self, status_code: i32) -> &Format {
+        match status_code {
+            200..=299 => &self.status_success,
+            300..=399 => &self.status_redirect,
+            400..=499 => &self.status_client_error,
+            500..=599 => &self.status_server_error,
+            _ => &self.cell,
+        }
+    }
+}
@@ -0,0 +1,610 @@
+//! `--where`で指定する条件式の解析と評価を実装
+//! 
+//! 条件式は`フィールド 演算子 値`の比較を`&&`・`||`・`!`・括弧で組み合わせたもの
+//! （例: `method==POST && status>=500 && host~"api\."`）
+
+use crate::analyzer::HarAnalyzer;
+use crate::body_kind::BodyKind;
+use crate::error::{AnalyzerError, Result};
+use crate::har_types::Entry;
+use regex::Regex;
+use std::iter::Peekable;
+use std::str::CharIndices;
+use url::Url;
+
+/// 条件式で参照するフィールド
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Field {
+    /// HTTPメソッド
+    Method,
+    /// ステータスコード
+    Status,
+    /// URLのホスト名
+    Host,
+    /// URL全体
+    Url,
+    /// URLのパス
+    Path,
+    /// レスポンスのMIMEタイプ
+    Mime,
+    /// レスポンスボディの内容から判定した種類（`json`・`javascript`等）
+    BodyKind,
+    /// 所要時間（ミリ秒）
+    Duration,
+    /// リクエストボディのサイズ（バイト）
+    RequestSize,
+    /// レスポンスボディのサイズ（バイト）
+    ResponseSize,
+    /// リクエストまたはレスポンスのヘッダーの値（`header.名前`）
+    Header(String),
+}
+
+impl Field {
+    /// フィールド名からフィールドを取得
+    /// 
+    /// # Arguments
+    /// * `name` - フィールド名
+    /// 
+    /// # Returns
+    /// * `Option<Field>` - 対応するフィールド
+    fn from_name(name: &str) -> Option<Self> {
+        let lower = name.to_ascii_lowercase();
+        if let Some(header) = lower.strip_prefix("header.") {
+            return (!header.is_empty()).then(|| Field::Header(header.to_string()));
+        }
+        match lower.as_str() {
+            "method" => Some(Field::Method),
+            "status" => Some(Field::Status),
+            "host" => Some(Field::Host),
+            "url" => Some(Field::Url),
+            "path" => Some(Field::Path),
+            "mime" => Some(Field::Mime),
+            "body-kind" => Some(Field::BodyKind),
+            "duration" => Some(Field::Duration),
+            "request-size" => Some(Field::RequestSize),
+            "response-size" => Some(Field::ResponseSize),
+            _ => None,
+        }
+    }
+
+    /// 数値のフィールドかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - 数値のフィールドの場合はtrue
+    fn is_numeric(&self) -> bool {
+        matches!(self, Field::Status | Field::Duration | Field::RequestSize | Field::ResponseSize)
+    }
+
+    /// エントリのフィールドの値を取得
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `Option<FieldValue>` - フィールドの値（ヘッダーが無い場合やURLを解析できない場合はNone）
+    fn value(&self, entry: &Entry) -> Option<FieldValue> {
+        let url = || Url::parse(&entry.request.url).ok();
+        let value = match self {
+            Field::Method => FieldValue::Text(entry.request.method.clone()),
+            Field::Status => FieldValue::Number(f64::from(entry.response.status)),
+            Field::Host => FieldValue::Text(url()?.host_str()?.to_string()),
+            Field::Url => FieldValue::Text(entry.request.url.clone()),
+            Field::Path => FieldValue::Text(url()?.path().to_string()),
+            Field::Mime => FieldValue::Text(entry.response.content.mime_type.clone()),
+            Field::BodyKind => FieldValue::Text(BodyKind::of_response(entry).label().to_string()),
+            Field::Duration => {
+                FieldValue::Number(if entry.time > 0.0 { entry.time } else { entry.timings.total() })
+            }
+            Field::RequestSize => FieldValue::Number(HarAnalyzer::request_size(entry) as f64),
+            Field::ResponseSize => FieldValue::Number(HarAnalyzer::response_size(entry) as f64),
+            Field::Header(name) => FieldValue::Text(
+                entry
+                    .request
+                    .headers
+                    .iter()
+                    .chain(&entry.response.headers)
+                    .find(|header| header.name.eq_ignore_ascii_case(name))?
+                    .value
+                    .clone(),
+            ),
+        };
+        Some(value)
+    }
+}
+
+/// フィールドの値
+enum FieldValue {
+    /// 文字列
+    Text(String),
+    /// 数値
+    Number(f64),
+}
+
+impl FieldValue {
+    /// 正規表現と照合するための文字列
+    /// 
+    /// # Returns
+    /// * `String` - 文字列（数値の場合は`500`のような表記）
+    fn to_text(&self) -> String {
+        match self {
+            FieldValue::Text(text) => text.clone(),
+            FieldValue::Number(number) => number.to_string(),
+        }
+    }
+}
+
+/// 比較演算子
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    /// `==`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+    /// `~`（正規表現に一致）
+    Match,
+    /// `!~`（正規表現に一致しない）
+    NotMatch,
+}
+
+/// 比較する値
+#[derive(Debug, Clone)]
+enum Operand {
+    /// 文字列
+    Text(String),
+    /// 数値
+    Number(f64),
+    /// 正規表現
+    Regex(Regex),
+}
+
+/// 条件式の構文木
+#[derive(Debug, Clone)]
+enum Expr {
+    /// 両方を満たす
+    And(Box<Expr>, Box<Expr>),
+    /// いずれかを満たす
+    Or(Box<Expr>, Box<Expr>),
+    /// 満たさない
+    Not(Box<Expr>),
+    /// フィールドの比較
+    Compare { field: Field, operator: Operator, operand: Operand },
+}
+
+impl Expr {
+    /// エントリが条件を満たすかどうかを評価
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `bool` - 条件を満たす場合はtrue
+    fn evaluate(&self, entry: &Entry) -> bool {
+        match self {
+            Expr::And(left, right) => left.evaluate(entry) && right.evaluate(entry),
+            Expr::Or(left, right) => left.evaluate(entry) || right.evaluate(entry),
+            Expr::Not(inner) => !inner.evaluate(entry),
+            Expr::Compare { field, operator, operand } => {
+                // 値が無いフィールドは否定の演算子のみを満たす
+                let Some(value) = field.value(entry) else {
+                    return matches!(operator, Operator::Ne | Operator::NotMatch);
+                };
+                compare(&value, *operator, operand)
+            }
+        }
+    }
+}
+
+/// フィールドの値を比較
+/// 
+/// # Arguments
+/// * `value` - フィールドの値
+/// * `operator` - 比較演算子
+/// * `operand` - 比較する値
+/// 
+/// # Returns
+/// * `bool` - 比較の結果
+fn compare(value: &FieldValue, operator: Operator, operand: &Operand) -> bool {
+    match (value, operand) {
+        (_, Operand::Regex(regex)) => regex.is_match(&value.to_text()) == (operator == Operator::Match),
+        (FieldValue::Number(value), Operand::Number(number)) => match operator {
+            Operator::Eq => value == number,
+            Operator::Ne => value != number,
+            Operator::Lt => value < number,
+            Operator::Le => value <= number,
+            Operator::Gt => value > number,
+            Operator::Ge => value >= number,
+            Operator::Match | Operator::NotMatch => false,
+        },
+        (FieldValue::Text(value), Operand::Text(text)) => value.eq_ignore_ascii_case(text) == (operator == Operator::Eq),
+        _ => false,
+    }
+}
+
+/// 字句
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// フィールド名または引用符で囲まれていない値
+    Word(String),
+    /// 引用符で囲まれた文字列
+    Quoted(String),
+    /// 比較演算子
+    Operator(Operator),
+    /// `&&`
+    And,
+    /// `||`
+    Or,
+    /// `!`
+    Not,
+    /// `(`
+    Open,
+    /// `)`
+    Close,
+}
+
+/// 条件式を字句に分割
+/// 
+/// # Arguments
+/// * `source` - 条件式
+/// 
+/// # Returns
+/// * `Result<Vec<Token>>` - 字句の並び
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some((position, c)) = chars.next() {
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '&' if next_is(&mut chars, '&') => Token::And,
+            '|' if next_is(&mut chars, '|') => Token::Or,
+            '=' if next_is(&mut chars, '=') => Token::Operator(Operator::Eq),
+            '!' if next_is(&mut chars, '=') => Token::Operator(Operator::Ne),
+            '!' if next_is(&mut chars, '~') => Token::Operator(Operator::NotMatch),
+            '!' => Token::Not,
+            '<' if next_is(&mut chars, '=') => Token::Operator(Operator::Le),
+            '<' => Token::Operator(Operator::Lt),
+            '>' if next_is(&mut chars, '=') => Token::Operator(Operator::Ge),
+            '>' => Token::Operator(Operator::Gt),
+            '~' => Token::Operator(Operator::Match),
+            '"' | '\'' => {
+                // `\`は引用符と`\`自身のみをエスケープし、それ以外（正規表現の`\.`等）はそのまま残す
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, escaped)) if escaped == c || escaped == '\\' => text.push(escaped),
+                            Some((_, other)) => {
+                                text.push('\\');
+                                text.push(other);
+                            }
+                            None => text.push('\\'),
+                        },
+                        Some((_, end)) if end == c => break,
+                        Some((_, other)) => text.push(other),
+                        None => return Err(syntax_error(source, position, "引用符が閉じられていません")),
+                    }
+                }
+                Token::Quoted(text)
+            }
+            _ if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some((_, next)) = chars.next_if(|&(_, next)| is_word_char(next)) {
+                    word.push(next);
+                }
+                Token::Word(word)
+            }
+            _ => return Err(syntax_error(source, position, &format!("不正な文字です: {}", c))),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// 次の文字が期待する文字の場合に読み進める
+/// 
+/// # Arguments
+/// * `chars` - 文字の並び
+/// * `expected` - 期待する文字
+/// 
+/// # Returns
+/// * `bool` - 読み進めた場合はtrue
+fn next_is(chars: &mut Peekable<CharIndices>, expected: char) -> bool {
+    chars.next_if(|&(_, next)| next == expected).is_some()
+}
+
+/// 引用符で囲まずに記述できる文字かどうか
+/// 
+/// # Arguments
+/// * `c` - 文字
+/// 
+/// # Returns
+/// * `bool` - フィールド名や値に使用できる文字の場合はtrue
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':' | '/' | '*' | '+')
+}
+
+/// 構文エラーを作成
+/// 
+/// # Arguments
+/// * `source` - 条件式
+/// * `position` - エラーの位置（バイト単位）
+/// * `message` - エラーの内容
+/// 
+/// # Returns
+/// * `AnalyzerError` - エラー
+fn syntax_error(source: &str, position: usize, message: &str) -> AnalyzerError {
+    let column = source[..position.min(source.len())].chars().count() + 1;
+    AnalyzerError::InvalidArgument(format!("条件式が不正です（{}文字目）: {}: {}", column, message, source))
+}
+
+/// 字句の並びを構文木に変換するパーサー
+/// 
+/// 優先順位は`!`、`&&`、`||`の順に高い
+struct Parser<'a> {
+    /// 条件式（エラーメッセージ用）
+    source: &'a str,
+    /// 字句の並び
+    tokens: Vec<Token>,
+    /// 次に読む字句の位置
+    position: usize,
+}
+
+impl Parser<'_> {
+    /// 次の字句を読まずに取得
+    /// 
+    /// # Returns
+    /// * `Option<&Token>` - 次の字句（終端の場合はNone）
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    /// 次の字句を読む
+    /// 
+    /// # Returns
+    /// * `Option<Token>` - 読んだ字句（終端の場合はNone）
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    /// 構文エラーを作成
+    /// 
+    /// # Arguments
+    /// * `message` - エラーの内容
+    /// 
+    /// # Returns
+    /// * `AnalyzerError` - エラー
+    fn error(&self, message: &str) -> AnalyzerError {
+        AnalyzerError::InvalidArgument(format!("条件式が不正です: {}: {}", message, self.source))
+    }
+
+    /// `||`で結合した式を解析
+    /// 
+    /// # Returns
+    /// * `Result<Expr>` - 構文木
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut expr = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    /// `&&`で結合した式を解析
+    /// 
+    /// # Returns
+    /// * `Result<Expr>` - 構文木
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut expr = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+        Ok(expr)
+    }
+
+    /// `!`・括弧・比較を解析
+    /// 
+    /// # Returns
+    /// * `Result<Expr>` - 構文木
+    fn parse_unary(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.parse_unary()?))),
+            Some(Token::Open) => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err(self.error("括弧が閉じられていません")),
+                }
+            }
+            Some(Token::Word(name)) => self.parse_compare(&name),
+            Some(token) => Err(self.error(&format!("フィールド名が必要な位置に{:?}があります", token))),
+            None => Err(self.error("式が途中で終わっています")),
+        }
+    }
+
+    /// `フィールド 演算子 値`の比較を解析
+    /// 
+    /// # Arguments
+    /// * `name` - フィールド名
+    /// 
+    /// # Returns
+    /// * `Result<Expr>` - 比較の構文木
+    fn parse_compare(&mut self, name: &str) -> Result<Expr> {
+        let field = Field::from_name(name).ok_or_else(|| {
+            self.error(&format!(
+                "不明なフィールドです: {}（method, status, host, url, path, mime, body-kind, duration, request-size, response-size, header.<名前>を指定できます）",
+                name
+            ))
+        })?;
+        let Some(Token::Operator(operator)) = self.next() else {
+            return Err(self.error(&format!("{}の後に比較演算子が必要です", name)));
+        };
+        let value = match self.next() {
+            Some(Token::Word(value) | Token::Quoted(value)) => value,
+            _ => return Err(self.error(&format!("{}の比較する値が必要です", name))),
+        };
+
+        let operand = match operator {
+            Operator::Match | Operator::NotMatch => Operand::Regex(
+                Regex::new(&value).map_err(|e| self.error(&format!("正規表現が不正です: {}", e)))?,
+            ),
+            _ if field.is_numeric() => Operand::Number(
+                value
+                    .parse()
+                    .map_err(|_| self.error(&format!("{}は数値と比較してください: {}", name, value)))?,
+            ),
+            Operator::Eq | Operator::Ne => Operand::Text(value),
+            _ => return Err(self.error(&format!("{}は大小を比較できません", name))),
+        };
+        Ok(Expr::Compare { field, operator, operand })
+    }
+}
+
+/// `--where`で指定された条件式
+#[derive(Debug, Clone)]
+pub struct FilterExpression {
+    /// 指定された条件式
+    source: String,
+    /// 解析した構文木
+    root: Expr,
+}
+
+impl FilterExpression {
+    /// 条件式を解析
+    /// 
+    /// # Arguments
+    /// * `source` - 条件式（例: `method==POST && status>=500`）
+    /// 
+    /// # Returns
+    /// * `Result<FilterExpression>` - 成功時は条件式、構文が不正な場合はエラー
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut parser = Parser {
+            source,
+            tokens: tokenize(source)?,
+            position: 0,
+        };
+        let root = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            return Err(parser.error(&format!("余分な字句があります: {:?}", token)));
+        }
+        Ok(FilterExpression {
+            source: source.to_string(),
+            root,
+        })
+    }
+
+    /// エントリが条件を満たすかどうか
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `bool` - 条件を満たす場合はtrue
+    pub fn matches(&self, entry: &Entry) -> bool {
+        self.root.evaluate(entry)
+    }
+
+    /// 指定された条件式
+    /// 
+    /// # Returns
+    /// * `&str` - 条件式
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry() -> Entry {
+        serde_json::from_value(json!({
+            "startedDateTime": "2024-01-01T00:00:00.000Z",
+            "time": 120.0,
+            "request": {
+                "method": "POST", "url": "https://api.example.com/v1/items?page=2", "httpVersion": "HTTP/1.1",
+                "headers": [{ "name": "X-Request-Id", "value": "abc-123" }], "queryString": [],
+                "cookies": [], "headersSize": -1, "bodySize": 2,
+                "postData": { "mimeType": "application/json", "text": "{}" }
+            },
+            "response": {
+                "status": 503, "statusText": "", "httpVersion": "HTTP/1.1", "headers": [], "cookies": [],
+                "content": { "size": 13, "mimeType": "application/json", "text": "{\"ok\": false}" },
+                "redirectURL": "", "headersSize": -1, "bodySize": 13
+            },
+            "timings": { "send": 0.0, "wait": 120.0, "receive": 0.0 }
+        }))
+        .unwrap()
+    }
+
+    fn matches(source: &str) -> bool {
+        FilterExpression::parse(source).unwrap().matches(&entry())
+    }
+
+    #[test]
+    fn compares_text_and_numeric_fields() {
+        assert!(matches("method==post"));
+        assert!(matches("status>=500 && status<600"));
+        assert!(!matches("status!=503"));
+        assert!(matches("host==api.example.com && path==/v1/items"));
+        assert!(matches("duration>100 && request-size==2"));
+        assert!(matches("mime==\"application/json\""));
+        assert!(matches("body-kind==json"));
+    }
+
+    #[test]
+    fn matches_regex_and_headers() {
+        assert!(matches(r#"host~"api\.""#));
+        assert!(matches("url!~'page=1$'"));
+        assert!(matches("header.x-request-id==abc-123"));
+        // 存在しないヘッダーは否定の演算子のみを満たす
+        assert!(!matches("header.authorization==x"));
+        assert!(matches("header.authorization!=x"));
+    }
+
+    #[test]
+    fn applies_operator_precedence() {
+        assert!(matches("method==GET || status==503 && host~example"));
+        assert!(!matches("(method==GET || status==503) && host~other"));
+        assert!(matches("!method==GET && !(status<500)"));
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for source in [
+            "unknown==1",
+            "status==abc",
+            "method>GET",
+            "status",
+            "status==",
+            "(method==GET",
+            "method==GET)",
+            "method==\"GET",
+            "host~\"(\"",
+            "method==GET &",
+            "header.==x",
+        ] {
+            let error = FilterExpression::parse(source).unwrap_err();
+            assert!(matches!(error, AnalyzerError::InvalidArgument(_)), "{}: {:?}", source, error);
+        }
+    }
+
+    #[test]
+    fn reports_error_column() {
+        let error = FilterExpression::parse("method==GET # x").unwrap_err();
+        assert!(error.to_string().contains("13文字目"), "{}", error);
+    }
+}
@@ -0,0 +1,286 @@
+//! 解析対象のエントリを絞り込むフィルタを実装
+
+use crate::error::{AnalyzerError, Result};
+use crate::expression::FilterExpression;
+use crate::har_types::{Entry, NameValue};
+use regex::Regex;
+
+/// ヘッダーの値のパターン（`名前=パターン`、パターンは`*`と`?`のワイルドカードを使用可能）
+#[derive(Debug, Clone)]
+pub struct HeaderPattern {
+    /// ヘッダー名
+    name: String,
+    /// 指定されたパターン
+    pattern: String,
+    /// パターンを変換した正規表現
+    regex: Regex,
+}
+
+impl HeaderPattern {
+    /// `名前=パターン`の形式の指定を解析
+    /// 
+    /// # Arguments
+    /// * `spec` - 指定（例: `x-api-version=2.*`）
+    /// 
+    /// # Returns
+    /// * `Result<HeaderPattern>` - 成功時はパターン、失敗時はエラー
+    pub fn parse(spec: &str) -> Result<Self> {
+        let Some((name, pattern)) = spec.split_once('=') else {
+            return Err(AnalyzerError::InvalidArgument(format!(
+                "ヘッダーのフィルタは「名前=パターン」の形式で指定してください: {}",
+                spec
+            )));
+        };
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AnalyzerError::InvalidArgument(format!("ヘッダー名が指定されていません: {}", spec)));
+        }
+        Ok(HeaderPattern {
+            name: name.to_string(),
+            pattern: pattern.to_string(),
+            regex: glob_to_regex(pattern),
+        })
+    }
+
+    /// ヘッダーのいずれかがパターンに一致するかどうか
+    /// 
+    /// # Arguments
+    /// * `headers` - ヘッダーのリスト
+    /// 
+    /// # Returns
+    /// * `bool` - 一致するヘッダーがある場合はtrue
+    fn matches(&self, headers: &[NameValue]) -> bool {
+        headers
+            .iter()
+            .any(|header| header.name.eq_ignore_ascii_case(&self.name) && self.regex.is_match(&header.value))
+    }
+}
+
+/// ワイルドカード（`*`は任意の文字列、`?`は任意の1文字）のパターンを全体に一致する正規表現に変換
+/// 
+/// # Arguments
+/// * `pattern` - ワイルドカードのパターン
+/// 
+/// # Returns
+/// * `Regex` - 正規表現
+fn glob_to_regex(pattern: &str) -> Regex {
+    let mut regex = String::from("(?s)^");
+    for c in pattern.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).expect("エスケープ済みの正規表現")
+}
+
+/// 巨大なHARファイルを素早く確認するためのエントリの抽出
+/// 
+/// 他の条件で絞り込んだ後のエントリに対し、割合での抽出、開始位置、件数の上限の順に適用する
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sampling {
+    /// 抽出する割合（0より大きく1以下、Noneの場合は全件）
+    pub rate: Option<f64>,
+    /// 読み飛ばす件数
+    pub offset: usize,
+    /// 件数の上限
+    pub limit: Option<usize>,
+    /// エンドポイントごとに残す最大の件数（Noneの場合は全件）
+    pub per_endpoint: Option<usize>,
+}
+
+impl Sampling {
+    /// `10%`の形式の割合を解析
+    /// 
+    /// # Arguments
+    /// * `spec` - 割合（例: `10%`）
+    /// 
+    /// # Returns
+    /// * `Result<f64>` - 成功時は0より大きく1以下の割合、失敗時はエラー
+    pub fn parse_rate(spec: &str) -> Result<f64> {
+        spec.trim()
+            .strip_suffix('%')
+            .and_then(|percent| percent.trim().parse::<f64>().ok())
+            .filter(|percent| *percent > 0.0 && *percent <= 100.0)
+            .map(|percent| percent / 100.0)
+            .ok_or_else(|| {
+                AnalyzerError::InvalidArgument(format!(
+                    "抽出する割合は0%より大きく100%以下の「10%」の形式で指定してください: {}",
+                    spec
+                ))
+            })
+    }
+
+    /// 抽出を行わないかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - 全件を対象とする場合はtrue
+    pub fn is_empty(&self) -> bool {
+        self.rate.is_none() && self.offset == 0 && self.limit.is_none() && self.per_endpoint.is_none()
+    }
+
+    /// 割合での抽出の対象かどうか
+    /// 
+    /// 実行ごとに同じ結果となるよう、乱数ではなく等間隔に抽出する
+    /// 
+    /// # Arguments
+    /// * `index` - 絞り込んだ後のエントリの番号（0始まり）
+    /// 
+    /// # Returns
+    /// * `bool` - 抽出の対象の場合はtrue
+    pub fn includes(&self, index: usize) -> bool {
+        self.rate
+            .is_none_or(|rate| ((index + 1) as f64 * rate).floor() > (index as f64 * rate).floor())
+    }
+
+    /// 適用する抽出の説明
+    /// 
+    /// # Returns
+    /// * `Option<String>` - 抽出の説明（抽出を行わない場合はNone）
+    pub fn describe(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(per_endpoint) = self.per_endpoint {
+            parts.push(format!("エンドポイントごとに最大{}件（エラー・所要時間の長い順）", per_endpoint));
+        }
+        if let Some(rate) = self.rate {
+            parts.push(format!("{}%を等間隔に抽出", rate * 100.0));
+        }
+        if self.offset > 0 {
+            parts.push(format!("先頭{}件を読み飛ばし", self.offset));
+        }
+        if let Some(limit) = self.limit {
+            parts.push(format!("最大{}件", limit));
+        }
+        (!parts.is_empty()).then(|| parts.join("、"))
+    }
+}
+
+/// 解析対象のエントリを絞り込むフィルタ
+/// 
+/// 指定された全ての条件を満たすエントリのみを対象とする
+#[derive(Debug, Clone, Default)]
+pub struct EntryFilter {
+    /// リクエストまたはレスポンスのヘッダーの値のパターン
+    pub header_patterns: Vec<HeaderPattern>,
+    /// リクエストまたはレスポンスに含まれている必要があるヘッダー名
+    pub required_headers: Vec<String>,
+    /// リクエストまたはレスポンスのペイロードに含まれている必要がある文字列
+    pub body_contains: Vec<String>,
+    /// リクエストまたはレスポンスのペイロードが一致する必要がある正規表現
+    pub body_regexes: Vec<Regex>,
+    /// `--where`で指定された条件式
+    pub expressions: Vec<FilterExpression>,
+    /// 対象とするリソースの種類（`_resourceType`、小文字）
+    pub resource_types: Vec<String>,
+    /// エントリの抽出
+    pub sampling: Sampling,
+    /// URLとETagが同じ静的アセットへの繰り返しのリクエストを1件に集約するかどうか
+    pub collapse_assets: bool,
+    /// ページ送りの一連のリクエストを1件に集約するかどうか
+    pub collapse_pages: bool,
+}
+
+impl EntryFilter {
+    /// 条件が指定されていないかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - 条件が無い場合はtrue
+    pub fn is_empty(&self) -> bool {
+        self.header_patterns.is_empty()
+            && self.required_headers.is_empty()
+            && self.expressions.is_empty()
+            && self.resource_types.is_empty()
+            && self.sampling.is_empty()
+            && !self.collapse_assets
+            && !self.collapse_pages
+            && !self.filters_body()
+    }
+
+    /// ペイロードの条件が指定されているかどうか
+    /// 
+    /// ペイロードの条件はデコード後のペイロードに対して判定するため、エントリの解析後に適用する
+    /// 
+    /// # Returns
+    /// * `bool` - ペイロードの条件がある場合はtrue
+    pub fn filters_body(&self) -> bool {
+        !self.body_contains.is_empty() || !self.body_regexes.is_empty()
+    }
+
+    /// エントリが全ての条件を満たすかどうか
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `bool` - 条件を満たす場合はtrue
+    pub fn matches(&self, entry: &Entry) -> bool {
+        let headers = [entry.request.headers.as_slice(), entry.response.headers.as_slice()];
+        self.header_patterns
+            .iter()
+            .all(|pattern| headers.iter().any(|headers| pattern.matches(headers)))
+            && self.required_headers.iter().all(|name| {
+                headers
+                    .iter()
+                    .any(|headers| headers.iter().any(|header| header.name.eq_ignore_ascii_case(name)))
+            })
+            && self.expressions.iter().all(|expression| expression.matches(entry))
+            && (self.resource_types.is_empty()
+                || entry
+                    .resource_type
+                    .as_deref()
+                    .is_some_and(|resource_type| self.resource_types.iter().any(|t| t.eq_ignore_ascii_case(resource_type))))
+    }
+
+    /// ペイロードが全ての条件を満たすかどうか
+    /// 
+    /// 条件ごとに、デコード・整形後のリクエストまたはレスポンスのペイロードのいずれかが満たせばよい
+    /// 
+    /// # Arguments
+    /// * `request` - リクエストのペイロード
+    /// * `response` - レスポンスのペイロード（HTMLの要約・JavaScript・CSSの整形の前のもの）
+    /// 
+    /// # Returns
+    /// * `bool` - 条件を満たす場合はtrue
+    pub fn matches_payloads(&self, request: &str, response: &str) -> bool {
+        if !self.filters_body() {
+            return true;
+        }
+        let payloads = [request, response];
+        self.body_contains
+            .iter()
+            .all(|text| payloads.iter().any(|payload| payload.contains(text.as_str())))
+            && self
+                .body_regexes
+                .iter()
+                .all(|regex| payloads.iter().any(|payload| regex.is_match(payload)))
+    }
+
+    /// 適用する条件の説明（実行情報シートに記録する）
+    /// 
+    /// # Returns
+    /// * `Vec<String>` - 条件ごとの説明
+    pub fn describe(&self) -> Vec<String> {
+        let patterns = self
+            .header_patterns
+            .iter()
+            .map(|pattern| format!("ヘッダー: {}={}", pattern.name, pattern.pattern));
+        let required = self.required_headers.iter().map(|name| format!("ヘッダーあり: {}", name));
+        let contains = self.body_contains.iter().map(|text| format!("ペイロードを含む: {}", text));
+        let regexes = self.body_regexes.iter().map(|regex| format!("ペイロードの正規表現: {}", regex.as_str()));
+        let expressions = self.expressions.iter().map(|expression| format!("条件式: {}", expression.as_str()));
+        let resource_types = (!self.resource_types.is_empty())
+            .then(|| format!("リソースの種類: {}", self.resource_types.join(", ")));
+        patterns
+            .chain(required)
+            .chain(contains)
+            .chain(regexes)
+            .chain(expressions)
+            .chain(resource_types)
+            .chain(self.collapse_assets.then(|| "静的アセットの集約: URLとETagが同じ200・304のGETを1件に集約".to_string()))
+            .chain(self.collapse_pages.then(|| "ページ送りの集約: ページのパラメータのみが異なる一連のリクエストを1件に集約".to_string()))
+            .chain(self.sampling.describe().map(|sampling| format!("サンプリング: {}", sampling)))
+            .collect()
+    }
+}
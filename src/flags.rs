@@ -0,0 +1,67 @@
+//! レポートで注目させるエントリの指定（`--flag-url-regex`・`--flag-index`）を実装
+//! 
+//! 指定したエントリの行を強調表示し、Flaggedシートに一覧として出力する
+
+use crate::error::{AnalyzerError, Result};
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 注目させるエントリの条件
+#[derive(Debug, Clone, Default)]
+pub struct EntryFlags {
+    /// リクエストURLが一致する正規表現
+    pub url_regexes: Vec<Regex>,
+    /// エントリ番号（HAR内の順序で1始まり）
+    pub indices: BTreeSet<usize>,
+    /// 解析の結果から注目させるエントリの位置（0始まり）ごとの理由（脅威インテリジェンスの指標との一致等）
+    pub reasons: BTreeMap<usize, Vec<String>>,
+}
+
+impl EntryFlags {
+    /// カンマ区切りのエントリ番号を解析
+    /// 
+    /// # Arguments
+    /// * `spec` - カンマ区切りのエントリ番号（例: `12,45`）
+    /// 
+    /// # Returns
+    /// * `Result<Vec<usize>>` - 成功時はエントリ番号（1始まり）のリスト、失敗時はエラー
+    pub fn parse_indices(spec: &str) -> Result<Vec<usize>> {
+        spec.split(',')
+            .map(str::trim)
+            .filter(|index| !index.is_empty())
+            .map(|index| {
+                index.parse::<usize>().ok().filter(|index| *index > 0).ok_or_else(|| {
+                    AnalyzerError::InvalidArgument(format!("エントリ番号は1以上の整数で指定してください: {}", index))
+                })
+            })
+            .collect()
+    }
+
+    /// エントリを注目させる理由を取得
+    /// 
+    /// URLはHARに記録された形式と、レポートに表示するデコード後の形式のいずれかが一致すればよい
+    /// 
+    /// # Arguments
+    /// * `index` - エントリの位置（0始まり）
+    /// * `url` - リクエストURL
+    /// 
+    /// # Returns
+    /// * `Option<String>` - 理由（条件に一致しない場合はNone）
+    pub fn reason(&self, index: usize, url: &str) -> Option<String> {
+        let mut reasons = Vec::new();
+        if self.indices.contains(&(index + 1)) {
+            reasons.push(format!("エントリ番号: {}", index + 1));
+        }
+        reasons.extend(self.reasons.get(&index).into_iter().flatten().cloned());
+        if !self.url_regexes.is_empty() {
+            let decoded = urlencoding::decode(url).map_or_else(|_| url.to_string(), |decoded| decoded.into_owned());
+            reasons.extend(
+                self.url_regexes
+                    .iter()
+                    .filter(|regex| regex.is_match(url) || regex.is_match(&decoded))
+                    .map(|regex| format!("URL: {}", regex.as_str())),
+            );
+        }
+        (!reasons.is_empty()).then(|| reasons.join("\n"))
+    }
+}
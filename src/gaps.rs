@@ -0,0 +1,67 @@
+//! 連続するリクエストの間隔（ユーザーの操作待ち時間等）の計算を実装
+//! 
+//! 各リクエストの開始時刻と、それより前に開始した全てのリクエストの終了時刻のうち最も遅いものとの差を
+//! 間隔とする（前のリクエストが通信中に開始した場合は0）。
+//! 手動操作のキャプチャでは、大きな間隔はサーバーの遅延ではなく操作の待ち時間であることが多い
+
+use crate::har_types::Entry;
+use chrono::DateTime;
+use std::collections::HashMap;
+use url::Url;
+
+/// 大きな間隔とみなす閾値（ミリ秒）
+pub const IDLE_GAP_THRESHOLD_MS: f64 = 5000.0;
+
+/// エントリの前の通信からの間隔
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EntryGap {
+    /// 前の通信からの間隔（ミリ秒、最初のリクエストの場合はNone）
+    pub overall: Option<f64>,
+    /// 同一ホストの前の通信からの間隔（ミリ秒、ホストの最初のリクエストの場合はNone）
+    pub host: Option<f64>,
+}
+
+/// 全てのエントリについて前の通信からの間隔を計算
+/// 
+/// エントリは開始時刻順に並べ替えて計算する（開始時刻を解析できないエントリは間隔無し）
+/// 
+/// # Arguments
+/// * `entries` - HARエントリのリスト
+/// * `duration` - エントリの所要時間（ミリ秒）を取得する関数
+/// 
+/// # Returns
+/// * `Vec<EntryGap>` - エントリと同じ順序の間隔のリスト
+pub fn entry_gaps(entries: &[Entry], duration: impl Fn(&Entry) -> f64) -> Vec<EntryGap> {
+    let mut starts: Vec<(usize, f64)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            DateTime::parse_from_rfc3339(&entry.started_date_time)
+                .ok()
+                .map(|started| (index, started.timestamp_millis() as f64))
+        })
+        .collect();
+    starts.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+
+    let mut gaps = vec![EntryGap::default(); entries.len()];
+    let mut latest_end: Option<f64> = None;
+    let mut host_latest_end: HashMap<String, f64> = HashMap::new();
+    for (index, start) in starts {
+        let entry = &entries[index];
+        let end = start + duration(entry).max(0.0);
+        let host = Url::parse(&entry.request.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+
+        gaps[index] = EntryGap {
+            overall: latest_end.map(|previous| (start - previous).max(0.0)),
+            host: host_latest_end.get(&host).map(|previous| (start - previous).max(0.0)),
+        };
+
+        latest_end = Some(latest_end.map_or(end, |previous| previous.max(end)));
+        let host_end = host_latest_end.entry(host).or_insert(end);
+        *host_end = host_end.max(end);
+    }
+    gaps
+}
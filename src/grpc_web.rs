@@ -0,0 +1,142 @@
+//! gRPC-webのペイロードのデコードを実装
+//! 
+//! gRPC-webのフレーム（1バイトのフラグと4バイトの長さに続くメッセージ）を分解し、
+//! `--proto-descriptor`で記述子セットが指定されている場合はメッセージをJSONに変換する
+
+use crate::error::{AnalyzerError, Result};
+use base64::{Engine as _, engine::general_purpose};
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor};
+use serde_json::{Map, Value, json};
+use std::fs;
+
+/// トレーラーのフレームを示すフラグのビット
+const TRAILER_FLAG: u8 = 0x80;
+
+/// フレームのヘッダーの長さ（フラグ1バイト + 長さ4バイト）
+const FRAME_HEADER_LEN: usize = 5;
+
+/// コンテンツタイプがgRPC-webかどうかを判定
+/// 
+/// # Arguments
+/// * `mime_type` - MIMEタイプ
+/// 
+/// # Returns
+/// * `bool` - gRPC-web（`application/grpc-web`、`application/grpc-web+proto`、`application/grpc-web-text`）の場合はtrue
+pub fn is_grpc_web_content(mime_type: &str) -> bool {
+    mime_type.to_ascii_lowercase().contains("application/grpc-web")
+}
+
+/// コンテンツタイプがgRPC-webのテキスト形式（Base64）かどうかを判定
+/// 
+/// # Arguments
+/// * `mime_type` - MIMEタイプ
+/// 
+/// # Returns
+/// * `bool` - `application/grpc-web-text`の場合はtrue
+pub fn is_grpc_web_text(mime_type: &str) -> bool {
+    mime_type.to_ascii_lowercase().contains("application/grpc-web-text")
+}
+
+/// 記述子セット（`protoc --descriptor_set_out`で作成したFileDescriptorSet）を読み込む
+/// 
+/// # Arguments
+/// * `path` - 記述子セットのパス
+/// 
+/// # Returns
+/// * `Result<DescriptorPool>` - 読み込んだ記述子
+pub fn load_descriptor_pool(path: &str) -> Result<DescriptorPool> {
+    let bytes = fs::read(path).map_err(AnalyzerError::io(format!("記述子セットの読み込みに失敗しました: {}", path)))?;
+    DescriptorPool::decode(bytes.as_slice())
+        .map_err(|e| AnalyzerError::InvalidArgument(format!("記述子セットを解析できません: {}: {}", path, e)))
+}
+
+/// リクエストURLのパス（`/パッケージ.サービス/メソッド`）からメッセージの型を取得
+/// 
+/// # Arguments
+/// * `pool` - 記述子
+/// * `path` - リクエストURLのパス
+/// * `request` - リクエストの型を取得する場合はtrue、レスポンスの型を取得する場合はfalse
+/// 
+/// # Returns
+/// * `Option<MessageDescriptor>` - メッセージの型（サービスまたはメソッドが見つからない場合はNone）
+pub fn message_descriptor(pool: &DescriptorPool, path: &str, request: bool) -> Option<MessageDescriptor> {
+    let (service, method) = path.trim_start_matches('/').rsplit_once('/')?;
+    let method = pool
+        .get_service_by_name(service)?
+        .methods()
+        .find(|candidate| candidate.name() == method)?;
+    Some(if request { method.input() } else { method.output() })
+}
+
+/// gRPC-webのボディをJSONに変換
+/// 
+/// 出力は`messages`（メッセージの配列）と、トレーラーがある場合は`trailers`を持つオブジェクトとなる。
+/// メッセージの型が分からない場合やデコードに失敗した場合、メッセージはBase64で出力する
+/// 
+/// # Arguments
+/// * `body` - ボディのバイト列（フレームの並び）
+/// * `text_format` - `application/grpc-web-text`（フレームの並び全体がBase64）の場合はtrue
+/// * `message` - メッセージの型
+/// 
+/// # Returns
+/// * `Option<String>` - 整形したJSON（フレームとして解釈できない場合はNone）
+pub fn render(body: &[u8], text_format: bool, message: Option<&MessageDescriptor>) -> Option<String> {
+    let decoded;
+    let mut frames = body;
+    if text_format {
+        let text: String = String::from_utf8_lossy(body).split_whitespace().collect();
+        decoded = general_purpose::STANDARD.decode(text).ok()?;
+        frames = &decoded;
+    }
+
+    let mut messages = Vec::new();
+    let mut trailers = Map::new();
+    while !frames.is_empty() {
+        if frames.len() < FRAME_HEADER_LEN {
+            return None;
+        }
+        let flag = frames[0];
+        let length = u32::from_be_bytes([frames[1], frames[2], frames[3], frames[4]]) as usize;
+        let payload = frames.get(FRAME_HEADER_LEN..FRAME_HEADER_LEN + length)?;
+        frames = &frames[FRAME_HEADER_LEN + length..];
+
+        if flag & TRAILER_FLAG != 0 {
+            // トレーラーはHTTP/1のヘッダーと同じ形式のテキスト
+            for line in String::from_utf8_lossy(payload).lines() {
+                if let Some((name, value)) = line.split_once(':') {
+                    trailers.insert(name.trim().to_ascii_lowercase(), json!(value.trim()));
+                }
+            }
+        } else {
+            messages.push(decode_message(payload, message));
+        }
+    }
+
+    let mut rendered = json!({ "messages": messages });
+    if !trailers.is_empty() {
+        rendered["trailers"] = Value::Object(trailers);
+    }
+    serde_json::to_string_pretty(&rendered).ok()
+}
+
+/// 1つのメッセージをJSONに変換
+/// 
+/// # Arguments
+/// * `payload` - メッセージのバイト列
+/// * `message` - メッセージの型
+/// 
+/// # Returns
+/// * `Value` - 変換したJSON（変換できない場合はBase64の文字列）
+fn decode_message(payload: &[u8], message: Option<&MessageDescriptor>) -> Value {
+    let encoded = || json!(general_purpose::STANDARD.encode(payload));
+    let Some(message) = message else {
+        return encoded();
+    };
+    match DynamicMessage::decode(message.clone(), payload) {
+        Ok(decoded) => serde_json::to_value(&decoded).unwrap_or_else(|_| encoded()),
+        Err(e) => json!({
+            "error": format!("{}としてデコードできません: {}", message.full_name(), e),
+            "base64": general_purpose::STANDARD.encode(payload),
+        }),
+    }
+}
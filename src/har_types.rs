@@ -1,6 +1,16 @@
 //! HARファイルの構造を定義するデータ型
 
+use crate::body_kind::BodyKind;
+use crate::entry_warnings::EntryWarnings;
+use crate::payload::LazyText;
+use crate::tls::SecurityDetails;
+use crate::protocol::TrafficProtocol;
+use crate::trackers::DomainCategory;
+use crate::url_parts::UrlParts;
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
 
 /// HARファイルのルート構造
 #[derive(Debug, Deserialize, Serialize)]
@@ -13,19 +23,54 @@ pub struct HarFile {
 pub struct Log {
     pub version: String,
     pub creator: Creator,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub browser: Option<Creator>,
+    #[serde(default)]
+    pub pages: Vec<Page>,
     pub entries: Vec<Entry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// 仕様外・ツール独自のフィールド（変換したHARに引き継ぐ）
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
-/// HARファイルを作成したツールの情報
+/// HARファイルを作成したツール（またはブラウザ）の情報
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Creator {
     pub name: String,
+    #[serde(default)]
     pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// ページ情報
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Page {
+    #[serde(rename = "startedDateTime")]
+    pub started_date_time: String,
+    pub id: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(rename = "pageTimings", default)]
+    pub page_timings: PageTimings,
+}
+
+/// ページのタイミング情報
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct PageTimings {
+    #[serde(rename = "onContentLoad", skip_serializing_if = "Option::is_none")]
+    pub on_content_load: Option<f64>,
+    #[serde(rename = "onLoad", skip_serializing_if = "Option::is_none")]
+    pub on_load: Option<f64>,
 }
 
 /// HTTPリクエスト/レスポンスのエントリ
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Entry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pageref: Option<String>,
     #[serde(rename = "startedDateTime")]
     pub started_date_time: String,
     pub time: f64,
@@ -33,10 +78,135 @@ pub struct Entry {
     pub response: Response,
     #[serde(default)]
     pub cache: Cache,
+    /// Safari等はタイミング情報を出力しない場合があるため省略可能とする
+    #[serde(default)]
     pub timings: Timings,
-    #[serde(rename = "serverIPAddress")]
+    #[serde(rename = "serverIPAddress", skip_serializing_if = "Option::is_none")]
     pub server_ip_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub connection: Option<String>,
+    /// リクエストの発生元（Chrome DevTools独自フィールド）
+    #[serde(rename = "_initiator", skip_serializing_if = "Option::is_none")]
+    pub initiator: Option<Initiator>,
+    /// リソースの種類（Chrome DevTools独自フィールド、document・script・xhr・fetch等）
+    #[serde(rename = "_resourceType", skip_serializing_if = "Option::is_none")]
+    pub resource_type: Option<String>,
+    /// リクエストの優先度（Chrome DevTools独自フィールド、VeryHigh・High・Medium・Low・VeryLow）
+    #[serde(rename = "_priority", skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    /// WebSocketで送受信したメッセージ（Chrome DevTools独自フィールド）
+    #[serde(rename = "_webSocketMessages", default, skip_serializing_if = "Vec::is_empty")]
+    pub web_socket_messages: Vec<WebSocketMessage>,
+    /// TLSの接続情報（一部のプロキシ等の独自フィールド）
+    #[serde(rename = "_securityDetails", skip_serializing_if = "Option::is_none")]
+    pub security_details: Option<SecurityDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// 仕様外・ツール独自のフィールド（変換したHARに引き継ぐ）
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// エントリIDの16進数の桁数
+const ENTRY_ID_LENGTH: usize = 16;
+
+impl Entry {
+    /// エントリの内容から求めた安定したIDを取得
+    /// 
+    /// エントリ全体（リクエスト・レスポンス・タイミング等）をJSONにしたSHA-256ハッシュの先頭16桁とする。
+    /// HAR内の順序に依存しないため、絞り込み・出力形式が異なる出力の行を同じエントリとして突き合わせられる
+    /// 
+    /// # Returns
+    /// * `String` - 16桁の16進数のID
+    pub fn content_id(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        Sha256::digest(&json)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>()[..ENTRY_ID_LENGTH]
+            .to_string()
+    }
+}
+
+/// WebSocketで送受信したメッセージ
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WebSocketMessage {
+    /// 送受信の種別（send/receive）
+    #[serde(rename = "type")]
+    pub message_type: String,
+    /// 送受信した時刻（UNIX時間の秒）
+    pub time: f64,
+    /// WebSocketのオペコード（1: テキスト、2: バイナリ）
+    pub opcode: i32,
+    #[serde(default)]
+    pub data: String,
+}
+
+/// リクエストの発生元情報
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Initiator {
+    #[serde(rename = "type")]
+    pub initiator_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(rename = "lineNumber", skip_serializing_if = "Option::is_none")]
+    pub line_number: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack: Option<InitiatorStack>,
+}
+
+impl Initiator {
+    /// 発生元のURLを取得
+    /// 
+    /// urlフィールドが無い場合はスタックトレースを親方向に辿って最初に見つかったURLを返す
+    /// 
+    /// # Returns
+    /// * `Option<&str>` - 発生元のURL
+    pub fn source_url(&self) -> Option<&str> {
+        self.source_location().map(|(url, _)| url)
+    }
+
+    /// 発生元のURLと行番号を取得
+    /// 
+    /// urlフィールドが無い場合はスタックトレースを親方向に辿って最初に見つかったフレームのURLと行番号を返す
+    /// 
+    /// # Returns
+    /// * `Option<(&str, Option<i64>)>` - (発生元のURL, 0始まりの行番号)
+    pub fn source_location(&self) -> Option<(&str, Option<i64>)> {
+        if let Some(url) = self.url.as_deref().filter(|u| !u.is_empty()) {
+            return Some((url, self.line_number));
+        }
+
+        let mut stack = self.stack.as_ref();
+        while let Some(current) = stack {
+            if let Some(frame) = current.call_frames.iter().find(|f| !f.url.is_empty()) {
+                return Some((&frame.url, frame.line_number));
+            }
+            stack = current.parent.as_deref();
+        }
+
+        None
+    }
+}
+
+/// 発生元のスタックトレース
+#[derive(Debug, Deserialize, Serialize)]
+pub struct InitiatorStack {
+    #[serde(rename = "callFrames", default)]
+    pub call_frames: Vec<CallFrame>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Box<InitiatorStack>>,
+}
+
+/// スタックトレースのフレーム
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CallFrame {
+    #[serde(rename = "functionName", default)]
+    pub function_name: String,
+    #[serde(default)]
+    pub url: String,
+    #[serde(rename = "lineNumber", skip_serializing_if = "Option::is_none")]
+    pub line_number: Option<i64>,
 }
 
 /// HTTPリクエストの詳細
@@ -56,8 +226,13 @@ pub struct Request {
     pub headers_size: i64,
     #[serde(rename = "bodySize")]
     pub body_size: i64,
-    #[serde(rename = "postData")]
+    #[serde(rename = "postData", skip_serializing_if = "Option::is_none")]
     pub post_data: Option<PostData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// 仕様外・ツール独自のフィールド（変換したHARに引き継ぐ）
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// HTTPレスポンスの詳細
@@ -79,10 +254,15 @@ pub struct Response {
     pub headers_size: i64,
     #[serde(rename = "bodySize")]
     pub body_size: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// 仕様外・ツール独自のフィールド（変換したHARに引き継ぐ）
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// 名前と値のペア
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct NameValue {
     pub name: String,
     pub value: String,
@@ -93,11 +273,15 @@ pub struct NameValue {
 pub struct Cookie {
     pub name: String,
     pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub expires: Option<String>,
-    #[serde(rename = "httpOnly")]
+    #[serde(rename = "httpOnly", skip_serializing_if = "Option::is_none")]
     pub http_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub secure: Option<bool>,
 }
 
@@ -108,6 +292,7 @@ pub struct PostData {
     pub mime_type: String,
     #[serde(default)]
     pub params: Vec<Param>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
 }
 
@@ -115,10 +300,11 @@ pub struct PostData {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Param {
     pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<String>,
-    #[serde(rename = "fileName")]
+    #[serde(rename = "fileName", skip_serializing_if = "Option::is_none")]
     pub file_name: Option<String>,
-    #[serde(rename = "contentType")]
+    #[serde(rename = "contentType", skip_serializing_if = "Option::is_none")]
     pub content_type: Option<String>,
 }
 
@@ -126,31 +312,28 @@ pub struct Param {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Content {
     pub size: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub compression: Option<i64>,
     #[serde(rename = "mimeType")]
     pub mime_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub encoding: Option<String>,
+    /// 仕様外・ツール独自のフィールド（変換したHARに引き継ぐ）
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// キャッシュ情報
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct Cache {
-    #[serde(rename = "beforeRequest")]
+    #[serde(rename = "beforeRequest", skip_serializing_if = "Option::is_none")]
     pub before_request: Option<CacheState>,
-    #[serde(rename = "afterRequest")]
+    #[serde(rename = "afterRequest", skip_serializing_if = "Option::is_none")]
     pub after_request: Option<CacheState>,
 }
 
-impl Default for Cache {
-    fn default() -> Self {
-        Cache {
-            before_request: None,
-            after_request: None,
-        }
-    }
-}
-
 /// キャッシュ状態
 #[derive(Debug, Deserialize, Serialize)]
 pub struct CacheState {
@@ -165,13 +348,39 @@ pub struct CacheState {
 /// タイミング情報
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Timings {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub blocked: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dns: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub connect: Option<f64>,
+    #[serde(default)]
     pub send: f64,
+    #[serde(default)]
     pub wait: f64,
+    #[serde(default)]
     pub receive: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ssl: Option<f64>,
+    /// 仕様外・ツール独自のフィールド（変換したHARに引き継ぐ）
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Timings {
+    /// 各フェーズの所要時間の合計（ミリ秒）
+    /// 
+    /// 計測されていないフェーズ（-1）は除外し、sslはconnectに含まれるため加算しない
+    /// 
+    /// # Returns
+    /// * `f64` - 合計時間
+    pub fn total(&self) -> f64 {
+        [self.blocked, self.dns, self.connect, Some(self.send), Some(self.wait), Some(self.receive)]
+            .into_iter()
+            .flatten()
+            .filter(|t| *t > 0.0)
+            .fold(0.0, |total, t| total + t)
+    }
 }
 
 impl Default for Timings {
@@ -184,6 +393,7 @@ impl Default for Timings {
             wait: 0.0,
             receive: 0.0,
             ssl: None,
+            extra: Map::new(),
         }
     }
 }
@@ -192,11 +402,84 @@ impl Default for Timings {
 #[derive(Debug, Clone)]
 pub struct AnalysisResult {
     pub timestamp: String,
+    /// ISO 8601形式の時刻（タイムゾーン付き）
+    pub timestamp_iso: String,
+    /// 記録されたタイムゾーンでの開始日時（解析できない場合はNone）
+    pub started_at: Option<NaiveDateTime>,
     pub source_ip: String,
     pub destination_ip: String,
     pub method: String,
     pub status_code: i32,
+    /// ステータスコードの説明（`statusText`）
+    pub status_text: String,
+    /// リダイレクト先のURL（リダイレクトでない場合は空文字列）
+    pub redirect_url: String,
+    /// エントリ全体の所要時間（ミリ秒）
+    pub duration: f64,
+    /// リクエストボディのサイズ（バイト）
+    pub request_size: i64,
+    /// レスポンスボディのサイズ（バイト）
+    pub response_size: i64,
+    /// エンドポイントの所要時間の外れ値かどうか
+    pub latency_outlier: bool,
+    /// 前の通信からの間隔（ミリ秒、最初のリクエストの場合はNone）
+    pub gap: Option<f64>,
+    /// 同一ホストの前の通信からの間隔（ミリ秒、ホストの最初のリクエストの場合はNone）
+    pub host_gap: Option<f64>,
+    /// SOAPリクエストの操作名（SOAPリクエストでない場合はNone）
+    pub soap_operation: Option<String>,
+    /// `--collapse-assets`で集約した同じ静的アセットへのリクエストの件数（集約していない場合は1）
+    pub repeat_count: usize,
+    /// HARのコメント（エントリ・リクエスト・レスポンス）と`--annotate`で指定したメモ（無い場合は空文字列）
+    pub comments: String,
+    /// HAR内のエントリの位置（0始まり）
+    pub entry_index: usize,
+    /// エントリの内容から求めた安定したID
+    pub entry_id: String,
+    /// `--flag-url-regex`・`--flag-index`・`--threat-intel`で注目させる理由（対象でない場合はNone）
+    pub flag: Option<String>,
+    /// TLSの接続情報（記録されていない場合はNone）
+    pub security_details: Option<SecurityDetails>,
+    /// 証明書の有効期限（ISO 8601形式、記録されていない場合はNone）
+    pub cert_expiry: Option<String>,
+    /// リクエスト先のドメインの分類
+    pub domain_category: DomainCategory,
+    /// DoH・gRPC等の通常のHTTPのリクエストと区別するプロトコル
+    pub protocol: TrafficProtocol,
+    /// レスポンスボディの内容から判定した種類
+    pub body_kind: BodyKind,
+    /// 開始時刻が営業時間内かどうか（営業時間を指定していない、または開始時刻を解析できない場合はNone）
+    pub business_hours: Option<bool>,
+    /// サーバーの応答を待った時間（ミリ秒、`timings.wait`）
+    pub wait: f64,
+    /// Server-Timingヘッダーの指標の一覧（記録されていない場合は空文字列）
+    pub server_timing: String,
+    /// Server-Timingヘッダーから求めたサーバーの処理時間（ミリ秒、記録されていない場合はNone）
+    pub server_time: Option<f64>,
+    /// 解析・デコードの警告
+    pub warnings: EntryWarnings,
+    /// 記録されたままのリクエストURL
     pub request_url: String,
-    pub request_payload: String,
-    pub response_payload: String,
+    /// クエリ・フラグメントをデコードしたリクエストURL
+    pub decoded_url: LazyText,
+    /// リクエストURLの構成要素
+    pub url_parts: UrlParts,
+    /// リソースの種類（`_resourceType`、記録されていない場合は空文字列）
+    pub resource_type: String,
+    /// リクエストの発生元のスクリプト等のURL（`_initiator`、記録されていない場合は空文字列）
+    pub initiator_url: String,
+    /// リクエストの発生元の行番号（1始まり、記録されていない場合はNone）
+    pub initiator_line: Option<i64>,
+    /// リクエストの優先度（`_priority`、記録されていない場合は空文字列）
+    pub priority: String,
+    /// レンダリングをブロックするリソースかどうか
+    pub render_blocking: bool,
+    /// 属するページのID（`--group-rows page`で行をグループ化する単位、ページに属さない場合はNone）
+    pub pageref: Option<String>,
+    /// リクエストヘッダー（`--cell-notes`でセルのメモに出力）
+    pub request_headers: Vec<NameValue>,
+    /// レスポンスヘッダー（`--cell-notes`でセルのメモに出力）
+    pub response_headers: Vec<NameValue>,
+    pub request_payload: LazyText,
+    pub response_payload: LazyText,
 }
\ No newline at end of file
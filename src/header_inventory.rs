@@ -0,0 +1,136 @@
+//! リクエスト・レスポンスのヘッダーの一覧（Headers Inventoryシート）の集計を実装
+//! 
+//! ヘッダーの名前ごとに設定したエントリの数・異なる値の数・値の例を集計し、
+//! サービスごとに名前や値の形式が揃っていない独自のヘッダーを確認できるようにする
+
+use crate::har_types::{Entry, NameValue};
+use std::collections::{BTreeMap, HashSet};
+
+/// 値の例の最大文字数
+const MAX_EXAMPLE_CHARS: usize = 200;
+
+/// ヘッダーの方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HeaderDirection {
+    /// リクエストヘッダー
+    Request,
+    /// レスポンスヘッダー
+    Response,
+}
+
+impl HeaderDirection {
+    /// シートに出力する名前
+    /// 
+    /// # Returns
+    /// * `&'static str` - 方向の名前
+    pub fn label(&self) -> &'static str {
+        match self {
+            HeaderDirection::Request => "リクエスト",
+            HeaderDirection::Response => "レスポンス",
+        }
+    }
+}
+
+/// 1つのヘッダーの使用状況
+#[derive(Debug, Clone)]
+pub struct HeaderUsage {
+    /// ヘッダーの方向
+    pub direction: HeaderDirection,
+    /// ヘッダーの名前（小文字）
+    pub name: String,
+    /// 記録された名前の表記（最初に出現した順、大文字と小文字の違いを含む）
+    pub spellings: Vec<String>,
+    /// ヘッダーを設定したエントリの数
+    pub entries: usize,
+    /// 異なる値の数
+    pub distinct_values: usize,
+    /// 値の例（最初に出現した順、長い値は切り詰める）
+    pub examples: Vec<String>,
+}
+
+/// 集計中のヘッダーの使用状況
+#[derive(Default)]
+struct Accumulator {
+    spellings: Vec<String>,
+    entries: usize,
+    values: HashSet<String>,
+    examples: Vec<String>,
+}
+
+/// ヘッダーの名前ごとに使用状況を集計
+/// 
+/// HTTP/2の疑似ヘッダー（`:authority`等）は集計しない
+/// 
+/// # Arguments
+/// * `entries` - HARエントリのイテレータ
+/// * `max_examples` - ヘッダーごとの値の例の最大数
+/// 
+/// # Returns
+/// * `Vec<HeaderUsage>` - 方向・名前の順の使用状況
+pub fn collect<'a>(entries: impl Iterator<Item = &'a Entry>, max_examples: usize) -> Vec<HeaderUsage> {
+    let mut headers: BTreeMap<(HeaderDirection, String), Accumulator> = BTreeMap::new();
+    for entry in entries {
+        for (direction, list) in [
+            (HeaderDirection::Request, &entry.request.headers),
+            (HeaderDirection::Response, &entry.response.headers),
+        ] {
+            add_headers(&mut headers, direction, list, max_examples);
+        }
+    }
+    headers
+        .into_iter()
+        .map(|((direction, name), accumulator)| HeaderUsage {
+            direction,
+            name,
+            spellings: accumulator.spellings,
+            entries: accumulator.entries,
+            distinct_values: accumulator.values.len(),
+            examples: accumulator.examples,
+        })
+        .collect()
+}
+
+/// 1件のエントリのヘッダーを集計に追加
+/// 
+/// # Arguments
+/// * `headers` - (方向, 小文字の名前)ごとの集計
+/// * `direction` - ヘッダーの方向
+/// * `list` - ヘッダーのリスト
+/// * `max_examples` - ヘッダーごとの値の例の最大数
+fn add_headers(
+    headers: &mut BTreeMap<(HeaderDirection, String), Accumulator>,
+    direction: HeaderDirection,
+    list: &[NameValue],
+    max_examples: usize,
+) {
+    // 同じエントリで同じヘッダーが複数回ある場合もエントリの数は1件とする
+    let mut seen: HashSet<String> = HashSet::new();
+    for header in list.iter().filter(|header| !header.name.starts_with(':')) {
+        let name = header.name.to_ascii_lowercase();
+        let accumulator = headers.entry((direction, name.clone())).or_default();
+        if seen.insert(name) {
+            accumulator.entries += 1;
+        }
+        if !accumulator.spellings.contains(&header.name) {
+            accumulator.spellings.push(header.name.clone());
+        }
+        if accumulator.values.insert(header.value.clone()) && accumulator.examples.len() < max_examples {
+            accumulator.examples.push(truncate(&header.value));
+        }
+    }
+}
+
+/// 値の例を切り詰める
+/// 
+/// # Arguments
+/// * `value` - ヘッダーの値
+/// 
+/// # Returns
+/// * `String` - 最大文字数を超える場合は切り詰めて「…」を付けた値
+fn truncate(value: &str) -> String {
+    if value.chars().count() <= MAX_EXAMPLE_CHARS {
+        value.to_string()
+    } else {
+        value.chars().take(MAX_EXAMPLE_CHARS).collect::<String>() + "…"
+    }
+}
@@ -0,0 +1,94 @@
+//! ホストごとの待機時間の分布（Summaryシート）を実装
+//! 
+//! 解析結果の待機時間（`timings.wait`）をホストごとに集め、最小・パーセンタイル・最大の表と
+//! パーセンタイルの棒グラフをSummaryシートに出力し、応答の遅いバックエンドを一目で分かるようにする
+
+use crate::har_types::AnalysisResult;
+use std::collections::BTreeMap;
+
+/// 表に出力するパーセンタイル
+pub const PERCENTILES: &[f64] = &[50.0, 90.0, 95.0, 99.0];
+
+/// ホストごとの待機時間の統計
+#[derive(Debug, Clone)]
+pub struct HostWaitStats {
+    /// ホスト
+    pub host: String,
+    /// リクエスト数（待機時間が記録されていないリクエストを除く）
+    pub requests: usize,
+    /// 最小の待機時間（ミリ秒）
+    pub min: f64,
+    /// 平均の待機時間（ミリ秒）
+    pub mean: f64,
+    /// `PERCENTILES`の順のパーセンタイル（ミリ秒）
+    pub percentiles: Vec<f64>,
+    /// 最大の待機時間（ミリ秒）
+    pub max: f64,
+}
+
+/// ホストごとの待機時間
+#[derive(Debug, Clone, Default)]
+pub struct HostLatency {
+    /// ホストごとの待機時間（ミリ秒）
+    waits: BTreeMap<String, Vec<f64>>,
+}
+
+impl HostLatency {
+    /// 解析結果を1件集計に加える（待機時間が記録されていない場合は無視する）
+    /// 
+    /// # Arguments
+    /// * `result` - 解析結果
+    pub fn add(&mut self, result: &AnalysisResult) {
+        if result.wait < 0.0 || result.url_parts.host.is_empty() {
+            return;
+        }
+        self.waits.entry(result.url_parts.host.clone()).or_default().push(result.wait);
+    }
+
+    /// 集計した通信が無いかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - 通信が無い場合はtrue
+    pub fn is_empty(&self) -> bool {
+        self.waits.is_empty()
+    }
+
+    /// ホストごとの統計
+    /// 
+    /// # Returns
+    /// * `Vec<HostWaitStats>` - 90パーセンタイルの遅い順の統計
+    pub fn hosts(&self) -> Vec<HostWaitStats> {
+        let mut hosts: Vec<HostWaitStats> = self
+            .waits
+            .iter()
+            .map(|(host, waits)| {
+                let mut sorted = waits.clone();
+                sorted.sort_by(f64::total_cmp);
+                HostWaitStats {
+                    host: host.clone(),
+                    requests: sorted.len(),
+                    min: sorted[0],
+                    mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+                    percentiles: PERCENTILES.iter().map(|p| percentile(&sorted, *p)).collect(),
+                    max: sorted[sorted.len() - 1],
+                }
+            })
+            .collect();
+        let p90 = |stats: &HostWaitStats| stats.percentiles[1];
+        hosts.sort_by(|a, b| p90(b).total_cmp(&p90(a)).then_with(|| a.host.cmp(&b.host)));
+        hosts
+    }
+}
+
+/// 最近傍順位法でパーセンタイルを求める
+/// 
+/// # Arguments
+/// * `sorted` - 昇順に並べた値（空でないこと）
+/// * `p` - パーセンタイル（0〜100）
+/// 
+/// # Returns
+/// * `f64` - パーセンタイルの値
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
@@ -0,0 +1,181 @@
+//! HTMLのレスポンスボディの要約を実装
+//! 
+//! 巨大なマークアップをそのままセルに出力する代わりに、タイトル・meta description・表示される本文の抜粋を取り出す
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// 本文の抜粋の最大文字数
+const EXCERPT_MAX_CHARS: usize = 500;
+
+/// `<title>`要素
+static TITLE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title\s*>").expect("固定の正規表現"));
+/// `<meta>`要素
+static META: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?is)<meta\s[^>]*>").expect("固定の正規表現"));
+/// 属性（名前と、引用符で囲まれた値または囲まれていない値）
+static ATTRIBUTE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?is)([a-z_:][-a-z0-9_:.]*)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).expect("固定の正規表現")
+});
+/// 表示されない要素（スクリプト・スタイル・コメント等）
+static INVISIBLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?is)<!--.*?-->|<script\b.*?</script\s*>|<style\b.*?</style\s*>|<noscript\b.*?</noscript\s*>|<template\b.*?</template\s*>|<head\b.*?</head\s*>",
+    )
+    .expect("固定の正規表現")
+});
+/// タグ
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<[^>]*>").expect("固定の正規表現"));
+/// 文字参照
+static ENTITY: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").expect("固定の正規表現"));
+
+/// HTMLのレスポンスボディの出力方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HtmlMode {
+    /// タイトルとmeta descriptionのみ
+    Title,
+    /// タイトル・meta descriptionと本文の抜粋
+    Excerpt,
+    /// マークアップをそのまま出力
+    #[default]
+    Full,
+}
+
+impl HtmlMode {
+    /// `--html-mode`で指定する名前から出力方法を取得
+    /// 
+    /// # Arguments
+    /// * `name` - 出力方法の名前
+    /// 
+    /// # Returns
+    /// * `Option<HtmlMode>` - 対応する出力方法
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "title" => Some(HtmlMode::Title),
+            "excerpt" => Some(HtmlMode::Excerpt),
+            "full" => Some(HtmlMode::Full),
+            _ => None,
+        }
+    }
+}
+
+/// MIMEタイプがHTMLかどうかを判定
+/// 
+/// # Arguments
+/// * `mime_type` - MIMEタイプ
+/// 
+/// # Returns
+/// * `bool` - HTMLの場合はtrue
+pub fn is_html_content(mime_type: &str) -> bool {
+    let mime_type = mime_type.to_ascii_lowercase();
+    mime_type.contains("text/html") || mime_type.contains("application/xhtml+xml")
+}
+
+/// HTMLを出力方法に従って要約
+/// 
+/// # Arguments
+/// * `html` - HTML
+/// * `mode` - 出力方法
+/// 
+/// # Returns
+/// * `String` - 要約したテキスト（`Full`の場合はそのまま）
+pub fn summarize(html: &str, mode: HtmlMode) -> String {
+    if mode == HtmlMode::Full {
+        return html.to_string();
+    }
+
+    let mut lines = Vec::new();
+    if let Some(title) = TITLE.captures(html).map(|c| normalize_text(&c[1])).filter(|t| !t.is_empty()) {
+        lines.push(format!("タイトル: {}", title));
+    }
+    if let Some(description) = meta_description(html) {
+        lines.push(format!("説明: {}", description));
+    }
+    if mode == HtmlMode::Excerpt {
+        let text = visible_text(html);
+        if !text.is_empty() {
+            lines.push(format!("本文: {}", excerpt(&text)));
+        }
+    }
+    lines.join("\n")
+}
+
+/// meta descriptionを取得
+/// 
+/// # Arguments
+/// * `html` - HTML
+/// 
+/// # Returns
+/// * `Option<String>` - meta descriptionの内容
+fn meta_description(html: &str) -> Option<String> {
+    META.find_iter(html).find_map(|meta| {
+        let mut name = None;
+        let mut content = None;
+        for attribute in ATTRIBUTE.captures_iter(meta.as_str()) {
+            let value = attribute.get(2).or(attribute.get(3)).or(attribute.get(4)).map_or("", |v| v.as_str());
+            match attribute[1].to_ascii_lowercase().as_str() {
+                "name" | "property" => name = Some(value.to_ascii_lowercase()),
+                "content" => content = Some(value),
+                _ => {}
+            }
+        }
+        match name.as_deref() {
+            Some("description" | "og:description") => content.map(normalize_text).filter(|c| !c.is_empty()),
+            _ => None,
+        }
+    })
+}
+
+/// 表示される本文のテキストを取得
+/// 
+/// # Arguments
+/// * `html` - HTML
+/// 
+/// # Returns
+/// * `String` - タグを除いて空白をまとめたテキスト
+fn visible_text(html: &str) -> String {
+    let without_invisible = INVISIBLE.replace_all(html, " ");
+    let without_tags = TAG.replace_all(&without_invisible, " ");
+    normalize_text(&without_tags)
+}
+
+/// 文字参照を展開し、連続する空白を1つにまとめる
+/// 
+/// # Arguments
+/// * `text` - テキスト
+/// 
+/// # Returns
+/// * `String` - 整形したテキスト
+fn normalize_text(text: &str) -> String {
+    let decoded = ENTITY.replace_all(text, |caps: &regex::Captures| {
+        let name = &caps[1];
+        let decoded = match name {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ if name.starts_with("#x") || name.starts_with("#X") => {
+                u32::from_str_radix(&name[2..], 16).ok().and_then(char::from_u32)
+            }
+            _ if name.starts_with('#') => name[1..].parse().ok().and_then(char::from_u32),
+            _ => None,
+        };
+        decoded.map_or_else(|| caps[0].to_string(), String::from)
+    });
+    decoded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 本文の先頭を抜粋
+/// 
+/// # Arguments
+/// * `text` - 本文
+/// 
+/// # Returns
+/// * `String` - 最大文字数までの本文（切り詰めた場合は末尾に`…`）
+fn excerpt(text: &str) -> String {
+    match text.char_indices().nth(EXCERPT_MAX_CHARS) {
+        Some((index, _)) => format!("{}…", &text[..index]),
+        None => text.to_string(),
+    }
+}
@@ -0,0 +1,213 @@
+//! 不審な通信のヒューリスティックな検出（`--hunt`）を実装
+//! 
+//! 侵害の調査向けに、IPアドレスへの直接のリクエスト・Punycodeのドメイン・一般的でないポート・
+//! エントロピーの高いURLのパスやリクエストボディ（持ち出しの可能性）・一定間隔のビーコンを検出する。
+//! 解析対象の絞り込みに関わらずキャプチャ全体を調べ、根拠となるエントリとともにSecurity Findingsシートに出力する
+
+use crate::har_types::{Entry, HarFile};
+use crate::security::{self, FindingKind, SecurityFinding};
+use chrono::DateTime;
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use url::Url;
+
+/// 一般的なポート（指定がない場合のスキームの既定のポートを含む）
+const COMMON_PORTS: &[u16] = &[80, 443, 8080, 8443];
+
+/// エントロピーを調べるURLのパスのセグメント・クエリの値の最小の文字数
+const MIN_TOKEN_CHARS: usize = 32;
+
+/// URLのパスのセグメント・クエリの値を高エントロピーとみなす1文字あたりのビット数
+/// （16進数のハッシュ値は最大4ビットのため対象外になる）
+const TOKEN_ENTROPY_BITS: f64 = 4.3;
+
+/// エントロピーを調べるリクエストボディの最小のバイト数
+const MIN_BODY_BYTES: usize = 128;
+
+/// リクエストボディを高エントロピーとみなす1バイトあたりのビット数（JSON・フォームの平文は通常これを下回る）
+const BODY_ENTROPY_BITS: f64 = 5.6;
+
+/// ビーコンとみなす最小のリクエスト数
+const BEACON_MIN_REQUESTS: usize = 5;
+
+/// ビーコンとみなす最小の平均間隔（ミリ秒）
+const BEACON_MIN_INTERVAL_MS: f64 = 1000.0;
+
+/// ビーコンとみなす間隔の変動係数（標準偏差÷平均）の上限
+const BEACON_MAX_VARIATION: f64 = 0.1;
+
+/// 詳細に記載するビーコンのエントリ番号の最大数
+const BEACON_LISTED_ENTRIES: usize = 10;
+
+/// キャプチャ全体から不審な通信を検出
+/// 
+/// # Arguments
+/// * `har` - HARデータ
+/// 
+/// # Returns
+/// * `Vec<SecurityFinding>` - エントリ順の指摘事項（ビーコンは最初のエントリの位置）
+pub fn scan(har: &HarFile) -> Vec<SecurityFinding> {
+    let mut findings = Vec::new();
+    // ビーコンの判定に使用する、ホスト・メソッド・パスごとのリクエストの開始時刻（ミリ秒）
+    let mut series: BTreeMap<(String, String, String), Vec<(usize, i64)>> = BTreeMap::new();
+    for (index, entry) in har.log.entries.iter().enumerate() {
+        let Ok(url) = Url::parse(&entry.request.url) else {
+            continue;
+        };
+        if !matches!(url.scheme(), "http" | "https" | "ws" | "wss") || security::is_loopback(&url) {
+            continue;
+        }
+        let finding = |kind: FindingKind, detail: String| finding_at(kind, index, entry, detail);
+        let host = url.host_str().unwrap_or_default();
+
+        if host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>().is_ok() {
+            findings.push(finding(FindingKind::RawIp, format!("ホスト名を使わずにIPアドレス{}へ接続しています", host)));
+        }
+        let punycode: Vec<&str> = host.split('.').filter(|label| label.to_ascii_lowercase().starts_with("xn--")).collect();
+        if !punycode.is_empty() {
+            let decoded = idna::domain_to_unicode(host).0;
+            findings.push(finding(
+                FindingKind::Punycode,
+                format!("国際化ドメイン名のラベルを含みます: {}（表示上は{}）", punycode.join(", "), decoded),
+            ));
+        }
+        if let Some(port) = url.port()
+            && !COMMON_PORTS.contains(&port)
+        {
+            findings.push(finding(FindingKind::UncommonPort, format!("一般的でないポート{}へ接続しています", port)));
+        }
+        for (location, token) in url_tokens(&url) {
+            let bits = entropy(token.as_bytes());
+            if token.chars().count() >= MIN_TOKEN_CHARS && bits >= TOKEN_ENTROPY_BITS {
+                findings.push(finding(
+                    FindingKind::HighEntropy,
+                    format!("{}のエントロピーが高い値です（{:.2}ビット/文字、{}文字）", location, bits, token.chars().count()),
+                ));
+            }
+        }
+        if let Some(text) = entry.request.post_data.as_ref().and_then(|post_data| post_data.text.as_deref())
+            && text.len() >= MIN_BODY_BYTES
+        {
+            let bits = entropy(text.as_bytes());
+            if bits >= BODY_ENTROPY_BITS {
+                findings.push(finding(
+                    FindingKind::HighEntropy,
+                    format!("リクエストボディのエントロピーが高い値です（{:.2}ビット/バイト、{} bytes）", bits, text.len()),
+                ));
+            }
+        }
+
+        if let Ok(started) = DateTime::parse_from_rfc3339(&entry.started_date_time) {
+            series
+                .entry((host.to_ascii_lowercase(), entry.request.method.clone(), url.path().to_string()))
+                .or_default()
+                .push((index, started.timestamp_millis()));
+        }
+    }
+
+    for ((host, method, path), mut requests) in series {
+        if requests.len() < BEACON_MIN_REQUESTS {
+            continue;
+        }
+        requests.sort_by_key(|(_, started)| *started);
+        let intervals: Vec<f64> = requests.windows(2).map(|pair| (pair[1].1 - pair[0].1) as f64).collect();
+        let mean = intervals.iter().sum::<f64>() / intervals.len() as f64;
+        if mean < BEACON_MIN_INTERVAL_MS {
+            continue;
+        }
+        let variance = intervals.iter().map(|interval| (interval - mean).powi(2)).sum::<f64>() / intervals.len() as f64;
+        let variation = variance.sqrt() / mean;
+        if variation > BEACON_MAX_VARIATION {
+            continue;
+        }
+        let numbers: Vec<String> = requests
+            .iter()
+            .take(BEACON_LISTED_ENTRIES)
+            .map(|(index, _)| (index + 1).to_string())
+            .collect();
+        let more = if requests.len() > BEACON_LISTED_ENTRIES { " ほか" } else { "" };
+        let (first, _) = requests[0];
+        findings.push(finding_at(
+            FindingKind::Beacon,
+            first,
+            &har.log.entries[first],
+            format!(
+                "{} {}{}へ{}件のリクエストを平均{:.1}秒間隔（変動係数{:.3}）で送信しています（エントリ番号: {}{}）",
+                method,
+                host,
+                path,
+                requests.len(),
+                mean / 1000.0,
+                variation,
+                numbers.join(", "),
+                more
+            ),
+        ));
+    }
+    findings.sort_by_key(|finding| finding.entry_number);
+    findings
+}
+
+/// 指摘事項を作成
+/// 
+/// # Arguments
+/// * `kind` - 種類
+/// * `index` - エントリの位置（0始まり）
+/// * `entry` - 根拠となるHARエントリ
+/// * `detail` - 詳細
+/// 
+/// # Returns
+/// * `SecurityFinding` - 指摘事項
+fn finding_at(kind: FindingKind, index: usize, entry: &Entry, detail: String) -> SecurityFinding {
+    SecurityFinding {
+        kind,
+        entry_number: index + 1,
+        timestamp: entry.started_date_time.clone(),
+        method: entry.request.method.clone(),
+        url: entry.request.url.clone(),
+        detail,
+    }
+}
+
+/// エントロピーを調べるURLのパスのセグメントとクエリの値を取得
+/// 
+/// # Arguments
+/// * `url` - リクエストURL
+/// 
+/// # Returns
+/// * `Vec<(String, String)>` - (場所の説明, 値)のリスト
+fn url_tokens(url: &Url) -> Vec<(String, String)> {
+    let segments = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| ("URLのパス".to_string(), segment.to_string()));
+    let values = url
+        .query_pairs()
+        .map(|(name, value)| (format!("クエリパラメータ{}", name), value.into_owned()));
+    segments.chain(values).collect()
+}
+
+/// シャノンエントロピーを求める
+/// 
+/// # Arguments
+/// * `bytes` - 値
+/// 
+/// # Returns
+/// * `f64` - 1バイトあたりのビット数
+fn entropy(bytes: &[u8]) -> f64 {
+    let mut counts = [0usize; 256];
+    for byte in bytes {
+        counts[*byte as usize] += 1;
+    }
+    let total = bytes.len() as f64;
+    counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let p = *count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
@@ -0,0 +1,129 @@
+//! レスポンスヘッダー（Server・X-Powered-By・Via・CDN固有のヘッダー）からのホストごとの基盤の推定を実装
+//! 
+//! キャプチャ中に観測したCDN・プロキシ・バックエンドの概要をInfrastructureシートに出力し、
+//! インシデント対応時に通信経路を把握できるようにする
+
+use crate::har_types::{Entry, NameValue};
+use std::collections::{BTreeMap, BTreeSet};
+use url::Url;
+
+/// CDNを判定するヘッダーの規則
+struct CdnRule {
+    /// CDNの名前
+    cdn: &'static str,
+    /// ヘッダーの名前（小文字）
+    header: &'static str,
+    /// 値に含まれる文字列（小文字、Noneの場合はヘッダーがあれば一致）
+    contains: Option<&'static str>,
+}
+
+/// CDNを判定するヘッダーの規則の一覧
+const CDN_RULES: &[CdnRule] = &[
+    CdnRule { cdn: "Cloudflare", header: "cf-ray", contains: None },
+    CdnRule { cdn: "Cloudflare", header: "server", contains: Some("cloudflare") },
+    CdnRule { cdn: "Amazon CloudFront", header: "x-amz-cf-id", contains: None },
+    CdnRule { cdn: "Amazon CloudFront", header: "via", contains: Some("cloudfront") },
+    CdnRule { cdn: "Fastly", header: "x-fastly-request-id", contains: None },
+    CdnRule { cdn: "Fastly", header: "x-served-by", contains: Some("cache-") },
+    CdnRule { cdn: "Akamai", header: "akamai-grn", contains: None },
+    CdnRule { cdn: "Akamai", header: "x-akamai-transformed", contains: None },
+    CdnRule { cdn: "Akamai", header: "server", contains: Some("akamaighost") },
+    CdnRule { cdn: "Azure Front Door", header: "x-azure-ref", contains: None },
+    CdnRule { cdn: "Google Cloud CDN", header: "via", contains: Some("google") },
+    CdnRule { cdn: "Vercel", header: "x-vercel-id", contains: None },
+    CdnRule { cdn: "Netlify", header: "x-nf-request-id", contains: None },
+    CdnRule { cdn: "Imperva", header: "x-iinfo", contains: None },
+    CdnRule { cdn: "Sucuri", header: "x-sucuri-id", contains: None },
+    CdnRule { cdn: "BunnyCDN", header: "server", contains: Some("bunnycdn") },
+    CdnRule { cdn: "KeyCDN", header: "server", contains: Some("keycdn") },
+];
+
+/// ホストごとに観測した基盤の情報
+#[derive(Debug, Clone, Default)]
+pub struct HostInfrastructure {
+    /// ホスト名
+    pub host: String,
+    /// レスポンスの数
+    pub responses: usize,
+    /// 推定したCDN
+    pub cdns: BTreeSet<&'static str>,
+    /// CDNの推定の根拠としたヘッダーの名前
+    pub evidence: BTreeSet<&'static str>,
+    /// Serverヘッダーの値
+    pub servers: BTreeSet<String>,
+    /// X-Powered-Byヘッダーの値
+    pub powered_by: BTreeSet<String>,
+    /// Viaヘッダーの値
+    pub via: BTreeSet<String>,
+}
+
+impl HostInfrastructure {
+    /// 基盤を推定できる情報が無いかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - CDN・Server・X-Powered-By・Viaのいずれも観測していない場合はtrue
+    pub fn is_empty(&self) -> bool {
+        self.cdns.is_empty() && self.servers.is_empty() && self.powered_by.is_empty() && self.via.is_empty()
+    }
+}
+
+/// ホストごとにレスポンスヘッダーから基盤を推定
+/// 
+/// # Arguments
+/// * `entries` - HARエントリのイテレータ
+/// 
+/// # Returns
+/// * `Vec<HostInfrastructure>` - ホスト名の順の基盤の情報（推定できる情報が無いホストは含めない）
+pub fn fingerprint<'a>(entries: impl Iterator<Item = &'a Entry>) -> Vec<HostInfrastructure> {
+    let mut hosts: BTreeMap<String, HostInfrastructure> = BTreeMap::new();
+    for entry in entries {
+        // レスポンスを受信していないエントリにはヘッダーが無い
+        if entry.response.status <= 0 {
+            continue;
+        }
+        let Some(host) = Url::parse(&entry.request.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+        else {
+            continue;
+        };
+        let infrastructure = hosts.entry(host.clone()).or_insert_with(|| HostInfrastructure {
+            host,
+            ..HostInfrastructure::default()
+        });
+        infrastructure.responses += 1;
+
+        let headers = &entry.response.headers;
+        infrastructure.servers.extend(header_values(headers, "server"));
+        infrastructure.powered_by.extend(header_values(headers, "x-powered-by"));
+        infrastructure.via.extend(header_values(headers, "via"));
+        for rule in CDN_RULES {
+            let matched = header_values(headers, rule.header).any(|value| {
+                rule.contains
+                    .is_none_or(|contains| value.to_ascii_lowercase().contains(contains))
+            });
+            if matched {
+                infrastructure.cdns.insert(rule.cdn);
+                infrastructure.evidence.insert(rule.header);
+            }
+        }
+    }
+    hosts.into_values().filter(|infrastructure| !infrastructure.is_empty()).collect()
+}
+
+/// 指定した名前のヘッダーの値を取得
+/// 
+/// # Arguments
+/// * `headers` - ヘッダーのリスト
+/// * `name` - ヘッダーの名前（小文字）
+/// 
+/// # Returns
+/// * `impl Iterator<Item = String>` - 前後の空白を除いた空でない値のイテレータ
+fn header_values<'a>(headers: &'a [NameValue], name: &'a str) -> impl Iterator<Item = String> + 'a {
+    headers
+        .iter()
+        .filter(move |header| header.name.eq_ignore_ascii_case(name))
+        .map(|header| header.value.trim())
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
@@ -0,0 +1,196 @@
+//! 2つのJSONの構造的な差分（追加・削除・変更されたキー）の検出を実装
+//! 
+//! オブジェクトはキーごとに、配列は位置ごとに比較し、差分をJSONPath形式のパス（例: `$.features[0].enabled`）で表す
+
+use serde_json::Value;
+
+/// 差分の要約に含める値の最大文字数
+const MAX_VALUE_CHARS: usize = 40;
+
+/// 差分の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// 後のJSONにのみ存在する
+    Added,
+    /// 前のJSONにのみ存在する
+    Removed,
+    /// 両方に存在するが値が異なる
+    Changed,
+}
+
+impl ChangeKind {
+    /// 差分の種類を表す記号
+    /// 
+    /// # Returns
+    /// * `&'static str` - 追加は"+"、削除は"-"、変更は"~"
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "+",
+            ChangeKind::Removed => "-",
+            ChangeKind::Changed => "~",
+        }
+    }
+}
+
+/// 1件の差分
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonChange {
+    /// 差分の位置（JSONPath形式）
+    pub path: String,
+    /// 差分の種類
+    pub kind: ChangeKind,
+    /// 前の値（追加の場合はNone）
+    pub before: Option<Value>,
+    /// 後の値（削除の場合はNone）
+    pub after: Option<Value>,
+}
+
+impl JsonChange {
+    /// 差分を1行のテキストに整形
+    /// 
+    /// # Returns
+    /// * `String` - 「記号 パス: 前の値 → 後の値」形式のテキスト（長い値は切り詰める）
+    pub fn to_line(&self) -> String {
+        let values = match self.kind {
+            ChangeKind::Added => compact_value(self.after.as_ref()),
+            ChangeKind::Removed => compact_value(self.before.as_ref()),
+            ChangeKind::Changed => format!(
+                "{} → {}",
+                compact_value(self.before.as_ref()),
+                compact_value(self.after.as_ref())
+            ),
+        };
+        format!("{} {}: {}", self.kind.symbol(), self.path, values)
+    }
+}
+
+/// 2つのJSONの構造的な差分を検出
+/// 
+/// # Arguments
+/// * `before` - 前のJSON
+/// * `after` - 後のJSON
+/// 
+/// # Returns
+/// * `Vec<JsonChange>` - 差分のリスト（前のJSONのキーの順、追加されたキーは後のJSONの順で末尾）
+pub fn diff(before: &Value, after: &Value) -> Vec<JsonChange> {
+    let mut changes = Vec::new();
+    diff_at("$", before, after, &mut changes);
+    changes
+}
+
+/// 差分を1行に要約
+/// 
+/// # Arguments
+/// * `changes` - 差分のリスト
+/// * `max` - 要約に含める差分の最大数
+/// 
+/// # Returns
+/// * `String` - 差分をセミコロン区切りで連結したテキスト（差分が無い場合は空文字列、省略した場合は残りの件数を付ける）
+pub fn summarize(changes: &[JsonChange], max: usize) -> String {
+    let mut parts: Vec<String> = changes.iter().take(max).map(JsonChange::to_line).collect();
+    if changes.len() > max {
+        parts.push(format!("…ほか{}件", changes.len() - max));
+    }
+    parts.join("; ")
+}
+
+/// 指定した位置以下の差分を検出
+/// 
+/// # Arguments
+/// * `path` - 比較する位置
+/// * `before` - 前の値
+/// * `after` - 後の値
+/// * `changes` - 差分の追加先
+fn diff_at(path: &str, before: &Value, after: &Value, changes: &mut Vec<JsonChange>) {
+    match (before, after) {
+        (Value::Object(before_map), Value::Object(after_map)) => {
+            for (key, before_value) in before_map {
+                let child = object_path(path, key);
+                match after_map.get(key) {
+                    Some(after_value) => diff_at(&child, before_value, after_value, changes),
+                    None => changes.push(JsonChange {
+                        path: child,
+                        kind: ChangeKind::Removed,
+                        before: Some(before_value.clone()),
+                        after: None,
+                    }),
+                }
+            }
+            for (key, after_value) in after_map {
+                if !before_map.contains_key(key) {
+                    changes.push(JsonChange {
+                        path: object_path(path, key),
+                        kind: ChangeKind::Added,
+                        before: None,
+                        after: Some(after_value.clone()),
+                    });
+                }
+            }
+        }
+        (Value::Array(before_items), Value::Array(after_items)) => {
+            for index in 0..before_items.len().max(after_items.len()) {
+                let child = format!("{}[{}]", path, index);
+                match (before_items.get(index), after_items.get(index)) {
+                    (Some(before_item), Some(after_item)) => diff_at(&child, before_item, after_item, changes),
+                    (Some(before_item), None) => changes.push(JsonChange {
+                        path: child,
+                        kind: ChangeKind::Removed,
+                        before: Some(before_item.clone()),
+                        after: None,
+                    }),
+                    (None, Some(after_item)) => changes.push(JsonChange {
+                        path: child,
+                        kind: ChangeKind::Added,
+                        before: None,
+                        after: Some(after_item.clone()),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if before != after => changes.push(JsonChange {
+            path: path.to_string(),
+            kind: ChangeKind::Changed,
+            before: Some(before.clone()),
+            after: Some(after.clone()),
+        }),
+        _ => {}
+    }
+}
+
+/// オブジェクトのキーの位置を作成
+/// 
+/// # Arguments
+/// * `path` - オブジェクトの位置
+/// * `key` - キー
+/// 
+/// # Returns
+/// * `String` - 識別子として使えるキーは`.key`、それ以外は`["key"]`を付けた位置
+fn object_path(path: &str, key: &str) -> String {
+    let is_identifier = key
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_identifier {
+        format!("{}.{}", path, key)
+    } else {
+        format!("{}[{}]", path, Value::String(key.to_string()))
+    }
+}
+
+/// 値を要約用の短いテキストに整形
+/// 
+/// # Arguments
+/// * `value` - 値
+/// 
+/// # Returns
+/// * `String` - 1行のJSONテキスト（長い値は切り詰めて「…」を付ける）
+fn compact_value(value: Option<&Value>) -> String {
+    let text = value.map(Value::to_string).unwrap_or_default();
+    if text.chars().count() <= MAX_VALUE_CHARS {
+        text
+    } else {
+        text.chars().take(MAX_VALUE_CHARS).collect::<String>() + "…"
+    }
+}
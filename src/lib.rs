@@ -0,0 +1,84 @@
+//! HARファイル（HTTP Archive）を解析してExcelファイルに出力するライブラリ
+
+pub mod analytics;
+pub mod analyzer;
+pub mod annotations;
+pub mod archive;
+pub mod asset_collapse;
+pub mod beautify;
+pub mod binary_payload;
+pub mod body_kind;
+pub mod budgets;
+pub mod business_hours;
+pub mod cache_lint;
+pub mod clock_skew;
+pub mod columns;
+pub mod compare;
+pub mod compression;
+pub mod conditional;
+pub mod config;
+pub mod conformance;
+pub mod connections;
+pub mod consent;
+pub mod critical_path;
+pub mod csv_export;
+pub mod dependency_graph;
+pub mod duplicates;
+pub mod entry_warnings;
+pub mod egress_cost;
+pub mod encryption;
+pub mod endpoint_sampling;
+pub mod error;
+pub mod excel_append;
+pub mod excel_events;
+pub mod excel_exporter;
+pub mod excel_styles;
+pub mod events;
+pub mod expression;
+pub mod filter;
+pub mod flags;
+pub mod gaps;
+pub mod grpc_web;
+pub mod har_types;
+pub mod header_inventory;
+pub mod host_latency;
+pub mod html;
+pub mod hunt;
+pub mod infrastructure;
+pub mod json_diff;
+pub mod logger;
+pub mod milestones;
+pub mod mime_map;
+pub mod mock_server;
+pub mod notify;
+pub mod openapi;
+pub mod outliers;
+pub mod pagination;
+pub mod pair_diff;
+pub mod payload;
+pub mod payload_cache;
+pub mod privacy;
+pub mod producer;
+pub mod protocol;
+pub mod report;
+pub mod row_grouping;
+pub mod run_info;
+pub mod search;
+pub mod security;
+pub mod server_timing;
+pub mod show;
+pub mod soap;
+pub mod source_maps;
+pub mod stubs;
+pub mod success_rate;
+pub mod summary;
+pub mod synthetic;
+pub mod template_export;
+pub mod threat_intel;
+pub mod tls;
+pub mod traffic;
+pub mod trackers;
+pub mod transform;
+pub mod trend;
+pub mod upload;
+pub mod url_parts;
@@ -1,6 +1,6 @@
 //! ログ設定を実装
 
-use anyhow::Result;
+use crate::error::Result;
 use chrono::Utc;
 use fern::colors::{Color, ColoredLevelConfig};
 use log::LevelFilter;
@@ -13,6 +13,34 @@ use log::LevelFilter;
 /// # Returns
 /// * `Result<()>` - 成功時はOk、失敗時はエラー
 pub fn init_logger(verbose: bool) -> Result<()> {
+    dispatch(verbose).chain(std::io::stdout()).apply()?;
+
+    Ok(())
+}
+
+/// ログを標準エラー出力に出力するようにログシステムを初期化
+/// 
+/// 結果を標準出力に出力するコマンド（show等）で、ログが結果に混ざらないようにする
+/// 
+/// # Arguments
+/// * `verbose` - 詳細ログを出力するかどうか
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn init_stderr_logger(verbose: bool) -> Result<()> {
+    dispatch(verbose).chain(std::io::stderr()).apply()?;
+
+    Ok(())
+}
+
+/// ログの書式と出力レベルを設定
+/// 
+/// # Arguments
+/// * `verbose` - 詳細ログを出力するかどうか
+/// 
+/// # Returns
+/// * `fern::Dispatch` - 出力先を指定する前のログの設定
+fn dispatch(verbose: bool) -> fern::Dispatch {
     let level = if verbose {
         LevelFilter::Debug
     } else {
@@ -36,8 +64,4 @@ pub fn init_logger(verbose: bool) -> Result<()> {
             ))
         })
         .level(level)
-        .chain(std::io::stdout())
-        .apply()?;
-
-    Ok(())
 }
@@ -1,138 +1,72 @@
 //! HARファイル解析アプリケーションのメイン関数
 
-mod analyzer;
-mod excel_exporter;
-mod har_types;
-mod logger;
+mod cli;
 
-use analyzer::HarAnalyzer;
 use anyhow::Result;
-use clap::{Arg, Command};
-use excel_exporter::ExcelExporter;
-use log::{error, info};
-use std::path::Path;
-
-/// アプリケーションの設定
-#[derive(Debug)]
-struct AppConfig {
-    input_file: String,
-    output_file: String,
-    verbose: bool,
-}
-
-impl AppConfig {
-    /// コマンドライン引数から設定を作成
-    /// 
-    /// # Returns
-    /// * `AppConfig` - アプリケーション設定
-    fn from_args() -> Self {
-        let matches = Command::new("rs_har_analyzer")
-            .version("0.1.0")
-            .author("Your Name <your.email@example.com>")
-            .about("HARファイルを解析してExcelファイルに出力するツール")
-            .arg(
-                Arg::new("input")
-                    .short('i')
-                    .long("input")
-                    .value_name("FILE")
-                    .help("入力するHARファイルのパス")
-                    .required(true)
-            )
-            .arg(
-                Arg::new("output")
-                    .short('o')
-                    .long("output")
-                    .value_name("FILE")
-                    .help("出力するExcelファイルのパス")
-                    .default_value("har_analysis.xlsx")
-            )
-            .arg(
-                Arg::new("verbose")
-                    .short('v')
-                    .long("verbose")
-                    .help("詳細ログを出力")
-                    .action(clap::ArgAction::SetTrue)
-            )
-            .get_matches();
-
-        AppConfig {
-            input_file: matches.get_one::<String>("input").unwrap().clone(),
-            output_file: matches.get_one::<String>("output").unwrap().clone(),
-            verbose: matches.get_flag("verbose"),
-        }
-    }
-
-    /// 設定の妥当性を検証
-    /// 
-    /// # Returns
-    /// * `Result<()>` - 成功時はOk、失敗時はエラー
-    fn validate(&self) -> Result<()> {
-        // 入力ファイルの存在確認
-        if !Path::new(&self.input_file).exists() {
-            return Err(anyhow::anyhow!("入力ファイルが見つかりません: {}", self.input_file));
-        }
-
-        // 入力ファイルの拡張子確認
-        if !self.input_file.to_lowercase().ends_with(".har") {
-            return Err(anyhow::anyhow!("入力ファイルはHARファイル(.har)である必要があります"));
-        }
-
-        // 出力ファイルの拡張子確認
-        if !self.output_file.to_lowercase().ends_with(".xlsx") {
-            return Err(anyhow::anyhow!("出力ファイルはExcelファイル(.xlsx)である必要があります"));
-        }
-
-        Ok(())
+use cli::Cli;
+use log::error;
+use rs_har_analyzer::error::AnalyzerError;
+use rs_har_analyzer::logger;
+
+/// 終了コード: 引数・設定ファイルの誤り
+const EXIT_INVALID_ARGUMENT: i32 = 2;
+/// 終了コード: ファイルの読み書きの失敗
+const EXIT_IO: i32 = 3;
+/// 終了コード: HARファイルの解析の失敗
+const EXIT_PARSE: i32 = 4;
+/// 終了コード: 出力ファイルの作成の失敗
+const EXIT_EXPORT: i32 = 5;
+/// 終了コード: サイズの予算の超過（`--fail-on-budget`）
+const EXIT_BUDGET: i32 = 6;
+/// 終了コード: オブジェクトストレージへのアップロードの失敗（`--upload`）
+const EXIT_UPLOAD: i32 = 7;
+
+/// エラーの種類に対応する終了コードを取得
+/// 
+/// # Arguments
+/// * `e` - エラー
+/// 
+/// # Returns
+/// * `i32` - 終了コード
+fn exit_code(e: &AnalyzerError) -> i32 {
+    match e {
+        AnalyzerError::InvalidArgument(_)
+        | AnalyzerError::Config(_)
+        | AnalyzerError::UnsupportedFormat(_) => EXIT_INVALID_ARGUMENT,
+        AnalyzerError::Io { .. } | AnalyzerError::Database(_) => EXIT_IO,
+        AnalyzerError::Parse { .. } => EXIT_PARSE,
+        AnalyzerError::Export { .. } => EXIT_EXPORT,
+        AnalyzerError::BudgetExceeded(_) => EXIT_BUDGET,
+        AnalyzerError::Upload { .. } => EXIT_UPLOAD,
+        AnalyzerError::Logger(_) => 1,
     }
 }
 
-/// アプリケーションを実行
+/// エラーの種類に応じた対処方法を取得
 /// 
 /// # Arguments
-/// * `config` - アプリケーション設定
+/// * `e` - エラー
 /// 
 /// # Returns
-/// * `Result<()>` - 成功時はOk、失敗時はエラー
-async fn run_app(config: AppConfig) -> Result<()> {
-    info!("HARファイル解析を開始します");
-    info!("入力ファイル: {}", config.input_file);
-    info!("出力ファイル: {}", config.output_file);
-
-    // HARファイルの解析
-    let analyzer = HarAnalyzer::new(&config.input_file)?;
-    let results = analyzer.analyze()?;
-
-    if results.is_empty() {
-        info!("解析対象のGET/POSTリクエストが見つかりませんでした");
-        return Ok(());
-    }
-
-    // 解析結果のサマリーを出力
-    info!("解析結果のサマリー:");
-    info!("  - 総エントリ数: {}", results.len());
-    
-    let get_count = results.iter().filter(|r| r.method == "GET").count();
-    let post_count = results.iter().filter(|r| r.method == "POST").count();
-    
-    info!("  - GETリクエスト: {}", get_count);
-    info!("  - POSTリクエスト: {}", post_count);
-
-    // ステータスコード別の集計
-    let mut status_counts = std::collections::HashMap::new();
-    for result in &results {
-        *status_counts.entry(result.status_code).or_insert(0) += 1;
+/// * `Option<&'static str>` - 対処方法
+fn error_hint(e: &AnalyzerError) -> Option<&'static str> {
+    match e {
+        AnalyzerError::Parse { .. } => Some(
+            "HARファイルが途中で切れているか、JSON形式ではない可能性があります。ブラウザから再度エクスポートしてください",
+        ),
+        AnalyzerError::Io { .. } => Some("ファイルのパスとアクセス権限を確認してください"),
+        AnalyzerError::Database(_) => Some(
+            "データベースのパスとアクセス権限を確認し、推移のデータベース以外のファイルを指定していないか確認してください",
+        ),
+        AnalyzerError::Export { .. } => Some(
+            "出力ファイルが他のアプリケーション（Excel等）で開かれていないか確認してください",
+        ),
+        AnalyzerError::Upload { .. } => Some(
+            "認証情報の環境変数（AWS_ACCESS_KEY_ID・GOOGLE_APPLICATION_CREDENTIALS等）とバケットへの書き込み権限を確認してください",
+        ),
+        AnalyzerError::BudgetExceeded(_) => Some("超過したエントリは出力ファイルのBudgetsシートを参照してください"),
+        _ => None,
     }
-
-    info!("  - ステータスコード別集計:");
-    for (status, count) in status_counts.iter() {
-        info!("    {}: {}", status, count);
-    }
-
-    // Excelファイルに出力
-    ExcelExporter::export(&results, &config.output_file)?;
-
-    info!("HARファイル解析が完了しました");
-    Ok(())
 }
 
 /// メイン関数
@@ -141,21 +75,32 @@ async fn run_app(config: AppConfig) -> Result<()> {
 /// * `Result<()>` - 成功時はOk、失敗時はエラー
 #[tokio::main]
 async fn main() -> Result<()> {
-    let config = AppConfig::from_args();
+    let cli = Cli::from_args();
     
     // ログシステムの初期化
-    logger::init_logger(config.verbose)?;
+    // 結果を標準出力に出力するコマンドでは、ログを標準エラー出力に分ける
+    if cli.command.writes_to_stdout() {
+        logger::init_stderr_logger(cli.verbose)?;
+    } else {
+        logger::init_logger(cli.verbose)?;
+    }
 
     // 設定の妥当性検証
-    if let Err(e) = config.validate() {
+    if let Err(e) = cli.command.validate() {
         error!("設定エラー: {}", e);
-        std::process::exit(1);
+        std::process::exit(EXIT_INVALID_ARGUMENT);
     }
 
-    // アプリケーションの実行
-    if let Err(e) = run_app(config).await {
+    // コマンドの実行
+    let result = cli.command.run().await;
+
+    if let Err(e) = result {
         error!("実行エラー: {}", e);
-        std::process::exit(1);
+        let analyzer_error = e.downcast_ref::<AnalyzerError>();
+        if let Some(hint) = analyzer_error.and_then(error_hint) {
+            error!("{}", hint);
+        }
+        std::process::exit(analyzer_error.map_or(1, exit_code));
     }
 
     Ok(())
@@ -0,0 +1,123 @@
+//! ページの読み込みの節目（DOMContentLoaded・load）までの通信の集計を実装
+//! 
+//! HARのpagesとエントリの時刻のみから、各節目までに完了したリクエスト数・受信サイズと、
+//! loadまでに読み込まれたブロッキングリソース（CSS・JavaScript）を求める。
+//! また、DOMContentLoadedより前にパーサーが同期的に読み込んだCSS・JavaScriptをレンダリングをブロックするリソースに分類する
+
+use crate::body_kind::BodyKind;
+use crate::har_types::{Entry, Page};
+use chrono::DateTime;
+
+/// ページの読み込みの節目までの通信の集計
+#[derive(Debug, Clone, Default)]
+pub struct PageMilestones {
+    /// DOMContentLoadedまでに完了したリクエスト数
+    pub requests_before_content_load: usize,
+    /// DOMContentLoadedまでに完了したリクエストのレスポンスサイズの合計（バイト）
+    pub bytes_before_content_load: i64,
+    /// loadまでに完了したリクエスト数
+    pub requests_before_load: usize,
+    /// loadまでに完了したリクエストのレスポンスサイズの合計（バイト）
+    pub bytes_before_load: i64,
+    /// loadまでに読み込まれたブロッキングリソース（URLと所要時間）
+    pub blocking_resources: Vec<String>,
+}
+
+impl PageMilestones {
+    /// ページに属するエントリから節目までの通信を集計
+    /// 
+    /// # Arguments
+    /// * `page` - ページ情報
+    /// * `entries` - ページに属するエントリのリスト
+    /// * `duration` - エントリの所要時間（ミリ秒）を取得する関数
+    /// * `response_size` - エントリのレスポンスサイズ（バイト）を取得する関数
+    /// 
+    /// # Returns
+    /// * `PageMilestones` - 節目までの通信の集計（ページの開始時刻を解析できない場合は空）
+    pub fn compute(
+        page: &Page,
+        entries: &[&Entry],
+        duration: impl Fn(&Entry) -> f64,
+        response_size: impl Fn(&Entry) -> i64,
+    ) -> Self {
+        let mut milestones = PageMilestones::default();
+        let Ok(page_start) = DateTime::parse_from_rfc3339(&page.started_date_time) else {
+            return milestones;
+        };
+        let on_content_load = page.page_timings.on_content_load.filter(|t| *t >= 0.0);
+        let on_load = page.page_timings.on_load.filter(|t| *t >= 0.0);
+
+        for entry in entries {
+            let Ok(started) = DateTime::parse_from_rfc3339(&entry.started_date_time) else {
+                continue;
+            };
+            // ページの開始時刻からエントリが完了するまでの時間
+            let elapsed = (started - page_start).num_milliseconds() as f64;
+            let finished = elapsed + duration(entry).max(0.0);
+            let size = response_size(entry);
+
+            if on_content_load.is_some_and(|milestone| finished <= milestone) {
+                milestones.requests_before_content_load += 1;
+                milestones.bytes_before_content_load += size;
+            }
+            if on_load.is_some_and(|milestone| finished <= milestone) {
+                milestones.requests_before_load += 1;
+                milestones.bytes_before_load += size;
+                if is_blocking_resource(entry) {
+                    milestones
+                        .blocking_resources
+                        .push(format!("{} ({:.0}ms)", entry.request.url, duration(entry)));
+                }
+            }
+        }
+        milestones
+    }
+}
+
+/// レンダリングをブロックするリソースかどうか
+/// 
+/// ページのDOMContentLoadedより前に開始した、パーサーが読み込んだ（`_initiator`が無い場合は発生元を問わない）CSS・JavaScriptのうち、
+/// 優先度（`_priority`）がLow・VeryLowでないものとする。Chromeはasync・deferのスクリプトと`media`が一致しないCSSを低い優先度で読み込む
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// * `page` - エントリが属するページ
+/// 
+/// # Returns
+/// * `bool` - レンダリングをブロックするリソースの場合はtrue
+pub fn is_render_blocking(entry: &Entry, page: &Page) -> bool {
+    let Some(on_content_load) = page.page_timings.on_content_load.filter(|t| *t >= 0.0) else {
+        return false;
+    };
+    let (Ok(page_start), Ok(started)) = (
+        DateTime::parse_from_rfc3339(&page.started_date_time),
+        DateTime::parse_from_rfc3339(&entry.started_date_time),
+    ) else {
+        return false;
+    };
+    let elapsed = (started - page_start).num_milliseconds() as f64;
+    let is_stylesheet_or_script = match entry.resource_type.as_deref() {
+        Some(resource_type) => resource_type == "stylesheet" || resource_type == "script",
+        None => is_blocking_resource(entry),
+    };
+    let parser_initiated = entry
+        .initiator
+        .as_ref()
+        .is_none_or(|initiator| initiator.initiator_type == "parser");
+    let low_priority = entry
+        .priority
+        .as_deref()
+        .is_some_and(|priority| priority == "Low" || priority == "VeryLow");
+    elapsed < on_content_load && is_stylesheet_or_script && parser_initiated && !low_priority
+}
+
+/// 読み込みをブロックし得るリソース（ボディの種類がCSS・JavaScript）かどうか
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `bool` - ブロッキングリソースの場合はtrue
+fn is_blocking_resource(entry: &Entry) -> bool {
+    matches!(BodyKind::of_response(entry), BodyKind::Css | BodyKind::JavaScript)
+}
@@ -0,0 +1,241 @@
+//! 設定ファイルに記述したMIMEタイプの分類（`[mime_types]`）を実装
+//! 
+//! ベンダー固有のMIMEタイプ（例: `application/vnd.mycorp.v2+json`）を分類に対応付け、
+//! JSONの判定・ボディのデコードと整形・圧縮の見積もりの対象の判定に使用する。
+//! 対応付けの無いMIMEタイプは組み込みの判定に従う
+
+use crate::binary_payload::BinaryFormat;
+use crate::grpc_web::{is_grpc_web_content, is_grpc_web_text};
+use crate::html::is_html_content;
+use crate::payload::{is_json_content, TextFormat};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// MIMEタイプの分類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MimeCategory {
+    /// JSON
+    Json,
+    /// 1行ごとのJSON（NDJSON・JSON Lines）
+    Ndjson,
+    /// HTML
+    Html,
+    /// XML
+    Xml,
+    /// その他のテキスト
+    Text,
+    /// JavaScript
+    Script,
+    /// CSS
+    Stylesheet,
+    /// フォント
+    Font,
+    /// 画像
+    Image,
+    /// 音声・動画
+    Media,
+    /// その他のバイナリ
+    Binary,
+}
+
+impl MimeCategory {
+    /// テキストの分類かどうか（圧縮の効果があるかどうか）
+    /// 
+    /// # Returns
+    /// * `bool` - JSON・NDJSON・HTML・XML・テキスト・JavaScript・CSSの場合はtrue
+    pub fn is_text(&self) -> bool {
+        matches!(
+            self,
+            MimeCategory::Json
+                | MimeCategory::Ndjson
+                | MimeCategory::Html
+                | MimeCategory::Xml
+                | MimeCategory::Text
+                | MimeCategory::Script
+                | MimeCategory::Stylesheet
+        )
+    }
+}
+
+/// MIMEタイプから判定したボディのデコード方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyFormat {
+    /// gRPC-web（`text`はBase64のテキスト形式かどうか）
+    GrpcWeb { text: bool },
+    /// MessagePack・CBOR
+    Binary(BinaryFormat),
+    /// テキスト
+    Text(TextFormat),
+}
+
+/// MIMEタイプから分類への対応付け
+#[derive(Debug, Clone, Default)]
+pub struct MimeMap {
+    /// 小文字のMIMEタイプ（パラメータを除く）から分類への対応付け
+    mappings: HashMap<String, MimeCategory>,
+}
+
+impl MimeMap {
+    /// 設定ファイルの対応付けから作成
+    /// 
+    /// # Arguments
+    /// * `mappings` - MIMEタイプから分類への対応付け
+    /// 
+    /// # Returns
+    /// * `MimeMap` - 対応付け（MIMEタイプは大文字と小文字を区別しない）
+    pub fn new(mappings: &BTreeMap<String, MimeCategory>) -> Self {
+        MimeMap {
+            mappings: mappings
+                .iter()
+                .map(|(mime_type, category)| (essence(mime_type), *category))
+                .collect(),
+        }
+    }
+
+    /// 対応付けが無いかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - 対応付けが無い場合はtrue
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    /// 対応付けをMIMEタイプの順に取得
+    /// 
+    /// # Returns
+    /// * `Vec<(&str, MimeCategory)>` - (小文字のMIMEタイプ, 分類)のリスト
+    pub fn entries(&self) -> Vec<(&str, MimeCategory)> {
+        let mut entries: Vec<(&str, MimeCategory)> = self
+            .mappings
+            .iter()
+            .map(|(mime_type, category)| (mime_type.as_str(), *category))
+            .collect();
+        entries.sort_by_key(|(mime_type, _)| *mime_type);
+        entries
+    }
+
+    /// MIMEタイプに対応付けた分類を取得
+    /// 
+    /// # Arguments
+    /// * `mime_type` - MIMEタイプ（charset等のパラメータを含んでもよい）
+    /// 
+    /// # Returns
+    /// * `Option<MimeCategory>` - 分類（対応付けが無い場合はNone）
+    pub fn mapped(&self, mime_type: &str) -> Option<MimeCategory> {
+        if self.mappings.is_empty() {
+            return None;
+        }
+        self.mappings.get(&essence(mime_type)).copied()
+    }
+
+    /// コンテンツタイプがJSONかどうかを判定
+    /// 
+    /// # Arguments
+    /// * `mime_type` - MIMEタイプ
+    /// 
+    /// # Returns
+    /// * `bool` - JSONに対応付けた場合、または対応付けが無く組み込みの判定でJSONの場合はtrue
+    pub fn is_json(&self, mime_type: &str) -> bool {
+        match self.mapped(mime_type) {
+            Some(category) => category == MimeCategory::Json,
+            None => is_json_content(mime_type),
+        }
+    }
+
+    /// コンテンツタイプがHTMLかどうかを判定
+    /// 
+    /// # Arguments
+    /// * `mime_type` - MIMEタイプ
+    /// 
+    /// # Returns
+    /// * `bool` - HTMLに対応付けた場合、または対応付けが無く組み込みの判定でHTMLの場合はtrue
+    pub fn is_html(&self, mime_type: &str) -> bool {
+        match self.mapped(mime_type) {
+            Some(category) => category == MimeCategory::Html,
+            None => is_html_content(mime_type),
+        }
+    }
+
+    /// ボディのデコード方法を判定
+    /// 
+    /// `binary`以外の分類に対応付けたMIMEタイプは、組み込みの判定でgRPC-web・MessagePack・CBORとなる場合も分類に従ってテキストとして扱う
+    /// 
+    /// # Arguments
+    /// * `mime_type` - MIMEタイプ
+    /// 
+    /// # Returns
+    /// * `BodyFormat` - デコード方法
+    pub fn body_format(&self, mime_type: &str) -> BodyFormat {
+        if !matches!(self.mapped(mime_type), None | Some(MimeCategory::Binary)) {
+            return BodyFormat::Text(self.text_format(mime_type));
+        }
+        if is_grpc_web_content(mime_type) {
+            BodyFormat::GrpcWeb { text: is_grpc_web_text(mime_type) }
+        } else if let Some(format) = BinaryFormat::from_mime(mime_type) {
+            BodyFormat::Binary(format)
+        } else {
+            BodyFormat::Text(self.text_format(mime_type))
+        }
+    }
+
+    /// ボディの整形に使用するテキストの形式を判定
+    /// 
+    /// # Arguments
+    /// * `mime_type` - MIMEタイプ
+    /// 
+    /// # Returns
+    /// * `TextFormat` - テキストの形式
+    pub fn text_format(&self, mime_type: &str) -> TextFormat {
+        match self.mapped(mime_type) {
+            Some(MimeCategory::Json) => TextFormat::Json,
+            Some(MimeCategory::Ndjson) => TextFormat::Ndjson,
+            Some(_) => TextFormat::Plain,
+            None => TextFormat::from_mime(mime_type),
+        }
+    }
+}
+
+/// MIMEタイプからパラメータを除いて小文字にする
+/// 
+/// # Arguments
+/// * `mime_type` - MIMEタイプ
+/// 
+/// # Returns
+/// * `String` - 小文字のMIMEタイプ（例: `application/json`）
+fn essence(mime_type: &str) -> String {
+    mime_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(mappings: &[(&str, MimeCategory)]) -> MimeMap {
+        MimeMap::new(&mappings.iter().map(|(mime_type, category)| (mime_type.to_string(), *category)).collect())
+    }
+
+    #[test]
+    fn body_format_uses_builtin_detection_without_mapping() {
+        let map = MimeMap::default();
+        assert_eq!(map.body_format("application/grpc-web-text"), BodyFormat::GrpcWeb { text: true });
+        assert_eq!(map.body_format("application/x-msgpack"), BodyFormat::Binary(BinaryFormat::MessagePack));
+        assert_eq!(map.body_format("application/json; charset=utf-8"), BodyFormat::Text(TextFormat::Json));
+        assert_eq!(map.body_format("text/plain"), BodyFormat::Text(TextFormat::Plain));
+    }
+
+    #[test]
+    fn body_format_follows_mapping_over_builtin_detection() {
+        let map = map(&[
+            ("application/x-msgpack", MimeCategory::Text),
+            ("application/grpc-web+json", MimeCategory::Json),
+            ("application/cbor", MimeCategory::Binary),
+            ("application/vnd.mycorp.v2", MimeCategory::Ndjson),
+        ]);
+        assert_eq!(map.body_format("application/x-msgpack"), BodyFormat::Text(TextFormat::Plain));
+        assert_eq!(map.body_format("Application/gRPC-web+JSON"), BodyFormat::Text(TextFormat::Json));
+        assert_eq!(map.body_format("application/cbor"), BodyFormat::Binary(BinaryFormat::Cbor));
+        assert_eq!(map.body_format("application/vnd.mycorp.v2"), BodyFormat::Text(TextFormat::Ndjson));
+        assert_eq!(map.body_format("application/grpc-web"), BodyFormat::GrpcWeb { text: false });
+    }
+}
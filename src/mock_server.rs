@@ -0,0 +1,679 @@
+//! HARに記録されたレスポンスを返すモックサーバーを実装
+//! 
+//! リクエストはメソッドとパス（指定した場合はホスト名・リクエストボディも）で記録と照合し、
+//! クエリ文字列まで一致する記録があればそれを優先する。同じリクエストが複数記録されている場合は記録順に返す
+//! 
+//! クエリ文字列はパラメーターの順序によらず一致させる
+
+use crate::error::{AnalyzerError, Result};
+use crate::har_types::{Entry, HarFile};
+use base64::Engine;
+use log::{debug, info, warn};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::{TcpListener, TcpStream};
+
+/// 記録から引き継がないレスポンスヘッダー（ボディは展開済みで返し、長さと接続はサーバーが決める）
+const SKIPPED_RESPONSE_HEADERS: &[&str] = &["content-length", "content-encoding", "transfer-encoding", "connection", "keep-alive"];
+
+/// リクエストヘッダーの大きさの上限（バイト）
+const MAX_HEADER_BYTES: usize = 64 * 1024;
+
+/// リクエストボディの大きさの上限（バイト、超える場合は413を返す）
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// モックサーバーのオプション
+#[derive(Debug, Clone, Default)]
+pub struct MockOptions {
+    /// リクエストボディも照合するかどうか
+    pub match_body: bool,
+    /// ホスト名（Hostヘッダー、絶対形式のリクエストターゲットの場合はそのホスト名）も照合するかどうか
+    pub match_host: bool,
+    /// 記録された所要時間だけ待ってから応答するかどうか
+    pub simulate_latency: bool,
+}
+
+/// 記録されたレスポンス
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    /// ホスト名（小文字、ポート番号を除く）
+    host: String,
+    /// 正規化したクエリ文字列（先頭の?を除く）
+    query: String,
+    /// リクエストボディ
+    request_body: String,
+    /// ステータスコード
+    status: u16,
+    /// ステータスの説明
+    status_text: String,
+    /// レスポンスヘッダー
+    headers: Vec<(String, String)>,
+    /// レスポンスボディ
+    body: Vec<u8>,
+    /// 記録された所要時間（ミリ秒）
+    time: f64,
+}
+
+/// 照合で絞り込んだ記録の候補を識別するキー
+/// 
+/// 候補ごとに次に返す記録の位置を持ち、クエリ文字列・ボディが異なるリクエストの再生が互いにずれないようにする
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CandidateKey {
+    /// HTTPメソッド（大文字）
+    method: String,
+    /// パス
+    path: String,
+    /// ホスト名も照合した場合はホスト名
+    host: Option<String>,
+    /// クエリ文字列まで一致した候補の場合は正規化したクエリ文字列
+    query: Option<String>,
+    /// リクエストボディも照合した場合はリクエストボディ
+    body: Option<String>,
+}
+
+/// メソッドとパスごとの記録されたレスポンス
+#[derive(Debug, Default)]
+pub struct MockRoutes {
+    /// (メソッド, パス)ごとの記録（記録順）
+    routes: HashMap<(String, String), Vec<MockResponse>>,
+    /// 候補ごとに次に返す記録の位置
+    cursors: Mutex<HashMap<CandidateKey, usize>>,
+}
+
+impl MockRoutes {
+    /// HARファイルからモックの記録を作成
+    /// 
+    /// ステータスコードが0（通信に失敗した）のエントリは除外する
+    /// 
+    /// # Arguments
+    /// * `har` - HARファイル
+    /// 
+    /// # Returns
+    /// * `MockRoutes` - モックの記録
+    pub fn from_har(har: &HarFile) -> Self {
+        let mut routes: HashMap<(String, String), Vec<MockResponse>> = HashMap::new();
+        for entry in &har.log.entries {
+            let Some((key, response)) = Self::record(entry) else {
+                continue;
+            };
+            routes.entry(key).or_default().push(response);
+        }
+        MockRoutes {
+            routes,
+            cursors: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// エントリをモックの記録に変換
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `Option<((String, String), MockResponse)>` - ((メソッド, パス), 記録)
+    fn record(entry: &Entry) -> Option<((String, String), MockResponse)> {
+        let status = u16::try_from(entry.response.status).ok().filter(|s| (100..=999).contains(s))?;
+        let url = url::Url::parse(&entry.request.url).ok()?;
+
+        let response = MockResponse {
+            host: url.host_str().unwrap_or_default().to_ascii_lowercase(),
+            query: normalize_query(url.query().unwrap_or_default()),
+            request_body: entry
+                .request
+                .post_data
+                .as_ref()
+                .and_then(|post_data| post_data.text.clone())
+                .unwrap_or_default(),
+            status,
+            status_text: entry.response.status_text.clone(),
+            headers: replay_headers(entry),
+            body: response_body(entry),
+            time: entry.time.max(0.0),
+        };
+        Some(((entry.request.method.to_uppercase(), url.path().to_string()), response))
+    }
+
+    /// 記録されたメソッドとパスの組み合わせの数
+    /// 
+    /// # Returns
+    /// * `usize` - 組み合わせの数
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    /// 記録が無いかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - 記録が無い場合はtrue
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// リクエストに一致する記録を取得
+    /// 
+    /// # Arguments
+    /// * `method` - HTTPメソッド
+    /// * `host` - Hostヘッダーの値
+    /// * `target` - リクエストターゲット（パスとクエリ文字列、またはプロキシ向けの絶対形式のURL）
+    /// * `body` - リクエストボディ
+    /// * `options` - モックサーバーのオプション
+    /// 
+    /// # Returns
+    /// * `Option<MockResponse>` - 一致した記録
+    fn find(
+        &self,
+        method: &str,
+        host: Option<&str>,
+        target: &str,
+        body: &str,
+        options: &MockOptions,
+    ) -> Option<MockResponse> {
+        let (authority, target) = split_absolute_target(target);
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let method = method.to_uppercase();
+        let recorded = self.routes.get(&(method.clone(), path.to_string()))?;
+        let host = options.match_host.then(|| authority.or(host).map(host_name)).flatten();
+        if options.match_host && host.is_none() {
+            return None;
+        }
+
+        let candidates: Vec<&MockResponse> = recorded
+            .iter()
+            .filter(|response| host.as_ref().is_none_or(|host| &response.host == host))
+            .filter(|response| !options.match_body || response.request_body == body)
+            .collect();
+        let query = normalize_query(query);
+        let same_query: Vec<&MockResponse> = candidates.iter().copied().filter(|r| r.query == query).collect();
+        let (candidates, query) = if same_query.is_empty() { (candidates, None) } else { (same_query, Some(query)) };
+        if candidates.is_empty() {
+            return None;
+        }
+
+        // 同じリクエストが複数記録されている場合は記録順に返す
+        let key = CandidateKey {
+            method,
+            path: path.to_string(),
+            host,
+            query,
+            body: options.match_body.then(|| body.to_string()),
+        };
+        let mut cursors = self.cursors.lock().unwrap_or_else(|e| e.into_inner());
+        let cursor = cursors.entry(key).or_insert(0);
+        let response = candidates[*cursor % candidates.len()].clone();
+        *cursor += 1;
+        Some(response)
+    }
+}
+
+/// 絶対形式のリクエストターゲット（`http://host/path`）をオーソリティとパス以降に分割
+/// 
+/// # Arguments
+/// * `target` - リクエストターゲット
+/// 
+/// # Returns
+/// * `(Option<&str>, &str)` - (絶対形式の場合はオーソリティ, パスとクエリ文字列)
+fn split_absolute_target(target: &str) -> (Option<&str>, &str) {
+    let Some(rest) = target.strip_prefix("http://").or_else(|| target.strip_prefix("https://")) else {
+        return (None, target);
+    };
+    match rest.find('/') {
+        Some(index) => (Some(&rest[..index]), &rest[index..]),
+        None => (Some(rest), "/"),
+    }
+}
+
+/// オーソリティからホスト名を取得（小文字にし、ポート番号を除く）
+/// 
+/// モックサーバーには記録時と異なるポートで接続するため、ポート番号は照合しない
+/// 
+/// # Arguments
+/// * `authority` - Hostヘッダーの値またはURLのオーソリティ
+/// 
+/// # Returns
+/// * `String` - ホスト名（IPv6アドレスは角括弧を含む）
+fn host_name(authority: &str) -> String {
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    let host = match authority.find(']') {
+        Some(end) if authority.starts_with('[') => &authority[..=end],
+        _ => authority.split(':').next().unwrap_or_default(),
+    };
+    host.to_ascii_lowercase()
+}
+
+/// クエリ文字列を正規化（パラメーターを並べ替え、空のパラメーターを除く）
+/// 
+/// # Arguments
+/// * `query` - クエリ文字列（先頭の?を除く）
+/// 
+/// # Returns
+/// * `String` - 正規化したクエリ文字列
+fn normalize_query(query: &str) -> String {
+    let mut params: Vec<&str> = query.split('&').filter(|param| !param.is_empty()).collect();
+    params.sort_unstable();
+    params.join("&")
+}
+
+/// 記録されたレスポンスボディを取得（Base64でエンコードされている場合はデコード）
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `Vec<u8>` - レスポンスボディ（記録されていない場合は空）
+pub(crate) fn response_body(entry: &Entry) -> Vec<u8> {
+    let content = &entry.response.content;
+    match (content.text.as_deref(), content.encoding.as_deref()) {
+        (Some(text), Some("base64")) => base64::engine::general_purpose::STANDARD
+            .decode(text)
+            .unwrap_or_else(|_| text.as_bytes().to_vec()),
+        (Some(text), _) => text.as_bytes().to_vec(),
+        (None, _) => Vec::new(),
+    }
+}
+
+/// 再生時に返すレスポンスヘッダーを取得
+/// 
+/// ボディの長さや圧縮に関するヘッダーとHTTP/2の疑似ヘッダーは除外する
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `Vec<(String, String)>` - (名前, 値)のリスト
+pub(crate) fn replay_headers(entry: &Entry) -> Vec<(String, String)> {
+    entry
+        .response
+        .headers
+        .iter()
+        .filter(|h| !SKIPPED_RESPONSE_HEADERS.contains(&h.name.to_ascii_lowercase().as_str()))
+        .filter(|h| !h.name.starts_with(':'))
+        .map(|h| (h.name.clone(), h.value.clone()))
+        .collect()
+}
+
+/// モックサーバーを起動し、Ctrl+Cで停止するまでリクエストに応答
+/// 
+/// # Arguments
+/// * `routes` - モックの記録
+/// * `address` - 待ち受けるアドレス
+/// * `options` - モックサーバーのオプション
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub async fn serve(routes: MockRoutes, address: SocketAddr, options: MockOptions) -> Result<()> {
+    let listener = TcpListener::bind(address)
+        .await
+        .map_err(AnalyzerError::io(format!("{}で待ち受けできません", address)))?;
+    info!("モックサーバーを起動しました: http://{} (Ctrl+Cで停止)", address);
+
+    let routes = Arc::new(routes);
+    let options = Arc::new(options);
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, peer) = accepted
+                    .map_err(AnalyzerError::io("接続の受け付けに失敗しました"))?;
+                let routes = Arc::clone(&routes);
+                let options = Arc::clone(&options);
+                tokio::spawn(async move {
+                    if let Err(e) = handle_connection(stream, &routes, &options).await {
+                        debug!("接続を終了しました ({}): {}", peer, e);
+                    }
+                });
+            }
+            _ = tokio::signal::ctrl_c() => {
+                info!("モックサーバーを停止します");
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// 1つの接続のリクエストに順に応答（Keep-Aliveに対応）
+/// 
+/// # Arguments
+/// * `stream` - 接続
+/// * `routes` - モックの記録
+/// * `options` - モックサーバーのオプション
+/// 
+/// # Returns
+/// * `std::io::Result<()>` - 接続が閉じられた場合はOk
+async fn handle_connection(stream: TcpStream, routes: &MockRoutes, options: &MockOptions) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+
+    loop {
+        // リクエスト行
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).await? == 0 {
+            return Ok(());
+        }
+        let mut parts = request_line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Ok(());
+        };
+        let (method, target) = (method.to_string(), target.to_string());
+        let http10 = parts.next() == Some("HTTP/1.0");
+
+        // ヘッダー
+        let mut content_length = 0usize;
+        let mut host = None;
+        let mut transfer_encoding = None;
+        let mut close = http10;
+        let mut header_bytes = 0;
+        loop {
+            let mut line = String::new();
+            let read = reader.read_line(&mut line).await?;
+            header_bytes += read;
+            if read == 0 || header_bytes > MAX_HEADER_BYTES {
+                return Ok(());
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                let value = value.trim();
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.parse().unwrap_or(0);
+                } else if name.eq_ignore_ascii_case("host") {
+                    host = Some(value.to_string());
+                } else if name.eq_ignore_ascii_case("transfer-encoding") {
+                    transfer_encoding = Some(value.to_ascii_lowercase());
+                } else if name.eq_ignore_ascii_case("connection") {
+                    close = value.eq_ignore_ascii_case("close");
+                }
+            }
+        }
+
+        // ボディ（Transfer-EncodingがContent-Lengthより優先。chunked以外は501を返して接続を閉じる）
+        let body = match transfer_encoding.as_deref() {
+            Some("chunked") => read_chunked_body(&mut reader).await?,
+            Some(encoding) => {
+                warn!("{} {} -> 501 (対応していないTransfer-Encodingです: {})", method, target, encoding);
+                let response = plain_response(
+                    501,
+                    "Not Implemented",
+                    format!("対応していないTransfer-Encodingです: {}", encoding),
+                );
+                return write_response(&mut writer, &method, &response, true).await;
+            }
+            None if content_length > MAX_BODY_BYTES => None,
+            None => {
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body).await?;
+                Some(body)
+            }
+        };
+        // 上限を超える場合は残りを読み込まずに413を返して接続を閉じる
+        let Some(body) = body else {
+            warn!("{} {} -> 413 (リクエストボディが上限の{}バイトを超えています)", method, target, MAX_BODY_BYTES);
+            let response = plain_response(
+                413,
+                "Payload Too Large",
+                format!("リクエストボディが大きすぎます（上限: {}バイト）", MAX_BODY_BYTES),
+            );
+            return write_response(&mut writer, &method, &response, true).await;
+        };
+        let body = String::from_utf8_lossy(&body);
+
+        let response = match routes.find(&method, host.as_deref(), &target, &body, options) {
+            Some(recorded) => {
+                info!("{} {} -> {}", method, target, recorded.status);
+                if options.simulate_latency && recorded.time > 0.0 {
+                    tokio::time::sleep(Duration::from_secs_f64(recorded.time / 1000.0)).await;
+                }
+                recorded
+            }
+            None => {
+                warn!("{} {} -> 404 (記録がありません)", method, target);
+                plain_response(404, "Not Found", format!("記録がありません: {} {}", method, target))
+            }
+        };
+
+        write_response(&mut writer, &method, &response, close).await?;
+        if close {
+            return Ok(());
+        }
+    }
+}
+
+/// chunked形式のリクエストボディを読み込んでデコード
+/// 
+/// チャンク拡張とトレーラーは読み飛ばす
+/// 
+/// # Arguments
+/// * `reader` - 接続の読み込み側
+/// 
+/// # Returns
+/// * `std::io::Result<Option<Vec<u8>>>` - 成功時はボディ（上限を超える場合はNone）、形式が不正な場合はエラー
+async fn read_chunked_body<R: AsyncBufReadExt + Unpin>(reader: &mut R) -> std::io::Result<Option<Vec<u8>>> {
+    let invalid = || std::io::Error::new(std::io::ErrorKind::InvalidData, "chunked形式のボディが不正です");
+    let mut body = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 || line.len() > MAX_HEADER_BYTES {
+            return Err(invalid());
+        }
+        let size = line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16).map_err(|_| invalid())?;
+        if size == 0 {
+            break;
+        }
+        if body.len() + size > MAX_BODY_BYTES {
+            return Ok(None);
+        }
+        let start = body.len();
+        body.resize(start + size, 0);
+        reader.read_exact(&mut body[start..]).await?;
+        let mut crlf = [0u8; 2];
+        reader.read_exact(&mut crlf).await?;
+        if &crlf != b"\r\n" {
+            return Err(invalid());
+        }
+    }
+
+    // トレーラー（空行まで）
+    let mut trailer_bytes = 0;
+    loop {
+        let mut line = String::new();
+        let read = reader.read_line(&mut line).await?;
+        trailer_bytes += read;
+        if read == 0 || trailer_bytes > MAX_HEADER_BYTES {
+            return Err(invalid());
+        }
+        if line.trim_end().is_empty() {
+            return Ok(Some(body));
+        }
+    }
+}
+
+/// サーバーが返すテキストのレスポンスを作成
+/// 
+/// # Arguments
+/// * `status` - ステータスコード
+/// * `status_text` - ステータスの説明
+/// * `message` - レスポンスボディ
+/// 
+/// # Returns
+/// * `MockResponse` - レスポンス
+fn plain_response(status: u16, status_text: &str, message: String) -> MockResponse {
+    MockResponse {
+        host: String::new(),
+        query: String::new(),
+        request_body: String::new(),
+        status,
+        status_text: status_text.to_string(),
+        headers: vec![("Content-Type".to_string(), "text/plain; charset=utf-8".to_string())],
+        body: message.into_bytes(),
+        time: 0.0,
+    }
+}
+
+/// レスポンスを書き込み
+/// 
+/// # Arguments
+/// * `writer` - 接続の書き込み側
+/// * `method` - リクエストのHTTPメソッド（HEADの場合はボディを書き込まない）
+/// * `response` - 返すレスポンス
+/// * `close` - 応答後に接続を閉じるかどうか
+/// 
+/// # Returns
+/// * `std::io::Result<()>` - 成功時はOk
+async fn write_response(
+    writer: &mut OwnedWriteHalf,
+    method: &str,
+    response: &MockResponse,
+    close: bool,
+) -> std::io::Result<()> {
+    let mut head = format!("HTTP/1.1 {} {}\r\n", response.status, response.status_text);
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    let body: &[u8] = if method.eq_ignore_ascii_case("HEAD") { &[] } else { &response.body };
+    head.push_str(&format!("Content-Length: {}\r\n", response.body.len()));
+    head.push_str(if close { "Connection: close\r\n\r\n" } else { "Connection: keep-alive\r\n\r\n" });
+
+    writer.write_all(head.as_bytes()).await?;
+    writer.write_all(body).await?;
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(method: &str, url: &str, request_body: &str, status: u16) -> serde_json::Value {
+        json!({
+            "startedDateTime": "2024-01-01T00:00:00.000Z",
+            "time": 1.0,
+            "request": {
+                "method": method, "url": url, "httpVersion": "HTTP/1.1", "headers": [], "queryString": [],
+                "cookies": [], "headersSize": -1, "bodySize": request_body.len(),
+                "postData": { "mimeType": "application/json", "text": request_body }
+            },
+            "response": {
+                "status": status, "statusText": "", "httpVersion": "HTTP/1.1", "headers": [], "cookies": [],
+                "content": { "size": 0, "mimeType": "text/plain", "text": status.to_string() },
+                "redirectURL": "", "headersSize": -1, "bodySize": 0
+            },
+            "timings": { "send": 0.0, "wait": 1.0, "receive": 0.0 }
+        })
+    }
+
+    fn routes(entries: Vec<serde_json::Value>) -> MockRoutes {
+        let har: HarFile = serde_json::from_value(json!({
+            "log": { "version": "1.2", "creator": { "name": "test" }, "entries": entries }
+        }))
+        .unwrap();
+        MockRoutes::from_har(&har)
+    }
+
+    fn status(routes: &MockRoutes, method: &str, target: &str, body: &str, options: &MockOptions) -> Option<u16> {
+        routes.find(method, Some("localhost:8080"), target, body, options).map(|response| response.status)
+    }
+
+    #[test]
+    fn find_prefers_same_query_and_ignores_parameter_order() {
+        let routes = routes(vec![
+            entry("GET", "https://example.com/items?page=1&sort=asc", "", 201),
+            entry("GET", "https://example.com/items?page=2", "", 202),
+        ]);
+        let options = MockOptions::default();
+        assert_eq!(status(&routes, "GET", "/items?sort=asc&page=1", "", &options), Some(201));
+        assert_eq!(status(&routes, "get", "/items?page=2", "", &options), Some(202));
+        assert_eq!(status(&routes, "POST", "/items?page=2", "", &options), None);
+        assert_eq!(status(&routes, "GET", "/other", "", &options), None);
+    }
+
+    #[test]
+    fn find_replays_each_candidate_set_in_recorded_order() {
+        let routes = routes(vec![
+            entry("GET", "https://example.com/poll?id=a", "", 201),
+            entry("GET", "https://example.com/poll?id=b", "", 301),
+            entry("GET", "https://example.com/poll?id=a", "", 202),
+            entry("GET", "https://example.com/poll?id=b", "", 302),
+        ]);
+        let options = MockOptions::default();
+        // 別のクエリ文字列のリクエストを挟んでも、クエリ文字列ごとに記録順に返す
+        assert_eq!(status(&routes, "GET", "/poll?id=a", "", &options), Some(201));
+        assert_eq!(status(&routes, "GET", "/poll?id=b", "", &options), Some(301));
+        assert_eq!(status(&routes, "GET", "/poll?id=a", "", &options), Some(202));
+        assert_eq!(status(&routes, "GET", "/poll?id=b", "", &options), Some(302));
+        assert_eq!(status(&routes, "GET", "/poll?id=a", "", &options), Some(201));
+    }
+
+    #[test]
+    fn find_matches_body_only_when_requested() {
+        let routes = routes(vec![
+            entry("POST", "https://example.com/login", "{\"user\":\"a\"}", 200),
+            entry("POST", "https://example.com/login", "{\"user\":\"b\"}", 401),
+        ]);
+        let options = MockOptions { match_body: true, ..MockOptions::default() };
+        assert_eq!(status(&routes, "POST", "/login", "{\"user\":\"b\"}", &options), Some(401));
+        assert_eq!(status(&routes, "POST", "/login", "{\"user\":\"a\"}", &options), Some(200));
+        assert_eq!(status(&routes, "POST", "/login", "{\"user\":\"b\"}", &options), Some(401));
+        assert_eq!(status(&routes, "POST", "/login", "{\"user\":\"c\"}", &options), None);
+
+        let options = MockOptions::default();
+        assert_eq!(status(&routes, "POST", "/login", "{\"user\":\"c\"}", &options), Some(200));
+    }
+
+    #[test]
+    fn from_har_skips_failed_entries() {
+        let routes = routes(vec![entry("GET", "https://example.com/a", "", 0), entry("GET", "https://example.com/b", "", 200)]);
+        assert_eq!(routes.len(), 1);
+    }
+
+    #[test]
+    fn find_separates_origins_only_with_match_host() {
+        let routes = routes(vec![
+            entry("GET", "https://api.example.com/config", "", 201),
+            entry("GET", "https://cdn.example.com:8443/config", "", 202),
+        ]);
+        let options = MockOptions { match_host: true, ..MockOptions::default() };
+        let find = |host: Option<&str>, target: &str| {
+            routes.find("GET", host, target, "", &options).map(|response| response.status)
+        };
+        assert_eq!(find(Some("CDN.example.com:8080"), "/config"), Some(202));
+        assert_eq!(find(Some("api.example.com"), "/config"), Some(201));
+        assert_eq!(find(Some("api.example.com"), "/config"), Some(201));
+        // プロキシ向けの絶対形式のリクエストターゲットはHostヘッダーより優先する
+        assert_eq!(find(Some("api.example.com"), "http://cdn.example.com/config"), Some(202));
+        assert_eq!(find(Some("other.example.com"), "/config"), None);
+        assert_eq!(find(None, "/config"), None);
+
+        let options = MockOptions::default();
+        assert_eq!(status(&routes, "GET", "/config", "", &options), Some(201));
+        assert_eq!(status(&routes, "GET", "/config", "", &options), Some(202));
+    }
+
+    #[test]
+    fn host_name_drops_port_and_case() {
+        assert_eq!(host_name("Example.COM:8080"), "example.com");
+        assert_eq!(host_name("[::1]:8080"), "[::1]");
+        assert_eq!(host_name("user@example.com"), "example.com");
+        assert_eq!(split_absolute_target("http://example.com/a?b=1"), (Some("example.com"), "/a?b=1"));
+        assert_eq!(split_absolute_target("https://example.com"), (Some("example.com"), "/"));
+        assert_eq!(split_absolute_target("/a"), (None, "/a"));
+    }
+
+    #[tokio::test]
+    async fn read_chunked_body_decodes_chunks_and_skips_trailers() {
+        let mut input: &[u8] = b"5;ext=1\r\nhello\r\n6\r\n world\r\n0\r\nX-Trailer: 1\r\n\r\nGET /next";
+        let body = read_chunked_body(&mut input).await.unwrap();
+        assert_eq!(body.as_deref(), Some(&b"hello world"[..]));
+        assert_eq!(input, b"GET /next");
+
+        let mut invalid: &[u8] = b"zz\r\nhello\r\n0\r\n\r\n";
+        assert!(read_chunked_body(&mut invalid).await.is_err());
+
+        let oversized = format!("{:x}\r\n", MAX_BODY_BYTES + 1);
+        assert_eq!(read_chunked_body(&mut oversized.as_bytes()).await.unwrap(), None);
+    }
+}
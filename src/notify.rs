@@ -0,0 +1,95 @@
+//! 解析結果のサマリーのWebhook（Slack・Microsoft Teams）への通知を実装
+//! 
+//! 定期的に実行する解析の結果をチャンネルに自動で投稿できるように、出力の完了後に
+//! リクエスト数・エラー率・予算の超過・出力先を`{"text": ...}`の形式でWebhookに送信する。
+//! SlackのIncoming WebhookとTeamsのIncoming Webhookはどちらもこの形式を受け付ける
+
+use crate::error::{AnalyzerError, Result};
+use crate::summary::Summary;
+use log::info;
+use serde_json::json;
+use std::path::Path;
+use std::time::Duration;
+
+/// Webhookへの送信のタイムアウト
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 通知する実行結果
+#[derive(Debug)]
+pub struct RunNotification<'a> {
+    /// 解析した入力（ファイル名またはバッチ処理の説明）
+    pub subject: String,
+    /// 解析結果のサマリー
+    pub summary: &'a Summary,
+    /// 出力したファイルのパスまたはURL
+    pub outputs: Vec<String>,
+    /// 処理に失敗したファイルの数（バッチ処理の場合）
+    pub failures: usize,
+}
+
+impl RunNotification<'_> {
+    /// 通知するメッセージを作成
+    /// 
+    /// # Returns
+    /// * `String` - メッセージ（Slackのmrkdwn形式）
+    pub fn message(&self) -> String {
+        let summary = self.summary;
+        let failed: usize = summary
+            .status_counts
+            .iter()
+            .filter(|(status, _)| !(1..400).contains(*status))
+            .map(|(_, count)| count)
+            .sum();
+        let methods: Vec<String> = summary
+            .method_counts
+            .iter()
+            .map(|(method, count)| format!("{} {}", method, count))
+            .collect();
+
+        let mut lines = vec![format!("*HAR解析が完了しました*: {}", self.subject)];
+        lines.push(format!("• 解析したリクエスト: {}件（{}）", summary.total, methods.join(" / ")));
+        lines.push(format!(
+            "• エラー率: {:.1}%（ステータスコード0・400以上 {}件）",
+            failed as f64 / summary.total.max(1) as f64 * 100.0,
+            failed
+        ));
+        lines.push(format!("• サイズの予算の超過: {}件", summary.budget_violations));
+        if summary.render_blocking > 0 {
+            lines.push(format!("• レンダリングをブロックするリソース: {}件", summary.render_blocking));
+        }
+        if self.failures > 0 {
+            lines.push(format!("• 処理に失敗したファイル: {}件", self.failures));
+        }
+        for output in &self.outputs {
+            lines.push(format!("• 出力: {}", output));
+        }
+        lines.join("\n")
+    }
+
+    /// メッセージをWebhookに送信
+    /// 
+    /// # Arguments
+    /// * `url` - WebhookのURL
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub fn post(&self, url: &str) -> Result<()> {
+        ureq::post(url)
+            .timeout(TIMEOUT)
+            .send_json(json!({ "text": self.message() }))
+            .map_err(AnalyzerError::export("Webhookへの通知に失敗しました"))?;
+        info!("解析結果をWebhookに通知しました");
+        Ok(())
+    }
+}
+
+/// 通知に記載する出力先のパス（絶対パスに変換できない場合は指定されたパス）
+/// 
+/// # Arguments
+/// * `path` - 出力先のパス
+/// 
+/// # Returns
+/// * `String` - 出力先のパス
+pub fn output_location(path: &Path) -> String {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf()).display().to_string()
+}
@@ -0,0 +1,471 @@
+//! OpenAPI仕様書（3.x）の読み込みとJSONスキーマの検証を実装
+//! 
+//! 仕様書の全機能ではなく、キャプチャとの突き合わせに必要なパス・メソッド・レスポンスのステータスコードと、
+//! JSONスキーマの主要なキーワード（type, enum, required, properties, items, allOf/anyOf/oneOf等）のみを扱う
+
+use crate::error::{AnalyzerError, Result};
+use log::info;
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use url::Url;
+
+/// $refの解決を打ち切る深さ（循環参照対策）
+const MAX_SCHEMA_DEPTH: usize = 64;
+
+/// 仕様書に記載された操作（パスとメソッドの組み合わせ）
+#[derive(Debug, Clone)]
+pub struct Operation {
+    /// HTTPメソッド（大文字）
+    pub method: String,
+    /// パスのテンプレート（例: "/users/{id}"）
+    pub path: String,
+    /// パスのテンプレートに一致する正規表現
+    pattern: Regex,
+    /// パスパラメータの数（少ないほど優先して一致させる）
+    parameter_count: usize,
+    /// リクエストボディのJSONスキーマ
+    pub request_schema: Option<Value>,
+    /// ステータスコード（"200"、"2XX"、"default"）ごとのレスポンスのJSONスキーマ
+    pub responses: Vec<(String, Option<Value>)>,
+}
+
+impl Operation {
+    /// ステータスコードが仕様書に記載されているか確認し、対応するレスポンスのスキーマを取得
+    /// 
+    /// # Arguments
+    /// * `status` - ステータスコード
+    /// 
+    /// # Returns
+    /// * `Option<Option<&Value>>` - 記載されている場合はSome（スキーマが無い場合はSome(None)）
+    pub fn response_schema(&self, status: i32) -> Option<Option<&Value>> {
+        let exact = status.to_string();
+        let range = format!("{}XX", status / 100);
+        [exact.as_str(), range.as_str(), "default"]
+            .iter()
+            .find_map(|key| {
+                self.responses
+                    .iter()
+                    .find(|(code, _)| code.eq_ignore_ascii_case(key))
+                    .map(|(_, schema)| schema.as_ref())
+            })
+    }
+}
+
+/// OpenAPI仕様書
+#[derive(Debug)]
+pub struct OpenApiSpec {
+    /// 仕様書全体（$refの解決に使用）
+    document: Value,
+    /// serversに記載されたホスト名（記載が無い、または相対URLの場合は空）
+    pub hosts: Vec<String>,
+    /// serversに記載されたベースパス（例: "/v1"）
+    base_paths: Vec<String>,
+    /// 記載された操作
+    pub operations: Vec<Operation>,
+}
+
+impl OpenApiSpec {
+    /// 仕様書（YAMLまたはJSON）を読み込み
+    /// 
+    /// # Arguments
+    /// * `file_path` - 仕様書のパス
+    /// 
+    /// # Returns
+    /// * `Result<OpenApiSpec>` - 成功時は仕様書、失敗時はエラー
+    pub fn load(file_path: &str) -> Result<Self> {
+        info!("OpenAPI仕様書を読み込んでいます: {}", file_path);
+
+        let content = fs::read_to_string(file_path)
+            .map_err(AnalyzerError::io("OpenAPI仕様書の読み込みに失敗しました"))?;
+        let is_json = Path::new(file_path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let document: Value = if is_json {
+            serde_json::from_str(&content)?
+        } else {
+            serde_yaml::from_str(&content)
+                .map_err(|e| AnalyzerError::Config(format!("OpenAPI仕様書の解析に失敗しました: {}", e)))?
+        };
+
+        let spec = Self::from_document(document)?;
+        info!("OpenAPI仕様書の読み込みが完了しました。操作数: {}", spec.operations.len());
+        Ok(spec)
+    }
+
+    /// 解析済みの仕様書から操作の一覧を作成
+    /// 
+    /// # Arguments
+    /// * `document` - 仕様書
+    /// 
+    /// # Returns
+    /// * `Result<OpenApiSpec>` - 成功時は仕様書、失敗時はエラー
+    pub fn from_document(document: Value) -> Result<Self> {
+        let paths = document
+            .get("paths")
+            .and_then(Value::as_object)
+            .ok_or_else(|| AnalyzerError::Config("OpenAPI仕様書にpathsがありません".to_string()))?;
+
+        let mut operations = Vec::new();
+        for (path, item) in paths {
+            let Some(item) = item.as_object() else {
+                continue;
+            };
+            for (method, operation) in item {
+                if !["get", "put", "post", "delete", "options", "head", "patch", "trace"].contains(&method.as_str()) {
+                    continue;
+                }
+                operations.push(Self::operation(path, method, operation)?);
+            }
+        }
+        // パスパラメータの少ない（より具体的な）操作を優先して一致させる
+        operations.sort_by_key(|operation| operation.parameter_count);
+
+        let mut hosts = Vec::new();
+        let mut base_paths = Vec::new();
+        for server in document.get("servers").and_then(Value::as_array).into_iter().flatten() {
+            let Some(url) = server.get("url").and_then(Value::as_str) else {
+                continue;
+            };
+            let path = match Url::parse(url) {
+                Ok(parsed) => {
+                    if let Some(host) = parsed.host_str() {
+                        hosts.push(host.to_lowercase());
+                    }
+                    parsed.path().to_string()
+                }
+                Err(_) => url.to_string(),
+            };
+            let path = path.trim_end_matches('/');
+            if !path.is_empty() {
+                base_paths.push(path.to_string());
+            }
+        }
+
+        Ok(OpenApiSpec {
+            document,
+            hosts,
+            base_paths,
+            operations,
+        })
+    }
+
+    /// 仕様書のパス項目から操作を作成
+    /// 
+    /// # Arguments
+    /// * `path` - パスのテンプレート
+    /// * `method` - HTTPメソッド（小文字）
+    /// * `operation` - 操作の定義
+    /// 
+    /// # Returns
+    /// * `Result<Operation>` - 成功時は操作、失敗時はエラー
+    fn operation(path: &str, method: &str, operation: &Value) -> Result<Operation> {
+        let placeholder = Regex::new(r"\\\{[^}]+\\\}").expect("固定の正規表現");
+        let escaped = regex::escape(path);
+        let parameter_count = placeholder.find_iter(&escaped).count();
+        let pattern = format!("^{}/?$", placeholder.replace_all(&escaped, "[^/]+"));
+        let pattern = Regex::new(&pattern)
+            .map_err(|e| AnalyzerError::Config(format!("パスを解析できません: {} ({})", path, e)))?;
+
+        let request_schema = operation
+            .pointer("/requestBody/content")
+            .and_then(Self::json_schema);
+        let responses = operation
+            .get("responses")
+            .and_then(Value::as_object)
+            .map(|responses| {
+                responses
+                    .iter()
+                    .map(|(code, response)| {
+                        let schema = response.get("content").and_then(Self::json_schema);
+                        (code.clone(), schema)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Operation {
+            method: method.to_uppercase(),
+            path: path.to_string(),
+            pattern,
+            parameter_count,
+            request_schema,
+            responses,
+        })
+    }
+
+    /// contentからJSONのメディアタイプのスキーマを取得
+    /// 
+    /// # Arguments
+    /// * `content` - メディアタイプごとの定義
+    /// 
+    /// # Returns
+    /// * `Option<Value>` - JSONのスキーマ
+    fn json_schema(content: &Value) -> Option<Value> {
+        content
+            .as_object()?
+            .iter()
+            .find(|(media_type, _)| media_type.contains("json"))
+            .and_then(|(_, media)| media.get("schema"))
+            .cloned()
+    }
+
+    /// URLが仕様書の対象のホストかどうか（serversにホストの記載が無い場合は全て対象）
+    /// 
+    /// # Arguments
+    /// * `url` - リクエストURL
+    /// 
+    /// # Returns
+    /// * `bool` - 対象の場合はtrue
+    pub fn covers(&self, url: &Url) -> bool {
+        self.hosts.is_empty()
+            || url
+                .host_str()
+                .is_some_and(|host| self.hosts.iter().any(|h| h.eq_ignore_ascii_case(host)))
+    }
+
+    /// リクエストURLのパスと、serversのベースパスを除いたパスの候補
+    /// 
+    /// # Arguments
+    /// * `path` - リクエストURLのパス
+    /// 
+    /// # Returns
+    /// * `Vec<&str>` - 仕様書のパスと照合するパスの候補
+    fn candidate_paths<'a>(&self, path: &'a str) -> Vec<&'a str> {
+        let mut candidates = vec![path];
+        candidates.extend(
+            self.base_paths
+                .iter()
+                .filter_map(|base| path.strip_prefix(base.as_str()))
+                .filter(|rest| rest.is_empty() || rest.starts_with('/')),
+        );
+        candidates
+    }
+
+    /// メソッドとパスに一致する操作を取得
+    /// 
+    /// # Arguments
+    /// * `method` - HTTPメソッド
+    /// * `path` - リクエストURLのパス
+    /// 
+    /// # Returns
+    /// * `Option<&Operation>` - 一致した操作
+    pub fn find_operation(&self, method: &str, path: &str) -> Option<&Operation> {
+        let candidates = self.candidate_paths(path);
+        self.operations.iter().find(|operation| {
+            operation.method.eq_ignore_ascii_case(method)
+                && candidates.iter().any(|candidate| operation.pattern.is_match(candidate))
+        })
+    }
+
+    /// パスが仕様書に記載されているか（メソッドを問わない）
+    /// 
+    /// # Arguments
+    /// * `path` - リクエストURLのパス
+    /// 
+    /// # Returns
+    /// * `bool` - 記載されている場合はtrue
+    pub fn has_path(&self, path: &str) -> bool {
+        let candidates = self.candidate_paths(path);
+        self.operations
+            .iter()
+            .any(|operation| candidates.iter().any(|candidate| operation.pattern.is_match(candidate)))
+    }
+
+    /// 値をJSONスキーマで検証
+    /// 
+    /// # Arguments
+    /// * `schema` - JSONスキーマ
+    /// * `value` - 検証する値
+    /// 
+    /// # Returns
+    /// * `Vec<String>` - 違反の内容のリスト（違反が無い場合は空）
+    pub fn validate(&self, schema: &Value, value: &Value) -> Vec<String> {
+        let mut errors = Vec::new();
+        self.validate_at(schema, value, "$", 0, &mut errors);
+        errors
+    }
+
+    /// 値をJSONスキーマで再帰的に検証
+    /// 
+    /// # Arguments
+    /// * `schema` - JSONスキーマ
+    /// * `value` - 検証する値
+    /// * `location` - 値の位置（例: "$.items[0].id"）
+    /// * `depth` - 再帰の深さ
+    /// * `errors` - 違反の内容を追加するリスト
+    fn validate_at(&self, schema: &Value, value: &Value, location: &str, depth: usize, errors: &mut Vec<String>) {
+        if depth > MAX_SCHEMA_DEPTH {
+            return;
+        }
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            match reference.strip_prefix('#').and_then(|pointer| self.document.pointer(pointer)) {
+                Some(resolved) => self.validate_at(resolved, value, location, depth + 1, errors),
+                None => errors.push(format!("{}: 参照を解決できません: {}", location, reference)),
+            }
+            return;
+        }
+
+        if value.is_null() && schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+            return;
+        }
+
+        for sub_schema in schema.get("allOf").and_then(Value::as_array).into_iter().flatten() {
+            self.validate_at(sub_schema, value, location, depth + 1, errors);
+        }
+        for (keyword, exactly_one) in [("anyOf", false), ("oneOf", true)] {
+            let Some(sub_schemas) = schema.get(keyword).and_then(Value::as_array) else {
+                continue;
+            };
+            let matched = sub_schemas
+                .iter()
+                .filter(|sub_schema| {
+                    let mut sub_errors = Vec::new();
+                    self.validate_at(sub_schema, value, location, depth + 1, &mut sub_errors);
+                    sub_errors.is_empty()
+                })
+                .count();
+            if matched == 0 || (exactly_one && matched > 1) {
+                errors.push(format!("{}: {}のいずれのスキーマにも一致しません", location, keyword));
+            }
+        }
+
+        if let Some(expected) = schema.get("type") {
+            let types: Vec<&str> = match expected {
+                Value::String(name) => vec![name.as_str()],
+                Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !types.is_empty() && !types.iter().any(|name| Self::is_type(value, name)) {
+                errors.push(format!("{}: 型が{}ではありません（値: {}）", location, types.join("|"), Self::describe(value)));
+                return;
+            }
+        }
+
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+            && !allowed.contains(value)
+        {
+            errors.push(format!("{}: 許可されていない値です（値: {}）", location, Self::describe(value)));
+        }
+
+        match value {
+            Value::Object(object) => {
+                for name in schema.get("required").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str) {
+                    if !object.contains_key(name) {
+                        errors.push(format!("{}: 必須のプロパティ{}がありません", location, name));
+                    }
+                }
+                let properties = schema.get("properties").and_then(Value::as_object);
+                for (name, property) in object {
+                    let property_location = format!("{}.{}", location, name);
+                    match properties.and_then(|properties| properties.get(name)) {
+                        Some(property_schema) => {
+                            self.validate_at(property_schema, property, &property_location, depth + 1, errors);
+                        }
+                        None => match schema.get("additionalProperties") {
+                            Some(Value::Bool(false)) => {
+                                errors.push(format!("{}: 定義されていないプロパティです", property_location));
+                            }
+                            Some(additional) if additional.is_object() => {
+                                self.validate_at(additional, property, &property_location, depth + 1, errors);
+                            }
+                            _ => {}
+                        },
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(item_schema) = schema.get("items") {
+                    for (index, item) in items.iter().enumerate() {
+                        self.validate_at(item_schema, item, &format!("{}[{}]", location, index), depth + 1, errors);
+                    }
+                }
+                if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+                    && (items.len() as u64) < min
+                {
+                    errors.push(format!("{}: 要素数が{}未満です", location, min));
+                }
+                if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+                    && (items.len() as u64) > max
+                {
+                    errors.push(format!("{}: 要素数が{}を超えています", location, max));
+                }
+            }
+            Value::String(text) => {
+                let length = text.chars().count() as u64;
+                if let Some(min) = schema.get("minLength").and_then(Value::as_u64)
+                    && length < min
+                {
+                    errors.push(format!("{}: 文字数が{}未満です", location, min));
+                }
+                if let Some(max) = schema.get("maxLength").and_then(Value::as_u64)
+                    && length > max
+                {
+                    errors.push(format!("{}: 文字数が{}を超えています", location, max));
+                }
+                if let Some(pattern) = schema.get("pattern").and_then(Value::as_str)
+                    && let Ok(regex) = Regex::new(pattern)
+                    && !regex.is_match(text)
+                {
+                    errors.push(format!("{}: パターン{}に一致しません", location, pattern));
+                }
+            }
+            Value::Number(number) => {
+                let Some(number) = number.as_f64() else {
+                    return;
+                };
+                if let Some(min) = schema.get("minimum").and_then(Value::as_f64)
+                    && number < min
+                {
+                    errors.push(format!("{}: {}未満です（値: {}）", location, min, number));
+                }
+                if let Some(max) = schema.get("maximum").and_then(Value::as_f64)
+                    && number > max
+                {
+                    errors.push(format!("{}: {}を超えています（値: {}）", location, max, number));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 値がJSONスキーマの型に一致するかどうか
+    /// 
+    /// # Arguments
+    /// * `value` - 値
+    /// * `name` - 型の名前
+    /// 
+    /// # Returns
+    /// * `bool` - 一致する場合はtrue
+    fn is_type(value: &Value, name: &str) -> bool {
+        match name {
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0),
+            "boolean" => value.is_boolean(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            "null" => value.is_null(),
+            _ => true,
+        }
+    }
+
+    /// エラーメッセージに表示する値（長い場合は省略）
+    /// 
+    /// # Arguments
+    /// * `value` - 値
+    /// 
+    /// # Returns
+    /// * `String` - 表示用の文字列
+    fn describe(value: &Value) -> String {
+        const MAX_LENGTH: usize = 50;
+        let text = value.to_string();
+        if text.chars().count() > MAX_LENGTH {
+            format!("{}...", text.chars().take(MAX_LENGTH).collect::<String>())
+        } else {
+            text
+        }
+    }
+}
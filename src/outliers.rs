@@ -0,0 +1,143 @@
+//! エンドポイントごとの所要時間の外れ値判定を実装
+//! 
+//! エンドポイント（メソッドとクエリ文字列を除いたURL）ごとに所要時間の中央値と
+//! 中央絶対偏差（MAD）を求め、中央値からMADの3倍を超えて遅い通信を外れ値とする。
+//! 全体で一律の閾値を設けるより、応答時間の傾向が異なるエンドポイントが混在する場合に有効
+
+use crate::events::TrafficEvent;
+use crate::har_types::AnalysisResult;
+use std::collections::BTreeMap;
+use url::Url;
+
+/// 外れ値と判定するMADの倍数
+pub const MAD_THRESHOLD: f64 = 3.0;
+
+/// 外れ値の判定に必要なエンドポイントあたりの最小件数
+pub const MIN_SAMPLES: usize = 5;
+
+/// 正規分布を仮定した場合にMADを標準偏差相当に換算する係数
+const MAD_SCALE: f64 = 1.4826;
+
+/// 外れ値を含むエンドポイントの集計
+#[derive(Debug, Clone)]
+pub struct EndpointOutliers {
+    /// エンドポイント（例: "GET https://example.com/api/users"）
+    pub endpoint: String,
+    /// エンドポイントの件数
+    pub samples: usize,
+    /// 所要時間の中央値（ミリ秒）
+    pub median: f64,
+    /// 所要時間のMAD（標準偏差相当に換算、ミリ秒）
+    pub mad: f64,
+    /// 外れ値と判定した件数
+    pub outliers: usize,
+    /// 所要時間の最大値（ミリ秒）
+    pub max: f64,
+}
+
+/// 通信イベントの所要時間の外れ値を判定し、HTTPの通信に外れ値のフラグを設定
+/// 
+/// # Arguments
+/// * `events` - 通信イベントのリスト
+/// 
+/// # Returns
+/// * `Vec<EndpointOutliers>` - 外れ値を含むエンドポイントの集計（外れ値の件数の多い順）
+pub fn mark_outliers(events: &mut [TrafficEvent]) -> Vec<EndpointOutliers> {
+    // エンドポイントごとにイベントの位置をまとめる
+    let mut endpoints: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (index, event) in events.iter().enumerate() {
+        if let Some(result) = event.as_http() {
+            endpoints.entry(endpoint_key(result)).or_default().push(index);
+        }
+    }
+
+    let mut summaries = Vec::new();
+    for (endpoint, indices) in endpoints {
+        if indices.len() < MIN_SAMPLES {
+            continue;
+        }
+
+        let durations: Vec<f64> = indices
+            .iter()
+            .filter_map(|&i| events[i].as_http().map(|r| r.duration))
+            .collect();
+        let center = median(&durations);
+        let deviations: Vec<f64> = durations.iter().map(|d| (d - center).abs()).collect();
+        let mad = median(&deviations) * MAD_SCALE;
+        // 所要時間がほぼ同一の場合はMADが0になり、僅かな差でも外れ値になるため判定しない
+        if mad <= 0.0 {
+            continue;
+        }
+
+        let threshold = center + MAD_THRESHOLD * mad;
+        let mut outliers = 0;
+        for &i in &indices {
+            if let TrafficEvent::HttpExchange(result) = &mut events[i]
+                && result.duration > threshold
+            {
+                result.latency_outlier = true;
+                outliers += 1;
+            }
+        }
+
+        if outliers > 0 {
+            summaries.push(EndpointOutliers {
+                endpoint,
+                samples: indices.len(),
+                median: center,
+                mad,
+                outliers,
+                max: durations.iter().copied().fold(0.0, f64::max),
+            });
+        }
+    }
+
+    summaries.sort_by(|a, b| b.outliers.cmp(&a.outliers).then_with(|| a.endpoint.cmp(&b.endpoint)));
+    summaries
+}
+
+/// 解析結果のエンドポイント（メソッドとクエリ文字列・フラグメントを除いたURL）
+/// 
+/// SOAPリクエストは全ての操作が同じURLを共有するため、操作名ごとに別のエンドポイントとする
+/// 
+/// # Arguments
+/// * `result` - 解析結果
+/// 
+/// # Returns
+/// * `String` - エンドポイント
+pub fn endpoint_key(result: &AnalysisResult) -> String {
+    let url = result.request_url.as_str();
+    let path = match Url::parse(url) {
+        Ok(mut parsed) => {
+            parsed.set_query(None);
+            parsed.set_fragment(None);
+            parsed.to_string()
+        }
+        Err(_) => url.split(['?', '#']).next().unwrap_or(url).to_string(),
+    };
+    match &result.soap_operation {
+        Some(operation) => format!("{} {} ({})", result.method, path, operation),
+        None => format!("{} {}", result.method, path),
+    }
+}
+
+/// 中央値を計算
+/// 
+/// # Arguments
+/// * `values` - 値のリスト
+/// 
+/// # Returns
+/// * `f64` - 中央値（空の場合は0）
+pub fn median(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
@@ -0,0 +1,211 @@
+//! ページ送り（page・cursor・offset等のクエリパラメータのみが異なる同じエンドポイントへの連続したリクエスト）の検出を実装
+//! 
+//! 一覧のAPIを何百ページも取得したキャプチャで、一連のリクエストを1件のページ送りとしてまとめ、
+//! ページ数・合計サイズ・合計時間をPaginationシートに出力する（`--collapse-pages`でデータシートの行も集約する）
+
+use crate::analyzer::HarAnalyzer;
+use crate::asset_collapse::COLLAPSED;
+use crate::har_types::Entry;
+use chrono::DateTime;
+use std::collections::{HashMap, HashSet};
+use url::Url;
+
+/// ページの位置を表すクエリパラメータ名（小文字）
+const PAGE_PARAMS: &[&str] = &[
+    "page",
+    "p",
+    "pg",
+    "page_number",
+    "pagenumber",
+    "page_no",
+    "offset",
+    "start",
+    "skip",
+    "from",
+    "cursor",
+    "after",
+    "before",
+    "next",
+    "next_cursor",
+    "nextcursor",
+    "page_token",
+    "pagetoken",
+    "continuation",
+    "continuationtoken",
+    "marker",
+    "since_id",
+    "max_id",
+];
+
+/// クエリパラメータの(名前, 値)のリスト
+type QueryPairs = Vec<(String, String)>;
+
+/// 検出中の一連のリクエスト
+#[derive(Debug, Default)]
+struct Group {
+    /// エンドポイント
+    endpoint: String,
+    /// ページの位置を表すクエリパラメータ名
+    params: Vec<String>,
+    /// エントリの位置
+    entry_indices: Vec<usize>,
+    /// 出現したページの位置
+    positions: HashSet<String>,
+}
+
+/// ページ送りの一連のリクエスト
+#[derive(Debug, Clone)]
+pub struct PageSequence {
+    /// エンドポイント（メソッドとページのパラメータを除いたURL）
+    pub endpoint: String,
+    /// ページの位置を表すクエリパラメータ名
+    pub params: Vec<String>,
+    /// エントリの位置（0始まり、HAR内の順序）
+    pub entry_indices: Vec<usize>,
+    /// レスポンスボディの合計サイズ（バイト）
+    pub bytes: i64,
+    /// 所要時間の合計（ミリ秒）
+    pub total_time: f64,
+    /// 最初のリクエストの開始から最後のリクエストの終了までの時間（ミリ秒）
+    pub elapsed: f64,
+}
+
+impl PageSequence {
+    /// ページ数
+    /// 
+    /// # Returns
+    /// * `usize` - 一連のリクエストの件数
+    pub fn pages(&self) -> usize {
+        self.entry_indices.len()
+    }
+}
+
+/// ページ送りの一連のリクエストを検出
+/// 
+/// メソッドとページのパラメータ以外のURLが同じリクエストを順にまとめ、同じページの位置が再び現れた場合は
+/// 別の一連のリクエスト（一覧の再読み込み）とする。2件以上で、ページのパラメータを含むリクエストがあるもののみを返す
+/// 
+/// # Arguments
+/// * `entries` - HARエントリのリスト
+/// * `is_target` - 解析対象のエントリかどうかを判定する関数
+/// * `duration` - エントリの所要時間（ミリ秒）を求める関数
+/// 
+/// # Returns
+/// * `Vec<PageSequence>` - 最初のリクエストの順のページ送り
+pub fn detect(
+    entries: &[Entry],
+    is_target: impl Fn(&Entry) -> bool,
+    duration: impl Fn(&Entry) -> f64,
+) -> Vec<PageSequence> {
+    let mut groups: Vec<Group> = Vec::new();
+    let mut open: HashMap<String, usize> = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        if !is_target(entry) {
+            continue;
+        }
+        let Some((endpoint, page)) = split_page_params(entry) else {
+            continue;
+        };
+        let position = page
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let current = open
+            .get(&endpoint)
+            .copied()
+            .filter(|&group| !groups[group].positions.contains(&position));
+        let group = match current {
+            Some(group) => group,
+            None => {
+                groups.push(Group {
+                    endpoint: endpoint.clone(),
+                    ..Group::default()
+                });
+                open.insert(endpoint, groups.len() - 1);
+                groups.len() - 1
+            }
+        };
+        let group = &mut groups[group];
+        for (name, _) in page {
+            if !group.params.contains(&name) {
+                group.params.push(name);
+            }
+        }
+        group.entry_indices.push(index);
+        group.positions.insert(position);
+    }
+
+    groups
+        .into_iter()
+        .filter(|group| group.entry_indices.len() >= 2 && !group.params.is_empty())
+        .map(|Group { endpoint, params, entry_indices, .. }| {
+            let sequence: Vec<&Entry> = entry_indices.iter().map(|&index| &entries[index]).collect();
+            let bytes = sequence.iter().map(|entry| HarAnalyzer::response_size(entry).max(0)).sum();
+            let total_time = sequence.iter().map(|entry| duration(entry)).sum();
+            let spans: Vec<(f64, f64)> = sequence
+                .iter()
+                .filter_map(|entry| {
+                    let start = DateTime::parse_from_rfc3339(&entry.started_date_time).ok()?.timestamp_millis() as f64;
+                    Some((start, start + duration(entry)))
+                })
+                .collect();
+            let elapsed = match (
+                spans.iter().map(|(start, _)| *start).reduce(f64::min),
+                spans.iter().map(|(_, end)| *end).reduce(f64::max),
+            ) {
+                (Some(start), Some(end)) => end - start,
+                _ => total_time,
+            };
+            PageSequence {
+                endpoint,
+                params,
+                entry_indices,
+                bytes,
+                total_time,
+                elapsed,
+            }
+        })
+        .collect()
+}
+
+/// ページ送りを1行に集約する場合のエントリごとの繰り返し回数を求める
+/// 
+/// # Arguments
+/// * `sequences` - ページ送りの一連のリクエスト
+/// * `len` - エントリの数
+/// 
+/// # Returns
+/// * `Vec<usize>` - エントリと同じ順序の繰り返し回数（最初のリクエストはページ数、集約されたリクエストは`COLLAPSED`、それ以外は1）
+pub fn repeat_counts(sequences: &[PageSequence], len: usize) -> Vec<usize> {
+    let mut counts = vec![1; len];
+    for sequence in sequences {
+        for (position, &index) in sequence.entry_indices.iter().enumerate() {
+            counts[index] = if position == 0 { sequence.pages() } else { COLLAPSED };
+        }
+    }
+    counts
+}
+
+/// リクエストURLをエンドポイントとページの位置に分ける
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `Option<(String, Vec<(String, String)>)>` - (メソッドとページのパラメータを除いたURL, ページのパラメータ)（URLを解析できない場合はNone）
+fn split_page_params(entry: &Entry) -> Option<(String, QueryPairs)> {
+    let mut url = Url::parse(&entry.request.url).ok()?;
+    let (page, others): (QueryPairs, QueryPairs) = url
+        .query_pairs()
+        .into_owned()
+        .partition(|(name, _)| PAGE_PARAMS.contains(&name.to_ascii_lowercase().as_str()));
+    if others.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(&others);
+    }
+    url.set_fragment(None);
+    Some((format!("{} {}", entry.request.method, url), page))
+}
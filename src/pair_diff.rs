@@ -0,0 +1,222 @@
+//! 同じエンドポイントへの連続した呼び出しのレスポンスボディを比較するpair-diffサブコマンドを実装
+//! 
+//! URLが正規表現に一致したエントリをエンドポイント（メソッドとクエリ文字列・フラグメントを除いたURL）ごとにまとめ、
+//! 直前の呼び出しからのレスポンスボディの変化（JSONの場合は追加・削除・変更されたキー）を表示する。
+//! セッション中に設定値が切り替わる（フラッピングする）箇所の確認に使用する
+
+use crate::har_types::Entry;
+use crate::json_diff::{self, ChangeKind, JsonChange};
+use crate::mock_server;
+use crate::show::{Painter, BOLD, DIM, GREEN, RED, YELLOW};
+use regex::Regex;
+use serde_json::Value;
+use std::fmt::Write;
+use url::Url;
+
+/// 連続した2件のレスポンスボディの違い
+#[derive(Debug, Clone)]
+pub enum BodyDifference {
+    /// 両方がJSONの場合の構造的な差分
+    Json(Vec<JsonChange>),
+    /// いずれかがJSONではない場合のボディのサイズ（バイト）
+    Text {
+        /// 前のボディのサイズ
+        before_size: usize,
+        /// 後のボディのサイズ
+        after_size: usize,
+    },
+}
+
+/// 直前の呼び出しからの変化
+#[derive(Debug, Clone)]
+pub struct BodyChange {
+    /// 前のエントリ番号（HAR内の順序で1始まり）
+    pub before_entry: usize,
+    /// 後のエントリ番号（HAR内の順序で1始まり）
+    pub after_entry: usize,
+    /// 後のリクエストの開始時刻
+    pub started_date_time: String,
+    /// 前のレスポンスのステータスコード
+    pub before_status: i32,
+    /// 後のレスポンスのステータスコード
+    pub after_status: i32,
+    /// レスポンスボディの違い
+    pub difference: BodyDifference,
+}
+
+/// エンドポイントごとの比較結果
+#[derive(Debug, Clone)]
+pub struct EndpointDiff {
+    /// エンドポイント（メソッドとクエリ文字列・フラグメントを除いたURL）
+    pub endpoint: String,
+    /// 比較したエントリ番号（HAR内の順序、1始まり）
+    pub entry_numbers: Vec<usize>,
+    /// レスポンスボディが変化した呼び出し
+    pub changes: Vec<BodyChange>,
+}
+
+/// URLが正規表現に一致したエントリの連続した呼び出しのレスポンスボディを比較
+/// 
+/// レスポンスボディが記録されていないエントリは比較しない
+/// 
+/// # Arguments
+/// * `entries` - HARエントリのスライス
+/// * `url_regex` - 比較するエントリのURLの正規表現
+/// 
+/// # Returns
+/// * `Vec<EndpointDiff>` - 最初に出現した順のエンドポイントごとの比較結果（呼び出しが2件以上のエンドポイントのみ）
+pub fn compare(entries: &[Entry], url_regex: &Regex) -> Vec<EndpointDiff> {
+    let mut groups: Vec<(String, Vec<(usize, &Entry)>)> = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        if !url_regex.is_match(&entry.request.url) || entry.response.content.text.is_none() {
+            continue;
+        }
+        let endpoint = endpoint_key(entry);
+        match groups.iter_mut().find(|(key, _)| *key == endpoint) {
+            Some((_, calls)) => calls.push((index, entry)),
+            None => groups.push((endpoint, vec![(index, entry)])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|(_, calls)| calls.len() > 1)
+        .map(|(endpoint, calls)| {
+            let changes = calls
+                .windows(2)
+                .filter_map(|pair| {
+                    let (before_index, before) = pair[0];
+                    let (after_index, after) = pair[1];
+                    let difference = body_difference(before, after)?;
+                    Some(BodyChange {
+                        before_entry: before_index + 1,
+                        after_entry: after_index + 1,
+                        started_date_time: after.started_date_time.clone(),
+                        before_status: before.response.status,
+                        after_status: after.response.status,
+                        difference,
+                    })
+                })
+                .collect();
+            EndpointDiff {
+                endpoint,
+                entry_numbers: calls.iter().map(|(index, _)| index + 1).collect(),
+                changes,
+            }
+        })
+        .collect()
+}
+
+/// 比較結果を表示用のテキストに整形
+/// 
+/// # Arguments
+/// * `diffs` - エンドポイントごとの比較結果
+/// * `color` - ANSIエスケープシーケンスで色を付ける場合はtrue
+/// 
+/// # Returns
+/// * `String` - 表示用のテキスト
+pub fn render(diffs: &[EndpointDiff], color: bool) -> String {
+    let painter = Painter { color };
+    let mut out = String::new();
+    for diff in diffs {
+        let _ = writeln!(
+            out,
+            "{} {}",
+            painter.paint(BOLD, &diff.endpoint),
+            painter.paint(
+                DIM,
+                &format!("（{}回の呼び出し、変化{}回）", diff.entry_numbers.len(), diff.changes.len())
+            )
+        );
+        if diff.changes.is_empty() {
+            let _ = writeln!(out, "  {}", painter.paint(DIM, "レスポンスボディは全て同じです"));
+        }
+        for change in &diff.changes {
+            let status = if change.before_status == change.after_status {
+                change.after_status.to_string()
+            } else {
+                format!("{} → {}", change.before_status, change.after_status)
+            };
+            let _ = writeln!(
+                out,
+                "  {} {} {}",
+                painter.paint(DIM, &format!("#{} → #{}", change.before_entry, change.after_entry)),
+                change.started_date_time,
+                painter.paint(DIM, &format!("[{}]", status))
+            );
+            match &change.difference {
+                BodyDifference::Json(changes) => {
+                    for json_change in changes {
+                        let style = match json_change.kind {
+                            ChangeKind::Added => GREEN,
+                            ChangeKind::Removed => RED,
+                            ChangeKind::Changed => YELLOW,
+                        };
+                        let _ = writeln!(out, "    {}", painter.paint(style, &json_change.to_line()));
+                    }
+                }
+                BodyDifference::Text { before_size, after_size } => {
+                    let _ = writeln!(
+                        out,
+                        "    {}",
+                        painter.paint(
+                            YELLOW,
+                            &format!("~ ボディ（JSON以外）: {} → {} バイト", before_size, after_size)
+                        )
+                    );
+                }
+            }
+        }
+    }
+    out
+}
+
+/// 2件のレスポンスボディの違いを取得
+/// 
+/// # Arguments
+/// * `before` - 前のHARエントリ
+/// * `after` - 後のHARエントリ
+/// 
+/// # Returns
+/// * `Option<BodyDifference>` - 違い（ボディが同じ場合はNone）
+pub(crate) fn body_difference(before: &Entry, after: &Entry) -> Option<BodyDifference> {
+    let before_body = mock_server::response_body(before);
+    let after_body = mock_server::response_body(after);
+    if before_body == after_body {
+        return None;
+    }
+    match (
+        serde_json::from_slice::<Value>(&before_body),
+        serde_json::from_slice::<Value>(&after_body),
+    ) {
+        (Ok(before_json), Ok(after_json)) => {
+            // キーの順序や空白のみが異なる場合は変化なしとする
+            let changes = json_diff::diff(&before_json, &after_json);
+            (!changes.is_empty()).then_some(BodyDifference::Json(changes))
+        }
+        _ => Some(BodyDifference::Text {
+            before_size: before_body.len(),
+            after_size: after_body.len(),
+        }),
+    }
+}
+
+/// エントリのエンドポイントを取得
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `String` - メソッドとクエリ文字列・フラグメントを除いたURL
+pub(crate) fn endpoint_key(entry: &Entry) -> String {
+    let url = entry.request.url.as_str();
+    let path = match Url::parse(url) {
+        Ok(mut parsed) => {
+            parsed.set_query(None);
+            parsed.set_fragment(None);
+            parsed.to_string()
+        }
+        Err(_) => url.split(['?', '#']).next().unwrap_or(url).to_string(),
+    };
+    format!("{} {}", entry.request.method, path)
+}
@@ -0,0 +1,425 @@
+//! URL・リクエスト/レスポンスボディの遅延加工を実装
+
+use base64::{engine::general_purpose, Engine as _};
+use crate::analytics::AnalyticsProvider;
+use crate::beautify::{self, JsCssMode};
+use crate::binary_payload::{self, BinaryFormat};
+use crate::body_kind::BodyKind;
+use crate::grpc_web;
+use crate::html::{self, HtmlMode};
+use crate::mime_map::MimeMap;
+use prost_reflect::{DescriptorPool, MessageDescriptor};
+use encoding_rs::{Encoding, UTF_8};
+use std::sync::OnceLock;
+
+/// 加工前のペイロード
+#[derive(Debug, Clone)]
+pub enum RawPayload {
+    /// 加工不要のテキスト
+    Text(String),
+    /// JSONとして整形するテキスト
+    Json(String),
+    /// 1行ごとのJSON（NDJSON）として整形するテキスト
+    Ndjson(String),
+    /// Base64でエンコードされたボディ（デコード後に文字コードを変換し、テキストの形式に従って整形する）
+    Base64 { text: String, format: TextFormat, encoding: &'static Encoding },
+    /// JSONオブジェクトとして出力する名前と値のペア
+    Pairs(Vec<(String, String)>),
+    /// パーセントエンコードされたURL
+    EncodedUrl(String),
+    /// 加工後に出力方法に従って要約するHTML
+    Html { body: Box<RawPayload>, mode: HtmlMode },
+    /// 加工後に出力方法に従って折り返し・整形するJavaScript・CSS
+    JsCss { body: Box<RawPayload>, kind: BodyKind, mode: JsCssMode },
+    /// gRPC-webのフレームの並び（Base64で記録されているか、テキスト形式か、メッセージの型）
+    GrpcWeb { text: String, base64: bool, text_format: bool, message: Option<MessageDescriptor> },
+    /// JSONに変換するバイナリ形式（MessagePack・CBOR）のボディ（Base64で記録されているかどうか）
+    Binary { text: String, base64: bool, format: BinaryFormat },
+    /// イベントの一覧に変換するアクセス解析のビーコン（デコードできない場合はフォールバックのペイロードを出力する）
+    Analytics { provider: AnalyticsProvider, url: String, body: Option<String>, fallback: Box<RawPayload> },
+}
+
+/// テキストのボディの形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextFormat {
+    /// 整形しないテキスト
+    Plain,
+    /// JSON
+    Json,
+    /// 1行ごとのJSON（NDJSON・JSON Lines）
+    Ndjson,
+    /// `{`・`[`で始まる場合のみJSONとして整形するテキスト（MIMEタイプを誤って指定したJSONの推定）
+    Sniffed,
+}
+
+impl TextFormat {
+    /// MIMEタイプからテキストの形式を判定
+    ///
+    /// # Arguments
+    /// * `mime_type` - MIMEタイプ
+    ///
+    /// # Returns
+    /// * `TextFormat` - テキストの形式
+    pub fn from_mime(mime_type: &str) -> Self {
+        if is_ndjson_content(mime_type) {
+            TextFormat::Ndjson
+        } else if is_json_content(mime_type) {
+            TextFormat::Json
+        } else {
+            TextFormat::Plain
+        }
+    }
+
+    /// 形式に従ってテキストを整形
+    ///
+    /// # Arguments
+    /// * `text` - テキスト
+    ///
+    /// # Returns
+    /// * `String` - 整形したテキスト
+    pub fn format(&self, text: &str) -> String {
+        match self {
+            TextFormat::Plain => text.to_string(),
+            TextFormat::Json => format_json(text),
+            TextFormat::Ndjson => format_ndjson(text),
+            TextFormat::Sniffed if looks_like_json(text) => format_json(text),
+            TextFormat::Sniffed => text.to_string(),
+        }
+    }
+}
+
+/// ボディの加工方法のオプション
+#[derive(Debug, Clone, Default)]
+pub struct PayloadOptions {
+    /// レスポンスボディの文字コードの指定（charsetパラメータより優先）
+    pub charset_override: Option<&'static Encoding>,
+    /// HTMLのレスポンスボディの出力方法
+    pub html_mode: HtmlMode,
+    /// JavaScript・CSSのレスポンスボディの出力方法
+    pub js_css_mode: JsCssMode,
+    /// gRPC-webのメッセージをデコードするための記述子
+    pub proto_descriptor: Option<DescriptorPool>,
+    /// MIMEタイプがtext/plainまたは未指定のボディが`{`・`[`で始まる場合はJSONとして整形する
+    pub sniff_json: bool,
+    /// 設定ファイルに記述したMIMEタイプの分類（JSONの判定・ボディの整形に使用）
+    pub mime_map: MimeMap,
+}
+
+/// 初めて参照された時点で加工されるテキスト
+///
+/// JSONの整形、Base64のデコード、URLのデコードは出力時に必要になるまで行わない
+#[derive(Debug, Clone)]
+pub struct LazyText {
+    raw: RawPayload,
+    rendered: OnceLock<String>,
+}
+
+impl LazyText {
+    /// 加工前のペイロードから作成
+    ///
+    /// # Arguments
+    /// * `raw` - 加工前のペイロード
+    ///
+    /// # Returns
+    /// * `LazyText` - 遅延加工されるテキスト
+    pub fn new(raw: RawPayload) -> Self {
+        LazyText {
+            raw,
+            rendered: OnceLock::new(),
+        }
+    }
+
+    /// 加工済みのテキストから作成（キャッシュから読み込んだテキスト等）
+    ///
+    /// # Arguments
+    /// * `text` - 加工済みのテキスト
+    ///
+    /// # Returns
+    /// * `LazyText` - 加工済みのテキスト
+    pub fn rendered(text: String) -> Self {
+        LazyText {
+            raw: RawPayload::Text(String::new()),
+            rendered: OnceLock::from(text),
+        }
+    }
+
+    /// 加工済みのテキストを取得（初回参照時に加工する）
+    ///
+    /// # Returns
+    /// * `&str` - 加工済みのテキスト
+    pub fn as_str(&self) -> &str {
+        self.rendered.get_or_init(|| Self::render(&self.raw))
+    }
+
+    /// 既に加工済みかどうか
+    ///
+    /// # Returns
+    /// * `bool` - 加工済みの場合はtrue
+    pub fn is_rendered(&self) -> bool {
+        self.rendered.get().is_some()
+    }
+
+    /// 加工前のペイロードを加工
+    ///
+    /// # Arguments
+    /// * `raw` - 加工前のペイロード
+    ///
+    /// # Returns
+    /// * `String` - 加工済みのテキスト
+    fn render(raw: &RawPayload) -> String {
+        match raw {
+            RawPayload::Text(text) => text.clone(),
+            RawPayload::Json(text) => format_json(text),
+            RawPayload::Ndjson(text) => format_ndjson(text),
+            RawPayload::Base64 { text, format, encoding } => match general_purpose::STANDARD.decode(text) {
+                Ok(decoded) => match encoding.decode_without_bom_handling_and_without_replacement(&decoded) {
+                    Some(decoded_text) => format.format(&decoded_text),
+                    None => text.clone(),
+                },
+                Err(_) => text.clone(),
+            },
+            RawPayload::Pairs(pairs) => {
+                let map: serde_json::Map<String, serde_json::Value> = pairs
+                    .iter()
+                    .map(|(name, value)| (name.clone(), serde_json::Value::String(value.clone())))
+                    .collect();
+                serde_json::to_string_pretty(&map).unwrap_or_else(|_| "{}".to_string())
+            }
+            RawPayload::EncodedUrl(url) => decode_url(url),
+            RawPayload::Html { body, mode } => html::summarize(&Self::render(body), *mode),
+            RawPayload::JsCss { body, kind, mode } => beautify::format(&Self::render(body), *kind, *mode),
+            RawPayload::GrpcWeb { text, base64, text_format, message } => {
+                let bytes = if *base64 {
+                    general_purpose::STANDARD.decode(text).ok()
+                } else {
+                    Some(text.as_bytes().to_vec())
+                };
+                bytes
+                    .and_then(|bytes| grpc_web::render(&bytes, *text_format, message.as_ref()))
+                    .unwrap_or_else(|| text.clone())
+            }
+            RawPayload::Binary { text, base64, format } => {
+                let bytes = if *base64 {
+                    general_purpose::STANDARD.decode(text).ok()
+                } else {
+                    Some(text.as_bytes().to_vec())
+                };
+                bytes
+                    .and_then(|bytes| binary_payload::to_json(&bytes, *format))
+                    .unwrap_or_else(|| text.clone())
+            }
+            RawPayload::Analytics { provider, url, body, fallback } => provider
+                .decode(url, body.as_deref())
+                .unwrap_or_else(|| Self::render(fallback)),
+        }
+    }
+}
+
+/// コンテンツタイプがJSONかどうかを判定
+///
+/// # Arguments
+/// * `mime_type` - MIMEタイプ
+///
+/// # Returns
+/// * `bool` - JSONの場合はtrue（`application/problem+json`等の`+json`で終わるMIMEタイプを含む）
+pub fn is_json_content(mime_type: &str) -> bool {
+    let mime_type = mime_type.to_ascii_lowercase();
+    let essence = mime_type.split(';').next().unwrap_or_default().trim();
+    mime_type.contains("application/json") || mime_type.contains("text/json") || essence.ends_with("+json")
+}
+
+/// 内容の種類を表さないMIMEタイプかどうかを判定（JSONの推定の対象）
+///
+/// # Arguments
+/// * `mime_type` - MIMEタイプ
+///
+/// # Returns
+/// * `bool` - text/plainまたは未指定の場合はtrue
+pub fn is_unlabeled_text(mime_type: &str) -> bool {
+    let essence = mime_type.split(';').next().unwrap_or_default().trim();
+    essence.is_empty() || essence.eq_ignore_ascii_case("text/plain")
+}
+
+/// テキストがJSONのオブジェクトまたは配列のように見えるかどうかを判定
+///
+/// # Arguments
+/// * `text` - テキスト
+///
+/// # Returns
+/// * `bool` - 先頭の空白を除いて`{`または`[`で始まる場合はtrue
+pub fn looks_like_json(text: &str) -> bool {
+    let text = text.trim_start_matches(|c: char| c.is_whitespace() || c == '\u{feff}');
+    text.starts_with('{') || text.starts_with('[')
+}
+
+/// MIMEタイプのcharsetパラメータから文字コードを取得
+///
+/// # Arguments
+/// * `mime_type` - MIMEタイプ（例: `text/html; charset=Shift_JIS`）
+///
+/// # Returns
+/// * `Option<&'static Encoding>` - 文字コード（指定が無いか、不明な文字コードの場合はNone）
+pub fn charset_from_mime(mime_type: &str) -> Option<&'static Encoding> {
+    mime_type.split(';').skip(1).find_map(|parameter| {
+        let (name, value) = parameter.split_once('=')?;
+        if !name.trim().eq_ignore_ascii_case("charset") {
+            return None;
+        }
+        Encoding::for_label(value.trim().trim_matches('"').as_bytes())
+    })
+}
+
+/// ボディの文字コードを決定
+///
+/// 指定された文字コードを優先し、無い場合はMIMEタイプのcharsetパラメータ、それも無い場合はUTF-8とする
+///
+/// # Arguments
+/// * `mime_type` - MIMEタイプ
+/// * `charset_override` - 指定された文字コード
+///
+/// # Returns
+/// * `&'static Encoding` - 文字コード
+pub fn body_encoding(mime_type: &str, charset_override: Option<&'static Encoding>) -> &'static Encoding {
+    charset_override
+        .or_else(|| charset_from_mime(mime_type))
+        .unwrap_or(UTF_8)
+}
+
+/// コンテンツタイプがNDJSON（JSON Lines）かどうかを判定
+///
+/// # Arguments
+/// * `mime_type` - MIMEタイプ
+///
+/// # Returns
+/// * `bool` - NDJSONの場合はtrue
+pub fn is_ndjson_content(mime_type: &str) -> bool {
+    let mime_type = mime_type.to_ascii_lowercase();
+    mime_type.contains("ndjson") || mime_type.contains("jsonl") || mime_type.contains("json-seq")
+}
+
+/// NDJSON（1行に1つのJSON）を整形したJSONの配列に変換
+///
+/// 空行は無視する。JSONとして解析できない行がある場合は元のテキストを返す
+///
+/// # Arguments
+/// * `text` - NDJSONのテキスト
+///
+/// # Returns
+/// * `String` - 整形したJSONの配列
+pub fn format_ndjson(text: &str) -> String {
+    // JSON text sequences（RFC 7464）の区切り文字（RS）は空白として扱う
+    let documents: Option<Vec<serde_json::Value>> = text
+        .lines()
+        .map(|line| line.trim_matches(|c: char| c.is_whitespace() || c == '\u{1e}'))
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_str(line).ok())
+        .collect();
+    match documents {
+        Some(documents) => {
+            serde_json::to_string_pretty(&documents).unwrap_or_else(|_| text.to_string())
+        }
+        None => text.to_string(),
+    }
+}
+
+/// JSON文字列をフォーマット
+///
+/// # Arguments
+/// * `json_str` - JSON文字列
+///
+/// # Returns
+/// * `String` - フォーマットされたJSON文字列
+pub fn format_json(json_str: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(json_str) {
+        Ok(value) => {
+            match serde_json::to_string_pretty(&value) {
+                Ok(formatted) => formatted,
+                Err(_) => json_str.to_string(),
+            }
+        }
+        Err(_) => json_str.to_string(),
+    }
+}
+
+/// クエリ・フラグメントの区切りとして意味を持つため、デコードせずに残す文字
+const RESERVED_QUERY_BYTES: &[u8] = b"&=+#%;";
+
+/// URLのクエリ・フラグメントをUTF-8でデコード
+///
+/// パスの`%2F`等はデコードするとパスの区切りが変わるため、スキーム・ホスト・パスはそのまま残す。
+/// クエリ・フラグメントでも区切り文字（`&`・`=`・`+`・`#`・`%`・`;`）・制御文字と、
+/// UTF-8としてデコードできないバイト列はデコードしないため、デコードしたURLも元のURLと同じように解析できる
+///
+/// # Arguments
+/// * `url_str` - エンコードされたURL文字列
+///
+/// # Returns
+/// * `String` - デコードされたURL文字列
+pub fn decode_url(url_str: &str) -> String {
+    match url_str.find(['?', '#']) {
+        Some(query_start) => {
+            let (base, rest) = url_str.split_at(query_start);
+            format!("{}{}", base, decode_query(rest))
+        }
+        None => url_str.to_string(),
+    }
+}
+
+/// クエリ・フラグメントのパーセントエンコーディングをデコード
+///
+/// 連続するパーセントエンコーディングを1つのバイト列としてデコードし、UTF-8として不正な場合は元の表記のまま残す
+///
+/// # Arguments
+/// * `text` - `?`または`#`で始まるクエリ・フラグメント
+///
+/// # Returns
+/// * `String` - デコードされた文字列
+fn decode_query(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while !rest.is_empty() {
+        let run_len = decodable_run_len(rest);
+        if run_len == 0 {
+            let c = rest.chars().next().unwrap_or_default();
+            decoded.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let (run, tail) = rest.split_at(run_len);
+        let bytes: Vec<u8> = run
+            .as_bytes()
+            .chunks(3)
+            .filter_map(|escape| std::str::from_utf8(&escape[1..]).ok())
+            .filter_map(|hex| u8::from_str_radix(hex, 16).ok())
+            .collect();
+        match String::from_utf8(bytes) {
+            Ok(text) => decoded.push_str(&text),
+            Err(_) => decoded.push_str(run),
+        }
+        rest = tail;
+    }
+    decoded
+}
+
+/// 先頭から連続するデコード対象のパーセントエンコーディングの長さを求める
+///
+/// # Arguments
+/// * `text` - 文字列
+///
+/// # Returns
+/// * `usize` - バイト数（先頭がデコード対象でない場合は0）
+fn decodable_run_len(text: &str) -> usize {
+    text.as_bytes()
+        .chunks(3)
+        .take_while(|escape| {
+            escape.len() == 3
+                && escape[0] == b'%'
+                && escape[1..].iter().all(u8::is_ascii_hexdigit)
+                && std::str::from_utf8(&escape[1..])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .is_some_and(|byte| !RESERVED_QUERY_BYTES.contains(&byte) && !byte.is_ascii_control())
+        })
+        .count()
+        * 3
+}
@@ -0,0 +1,337 @@
+//! 加工済みのボディのキャッシュを実装
+//! 
+//! エントリの内容のハッシュをキーとして、デコード・整形したリクエスト・レスポンスのボディを
+//! キャッシュディレクトリ（既定は`.cache`）に保存する。同じHARを出力形式・列の指定を変えて
+//! 再実行した際に、数MBのボディのデコード・整形を省略できるようにする。
+//! ボディの加工方法のオプションとツールのバージョンをキーに含めるため、オプションを変えた場合は別のキャッシュを使用する
+//! 
+//! 出力に使用するボディ（リクエスト・レスポンス）のみを加工し、キャッシュに無い側は加工して追記する
+
+use crate::error::{AnalyzerError, Result};
+use crate::har_types::Entry;
+use crate::payload::PayloadOptions;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// `--cache`でディレクトリを省略した場合のキャッシュディレクトリ
+pub const DEFAULT_CACHE_DIR: &str = ".cache";
+
+/// キャッシュファイルに保存する加工済みのボディ（加工していない側はNone）
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CachedPayloads {
+    /// リクエストボディ
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request: Option<String>,
+    /// レスポンスボディ
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    response: Option<String>,
+}
+
+/// 出力に使用するボディ
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PayloadSides {
+    /// リクエストボディを使用するかどうか
+    pub request: bool,
+    /// レスポンスボディを使用するかどうか
+    pub response: bool,
+}
+
+/// 加工済みのボディのキャッシュ
+#[derive(Debug)]
+pub struct PayloadCache {
+    /// キャッシュディレクトリ
+    dir: PathBuf,
+    /// ボディの加工方法のオプションとツールのバージョンから作成した識別子
+    fingerprint: String,
+    /// 加工・キャッシュするボディ
+    sides: PayloadSides,
+    /// キャッシュから読み込んだエントリ数
+    hits: AtomicUsize,
+    /// キャッシュに無かったエントリ数
+    misses: AtomicUsize,
+    /// 保存に失敗したかどうか（警告を1回だけ出力する）
+    write_failed: AtomicBool,
+    /// 一時ファイル名の連番
+    sequence: AtomicUsize,
+}
+
+impl PayloadCache {
+    /// キャッシュディレクトリを作成してキャッシュを開く
+    /// 
+    /// # Arguments
+    /// * `dir` - キャッシュディレクトリ
+    /// * `options` - ボディの加工方法のオプション
+    /// * `sides` - 出力に使用するボディ（使用しない側は加工・キャッシュしない）
+    /// 
+    /// # Returns
+    /// * `Result<PayloadCache>` - 成功時はキャッシュ、失敗時はエラー
+    pub fn open(dir: impl AsRef<Path>, options: &PayloadOptions, sides: PayloadSides) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(AnalyzerError::io("キャッシュディレクトリの作成に失敗しました"))?;
+        info!("ボディのキャッシュを使用します: {}", dir.display());
+        Ok(PayloadCache {
+            dir,
+            fingerprint: Self::fingerprint(options),
+            sides,
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+            write_failed: AtomicBool::new(false),
+            sequence: AtomicUsize::new(0),
+        })
+    }
+
+    /// ボディの加工結果に影響するオプションから識別子を作成
+    /// 
+    /// # Arguments
+    /// * `options` - ボディの加工方法のオプション
+    /// 
+    /// # Returns
+    /// * `String` - 識別子
+    fn fingerprint(options: &PayloadOptions) -> String {
+        let mime_map: Vec<String> = options
+            .mime_map
+            .entries()
+            .into_iter()
+            .map(|(mime_type, category)| format!("{}={:?}", mime_type, category))
+            .collect();
+        let descriptor = options
+            .proto_descriptor
+            .as_ref()
+            .map(|pool| hex(&Sha256::digest(pool.encode_to_vec())))
+            .unwrap_or_default();
+        format!(
+            "{} {}|charset={}|html={:?}|js_css={:?}|sniff_json={}|mime={}|proto={}",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            options.charset_override.map(|encoding| encoding.name()).unwrap_or_default(),
+            options.html_mode,
+            options.js_css_mode,
+            options.sniff_json,
+            mime_map.join(","),
+            descriptor
+        )
+    }
+
+    /// エントリのキャッシュファイルのパスを取得
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `Option<PathBuf>` - キャッシュファイルのパス（エントリをシリアライズできない場合はNone）
+    fn path(&self, entry: &Entry) -> Option<PathBuf> {
+        let json = serde_json::to_vec(entry).ok()?;
+        let mut hasher = Sha256::new();
+        hasher.update(self.fingerprint.as_bytes());
+        hasher.update([0]);
+        hasher.update(&json);
+        let key = hex(&hasher.finalize());
+        Some(self.dir.join(&key[..2]).join(format!("{}.json", key)))
+    }
+
+    /// キャッシュから加工済みのボディを取得し、無い場合は加工してキャッシュに保存
+    /// 
+    /// 出力に使用しない側のボディは加工せずにNoneを返す。
+    /// キャッシュの読み込み・保存に失敗した場合は加工した結果をそのまま返す
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// * `render_request` - リクエストボディを加工する処理
+    /// * `render_response` - レスポンスボディを加工する処理
+    /// 
+    /// # Returns
+    /// * `(Option<String>, Option<String>)` - (リクエストボディ, レスポンスボディ)
+    pub fn get_or_render(
+        &self,
+        entry: &Entry,
+        render_request: impl FnOnce() -> String,
+        render_response: impl FnOnce() -> String,
+    ) -> (Option<String>, Option<String>) {
+        let path = self.path(entry);
+        let mut cached = path
+            .as_ref()
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|content| serde_json::from_slice::<CachedPayloads>(&content).ok())
+            .unwrap_or_default();
+
+        let mut rendered = false;
+        if self.sides.request && cached.request.is_none() {
+            cached.request = Some(render_request());
+            rendered = true;
+        }
+        if self.sides.response && cached.response.is_none() {
+            cached.response = Some(render_response());
+            rendered = true;
+        }
+        if !rendered {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            if let Some(path) = &path
+                && let Err(e) = self.store(path, &cached)
+                && !self.write_failed.swap(true, Ordering::Relaxed)
+            {
+                warn!("ボディのキャッシュの保存に失敗しました: {}", e);
+            }
+        }
+        (
+            cached.request.filter(|_| self.sides.request),
+            cached.response.filter(|_| self.sides.response),
+        )
+    }
+
+    /// 加工済みのボディをキャッシュファイルに保存
+    /// 
+    /// 並行して実行した別のプロセス・スレッドが途中まで書き込んだファイルを読まないように、
+    /// 一時ファイルに書き込んでから名前を変更する
+    /// 
+    /// # Arguments
+    /// * `path` - キャッシュファイルのパス
+    /// * `cached` - 加工済みのボディ
+    /// 
+    /// # Returns
+    /// * `std::io::Result<()>` - 成功時はOk、失敗時はエラー
+    fn store(&self, path: &Path, cached: &CachedPayloads) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temp = path.with_extension(format!(
+            "{}-{}.tmp",
+            std::process::id(),
+            self.sequence.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&temp, serde_json::to_vec(cached)?)?;
+        fs::rename(&temp, path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp);
+        })
+    }
+
+    /// キャッシュの使用状況をログに出力
+    pub fn log(&self) {
+        info!(
+            "ボディのキャッシュ: {}件を再利用、{}件を新たに加工しました",
+            self.hits.load(Ordering::Relaxed),
+            self.misses.load(Ordering::Relaxed)
+        );
+    }
+}
+
+/// バイト列を16進数の文字列に変換
+/// 
+/// # Arguments
+/// * `bytes` - バイト列
+/// 
+/// # Returns
+/// * `String` - 16進数の文字列
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::HtmlMode;
+    use serde_json::json;
+
+    fn work_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rs_har_analyzer_payload_cache_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn entry(response_body: &str) -> Entry {
+        serde_json::from_value(json!({
+            "startedDateTime": "2024-01-01T00:00:00.000Z",
+            "time": 1.0,
+            "request": {
+                "method": "POST", "url": "https://example.com/api", "httpVersion": "HTTP/1.1", "headers": [],
+                "queryString": [], "cookies": [], "headersSize": -1, "bodySize": 2,
+                "postData": { "mimeType": "application/json", "text": "{}" }
+            },
+            "response": {
+                "status": 200, "statusText": "OK", "httpVersion": "HTTP/1.1", "headers": [], "cookies": [],
+                "content": { "size": 0, "mimeType": "text/plain", "text": response_body },
+                "redirectURL": "", "headersSize": -1, "bodySize": 0
+            },
+            "timings": { "send": 0.0, "wait": 1.0, "receive": 0.0 }
+        }))
+        .unwrap()
+    }
+
+    fn both() -> PayloadSides {
+        PayloadSides { request: true, response: true }
+    }
+
+    fn counts(cache: &PayloadCache) -> (usize, usize) {
+        (cache.hits.load(Ordering::Relaxed), cache.misses.load(Ordering::Relaxed))
+    }
+
+    #[test]
+    fn second_lookup_reuses_cached_payloads_without_rendering() {
+        let dir = work_dir("reuse");
+        let entry = entry("hello");
+        let first = PayloadCache::open(&dir, &PayloadOptions::default(), both()).unwrap();
+        let rendered = first.get_or_render(&entry, || "req".to_string(), || "res".to_string());
+        assert_eq!(rendered, (Some("req".to_string()), Some("res".to_string())));
+        assert_eq!(counts(&first), (0, 1));
+
+        // 別の実行でも同じエントリ・オプションであれば加工しない
+        let second = PayloadCache::open(&dir, &PayloadOptions::default(), both()).unwrap();
+        let cached = second.get_or_render(&entry, || panic!("request rendered"), || panic!("response rendered"));
+        assert_eq!(cached, rendered);
+        assert_eq!(counts(&second), (1, 0));
+    }
+
+    #[test]
+    fn changed_entry_or_options_use_a_different_key() {
+        let dir = work_dir("key");
+        let cache = PayloadCache::open(&dir, &PayloadOptions::default(), both()).unwrap();
+        cache.get_or_render(&entry("hello"), || "req".to_string(), || "hello".to_string());
+
+        let changed = cache.get_or_render(&entry("changed"), || "req".to_string(), || "changed".to_string());
+        assert_eq!(changed.1.as_deref(), Some("changed"));
+        assert_eq!(counts(&cache), (0, 2));
+
+        let options = PayloadOptions { html_mode: HtmlMode::Title, ..Default::default() };
+        assert_ne!(PayloadCache::fingerprint(&options), PayloadCache::fingerprint(&PayloadOptions::default()));
+        let other = PayloadCache::open(&dir, &options, both()).unwrap();
+        let rendered = other.get_or_render(&entry("hello"), || "req".to_string(), || "title".to_string());
+        assert_eq!(rendered.1.as_deref(), Some("title"));
+        assert_eq!(counts(&other), (0, 1));
+    }
+
+    #[test]
+    fn missing_side_is_rendered_and_merged_into_the_cache_file() {
+        let dir = work_dir("sides");
+        let entry = entry("hello");
+        let request_only = PayloadCache::open(&dir, &PayloadOptions::default(), PayloadSides { request: true, response: false })
+            .unwrap();
+        let rendered = request_only.get_or_render(&entry, || "req".to_string(), || panic!("response rendered"));
+        assert_eq!(rendered, (Some("req".to_string()), None));
+
+        let cache = PayloadCache::open(&dir, &PayloadOptions::default(), both()).unwrap();
+        let merged = cache.get_or_render(&entry, || panic!("request rendered"), || "res".to_string());
+        assert_eq!(merged, (Some("req".to_string()), Some("res".to_string())));
+        let stored: CachedPayloads = serde_json::from_slice(&fs::read(cache.path(&entry).unwrap()).unwrap()).unwrap();
+        assert_eq!((stored.request.as_deref(), stored.response.as_deref()), (Some("req"), Some("res")));
+    }
+
+    #[test]
+    fn unreadable_cache_file_is_rendered_again_and_replaced() {
+        let dir = work_dir("broken");
+        let entry = entry("hello");
+        let cache = PayloadCache::open(&dir, &PayloadOptions::default(), both()).unwrap();
+        let path = cache.path(&entry).unwrap();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "not json").unwrap();
+
+        let rendered = cache.get_or_render(&entry, || "req".to_string(), || "res".to_string());
+        assert_eq!(rendered, (Some("req".to_string()), Some("res".to_string())));
+        assert_eq!(counts(&cache), (0, 1));
+        assert!(serde_json::from_slice::<CachedPayloads>(&fs::read(&path).unwrap()).is_ok());
+    }
+}
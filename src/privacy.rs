@@ -0,0 +1,393 @@
+//! 列ごとの匿名化のレベル（`--privacy`）を実装
+//! 
+//! 列ごとに値をそのまま出力する（keep）・ハッシュ値に置き換える（hash）・先頭のみ出力する（truncate）・
+//! 空欄にする（drop）のいずれかを指定し、Excel・CSV・テンプレート・Word形式の全ての出力に同じように適用する。
+//! 同梱の`strict`に加えて、設定ファイルの`[privacy.NAME]`に列名とレベルを記述して独自の設定を定義できる。
+//! hashは鍵付きのHMAC-SHA256で求めるため、鍵を知らなければ総当たりで元の値を求められない
+
+use crate::columns::{CellValue, Column};
+use crate::error::{AnalyzerError, Result};
+use crate::har_types::AnalysisResult;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// 同梱の設定の名前
+pub const STRICT: &str = "strict";
+
+/// truncateで出力する先頭の文字数
+const TRUNCATE_CHARS: usize = 32;
+
+/// hashで出力するHMAC-SHA256の16進数の桁数
+const HASH_DIGITS: usize = 12;
+
+/// 実行ごとに生成するhashの鍵のバイト数
+const HASH_KEY_BYTES: usize = 32;
+
+/// SHA-256のブロック長（HMACの鍵を埋めるバイト数）
+const HMAC_BLOCK_BYTES: usize = 64;
+
+/// 同梱の`strict`の設定（記述していない列はkeep）
+const STRICT_LEVELS: &[(Column, PrivacyLevel)] = &[
+    (Column::SourceIp, PrivacyLevel::Hash),
+    (Column::DestinationIp, PrivacyLevel::Hash),
+    (Column::RedirectUrl, PrivacyLevel::Hash),
+    (Column::RequestUrl, PrivacyLevel::Hash),
+    (Column::DecodedUrl, PrivacyLevel::Hash),
+    (Column::Host, PrivacyLevel::Hash),
+    (Column::Path, PrivacyLevel::Hash),
+    (Column::InitiatorUrl, PrivacyLevel::Hash),
+    (Column::Query, PrivacyLevel::Drop),
+    (Column::RequestPayload, PrivacyLevel::Drop),
+    (Column::ResponsePayload, PrivacyLevel::Drop),
+    (Column::Comments, PrivacyLevel::Truncate),
+    (Column::StatusText, PrivacyLevel::Truncate),
+];
+
+/// 列の匿名化のレベル
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrivacyLevel {
+    /// そのまま出力する
+    Keep,
+    /// HMAC-SHA256の先頭12桁に置き換える
+    /// 
+    /// 鍵は実行ごとに生成するため、同じ実行（batchの全ファイルを含む）の中でのみ同じ値が同じハッシュ値になる。
+    /// 実行をまたいで突き合わせる場合は`[privacy.NAME]`の`hash-key`に鍵を指定する。
+    /// 鍵を付けないハッシュはIPアドレスや辞書にあるホスト名を総当たりで元に戻せるため使用しない
+    Hash,
+    /// 先頭32文字のみ出力する
+    Truncate,
+    /// 空欄にする
+    Drop,
+}
+
+/// 設定ファイルの`[privacy.NAME]`の設定
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PrivacyConfig {
+    /// hashの鍵（指定しない場合は実行ごとに生成する）
+    #[serde(default)]
+    pub hash_key: Option<String>,
+    /// 列名からレベルへの対応付け
+    #[serde(flatten)]
+    pub levels: BTreeMap<String, PrivacyLevel>,
+}
+
+/// 列ごとの匿名化の設定
+#[derive(Clone, Default)]
+pub struct PrivacyPolicy {
+    /// 設定の名前（`--privacy`を指定していない場合は空）
+    name: String,
+    /// keep以外のレベルを指定した列
+    levels: Vec<(Column, PrivacyLevel)>,
+    /// hashのHMACの鍵
+    key: Vec<u8>,
+}
+
+impl PrivacyPolicy {
+    /// 名前から設定を取得
+    /// 
+    /// 設定ファイルに同じ名前の設定がある場合は同梱の設定より優先する
+    /// 
+    /// # Arguments
+    /// * `name` - 設定の名前
+    /// * `defined` - 設定ファイルの`[privacy.NAME]`の設定
+    /// 
+    /// # Returns
+    /// * `Result<PrivacyPolicy>` - 成功時は設定、失敗時はエラー
+    pub fn resolve(name: &str, defined: &BTreeMap<String, PrivacyConfig>) -> Result<Self> {
+        if let Some(config) = defined.get(name) {
+            let levels = config
+                .levels
+                .iter()
+                .map(|(key, level)| {
+                    Column::from_key(key).map(|column| (column, *level)).ok_or_else(|| {
+                        AnalyzerError::Config(format!("[privacy.{}]の列名が不明です: {}", name, key))
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let key = match &config.hash_key {
+                Some(key) if key.is_empty() => {
+                    return Err(AnalyzerError::Config(format!("[privacy.{}]のhash-keyが空です", name)));
+                }
+                Some(key) => key.as_bytes().to_vec(),
+                None => random_key()?,
+            };
+            return Ok(Self::new(name, levels, key));
+        }
+        if name == STRICT {
+            return Ok(Self::new(name, STRICT_LEVELS.to_vec(), random_key()?));
+        }
+
+        let mut names: Vec<&str> = defined.keys().map(String::as_str).collect();
+        if !names.contains(&STRICT) {
+            names.insert(0, STRICT);
+        }
+        Err(AnalyzerError::InvalidArgument(format!(
+            "不明な匿名化の設定です: {}（指定できる設定: {}）",
+            name,
+            names.join(", ")
+        )))
+    }
+
+    /// 設定を作成（keepの列は除く）
+    /// 
+    /// # Arguments
+    /// * `name` - 設定の名前
+    /// * `levels` - 列ごとのレベル
+    /// * `key` - hashのHMACの鍵
+    /// 
+    /// # Returns
+    /// * `PrivacyPolicy` - 設定
+    fn new(name: &str, levels: Vec<(Column, PrivacyLevel)>, key: Vec<u8>) -> Self {
+        PrivacyPolicy {
+            name: name.to_string(),
+            levels: levels.into_iter().filter(|(_, level)| *level != PrivacyLevel::Keep).collect(),
+            key,
+        }
+    }
+
+    /// 設定の名前
+    /// 
+    /// # Returns
+    /// * `&str` - 設定の名前（`--privacy`を指定していない場合は空）
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 匿名化する列があるかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - keep以外のレベルを指定した列がある場合はtrue
+    pub fn is_active(&self) -> bool {
+        !self.levels.is_empty()
+    }
+
+    /// 列のレベルを取得
+    /// 
+    /// # Arguments
+    /// * `column` - 列
+    /// 
+    /// # Returns
+    /// * `PrivacyLevel` - レベル（指定していない列はkeep）
+    pub fn level(&self, column: Column) -> PrivacyLevel {
+        self.levels
+            .iter()
+            .find(|(target, _)| *target == column)
+            .map_or(PrivacyLevel::Keep, |(_, level)| *level)
+    }
+
+    /// 解析結果から匿名化した列の値を取得
+    /// 
+    /// # Arguments
+    /// * `column` - 列
+    /// * `result` - 解析結果
+    /// * `buffer` - 置き換えた値を保持するバッファ
+    /// 
+    /// # Returns
+    /// * `CellValue` - セルに書き込む値
+    pub fn value<'a>(&self, column: Column, result: &'a AnalysisResult, buffer: &'a mut String) -> CellValue<'a> {
+        let value = column.value(result);
+        match (self.level(column), value) {
+            (PrivacyLevel::Keep, value) => value,
+            (_, CellValue::Text(text)) => match self.text(column, text) {
+                Cow::Borrowed(text) => CellValue::Text(text),
+                Cow::Owned(text) => {
+                    *buffer = text;
+                    CellValue::Text(buffer)
+                }
+            },
+            (PrivacyLevel::Drop, _) => CellValue::Text(""),
+            (PrivacyLevel::Hash, CellValue::Number(number)) => {
+                *buffer = self.hash(&number.to_string());
+                CellValue::Text(buffer)
+            }
+            (PrivacyLevel::Hash, CellValue::DateTime(_)) => {
+                *buffer = self.hash(&result.timestamp);
+                CellValue::Text(buffer)
+            }
+            (PrivacyLevel::Truncate, value) => value,
+        }
+    }
+
+    /// 列のレベルに従って文字列を匿名化
+    /// 
+    /// 解析結果の列以外の出力（依存関係グラフ・補助シート等）のURL・ホスト名に、対応する列のレベルを適用する
+    /// 
+    /// # Arguments
+    /// * `column` - レベルを適用する列
+    /// * `text` - 値
+    /// 
+    /// # Returns
+    /// * `Cow<str>` - 匿名化した値（keepの場合はそのまま）
+    pub fn text<'a>(&self, column: Column, text: &'a str) -> Cow<'a, str> {
+        match self.level(column) {
+            PrivacyLevel::Keep => Cow::Borrowed(text),
+            PrivacyLevel::Drop => Cow::Borrowed(""),
+            PrivacyLevel::Hash if text.is_empty() => Cow::Borrowed(""),
+            PrivacyLevel::Hash => Cow::Owned(self.hash(text)),
+            PrivacyLevel::Truncate if text.chars().count() > TRUNCATE_CHARS => {
+                Cow::Owned(text.chars().take(TRUNCATE_CHARS).collect::<String>() + "…")
+            }
+            PrivacyLevel::Truncate => Cow::Borrowed(text),
+        }
+    }
+
+    /// 値のHMAC-SHA256の先頭を求める
+    /// 
+    /// # Arguments
+    /// * `text` - 値
+    /// 
+    /// # Returns
+    /// * `String` - 16進数の先頭12桁
+    fn hash(&self, text: &str) -> String {
+        let digest = hmac_sha256(&self.key, text.as_bytes());
+        digest.iter().map(|b| format!("{:02x}", b)).collect::<String>()[..HASH_DIGITS].to_string()
+    }
+}
+
+impl fmt::Debug for PrivacyPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 鍵はログ等に出力しない
+        f.debug_struct("PrivacyPolicy")
+            .field("name", &self.name)
+            .field("levels", &self.levels)
+            .finish_non_exhaustive()
+    }
+}
+
+/// hashの鍵を生成
+/// 
+/// # Returns
+/// * `Result<Vec<u8>>` - 成功時は乱数の鍵、失敗時はエラー
+fn random_key() -> Result<Vec<u8>> {
+    let mut key = vec![0; HASH_KEY_BYTES];
+    getrandom::fill(&mut key).map_err(AnalyzerError::export("匿名化の鍵の生成に失敗しました"))?;
+    Ok(key)
+}
+
+/// HMAC-SHA256（RFC 2104）を求める
+/// 
+/// # Arguments
+/// * `key` - 鍵
+/// * `message` - 値
+/// 
+/// # Returns
+/// * `Vec<u8>` - 32バイトのHMAC
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut block = [0u8; HMAC_BLOCK_BYTES];
+    if key.len() > HMAC_BLOCK_BYTES {
+        let digest = Sha256::digest(key);
+        block[..digest.len()].copy_from_slice(&digest);
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let pad = |byte: u8| block.iter().map(|b| b ^ byte).collect::<Vec<u8>>();
+    let inner = Sha256::new().chain_update(pad(0x36)).chain_update(message).finalize();
+    Sha256::new().chain_update(pad(0x5c)).chain_update(inner).finalize().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defined(name: &str, levels: &[(&str, PrivacyLevel)]) -> BTreeMap<String, PrivacyConfig> {
+        let levels = levels.iter().map(|(key, level)| (key.to_string(), *level)).collect();
+        BTreeMap::from([(name.to_string(), PrivacyConfig { hash_key: None, levels })])
+    }
+
+    #[test]
+    fn resolve_returns_bundled_strict() {
+        let policy = PrivacyPolicy::resolve(STRICT, &BTreeMap::new()).unwrap();
+        assert_eq!(policy.name(), STRICT);
+        assert!(policy.is_active());
+        assert_eq!(policy.level(Column::Host), PrivacyLevel::Hash);
+        assert_eq!(policy.level(Column::Query), PrivacyLevel::Drop);
+        assert_eq!(policy.level(Column::StatusText), PrivacyLevel::Truncate);
+        assert_eq!(policy.level(Column::Method), PrivacyLevel::Keep);
+    }
+
+    #[test]
+    fn resolve_prefers_config_over_bundled_strict() {
+        let defined = defined(STRICT, &[("host", PrivacyLevel::Drop)]);
+        let policy = PrivacyPolicy::resolve(STRICT, &defined).unwrap();
+        assert_eq!(policy.level(Column::Host), PrivacyLevel::Drop);
+        assert_eq!(policy.level(Column::Query), PrivacyLevel::Keep);
+    }
+
+    #[test]
+    fn resolve_ignores_keep_levels() {
+        let defined = defined("internal", &[("host", PrivacyLevel::Keep)]);
+        let policy = PrivacyPolicy::resolve("internal", &defined).unwrap();
+        assert_eq!(policy.name(), "internal");
+        assert!(!policy.is_active());
+        assert!(!PrivacyPolicy::default().is_active());
+    }
+
+    #[test]
+    fn resolve_rejects_unknown_names_and_columns() {
+        let defined = defined("internal", &[("no-such-column", PrivacyLevel::Hash)]);
+        let error = PrivacyPolicy::resolve("internal", &defined).unwrap_err();
+        assert!(matches!(error, AnalyzerError::Config(message) if message.contains("no-such-column")));
+
+        let error = PrivacyPolicy::resolve("missing", &defined).unwrap_err();
+        assert!(
+            matches!(error, AnalyzerError::InvalidArgument(message) if message.contains("strict, internal"))
+        );
+    }
+
+    #[test]
+    fn text_applies_column_level() {
+        let policy = PrivacyPolicy::resolve(STRICT, &BTreeMap::new()).unwrap();
+        let hashed = policy.text(Column::Host, "example.com");
+        assert_eq!(hashed.len(), HASH_DIGITS);
+        assert!(hashed.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(hashed, policy.text(Column::Host, "example.com"));
+        assert_ne!(hashed, policy.text(Column::Host, "example.org"));
+        assert_eq!(policy.text(Column::Host, ""), "");
+
+        assert_eq!(policy.text(Column::Query, "a=1"), "");
+        assert_eq!(policy.text(Column::Method, "POST"), "POST");
+
+        let long = "x".repeat(TRUNCATE_CHARS + 1);
+        assert_eq!(policy.text(Column::StatusText, &long), "x".repeat(TRUNCATE_CHARS) + "…");
+        assert_eq!(policy.text(Column::StatusText, "Not Found"), "Not Found");
+    }
+
+    #[test]
+    fn hmac_matches_rfc4231() {
+        let digest = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(hex, "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+    }
+
+    #[test]
+    fn hash_key_links_runs_only_when_configured() {
+        let first = PrivacyPolicy::resolve(STRICT, &BTreeMap::new()).unwrap();
+        let second = PrivacyPolicy::resolve(STRICT, &BTreeMap::new()).unwrap();
+        assert_ne!(first.text(Column::Host, "10.0.0.1"), second.text(Column::Host, "10.0.0.1"));
+        // 鍵を付けないSHA-256とは一致しない
+        let unkeyed: String = Sha256::digest(b"10.0.0.1").iter().map(|b| format!("{:02x}", b)).collect();
+        assert_ne!(first.text(Column::Host, "10.0.0.1"), &unkeyed[..HASH_DIGITS]);
+
+        let mut defined = defined("share", &[("host", PrivacyLevel::Hash)]);
+        defined.get_mut("share").unwrap().hash_key = Some("team-secret".to_string());
+        let first = PrivacyPolicy::resolve("share", &defined).unwrap();
+        let second = PrivacyPolicy::resolve("share", &defined).unwrap();
+        assert_eq!(first.text(Column::Host, "10.0.0.1"), second.text(Column::Host, "10.0.0.1"));
+
+        defined.get_mut("share").unwrap().hash_key = Some(String::new());
+        assert!(matches!(PrivacyPolicy::resolve("share", &defined), Err(AnalyzerError::Config(_))));
+    }
+
+    #[test]
+    fn config_reads_hash_key_beside_levels() {
+        let config: PrivacyConfig = toml::from_str("hash-key = \"k\"\nhost = \"hash\"\nquery = \"drop\"").unwrap();
+        assert_eq!(config.hash_key.as_deref(), Some("k"));
+        assert_eq!(config.levels.get("host"), Some(&PrivacyLevel::Hash));
+        assert_eq!(config.levels.len(), 2);
+    }
+}
@@ -0,0 +1,128 @@
+//! HARファイルの作成ツールの判定と既知の癖への対応を実装
+
+use crate::har_types::Log;
+use log::{info, warn};
+
+/// HARファイルを作成したツール
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Producer {
+    /// Chrome/Edge等のChromium系ブラウザの開発者ツール
+    Chromium,
+    /// Firefoxの開発者ツール
+    Firefox,
+    /// Safari（WebKit）のWebインスペクタ
+    Safari,
+    /// Charles Proxy
+    Charles,
+    /// Fiddler
+    Fiddler,
+    /// mitmproxy
+    Mitmproxy,
+    /// 動作確認されていないツール
+    Unknown,
+}
+
+impl Producer {
+    /// HARログのcreator/browserから作成ツールを判定
+    /// 
+    /// # Arguments
+    /// * `log` - HARログ
+    /// 
+    /// # Returns
+    /// * `Producer` - 作成ツール
+    pub fn detect(log: &Log) -> Self {
+        let creator = log.creator.name.to_lowercase();
+        let browser = log
+            .browser
+            .as_ref()
+            .map(|b| b.name.to_lowercase())
+            .unwrap_or_default();
+
+        // SafariのcreatorはWebKit Web Inspectorのため、Chromiumより先に判定する
+        if creator.contains("webkit") || browser.contains("safari") {
+            Producer::Safari
+        } else if creator.contains("firefox") || browser.contains("firefox") {
+            Producer::Firefox
+        } else if creator.contains("webinspector")
+            || ["chrome", "edge", "chromium"].iter().any(|name| browser.contains(name))
+        {
+            Producer::Chromium
+        } else if creator.contains("charles") {
+            Producer::Charles
+        } else if creator.contains("fiddler") {
+            Producer::Fiddler
+        } else if creator.contains("mitmproxy") {
+            Producer::Mitmproxy
+        } else {
+            Producer::Unknown
+        }
+    }
+
+    /// 作成ツールの表示名
+    /// 
+    /// # Returns
+    /// * `&'static str` - 表示名
+    pub fn name(&self) -> &'static str {
+        match self {
+            Producer::Chromium => "Chromium",
+            Producer::Firefox => "Firefox",
+            Producer::Safari => "Safari",
+            Producer::Charles => "Charles",
+            Producer::Fiddler => "Fiddler",
+            Producer::Mitmproxy => "mitmproxy",
+            Producer::Unknown => "不明",
+        }
+    }
+
+    /// 既知の癖とその対応
+    /// 
+    /// # Returns
+    /// * `&'static [&'static str]` - 癖の説明のリスト
+    pub fn quirks(&self) -> &'static [&'static str] {
+        match self {
+            Producer::Safari => &[
+                "タイミング情報や所要時間が記録されていない場合があるため、所要時間が0の場合は各フェーズの合計で補完します",
+            ],
+            Producer::Firefox => &[
+                "IPv6の送信先IPが角括弧付きで記録されるため、括弧を除去して出力します",
+            ],
+            Producer::Charles => &[
+                "serverIPAddressが記録されない場合があるため、送信先IPはURLのホスト名で補完されます",
+            ],
+            Producer::Fiddler => &[
+                "ページ情報と発生元（_initiator）が記録されないため、依存関係グラフはRefererヘッダーのみから作成されます",
+            ],
+            Producer::Chromium | Producer::Mitmproxy | Producer::Unknown => &[],
+        }
+    }
+
+    /// 所要時間を各フェーズの合計で補完するかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - 補完する場合はtrue
+    pub fn recomputes_duration(&self) -> bool {
+        matches!(self, Producer::Safari)
+    }
+
+    /// 作成ツールと既知の癖をログに出力
+    /// 
+    /// # Arguments
+    /// * `log` - HARログ
+    pub fn log(&self, log: &Log) {
+        info!("HAR作成ツール: {} {}", log.creator.name, log.creator.version);
+        if let Some(browser) = &log.browser {
+            info!("ブラウザ: {} {}", browser.name, browser.version);
+        }
+
+        if *self == Producer::Unknown {
+            warn!(
+                "動作確認されていないツール（{}）で作成されたHARファイルです。一部の情報が正しく解析されない場合があります",
+                log.creator.name
+            );
+        }
+
+        for quirk in self.quirks() {
+            warn!("{}で作成されたHARファイルです: {}", self.name(), quirk);
+        }
+    }
+}
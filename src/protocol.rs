@@ -0,0 +1,153 @@
+//! 通常のHTTPのリクエストと区別するプロトコルの分類（`protocol`列）を実装
+//! 
+//! DNS over HTTPS・WebRTCのTURN/STUN・gRPC・WebSocket・Server-Sent Eventsの通信を、
+//! コンテンツタイプ・URL・ヘッダーから判定し、通常のAPIの呼び出しと誤読されないように列に出力する
+
+use crate::har_types::{Entry, NameValue};
+use url::Url;
+
+/// DNS over HTTPSのコンテンツタイプ（RFC 8484のワイヤー形式とJSON形式）
+const DOH_CONTENT_TYPES: &[&str] = &["application/dns-message", "application/dns-json"];
+
+/// TURN・STUNの既定のポート（3478: TURN/STUN、5349: TLS上のTURN/STUN）
+const TURN_PORTS: &[u16] = &[3478, 5349];
+
+/// TURN・STUNのURIのスキーム（RFC 7064・RFC 7065）
+const TURN_SCHEMES: &[&str] = &["turn", "turns", "stun", "stuns"];
+
+/// リクエストのプロトコル
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrafficProtocol {
+    /// 通常のHTTPのリクエスト
+    #[default]
+    Http,
+    /// DNS over HTTPS
+    DnsOverHttps,
+    /// WebRTCのTURN/STUN（HTTPのプロキシ経由のトンネル・TURNの認証情報の取得）
+    TurnStun,
+    /// gRPC
+    Grpc,
+    /// gRPC-Web
+    GrpcWeb,
+    /// WebSocket
+    WebSocket,
+    /// Server-Sent Events
+    ServerSentEvents,
+}
+
+impl TrafficProtocol {
+    /// エントリのプロトコルを判定
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `TrafficProtocol` - プロトコル（いずれにも該当しない場合はHTTP）
+    pub fn detect(entry: &Entry) -> Self {
+        let request_type = entry
+            .request
+            .post_data
+            .as_ref()
+            .map(|post_data| post_data.mime_type.as_str())
+            .or_else(|| header_value(&entry.request.headers, "content-type"))
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let accept = header_value(&entry.request.headers, "accept").unwrap_or_default().to_ascii_lowercase();
+        let response_type = entry.response.content.mime_type.to_ascii_lowercase();
+        let url = Url::parse(&entry.request.url).ok();
+
+        let content_types = [request_type.as_str(), response_type.as_str()];
+        let has_type = |prefix: &str| content_types.iter().any(|mime_type| mime_type.starts_with(prefix));
+
+        if DOH_CONTENT_TYPES.iter().any(|doh| has_type(doh) || accept.contains(doh))
+            || url.as_ref().is_some_and(|url| url.path().ends_with("/dns-query"))
+        {
+            return TrafficProtocol::DnsOverHttps;
+        }
+        if Self::is_turn_stun(entry, url.as_ref()) {
+            return TrafficProtocol::TurnStun;
+        }
+        if has_type("application/grpc-web") {
+            return TrafficProtocol::GrpcWeb;
+        }
+        if has_type("application/grpc") {
+            return TrafficProtocol::Grpc;
+        }
+        let upgrade = header_value(&entry.request.headers, "upgrade").unwrap_or_default();
+        if !entry.web_socket_messages.is_empty()
+            || upgrade.eq_ignore_ascii_case("websocket")
+            || url.as_ref().is_some_and(|url| matches!(url.scheme(), "ws" | "wss"))
+        {
+            return TrafficProtocol::WebSocket;
+        }
+        if response_type.starts_with("text/event-stream") {
+            return TrafficProtocol::ServerSentEvents;
+        }
+        TrafficProtocol::Http
+    }
+
+    /// TURN・STUNの通信かどうかを判定
+    /// 
+    /// TURN・STUNのURIのスキーム、プロキシへのTURN・STUNのポートへのCONNECT、
+    /// TURNのREST API（`service=turn`）とICEサーバーの設定（`turn:`・`stun:`のURIを含むJSON）の取得を対象とする
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// * `url` - 解析したリクエストURL
+    /// 
+    /// # Returns
+    /// * `bool` - TURN・STUNの通信の場合はtrue
+    fn is_turn_stun(entry: &Entry, url: Option<&Url>) -> bool {
+        if let Some(url) = url {
+            if TURN_SCHEMES.contains(&url.scheme()) {
+                return true;
+            }
+            if url.query_pairs().any(|(name, value)| name == "service" && value.eq_ignore_ascii_case("turn")) {
+                return true;
+            }
+        }
+        if entry.request.method.eq_ignore_ascii_case("CONNECT") {
+            let port = entry.request.url.rsplit(':').next().and_then(|port| port.trim_end_matches('/').parse::<u16>().ok());
+            if port.is_some_and(|port| TURN_PORTS.contains(&port)) {
+                return true;
+            }
+        }
+        let content = &entry.response.content;
+        content.mime_type.to_ascii_lowercase().contains("json")
+            && content.encoding.as_deref() != Some("base64")
+            && content.text.as_deref().is_some_and(|text| {
+                TURN_SCHEMES.iter().any(|scheme| text.contains(&format!("\"{}:", scheme)))
+            })
+    }
+
+    /// 列に表示する名前
+    /// 
+    /// # Returns
+    /// * `&'static str` - 名前
+    pub fn label(&self) -> &'static str {
+        match self {
+            TrafficProtocol::Http => "HTTP",
+            TrafficProtocol::DnsOverHttps => "DoH",
+            TrafficProtocol::TurnStun => "TURN/STUN",
+            TrafficProtocol::Grpc => "gRPC",
+            TrafficProtocol::GrpcWeb => "gRPC-Web",
+            TrafficProtocol::WebSocket => "WebSocket",
+            TrafficProtocol::ServerSentEvents => "SSE",
+        }
+    }
+}
+
+/// ヘッダーの値を取得（名前の大文字・小文字は区別しない）
+/// 
+/// # Arguments
+/// * `headers` - ヘッダーのリスト
+/// * `name` - ヘッダー名
+/// 
+/// # Returns
+/// * `Option<&str>` - 最初に一致したヘッダーの値
+fn header_value<'a>(headers: &'a [NameValue], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case(name))
+        .map(|header| header.value.as_str())
+}
@@ -0,0 +1,867 @@
+//! 関係者向けのWord形式（docx）のレポートと、他のツールに取り込むCSV・TSV形式の出力を実装
+//! 
+//! Excelのワークブックを開かない読み手向けに、サマリー・グラフ（PNG画像）・主な指摘事項を
+//! ページ番号付きの文書にまとめる。Wordの文書はWordprocessingMLのXMLをZIPにまとめて直接作成する
+
+use crate::columns::{CellValue, Column};
+use crate::csv_export;
+use crate::egress_cost::REPETITIONS;
+use crate::events::TrafficEvent;
+use crate::excel_exporter::ExportOptions;
+use crate::error::{AnalyzerError, Result};
+use crate::har_types::AnalysisResult;
+use crate::summary::Summary;
+use crate::template_export;
+use crate::trackers::CategoryTotals;
+use log::info;
+use std::fs::File;
+use std::io::Write;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+/// 表に出力する指摘事項の最大件数
+const MAX_TABLE_ROWS: usize = 10;
+
+/// グラフの画像の幅（ピクセル）
+const CHART_WIDTH: u32 = 600;
+
+/// グラフの棒の高さ（ピクセル）
+const CHART_BAR_HEIGHT: u32 = 24;
+
+/// グラフの棒の間隔（ピクセル）
+const CHART_BAR_GAP: u32 = 8;
+
+/// 1ピクセルあたりのEMU（96dpi）
+const EMU_PER_PIXEL: u32 = 9525;
+
+/// グラフの棒の色（表の行の順に使用）
+const CHART_COLORS: &[[u8; 3]] = &[
+    [0x44, 0x72, 0xC4],
+    [0xED, 0x7D, 0x31],
+    [0xA5, 0xA5, 0xA5],
+    [0xFF, 0xC0, 0x00],
+    [0x5B, 0x9B, 0xD5],
+    [0x70, 0xAD, 0x47],
+    [0x26, 0x44, 0x78],
+    [0x9E, 0x48, 0x0E],
+];
+
+/// レポートの形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Word形式（Office Open XML）
+    Docx,
+    /// カンマ区切り
+    Csv,
+    /// タブ区切り
+    Tsv,
+    /// 利用者が用意したTeraテンプレートによるテキスト
+    Template,
+}
+
+impl ReportFormat {
+    /// `--format`で指定する名前から形式を取得
+    /// 
+    /// # Arguments
+    /// * `name` - 形式の名前
+    /// 
+    /// # Returns
+    /// * `Option<ReportFormat>` - 対応する形式（レポートの形式でない場合はNone）
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "docx" => Some(ReportFormat::Docx),
+            "csv" => Some(ReportFormat::Csv),
+            "tsv" => Some(ReportFormat::Tsv),
+            "template" => Some(ReportFormat::Template),
+            _ => None,
+        }
+    }
+
+    /// 出力先が指定されていない場合の出力先
+    /// 
+    /// # Returns
+    /// * `&'static str` - 出力先のパス
+    pub fn default_output(&self) -> &'static str {
+        match self {
+            ReportFormat::Docx => "har_report.docx",
+            ReportFormat::Csv => "har_analysis.csv",
+            ReportFormat::Tsv => "har_analysis.tsv",
+            ReportFormat::Template => "har_report.txt",
+        }
+    }
+
+    /// 出力先のファイルの拡張子
+    /// 
+    /// # Returns
+    /// * `Option<&'static str>` - 拡張子（ドットを含む、テンプレートの出力のように任意の場合はNone）
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            ReportFormat::Docx => Some(".docx"),
+            ReportFormat::Csv => Some(".csv"),
+            ReportFormat::Tsv => Some(".tsv"),
+            ReportFormat::Template => None,
+        }
+    }
+}
+
+/// 本文のブロック
+enum Block {
+    /// 見出し（レベル0は表題）
+    Heading(u8, String),
+    /// 段落
+    Paragraph(String),
+    /// 箇条書き
+    Bullet(String),
+    /// 表（先頭の行は見出し）
+    Table(Vec<Vec<String>>),
+    /// 画像（画像ファイルの番号, 幅, 高さ）
+    Image(usize, u32, u32),
+    /// 改ページ
+    PageBreak,
+}
+
+/// 作成中の文書
+#[derive(Default)]
+struct Document {
+    /// 本文のブロック
+    blocks: Vec<Block>,
+    /// 画像（PNG）
+    images: Vec<Vec<u8>>,
+}
+
+impl Document {
+    /// 見出しを追加
+    /// 
+    /// # Arguments
+    /// * `level` - 見出しのレベル（0は表題）
+    /// * `text` - 見出しの文字列
+    fn heading(&mut self, level: u8, text: impl Into<String>) {
+        self.blocks.push(Block::Heading(level, text.into()));
+    }
+
+    /// 段落を追加
+    /// 
+    /// # Arguments
+    /// * `text` - 段落の文字列
+    fn paragraph(&mut self, text: impl Into<String>) {
+        self.blocks.push(Block::Paragraph(text.into()));
+    }
+
+    /// 箇条書きの項目を追加
+    /// 
+    /// # Arguments
+    /// * `text` - 項目の文字列
+    fn bullet(&mut self, text: impl Into<String>) {
+        self.blocks.push(Block::Bullet(text.into()));
+    }
+
+    /// 表を追加
+    /// 
+    /// # Arguments
+    /// * `header` - 見出しの行
+    /// * `rows` - 行のリスト
+    fn table(&mut self, header: &[&str], rows: Vec<Vec<String>>) {
+        let mut table = vec![header.iter().map(|cell| cell.to_string()).collect()];
+        table.extend(rows);
+        self.blocks.push(Block::Table(table));
+    }
+
+    /// 横棒グラフの画像を追加
+    /// 
+    /// # Arguments
+    /// * `values` - 棒の値（表の行と同じ順）
+    fn bar_chart(&mut self, values: &[f64]) {
+        if values.is_empty() || values.iter().all(|value| *value <= 0.0) {
+            return;
+        }
+        let (png, width, height) = bar_chart_png(values);
+        self.images.push(png);
+        self.blocks.push(Block::Image(self.images.len(), width, height));
+        self.paragraph("（グラフの棒は下の表の行と同じ順）");
+    }
+
+    /// 改ページを追加
+    fn page_break(&mut self) {
+        self.blocks.push(Block::PageBreak);
+    }
+}
+
+/// 解析結果のレポートをWord形式、解析結果をCSV・TSV形式、またはテンプレートで出力
+/// 
+/// # Arguments
+/// * `events` - 通信イベントのリスト
+/// * `summary` - 解析結果のサマリー
+/// * `options` - 出力オプション（実行情報と指摘事項）
+/// * `format` - レポートの形式
+/// * `output_path` - 出力先のパス
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn export(
+    events: &[TrafficEvent],
+    summary: &Summary,
+    options: &ExportOptions,
+    format: ReportFormat,
+    output_path: &str,
+) -> Result<()> {
+    info!("レポートを出力しています: {}", output_path);
+    let results: Vec<&AnalysisResult> = events.iter().filter_map(TrafficEvent::as_http).collect();
+    match format {
+        ReportFormat::Docx => write_docx(&build_document(&results, summary, options), output_path)?,
+        ReportFormat::Csv => csv_export::export(&results, &options.columns, b',', &options.csv, &options.privacy, output_path)?,
+        ReportFormat::Tsv => csv_export::export(&results, &options.columns, b'\t', &options.csv, &options.privacy, output_path)?,
+        ReportFormat::Template => template_export::export(&results, summary, options, output_path)?,
+    }
+    info!("レポートの出力が完了しました: {}", output_path);
+    Ok(())
+}
+
+/// レポートの本文を作成
+/// 
+/// # Arguments
+/// * `results` - 解析結果のリスト
+/// * `summary` - 解析結果のサマリー
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Document` - 文書
+fn build_document(results: &[&AnalysisResult], summary: &Summary, options: &ExportOptions) -> Document {
+    let mut document = Document::default();
+    document.heading(0, "HAR解析レポート");
+
+    // 概要
+    document.heading(1, "概要");
+    let mut overview = Vec::new();
+    if let Some(run_info) = &options.run_info {
+        overview.push(vec!["入力ファイル".to_string(), run_info.input_file.clone()]);
+        overview.push(vec!["作成日時".to_string(), run_info.generated_at.clone()]);
+        overview.push(vec!["HARを作成したツール".to_string(), run_info.creator.clone()]);
+        overview.push(vec!["HARの総エントリ数".to_string(), run_info.total_entries.to_string()]);
+    }
+    overview.push(vec!["解析したリクエスト数".to_string(), summary.total.to_string()]);
+    for (method, count) in &summary.method_counts {
+        overview.push(vec![format!("{}リクエスト", method), count.to_string()]);
+    }
+    let failed = results.iter().filter(|result| !(1..400).contains(&result.status_code)).count();
+    overview.push(vec!["失敗したリクエスト（ステータスコード0・400以上）".to_string(), failed.to_string()]);
+    let bytes: i64 = results.iter().map(|result| result.request_size.max(0) + result.response_size.max(0)).sum();
+    overview.push(vec!["転送量(bytes)".to_string(), bytes.to_string()]);
+    if summary.render_blocking > 0 {
+        overview.push(vec!["レンダリングをブロックするリソース".to_string(), summary.render_blocking.to_string()]);
+    }
+    if !summary.business_hours.is_empty() {
+        let hours = &summary.business_hours;
+        overview.push(vec![
+            "営業時間内のリクエスト".to_string(),
+            format!("{}件（エラー {}件）", hours.inside, hours.inside_errors),
+        ]);
+        overview.push(vec![
+            "営業時間外のリクエスト".to_string(),
+            format!("{}件（エラー {}件）", hours.outside, hours.outside_errors),
+        ]);
+    }
+    if !summary.endpoint_success.is_empty() {
+        let operations: usize = summary.endpoint_success.iter().map(|success| success.operations).sum();
+        let succeeded: usize = summary.endpoint_success.iter().map(|success| success.succeeded).sum();
+        overview.push(vec![
+            "エンドポイントの成功率".to_string(),
+            format!("{:.1}% ({}/{}操作)", succeeded as f64 / operations.max(1) as f64 * 100.0, succeeded, operations),
+        ]);
+    }
+    if let Some(egress_cost) = &summary.egress_cost {
+        overview.push(vec![
+            "転送量の費用の見積もり".to_string(),
+            format!(
+                "{:.6} {}（100万回あたり {:.2} {}）",
+                egress_cost.total_cost(),
+                egress_cost.currency,
+                egress_cost.total_cost() * REPETITIONS as f64,
+                egress_cost.currency
+            ),
+        ]);
+    }
+    if let Some(sampling) = &summary.sampling {
+        overview.push(vec!["サンプリング".to_string(), sampling.clone()]);
+    }
+    document.table(&["項目", "値"], overview);
+    if let Some(run_info) = options.run_info.as_ref().filter(|run_info| !run_info.filters.is_empty()) {
+        document.paragraph("適用したフィルタ:");
+        for filter in &run_info.filters {
+            document.bullet(filter.clone());
+        }
+    }
+
+    // ステータスコードの内訳
+    document.heading(1, "ステータスコードの内訳");
+    let statuses: Vec<(&i32, &usize)> = summary.status_counts.iter().collect();
+    document.bar_chart(&statuses.iter().map(|(_, count)| **count as f64).collect::<Vec<_>>());
+    document.table(
+        &["ステータスコード", "件数", "割合"],
+        statuses
+            .iter()
+            .map(|(status, count)| {
+                vec![
+                    status.to_string(),
+                    count.to_string(),
+                    format!("{:.1}%", **count as f64 / summary.total.max(1) as f64 * 100.0),
+                ]
+            })
+            .collect(),
+    );
+
+    // ドメインの分類
+    let mut categories = CategoryTotals::default();
+    for result in results {
+        categories.add(result);
+    }
+    if !categories.is_empty() {
+        document.heading(1, "ドメインの分類ごとの転送量");
+        let rows: Vec<_> = categories
+            .categories()
+            .into_iter()
+            .filter(|(_, bucket)| bucket.requests > 0)
+            .collect();
+        document.bar_chart(&rows.iter().map(|(_, bucket)| bucket.bytes as f64).collect::<Vec<_>>());
+        document.table(
+            &["分類", "リクエスト数", "転送量(bytes)"],
+            rows.iter()
+                .map(|(category, bucket)| {
+                    vec![category.label().to_string(), bucket.requests.to_string(), bucket.bytes.to_string()]
+                })
+                .collect(),
+        );
+    }
+
+    // 転送量の費用の見積もり
+    if let Some(egress_cost) = summary.egress_cost.as_ref().filter(|egress_cost| !egress_cost.costs.is_empty()) {
+        document.heading(1, "転送量の費用の見積もり");
+        for (label, groups) in [("ホスト", egress_cost.by_host()), ("コンテンツタイプ", egress_cost.by_content_type())] {
+            document.paragraph(format!("{}別:", label));
+            document.table(
+                &[label, "転送量(bytes)", &format!("費用({})", egress_cost.currency)],
+                groups
+                    .iter()
+                    .take(MAX_TABLE_ROWS)
+                    .map(|(name, bytes, cost)| vec![name.clone(), bytes.to_string(), format!("{:.6}", cost)])
+                    .collect(),
+            );
+        }
+    }
+
+    // 所要時間の長いリクエスト
+    let mut slowest: Vec<&&AnalysisResult> = results.iter().collect();
+    slowest.sort_by(|a, b| b.duration.total_cmp(&a.duration));
+    slowest.truncate(MAX_TABLE_ROWS);
+    if !slowest.is_empty() {
+        document.heading(1, "所要時間の長いリクエスト");
+        document.bar_chart(&slowest.iter().map(|result| result.duration).collect::<Vec<_>>());
+        document.table(
+            &["エントリ番号", "メソッド", "URL", "ステータスコード", "所要時間(ms)"],
+            slowest
+                .iter()
+                .map(|result| {
+                    let mut url = String::new();
+                    let url = match options.privacy.value(Column::DecodedUrl, result, &mut url) {
+                        CellValue::Text(text) => text.to_string(),
+                        _ => String::new(),
+                    };
+                    vec![
+                        (result.entry_index + 1).to_string(),
+                        result.method.clone(),
+                        url,
+                        result.status_code.to_string(),
+                        format!("{:.1}", result.duration),
+                    ]
+                })
+                .collect(),
+        );
+    }
+
+    document.page_break();
+    build_findings(&mut document, summary, options);
+    document
+}
+
+/// 主な指摘事項の章を作成
+/// 
+/// # Arguments
+/// * `document` - 文書
+/// * `summary` - 解析結果のサマリー
+/// * `options` - 出力オプション
+fn build_findings(document: &mut Document, summary: &Summary, options: &ExportOptions) {
+    document.heading(1, "主な指摘事項");
+    // 指摘事項の表にはURL・エンドポイントがそのまま含まれるため、匿名化する場合は出力しない
+    if options.privacy.is_active() {
+        document.paragraph(format!(
+            "匿名化の設定（{}）を指定したため、URLを含む指摘事項は出力していません。",
+            options.privacy.name()
+        ));
+        return;
+    }
+    let mut found = false;
+
+    let failing: Vec<_> = summary
+        .endpoint_success
+        .iter()
+        .filter(|success| success.succeeded < success.operations)
+        .take(MAX_TABLE_ROWS)
+        .collect();
+    if !failing.is_empty() {
+        found = true;
+        document.heading(2, "成功率の低いエンドポイント");
+        document.table(
+            &["エンドポイント", "成功率", "操作数", "リクエスト数", "再試行あり"],
+            failing
+                .iter()
+                .map(|success| {
+                    vec![
+                        success.endpoint.clone(),
+                        format!("{:.1}%", success.rate() * 100.0),
+                        success.operations.to_string(),
+                        success.requests.to_string(),
+                        success.retried.to_string(),
+                    ]
+                })
+                .collect(),
+        );
+    }
+
+    if !summary.outliers.is_empty() {
+        found = true;
+        document.heading(2, "所要時間の外れ値を含むエンドポイント");
+        document.table(
+            &["エンドポイント", "外れ値の件数", "中央値(ms)", "最大(ms)"],
+            summary
+                .outliers
+                .iter()
+                .take(MAX_TABLE_ROWS)
+                .map(|endpoint| {
+                    vec![
+                        endpoint.endpoint.clone(),
+                        format!("{}/{}", endpoint.outliers, endpoint.samples),
+                        format!("{:.1}", endpoint.median),
+                        format!("{:.1}", endpoint.max),
+                    ]
+                })
+                .collect(),
+        );
+    }
+
+    if !options.security_findings.is_empty() {
+        found = true;
+        document.heading(2, "セキュリティ");
+        document.paragraph(format!("指摘事項: {}件", options.security_findings.len()));
+        document.table(
+            &["種類", "エントリ番号", "URL", "詳細"],
+            options
+                .security_findings
+                .iter()
+                .take(MAX_TABLE_ROWS)
+                .map(|finding| {
+                    vec![
+                        finding.kind.label().to_string(),
+                        finding.entry_number.to_string(),
+                        finding.url.clone(),
+                        finding.detail.clone(),
+                    ]
+                })
+                .collect(),
+        );
+    }
+
+    if !options.consent_report.cookies.is_empty() {
+        found = true;
+        document.heading(2, "同意より前に設定されたサードパーティのCookie");
+        document.paragraph(format!("Cookie: {}件", options.consent_report.cookies.len()));
+        document.table(
+            &["エントリ番号", "ホスト", "Cookie名", "分類"],
+            options
+                .consent_report
+                .cookies
+                .iter()
+                .take(MAX_TABLE_ROWS)
+                .map(|cookie| {
+                    vec![
+                        cookie.entry_number.to_string(),
+                        cookie.host.clone(),
+                        cookie.name.clone(),
+                        cookie.category.label().to_string(),
+                    ]
+                })
+                .collect(),
+        );
+    }
+
+    if !options.cache_violations.is_empty() {
+        found = true;
+        document.heading(2, "キャッシュの設定");
+        document.paragraph(format!("規則に違反するレスポンス: {}件", options.cache_violations.len()));
+        document.table(
+            &["エントリ番号", "リソースの種類", "URL", "推奨する値"],
+            options
+                .cache_violations
+                .iter()
+                .take(MAX_TABLE_ROWS)
+                .map(|violation| {
+                    vec![
+                        violation.entry_number.to_string(),
+                        violation.class.label().to_string(),
+                        violation.url.clone(),
+                        violation.suggested.clone(),
+                    ]
+                })
+                .collect(),
+        );
+    }
+
+    if !options.compression_savings.is_empty() {
+        found = true;
+        let total = crate::compression::total(&options.compression_savings);
+        document.heading(2, "圧縮されていないテキストのレスポンス");
+        document.paragraph(format!(
+            "{}件 {} bytesのレスポンスが圧縮されずに配信されています。gzipで{} bytes、brotliで{} bytes削減できる見込みです。",
+            total.responses,
+            total.original_bytes,
+            total.gzip_savings(),
+            total.brotli_savings()
+        ));
+        document.table(
+            &["ホスト", "レスポンス数", "元のサイズ(bytes)", "brotliで削減できるサイズ(bytes)"],
+            options
+                .compression_savings
+                .iter()
+                .take(MAX_TABLE_ROWS)
+                .map(|host| {
+                    vec![
+                        host.host.clone(),
+                        host.responses.to_string(),
+                        host.original_bytes.to_string(),
+                        host.brotli_savings().to_string(),
+                    ]
+                })
+                .collect(),
+        );
+    }
+
+    if !options.duplicate_responses.is_empty() {
+        found = true;
+        document.heading(2, "異なるURLで同じ内容のレスポンス");
+        document.table(
+            &["URL数", "サイズ(bytes)", "削減できるサイズ(bytes)", "URL"],
+            options
+                .duplicate_responses
+                .iter()
+                .take(MAX_TABLE_ROWS)
+                .map(|group| {
+                    vec![
+                        group.urls.len().to_string(),
+                        group.size.to_string(),
+                        group.savings().to_string(),
+                        group.urls.join("\n"),
+                    ]
+                })
+                .collect(),
+        );
+    }
+
+    if !options.page_sequences.is_empty() {
+        found = true;
+        document.heading(2, "ページ送りの一連のリクエスト");
+        document.table(
+            &["エンドポイント", "ページ数", "合計サイズ(bytes)", "経過時間(ms)"],
+            options
+                .page_sequences
+                .iter()
+                .take(MAX_TABLE_ROWS)
+                .map(|sequence| {
+                    vec![
+                        sequence.endpoint.clone(),
+                        sequence.pages().to_string(),
+                        sequence.bytes.to_string(),
+                        format!("{:.1}", sequence.elapsed),
+                    ]
+                })
+                .collect(),
+        );
+    }
+
+    if !found {
+        document.paragraph("指摘事項はありません。");
+    }
+}
+
+/// 横棒グラフのPNG画像を作成
+/// 
+/// # Arguments
+/// * `values` - 棒の値
+/// 
+/// # Returns
+/// * `(Vec<u8>, u32, u32)` - (PNG画像, 幅, 高さ)
+fn bar_chart_png(values: &[f64]) -> (Vec<u8>, u32, u32) {
+    let width = CHART_WIDTH;
+    let height = values.len() as u32 * (CHART_BAR_HEIGHT + CHART_BAR_GAP) + CHART_BAR_GAP;
+    let mut pixels = vec![0xFFu8; (width * height * 3) as usize];
+    let max = values.iter().copied().fold(0.0, f64::max);
+    let mut fill = |x0: u32, y0: u32, x1: u32, y1: u32, color: [u8; 3]| {
+        for y in y0..y1.min(height) {
+            for x in x0..x1.min(width) {
+                let offset = ((y * width + x) * 3) as usize;
+                pixels[offset..offset + 3].copy_from_slice(&color);
+            }
+        }
+    };
+
+    // 目盛り線（25%ごと）と縦軸
+    let plot_width = width - 2 * CHART_BAR_GAP;
+    for quarter in 1..=4 {
+        let x = CHART_BAR_GAP + plot_width * quarter / 4;
+        fill(x, 0, x + 1, height, [0xD9, 0xD9, 0xD9]);
+    }
+    for (index, value) in values.iter().enumerate() {
+        let y = CHART_BAR_GAP + index as u32 * (CHART_BAR_HEIGHT + CHART_BAR_GAP);
+        let bar = if max > 0.0 { (value.max(0.0) / max * plot_width as f64).round() as u32 } else { 0 };
+        fill(CHART_BAR_GAP, y, CHART_BAR_GAP + bar.max(1), y + CHART_BAR_HEIGHT, CHART_COLORS[index % CHART_COLORS.len()]);
+    }
+    fill(CHART_BAR_GAP, 0, CHART_BAR_GAP + 1, height, [0x59, 0x59, 0x59]);
+
+    let mut png = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        // メモリへの書き込みのため失敗しない
+        if let Ok(mut writer) = encoder.write_header() {
+            let _ = writer.write_image_data(&pixels);
+        }
+    }
+    (png, width, height)
+}
+
+/// 文書をWord形式のファイルに書き込む
+/// 
+/// # Arguments
+/// * `document` - 文書
+/// * `output_path` - 出力先のパス
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+fn write_docx(document: &Document, output_path: &str) -> Result<()> {
+    let file = File::create(output_path).map_err(AnalyzerError::io("レポートの作成に失敗しました"))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+
+    let mut parts = vec![
+        ("[Content_Types].xml".to_string(), CONTENT_TYPES_XML.as_bytes().to_vec()),
+        ("_rels/.rels".to_string(), ROOT_RELS_XML.as_bytes().to_vec()),
+        ("word/styles.xml".to_string(), STYLES_XML.as_bytes().to_vec()),
+        ("word/numbering.xml".to_string(), NUMBERING_XML.as_bytes().to_vec()),
+        ("word/footer1.xml".to_string(), FOOTER_XML.as_bytes().to_vec()),
+        ("word/_rels/document.xml.rels".to_string(), document_rels_xml(document.images.len()).into_bytes()),
+        ("word/document.xml".to_string(), document_xml(document).into_bytes()),
+    ];
+    for (index, image) in document.images.iter().enumerate() {
+        parts.push((format!("word/media/chart{}.png", index + 1), image.clone()));
+    }
+    for (name, content) in parts {
+        zip.start_file(name, options)
+            .map_err(AnalyzerError::export("レポートの作成に失敗しました"))?;
+        zip.write_all(&content).map_err(AnalyzerError::io("レポートの作成に失敗しました"))?;
+    }
+    zip.finish().map_err(AnalyzerError::export("レポートの作成に失敗しました"))?;
+    Ok(())
+}
+
+/// XML用にエスケープ
+/// 
+/// # Arguments
+/// * `value` - 文字列
+/// 
+/// # Returns
+/// * `String` - エスケープした文字列
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// 文字列の実行（改行は`w:br`）を作成
+/// 
+/// # Arguments
+/// * `text` - 文字列
+/// 
+/// # Returns
+/// * `String` - `w:r`要素
+fn run_xml(text: &str) -> String {
+    let runs: Vec<String> = text
+        .split('\n')
+        .map(|line| format!(r#"<w:t xml:space="preserve">{}</w:t>"#, escape_xml(line)))
+        .collect();
+    format!("<w:r>{}</w:r>", runs.join("<w:br/>"))
+}
+
+/// 本文のXMLを作成
+/// 
+/// # Arguments
+/// * `document` - 文書
+/// 
+/// # Returns
+/// * `String` - `word/document.xml`の内容
+fn document_xml(document: &Document) -> String {
+    let mut body = String::new();
+    for block in &document.blocks {
+        match block {
+            Block::Heading(level, text) => {
+                let style = if *level == 0 { "Title".to_string() } else { format!("Heading{}", level) };
+                body.push_str(&format!(r#"<w:p><w:pPr><w:pStyle w:val="{}"/></w:pPr>{}</w:p>"#, style, run_xml(text)));
+            }
+            Block::Paragraph(text) => body.push_str(&format!("<w:p>{}</w:p>", run_xml(text))),
+            Block::Bullet(text) => body.push_str(&format!(
+                r#"<w:p><w:pPr><w:pStyle w:val="ListParagraph"/><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr>{}</w:p>"#,
+                run_xml(text)
+            )),
+            Block::Table(rows) => {
+                body.push_str(r#"<w:tbl><w:tblPr><w:tblStyle w:val="TableGrid"/><w:tblW w:w="5000" w:type="pct"/></w:tblPr>"#);
+                let columns = rows.first().map_or(0, Vec::len);
+                body.push_str(&format!("<w:tblGrid>{}</w:tblGrid>", "<w:gridCol/>".repeat(columns)));
+                for (index, row) in rows.iter().enumerate() {
+                    body.push_str(if index == 0 { "<w:tr><w:trPr><w:tblHeader/></w:trPr>" } else { "<w:tr>" });
+                    for cell in row {
+                        let shading = if index == 0 { r#"<w:tcPr><w:shd w:val="clear" w:color="auto" w:fill="D9E2F3"/></w:tcPr>"# } else { "" };
+                        body.push_str(&format!("<w:tc>{}<w:p>{}</w:p></w:tc>", shading, run_xml(cell)));
+                    }
+                    body.push_str("</w:tr>");
+                }
+                body.push_str("</w:tbl><w:p/>");
+            }
+            Block::Image(number, width, height) => {
+                let (cx, cy) = (width * EMU_PER_PIXEL, height * EMU_PER_PIXEL);
+                body.push_str(&format!(
+                    concat!(
+                        r#"<w:p><w:r><w:drawing><wp:inline distT="0" distB="0" distL="0" distR="0">"#,
+                        r#"<wp:extent cx="{cx}" cy="{cy}"/><wp:docPr id="{n}" name="グラフ{n}"/>"#,
+                        r#"<a:graphic xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">"#,
+                        r#"<a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/picture">"#,
+                        r#"<pic:pic xmlns:pic="http://schemas.openxmlformats.org/drawingml/2006/picture">"#,
+                        r#"<pic:nvPicPr><pic:cNvPr id="{n}" name="chart{n}.png"/><pic:cNvPicPr/></pic:nvPicPr>"#,
+                        r#"<pic:blipFill><a:blip r:embed="rIdChart{n}"/><a:stretch><a:fillRect/></a:stretch></pic:blipFill>"#,
+                        r#"<pic:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="{cx}" cy="{cy}"/></a:xfrm>"#,
+                        r#"<a:prstGeom prst="rect"><a:avLst/></a:prstGeom></pic:spPr>"#,
+                        r#"</pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r></w:p>"#
+                    ),
+                    cx = cx,
+                    cy = cy,
+                    n = number
+                ));
+            }
+            Block::PageBreak => body.push_str(r#"<w:p><w:r><w:br w:type="page"/></w:r></w:p>"#),
+        }
+    }
+    format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" "#,
+            r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" "#,
+            r#"xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing">"#,
+            r#"<w:body>{}<w:sectPr><w:footerReference w:type="default" r:id="rIdFooter"/>"#,
+            r#"<w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1134" w:right="1134" w:bottom="1134" w:left="1134" w:header="567" w:footer="567" w:gutter="0"/>"#,
+            r#"</w:sectPr></w:body></w:document>"#
+        ),
+        body
+    )
+}
+
+/// 本文の関係（スタイル・番号付け・フッター・画像）のXMLを作成
+/// 
+/// # Arguments
+/// * `images` - 画像の数
+/// 
+/// # Returns
+/// * `String` - `word/_rels/document.xml.rels`の内容
+fn document_rels_xml(images: usize) -> String {
+    let mut relationships = String::from(concat!(
+        r#"<Relationship Id="rIdStyles" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>"#,
+        r#"<Relationship Id="rIdNumbering" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering" Target="numbering.xml"/>"#,
+        r#"<Relationship Id="rIdFooter" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/footer" Target="footer1.xml"/>"#
+    ));
+    for number in 1..=images {
+        relationships.push_str(&format!(
+            r#"<Relationship Id="rIdChart{n}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="media/chart{n}.png"/>"#,
+            n = number
+        ));
+    }
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">{}</Relationships>"#,
+        relationships
+    )
+}
+
+/// パッケージの内容の種類
+const CONTENT_TYPES_XML: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+    r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+    r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+    r#"<Default Extension="png" ContentType="image/png"/>"#,
+    r#"<Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>"#,
+    r#"<Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/>"#,
+    r#"<Override PartName="/word/numbering.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/>"#,
+    r#"<Override PartName="/word/footer1.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.footer+xml"/>"#,
+    r#"</Types>"#
+);
+
+/// パッケージの関係
+const ROOT_RELS_XML: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>"#,
+    r#"</Relationships>"#
+);
+
+/// スタイル（本文・表題・見出し・箇条書き・表）
+const STYLES_XML: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">"#,
+    r#"<w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Yu Gothic" w:eastAsia="Yu Gothic" w:hAnsi="Yu Gothic" w:cs="Yu Gothic"/>"#,
+    r#"<w:sz w:val="20"/><w:lang w:val="ja-JP" w:eastAsia="ja-JP"/></w:rPr></w:rPrDefault>"#,
+    r#"<w:pPrDefault><w:pPr><w:spacing w:after="120"/></w:pPr></w:pPrDefault></w:docDefaults>"#,
+    r#"<w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style>"#,
+    r#"<w:style w:type="paragraph" w:styleId="Title"><w:name w:val="Title"/><w:basedOn w:val="Normal"/>"#,
+    r#"<w:pPr><w:spacing w:after="240"/></w:pPr><w:rPr><w:b/><w:sz w:val="40"/></w:rPr></w:style>"#,
+    r#"<w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/>"#,
+    r#"<w:pPr><w:keepNext/><w:spacing w:before="360" w:after="120"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/><w:color w:val="2F5496"/><w:sz w:val="30"/></w:rPr></w:style>"#,
+    r#"<w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/>"#,
+    r#"<w:pPr><w:keepNext/><w:spacing w:before="240" w:after="80"/><w:outlineLvl w:val="1"/></w:pPr><w:rPr><w:b/><w:color w:val="2F5496"/><w:sz w:val="24"/></w:rPr></w:style>"#,
+    r#"<w:style w:type="paragraph" w:styleId="ListParagraph"><w:name w:val="List Paragraph"/><w:basedOn w:val="Normal"/>"#,
+    r#"<w:pPr><w:ind w:left="720"/></w:pPr></w:style>"#,
+    r#"<w:style w:type="table" w:styleId="TableGrid"><w:name w:val="Table Grid"/><w:tblPr><w:tblBorders>"#,
+    r#"<w:top w:val="single" w:sz="4" w:space="0" w:color="A6A6A6"/><w:left w:val="single" w:sz="4" w:space="0" w:color="A6A6A6"/>"#,
+    r#"<w:bottom w:val="single" w:sz="4" w:space="0" w:color="A6A6A6"/><w:right w:val="single" w:sz="4" w:space="0" w:color="A6A6A6"/>"#,
+    r#"<w:insideH w:val="single" w:sz="4" w:space="0" w:color="A6A6A6"/><w:insideV w:val="single" w:sz="4" w:space="0" w:color="A6A6A6"/>"#,
+    r#"</w:tblBorders></w:tblPr></w:style>"#,
+    r#"</w:styles>"#
+);
+
+/// 箇条書きの番号付け
+const NUMBERING_XML: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">"#,
+    r#"<w:abstractNum w:abstractNumId="0"><w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="bullet"/>"#,
+    r#"<w:lvlText w:val="・"/><w:lvlJc w:val="left"/><w:pPr><w:ind w:left="720" w:hanging="360"/></w:pPr></w:lvl></w:abstractNum>"#,
+    r#"<w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num>"#,
+    r#"</w:numbering>"#
+);
+
+/// ページ番号を表示するフッター
+const FOOTER_XML: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<w:ftr xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">"#,
+    r#"<w:p><w:pPr><w:jc w:val="center"/></w:pPr>"#,
+    r#"<w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve"> PAGE </w:instrText></w:r>"#,
+    r#"<w:r><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:t>1</w:t></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r>"#,
+    r#"<w:r><w:t xml:space="preserve"> / </w:t></w:r>"#,
+    r#"<w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve"> NUMPAGES </w:instrText></w:r>"#,
+    r#"<w:r><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:t>1</w:t></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r>"#,
+    r#"</w:p></w:ftr>"#
+);
@@ -0,0 +1,71 @@
+//! データシートの行のグループ化（アウトライン）を実装
+//! 
+//! 同じページ・ホストのリクエストを連続した行にまとめ、先頭の行以外をExcelのアウトラインでグループ化して
+//! 数千行のシートでもページ・ホストごとに折りたたんで確認できるようにする
+
+use crate::events::TrafficEvent;
+use crate::har_types::AnalysisResult;
+use std::collections::HashMap;
+
+/// 行をグループ化する単位
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowGrouping {
+    /// HARのページ（`pageref`）
+    Page,
+    /// リクエスト先のホスト
+    Host,
+}
+
+impl RowGrouping {
+    /// `--group-rows`で指定する名前からグループ化する単位を取得
+    /// 
+    /// # Arguments
+    /// * `name` - 単位の名前
+    /// 
+    /// # Returns
+    /// * `Option<RowGrouping>` - 対応する単位
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "page" => Some(RowGrouping::Page),
+            "host" => Some(RowGrouping::Host),
+            _ => None,
+        }
+    }
+
+    /// 行が属するグループのキーを取得
+    /// 
+    /// # Arguments
+    /// * `result` - 解析結果
+    /// 
+    /// # Returns
+    /// * `Option<&str>` - ページのIDまたはホスト名（ページに属さない場合はNone）
+    pub fn key<'a>(&self, result: &'a AnalysisResult) -> Option<&'a str> {
+        match self {
+            RowGrouping::Page => result.pageref.as_deref(),
+            RowGrouping::Host => Some(result.url_parts.host.as_str()),
+        }
+    }
+
+    /// 同じグループの行が連続するように通信イベントを並べ替える
+    /// 
+    /// グループは最初に出現した順に並べ、グループ内の行は元の順（時刻順）を保つ
+    /// 
+    /// # Arguments
+    /// * `events` - 通信イベントのリスト
+    /// 
+    /// # Returns
+    /// * `Vec<&TrafficEvent>` - 並べ替えた通信イベント
+    pub fn order<'a>(&self, events: &'a [TrafficEvent]) -> Vec<&'a TrafficEvent> {
+        let mut first_seen: HashMap<Option<&str>, usize> = HashMap::new();
+        let mut ordered: Vec<(usize, &TrafficEvent)> = events
+            .iter()
+            .map(|event| {
+                let key = event.as_http().and_then(|result| self.key(result));
+                let next = first_seen.len();
+                (*first_seen.entry(key).or_insert(next), event)
+            })
+            .collect();
+        ordered.sort_by_key(|(group, _)| *group);
+        ordered.into_iter().map(|(_, event)| event).collect()
+    }
+}
@@ -0,0 +1,193 @@
+//! レポートの作成経緯（実行情報）を実装
+
+use crate::har_types::{Creator, HarFile};
+use crate::producer::Producer;
+use crate::error::{AnalyzerError, Result};
+use chrono::Local;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// `--deterministic`で出力する作成日時（実行するたびに変わらないように固定する）
+pub const DETERMINISTIC_GENERATED_AT: &str = "1970-01-01 00:00:00+00:00";
+
+/// レポートを作成した時の実行情報
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RunInfo {
+    /// ツールのバージョン
+    pub tool_version: String,
+    /// 作成日時
+    pub generated_at: String,
+    /// 入力ファイル名
+    pub input_file: String,
+    /// 入力ファイルのサイズ（バイト）
+    pub input_size: u64,
+    /// 入力ファイルのSHA-256ハッシュ
+    pub input_sha256: String,
+    /// HARのバージョン
+    pub har_version: String,
+    /// HARを作成したツール（log.creator）
+    pub creator: String,
+    /// HARを作成したブラウザ（log.browser）
+    pub browser: String,
+    /// 判定した作成ツールと既知の癖
+    pub producer: String,
+    /// HARのコメント（log.comment）
+    pub comment: String,
+    /// HARに含まれる総エントリ数
+    pub total_entries: usize,
+    /// 適用したフィルタ
+    pub filters: Vec<String>,
+    /// コマンドライン引数
+    pub arguments: Vec<String>,
+}
+
+impl RunInfo {
+    /// 入力ファイルと解析したHARデータから実行情報を作成
+    /// 
+    /// # Arguments
+    /// * `input_file` - 入力ファイルのパス
+    /// * `har` - 解析したHARデータ
+    /// * `filters` - 適用したフィルタの説明
+    /// 
+    /// # Returns
+    /// * `Result<RunInfo>` - 成功時は実行情報、失敗時はエラー
+    pub fn collect(input_file: &str, har: &HarFile, filters: Vec<String>) -> Result<Self> {
+        let (input_size, input_sha256) = Self::hash_file(input_file)?;
+        let file_name = Path::new(input_file)
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or(input_file);
+
+        Ok(RunInfo {
+            tool_version: format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            generated_at: Local::now().format("%Y-%m-%d %H:%M:%S%:z").to_string(),
+            input_file: file_name.to_string(),
+            input_size,
+            input_sha256,
+            har_version: har.log.version.clone(),
+            creator: Self::describe(&har.log.creator),
+            browser: har.log.browser.as_ref().map(Self::describe).unwrap_or_default(),
+            producer: Self::describe_producer(Producer::detect(&har.log)),
+            comment: har.log.comment.clone().unwrap_or_default(),
+            total_entries: har.log.entries.len(),
+            filters,
+            arguments: std::env::args().collect(),
+        })
+    }
+
+    /// 実行するたびに変わる情報を固定した実行情報に変換
+    /// 
+    /// 作成日時を固定し、コマンドライン引数の絶対パスをファイル名に置き換える
+    /// 
+    /// # Returns
+    /// * `RunInfo` - 同じ入力・引数から同じ内容になる実行情報
+    pub fn deterministic(self) -> Self {
+        RunInfo {
+            generated_at: DETERMINISTIC_GENERATED_AT.to_string(),
+            arguments: self
+                .arguments
+                .iter()
+                .map(|argument| {
+                    let path = Path::new(argument);
+                    match path.file_name().and_then(|name| name.to_str()) {
+                        Some(name) if path.is_absolute() => name.to_string(),
+                        _ => argument.clone(),
+                    }
+                })
+                .collect(),
+            ..self
+        }
+    }
+
+    /// 作成ツール・ブラウザの情報を文字列に変換
+    /// 
+    /// # Arguments
+    /// * `creator` - 作成ツール・ブラウザの情報
+    /// 
+    /// # Returns
+    /// * `String` - 名前とバージョン（コメントがある場合は括弧書きで付加）
+    fn describe(creator: &Creator) -> String {
+        let text = format!("{} {}", creator.name, creator.version).trim_end().to_string();
+        match creator.comment.as_deref().filter(|c| !c.is_empty()) {
+            Some(comment) => format!("{} ({})", text, comment),
+            None => text,
+        }
+    }
+
+    /// 判定した作成ツールと既知の癖を文字列に変換
+    /// 
+    /// # Arguments
+    /// * `producer` - 作成ツール
+    /// 
+    /// # Returns
+    /// * `String` - 作成ツール名と既知の癖（改行区切り）
+    fn describe_producer(producer: Producer) -> String {
+        std::iter::once(producer.name())
+            .chain(producer.quirks().iter().copied())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// ファイルのサイズとSHA-256ハッシュを計算
+    /// 
+    /// # Arguments
+    /// * `path` - ファイルのパス
+    /// 
+    /// # Returns
+    /// * `Result<(u64, String)>` - (サイズ, 16進数のハッシュ値)
+    fn hash_file(path: &str) -> Result<(u64, String)> {
+        let mut file = File::open(path)
+            .map_err(AnalyzerError::io("入力ファイルのハッシュ計算に失敗しました"))?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut size = 0u64;
+
+        loop {
+            let read = file.read(&mut buffer)
+                .map_err(AnalyzerError::io("入力ファイルのハッシュ計算に失敗しました"))?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            size += read as u64;
+        }
+
+        let hash = hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        Ok((size, hash))
+    }
+
+    /// シートに出力する項目と値のリスト
+    /// 
+    /// # Arguments
+    /// * `analyzed_entries` - 出力したエントリ数
+    /// * `columns` - 出力した列名
+    /// 
+    /// # Returns
+    /// * `Vec<(&'static str, String)>` - (項目名, 値)のリスト
+    pub fn rows(&self, analyzed_entries: usize, columns: &[&str]) -> Vec<(&'static str, String)> {
+        vec![
+            ("ツール", self.tool_version.clone()),
+            ("作成日時", self.generated_at.clone()),
+            ("入力ファイル", self.input_file.clone()),
+            ("入力ファイルサイズ(bytes)", self.input_size.to_string()),
+            ("入力ファイルSHA-256", self.input_sha256.clone()),
+            ("HARバージョン", self.har_version.clone()),
+            ("HAR作成ツール", self.creator.clone()),
+            ("HARブラウザ", self.browser.clone()),
+            ("HARコメント", self.comment.clone()),
+            ("判定した作成ツール", self.producer.clone()),
+            ("総エントリ数", self.total_entries.to_string()),
+            ("出力エントリ数", analyzed_entries.to_string()),
+            ("適用したフィルタ", self.filters.join("\n")),
+            ("出力した列", columns.join(", ")),
+            ("コマンドライン引数", self.arguments.join(" ")),
+        ]
+    }
+}
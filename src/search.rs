@@ -0,0 +1,275 @@
+//! HARファイルのURL・ヘッダー・デコードしたボディを正規表現で検索するgrepサブコマンドを実装
+//! 
+//! 一致したエントリを番号・メソッド・URLとともに、一致した行（長い行は一致した部分の前後のみ）を表示する
+
+use crate::analyzer::HarAnalyzer;
+use crate::har_types::{Entry, NameValue};
+use crate::show::{Painter, BOLD, CYAN, DIM, RED};
+use regex::Regex;
+use std::fmt::Write;
+
+/// 一致した行を表示する最大の文字数（これより長い行は一致した部分の前後のみを表示する）
+const MAX_LINE_CHARS: usize = 160;
+
+/// 長い行で一致した部分の前後に表示する文字数
+const CONTEXT_CHARS: usize = 60;
+
+/// 検索結果の表示方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputMode {
+    /// 一致したエントリと一致した行を表示
+    #[default]
+    Lines,
+    /// 一致したエントリの数のみを表示
+    Count,
+    /// 一致したエントリのURLのみを表示
+    UrlsOnly,
+}
+
+/// 一致した箇所
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchLocation {
+    /// リクエストURL
+    Url,
+    /// リクエストヘッダー
+    RequestHeader,
+    /// リクエストボディ
+    RequestBody,
+    /// レスポンスヘッダー
+    ResponseHeader,
+    /// レスポンスボディ
+    ResponseBody,
+}
+
+impl MatchLocation {
+    /// 表示名を取得
+    /// 
+    /// # Returns
+    /// * `&'static str` - 表示名
+    pub fn label(&self) -> &'static str {
+        match self {
+            MatchLocation::Url => "URL",
+            MatchLocation::RequestHeader => "リクエストヘッダー",
+            MatchLocation::RequestBody => "リクエストボディ",
+            MatchLocation::ResponseHeader => "レスポンスヘッダー",
+            MatchLocation::ResponseBody => "レスポンスボディ",
+        }
+    }
+}
+
+/// 一致した行
+#[derive(Debug, Clone)]
+pub struct LineMatch {
+    /// 一致した箇所
+    pub location: MatchLocation,
+    /// 箇所内の行番号（1始まり）
+    pub line_number: usize,
+    /// 行のテキスト
+    pub line: String,
+}
+
+/// 一致したエントリ
+#[derive(Debug, Clone)]
+pub struct EntryMatch {
+    /// エントリ番号（HAR内の順序で1始まり）
+    pub entry_number: usize,
+    /// HTTPメソッド
+    pub method: String,
+    /// リクエストURL
+    pub url: String,
+    /// 一致した行（URL・リクエスト・レスポンスの順）
+    pub lines: Vec<LineMatch>,
+}
+
+/// 全てのエントリのURL・ヘッダー・デコードしたボディを検索
+/// 
+/// ボディはデータシートと同じ方法でデコード・整形してから行ごとに検索する
+/// 
+/// # Arguments
+/// * `analyzer` - HARファイルを読み込んだアナライザ
+/// * `regex` - 検索する正規表現
+/// 
+/// # Returns
+/// * `Vec<EntryMatch>` - HAR内の順序の一致したエントリ
+pub fn search(analyzer: &HarAnalyzer, regex: &Regex) -> Vec<EntryMatch> {
+    analyzer
+        .har_data()
+        .log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let lines = search_entry(analyzer, index, entry, regex);
+            (!lines.is_empty()).then(|| EntryMatch {
+                entry_number: index + 1,
+                method: entry.request.method.clone(),
+                url: entry.request.url.clone(),
+                lines,
+            })
+        })
+        .collect()
+}
+
+/// 1件のエントリを検索
+/// 
+/// # Arguments
+/// * `analyzer` - HARファイルを読み込んだアナライザ
+/// * `index` - エントリの位置（0始まり）
+/// * `entry` - HARエントリ
+/// * `regex` - 検索する正規表現
+/// 
+/// # Returns
+/// * `Vec<LineMatch>` - 一致した行
+fn search_entry(analyzer: &HarAnalyzer, index: usize, entry: &Entry, regex: &Regex) -> Vec<LineMatch> {
+    let mut lines = Vec::new();
+    push_matches(&mut lines, MatchLocation::Url, &entry.request.url, regex);
+    push_matches(&mut lines, MatchLocation::RequestHeader, &header_text(&entry.request.headers), regex);
+
+    let (request_body, response_body) = analyzer.entry_payloads(index).unwrap_or_default();
+    // ボディが無いリクエストのペイロードはクエリパラメータのため、URLと重複して一致しないようにする
+    if entry.request.post_data.is_some() {
+        push_matches(&mut lines, MatchLocation::RequestBody, &request_body, regex);
+    }
+    push_matches(&mut lines, MatchLocation::ResponseHeader, &header_text(&entry.response.headers), regex);
+    if entry.response.content.text.is_some() {
+        push_matches(&mut lines, MatchLocation::ResponseBody, &response_body, regex);
+    }
+    lines
+}
+
+/// ヘッダーを1行に1つずつ「名前: 値」の形式で並べる
+/// 
+/// # Arguments
+/// * `headers` - ヘッダーのリスト
+/// 
+/// # Returns
+/// * `String` - ヘッダーのテキスト
+fn header_text(headers: &[NameValue]) -> String {
+    headers
+        .iter()
+        .map(|header| format!("{}: {}", header.name, header.value))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// テキストの一致した行を追加
+/// 
+/// # Arguments
+/// * `lines` - 一致した行の追加先
+/// * `location` - 一致した箇所
+/// * `text` - 検索するテキスト
+/// * `regex` - 検索する正規表現
+fn push_matches(lines: &mut Vec<LineMatch>, location: MatchLocation, text: &str, regex: &Regex) {
+    lines.extend(
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| regex.is_match(line))
+            .map(|(number, line)| LineMatch {
+                location,
+                line_number: number + 1,
+                line: line.to_string(),
+            }),
+    );
+}
+
+/// 検索結果を表示用のテキストに整形
+/// 
+/// # Arguments
+/// * `matches` - 一致したエントリ
+/// * `regex` - 検索した正規表現（一致した部分の強調に使用）
+/// * `mode` - 表示方法
+/// * `color` - ANSIエスケープシーケンスで色を付ける場合はtrue
+/// 
+/// # Returns
+/// * `String` - 表示用のテキスト
+pub fn render(matches: &[EntryMatch], regex: &Regex, mode: OutputMode, color: bool) -> String {
+    let painter = Painter { color };
+    let mut out = String::new();
+    match mode {
+        OutputMode::Count => {
+            let _ = writeln!(out, "{}", matches.len());
+        }
+        OutputMode::UrlsOnly => {
+            for entry in matches {
+                let _ = writeln!(out, "{}", entry.url);
+            }
+        }
+        OutputMode::Lines => {
+            for entry in matches {
+                let _ = writeln!(
+                    out,
+                    "{} {} {}",
+                    painter.paint(DIM, &format!("#{}", entry.entry_number)),
+                    painter.paint(BOLD, &entry.method),
+                    entry.url
+                );
+                for line in &entry.lines {
+                    let _ = writeln!(
+                        out,
+                        "  {} {}",
+                        painter.paint(CYAN, &format!("[{}:{}]", line.location.label(), line.line_number)),
+                        highlight(&excerpt(&line.line, regex), regex, painter)
+                    );
+                }
+            }
+        }
+    }
+    out
+}
+
+/// 長い行を一致した部分の前後に切り詰める
+/// 
+/// # Arguments
+/// * `line` - 行のテキスト
+/// * `regex` - 検索した正規表現
+/// 
+/// # Returns
+/// * `String` - 表示する部分（切り詰めた側には「…」を付ける）
+fn excerpt(line: &str, regex: &Regex) -> String {
+    let line = line.trim();
+    if line.chars().count() <= MAX_LINE_CHARS {
+        return line.to_string();
+    }
+    let Some(found) = regex.find(line) else {
+        return line.chars().take(MAX_LINE_CHARS).collect::<String>() + "…";
+    };
+    let start = line[..found.start()]
+        .char_indices()
+        .rev()
+        .nth(CONTEXT_CHARS - 1)
+        .map_or(0, |(position, _)| position);
+    let end = line[found.end()..]
+        .char_indices()
+        .nth(CONTEXT_CHARS)
+        .map_or(line.len(), |(position, _)| found.end() + position);
+    format!(
+        "{}{}{}",
+        if start > 0 { "…" } else { "" },
+        &line[start..end],
+        if end < line.len() { "…" } else { "" }
+    )
+}
+
+/// 一致した部分に色を付ける
+/// 
+/// # Arguments
+/// * `line` - 行のテキスト
+/// * `regex` - 検索した正規表現
+/// * `painter` - 色の指定
+/// 
+/// # Returns
+/// * `String` - 色を付けたテキスト
+fn highlight(line: &str, regex: &Regex, painter: Painter) -> String {
+    if !painter.color {
+        return line.to_string();
+    }
+    let mut out = String::new();
+    let mut last = 0;
+    for found in regex.find_iter(line).filter(|found| !found.is_empty()) {
+        out.push_str(&line[last..found.start()]);
+        out.push_str(&painter.paint(RED, found.as_str()));
+        last = found.end();
+    }
+    out.push_str(&line[last..]);
+    out
+}
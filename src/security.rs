@@ -0,0 +1,203 @@
+//! セキュリティレビュー向けの指摘事項（混在コンテンツ・平文の認証情報）の検出を実装
+//! 
+//! 解析対象の絞り込みに関わらずキャプチャ全体のエントリを調べ、Security Findingsシートに出力する
+
+use crate::har_types::{Entry, HarFile};
+use log::warn;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use url::Url;
+
+/// 指摘事項の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FindingKind {
+    /// HTTPSのページから読み込まれたHTTPのリクエスト
+    MixedContent,
+    /// Cookie・Authorizationヘッダーを平文のHTTPで送信しているリクエスト
+    InsecureCredentials,
+    /// リクエスト先が脅威インテリジェンスの指標に一致したリクエスト
+    ThreatIntel,
+    /// ホスト名を使わずにIPアドレスへ接続したリクエスト（`--hunt`）
+    RawIp,
+    /// Punycodeで表記した国際化ドメイン名へのリクエスト（`--hunt`）
+    Punycode,
+    /// 一般的でないポートへのリクエスト（`--hunt`）
+    UncommonPort,
+    /// URLのパス・クエリやリクエストボディのエントロピーが高いリクエスト（`--hunt`）
+    HighEntropy,
+    /// 一定の間隔で繰り返し送信したリクエスト（`--hunt`）
+    Beacon,
+    /// ソースマップを公開しているJavaScript・CSS
+    SourceMap,
+}
+
+impl FindingKind {
+    /// シートに表示する種類の名前
+    /// 
+    /// # Returns
+    /// * `&'static str` - 種類の名前
+    pub fn label(&self) -> &'static str {
+        match self {
+            FindingKind::MixedContent => "混在コンテンツ",
+            FindingKind::InsecureCredentials => "平文の認証情報",
+            FindingKind::ThreatIntel => "脅威インテリジェンス",
+            FindingKind::RawIp => "IPアドレスへの接続",
+            FindingKind::Punycode => "Punycodeのドメイン",
+            FindingKind::UncommonPort => "一般的でないポート",
+            FindingKind::HighEntropy => "高エントロピー",
+            FindingKind::Beacon => "ビーコン",
+            FindingKind::SourceMap => "ソースマップの公開",
+        }
+    }
+}
+
+/// 指摘事項
+#[derive(Debug, Clone)]
+pub struct SecurityFinding {
+    /// 種類
+    pub kind: FindingKind,
+    /// エントリ番号（HAR内の順序で1始まり）
+    pub entry_number: usize,
+    /// リクエストの開始時刻
+    pub timestamp: String,
+    /// HTTPメソッド
+    pub method: String,
+    /// リクエストURL
+    pub url: String,
+    /// 詳細
+    pub detail: String,
+}
+
+/// キャプチャ全体から指摘事項を検出
+/// 
+/// # Arguments
+/// * `har` - HARデータ
+/// 
+/// # Returns
+/// * `Vec<SecurityFinding>` - エントリ順の指摘事項
+pub fn scan(har: &HarFile) -> Vec<SecurityFinding> {
+    let page_urls: HashMap<&str, &str> = har
+        .log
+        .pages
+        .iter()
+        .map(|page| (page.id.as_str(), page.title.as_str()))
+        .collect();
+
+    let mut findings = Vec::new();
+    for (index, entry) in har.log.entries.iter().enumerate() {
+        let Ok(url) = Url::parse(&entry.request.url) else {
+            continue;
+        };
+        if !matches!(url.scheme(), "http" | "ws") || is_loopback(&url) {
+            continue;
+        }
+        let finding = |kind: FindingKind, detail: String| SecurityFinding {
+            kind,
+            entry_number: index + 1,
+            timestamp: entry.started_date_time.clone(),
+            method: entry.request.method.clone(),
+            url: entry.request.url.clone(),
+            detail,
+        };
+
+        if let Some(origin) = secure_initiator(entry, &page_urls) {
+            findings.push(finding(FindingKind::MixedContent, format!("HTTPSのページから読み込まれています: {}", origin)));
+        }
+        for credential in plaintext_credentials(entry) {
+            findings.push(finding(FindingKind::InsecureCredentials, format!("{}を平文で送信しています", credential)));
+        }
+    }
+    findings
+}
+
+/// 指摘事項の件数を種類ごとにログに出力
+/// 
+/// # Arguments
+/// * `findings` - 指摘事項のリスト
+pub fn log_findings(findings: &[SecurityFinding]) {
+    let mut counts: Vec<(FindingKind, usize)> = Vec::new();
+    for finding in findings {
+        match counts.iter_mut().find(|(kind, _)| *kind == finding.kind) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((finding.kind, 1)),
+        }
+    }
+    for (kind, count) in counts {
+        warn!("セキュリティの指摘事項: {} {}件（Security Findingsシートを参照）", kind.label(), count);
+    }
+}
+
+/// リクエストの発生元がHTTPSのページかどうかを判定
+/// 
+/// Refererヘッダーを優先し、無い場合はエントリが属するページのタイトル（ブラウザはURLを記録する）を使用する
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// * `page_urls` - ページIDごとのタイトル
+/// 
+/// # Returns
+/// * `Option<String>` - HTTPSの発生元のURL（HTTPSのページからでない場合はNone）
+fn secure_initiator(entry: &Entry, page_urls: &HashMap<&str, &str>) -> Option<String> {
+    let referer = entry
+        .request
+        .headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case("referer"))
+        .map(|header| header.value.as_str());
+    let origin = match referer {
+        Some(referer) => referer,
+        None => page_urls.get(entry.pageref.as_deref()?)?,
+    };
+    origin.to_ascii_lowercase().starts_with("https://").then(|| origin.to_string())
+}
+
+/// 平文で送信している認証情報の種類を取得
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `Vec<String>` - 認証情報の説明（値そのものは含めない）
+fn plaintext_credentials(entry: &Entry) -> Vec<String> {
+    let mut credentials = Vec::new();
+    let has_cookie_header = entry
+        .request
+        .headers
+        .iter()
+        .any(|header| header.name.eq_ignore_ascii_case("cookie"));
+    if has_cookie_header || !entry.request.cookies.is_empty() {
+        credentials.push("Cookie".to_string());
+    }
+    if let Some(authorization) = entry
+        .request
+        .headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case("authorization"))
+    {
+        // 資格情報そのものはレポートに残さず、認証方式のみを記録する
+        let scheme = authorization.value.split_whitespace().next().unwrap_or_default();
+        credentials.push(format!("Authorizationヘッダー（{}）", scheme));
+    }
+    credentials
+}
+
+/// ループバックアドレス（開発環境）へのリクエストかどうか
+/// 
+/// # Arguments
+/// * `url` - リクエストURL
+/// 
+/// # Returns
+/// * `bool` - localhostまたはループバックアドレスの場合はtrue
+pub(crate) fn is_loopback(url: &Url) -> bool {
+    match url.host_str() {
+        Some(host) => {
+            host.eq_ignore_ascii_case("localhost")
+                || host
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .parse::<IpAddr>()
+                    .is_ok_and(|ip| ip.is_loopback())
+        }
+        None => false,
+    }
+}
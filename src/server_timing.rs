@@ -0,0 +1,227 @@
+//! Server-Timingレスポンスヘッダーの解析と待機時間との比較を実装
+//! 
+//! バックエンドが報告した処理時間（`db;dur=12.3, app;dur=40`）を指標ごとに分解し、
+//! HARに記録された待機時間（サーバーの応答を待った時間）と並べてServer Timingシートに出力する。
+//! 待機時間との差はネットワークの往復・キューイング等のサーバーの処理以外の時間の目安になる
+
+use crate::har_types::Entry;
+
+/// 処理全体の時間として扱う指標の名前
+const TOTAL_METRIC: &str = "total";
+
+/// Server-Timingの1件の指標
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerTimingMetric {
+    /// 指標の名前
+    pub name: String,
+    /// 処理時間（ミリ秒、`dur`が無い場合はNone）
+    pub duration: Option<f64>,
+    /// 説明（`desc`、無い場合は空文字列）
+    pub description: String,
+}
+
+/// Server-Timingヘッダーを記録したエントリ
+#[derive(Debug, Clone)]
+pub struct ServerTimingEntry {
+    /// エントリ番号（HAR内の順序で1始まり）
+    pub entry_number: usize,
+    /// HTTPメソッド
+    pub method: String,
+    /// リクエストURL
+    pub url: String,
+    /// HARに記録された待機時間（ミリ秒）
+    pub wait: f64,
+    /// 報告された指標（ヘッダーの順）
+    pub metrics: Vec<ServerTimingMetric>,
+}
+
+impl ServerTimingEntry {
+    /// サーバーの処理時間
+    /// 
+    /// # Returns
+    /// * `Option<f64>` - 処理時間（ミリ秒、時間を報告した指標が無い場合はNone）
+    pub fn server_time(&self) -> Option<f64> {
+        server_time(&self.metrics)
+    }
+
+    /// 待機時間とサーバーの処理時間の差
+    /// 
+    /// # Returns
+    /// * `Option<f64>` - 差（ミリ秒、負の場合はサーバーの報告が待機時間を超えている）
+    pub fn overhead(&self) -> Option<f64> {
+        self.server_time().map(|server_time| self.wait - server_time)
+    }
+
+    /// 指標の時間を取得
+    /// 
+    /// # Arguments
+    /// * `name` - 指標の名前
+    /// 
+    /// # Returns
+    /// * `Option<f64>` - 処理時間（ミリ秒、指標が無いか時間が無い場合はNone）
+    pub fn duration(&self, name: &str) -> Option<f64> {
+        self.metrics
+            .iter()
+            .find(|metric| metric.name == name)
+            .and_then(|metric| metric.duration)
+    }
+}
+
+/// エントリのServer-Timingヘッダーを解析
+/// 
+/// 複数のヘッダーがある場合は全ての指標を順に連結する
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `Vec<ServerTimingMetric>` - 指標（ヘッダーが無い場合は空）
+pub fn metrics(entry: &Entry) -> Vec<ServerTimingMetric> {
+    entry
+        .response
+        .headers
+        .iter()
+        .filter(|header| header.name.eq_ignore_ascii_case("server-timing"))
+        .flat_map(|header| parse(&header.value))
+        .collect()
+}
+
+/// Server-Timingヘッダーの値を解析
+/// 
+/// `name;dur=12.3;desc="説明"`をカンマで区切った形式で、不正なパラメータは無視する
+/// 
+/// # Arguments
+/// * `value` - ヘッダーの値
+/// 
+/// # Returns
+/// * `Vec<ServerTimingMetric>` - 指標
+pub fn parse(value: &str) -> Vec<ServerTimingMetric> {
+    split_outside_quotes(value, ',')
+        .into_iter()
+        .filter_map(|metric| {
+            let mut parts = split_outside_quotes(metric, ';').into_iter();
+            let name = parts.next()?.trim();
+            if name.is_empty() {
+                return None;
+            }
+            let mut duration = None;
+            let mut description = String::new();
+            for parameter in parts {
+                let Some((key, value)) = parameter.split_once('=') else {
+                    continue;
+                };
+                let value = value.trim().trim_matches('"');
+                match key.trim().to_ascii_lowercase().as_str() {
+                    "dur" if duration.is_none() => duration = value.parse::<f64>().ok().filter(|dur| dur.is_finite()),
+                    "desc" if description.is_empty() => description = value.replace("\\\"", "\""),
+                    _ => {}
+                }
+            }
+            Some(ServerTimingMetric { name: name.to_string(), duration, description })
+        })
+        .collect()
+}
+
+/// 引用符の外側の区切り文字で分割
+/// 
+/// # Arguments
+/// * `value` - 分割する文字列
+/// * `separator` - 区切り文字
+/// 
+/// # Returns
+/// * `Vec<&str>` - 分割した文字列
+fn split_outside_quotes(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (position, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => {
+                parts.push(&value[start..position]);
+                start = position + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+/// 指標からサーバーの処理時間を求める
+/// 
+/// `total`の指標があればその時間、無い場合は指標が入れ子になっている可能性があるため
+/// 合計ではなく最大の時間とする
+/// 
+/// # Arguments
+/// * `metrics` - 指標
+/// 
+/// # Returns
+/// * `Option<f64>` - 処理時間（ミリ秒、時間を報告した指標が無い場合はNone）
+pub fn server_time(metrics: &[ServerTimingMetric]) -> Option<f64> {
+    metrics
+        .iter()
+        .find(|metric| metric.name.eq_ignore_ascii_case(TOTAL_METRIC))
+        .and_then(|metric| metric.duration)
+        .or_else(|| metrics.iter().filter_map(|metric| metric.duration).reduce(f64::max))
+}
+
+/// 指標を1行の文字列に変換（`db=12.3ms; cache; app=40ms`）
+/// 
+/// # Arguments
+/// * `metrics` - 指標
+/// 
+/// # Returns
+/// * `String` - 指標の一覧（指標が無い場合は空文字列）
+pub fn describe(metrics: &[ServerTimingMetric]) -> String {
+    metrics
+        .iter()
+        .map(|metric| match metric.duration {
+            Some(duration) => format!("{}={}ms", metric.name, duration),
+            None => metric.name.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Server-Timingヘッダーを記録したエントリを収集
+/// 
+/// # Arguments
+/// * `entries` - (エントリの位置, エントリ)のイテレータ
+/// 
+/// # Returns
+/// * `Vec<ServerTimingEntry>` - エントリ順のServer-Timingヘッダーを記録したエントリ
+pub fn collect<'a>(entries: impl Iterator<Item = (usize, &'a Entry)>) -> Vec<ServerTimingEntry> {
+    entries
+        .filter_map(|(index, entry)| {
+            let metrics = metrics(entry);
+            (!metrics.is_empty()).then(|| ServerTimingEntry {
+                entry_number: index + 1,
+                method: entry.request.method.clone(),
+                url: entry.request.url.clone(),
+                wait: entry.timings.wait.max(0.0),
+                metrics,
+            })
+        })
+        .collect()
+}
+
+/// 指標の名前の一覧（最初に現れた順）
+/// 
+/// # Arguments
+/// * `entries` - Server-Timingヘッダーを記録したエントリ
+/// 
+/// # Returns
+/// * `Vec<&str>` - 指標の名前
+pub fn metric_names(entries: &[ServerTimingEntry]) -> Vec<&str> {
+    let mut names: Vec<&str> = Vec::new();
+    for metric in entries.iter().flat_map(|entry| &entry.metrics) {
+        if !names.contains(&metric.name.as_str()) {
+            names.push(&metric.name);
+        }
+    }
+    names
+}
@@ -0,0 +1,290 @@
+//! 1件のエントリのリクエスト・レスポンスを端末に表示するshowサブコマンドの出力を実装
+//! 
+//! ヘッダー・デコードしたボディ・タイミングを読みやすく整形し、端末に出力する場合はANSIエスケープシーケンスで色を付ける
+
+use crate::har_types::{Entry, NameValue, Timings};
+use regex::Regex;
+use std::fmt::Write;
+
+/// 見出しの色（太字）
+pub(crate) const BOLD: &str = "\x1b[1m";
+/// ヘッダー名・JSONのキーの色（シアン）
+pub(crate) const CYAN: &str = "\x1b[36m";
+/// JSONの文字列・成功したステータスの色（緑）
+pub(crate) const GREEN: &str = "\x1b[32m";
+/// JSONの数値・クライアントエラーのステータスの色（黄）
+pub(crate) const YELLOW: &str = "\x1b[33m";
+/// JSONのtrue・false・nullの色（マゼンタ）
+pub(crate) const MAGENTA: &str = "\x1b[35m";
+/// サーバーエラーのステータスの色（赤）
+pub(crate) const RED: &str = "\x1b[31m";
+/// 補足情報の色（灰色）
+pub(crate) const DIM: &str = "\x1b[2m";
+/// 色の指定の解除
+pub(crate) const RESET: &str = "\x1b[0m";
+
+/// 表示するエントリの指定
+#[derive(Debug, Clone)]
+pub enum EntrySelector {
+    /// エントリ番号（HAR内の順序で1始まり）
+    Number(usize),
+    /// リクエストURLが最初に一致するエントリ
+    UrlRegex(Regex),
+    /// エントリの内容から求めたID（エントリID列の値）
+    Id(String),
+}
+
+impl EntrySelector {
+    /// 指定に一致するエントリの位置を求める
+    /// 
+    /// # Arguments
+    /// * `entries` - HARエントリのリスト
+    /// 
+    /// # Returns
+    /// * `Option<usize>` - エントリの位置（0始まり、一致しない場合はNone）
+    pub fn find(&self, entries: &[Entry]) -> Option<usize> {
+        match self {
+            EntrySelector::Number(number) => {
+                (*number >= 1 && *number <= entries.len()).then(|| number - 1)
+            }
+            EntrySelector::UrlRegex(regex) => entries.iter().position(|entry| regex.is_match(&entry.request.url)),
+            EntrySelector::Id(id) => entries.iter().position(|entry| entry.content_id().eq_ignore_ascii_case(id)),
+        }
+    }
+}
+
+/// 色を付けるかどうかを切り替えて文字列を装飾する
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Painter {
+    pub(crate) color: bool,
+}
+
+impl Painter {
+    /// 色を付けた文字列を作成
+    /// 
+    /// # Arguments
+    /// * `style` - ANSIエスケープシーケンス
+    /// * `text` - 文字列
+    /// 
+    /// # Returns
+    /// * `String` - 色を付けない場合はそのままの文字列
+    pub(crate) fn paint(&self, style: &str, text: &str) -> String {
+        if self.color {
+            format!("{}{}{}", style, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// 1件のエントリを表示用のテキストに整形
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// * `number` - エントリ番号（1始まり）
+/// * `total` - HAR内のエントリの数
+/// * `payloads` - デコード・整形済みの(リクエストボディ, レスポンスボディ)
+/// * `color` - ANSIエスケープシーケンスで色を付ける場合はtrue
+/// 
+/// # Returns
+/// * `String` - 表示用のテキスト
+pub fn render(entry: &Entry, number: usize, total: usize, payloads: (&str, &str), color: bool) -> String {
+    let painter = Painter { color };
+    let (request_body, response_body) = payloads;
+    let mut out = String::new();
+
+    let _ = writeln!(
+        out,
+        "{}",
+        painter.paint(DIM, &format!("エントリ {} / {} (ID: {})", number, total, entry.content_id()))
+    );
+    let _ = writeln!(
+        out,
+        "{} {} {}",
+        painter.paint(BOLD, &entry.request.method),
+        entry.request.url,
+        painter.paint(DIM, &entry.request.http_version)
+    );
+    let status = format!("{} {}", entry.response.status, entry.response.status_text);
+    let _ = writeln!(
+        out,
+        "{} {}",
+        painter.paint(status_style(entry.response.status), status.trim_end()),
+        painter.paint(DIM, &entry.response.http_version)
+    );
+    let _ = writeln!(out, "開始時刻: {}", entry.started_date_time);
+    if let Some(ip) = &entry.server_ip_address {
+        let _ = writeln!(out, "サーバーのIPアドレス: {}", ip);
+    }
+
+    section(&mut out, painter, "タイミング（ミリ秒）");
+    write_timings(&mut out, painter, &entry.timings, entry.time);
+
+    section(&mut out, painter, "リクエストヘッダー");
+    write_headers(&mut out, painter, &entry.request.headers);
+
+    // ボディが無いリクエストのクエリパラメータはURLで確認できるため表示しない
+    if entry.request.post_data.is_some() {
+        section(&mut out, painter, "リクエストボディ");
+        write_body(&mut out, painter, request_body);
+    }
+
+    section(&mut out, painter, "レスポンスヘッダー");
+    write_headers(&mut out, painter, &entry.response.headers);
+
+    section(&mut out, painter, "レスポンスボディ");
+    if entry.response.content.text.is_some() {
+        write_body(&mut out, painter, response_body);
+    } else {
+        let _ = writeln!(out, "{}", painter.paint(DIM, "（記録されていません）"));
+    }
+    out
+}
+
+/// ステータスコードの色を取得
+/// 
+/// # Arguments
+/// * `status` - ステータスコード
+/// 
+/// # Returns
+/// * `&'static str` - ANSIエスケープシーケンス
+fn status_style(status: i32) -> &'static str {
+    match status {
+        200..=299 => GREEN,
+        300..=399 => CYAN,
+        400..=499 => YELLOW,
+        _ => RED,
+    }
+}
+
+/// 見出しを出力
+/// 
+/// # Arguments
+/// * `out` - 出力先
+/// * `painter` - 色の指定
+/// * `title` - 見出し
+fn section(out: &mut String, painter: Painter, title: &str) {
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{}", painter.paint(BOLD, &format!("== {} ==", title)));
+}
+
+/// 各フェーズの所要時間を出力
+/// 
+/// # Arguments
+/// * `out` - 出力先
+/// * `painter` - 色の指定
+/// * `timings` - タイミング情報
+/// * `time` - エントリの所要時間（ミリ秒）
+fn write_timings(out: &mut String, painter: Painter, timings: &Timings, time: f64) {
+    let phases = [
+        ("blocked", timings.blocked),
+        ("dns", timings.dns),
+        ("connect", timings.connect),
+        ("ssl", timings.ssl),
+        ("send", Some(timings.send)),
+        ("wait", Some(timings.wait)),
+        ("receive", Some(timings.receive)),
+        ("time", Some(time)),
+    ];
+    for (name, value) in phases {
+        // 計測されていないフェーズは-1で記録される
+        let value = match value {
+            Some(value) if value >= 0.0 => format!("{:.3}", value),
+            _ => "-".to_string(),
+        };
+        let _ = writeln!(out, "{} {:>12}", painter.paint(CYAN, &format!("{:<8}", name)), value);
+    }
+}
+
+/// ヘッダーを出力
+/// 
+/// # Arguments
+/// * `out` - 出力先
+/// * `painter` - 色の指定
+/// * `headers` - ヘッダーのリスト
+fn write_headers(out: &mut String, painter: Painter, headers: &[NameValue]) {
+    if headers.is_empty() {
+        let _ = writeln!(out, "{}", painter.paint(DIM, "（なし）"));
+    }
+    for header in headers {
+        let _ = writeln!(out, "{}: {}", painter.paint(CYAN, &header.name), header.value);
+    }
+}
+
+/// ボディを出力（JSONの場合はキー・値の種類ごとに色を付ける）
+/// 
+/// # Arguments
+/// * `out` - 出力先
+/// * `painter` - 色の指定
+/// * `body` - デコード・整形済みのボディ
+fn write_body(out: &mut String, painter: Painter, body: &str) {
+    if body.is_empty() {
+        let _ = writeln!(out, "{}", painter.paint(DIM, "（空）"));
+        return;
+    }
+    let trimmed = body.trim_start();
+    if painter.color && (trimmed.starts_with('{') || trimmed.starts_with('[')) {
+        out.push_str(&highlight_json(body));
+    } else {
+        out.push_str(body);
+    }
+    if !body.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// JSONのテキストに色を付ける
+/// 
+/// 整形済みのテキストを1文字ずつ走査し、文字列（キーと値を区別）・数値・リテラルを色分けする。
+/// JSONとして不正な部分があってもそのまま出力する
+/// 
+/// # Arguments
+/// * `json` - JSONのテキスト
+/// 
+/// # Returns
+/// * `String` - 色を付けたテキスト
+fn highlight_json(json: &str) -> String {
+    let chars: Vec<char> = json.chars().collect();
+    let mut out = String::with_capacity(json.len() * 2);
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '"' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            let token: String = chars[start..i].iter().collect();
+            let is_key = chars[i..].iter().find(|c| !c.is_whitespace()) == Some(&':');
+            let style = if is_key { CYAN } else { GREEN };
+            let _ = write!(out, "{}{}{}", style, token, RESET);
+        } else if c == '-' || c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || matches!(chars[i], '-' | '+' | '.' | 'e' | 'E')) {
+                i += 1;
+            }
+            let token: String = chars[start..i].iter().collect();
+            let _ = write!(out, "{}{}{}", YELLOW, token, RESET);
+        } else if c.is_ascii_alphabetic() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_alphabetic() {
+                i += 1;
+            }
+            let token: String = chars[start..i].iter().collect();
+            if matches!(token.as_str(), "true" | "false" | "null") {
+                let _ = write!(out, "{}{}{}", MAGENTA, token, RESET);
+            } else {
+                out.push_str(&token);
+            }
+        } else {
+            out.push(c);
+            i += 1;
+        }
+    }
+    out
+}
@@ -0,0 +1,64 @@
+//! SOAPリクエストの操作名の抽出を実装
+//! 
+//! SOAPのサービスは全ての操作で同じURLを共有するため、Body要素の最初の子要素の名前
+//! （無い場合はSOAPActionヘッダー、SOAP 1.2ではContent-Typeのactionパラメータ）から操作名を求める
+
+use crate::har_types::Entry;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Body要素の最初の子要素（名前空間の接頭辞を除いた名前を取り出す）
+static BODY_OPERATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)<(?:[\w.-]+:)?Body\b[^>]*>(?:\s|<!--.*?-->)*<(?:[\w.-]+:)?([\w.-]+)").expect("固定の正規表現")
+});
+
+/// SOAPのエンベロープ
+static ENVELOPE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<(?:[\w.-]+:)?Envelope\b").expect("固定の正規表現"));
+
+/// SOAPリクエストの操作名を取得
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `Option<String>` - 操作名（SOAPリクエストでない場合はNone）
+pub fn operation(entry: &Entry) -> Option<String> {
+    let body = entry
+        .request
+        .post_data
+        .as_ref()
+        .and_then(|post_data| post_data.text.as_deref())
+        .filter(|text| ENVELOPE.is_match(text));
+
+    if let Some(operation) = body.and_then(|text| BODY_OPERATION.captures(text)) {
+        return Some(operation[1].to_string());
+    }
+
+    // SOAP 1.1はSOAPActionヘッダー、SOAP 1.2はContent-Typeのactionパラメータ
+    let action = entry.request.headers.iter().find_map(|header| {
+        if header.name.eq_ignore_ascii_case("soapaction") {
+            Some(header.value.clone())
+        } else if header.name.eq_ignore_ascii_case("content-type") {
+            header.value.split(';').find_map(|parameter| {
+                let (name, value) = parameter.split_once('=')?;
+                name.trim().eq_ignore_ascii_case("action").then(|| value.to_string())
+            })
+        } else {
+            None
+        }
+    })?;
+    action_name(&action)
+}
+
+/// SOAPActionのURIから操作名を取得
+/// 
+/// # Arguments
+/// * `action` - SOAPActionの値（例: `"http://example.com/Service/GetUser"`）
+/// 
+/// # Returns
+/// * `Option<String>` - URIの最後の`/`・`#`・`:`以降の名前（空の場合はNone）
+fn action_name(action: &str) -> Option<String> {
+    let action = action.trim().trim_matches('"').trim_end_matches('/');
+    let name = action.rsplit(['/', '#', ':']).next().unwrap_or(action);
+    (!name.is_empty()).then(|| name.to_string())
+}
@@ -0,0 +1,164 @@
+//! 本番のJavaScript・CSSが参照するソースマップの検出を実装
+//! 
+//! ボディの末尾の`sourceMappingURL`のコメントと`SourceMap`・`X-SourceMap`レスポンスヘッダーから参照先を取り出し、
+//! キャプチャ内で取得できたソースマップ（圧縮前のソースコードを復元できる）や、ボディに埋め込まれたソースマップを
+//! Security Findingsシートに出力する。参照先の取得が失敗した（公開されていない）ものは対象外とする
+
+use crate::body_kind::BodyKind;
+use crate::har_types::{Entry, HarFile};
+use crate::mock_server;
+use crate::security::{self, FindingKind, SecurityFinding};
+use std::collections::HashMap;
+use url::Url;
+
+/// ソースマップを参照するレスポンスヘッダー（`X-SourceMap`は廃止された旧名）
+const SOURCE_MAP_HEADERS: &[&str] = &["sourcemap", "x-sourcemap"];
+
+/// ソースマップを参照するコメントの前置き（`//@`・`/*@`は旧形式）
+const COMMENT_PREFIXES: &[&str] = &["//#", "//@", "/*#", "/*@"];
+
+/// ソースマップのURLを指定するコメントのキー
+const COMMENT_KEY: &str = "sourceMappingURL=";
+
+/// キャプチャ全体からソースマップを公開しているJavaScript・CSSを検出
+/// 
+/// # Arguments
+/// * `har` - HARデータ
+/// 
+/// # Returns
+/// * `Vec<SecurityFinding>` - エントリ順の指摘事項（参照元のJavaScript・CSSのエントリの位置）
+pub fn scan(har: &HarFile) -> Vec<SecurityFinding> {
+    // 参照先のURL（フラグメントを除く）ごとの最後に取得したエントリの位置
+    let mut fetched: HashMap<String, usize> = HashMap::new();
+    for (index, entry) in har.log.entries.iter().enumerate() {
+        if let Ok(url) = Url::parse(&entry.request.url) {
+            fetched.insert(without_fragment(url), index);
+        }
+    }
+
+    let mut findings = Vec::new();
+    for (index, entry) in har.log.entries.iter().enumerate() {
+        let Ok(url) = Url::parse(&entry.request.url) else {
+            continue;
+        };
+        if !(200..300).contains(&entry.response.status)
+            || !matches!(url.scheme(), "http" | "https")
+            || security::is_loopback(&url)
+            || !matches!(BodyKind::of_response(entry), BodyKind::JavaScript | BodyKind::Css)
+        {
+            continue;
+        }
+        let Some(reference) = source_map_reference(entry) else {
+            continue;
+        };
+        let finding = |detail: String| SecurityFinding {
+            kind: FindingKind::SourceMap,
+            entry_number: index + 1,
+            timestamp: entry.started_date_time.clone(),
+            method: entry.request.method.clone(),
+            url: entry.request.url.clone(),
+            detail,
+        };
+
+        if reference.get(..5).is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:")) {
+            findings.push(finding(format!(
+                "ソースマップをボディに埋め込んでいます（data: URI、{} bytes）",
+                reference.len()
+            )));
+            continue;
+        }
+        let Ok(map_url) = url.join(&reference) else {
+            continue;
+        };
+        let map_url = without_fragment(map_url);
+        match fetched.get(&map_url).map(|map_index| (*map_index, &har.log.entries[*map_index])) {
+            Some((map_index, map_entry)) if (200..300).contains(&map_entry.response.status) => {
+                let sources = if includes_sources(map_entry) { "、元のソースコード（sourcesContent）を含みます" } else { "" };
+                findings.push(finding(format!(
+                    "ソースマップ{}を公開しています（エントリ番号{}、ステータス{}{}）",
+                    map_url,
+                    map_index + 1,
+                    map_entry.response.status,
+                    sources
+                )));
+            }
+            // 取得に失敗したソースマップは公開されていない
+            Some(_) => {}
+            None => findings.push(finding(format!(
+                "ソースマップ{}を参照しています（キャプチャに取得が記録されていないため公開の有無は未確認）",
+                map_url
+            ))),
+        }
+    }
+    findings
+}
+
+/// レスポンスが参照するソースマップのURLを取得
+/// 
+/// `SourceMap`・`X-SourceMap`ヘッダーを優先し、無い場合はボディの最後の`sourceMappingURL`のコメントを使用する
+/// 
+/// # Arguments
+/// * `entry` - HARエントリ
+/// 
+/// # Returns
+/// * `Option<String>` - 参照先のURL（相対URLの場合はそのまま、参照が無い場合はNone）
+fn source_map_reference(entry: &Entry) -> Option<String> {
+    if let Some(header) = entry
+        .response
+        .headers
+        .iter()
+        .find(|header| SOURCE_MAP_HEADERS.iter().any(|name| header.name.eq_ignore_ascii_case(name)))
+        && !header.value.trim().is_empty()
+    {
+        return Some(header.value.trim().to_string());
+    }
+    let body = mock_server::response_body(entry);
+    comment_reference(&String::from_utf8_lossy(&body))
+}
+
+/// ボディの最後の`sourceMappingURL`のコメントから参照先を取得
+/// 
+/// # Arguments
+/// * `body` - JavaScript・CSSのボディ
+/// 
+/// # Returns
+/// * `Option<String>` - 参照先のURL（コメントが無い場合はNone）
+fn comment_reference(body: &str) -> Option<String> {
+    let mut rest = body;
+    while let Some(position) = rest.rfind(COMMENT_KEY) {
+        let before = rest[..position].trim_end_matches([' ', '\t']);
+        let value = &rest[position + COMMENT_KEY.len()..];
+        // バンドラーの実行時コードの文字列（`"//# sourceMappingURL="+url`）等のコメントでない記述は読み飛ばす
+        if COMMENT_PREFIXES.iter().any(|prefix| before.ends_with(prefix)) && !value.starts_with(['"', '\'']) {
+            let end = value.find(|c: char| c.is_whitespace() || c == '*').unwrap_or(value.len());
+            let reference = &value[..end];
+            return (!reference.is_empty()).then(|| reference.to_string());
+        }
+        rest = &rest[..position];
+    }
+    None
+}
+
+/// ソースマップが元のソースコードを含むかどうか
+/// 
+/// # Arguments
+/// * `entry` - ソースマップのHARエントリ
+/// 
+/// # Returns
+/// * `bool` - ボディに`sourcesContent`が記録されている場合はtrue
+fn includes_sources(entry: &Entry) -> bool {
+    let body = mock_server::response_body(entry);
+    String::from_utf8_lossy(&body).contains("\"sourcesContent\"")
+}
+
+/// URLからフラグメントを除いた文字列
+/// 
+/// # Arguments
+/// * `url` - URL
+/// 
+/// # Returns
+/// * `String` - フラグメントを除いたURL
+fn without_fragment(mut url: Url) -> String {
+    url.set_fragment(None);
+    url.to_string()
+}
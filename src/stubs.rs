@@ -0,0 +1,337 @@
+//! 解析対象のエントリからテスト用のスタブ定義を出力
+//! 
+//! 記録したレスポンスを既存のモック基盤（WireMock等）で再生するための定義を作成する
+
+use crate::error::{AnalyzerError, Result};
+use crate::har_types::Entry;
+use crate::mock_server::{replay_headers, response_body};
+use crate::payload::is_json_content;
+use base64::Engine;
+use log::info;
+use serde_json::{Map, Value, json};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use url::Url;
+
+/// ファイル名に含めるパスの最大文字数
+const MAX_FILE_NAME_PATH_LEN: usize = 60;
+
+/// Playwrightのルート定義のモジュールのテンプレート（`{{ROUTES}}`をルート定義の配列に置き換える）
+const PLAYWRIGHT_ROUTES_TEMPLATE: &str = r#"// rs_har_analyzerがHARファイルから生成したルート定義
+//
+// 使い方:
+//   import { installRoutes } from './routes.js';
+//   await installRoutes(page);
+
+export const routes = {{ROUTES}};
+
+/**
+ * 記録されたレスポンスを返すルートをページ（またはブラウザコンテキスト）に登録する。
+ * 一致する記録が無いリクエストは通常通り処理する。
+ */
+export async function installRoutes(page) {
+  await page.route('**/*', async (route) => {
+    const request = route.request();
+    const recorded = routes.find((r) =>
+      r.method === request.method() &&
+      r.url === request.url() &&
+      (r.postData === undefined || r.postData === request.postData()));
+    if (!recorded) {
+      await route.fallback();
+      return;
+    }
+    await route.fulfill({
+      status: recorded.status,
+      headers: recorded.headers,
+      body: recorded.bodyBase64 !== undefined ? Buffer.from(recorded.bodyBase64, 'base64') : (recorded.body ?? ''),
+    });
+  });
+}
+"#;
+
+/// スタブ定義の形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StubFormat {
+    /// WireMockのスタブマッピング（1リクエスト1ファイルのJSON）
+    WireMock,
+    /// Playwrightの`page.route()`に渡すルート定義のモジュール（JavaScript）
+    PlaywrightRoutes,
+}
+
+impl StubFormat {
+    /// `--format`で指定する名前から形式を取得
+    /// 
+    /// # Arguments
+    /// * `name` - 形式の名前
+    /// 
+    /// # Returns
+    /// * `Option<StubFormat>` - 対応する形式（スタブ定義の形式でない場合はNone）
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "wiremock" => Some(StubFormat::WireMock),
+            "playwright-routes" => Some(StubFormat::PlaywrightRoutes),
+            _ => None,
+        }
+    }
+
+    /// 出力先が指定されていない場合の出力先
+    /// 
+    /// # Returns
+    /// * `&'static str` - 出力先のパス
+    pub fn default_output(&self) -> &'static str {
+        match self {
+            StubFormat::WireMock => "mappings",
+            StubFormat::PlaywrightRoutes => "routes.js",
+        }
+    }
+}
+
+/// スタブ定義を作成するリクエスト
+struct StubRequest<'a> {
+    /// HARエントリ
+    entry: &'a Entry,
+    /// フラグメントを除いたURL
+    url: String,
+    /// パスとクエリ文字列
+    path_and_query: String,
+    /// リクエストボディ（空の場合はNone）
+    body: Option<&'a str>,
+}
+
+impl<'a> StubRequest<'a> {
+    /// エントリからスタブ定義を作成するリクエストを作成
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `Option<StubRequest>` - URLを解析できない場合や通信に失敗した場合はNone
+    fn new(entry: &'a Entry) -> Option<Self> {
+        if entry.response.status <= 0 {
+            return None;
+        }
+        let mut url = Url::parse(&entry.request.url).ok()?;
+        url.set_fragment(None);
+        let path_and_query = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        let body = entry
+            .request
+            .post_data
+            .as_ref()
+            .and_then(|post_data| post_data.text.as_deref())
+            .filter(|text| !text.is_empty());
+        Some(StubRequest { entry, url: url.to_string(), path_and_query, body })
+    }
+}
+
+/// 解析対象のエントリからスタブ定義を出力
+/// 
+/// メソッド・URL・リクエストボディが同じリクエストは最初に記録されたものだけを出力する
+/// （WireMockはホスト名を照合しないため、ホスト名が異なるだけのリクエストも同じとみなす）
+/// 
+/// # Arguments
+/// * `entries` - 解析対象のエントリ
+/// * `format` - スタブ定義の形式
+/// * `output_path` - 出力先のパス
+/// 
+/// # Returns
+/// * `Result<usize>` - 出力したスタブ定義の数
+pub fn export<'a>(entries: impl IntoIterator<Item = &'a Entry>, format: StubFormat, output_path: &str) -> Result<usize> {
+    let mut seen = HashSet::new();
+    let requests: Vec<StubRequest> = entries
+        .into_iter()
+        .filter_map(StubRequest::new)
+        .filter(|request| {
+            let url = match format {
+                StubFormat::WireMock => &request.path_and_query,
+                StubFormat::PlaywrightRoutes => &request.url,
+            };
+            seen.insert((request.entry.request.method.to_uppercase(), url.clone(), request.body.map(str::to_string)))
+        })
+        .collect();
+
+    match format {
+        StubFormat::WireMock => export_wiremock(&requests, output_path)?,
+        StubFormat::PlaywrightRoutes => export_playwright_routes(&requests, output_path)?,
+    }
+
+    info!("スタブ定義の出力が完了しました: {} ({}件)", output_path, requests.len());
+    Ok(requests.len())
+}
+
+/// WireMockのスタブマッピングをディレクトリに出力
+/// 
+/// # Arguments
+/// * `requests` - スタブ定義を作成するリクエスト
+/// * `output_dir` - 出力先のディレクトリ（WireMockのmappingsディレクトリ）
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+fn export_wiremock(requests: &[StubRequest], output_dir: &str) -> Result<()> {
+    fs::create_dir_all(output_dir)
+        .map_err(AnalyzerError::io("出力ディレクトリの作成に失敗しました"))?;
+
+    for (index, request) in requests.iter().enumerate() {
+        let mapping = wiremock_mapping(request);
+        let json = serde_json::to_string_pretty(&mapping)
+            .map_err(AnalyzerError::export("スタブ定義の作成に失敗しました"))?;
+        let file_name = format!(
+            "{:04}-{}-{}.json",
+            index + 1,
+            request.entry.request.method.to_lowercase(),
+            file_name_path(&request.path_and_query)
+        );
+        fs::write(Path::new(output_dir).join(&file_name), json)
+            .map_err(AnalyzerError::io(format!("スタブ定義の書き込みに失敗しました: {}", file_name)))?;
+    }
+    Ok(())
+}
+
+/// WireMockのスタブマッピングを作成
+/// 
+/// # Arguments
+/// * `request` - スタブ定義を作成するリクエスト
+/// 
+/// # Returns
+/// * `Value` - スタブマッピングのJSON
+fn wiremock_mapping(request: &StubRequest) -> Value {
+    let entry = request.entry;
+
+    let mut request_pattern = json!({
+        "method": entry.request.method.to_uppercase(),
+        "url": request.path_and_query,
+    });
+    if let Some(body) = request.body {
+        let json_body = entry
+            .request
+            .post_data
+            .as_ref()
+            .is_some_and(|post_data| is_json_content(&post_data.mime_type))
+            && serde_json::from_str::<Value>(body).is_ok();
+        request_pattern["bodyPatterns"] = if json_body {
+            json!([{ "equalToJson": body }])
+        } else {
+            json!([{ "equalTo": body }])
+        };
+    }
+
+    // 同じ名前のヘッダーが複数ある場合は配列にまとめる
+    let mut headers = Map::new();
+    for (name, value) in replay_headers(entry) {
+        match headers.get_mut(&name) {
+            Some(Value::Array(values)) => values.push(Value::String(value)),
+            Some(existing) => *existing = json!([existing.take(), value]),
+            None => {
+                headers.insert(name, Value::String(value));
+            }
+        }
+    }
+
+    let mut response = json!({
+        "status": entry.response.status,
+        "headers": headers,
+    });
+    if !entry.response.status_text.is_empty() {
+        response["statusMessage"] = json!(entry.response.status_text);
+    }
+    insert_body(&mut response, entry, "body", "base64Body");
+
+    json!({
+        "name": format!("{} {}", entry.request.method.to_uppercase(), request.path_and_query),
+        "request": request_pattern,
+        "response": response,
+    })
+}
+
+/// Playwrightのルート定義のモジュールを出力
+/// 
+/// # Arguments
+/// * `requests` - スタブ定義を作成するリクエスト
+/// * `output_path` - 出力先のファイルのパス
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+fn export_playwright_routes(requests: &[StubRequest], output_path: &str) -> Result<()> {
+    let routes: Vec<Value> = requests
+        .iter()
+        .map(|request| {
+            let entry = request.entry;
+            let mut headers = Map::new();
+            for (name, value) in replay_headers(entry) {
+                // Playwrightは同じ名前のヘッダーを改行で区切った1つの値として扱う
+                match headers.get_mut(&name) {
+                    Some(Value::String(existing)) => {
+                        existing.push('\n');
+                        existing.push_str(&value);
+                    }
+                    _ => {
+                        headers.insert(name, Value::String(value));
+                    }
+                }
+            }
+
+            let mut route = json!({
+                "method": entry.request.method.to_uppercase(),
+                "url": request.url,
+                "status": entry.response.status,
+                "headers": headers,
+            });
+            if let Some(body) = request.body {
+                route["postData"] = json!(body);
+            }
+            insert_body(&mut route, entry, "body", "bodyBase64");
+            route
+        })
+        .collect();
+
+    let json = serde_json::to_string_pretty(&routes)
+        .map_err(AnalyzerError::export("スタブ定義の作成に失敗しました"))?;
+    let module = PLAYWRIGHT_ROUTES_TEMPLATE.replace("{{ROUTES}}", &json);
+
+    if let Some(parent) = Path::new(output_path).parent() {
+        fs::create_dir_all(parent)
+            .map_err(AnalyzerError::io("出力ディレクトリの作成に失敗しました"))?;
+    }
+    fs::write(output_path, module)
+        .map_err(AnalyzerError::io(format!("スタブ定義の書き込みに失敗しました: {}", output_path)))?;
+    Ok(())
+}
+
+/// レスポンスボディをスタブ定義に追加
+/// 
+/// # Arguments
+/// * `target` - 追加先のJSONオブジェクト
+/// * `entry` - HARエントリ
+/// * `text_key` - テキストのボディのキー
+/// * `base64_key` - テキストでないボディ（Base64）のキー
+fn insert_body(target: &mut Value, entry: &Entry, text_key: &str, base64_key: &str) {
+    let body = response_body(entry);
+    if body.is_empty() {
+        return;
+    }
+    match String::from_utf8(body) {
+        Ok(text) => target[text_key] = json!(text),
+        Err(e) => target[base64_key] = json!(base64::engine::general_purpose::STANDARD.encode(e.into_bytes())),
+    }
+}
+
+/// ファイル名に使用できる形にパスを変換
+/// 
+/// # Arguments
+/// * `path_and_query` - パスとクエリ文字列
+/// 
+/// # Returns
+/// * `String` - 英数字・ハイフン・アンダースコア以外を`_`に置き換えたパス
+fn file_name_path(path_and_query: &str) -> String {
+    let path = path_and_query.split('?').next().unwrap_or_default().trim_matches('/');
+    if path.is_empty() {
+        return "root".to_string();
+    }
+    path.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .take(MAX_FILE_NAME_PATH_LEN)
+        .collect()
+}
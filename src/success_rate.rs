@@ -0,0 +1,140 @@
+//! 再試行を考慮したエンドポイントごとの成功率の集計を実装
+//! 
+//! 失敗したリクエストの後、一定時間内に同じエンドポイントへ送信されたリクエストは再試行とみなし、
+//! 一連のリクエストを1回の操作として最後のリクエストの結果で成否を判定する
+
+use crate::har_types::AnalysisResult;
+use crate::outliers::endpoint_key;
+use chrono::DateTime;
+use std::collections::BTreeMap;
+
+/// エンドポイントへの1件のリクエスト
+#[derive(Debug, Clone, Copy)]
+struct Attempt {
+    /// 開始時刻（UNIXエポックからのミリ秒）
+    start: f64,
+    /// 終了時刻（UNIXエポックからのミリ秒）
+    end: f64,
+    /// 成功したかどうか
+    succeeded: bool,
+}
+
+/// エンドポイントの成功率
+#[derive(Debug, Clone)]
+pub struct EndpointSuccess {
+    /// エンドポイント（例: "GET https://example.com/api/users"）
+    pub endpoint: String,
+    /// リクエスト数
+    pub requests: usize,
+    /// 操作の数（再試行をまとめた数）
+    pub operations: usize,
+    /// 成功した操作の数
+    pub succeeded: usize,
+    /// 再試行を含む操作の数
+    pub retried: usize,
+}
+
+impl EndpointSuccess {
+    /// 操作の成功率
+    /// 
+    /// # Returns
+    /// * `f64` - 成功率（0〜1、操作が無い場合は1）
+    pub fn rate(&self) -> f64 {
+        if self.operations == 0 {
+            1.0
+        } else {
+            self.succeeded as f64 / self.operations as f64
+        }
+    }
+}
+
+/// エンドポイントごとのリクエストを集め、再試行を考慮した成功率を求める
+#[derive(Debug, Clone)]
+pub struct SuccessTracker {
+    /// 再試行とみなす間隔（ミリ秒）
+    retry_window_ms: f64,
+    /// エンドポイントごとのリクエスト
+    attempts: BTreeMap<String, Vec<Attempt>>,
+}
+
+impl SuccessTracker {
+    /// 空の集計を作成
+    /// 
+    /// # Arguments
+    /// * `retry_window_ms` - 失敗したリクエストの終了から次のリクエストの開始までを再試行とみなす間隔（ミリ秒、0の場合は再試行をまとめない）
+    /// 
+    /// # Returns
+    /// * `SuccessTracker` - 空の集計
+    pub fn new(retry_window_ms: u64) -> Self {
+        SuccessTracker {
+            retry_window_ms: retry_window_ms as f64,
+            attempts: BTreeMap::new(),
+        }
+    }
+
+    /// 解析結果を1件集計に加える
+    /// 
+    /// ステータスコードが0（通信の失敗・中断）または400以上の場合を失敗とする
+    /// 
+    /// # Arguments
+    /// * `result` - 解析結果
+    pub fn add(&mut self, result: &AnalysisResult) {
+        let start = DateTime::parse_from_rfc3339(&result.timestamp_iso)
+            .map(|started| started.timestamp_millis() as f64)
+            .unwrap_or_default();
+        self.attempts.entry(endpoint_key(result)).or_default().push(Attempt {
+            start,
+            end: start + result.duration.max(0.0),
+            succeeded: (1..400).contains(&result.status_code),
+        });
+    }
+
+    /// エンドポイントごとの成功率を求める
+    /// 
+    /// # Returns
+    /// * `Vec<EndpointSuccess>` - エンドポイントごとの成功率（成功率の低い順）
+    pub fn finish(self) -> Vec<EndpointSuccess> {
+        let mut endpoints: Vec<EndpointSuccess> = self
+            .attempts
+            .into_iter()
+            .map(|(endpoint, mut attempts)| {
+                attempts.sort_by(|a, b| a.start.total_cmp(&b.start));
+                let mut success = EndpointSuccess {
+                    endpoint,
+                    requests: attempts.len(),
+                    operations: 0,
+                    succeeded: 0,
+                    retried: 0,
+                };
+                let mut previous: Option<Attempt> = None;
+                let mut retrying = false;
+                for attempt in attempts {
+                    let is_retry = self.retry_window_ms > 0.0
+                        && previous.is_some_and(|previous| {
+                            !previous.succeeded && attempt.start - previous.end <= self.retry_window_ms
+                        });
+                    if is_retry {
+                        // 直前の失敗と同じ操作の再試行として、操作の成否を最後のリクエストの結果で置き換える
+                        if !retrying {
+                            success.retried += 1;
+                            retrying = true;
+                        }
+                        if attempt.succeeded {
+                            success.succeeded += 1;
+                        }
+                    } else {
+                        success.operations += 1;
+                        retrying = false;
+                        if attempt.succeeded {
+                            success.succeeded += 1;
+                        }
+                    }
+                    previous = Some(attempt);
+                }
+                success
+            })
+            .collect();
+        endpoints.sort_by(|a, b| a.rate().total_cmp(&b.rate()).then_with(|| a.endpoint.cmp(&b.endpoint)));
+        endpoints
+    }
+}
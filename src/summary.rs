@@ -0,0 +1,286 @@
+//! 解析結果のサマリー（集計）を実装
+
+use crate::business_hours::BusinessHoursCounts;
+use crate::egress_cost::{EgressCostReport, REPETITIONS};
+use crate::events::TrafficEvent;
+use crate::gaps::IDLE_GAP_THRESHOLD_MS;
+use crate::har_types::AnalysisResult;
+use crate::outliers::{EndpointOutliers, MAD_THRESHOLD};
+use crate::success_rate::EndpointSuccess;
+use log::info;
+use std::collections::BTreeMap;
+
+/// サマリーのログに出力する大きな間隔の件数
+const MAX_LOGGED_IDLE_GAPS: usize = 10;
+
+/// サマリーのログに出力する成功率の低いエンドポイントの件数
+const MAX_LOGGED_ENDPOINTS: usize = 20;
+
+/// サマリーのログに出力する転送量の費用の大きいホスト・コンテンツタイプの件数
+const MAX_LOGGED_COSTS: usize = 10;
+
+/// 前の通信から大きな間隔が空いたリクエスト
+#[derive(Debug, Clone)]
+pub struct IdleGap {
+    /// リクエストの時刻
+    pub timestamp: String,
+    /// リクエストのメソッドとURL
+    pub request: String,
+    /// 前の通信からの間隔（ミリ秒）
+    pub gap: f64,
+}
+
+/// 解析結果のサマリー
+#[derive(Debug, Default, Clone)]
+pub struct Summary {
+    /// 総エントリ数
+    pub total: usize,
+    /// メソッド別の件数
+    pub method_counts: BTreeMap<String, usize>,
+    /// ステータスコード別の件数
+    pub status_counts: BTreeMap<i32, usize>,
+    /// 所要時間の外れ値を含むエンドポイント
+    pub outliers: Vec<EndpointOutliers>,
+    /// 前の通信から大きな間隔が空いたリクエスト
+    pub idle_gaps: Vec<IdleGap>,
+    /// 再試行を考慮したエンドポイントごとの成功率（成功率の低い順）
+    pub endpoint_success: Vec<EndpointSuccess>,
+    /// 再試行とみなす間隔（ミリ秒）
+    pub retry_window_ms: u64,
+    /// 適用したサンプリングの説明（サンプリングしていない場合はNone）
+    pub sampling: Option<String>,
+    /// 転送量の費用の見積もり（単価を設定していない場合はNone）
+    pub egress_cost: Option<EgressCostReport>,
+    /// レンダリングをブロックするリソースの件数
+    pub render_blocking: usize,
+    /// サイズの予算を超過した件数
+    pub budget_violations: usize,
+    /// 営業時間の内外ごとの件数（営業時間を指定していない場合は0件）
+    pub business_hours: BusinessHoursCounts,
+}
+
+impl Summary {
+    /// 解析結果のリストからサマリーを作成
+    ///
+    /// # Arguments
+    /// * `results` - 解析結果のリスト
+    ///
+    /// # Returns
+    /// * `Summary` - サマリー
+    pub fn from_results(results: &[AnalysisResult]) -> Self {
+        let mut summary = Summary::default();
+        for result in results {
+            summary.add(result);
+        }
+        summary
+    }
+
+    /// 通信イベントのリストからサマリーを作成（HTTPの通信のみを集計）
+    /// 
+    /// # Arguments
+    /// * `events` - 通信イベントのリスト
+    /// 
+    /// # Returns
+    /// * `Summary` - サマリー
+    pub fn from_events(events: &[TrafficEvent]) -> Self {
+        let mut summary = Summary::default();
+        for result in events.iter().filter_map(TrafficEvent::as_http) {
+            summary.add(result);
+        }
+        summary
+    }
+
+    /// 解析結果を1件集計に加える
+    ///
+    /// # Arguments
+    /// * `result` - 解析結果
+    pub fn add(&mut self, result: &AnalysisResult) {
+        self.total += 1;
+        *self.method_counts.entry(result.method.clone()).or_insert(0) += 1;
+        *self.status_counts.entry(result.status_code).or_insert(0) += 1;
+        if result.render_blocking {
+            self.render_blocking += 1;
+        }
+        if let Some(inside) = result.business_hours {
+            self.business_hours.add(inside, result.status_code);
+        }
+
+        if let Some(gap) = result.gap.filter(|gap| *gap >= IDLE_GAP_THRESHOLD_MS) {
+            self.idle_gaps.push(IdleGap {
+                timestamp: result.timestamp.clone(),
+                request: format!("{} {}", result.method, result.decoded_url.as_str()),
+                gap,
+            });
+        }
+    }
+
+    /// 別のサマリーの件数を合算する
+    ///
+    /// # Arguments
+    /// * `other` - 合算するサマリー
+    pub fn merge(&mut self, other: &Summary) {
+        self.total += other.total;
+        self.render_blocking += other.render_blocking;
+        self.budget_violations += other.budget_violations;
+        self.business_hours.merge(&other.business_hours);
+        for (method, count) in &other.method_counts {
+            *self.method_counts.entry(method.clone()).or_insert(0) += count;
+        }
+        for (status, count) in &other.status_counts {
+            *self.status_counts.entry(*status).or_insert(0) += count;
+        }
+        self.outliers.extend(other.outliers.iter().cloned());
+        self.idle_gaps.extend(other.idle_gaps.iter().cloned());
+        // 同じエンドポイントは件数を合算する
+        for success in &other.endpoint_success {
+            match self.endpoint_success.iter_mut().find(|own| own.endpoint == success.endpoint) {
+                Some(own) => {
+                    own.requests += success.requests;
+                    own.operations += success.operations;
+                    own.succeeded += success.succeeded;
+                    own.retried += success.retried;
+                }
+                None => self.endpoint_success.push(success.clone()),
+            }
+        }
+        self.endpoint_success
+            .sort_by(|a, b| a.rate().total_cmp(&b.rate()).then_with(|| a.endpoint.cmp(&b.endpoint)));
+        self.retry_window_ms = self.retry_window_ms.max(other.retry_window_ms);
+        if self.sampling.is_none() {
+            self.sampling = other.sampling.clone();
+        }
+        if let Some(other_cost) = &other.egress_cost {
+            self.egress_cost.get_or_insert_with(EgressCostReport::default).merge(other_cost);
+        }
+    }
+
+    /// 指定したメソッドの件数を取得
+    ///
+    /// # Arguments
+    /// * `method` - HTTPメソッド
+    ///
+    /// # Returns
+    /// * `usize` - 件数
+    pub fn method_count(&self, method: &str) -> usize {
+        self.method_counts.get(method).copied().unwrap_or(0)
+    }
+
+    /// サマリーをログに出力
+    pub fn log(&self) {
+        info!("解析結果のサマリー:");
+        info!("  - 総エントリ数: {}", self.total);
+        if let Some(sampling) = &self.sampling {
+            info!("  - サンプリング: {}（以下の集計は抽出したエントリのみ）", sampling);
+        }
+        info!("  - GETリクエスト: {}", self.method_count("GET"));
+        info!("  - POSTリクエスト: {}", self.method_count("POST"));
+
+        info!("  - ステータスコード別集計:");
+        for (status, count) in &self.status_counts {
+            info!("    {}: {}", status, count);
+        }
+        if self.render_blocking > 0 {
+            info!(
+                "  - レンダリングをブロックするリソース（DOMContentLoadedより前に同期的に読み込んだCSS・JavaScript）: {}",
+                self.render_blocking
+            );
+        }
+        if !self.business_hours.is_empty() {
+            let hours = &self.business_hours;
+            info!(
+                "  - 営業時間内: {}件（エラー {}件）、営業時間外: {}件（エラー {}件）",
+                hours.inside, hours.inside_errors, hours.outside, hours.outside_errors
+            );
+        }
+
+        if !self.outliers.is_empty() {
+            info!("  - 外れ値（所要時間が中央値+{}×MADを超える通信）:", MAD_THRESHOLD);
+            for endpoint in &self.outliers {
+                info!(
+                    "    {}: {}/{}件 (中央値 {:.1}ms, MAD {:.1}ms, 最大 {:.1}ms)",
+                    endpoint.endpoint,
+                    endpoint.outliers,
+                    endpoint.samples,
+                    endpoint.median,
+                    endpoint.mad,
+                    endpoint.max
+                );
+            }
+        }
+
+        if !self.endpoint_success.is_empty() {
+            let operations: usize = self.endpoint_success.iter().map(|success| success.operations).sum();
+            let succeeded: usize = self.endpoint_success.iter().map(|success| success.succeeded).sum();
+            let retry = if self.retry_window_ms == 0 {
+                "再試行をまとめない".to_string()
+            } else {
+                format!(
+                    "失敗から{}秒以内の同じエンドポイントへのリクエストは再試行として1回の操作に集計",
+                    self.retry_window_ms as f64 / 1000.0
+                )
+            };
+            info!(
+                "  - エンドポイントの成功率（{}）: {}/{}操作 ({:.1}%)",
+                retry,
+                succeeded,
+                operations,
+                succeeded as f64 / operations.max(1) as f64 * 100.0
+            );
+            let failing: Vec<&EndpointSuccess> = self
+                .endpoint_success
+                .iter()
+                .filter(|success| success.succeeded < success.operations || success.retried > 0)
+                .collect();
+            for success in failing.iter().take(MAX_LOGGED_ENDPOINTS) {
+                info!(
+                    "    {}: {:.1}% ({}/{}操作, リクエスト{}件, 再試行あり{}操作)",
+                    success.endpoint,
+                    success.rate() * 100.0,
+                    success.succeeded,
+                    success.operations,
+                    success.requests,
+                    success.retried
+                );
+            }
+            if failing.len() > MAX_LOGGED_ENDPOINTS {
+                info!("    ほか{}件のエンドポイント", failing.len() - MAX_LOGGED_ENDPOINTS);
+            }
+        }
+
+        if let Some(egress_cost) = &self.egress_cost {
+            info!(
+                "  - 転送量の費用の見積もり: {:.6} {}（レスポンス {} bytes、100万回あたり {:.2} {}）",
+                egress_cost.total_cost(),
+                egress_cost.currency,
+                egress_cost.total_bytes(),
+                egress_cost.total_cost() * REPETITIONS as f64,
+                egress_cost.currency
+            );
+            for (label, groups) in [("ホスト別", egress_cost.by_host()), ("コンテンツタイプ別", egress_cost.by_content_type())] {
+                info!("    {}:", label);
+                for (name, bytes, cost) in groups.iter().take(MAX_LOGGED_COSTS) {
+                    let name = if name.is_empty() { "(なし)" } else { name.as_str() };
+                    info!("      {}: {:.6} {} ({} bytes)", name, cost, egress_cost.currency, bytes);
+                }
+                if groups.len() > MAX_LOGGED_COSTS {
+                    info!("      ほか{}件", groups.len() - MAX_LOGGED_COSTS);
+                }
+            }
+        }
+
+        if !self.idle_gaps.is_empty() {
+            let total: f64 = self.idle_gaps.iter().map(|idle| idle.gap).sum();
+            info!(
+                "  - 大きな間隔（前の通信から{}秒以上、操作待ちの可能性）: {}件、合計 {:.1}秒",
+                IDLE_GAP_THRESHOLD_MS / 1000.0,
+                self.idle_gaps.len(),
+                total / 1000.0
+            );
+            let mut largest: Vec<&IdleGap> = self.idle_gaps.iter().collect();
+            largest.sort_by(|a, b| b.gap.total_cmp(&a.gap));
+            for idle in largest.into_iter().take(MAX_LOGGED_IDLE_GAPS) {
+                info!("    {} {:.1}秒: {}", idle.timestamp, idle.gap / 1000.0, idle.request);
+            }
+        }
+    }
+}
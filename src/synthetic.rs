@@ -0,0 +1,35 @@
+//! 性能の計測に使用する合成HARの作成を実装
+//! 
+//! `bench`サブコマンドとベンチマーク（`cargo bench --bench parse`）で同じ内容のHARを使用し、
+//! バージョン間で処理時間を比較できるようにする
+
+use std::fmt::Write as _;
+
+/// 合成HARの内容を作成
+/// 
+/// 全てのエントリは同じ内容のJSONのレスポンスを返すGETリクエストとする
+/// 
+/// # Arguments
+/// * `entries` - エントリ数
+/// 
+/// # Returns
+/// * `String` - HARファイルのJSON文字列
+pub fn synthetic_har(entries: usize) -> String {
+    let body = "{\\\"id\\\":1,\\\"name\\\":\\\"item\\\",\\\"tags\\\":[\\\"a\\\",\\\"b\\\"]}".repeat(40);
+    let mut har = String::from(
+        r#"{"log":{"version":"1.2","creator":{"name":"bench","version":"1.0"},"entries":["#,
+    );
+
+    for index in 0..entries {
+        if index > 0 {
+            har.push(',');
+        }
+        let _ = write!(
+            har,
+            r#"{{"startedDateTime":"2024-01-01T00:00:00.000Z","time":12.5,"request":{{"method":"GET","url":"https://example.com/api/items/{index}?page={index}","httpVersion":"HTTP/2","headers":[{{"name":"Accept","value":"application/json"}}],"queryString":[{{"name":"page","value":"{index}"}}],"cookies":[],"headersSize":-1,"bodySize":0}},"response":{{"status":200,"statusText":"OK","httpVersion":"HTTP/2","headers":[{{"name":"Content-Type","value":"application/json"}}],"cookies":[],"content":{{"size":1000,"mimeType":"application/json","text":"[{body}]"}},"redirectURL":"","headersSize":-1,"bodySize":1000}},"cache":{{}},"timings":{{"send":1,"wait":10,"receive":1.5}}}}"#
+        );
+    }
+
+    har.push_str("]}}");
+    har
+}
@@ -0,0 +1,193 @@
+//! 利用者が用意したTeraテンプレートによるテキスト形式の出力を実装
+//! 
+//! 解析結果とサマリーをテンプレートのコンテキストとして渡し、独自のHTML・AsciiDoc・チャットへの投稿文等の
+//! 任意のテキストを出力する。新しい形式ごとに出力処理を実装せずに済むようにする。
+//! テンプレートのファイル名が`.html`・`.htm`・`.xml`で終わる場合は値をエスケープする
+
+use crate::columns::{CellValue, Column};
+use crate::error::{AnalyzerError, Result};
+use crate::excel_exporter::ExportOptions;
+use crate::har_types::AnalysisResult;
+use crate::privacy::PrivacyPolicy;
+use crate::summary::Summary;
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::Path;
+use tera::{Context, Tera};
+
+/// テンプレートで解析結果を出力
+/// 
+/// # Arguments
+/// * `results` - 解析結果のリスト
+/// * `summary` - 解析結果のサマリー
+/// * `options` - 出力オプション（テンプレートのパス・出力する列・実行情報）
+/// * `output_path` - 出力先のパス
+/// 
+/// # Returns
+/// * `Result<()>` - 成功時はOk、失敗時はエラー
+pub fn export(results: &[&AnalysisResult], summary: &Summary, options: &ExportOptions, output_path: &str) -> Result<()> {
+    let Some(template_path) = &options.template else {
+        return Err(AnalyzerError::InvalidArgument("--templateでテンプレートを指定してください".to_string()));
+    };
+    let mut tera = Tera::default();
+    // テンプレートの誤りは出力先の問題ではないため、引数の誤りとして扱う
+    tera.add_template_file(template_path, Some(&template_name(template_path)))
+        .map_err(|e| {
+            AnalyzerError::InvalidArgument(format!("テンプレートの読み込みに失敗しました: {}: {}", template_path, error_chain(&e)))
+        })?;
+
+    let context = build_context(results, summary, options);
+    let output = tera.render(&template_name(template_path), &context).map_err(|e| {
+        AnalyzerError::InvalidArgument(format!("テンプレートの展開に失敗しました: {}: {}", template_path, error_chain(&e)))
+    })?;
+
+    if let Some(parent) = Path::new(output_path).parent() {
+        fs::create_dir_all(parent).map_err(AnalyzerError::io("出力ディレクトリの作成に失敗しました"))?;
+    }
+    fs::write(output_path, output).map_err(AnalyzerError::io("テンプレートの出力の書き込みに失敗しました"))?;
+    Ok(())
+}
+
+/// テンプレートの名前（エスケープの要否の判定に使用するファイル名）
+/// 
+/// # Arguments
+/// * `template_path` - テンプレートのパス
+/// 
+/// # Returns
+/// * `String` - テンプレートのファイル名
+fn template_name(template_path: &str) -> String {
+    Path::new(template_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| template_path.to_string())
+}
+
+/// Teraのエラーと原因を1つのメッセージにまとめる（展開に失敗した変数・フィルタは原因に含まれる）
+/// 
+/// # Arguments
+/// * `error` - Teraのエラー
+/// 
+/// # Returns
+/// * `String` - エラーメッセージ
+fn error_chain(error: &tera::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        message.push_str(&format!(": {}", cause));
+        source = cause.source();
+    }
+    message
+}
+
+/// テンプレートに渡すコンテキストを作成
+/// 
+/// # Arguments
+/// * `results` - 解析結果のリスト
+/// * `summary` - 解析結果のサマリー
+/// * `options` - 出力オプション
+/// 
+/// # Returns
+/// * `Context` - テンプレートのコンテキスト
+fn build_context(results: &[&AnalysisResult], summary: &Summary, options: &ExportOptions) -> Context {
+    let columns: Vec<Value> = options
+        .columns
+        .iter()
+        .map(|column| json!({ "key": variable_name(column), "header": column.header() }))
+        .collect();
+    let entries: Vec<Value> = results
+        .iter()
+        .map(|result| {
+            let mut entry = Map::new();
+            for column in &options.columns {
+                entry.insert(variable_name(column), cell_value(column, result, &options.privacy));
+            }
+            Value::Object(entry)
+        })
+        .collect();
+
+    let mut context = Context::new();
+    context.insert("columns", &columns);
+    context.insert("entries", &entries);
+    context.insert("summary", &summary_value(summary, results, &options.privacy));
+    if let Some(run_info) = &options.run_info {
+        context.insert("run_info", run_info);
+    }
+    context
+}
+
+/// テンプレートで列の値を参照する名前（`-`を`_`に置換した列名）
+/// 
+/// # Arguments
+/// * `column` - 列
+/// 
+/// # Returns
+/// * `String` - 変数名（例: `status`・`request_payload`）
+fn variable_name(column: &Column) -> String {
+    column.key().replace('-', "_")
+}
+
+/// 列の値をテンプレートに渡す値に変換
+/// 
+/// # Arguments
+/// * `column` - 列
+/// * `result` - 解析結果
+/// * `privacy` - 列ごとの匿名化の設定
+/// 
+/// # Returns
+/// * `Value` - 文字列または数値（日時は記録された時刻の文字列）
+fn cell_value(column: &Column, result: &AnalysisResult, privacy: &PrivacyPolicy) -> Value {
+    let mut buffer = String::new();
+    match privacy.value(*column, result, &mut buffer) {
+        CellValue::Text(text) => Value::from(text),
+        CellValue::Number(number) => json!(number),
+        CellValue::DateTime(_) => Value::from(result.timestamp.as_str()),
+    }
+}
+
+/// サマリーをテンプレートに渡す値に変換
+/// 
+/// # Arguments
+/// * `summary` - 解析結果のサマリー
+/// * `results` - 解析結果のリスト
+/// * `privacy` - 列ごとの匿名化の設定（匿名化する場合はURLのパスを含むエンドポイントを渡さない）
+/// 
+/// # Returns
+/// * `Value` - サマリーの値
+fn summary_value(summary: &Summary, results: &[&AnalysisResult], privacy: &PrivacyPolicy) -> Value {
+    let failed = results.iter().filter(|result| !(1..400).contains(&result.status_code)).count();
+    let statuses: Map<String, Value> = summary
+        .status_counts
+        .iter()
+        .map(|(status, count)| (status.to_string(), json!(count)))
+        .collect();
+    let endpoint_success = if privacy.is_active() { &[][..] } else { &summary.endpoint_success[..] };
+    let endpoints: Vec<Value> = endpoint_success
+        .iter()
+        .map(|success| {
+            json!({
+                "endpoint": success.endpoint,
+                "requests": success.requests,
+                "operations": success.operations,
+                "succeeded": success.succeeded,
+                "retried": success.retried,
+                "rate": success.rate(),
+            })
+        })
+        .collect();
+    json!({
+        "total": summary.total,
+        "failed": failed,
+        "error_rate": failed as f64 / summary.total.max(1) as f64,
+        "methods": summary.method_counts,
+        "statuses": statuses,
+        "render_blocking": summary.render_blocking,
+        "endpoints": endpoints,
+        "sampling": summary.sampling,
+        "business_hours": {
+            "inside": summary.business_hours.inside,
+            "inside_errors": summary.business_hours.inside_errors,
+            "outside": summary.business_hours.outside,
+            "outside_errors": summary.business_hours.outside_errors,
+        },
+    })
+}
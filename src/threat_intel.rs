@@ -0,0 +1,316 @@
+//! 脅威インテリジェンスの指標（IPアドレス・ドメイン）との照合（`--threat-intel`・`--threat-intel-lookup`）を実装
+//! 
+//! 侵害された端末から取得したHARのトリアージ向けに、リクエスト先のホストとIPアドレスをローカルのCSVの指標、
+//! または指標を照会するHTTPのエンドポイントと照合する。一致したエントリはSecurity Findingsシートの先頭に出力し、
+//! Flaggedシートと同じように行を強調表示する
+
+use crate::error::{AnalyzerError, Result};
+use crate::har_types::{Entry, HarFile};
+use crate::security::{FindingKind, SecurityFinding};
+use ipnet::IpNet;
+use log::{info, warn};
+use std::collections::{BTreeSet, HashMap};
+use std::net::IpAddr;
+use std::time::Duration;
+use url::Url;
+
+/// 照会のURLで指標に置き換える文字列
+const LOOKUP_PLACEHOLDER: &str = "{indicator}";
+
+/// 照会のタイムアウト
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 照会の応答から詳細として使用する最大の文字数
+const LOOKUP_DETAIL_CHARS: usize = 200;
+
+/// 指標の値
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum IndicatorValue {
+    /// IPアドレスまたはCIDR形式のネットワーク
+    Network(IpNet),
+    /// ドメイン（サブドメインも一致する）
+    Domain(String),
+}
+
+/// 脅威インテリジェンスの指標
+#[derive(Debug, Clone)]
+struct Indicator {
+    /// 指標の値
+    value: IndicatorValue,
+    /// CSVに記述された指標（表示用）
+    text: String,
+    /// 説明（マルウェア名・キャンペーン等）
+    description: String,
+    /// 情報源
+    source: String,
+}
+
+/// 脅威インテリジェンスの指標の照合の設定
+#[derive(Debug, Clone, Default)]
+pub struct ThreatIntel {
+    /// CSVファイルから読み込んだ指標
+    indicators: Vec<Indicator>,
+    /// 指標を照会するURL（`{indicator}`をホストまたはIPアドレスに置き換える）
+    lookup: Option<String>,
+}
+
+impl ThreatIntel {
+    /// 指標のCSVファイルを読み込む
+    /// 
+    /// 1列目に指標（IPアドレス・CIDR・ドメイン・URL）、2列目に説明、3列目に情報源を記述する（2列目以降は省略可）。
+    /// `#`で始まる行と、1行目の1列目が指標として解釈できない場合は見出し行として読み飛ばす
+    /// 
+    /// # Arguments
+    /// * `path` - CSVファイルのパス
+    /// 
+    /// # Returns
+    /// * `Result<ThreatIntel>` - 成功時はファイルの指標のみを含む設定、失敗時はエラー
+    pub fn load(path: &str) -> Result<Self> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .comment(Some(b'#'))
+            .from_path(path)
+            .map_err(|e| AnalyzerError::InvalidArgument(format!("指標のファイルを開けません: {}: {}", path, e)))?;
+
+        let mut indicators = Vec::new();
+        for (line, record) in reader.records().enumerate() {
+            let record = record.map_err(|e| {
+                AnalyzerError::InvalidArgument(format!("指標のファイルを解析できません: {}: {}", path, e))
+            })?;
+            let text = record.get(0).unwrap_or_default().trim();
+            if text.is_empty() {
+                continue;
+            }
+            let Some(value) = Self::parse_indicator(text) else {
+                if line == 0 {
+                    continue;
+                }
+                return Err(AnalyzerError::InvalidArgument(format!(
+                    "指標として解釈できない値です: {}: {}行目: {}",
+                    path,
+                    line + 1,
+                    text
+                )));
+            };
+            indicators.push(Indicator {
+                value,
+                text: text.to_string(),
+                description: record.get(1).unwrap_or_default().trim().to_string(),
+                source: record.get(2).unwrap_or_default().trim().to_string(),
+            });
+        }
+        if indicators.is_empty() {
+            return Err(AnalyzerError::InvalidArgument(format!("指標のファイルに指標がありません: {}", path)));
+        }
+        Ok(ThreatIntel { indicators, lookup: None })
+    }
+
+    /// 指標を照会するURLを解析
+    /// 
+    /// # Arguments
+    /// * `template` - `{indicator}`を含むhttp・httpsのURL
+    /// 
+    /// # Returns
+    /// * `Result<String>` - 成功時はURL、失敗時はエラー
+    pub fn parse_lookup(template: &str) -> Result<String> {
+        if !template.contains(LOOKUP_PLACEHOLDER) {
+            return Err(AnalyzerError::InvalidArgument(format!(
+                "照会のURLに{}がありません: {}",
+                LOOKUP_PLACEHOLDER, template
+            )));
+        }
+        let url = Url::parse(&template.replace(LOOKUP_PLACEHOLDER, "example.com"))
+            .map_err(|e| AnalyzerError::InvalidArgument(format!("照会のURLが不正です: {}: {}", template, e)))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(AnalyzerError::InvalidArgument(format!(
+                "照会のURLはhttpまたはhttpsで指定してください: {}",
+                template
+            )));
+        }
+        Ok(template.to_string())
+    }
+
+    /// 別の設定の指標を追加
+    /// 
+    /// # Arguments
+    /// * `other` - 追加する設定
+    pub fn extend(&mut self, other: &ThreatIntel) {
+        self.indicators.extend(other.indicators.iter().cloned());
+    }
+
+    /// 指標を照会するURLを指定
+    /// 
+    /// # Arguments
+    /// * `lookup` - `{indicator}`を含むURL
+    /// 
+    /// # Returns
+    /// * `ThreatIntel` - URLを指定した設定
+    pub fn with_lookup(mut self, lookup: Option<String>) -> Self {
+        self.lookup = lookup;
+        self
+    }
+
+    /// 照合する指標または照会のURLがあるかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - 照合を行う場合はtrue
+    pub fn is_active(&self) -> bool {
+        !self.indicators.is_empty() || self.lookup.is_some()
+    }
+
+    /// キャプチャ全体のリクエスト先を指標と照合
+    /// 
+    /// 同じホスト・IPアドレスは1回だけ照合し、照会のエンドポイントがエラーを返した場合は以降の照会を行わない
+    /// 
+    /// # Arguments
+    /// * `har` - HARデータ
+    /// 
+    /// # Returns
+    /// * `Vec<SecurityFinding>` - エントリ順の一致した指摘事項
+    pub fn scan(&self, har: &HarFile) -> Vec<SecurityFinding> {
+        if !self.is_active() {
+            return Vec::new();
+        }
+        let mut cache: HashMap<String, Option<String>> = HashMap::new();
+        let mut lookup_enabled = self.lookup.is_some();
+        let mut matched: BTreeSet<String> = BTreeSet::new();
+        let mut findings = Vec::new();
+        for (index, entry) in har.log.entries.iter().enumerate() {
+            for target in Self::targets(entry) {
+                let detail = cache
+                    .entry(target.clone())
+                    .or_insert_with(|| {
+                        self.match_local(&target)
+                            .or_else(|| lookup_enabled.then(|| self.match_remote(&target, &mut lookup_enabled)).flatten())
+                    })
+                    .clone();
+                let Some(detail) = detail else {
+                    continue;
+                };
+                if matched.insert(target.clone()) {
+                    warn!("脅威インテリジェンスの指標に一致しました: {}（{}）", target, detail);
+                }
+                findings.push(SecurityFinding {
+                    kind: FindingKind::ThreatIntel,
+                    entry_number: index + 1,
+                    timestamp: entry.started_date_time.clone(),
+                    method: entry.request.method.clone(),
+                    url: entry.request.url.clone(),
+                    detail: format!("{}: {}", target, detail),
+                });
+            }
+        }
+        if findings.is_empty() {
+            info!("脅威インテリジェンスの指標に一致するリクエスト先はありませんでした（照合したホスト・IPアドレス: {}件）", cache.len());
+        }
+        findings
+    }
+
+    /// エントリのリクエスト先（ホストとIPアドレス）を取得
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// 
+    /// # Returns
+    /// * `Vec<String>` - 小文字のホストとIPアドレス（重複を除く）
+    fn targets(entry: &Entry) -> Vec<String> {
+        let mut targets = Vec::new();
+        if let Ok(url) = Url::parse(&entry.request.url)
+            && let Some(host) = url.host_str()
+        {
+            targets.push(host.trim_start_matches('[').trim_end_matches(']').trim_end_matches('.').to_ascii_lowercase());
+        }
+        if let Some(ip) = &entry.server_ip_address {
+            let ip = ip.trim_start_matches('[').trim_end_matches(']').to_ascii_lowercase();
+            if ip.parse::<IpAddr>().is_ok() && !targets.contains(&ip) {
+                targets.push(ip);
+            }
+        }
+        targets
+    }
+
+    /// CSVの指標と照合
+    /// 
+    /// # Arguments
+    /// * `target` - ホストまたはIPアドレス
+    /// 
+    /// # Returns
+    /// * `Option<String>` - 一致した指標の説明（一致しない場合はNone）
+    fn match_local(&self, target: &str) -> Option<String> {
+        let ip = target.parse::<IpAddr>().ok();
+        let indicator = self.indicators.iter().find(|indicator| match (&indicator.value, ip) {
+            (IndicatorValue::Network(network), Some(ip)) => network.contains(&ip),
+            (IndicatorValue::Domain(domain), None) => {
+                target == domain || target.strip_suffix(domain.as_str()).is_some_and(|sub| sub.ends_with('.'))
+            }
+            _ => false,
+        })?;
+        let mut detail = format!("指標 {}", indicator.text);
+        let notes: Vec<&str> = [indicator.description.as_str(), indicator.source.as_str()]
+            .into_iter()
+            .filter(|note| !note.is_empty())
+            .collect();
+        if !notes.is_empty() {
+            detail.push_str(&format!("（{}）", notes.join("、")));
+        }
+        Some(detail)
+    }
+
+    /// 照会のエンドポイントで照合
+    /// 
+    /// 200番台の応答を一致、404を不一致とみなし、応答の本文の1行目を説明とする
+    /// 
+    /// # Arguments
+    /// * `target` - ホストまたはIPアドレス
+    /// * `enabled` - 以降の照会を行うかどうか（エラーの場合はfalseにする）
+    /// 
+    /// # Returns
+    /// * `Option<String>` - 一致した場合の説明（一致しない場合・照会に失敗した場合はNone）
+    fn match_remote(&self, target: &str, enabled: &mut bool) -> Option<String> {
+        let template = self.lookup.as_deref()?;
+        let url = template.replace(LOOKUP_PLACEHOLDER, &urlencoding::encode(target));
+        match ureq::get(&url).timeout(LOOKUP_TIMEOUT).call() {
+            Ok(response) => {
+                let body = response.into_string().unwrap_or_default();
+                let line: String = body.lines().next().unwrap_or_default().trim().chars().take(LOOKUP_DETAIL_CHARS).collect();
+                Some(if line.is_empty() { "照会で一致".to_string() } else { format!("照会で一致: {}", line) })
+            }
+            Err(ureq::Error::Status(404, _)) => None,
+            Err(e) => {
+                warn!("脅威インテリジェンスの照会に失敗したため、以降の照会を行いません: {}: {}", target, e);
+                *enabled = false;
+                None
+            }
+        }
+    }
+
+    /// CSVの1列目を指標として解釈
+    /// 
+    /// # Arguments
+    /// * `text` - 指標（IPアドレス・CIDR・ドメイン・URL）
+    /// 
+    /// # Returns
+    /// * `Option<IndicatorValue>` - 指標の値（解釈できない場合はNone）
+    fn parse_indicator(text: &str) -> Option<IndicatorValue> {
+        let text = text.trim_start_matches('[').trim_end_matches(']');
+        if let Ok(network) = text.parse::<IpNet>() {
+            return Some(IndicatorValue::Network(network));
+        }
+        if let Ok(ip) = text.parse::<IpAddr>() {
+            return Some(IndicatorValue::Network(IpNet::from(ip)));
+        }
+        let host = match Url::parse(text) {
+            Ok(url) if text.contains("://") => url.host_str()?.to_string(),
+            _ => text.to_string(),
+        };
+        // 脅威情報の共有で使われる無害化の表記（example[.]com）を戻す
+        let domain = host.replace("[.]", ".").trim_start_matches("*.").trim_end_matches('.').to_ascii_lowercase();
+        if let Ok(ip) = domain.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() {
+            return Some(IndicatorValue::Network(IpNet::from(ip)));
+        }
+        let valid = domain.contains('.')
+            && domain.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+        valid.then_some(IndicatorValue::Domain(domain))
+    }
+}
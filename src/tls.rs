@@ -0,0 +1,143 @@
+//! TLSの接続情報（プロキシ等が記録する`_securityDetails`）の解析を実装
+//! 
+//! プロトコル・暗号スイート・証明書の発行者と有効期限を列として出力し、
+//! TLS 1.2未満のプロトコルや期限が近い証明書をホストごとに警告する
+
+use crate::har_types::Entry;
+use chrono::{DateTime, SecondsFormat, Utc};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use url::Url;
+
+/// 証明書の期限が近いとみなす残り日数
+pub const CERT_EXPIRY_WARNING_DAYS: i64 = 30;
+
+/// TLSの接続情報（`_securityDetails`、Chrome DevTools ProtocolのSecurityDetailsと同じ形式）
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SecurityDetails {
+    /// プロトコル（例: "TLS 1.3"）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    /// 暗号スイート（例: "AES_128_GCM"）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cipher: Option<String>,
+    /// 証明書のサブジェクト
+    #[serde(rename = "subjectName", default, skip_serializing_if = "Option::is_none")]
+    pub subject_name: Option<String>,
+    /// 証明書の発行者
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    /// 証明書の有効期間の開始（UNIX時間の秒またはRFC 3339形式の文字列）
+    #[serde(rename = "validFrom", default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<Value>,
+    /// 証明書の有効期間の終了（UNIX時間の秒またはRFC 3339形式の文字列）
+    #[serde(rename = "validTo", default, skip_serializing_if = "Option::is_none")]
+    pub valid_to: Option<Value>,
+}
+
+impl SecurityDetails {
+    /// 証明書の有効期限を取得
+    /// 
+    /// # Returns
+    /// * `Option<DateTime<Utc>>` - 有効期限（記録されていない場合や解析できない場合はNone）
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        match self.valid_to.as_ref()? {
+            Value::Number(seconds) => DateTime::from_timestamp(seconds.as_f64()? as i64, 0),
+            Value::String(text) => DateTime::parse_from_rfc3339(text).ok().map(|dt| dt.with_timezone(&Utc)),
+            _ => None,
+        }
+    }
+
+    /// 証明書の有効期限をISO 8601形式で取得
+    /// 
+    /// # Returns
+    /// * `Option<String>` - UTCの有効期限（例: "2025-01-31T23:59:59Z"）
+    pub fn expiry_iso(&self) -> Option<String> {
+        self.expires_at().map(|expires| expires.to_rfc3339_opts(SecondsFormat::Secs, true))
+    }
+
+    /// TLS 1.2未満（SSLを含む）のプロトコルかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - TLS 1.2未満の場合はtrue（プロトコルが不明な場合やQUIC等はfalse）
+    pub fn is_legacy_protocol(&self) -> bool {
+        let Some(protocol) = &self.protocol else {
+            return false;
+        };
+        let normalized: String = protocol
+            .to_ascii_lowercase()
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '_' | 'v'))
+            .collect();
+        if normalized.starts_with("ssl") {
+            return true;
+        }
+        normalized
+            .strip_prefix("tls")
+            .and_then(|version| version.parse::<f64>().ok())
+            .is_some_and(|version| version < 1.2)
+    }
+
+    /// 接続時点での警告を取得
+    /// 
+    /// # Arguments
+    /// * `at` - 通信の時刻（証明書の残り日数の基準）
+    /// 
+    /// # Returns
+    /// * `Vec<String>` - 警告のリスト
+    pub fn warnings(&self, at: Option<DateTime<Utc>>) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.is_legacy_protocol() {
+            warnings.push(format!(
+                "TLS 1.2未満のプロトコルです: {}",
+                self.protocol.as_deref().unwrap_or_default()
+            ));
+        }
+        if let (Some(expires), Some(at)) = (self.expires_at(), at) {
+            let days = (expires - at).num_days();
+            if expires < at {
+                warnings.push(format!("通信時点で証明書の有効期限が切れています: {}", self.expiry_iso().unwrap_or_default()));
+            } else if days < CERT_EXPIRY_WARNING_DAYS {
+                warnings.push(format!(
+                    "通信時点で証明書の有効期限まで{}日です: {}",
+                    days,
+                    self.expiry_iso().unwrap_or_default()
+                ));
+            }
+        }
+        warnings
+    }
+}
+
+/// ホストごとにTLSの接続情報の警告をログに出力
+/// 
+/// # Arguments
+/// * `entries` - HARエントリのリスト
+pub fn log_warnings(entries: &[Entry]) {
+    let mut warnings_by_host: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for entry in entries {
+        let Some(details) = &entry.security_details else {
+            continue;
+        };
+        let at = DateTime::parse_from_rfc3339(&entry.started_date_time)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc));
+        let host = Url::parse(&entry.request.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| entry.request.url.clone());
+        let host_warnings = warnings_by_host.entry(host).or_default();
+        for warning in details.warnings(at) {
+            if !host_warnings.contains(&warning) {
+                host_warnings.push(warning);
+            }
+        }
+    }
+    for (host, warnings) in warnings_by_host {
+        for warning in warnings {
+            warn!("TLS: {}: {}", host, warning);
+        }
+    }
+}
@@ -0,0 +1,394 @@
+//! リクエスト先のドメインの分類（トラッカー・広告・CDN・ファーストパーティ）を実装
+//! 
+//! 主要なトラッカー・広告・CDNのドメインを同梱し、`--blocklist`で指定したフィルタリスト
+//! （EasyList・EasyPrivacy形式の`||example.com^`やhosts形式）のドメインを追加できる
+
+use crate::error::{AnalyzerError, Result};
+use crate::har_types::{AnalysisResult, Log};
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+use url::Url;
+
+/// 同梱するトラッカー（アクセス解析・行動計測）のドメイン
+const BUNDLED_TRACKERS: &[&str] = &[
+    "google-analytics.com",
+    "analytics.google.com",
+    "googletagmanager.com",
+    "segment.io",
+    "segment.com",
+    "amplitude.com",
+    "mixpanel.com",
+    "heapanalytics.com",
+    "hotjar.com",
+    "hotjar.io",
+    "fullstory.com",
+    "clarity.ms",
+    "mouseflow.com",
+    "nr-data.net",
+    "scorecardresearch.com",
+    "quantserve.com",
+    "chartbeat.com",
+    "chartbeat.net",
+    "bat.bing.com",
+    "yjtag.jp",
+];
+
+/// 同梱する広告配信のドメイン
+const BUNDLED_ADS: &[&str] = &[
+    "doubleclick.net",
+    "googlesyndication.com",
+    "googleadservices.com",
+    "adservice.google.com",
+    "amazon-adsystem.com",
+    "adnxs.com",
+    "adsrvr.org",
+    "criteo.com",
+    "criteo.net",
+    "taboola.com",
+    "outbrain.com",
+    "pubmatic.com",
+    "rubiconproject.com",
+    "openx.net",
+    "casalemedia.com",
+    "connect.facebook.net",
+    "ads-twitter.com",
+    "ads.linkedin.com",
+    "microad.jp",
+    "i-mobile.co.jp",
+];
+
+/// 同梱するCDNのドメイン
+const BUNDLED_CDNS: &[&str] = &[
+    "cloudfront.net",
+    "akamaihd.net",
+    "akamaized.net",
+    "fastly.net",
+    "azureedge.net",
+    "edgecastcdn.net",
+    "b-cdn.net",
+    "cdn77.org",
+    "cdnjs.cloudflare.com",
+    "jsdelivr.net",
+    "unpkg.com",
+    "bootstrapcdn.com",
+    "code.jquery.com",
+    "gstatic.com",
+    "fonts.googleapis.com",
+    "ajax.googleapis.com",
+];
+
+/// 2階層目が組織種別を表す国別トップレベルドメインのラベル（例: co.jp、com.au）
+const SECOND_LEVEL_LABELS: &[&str] = &[
+    "co", "com", "ne", "net", "or", "org", "ac", "ad", "ed", "go", "gr", "lg", "gov", "edu",
+];
+
+/// リクエスト先のドメインの分類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum DomainCategory {
+    /// キャプチャしたページと同じサイト
+    FirstParty,
+    /// トラッカー（アクセス解析・行動計測）
+    Tracker,
+    /// 広告配信
+    Ad,
+    /// CDN
+    Cdn,
+    /// その他の外部サイト
+    #[default]
+    ThirdParty,
+}
+
+impl DomainCategory {
+    /// 全ての分類（シートに出力する順序）
+    pub const ALL: &'static [DomainCategory] = &[
+        DomainCategory::FirstParty,
+        DomainCategory::Tracker,
+        DomainCategory::Ad,
+        DomainCategory::Cdn,
+        DomainCategory::ThirdParty,
+    ];
+
+    /// `--blocklist`で指定する分類名
+    /// 
+    /// # Returns
+    /// * `&'static str` - 分類名
+    pub fn key(&self) -> &'static str {
+        match self {
+            DomainCategory::FirstParty => "first-party",
+            DomainCategory::Tracker => "tracker",
+            DomainCategory::Ad => "ad",
+            DomainCategory::Cdn => "cdn",
+            DomainCategory::ThirdParty => "third-party",
+        }
+    }
+
+    /// シートに表示する分類の名前
+    /// 
+    /// # Returns
+    /// * `&'static str` - 分類の名前
+    pub fn label(&self) -> &'static str {
+        match self {
+            DomainCategory::FirstParty => "ファーストパーティ",
+            DomainCategory::Tracker => "トラッカー",
+            DomainCategory::Ad => "広告",
+            DomainCategory::Cdn => "CDN",
+            DomainCategory::ThirdParty => "サードパーティ",
+        }
+    }
+}
+
+/// ドメインの分類のリスト
+#[derive(Debug, Clone)]
+pub struct Blocklist {
+    /// ドメイン（小文字）ごとの分類
+    domains: HashMap<String, DomainCategory>,
+}
+
+impl Default for Blocklist {
+    fn default() -> Self {
+        let bundled = [
+            (BUNDLED_TRACKERS, DomainCategory::Tracker),
+            (BUNDLED_ADS, DomainCategory::Ad),
+            (BUNDLED_CDNS, DomainCategory::Cdn),
+        ];
+        Blocklist {
+            domains: bundled
+                .into_iter()
+                .flat_map(|(domains, category)| domains.iter().map(move |domain| (domain.to_string(), category)))
+                .collect(),
+        }
+    }
+}
+
+impl Blocklist {
+    /// フィルタリストのファイルを読み込む
+    /// 
+    /// `ad=easylist.txt`のように分類名を前置でき、省略した場合はトラッカーとして扱う。
+    /// ドメイン全体を対象とするルール（`||example.com^`、hosts形式、ドメインのみの行）だけを使用し、
+    /// パスを含むルール・例外ルール・要素隠しルールは読み飛ばす
+    /// 
+    /// # Arguments
+    /// * `spec` - `[分類名=]ファイルのパス`
+    /// 
+    /// # Returns
+    /// * `Result<Blocklist>` - 成功時はファイルのドメインのみを含むリスト、失敗時はエラー
+    pub fn load(spec: &str) -> Result<Self> {
+        let (category, path) = match spec.split_once('=') {
+            Some((key, path)) => {
+                let category = [DomainCategory::Tracker, DomainCategory::Ad, DomainCategory::Cdn]
+                    .into_iter()
+                    .find(|category| category.key().eq_ignore_ascii_case(key.trim()))
+                    .ok_or_else(|| {
+                        AnalyzerError::InvalidArgument(format!(
+                            "不明な分類名です: {}（tracker、ad、cdnのいずれかを指定してください）",
+                            key
+                        ))
+                    })?;
+                (category, path)
+            }
+            None => (DomainCategory::Tracker, spec),
+        };
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| AnalyzerError::InvalidArgument(format!("フィルタリストを読み込めません: {}: {}", path, e)))?;
+
+        let domains: HashMap<String, DomainCategory> = content
+            .lines()
+            .filter_map(Self::rule_domain)
+            .map(|domain| (domain, category))
+            .collect();
+        if domains.is_empty() {
+            return Err(AnalyzerError::InvalidArgument(format!(
+                "フィルタリストにドメインのルールがありません: {}",
+                path
+            )));
+        }
+        Ok(Blocklist { domains })
+    }
+
+    /// 別のリストのドメインを追加（同じドメインは追加する側の分類を優先する）
+    /// 
+    /// # Arguments
+    /// * `other` - 追加するリスト
+    pub fn extend(&mut self, other: &Blocklist) {
+        self.domains
+            .extend(other.domains.iter().map(|(domain, category)| (domain.clone(), *category)));
+    }
+
+    /// ホストの分類を取得
+    /// 
+    /// リストのドメインに一致するか、そのサブドメインであればリストの分類とし、
+    /// それ以外はファーストパーティのサイトと同じかどうかで分類する
+    /// 
+    /// # Arguments
+    /// * `host` - リクエスト先のホスト
+    /// * `first_party_site` - キャプチャしたページのサイト（`site_of`で求めた値）
+    /// 
+    /// # Returns
+    /// * `DomainCategory` - 分類
+    pub fn classify(&self, host: &str, first_party_site: Option<&str>) -> DomainCategory {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        // 長いドメインから順に照合し、より具体的なルールを優先する
+        let mut suffix = host.as_str();
+        loop {
+            if let Some(category) = self.domains.get(suffix) {
+                return *category;
+            }
+            match suffix.split_once('.') {
+                Some((_, parent)) => suffix = parent,
+                None => break,
+            }
+        }
+        if first_party_site.is_some_and(|site| site == site_of(&host)) {
+            DomainCategory::FirstParty
+        } else {
+            DomainCategory::ThirdParty
+        }
+    }
+
+    /// フィルタリストの1行からドメインを取り出す
+    /// 
+    /// # Arguments
+    /// * `line` - フィルタリストの行
+    /// 
+    /// # Returns
+    /// * `Option<String>` - ドメイン（ドメイン全体を対象とするルールでない場合はNone）
+    fn rule_domain(line: &str) -> Option<String> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(['!', '[', '#']) || line.starts_with("@@") || line.contains('#') {
+            return None;
+        }
+        let domain = if let Some(rule) = line.strip_prefix("||") {
+            // オプション（$third-party等）は無視し、ドメインの直後が区切り（^）で終わるルールのみを使用する
+            let rule = rule.split('$').next().unwrap_or_default();
+            rule.strip_suffix('^').or_else(|| (!rule.contains(['^', '/', '*'])).then_some(rule))?
+        } else {
+            // hosts形式（0.0.0.0 example.com）またはドメインのみの行
+            let mut fields = line.split_whitespace();
+            let first = fields.next()?;
+            match fields.next() {
+                Some(second) if first.parse::<IpAddr>().is_ok() => second,
+                Some(_) => return None,
+                None => first,
+            }
+        };
+        let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+        let valid = domain.contains('.')
+            && !matches!(domain.as_str(), "localhost" | "local" | "broadcasthost")
+            && domain.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+        valid.then_some(domain)
+    }
+}
+
+/// ホストのサイト（登録可能なドメイン）を求める
+/// 
+/// 公開サフィックスリストを使わない簡易的な判定で、末尾の2ラベル
+/// （`co.jp`のような国別の組織種別ドメインの場合は3ラベル）をサイトとみなす
+/// 
+/// # Arguments
+/// * `host` - ホスト
+/// 
+/// # Returns
+/// * `String` - サイト（IPアドレスの場合はそのまま）
+pub fn site_of(host: &str) -> String {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    if host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>().is_ok() {
+        return host;
+    }
+    let labels: Vec<&str> = host.split('.').collect();
+    let count = match labels.as_slice() {
+        [.., second, top] if top.len() == 2 && SECOND_LEVEL_LABELS.contains(second) => 3,
+        _ => 2,
+    };
+    labels[labels.len().saturating_sub(count)..].join(".")
+}
+
+/// キャプチャしたページのサイトを求める
+/// 
+/// 最初のページのタイトル（ブラウザはURLを記録する）を優先し、URLでない場合は最初のエントリのURLを使用する
+/// 
+/// # Arguments
+/// * `log` - HARのログ
+/// 
+/// # Returns
+/// * `Option<String>` - サイト（求められない場合はNone）
+pub fn first_party_site(log: &Log) -> Option<String> {
+    let page_url = log.pages.first().map(|page| page.title.as_str());
+    let entry_url = log.entries.first().map(|entry| entry.request.url.as_str());
+    page_url
+        .into_iter()
+        .chain(entry_url)
+        .filter_map(|url| Url::parse(url).ok())
+        .find_map(|url| url.host_str().map(site_of))
+}
+
+/// 分類・ホストごとの集計値
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CategoryBucket {
+    /// リクエスト数
+    pub requests: usize,
+    /// 転送量（リクエストとレスポンスのボディの合計、バイト）
+    pub bytes: i64,
+}
+
+/// 分類ごと・ホストごとのリクエスト数と転送量
+#[derive(Debug, Clone, Default)]
+pub struct CategoryTotals {
+    /// 分類ごとの集計値
+    categories: BTreeMap<DomainCategory, CategoryBucket>,
+    /// (分類, ホスト)ごとの集計値
+    hosts: BTreeMap<(DomainCategory, String), CategoryBucket>,
+}
+
+impl CategoryTotals {
+    /// 解析結果を1件集計に加える
+    /// 
+    /// # Arguments
+    /// * `result` - 解析結果
+    pub fn add(&mut self, result: &AnalysisResult) {
+        let host = Url::parse(&result.request_url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+        let bytes = result.request_size.max(0) + result.response_size.max(0);
+        for bucket in [
+            self.categories.entry(result.domain_category).or_default(),
+            self.hosts.entry((result.domain_category, host)).or_default(),
+        ] {
+            bucket.requests += 1;
+            bucket.bytes += bytes;
+        }
+    }
+
+    /// 集計した通信が無いかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - 通信が無い場合はtrue
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+
+    /// 分類ごとの集計値
+    /// 
+    /// # Returns
+    /// * `Vec<(DomainCategory, CategoryBucket)>` - 分類の順の集計値（通信が無い分類を含む）
+    pub fn categories(&self) -> Vec<(DomainCategory, CategoryBucket)> {
+        DomainCategory::ALL
+            .iter()
+            .map(|category| (*category, self.categories.get(category).copied().unwrap_or_default()))
+            .collect()
+    }
+
+    /// ホストごとの集計値
+    /// 
+    /// # Returns
+    /// * `Vec<(DomainCategory, &str, CategoryBucket)>` - 分類の順、同じ分類内は転送量の多い順の集計値
+    pub fn hosts(&self) -> Vec<(DomainCategory, &str, CategoryBucket)> {
+        let mut hosts: Vec<_> = self
+            .hosts
+            .iter()
+            .map(|((category, host), bucket)| (*category, host.as_str(), *bucket))
+            .collect();
+        hosts.sort_by(|a, b| a.0.cmp(&b.0).then(b.2.bytes.cmp(&a.2.bytes)).then(a.1.cmp(b.1)));
+        hosts
+    }
+}
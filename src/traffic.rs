@@ -0,0 +1,176 @@
+//! 一定間隔の時間帯ごとのリクエスト数と転送量の集計を実装
+//! 
+//! 長時間の監視キャプチャで通信の急増・急減を見つけられるように、
+//! 直前の時間帯のリクエスト数に対する移動zスコアで異常な時間帯を判定する
+
+use crate::har_types::AnalysisResult;
+use chrono::{DateTime, FixedOffset};
+use std::collections::BTreeMap;
+
+/// 出力する時間帯の数の上限（通信の間隔が空きすぎている場合に空の時間帯で膨れないようにする）
+pub const MAX_BUCKETS: usize = 100_000;
+
+/// 移動zスコアの基準にする直前の時間帯の数
+pub const ANOMALY_WINDOW: usize = 30;
+
+/// 異常とみなすzスコアの絶対値
+pub const ANOMALY_Z_SCORE: f64 = 3.0;
+
+/// 移動zスコアを計算するために必要な直前の時間帯の最小数
+const MIN_ANOMALY_HISTORY: usize = 5;
+
+/// 時間帯ごとのリクエスト数の移動zスコア
+#[derive(Debug, Clone, Copy)]
+pub struct AnomalyScore {
+    /// 直前の時間帯のリクエスト数の平均
+    pub mean: f64,
+    /// 正常とみなすリクエスト数の下限（平均 - 閾値×標準偏差、0未満は0）
+    pub lower: f64,
+    /// 正常とみなすリクエスト数の上限（平均 + 閾値×標準偏差）
+    pub upper: f64,
+    /// zスコア
+    pub z_score: f64,
+}
+
+impl AnomalyScore {
+    /// 異常な時間帯かどうか
+    /// 
+    /// # Returns
+    /// * `bool` - zスコアの絶対値が閾値以上の場合はtrue
+    pub fn is_anomalous(&self) -> bool {
+        self.z_score.abs() >= ANOMALY_Z_SCORE
+    }
+}
+
+/// 時間帯ごとの集計値
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TrafficBucket {
+    /// リクエスト数
+    pub requests: usize,
+    /// 転送量（リクエストとレスポンスのボディの合計、バイト）
+    pub bytes: i64,
+}
+
+/// 時間帯ごとのリクエスト数と転送量
+#[derive(Debug, Clone)]
+pub struct TrafficTimeline {
+    /// 時間帯の間隔（ミリ秒）
+    interval_ms: i64,
+    /// 時間帯の開始時刻（UNIXエポックからのミリ秒）ごとの集計値
+    buckets: BTreeMap<i64, TrafficBucket>,
+    /// 時刻を表示するタイムゾーン（時刻列と揃えるため最初の通信のタイムゾーンを使用）
+    offset: Option<FixedOffset>,
+}
+
+impl TrafficTimeline {
+    /// 空の集計を作成
+    /// 
+    /// # Arguments
+    /// * `interval_ms` - 時間帯の間隔（ミリ秒）
+    /// 
+    /// # Returns
+    /// * `TrafficTimeline` - 空の集計
+    pub fn new(interval_ms: u64) -> Self {
+        TrafficTimeline {
+            interval_ms: interval_ms.max(1) as i64,
+            buckets: BTreeMap::new(),
+            offset: None,
+        }
+    }
+
+    /// 時間帯の間隔（ミリ秒）
+    /// 
+    /// # Returns
+    /// * `u64` - 時間帯の間隔（ミリ秒）
+    pub fn interval_ms(&self) -> u64 {
+        self.interval_ms as u64
+    }
+
+    /// 解析結果を1件集計に加える（時刻を解析できない場合は無視する）
+    /// 
+    /// # Arguments
+    /// * `result` - 解析結果
+    pub fn add(&mut self, result: &AnalysisResult) {
+        let Ok(started) = DateTime::parse_from_rfc3339(&result.timestamp_iso) else {
+            return;
+        };
+        let offset = *self.offset.get_or_insert(*started.offset());
+        let millis = started.timestamp_millis();
+        // 時間帯の区切りを表示するタイムゾーンの時刻に揃える
+        let local_millis = millis + offset.local_minus_utc() as i64 * 1000;
+        let start = millis - local_millis.rem_euclid(self.interval_ms);
+
+        let bucket = self.buckets.entry(start).or_default();
+        bucket.requests += 1;
+        bucket.bytes += result.request_size.max(0) + result.response_size.max(0);
+    }
+
+    /// 集計した通信が無いかどうか
+    /// 
+    /// # Returns
+    /// * `bool` - 通信が無い場合はtrue
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    /// 最初から最後の時間帯までの数（通信の無い時間帯を含む）
+    /// 
+    /// # Returns
+    /// * `usize` - 時間帯の数
+    pub fn len(&self) -> usize {
+        match (self.buckets.keys().next(), self.buckets.keys().next_back()) {
+            (Some(first), Some(last)) => ((last - first) / self.interval_ms) as usize + 1,
+            _ => 0,
+        }
+    }
+
+    /// 最初から最後の時間帯までの集計値（通信の無い時間帯は0件として含める）
+    /// 
+    /// # Returns
+    /// * `Vec<(DateTime<FixedOffset>, TrafficBucket)>` - (時間帯の開始時刻, 集計値)のリスト
+    pub fn rows(&self) -> Vec<(DateTime<FixedOffset>, TrafficBucket)> {
+        let (Some(&first), Some(offset)) = (self.buckets.keys().next(), self.offset) else {
+            return Vec::new();
+        };
+
+        (0..self.len())
+            .filter_map(|index| {
+                let start = first + index as i64 * self.interval_ms;
+                let bucket = self.buckets.get(&start).copied().unwrap_or_default();
+                DateTime::from_timestamp_millis(start).map(|time| (time.with_timezone(&offset), bucket))
+            })
+            .collect()
+    }
+}
+
+/// 時間帯ごとのリクエスト数の移動zスコアを計算
+/// 
+/// 直前の最大`ANOMALY_WINDOW`件の時間帯の平均と標準偏差を基準にする。
+/// 通信量が一定の場合に1件の違いで異常とならないように、標準偏差が1件未満の場合は1件とみなす
+/// 
+/// # Arguments
+/// * `rows` - `TrafficTimeline::rows`の集計値
+/// 
+/// # Returns
+/// * `Vec<Option<AnomalyScore>>` - 時間帯ごとのzスコア（直前の時間帯が足りない場合はNone）
+pub fn anomaly_scores(rows: &[(DateTime<FixedOffset>, TrafficBucket)]) -> Vec<Option<AnomalyScore>> {
+    let requests: Vec<f64> = rows.iter().map(|(_, bucket)| bucket.requests as f64).collect();
+    (0..requests.len())
+        .map(|index| {
+            let history = &requests[index.saturating_sub(ANOMALY_WINDOW)..index];
+            if history.len() < MIN_ANOMALY_HISTORY {
+                return None;
+            }
+            let count = history.len() as f64;
+            let mean = history.iter().sum::<f64>() / count;
+            let variance = history.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / count;
+            let deviation = variance.sqrt().max(1.0);
+            Some(AnomalyScore {
+                mean,
+                lower: (mean - ANOMALY_Z_SCORE * deviation).max(0.0),
+                upper: mean + ANOMALY_Z_SCORE * deviation,
+                z_score: (requests[index] - mean) / deviation,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,307 @@
+//! HARファイルを変換するパイプライン（filter/rewrite-url/drop-headers等）を実装
+
+use crate::har_types::{Entry, HarFile, NameValue};
+use crate::error::{AnalyzerError, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use log::{debug, info};
+use regex::Regex;
+use serde::Deserialize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use url::Url;
+
+/// ヘッダー削除の対象
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HeaderTarget {
+    Request,
+    Response,
+    #[default]
+    Both,
+}
+
+/// 設定ファイルに記述する変換処理
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "kebab-case")]
+pub enum TransformStep {
+    /// 条件に一致するエントリのみを残す
+    Filter {
+        #[serde(default)]
+        methods: Vec<String>,
+        url_regex: Option<String>,
+        status_min: Option<i32>,
+        status_max: Option<i32>,
+        mime_type: Option<String>,
+    },
+    /// リクエストURLを正規表現で置換
+    RewriteUrl { pattern: String, replacement: String },
+    /// 指定した名前のヘッダーを削除
+    DropHeaders {
+        names: Vec<String>,
+        #[serde(default)]
+        target: HeaderTarget,
+    },
+    /// リクエスト/レスポンスボディを指定文字数に切り詰め
+    TruncateBodies { max_length: usize },
+    /// ページIDを付け替え
+    RelabelPage {
+        from: String,
+        to: String,
+        title: Option<String>,
+    },
+}
+
+/// 正規表現等を事前に準備した変換処理
+#[derive(Debug)]
+enum CompiledStep {
+    Filter {
+        methods: Vec<String>,
+        url_regex: Option<Regex>,
+        status_min: Option<i32>,
+        status_max: Option<i32>,
+        mime_type: Option<String>,
+    },
+    RewriteUrl { pattern: Regex, replacement: String },
+    DropHeaders {
+        names: Vec<String>,
+        target: HeaderTarget,
+    },
+    TruncateBodies { max_length: usize },
+    RelabelPage {
+        from: String,
+        to: String,
+        title: Option<String>,
+    },
+}
+
+/// 変換パイプライン
+#[derive(Debug)]
+pub struct TransformPipeline {
+    steps: Vec<CompiledStep>,
+}
+
+impl TransformPipeline {
+    /// 設定から変換パイプラインを作成
+    /// 
+    /// # Arguments
+    /// * `steps` - 設定ファイルに記述された変換処理
+    /// 
+    /// # Returns
+    /// * `Result<TransformPipeline>` - 成功時はパイプライン、失敗時はエラー
+    pub fn new(steps: &[TransformStep]) -> Result<Self> {
+        let mut compiled = Vec::with_capacity(steps.len());
+
+        for step in steps {
+            let step = match step {
+                TransformStep::Filter {
+                    methods,
+                    url_regex,
+                    status_min,
+                    status_max,
+                    mime_type,
+                } => CompiledStep::Filter {
+                    methods: methods.iter().map(|m| m.to_uppercase()).collect(),
+                    url_regex: url_regex.as_deref().map(Self::compile_regex).transpose()?,
+                    status_min: *status_min,
+                    status_max: *status_max,
+                    mime_type: mime_type.clone(),
+                },
+                TransformStep::RewriteUrl { pattern, replacement } => CompiledStep::RewriteUrl {
+                    pattern: Self::compile_regex(pattern)?,
+                    replacement: replacement.clone(),
+                },
+                TransformStep::DropHeaders { names, target } => CompiledStep::DropHeaders {
+                    names: names.iter().map(|n| n.to_lowercase()).collect(),
+                    target: *target,
+                },
+                TransformStep::TruncateBodies { max_length } => CompiledStep::TruncateBodies {
+                    max_length: *max_length,
+                },
+                TransformStep::RelabelPage { from, to, title } => CompiledStep::RelabelPage {
+                    from: from.clone(),
+                    to: to.clone(),
+                    title: title.clone(),
+                },
+            };
+            compiled.push(step);
+        }
+
+        Ok(TransformPipeline { steps: compiled })
+    }
+
+    /// 正規表現をコンパイル
+    fn compile_regex(pattern: &str) -> Result<Regex> {
+        Regex::new(pattern).map_err(|e| {
+            AnalyzerError::InvalidArgument(format!("正規表現が不正です: {} ({})", pattern, e))
+        })
+    }
+
+    /// HARデータに変換処理を順に適用
+    /// 
+    /// # Arguments
+    /// * `har` - 変換するHARデータ
+    pub fn apply(&self, har: &mut HarFile) {
+        for (index, step) in self.steps.iter().enumerate() {
+            let before = har.log.entries.len();
+
+            match step {
+                CompiledStep::Filter {
+                    methods,
+                    url_regex,
+                    status_min,
+                    status_max,
+                    mime_type,
+                } => {
+                    har.log.entries.retain(|entry| {
+                        (methods.is_empty() || methods.contains(&entry.request.method.to_uppercase()))
+                            && url_regex.as_ref().is_none_or(|r| r.is_match(&entry.request.url))
+                            && status_min.is_none_or(|min| entry.response.status >= min)
+                            && status_max.is_none_or(|max| entry.response.status <= max)
+                            && mime_type
+                                .as_ref()
+                                .is_none_or(|m| entry.response.content.mime_type.contains(m.as_str()))
+                    });
+                }
+                CompiledStep::RewriteUrl { pattern, replacement } => {
+                    for entry in &mut har.log.entries {
+                        Self::rewrite_url(entry, pattern, replacement);
+                    }
+                }
+                CompiledStep::DropHeaders { names, target } => {
+                    let drop = |headers: &mut Vec<NameValue>| {
+                        headers.retain(|h| !names.contains(&h.name.to_lowercase()));
+                    };
+                    for entry in &mut har.log.entries {
+                        if matches!(target, HeaderTarget::Request | HeaderTarget::Both) {
+                            drop(&mut entry.request.headers);
+                        }
+                        if matches!(target, HeaderTarget::Response | HeaderTarget::Both) {
+                            drop(&mut entry.response.headers);
+                        }
+                    }
+                }
+                CompiledStep::TruncateBodies { max_length } => {
+                    for entry in &mut har.log.entries {
+                        Self::truncate_bodies(entry, *max_length);
+                    }
+                }
+                CompiledStep::RelabelPage { from, to, title } => {
+                    for page in &mut har.log.pages {
+                        if page.id == *from {
+                            page.id = to.clone();
+                            if let Some(title) = title {
+                                page.title = title.clone();
+                            }
+                        }
+                    }
+                    for entry in &mut har.log.entries {
+                        if entry.pageref.as_deref() == Some(from.as_str()) {
+                            entry.pageref = Some(to.clone());
+                        }
+                    }
+                }
+            }
+
+            debug!(
+                "変換処理 {} を適用しました: エントリ数 {} -> {}",
+                index + 1,
+                before,
+                har.log.entries.len()
+            );
+        }
+    }
+
+    /// リクエストURLを置換し、クエリ文字列を再構築
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// * `pattern` - 置換対象の正規表現
+    /// * `replacement` - 置換後の文字列
+    fn rewrite_url(entry: &mut Entry, pattern: &Regex, replacement: &str) {
+        let rewritten = pattern.replace_all(&entry.request.url, replacement);
+        if rewritten == entry.request.url {
+            return;
+        }
+
+        entry.request.url = rewritten.into_owned();
+        if let Ok(url) = Url::parse(&entry.request.url) {
+            entry.request.query_string = url
+                .query_pairs()
+                .map(|(name, value)| NameValue {
+                    name: name.into_owned(),
+                    value: value.into_owned(),
+                })
+                .collect();
+        }
+    }
+
+    /// リクエスト/レスポンスボディを切り詰め
+    /// 
+    /// # Arguments
+    /// * `entry` - HARエントリ
+    /// * `max_length` - 最大文字数
+    fn truncate_bodies(entry: &mut Entry, max_length: usize) {
+        if let Some(text) = entry.request.post_data.as_mut().and_then(|p| p.text.as_mut()) {
+            Self::truncate_text(text, max_length);
+        }
+
+        let content = &mut entry.response.content;
+        let base64 = content.encoding.as_deref() == Some("base64");
+        if let Some(text) = content.text.as_mut() {
+            // Base64の場合はデコード可能な長さ（4の倍数）に揃える
+            let max_length = if base64 { max_length - max_length % 4 } else { max_length };
+            if Self::truncate_text(text, max_length) {
+                // content.sizeは切り詰めた後のボディ（Base64の場合はデコードした）のバイト数とする
+                content.size = if base64 {
+                    STANDARD.decode(text.as_bytes()).map_or(text.len() / 4 * 3, |bytes| bytes.len()) as i64
+                } else {
+                    text.len() as i64
+                };
+            }
+        }
+    }
+
+    /// 文字境界を考慮して文字列を切り詰め
+    /// 
+    /// # Returns
+    /// * `bool` - 切り詰めた場合はtrue
+    fn truncate_text(text: &mut String, max_length: usize) -> bool {
+        match text.char_indices().nth(max_length) {
+            Some((byte_index, _)) => {
+                text.truncate(byte_index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// HARデータをファイルに書き込み
+    /// 
+    /// # Arguments
+    /// * `har` - HARデータ
+    /// * `output_path` - 出力ファイルのパス
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub fn write_har(har: &HarFile, output_path: &str) -> Result<()> {
+        info!("HARファイルに出力しています: {}", output_path);
+
+        if let Some(parent) = Path::new(output_path).parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(AnalyzerError::io("出力ディレクトリの作成に失敗しました"))?;
+        }
+
+        let file = File::create(output_path)
+            .map_err(AnalyzerError::io("HARファイルの作成に失敗しました"))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, har)
+            .map_err(AnalyzerError::export("HARファイルの書き込みに失敗しました"))?;
+        writer.flush()
+            .map_err(AnalyzerError::io("HARファイルの書き込みに失敗しました"))?;
+
+        info!("HARファイルの出力が完了しました: {}", output_path);
+        Ok(())
+    }
+}
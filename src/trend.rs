@@ -0,0 +1,339 @@
+//! 複数回のキャプチャにわたるエンドポイントごとの所要時間・エラー率の推移の記録と出力を実装
+//! 
+//! 実行ごとのエンドポイントの集計をローカルのSQLiteデータベースに追記し、直近の実行の推移をTrendシートに出力する。
+//! 単発の解析を継続的な監視に使用できるようにする
+
+use crate::excel_styles::ExcelStyles;
+use crate::har_types::AnalysisResult;
+use crate::outliers::{endpoint_key, median};
+use crate::error::{AnalyzerError, Result};
+use log::info;
+use rusqlite::{Connection, params};
+use rust_xlsxwriter::{Format, Workbook};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// 推移を出力するシートの名前
+const TREND_SHEET_NAME: &str = "Trend";
+
+/// 記録した実行の一覧を出力するシートの名前
+const RUNS_SHEET_NAME: &str = "Runs";
+
+/// 集計から推移に出力する指標の値を取得する関数
+type MetricValue = fn(&EndpointAggregate) -> f64;
+
+/// データベースのテーブルの定義
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    recorded_at TEXT NOT NULL,
+    input_file TEXT NOT NULL,
+    requests INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS endpoint_stats (
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    endpoint TEXT NOT NULL,
+    requests INTEGER NOT NULL,
+    errors INTEGER NOT NULL,
+    median_ms REAL NOT NULL,
+    mean_ms REAL NOT NULL,
+    PRIMARY KEY (run_id, endpoint)
+);
+";
+
+/// 1回の実行のエンドポイントの集計
+#[derive(Debug, Clone)]
+pub struct EndpointAggregate {
+    /// エンドポイント（例: "GET https://example.com/api/users"）
+    pub endpoint: String,
+    /// リクエスト数
+    pub requests: usize,
+    /// 失敗したリクエスト（ステータスコードが0または400以上）の数
+    pub errors: usize,
+    /// 所要時間の中央値（ミリ秒）
+    pub median_ms: f64,
+    /// 所要時間の平均（ミリ秒）
+    pub mean_ms: f64,
+}
+
+impl EndpointAggregate {
+    /// エラー率
+    /// 
+    /// # Returns
+    /// * `f64` - エラー率（0.0〜1.0、リクエストが無い場合は0.0）
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.requests as f64
+        }
+    }
+}
+
+/// 解析結果をエンドポイントごとに集計
+/// 
+/// # Arguments
+/// * `results` - 解析結果のリスト
+/// 
+/// # Returns
+/// * `Vec<EndpointAggregate>` - エンドポイントの順の集計
+pub fn aggregate(results: &[AnalysisResult]) -> Vec<EndpointAggregate> {
+    let mut endpoints: BTreeMap<String, (Vec<f64>, usize)> = BTreeMap::new();
+    for result in results {
+        let (durations, errors) = endpoints.entry(endpoint_key(result)).or_default();
+        durations.push(result.duration.max(0.0));
+        if !(1..400).contains(&result.status_code) {
+            *errors += 1;
+        }
+    }
+    endpoints
+        .into_iter()
+        .map(|(endpoint, (durations, errors))| EndpointAggregate {
+            endpoint,
+            requests: durations.len(),
+            errors,
+            median_ms: median(&durations),
+            mean_ms: durations.iter().sum::<f64>() / durations.len() as f64,
+        })
+        .collect()
+}
+
+/// 記録した1回の実行
+#[derive(Debug, Clone)]
+pub struct TrendRun {
+    /// 実行のID
+    pub id: i64,
+    /// 記録した日時
+    pub recorded_at: String,
+    /// 解析したHARファイルのパス
+    pub input_file: String,
+    /// リクエスト数
+    pub requests: usize,
+}
+
+/// 直近の実行のエンドポイントごとの推移
+#[derive(Debug, Clone, Default)]
+pub struct TrendHistory {
+    /// 古い順の実行
+    pub runs: Vec<TrendRun>,
+    /// エンドポイントごとの実行のIDと集計
+    pub endpoints: BTreeMap<String, BTreeMap<i64, EndpointAggregate>>,
+}
+
+/// 推移を記録するSQLiteデータベース
+pub struct TrendDatabase {
+    connection: Connection,
+}
+
+impl TrendDatabase {
+    /// データベースを開く（無い場合は作成する）
+    /// 
+    /// # Arguments
+    /// * `path` - データベースのファイルのパス
+    /// 
+    /// # Returns
+    /// * `Result<TrendDatabase>` - 成功時はデータベース、失敗時はエラー
+    pub fn open(path: &str) -> Result<Self> {
+        if let Some(parent) = Path::new(path).parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent).map_err(AnalyzerError::io("データベースのディレクトリの作成に失敗しました"))?;
+        }
+        let connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(TrendDatabase { connection })
+    }
+
+    /// 1回の実行の集計を追記
+    /// 
+    /// # Arguments
+    /// * `input_file` - 解析したHARファイルのパス
+    /// * `recorded_at` - 記録する日時
+    /// * `aggregates` - エンドポイントごとの集計
+    /// 
+    /// # Returns
+    /// * `Result<i64>` - 成功時は追記した実行のID、失敗時はエラー
+    pub fn record(&mut self, input_file: &str, recorded_at: &str, aggregates: &[EndpointAggregate]) -> Result<i64> {
+        let transaction = self.connection.transaction()?;
+        let requests: usize = aggregates.iter().map(|aggregate| aggregate.requests).sum();
+        transaction.execute(
+            "INSERT INTO runs (recorded_at, input_file, requests) VALUES (?1, ?2, ?3)",
+            params![recorded_at, input_file, requests as i64],
+        )?;
+        let run_id = transaction.last_insert_rowid();
+        {
+            let mut statement = transaction.prepare(
+                "INSERT INTO endpoint_stats (run_id, endpoint, requests, errors, median_ms, mean_ms)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for aggregate in aggregates {
+                statement.execute(params![
+                    run_id,
+                    aggregate.endpoint,
+                    aggregate.requests as i64,
+                    aggregate.errors as i64,
+                    aggregate.median_ms,
+                    aggregate.mean_ms,
+                ])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(run_id)
+    }
+
+    /// 直近の実行の推移を取得
+    /// 
+    /// # Arguments
+    /// * `last_runs` - 取得する実行の数
+    /// 
+    /// # Returns
+    /// * `Result<TrendHistory>` - 成功時は推移、失敗時はエラー
+    pub fn history(&self, last_runs: usize) -> Result<TrendHistory> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT id, recorded_at, input_file, requests FROM runs ORDER BY id DESC LIMIT ?1")?;
+        let mut runs = statement
+            .query_map(params![last_runs as i64], |row| {
+                Ok(TrendRun {
+                    id: row.get(0)?,
+                    recorded_at: row.get(1)?,
+                    input_file: row.get(2)?,
+                    requests: row.get::<_, i64>(3)? as usize,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        runs.reverse();
+
+        let mut history = TrendHistory::default();
+        let Some(oldest) = runs.first().map(|run| run.id) else {
+            return Ok(history);
+        };
+        let mut statement = self.connection.prepare(
+            "SELECT run_id, endpoint, requests, errors, median_ms, mean_ms FROM endpoint_stats WHERE run_id >= ?1",
+        )?;
+        let rows = statement.query_map(params![oldest], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                EndpointAggregate {
+                    endpoint: row.get(1)?,
+                    requests: row.get::<_, i64>(2)? as usize,
+                    errors: row.get::<_, i64>(3)? as usize,
+                    median_ms: row.get(4)?,
+                    mean_ms: row.get(5)?,
+                },
+            ))
+        })?;
+        for row in rows {
+            let (run_id, aggregate) = row?;
+            history
+                .endpoints
+                .entry(aggregate.endpoint.clone())
+                .or_default()
+                .insert(run_id, aggregate);
+        }
+        history.runs = runs;
+        Ok(history)
+    }
+}
+
+impl TrendHistory {
+    /// 推移をExcelファイルに出力
+    /// 
+    /// エンドポイントごとに所要時間の中央値とエラー率の2行を出力し、実行ごとの値を古い順に列として並べる
+    /// 
+    /// # Arguments
+    /// * `output_path` - 出力するExcelファイルのパス
+    /// 
+    /// # Returns
+    /// * `Result<()>` - 成功時はOk、失敗時はエラー
+    pub fn export(&self, output_path: &str) -> Result<()> {
+        info!("Excelファイルに出力しています: {}", output_path);
+
+        if let Some(parent) = Path::new(output_path).parent() {
+            fs::create_dir_all(parent)
+                .map_err(AnalyzerError::io("出力ディレクトリの作成に失敗しました"))?;
+        }
+
+        let styles = ExcelStyles::new(true);
+        let milliseconds = styles.cell.clone().set_num_format("#,##0.000");
+        let percent = styles.cell.clone().set_num_format("0.0%");
+        let mut workbook = Workbook::new();
+
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(TREND_SHEET_NAME)?;
+        let mut headers = vec![("エンドポイント".to_string(), 60.0), ("指標".to_string(), 20.0)];
+        headers.extend(self.runs.iter().map(|run| (format!("#{} {}", run.id, run.recorded_at), 22.0)));
+        headers.push(("前回からの変化".to_string(), 16.0));
+        for (col, (header, width)) in headers.iter().enumerate() {
+            worksheet.write_string_with_format(0, col as u16, header, &styles.header)?;
+            worksheet.set_column_width(col as u16, *width)?;
+        }
+        worksheet.set_freeze_panes(1, 2)?;
+
+        let change_col = headers.len() as u16 - 1;
+        let mut row = 1;
+        for (endpoint, runs) in &self.endpoints {
+            let metrics: [(&str, MetricValue, &Format); 2] = [
+                ("所要時間の中央値(ms)", |aggregate| aggregate.median_ms, &milliseconds),
+                ("エラー率", EndpointAggregate::error_rate, &percent),
+            ];
+            for (label, value, format) in metrics {
+                worksheet.write_string_with_format(row, 0, endpoint, &styles.cell)?;
+                worksheet.write_string_with_format(row, 1, label, &styles.cell)?;
+                let mut values = Vec::new();
+                for (index, run) in self.runs.iter().enumerate() {
+                    let col = index as u16 + 2;
+                    match runs.get(&run.id) {
+                        Some(aggregate) => {
+                            worksheet.write_number_with_format(row, col, value(aggregate), format)?;
+                            values.push(value(aggregate));
+                        }
+                        None => {
+                            worksheet.write_string_with_format(row, col, "", &styles.cell)?;
+                        }
+                    }
+                }
+                // 最後の実行に記録が無いエンドポイントは変化を出力しない
+                let latest = self.runs.last().and_then(|run| runs.get(&run.id)).map(value);
+                match (latest, values.iter().rev().nth(1)) {
+                    (Some(latest), Some(previous)) => {
+                        worksheet.write_number_with_format(row, change_col, latest - previous, format)?
+                    }
+                    _ => worksheet.write_string_with_format(row, change_col, "", &styles.cell)?,
+                };
+                row += 1;
+            }
+        }
+        if row > 1 {
+            worksheet.autofilter(0, 0, row - 1, change_col)?;
+        }
+
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(RUNS_SHEET_NAME)?;
+        let headers = [("実行ID", 10.0), ("記録日時", 22.0), ("HARファイル", 60.0), ("リクエスト数", 14.0)];
+        for (col, (header, width)) in headers.iter().enumerate() {
+            worksheet.write_string_with_format(0, col as u16, *header, &styles.header)?;
+            worksheet.set_column_width(col as u16, *width)?;
+        }
+        worksheet.set_freeze_panes(1, 0)?;
+        for (index, run) in self.runs.iter().enumerate() {
+            let row = index as u32 + 1;
+            worksheet.write_number_with_format(row, 0, run.id as f64, &styles.cell)?;
+            worksheet.write_string_with_format(row, 1, &run.recorded_at, &styles.cell)?;
+            worksheet.write_string_with_format(row, 2, &run.input_file, &styles.cell)?;
+            worksheet.write_number_with_format(row, 3, run.requests as f64, &styles.cell)?;
+        }
+
+        workbook.save(output_path)
+            .map_err(AnalyzerError::export("Excelファイルの保存に失敗しました"))?;
+
+        info!(
+            "Excelファイルの出力が完了しました: {} ({}件のエンドポイント、{}回の実行)",
+            output_path,
+            self.endpoints.len(),
+            self.runs.len()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+//! 出力ファイルのオブジェクトストレージ（S3・GCS）へのアップロードを実装
+//! 
+//! CIの各パイプラインでアップロードの処理を実装せずに済むように、出力したワークブックと外部ファイルを
+//! `s3://bucket/prefix/`・`gs://bucket/prefix/`の下にファイル名をキーとしてアップロードする。
+//! 認証情報・リージョン等は各ストレージの環境変数（`AWS_ACCESS_KEY_ID`・`AWS_REGION`・`GOOGLE_APPLICATION_CREDENTIALS`等）から取得する
+
+use crate::error::{AnalyzerError, Result};
+use log::info;
+use object_store::aws::AmazonS3Builder;
+use object_store::gcp::GoogleCloudStorageBuilder;
+use object_store::path::Path as ObjectPath;
+use object_store::{ObjectStore, PutPayload};
+use std::fmt;
+use std::path::PathBuf;
+use url::Url;
+
+/// アップロード先のストレージの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageProvider {
+    /// Amazon S3（`s3://`）
+    S3,
+    /// Google Cloud Storage（`gs://`）
+    Gcs,
+}
+
+impl StorageProvider {
+    /// URLのスキーム
+    /// 
+    /// # Returns
+    /// * `&'static str` - スキーム
+    pub fn scheme(&self) -> &'static str {
+        match self {
+            StorageProvider::S3 => "s3",
+            StorageProvider::Gcs => "gs",
+        }
+    }
+}
+
+/// アップロード先（バケットとキーの接頭辞）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadTarget {
+    /// ストレージの種類
+    pub provider: StorageProvider,
+    /// バケット名
+    pub bucket: String,
+    /// キーの接頭辞（前後の`/`を除く。空の場合はバケットの直下）
+    pub prefix: String,
+}
+
+impl UploadTarget {
+    /// `s3://bucket/prefix/`・`gs://bucket/prefix/`形式のアップロード先を解析
+    /// 
+    /// # Arguments
+    /// * `spec` - アップロード先のURL
+    /// 
+    /// # Returns
+    /// * `Result<UploadTarget>` - 成功時はアップロード先、失敗時はエラー
+    pub fn parse(spec: &str) -> Result<Self> {
+        let url = Url::parse(spec)
+            .map_err(|e| AnalyzerError::InvalidArgument(format!("アップロード先のURLが不正です: {}: {}", spec, e)))?;
+        let provider = match url.scheme() {
+            "s3" => StorageProvider::S3,
+            "gs" => StorageProvider::Gcs,
+            _ => {
+                return Err(AnalyzerError::InvalidArgument(format!(
+                    "対応していないアップロード先です: {}（s3://またはgs://で指定してください）",
+                    spec
+                )));
+            }
+        };
+        let bucket = url
+            .host_str()
+            .filter(|bucket| !bucket.is_empty())
+            .ok_or_else(|| AnalyzerError::InvalidArgument(format!("アップロード先のバケット名がありません: {}", spec)))?;
+        Ok(UploadTarget {
+            provider,
+            bucket: bucket.to_string(),
+            prefix: url.path().trim_matches('/').to_string(),
+        })
+    }
+
+    /// ファイル名に対応するオブジェクトのキー
+    /// 
+    /// # Arguments
+    /// * `file_name` - ファイル名
+    /// 
+    /// # Returns
+    /// * `String` - キー
+    fn key(&self, file_name: &str) -> String {
+        if self.prefix.is_empty() {
+            file_name.to_string()
+        } else {
+            format!("{}/{}", self.prefix, file_name)
+        }
+    }
+
+    /// 環境変数の認証情報でストレージのクライアントを作成
+    /// 
+    /// # Returns
+    /// * `Result<Box<dyn ObjectStore>>` - 成功時はクライアント、失敗時はエラー
+    fn store(&self) -> Result<Box<dyn ObjectStore>> {
+        let context = format!("{}://{}への接続の設定に失敗しました", self.provider.scheme(), self.bucket);
+        Ok(match self.provider {
+            StorageProvider::S3 => Box::new(
+                AmazonS3Builder::from_env()
+                    .with_bucket_name(&self.bucket)
+                    .build()
+                    .map_err(AnalyzerError::upload(context))?,
+            ),
+            StorageProvider::Gcs => Box::new(
+                GoogleCloudStorageBuilder::from_env()
+                    .with_bucket_name(&self.bucket)
+                    .build()
+                    .map_err(AnalyzerError::upload(context))?,
+            ),
+        })
+    }
+
+    /// ファイルをアップロード
+    /// 
+    /// 同じキーのオブジェクトがある場合は上書きする
+    /// 
+    /// # Arguments
+    /// * `paths` - アップロードするファイルのパス
+    /// 
+    /// # Returns
+    /// * `Result<Vec<String>>` - 成功時はアップロードしたオブジェクトのURL、失敗時はエラー
+    pub async fn upload(&self, paths: &[PathBuf]) -> Result<Vec<String>> {
+        let store = self.store()?;
+        let mut urls = Vec::with_capacity(paths.len());
+        for path in paths {
+            let Some(file_name) = path.file_name() else {
+                continue;
+            };
+            let key = self.key(&file_name.to_string_lossy());
+            let content = tokio::fs::read(path)
+                .await
+                .map_err(AnalyzerError::io(format!("アップロードするファイルの読み込みに失敗しました: {}", path.display())))?;
+            store
+                .put(&ObjectPath::from(key.as_str()), PutPayload::from(content))
+                .await
+                .map_err(AnalyzerError::upload(format!("{}のアップロードに失敗しました", path.display())))?;
+
+            let url = format!("{}://{}/{}", self.provider.scheme(), self.bucket, key);
+            info!("アップロードしました: {}", url);
+            urls.push(url);
+        }
+        Ok(urls)
+    }
+}
+
+impl fmt::Display for UploadTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.prefix.is_empty() {
+            write!(f, "{}://{}/", self.provider.scheme(), self.bucket)
+        } else {
+            write!(f, "{}://{}/{}/", self.provider.scheme(), self.bucket, self.prefix)
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! リクエストURLの構成要素（スキーム・ホスト・ポート・パス・クエリ）への分解を実装
+//! 
+//! 構成要素ごとの列として出力し、Excelのピボットテーブルでホストやパスごとに集計できるようにする
+
+use url::Url;
+
+/// リクエストURLの構成要素
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UrlParts {
+    /// スキーム（例: "https"）
+    pub scheme: String,
+    /// ホスト名またはIPアドレス
+    pub host: String,
+    /// ポート番号（省略されている場合はスキームの既定のポート）
+    pub port: Option<u16>,
+    /// パス（パーセントエンコードされたまま）
+    pub path: String,
+    /// 先頭の`?`を除いたクエリ（無い場合は空文字列）
+    pub query: String,
+}
+
+impl UrlParts {
+    /// URLを構成要素に分解
+    /// 
+    /// # Arguments
+    /// * `url` - リクエストURL
+    /// 
+    /// # Returns
+    /// * `UrlParts` - 構成要素（URLを解析できない場合は全て空）
+    pub fn parse(url: &str) -> Self {
+        let Ok(parsed) = Url::parse(url) else {
+            return UrlParts::default();
+        };
+        UrlParts {
+            scheme: parsed.scheme().to_string(),
+            host: parsed.host_str().unwrap_or_default().to_string(),
+            port: parsed.port_or_known_default(),
+            path: parsed.path().to_string(),
+            query: parsed.query().unwrap_or_default().to_string(),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! `--append`で同じ日に複数回追記した場合のシート名の確認
+
+use serde_json::json;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Excelのシート名の最大文字数
+const MAX_SHEET_NAME_LENGTH: usize = 31;
+
+/// テストごとの作業ディレクトリを作成
+/// 
+/// # Arguments
+/// * `name` - テストの名前
+/// 
+/// # Returns
+/// * `PathBuf` - 空の作業ディレクトリ
+fn work_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rs_har_analyzer_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// セキュリティの指摘事項（混在コンテンツ）を含むHARファイルを作成
+/// 
+/// # Arguments
+/// * `dir` - 作業ディレクトリ
+/// 
+/// # Returns
+/// * `PathBuf` - HARファイルのパス
+fn write_har(dir: &Path) -> PathBuf {
+    let entry = json!({
+        "startedDateTime": "2024-01-01T00:00:00.000Z",
+        "time": 10.0,
+        "request": {
+            "method": "GET", "url": "http://example.com/api/items", "httpVersion": "HTTP/1.1",
+            "headers": [{ "name": "Referer", "value": "https://example.com/" }], "queryString": [], "cookies": [], "headersSize": -1, "bodySize": 0
+        },
+        "response": {
+            "status": 200, "statusText": "OK", "httpVersion": "HTTP/1.1",
+            "headers": [], "cookies": [],
+            "content": { "size": 7, "mimeType": "application/json", "text": "{\"a\":1}" },
+            "redirectURL": "", "headersSize": -1, "bodySize": 7
+        },
+        "cache": {},
+        "timings": { "send": 1.0, "wait": 5.0, "receive": 4.0 }
+    });
+    let har = json!({ "log": { "version": "1.2", "creator": { "name": "test", "version": "1" }, "entries": [entry] } });
+    let path = dir.join("input.har");
+    fs::write(&path, serde_json::to_vec(&har).unwrap()).unwrap();
+    path
+}
+
+/// xlsxファイルのシート名の一覧
+/// 
+/// # Arguments
+/// * `path` - xlsxファイルのパス
+/// 
+/// # Returns
+/// * `Vec<String>` - ブック内の順のシート名
+fn sheet_names(path: &Path) -> Vec<String> {
+    let mut archive = zip::ZipArchive::new(fs::File::open(path).unwrap()).unwrap();
+    let mut xml = String::new();
+    archive.by_name("xl/workbook.xml").unwrap().read_to_string(&mut xml).unwrap();
+    xml.split("<sheet name=\"")
+        .skip(1)
+        .filter_map(|part| part.split('"').next())
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn same_day_appends_keep_sheet_names_within_limit() {
+    let dir = work_dir("same_day_append");
+    let har = write_har(&dir);
+    let output = dir.join("out.xlsx");
+    for _ in 0..3 {
+        let result = Command::new(env!("CARGO_BIN_EXE_rs_har_analyzer"))
+            .args(["-i", har.to_str().unwrap(), "-o", output.to_str().unwrap(), "--append"])
+            .output()
+            .unwrap();
+        assert!(result.status.success(), "{}", String::from_utf8_lossy(&result.stderr));
+    }
+
+    let names = sheet_names(&output);
+    let date = names[0].clone();
+    assert!(names.contains(&format!("{}_2", date)), "{:?}", names);
+    assert!(names.contains(&format!("{}_3", date)), "{:?}", names);
+    assert!(names.contains(&format!("Security Findings {}_3", date)), "{:?}", names);
+    for name in &names {
+        assert!(name.chars().count() <= MAX_SHEET_NAME_LENGTH, "{} は31文字を超えています", name);
+    }
+}
@@ -0,0 +1,94 @@
+//! `--body-contains`・`--body-regex`がHTMLの要約・JavaScript・CSSの整形の前のボディで判定されることの確認
+
+use calamine::{Reader, Xlsx, open_workbook};
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// テストごとの作業ディレクトリを作成
+/// 
+/// # Arguments
+/// * `name` - テストの名前
+/// 
+/// # Returns
+/// * `PathBuf` - 空の作業ディレクトリ
+fn work_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rs_har_analyzer_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// テスト用のHARエントリを作成
+/// 
+/// # Arguments
+/// * `path` - リクエストURLのパス
+/// * `mime_type` - レスポンスのMIMEタイプ
+/// * `body` - レスポンスボディ
+/// 
+/// # Returns
+/// * `Value` - HARエントリ
+fn entry(path: &str, mime_type: &str, body: &str) -> serde_json::Value {
+    json!({
+        "startedDateTime": "2024-01-01T00:00:00.000Z",
+        "time": 10.0,
+        "request": {
+            "method": "GET", "url": format!("https://example.com{}", path), "httpVersion": "HTTP/1.1",
+            "headers": [], "queryString": [], "cookies": [], "headersSize": -1, "bodySize": 0
+        },
+        "response": {
+            "status": 200, "statusText": "OK", "httpVersion": "HTTP/1.1", "headers": [], "cookies": [],
+            "content": { "size": body.len(), "mimeType": mime_type, "text": body },
+            "redirectURL": "", "headersSize": -1, "bodySize": body.len()
+        },
+        "cache": {},
+        "timings": { "send": 1.0, "wait": 5.0, "receive": 4.0 }
+    })
+}
+
+/// 本文・スクリプトにのみ注文番号を含むHARファイルを作成
+/// 
+/// # Arguments
+/// * `dir` - 作業ディレクトリ
+/// 
+/// # Returns
+/// * `PathBuf` - HARファイルのパス
+fn write_har(dir: &Path) -> PathBuf {
+    let entries = vec![
+        entry("/order", "text/html", "<html><head><title>Order</title></head><body><p>order-12345</p></body></html>"),
+        entry("/other", "text/html", "<html><head><title>Other</title></head><body><p>nothing</p></body></html>"),
+        entry("/app.js", "application/javascript", "function f(){return \"order-12345\"}"),
+    ];
+    let har = json!({ "log": { "version": "1.2", "creator": { "name": "test", "version": "1" }, "entries": entries } });
+    let path = dir.join("input.har");
+    fs::write(&path, serde_json::to_vec(&har).unwrap()).unwrap();
+    path
+}
+
+#[test]
+fn body_filter_matches_text_removed_by_html_summary() {
+    let dir = work_dir("body_filter_summary");
+    let har = write_har(&dir);
+    let output = dir.join("out.xlsx");
+    let result = Command::new(env!("CARGO_BIN_EXE_rs_har_analyzer"))
+        .args(["-i", har.to_str().unwrap(), "-o", output.to_str().unwrap()])
+        .args(["--columns", "url,response-payload", "--html-mode", "title", "--js-css-mode", "pretty"])
+        .args(["--body-contains", "order-12345"])
+        .output()
+        .unwrap();
+    assert!(result.status.success(), "{}", String::from_utf8_lossy(&result.stderr));
+
+    let mut workbook: Xlsx<_> = open_workbook(&output).unwrap();
+    let range = workbook.worksheet_range("Data").unwrap();
+    let rows: Vec<(String, String)> = range
+        .rows()
+        .skip(1)
+        .map(|row| (row[0].to_string(), row[1].to_string()))
+        .collect();
+    let urls: Vec<&str> = rows.iter().map(|(url, _)| url.as_str()).collect();
+    assert_eq!(urls, ["https://example.com/order", "https://example.com/app.js"]);
+    // 出力するセルは要約したまま
+    assert!(!rows[0].1.contains("order-12345"), "{:?}", rows[0]);
+    assert!(rows[0].1.contains("Order"), "{:?}", rows[0]);
+}
@@ -0,0 +1,86 @@
+//! grepサブコマンドの終了コードが一致の有無とエラーで区別できることの確認
+
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// テストごとの作業ディレクトリを作成
+/// 
+/// # Arguments
+/// * `name` - テストの名前
+/// 
+/// # Returns
+/// * `PathBuf` - 空の作業ディレクトリ
+fn work_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rs_har_analyzer_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// レスポンスボディにfound-meを含むエントリが1件のHARファイルを作成
+/// 
+/// # Arguments
+/// * `dir` - 作業ディレクトリ
+/// 
+/// # Returns
+/// * `PathBuf` - HARファイルのパス
+fn write_har(dir: &Path) -> PathBuf {
+    let har = json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "test", "version": "1" },
+            "entries": [{
+                "startedDateTime": "2024-01-01T00:00:00.000Z",
+                "time": 10.0,
+                "request": {
+                    "method": "GET", "url": "https://example.com/api", "httpVersion": "HTTP/1.1",
+                    "headers": [], "queryString": [], "cookies": [], "headersSize": -1, "bodySize": 0
+                },
+                "response": {
+                    "status": 200, "statusText": "OK", "httpVersion": "HTTP/1.1", "headers": [], "cookies": [],
+                    "content": { "size": 20, "mimeType": "application/json", "text": "{\"value\":\"found-me\"}" },
+                    "redirectURL": "", "headersSize": -1, "bodySize": 20
+                },
+                "cache": {},
+                "timings": { "send": 1.0, "wait": 5.0, "receive": 4.0 }
+            }]
+        }
+    });
+    let path = dir.join("input.har");
+    fs::write(&path, serde_json::to_vec(&har).unwrap()).unwrap();
+    path
+}
+
+/// grepサブコマンドを実行して終了コードを取得
+/// 
+/// # Arguments
+/// * `pattern` - 検索する正規表現
+/// * `input` - 入力するHARファイルのパス
+/// 
+/// # Returns
+/// * `Option<i32>` - 終了コード
+fn grep(pattern: &str, input: &Path) -> Option<i32> {
+    Command::new(env!("CARGO_BIN_EXE_rs_har_analyzer"))
+        .args(["grep", pattern, "--no-color", "-i"])
+        .arg(input)
+        .output()
+        .unwrap()
+        .status
+        .code()
+}
+
+#[test]
+fn exit_code_distinguishes_no_match_from_errors() {
+    let dir = work_dir("grep_exit_code");
+    let har = write_har(&dir);
+    let broken = dir.join("broken.har");
+    fs::write(&broken, "{").unwrap();
+
+    assert_eq!(grep("found-me", &har), Some(0));
+    assert_eq!(grep("not-there", &har), Some(1));
+    assert_eq!(grep("(", &har), Some(2));
+    assert_eq!(grep("found-me", &dir.join("missing.har")), Some(2));
+    assert_eq!(grep("found-me", &broken), Some(4));
+}
@@ -0,0 +1,163 @@
+//! `--privacy strict`の出力に元のホスト名・URLが残らないことの確認
+
+use serde_json::json;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// テスト用のHARに含めるホスト名
+const HOSTS: &[&str] = &["example.com", "tracker.doubleclick.net", "cdn.example.org"];
+
+/// テストごとの作業ディレクトリを作成
+/// 
+/// # Arguments
+/// * `name` - テストの名前
+/// 
+/// # Returns
+/// * `PathBuf` - 空の作業ディレクトリ
+fn work_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rs_har_analyzer_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// テスト用のHARエントリを作成
+/// 
+/// # Arguments
+/// * `url` - リクエストURL
+/// * `method` - HTTPメソッド
+/// * `mime_type` - レスポンスのMIMEタイプ
+/// * `body` - レスポンスボディ
+/// 
+/// # Returns
+/// * `Value` - HARエントリ
+fn entry(url: &str, method: &str, mime_type: &str, body: &str) -> serde_json::Value {
+    json!({
+        "startedDateTime": "2024-01-01T00:00:00.000Z",
+        "time": 10.0,
+        "request": {
+            "method": method, "url": url, "httpVersion": "HTTP/1.1",
+            "headers": [{ "name": "Referer", "value": "https://example.com/" }, { "name": "Cookie", "value": "sid=1" }],
+            "queryString": [], "cookies": [], "headersSize": -1, "bodySize": 0,
+            "postData": { "mimeType": "application/x-www-form-urlencoded", "text": "password=y" }
+        },
+        "response": {
+            "status": 200, "statusText": "OK", "httpVersion": "HTTP/1.1",
+            "headers": [{ "name": "Server", "value": "nginx" }, { "name": "Location", "value": "/x" }], "cookies": [],
+            "content": { "size": body.len(), "mimeType": mime_type, "text": body },
+            "redirectURL": "", "headersSize": -1, "bodySize": body.len()
+        },
+        "cache": {},
+        "timings": { "send": 1.0, "wait": 5.0, "receive": 4.0 }
+    })
+}
+
+/// ホスト名・URLを含むHARファイルを作成
+/// 
+/// # Arguments
+/// * `dir` - 作業ディレクトリ
+/// 
+/// # Returns
+/// * `PathBuf` - HARファイルのパス
+fn write_har(dir: &Path) -> PathBuf {
+    let entries = vec![
+        entry("https://example.com/api/items?page=1&q=secret", "GET", "application/json", "{\"a\":1}"),
+        entry("https://example.com/login", "POST", "text/html", "<html><body>ok</body></html>"),
+        entry("http://tracker.doubleclick.net/px?x=1", "GET", "image/gif", ""),
+        entry("https://cdn.example.org/a.js", "GET", "application/javascript", "var a=1;\n//# sourceMappingURL=a.js.map"),
+    ];
+    let har = json!({ "log": { "version": "1.2", "creator": { "name": "test", "version": "1" }, "entries": entries } });
+    let path = dir.join("input.har");
+    fs::write(&path, serde_json::to_vec(&har).unwrap()).unwrap();
+    path
+}
+
+/// ツールを実行
+/// 
+/// # Arguments
+/// * `args` - コマンドライン引数
+/// 
+/// # Returns
+/// * `Output` - 終了コードと出力
+fn run(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_rs_har_analyzer")).args(args).output().unwrap()
+}
+
+/// xlsxファイルの全てのパート（シート・共有文字列・グラフ）を連結した文字列
+/// 
+/// # Arguments
+/// * `path` - xlsxファイルのパス
+/// 
+/// # Returns
+/// * `String` - 全てのパートの内容
+fn xlsx_text(path: &Path) -> String {
+    let mut archive = zip::ZipArchive::new(fs::File::open(path).unwrap()).unwrap();
+    let mut text = String::new();
+    for index in 0..archive.len() {
+        let mut part = archive.by_index(index).unwrap();
+        part.read_to_string(&mut text).unwrap();
+    }
+    text
+}
+
+#[test]
+fn strict_workbook_contains_no_raw_hosts() {
+    let dir = work_dir("strict_workbook");
+    let har = write_har(&dir);
+    let output = dir.join("out.xlsx");
+    let graph = dir.join("graph.dot");
+    let result = run(&[
+        "-i", har.to_str().unwrap(), "-o", output.to_str().unwrap(), "-g", graph.to_str().unwrap(), "--privacy", "strict",
+    ]);
+    assert!(result.status.success(), "{}", String::from_utf8_lossy(&result.stderr));
+
+    let workbook = xlsx_text(&output);
+    let graph = fs::read_to_string(&graph).unwrap();
+    for host in HOSTS {
+        assert!(!workbook.contains(host), "ブックに{}が残っています", host);
+        assert!(!graph.contains(host), "グラフに{}が残っています", host);
+    }
+    assert!(!workbook.contains("password=y"));
+}
+
+#[test]
+fn strict_workbook_has_no_header_notes() {
+    let dir = work_dir("strict_notes");
+    let har = write_har(&dir);
+    let output = dir.join("out.xlsx");
+    let result = run(&[
+        "-i", har.to_str().unwrap(), "-o", output.to_str().unwrap(), "--privacy", "strict", "--cell-notes",
+    ]);
+    assert!(result.status.success(), "{}", String::from_utf8_lossy(&result.stderr));
+
+    let workbook = xlsx_text(&output);
+    for header in ["Referer", "Cookie", "sid=1", "Server: nginx", "Location: /x"] {
+        assert!(!workbook.contains(header), "ブックに{}が残っています", header);
+    }
+}
+
+#[test]
+fn strict_rejects_sheets() {
+    let dir = work_dir("strict_sheets");
+    let har = write_har(&dir);
+    let output = dir.join("out.xlsx");
+    let result = run(&[
+        "-i", har.to_str().unwrap(), "-o", output.to_str().unwrap(), "--privacy", "strict", "--sheets", "Security Findings,Domains",
+    ]);
+    assert_eq!(result.status.code(), Some(2));
+    assert!(!output.exists());
+}
+
+#[test]
+fn strict_rejects_stub_formats() {
+    let dir = work_dir("strict_stubs");
+    let har = write_har(&dir);
+    let output = dir.join("routes.js");
+    let result = run(&[
+        "-i", har.to_str().unwrap(), "-o", output.to_str().unwrap(), "--format", "playwright-routes", "--privacy", "strict",
+    ]);
+    assert!(!result.status.success());
+    assert!(!output.exists());
+}
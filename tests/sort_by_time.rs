@@ -0,0 +1,126 @@
+//! `--sort-by-time`で並べ替えてもエントリ番号がHAR内の順序のまま保たれることの確認
+
+use calamine::{Data, Reader, Xlsx, open_workbook};
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// テストごとの作業ディレクトリを作成
+/// 
+/// # Arguments
+/// * `name` - テストの名前
+/// 
+/// # Returns
+/// * `PathBuf` - 空の作業ディレクトリ
+fn work_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rs_har_analyzer_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// テスト用のHARエントリを作成
+/// 
+/// # Arguments
+/// * `path` - リクエストURLのパス
+/// * `started` - 開始時刻
+/// 
+/// # Returns
+/// * `Value` - HARエントリ
+fn entry(path: &str, started: &str) -> serde_json::Value {
+    json!({
+        "startedDateTime": started,
+        "time": 10.0,
+        "request": {
+            "method": "GET", "url": format!("https://example.com{}", path), "httpVersion": "HTTP/1.1",
+            "headers": [], "queryString": [], "cookies": [], "headersSize": -1, "bodySize": 0
+        },
+        "response": {
+            "status": 200, "statusText": "OK", "httpVersion": "HTTP/1.1", "headers": [], "cookies": [],
+            "content": { "size": 2, "mimeType": "application/json", "text": "{}" },
+            "redirectURL": "", "headersSize": -1, "bodySize": 2
+        },
+        "cache": {},
+        "timings": { "send": 1.0, "wait": 5.0, "receive": 4.0 }
+    })
+}
+
+/// 記録順と開始時刻の順が異なるHARファイルを作成
+/// 
+/// # Arguments
+/// * `dir` - 作業ディレクトリ
+/// 
+/// # Returns
+/// * `PathBuf` - HARファイルのパス
+fn write_har(dir: &Path) -> PathBuf {
+    let entries = vec![
+        entry("/a", "2024-01-01T00:00:00.000Z"),
+        entry("/b", "2024-01-01T00:00:03.000Z"),
+        entry("/c", "2024-01-01T00:00:01.000Z"),
+        entry("/d", "2024-01-01T00:00:02.000Z"),
+    ];
+    let har = json!({ "log": { "version": "1.2", "creator": { "name": "test", "version": "1" }, "entries": entries } });
+    let path = dir.join("input.har");
+    fs::write(&path, serde_json::to_vec(&har).unwrap()).unwrap();
+    path
+}
+
+/// シートの見出し行を除いた各行を文字列にして取得
+/// 
+/// # Arguments
+/// * `workbook` - ブック
+/// * `sheet` - シート名
+/// 
+/// # Returns
+/// * `Vec<Vec<String>>` - 行ごとのセルの値
+fn rows(workbook: &mut Xlsx<std::io::BufReader<fs::File>>, sheet: &str) -> Vec<Vec<String>> {
+    let range = workbook.worksheet_range(sheet).unwrap();
+    range
+        .rows()
+        .skip(1)
+        .map(|row| {
+            row.iter()
+                .map(|cell| match cell {
+                    Data::Float(number) => number.to_string(),
+                    other => other.to_string(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn sorted_rows_keep_har_entry_numbers() {
+    let dir = work_dir("sort_by_time");
+    let har = write_har(&dir);
+    let notes = dir.join("notes.csv");
+    fs::write(&notes, "3,third\n").unwrap();
+    let output = dir.join("out.xlsx");
+    let result = Command::new(env!("CARGO_BIN_EXE_rs_har_analyzer"))
+        .args(["-i", har.to_str().unwrap(), "-o", output.to_str().unwrap(), "--sort-by-time"])
+        .args(["--columns", "entry-number,url,comments", "--flag-index", "4", "--annotate", notes.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(result.status.success(), "{}", String::from_utf8_lossy(&result.stderr));
+
+    let mut workbook: Xlsx<_> = open_workbook(&output).unwrap();
+    let data = rows(&mut workbook, "Data");
+    let order: Vec<(&str, &str)> = data.iter().map(|row| (row[0].as_str(), row[1].as_str())).collect();
+    assert_eq!(
+        order,
+        [
+            ("1", "https://example.com/a"),
+            ("3", "https://example.com/c"),
+            ("4", "https://example.com/d"),
+            ("2", "https://example.com/b"),
+        ]
+    );
+    assert!(data[1][2].contains("third"), "{:?}", data[1]);
+    assert!(data.iter().filter(|row| row[2].contains("third")).count() == 1);
+
+    let flagged = rows(&mut workbook, "Flagged");
+    assert_eq!(flagged.len(), 1);
+    assert!(flagged[0].contains(&"4".to_string()), "{:?}", flagged[0]);
+    assert!(flagged[0].iter().any(|cell| cell.ends_with("/d")), "{:?}", flagged[0]);
+}